@@ -1,7 +1,8 @@
-use esr::ast::{Expression, Literal, OperatorKind, OperatorCategory, Property, PropertyKey, Pattern};
+use esr::ast::{Expression, ExpressionNode, Literal, OperatorKind, OperatorCategory, Property, PropertyKey, Pattern};
 use esr::ast::expression::*;
 
 use crate::{ToCode, Generator};
+use crate::function;
 
 
 impl<'ast, G: Generator> ToCode<G> for Expression<'ast> {
@@ -12,14 +13,16 @@ impl<'ast, G: Generator> ToCode<G> for Expression<'ast> {
         match *self {
             Void                         => {},
             This(_)                      => gen.write_bytes(b"this"),
+            Super(_)                     => gen.write_bytes(b"super"),
             Identifier(ref ident)        => gen.write(ident),
             Literal(ref value)           => gen.write(value),
             Sequence(ref sequence)       => gen.write(sequence),
+            Parenthesized(ref expression) => gen.write(expression),
             Array(ref array)             => gen.write(array),
-            Member(ref member)           => gen.write(member),
-            ComputedMember(ref computed) => gen.write(computed),
+            Member(_)                    |
+            ComputedMember(_)            => write_chain(gen, self),
             MetaProperty(ref property)   => gen.write(property),
-            Call(ref call)               => gen.write(call),
+            Call(_)                      => write_chain(gen, self),
             Binary(ref binary)           => gen.write(binary),
             Prefix(ref prefix)           => gen.write(prefix),
             Postfix(ref postfix)         => gen.write(postfix),
@@ -27,10 +30,19 @@ impl<'ast, G: Generator> ToCode<G> for Expression<'ast> {
             Template(ref template)       => gen.write(template),
             TaggedTemplate(ref tagged)   => gen.write(tagged),
             Spread(ref spread)           => gen.write(spread),
+            Await(ref await_expression)  => gen.write(await_expression),
             Arrow(ref arrow)             => gen.write(arrow),
             Object(ref object)           => gen.write(object),
             Function(ref function)       => gen.write(function),
             Class(ref class)             => gen.write(class),
+            JSXElement(ref element)      => gen.write(element),
+            JSXFragment(ref fragment)    => gen.write(fragment),
+            As(ref expression)           => gen.write(expression),
+            Satisfies(ref expression)    => gen.write(expression),
+            TypeCast(ref expression)     => gen.write(expression),
+            NonNull(ref expression)      => gen.write(expression),
+            TypeAssertion(ref assertion) => gen.write(assertion),
+            Instantiation(ref expression) => gen.write(expression),
         }
     }
 }
@@ -117,6 +129,15 @@ impl<'ast, G: Generator> ToCode<G> for SequenceExpression<'ast> {
     }
 }
 
+impl<'ast, G: Generator> ToCode<G> for ParenthesizedExpression<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write_byte(b'(');
+        gen.write(&self.expression);
+        gen.write_byte(b')');
+    }
+}
+
 impl<'ast, G: Generator> ToCode<G> for ArrayExpression<'ast> {
     #[inline]
     fn to_code(&self, gen: &mut G) {
@@ -126,10 +147,74 @@ impl<'ast, G: Generator> ToCode<G> for ArrayExpression<'ast> {
     }
 }
 
+/// Write the object of a member expression, chains of members and calls
+/// bind tight enough to go without parentheses, `foo.bar().baz`.
+fn write_member_object<'ast, G: Generator>(gen: &mut G, object: &ExpressionNode<'ast>) {
+    let mut expression = object;
+
+    while let Some(inner) = expression.type_erased() {
+        expression = inner;
+    }
+
+    match expression.item {
+        Expression::Member(_)         |
+        Expression::ComputedMember(_) |
+        Expression::MetaProperty(_)   |
+        Expression::Call(_)           => gen.write(object),
+        _                             => gen.write_expression(object, 19),
+    }
+}
+
+/// Write a chain of member accesses and calls, `a.b[c](d)`, in a loop,
+/// since such chains can be too long to recurse into.
+fn write_chain<'ast, G: Generator>(gen: &mut G, expression: &Expression<'ast>) {
+    use esr::ast::Expression::*;
+
+    let mut links = Vec::new();
+    let mut expression = expression;
+
+    while let Some(object) = expression.chained() {
+        match object.item {
+            Member(_) | ComputedMember(_) | Call(_) => {
+                links.push(expression);
+                expression = &object.item;
+            },
+            _ => break,
+        }
+    }
+
+    match *expression {
+        Member(ref member)           => gen.write(member),
+        ComputedMember(ref computed) => gen.write(computed),
+        Call(ref call)               => gen.write(call),
+        _                            => unreachable!(),
+    }
+
+    for link in links.iter().rev() {
+        match **link {
+            Member(ref member) => {
+                gen.write_byte(b'.');
+                gen.write(&member.property);
+            },
+            ComputedMember(ref computed) => {
+                gen.write_byte(b'[');
+                gen.write(&computed.property);
+                gen.write_byte(b']');
+            },
+            Call(ref call) => {
+                gen.write_byte(b'(');
+                gen.write_list(call.arguments);
+                gen.write_byte(b')');
+            },
+            _ => unreachable!(),
+        }
+    }
+}
+
 impl<'ast, G: Generator> ToCode<G> for MemberExpression<'ast> {
     #[inline]
     fn to_code(&self, gen: &mut G) {
-        gen.write_expression(&self.object, 19);
+        write_member_object(gen, &self.object);
         gen.write_byte(b'.');
         gen.write(&self.property);
     }
@@ -138,7 +223,7 @@ impl<'ast, G: Generator> ToCode<G> for MemberExpression<'ast> {
 impl<'ast, G: Generator> ToCode<G> for ComputedMemberExpression<'ast> {
     #[inline]
     fn to_code(&self, gen: &mut G) {
-        gen.write_expression(&self.object, 19);
+        write_member_object(gen, &self.object);
         gen.write_byte(b'[');
         gen.write(&self.property);
         gen.write_byte(b']');
@@ -157,6 +242,12 @@ impl<'ast, G: Generator> ToCode<G> for MetaPropertyExpression<'ast> {
 impl<'ast, G: Generator> ToCode<G> for CallExpression<'ast> {
     #[inline]
     fn to_code(&self, gen: &mut G) {
+        if let Expression::Super(_) = self.callee.item {
+            if function::write_super_call(gen, self, false) {
+                return;
+            }
+        }
+
         gen.write(&self.callee);
         gen.write_byte(b'(');
         gen.write_list(&self.arguments);
@@ -164,55 +255,94 @@ impl<'ast, G: Generator> ToCode<G> for CallExpression<'ast> {
     }
 }
 
+/// Binding powers the left and right operands of a binary expression
+/// need to go without parentheses.
+fn operand_binding_powers(binary: &BinaryExpression<'_>) -> (u8, u8) {
+    let bp = binary.operator.binding_power();
+
+    // `**` is right associative, and can't take a unary operand on its left
+    match binary.operator {
+        OperatorKind::Exponent => (bp + 2, bp),
+        _                      => (bp, bp + 1),
+    }
+}
+
 impl<'ast, G: Generator> ToCode<G> for BinaryExpression<'ast> {
     #[inline]
     fn to_code(&self, gen: &mut G) {
-        let bp = self.operator.binding_power();
-        let category = self.operator.category();
-
-        let (spacing_left, spacing_right) = match category {
-            OperatorCategory::Word => (true, true),
-            OperatorCategory::Safe => (false, false),
-            category => {
-                let spacing_left = match self.left.item {
-                    Expression::Postfix(PostfixExpression { operator, .. }) => {
-                        category == operator.category()
-                    },
-                    _ => false
-                };
-
-                let spacing_right = match self.right.item {
-                    Expression::Prefix(PrefixExpression { operator, .. }) => {
-                        category == operator.category()
-                    },
-                    _ => false
-                };
-
-                (spacing_left, spacing_right)
-            }
-        };
+        // Left associative chains, `a + b + c`, can be too long to recurse
+        // into, so the left operands are collected in a loop first.
+        let mut links = vec![self];
+        let mut left = &self.left;
 
-        gen.write_expression(&self.left, bp);
+        while let Expression::Binary(ref binary) = left.item {
+            let (left_bp, _) = operand_binding_powers(links[links.len() - 1]);
 
-        if spacing_left {
-            gen.write_byte(b' ');
-        } else {
-            gen.write_pretty(b' ');
+            if left.binding_power() < left_bp {
+                break;
+            }
+
+            links.push(binary);
+            left = &binary.left;
         }
 
-        gen.write(&self.operator);
+        let (left_bp, _) = operand_binding_powers(links[links.len() - 1]);
 
-        if spacing_right {
-            gen.write_byte(b' ');
-        } else {
-            gen.write_pretty(b' ');
+        gen.write_expression(left, left_bp);
+
+        for link in links.iter().rev() {
+            write_operator_and_right(gen, link);
+        }
+    }
+}
+
+/// Write the operator and right operand of a binary expression, the
+/// left operand having been written already.
+fn write_operator_and_right<G: Generator>(gen: &mut G, binary: &BinaryExpression<'_>) {
+    let category = binary.operator.category();
+
+    let (spacing_left, spacing_right) = match category {
+        OperatorCategory::Word => (true, true),
+        OperatorCategory::Safe => (false, false),
+        category => {
+            let spacing_left = match binary.left.item {
+                Expression::Postfix(PostfixExpression { operator, .. }) => {
+                    category == operator.category()
+                },
+                _ => false
+            };
+
+            let spacing_right = match binary.right.item {
+                Expression::Prefix(PrefixExpression { operator, .. }) => {
+                    category == operator.category()
+                },
+                _ => false
+            };
+
+            (spacing_left, spacing_right)
         }
+    };
+
+    let (_, right_bp) = operand_binding_powers(binary);
+
+    if spacing_left {
+        gen.write_byte(b' ');
+    } else {
+        gen.write_pretty(b' ');
+    }
 
-        // `2 / 2 * 2` and `2 / (2 * 2)` are different expressions,
-        // hence the need for parenthesis in a right-balanced tree
-        // even if binding power of operators is exactly the same.
-        gen.write_expression(&self.right, bp + 1);
+    gen.write(&binary.operator);
+
+    if spacing_right {
+        gen.write_byte(b' ');
+    } else {
+        gen.write_pretty(b' ');
     }
+
+    // `2 / 2 * 2` and `2 / (2 * 2)` are different expressions,
+    // hence the need for parenthesis in a right-balanced tree
+    // even if binding power of operators is exactly the same.
+    gen.write_expression(&binary.right, right_bp);
 }
 
 impl<'ast, G: Generator> ToCode<G> for PrefixExpression<'ast> {
@@ -294,11 +424,26 @@ impl<'ast, G: Generator> ToCode<G> for SpreadExpression<'ast> {
     }
 }
 
+impl<'ast, G: Generator> ToCode<G> for AwaitExpression<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write_bytes(b"await ");
+        gen.write_expression(&self.argument, 15);
+    }
+}
+
 impl<'ast, G: Generator> ToCode<G> for ArrowBody<'ast> {
     #[inline]
     fn to_code(&self, gen: &mut G) {
         match *self {
-            ArrowBody::Expression(ref expression) => gen.write(expression),
+            // Sequences and bodies starting with `{` need parentheses,
+            // the latter would be read as a block otherwise.
+            ArrowBody::Expression(ref expression) if !expression.is_allowed_as_bare_statement() => {
+                gen.write_byte(b'(');
+                gen.write(expression);
+                gen.write_byte(b')');
+            },
+            ArrowBody::Expression(ref expression) => gen.write_expression(expression, 1),
             ArrowBody::Block(ref block)           => gen.write(block),
         }
     }
@@ -378,6 +523,34 @@ mod test {
         assert_min("foo`${ 10 }`", "foo`${10}`;");
     }
 
+    #[test]
+    fn parenthesized_expression() {
+        use esr::{parse_with_options, ParseOptions};
+        use crate::codegen;
+
+        let options = ParseOptions { parenthesized: true, ..ParseOptions::default() };
+        let generate = |source| codegen(&parse_with_options(source, options).unwrap(), true);
+
+        assert_eq!(generate("(a);"), "(a);");
+        assert_eq!(generate("((a + b)) * c;"), "((a+b))*c;");
+        assert_eq!(generate("(a * b) + c;"), "(a*b)+c;");
+        assert_eq!(generate("(function () {})();"), "(function(){})();");
+        assert_eq!(generate("(a, b) => a;"), "(a,b)=>a;");
+    }
+
+    #[test]
+    fn await_expression() {
+        use esr::{parse_with_options, ParseOptions};
+        use crate::codegen;
+
+        let options = ParseOptions { top_level_await: true, ..ParseOptions::default() };
+        let generate = |source| codegen(&parse_with_options(source, options).unwrap(), true);
+
+        assert_eq!(generate("await foo();"), "await foo();");
+        assert_eq!(generate("await (a + b);"), "await (a+b);");
+        assert_eq!(generate("(await a) + b;"), "await a+b;");
+    }
+
     #[test]
     fn sequence_expression() {
         assert_min("foo, bar, baz;", "foo,bar,baz;");
@@ -417,6 +590,11 @@ mod test {
 
     #[test]
     fn binary_expression_precedence() {
+        assert_min("a ** b ** c", "a**b**c;");
+        assert_min("(a ** b) ** c", "(a**b)**c;");
+        assert_min("(-a) ** b", "(-a)**b;");
+        assert_min("a ** -b", "a**-b;");
+        assert_min("a++ ** b", "a++**b;");
         assert_min("2 + 2 * 2", "2+2*2;");
         assert_min("2 + (2 * 2)", "2+2*2;");
         assert_min("(2 + 2) * 2", "(2+2)*2;");
@@ -450,6 +628,10 @@ mod test {
     fn function_expression() {
         assert_min("(function () {})", "(function(){});");
         assert_min("(function foo() {})", "(function foo(){});");
+        assert_min("(function () {})()", "(function(){}());");
+        assert_min("(function () {}).call(this)", "(function(){}.call(this));");
+        assert_min("(function () {}) ? a : b, c", "(function(){}?a:b,c);");
+        assert_min("a = function () {}", "a=function(){};");
     }
 
     #[test]
@@ -471,11 +653,24 @@ mod test {
     fn member_expression() {
         assert_min("foo.bar", "foo.bar;");
         assert_min("this.bar", "this.bar;");
+        assert_min("foo.bar.baz", "foo.bar.baz;");
+        assert_min("foo[0].bar[1]", "foo[0].bar[1];");
+        assert_min("foo().bar", "foo().bar;");
+        assert_min("(a => a).b", "(a=>a).b;");
         assert_min("10..fooz", "10..fooz;");
         assert_min("foo[10]", "foo[10];");
         assert_min(r#"foo["bar"]"#, r#"foo["bar"];"#);
     }
 
+    #[test]
+    fn arrow_expression() {
+        assert_min("a => b", "a=>b;");
+        assert_min("(a, b) => (c, d)", "(a,b)=>(c,d);");
+        assert_min("() => ({})", "()=>({});");
+        assert_min("() => ({}).a = b", "()=>({}.a=b);");
+        assert_min("() => () => (a, b)", "()=>()=>(a,b);");
+    }
+
     #[test]
     fn array_expression() {
         assert_min("[]", "[];");
@@ -520,6 +715,8 @@ mod test {
         assert_min("({ foo: 10, bar: 20 });", "({foo:10,bar:20});");
         assert_min("({ foo: 10, bar() {} });", "({foo:10,bar(){}});");
         assert_min("({ foo(bar, baz) {} });", "({foo(bar,baz){}});");
+        assert_min("({ foo } = bar);", "({foo}=bar);");
+        assert_min("({}).toString();", "({}.toString());");
         let expected = "({\n    foo: true,\n    bar: false\n});";
         assert_pretty("({ foo: true, bar: false })", expected);
     }
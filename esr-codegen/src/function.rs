@@ -1,4 +1,8 @@
+use std::cell::RefCell;
+
 use esr::ast::{Function, Class, ClassMember, Name, EmptyName, MandatoryName, OptionalName, MethodKind};
+use esr::ast::{Pattern, Expression, ExpressionNode, Decorator, Statement, StatementList, Literal, AnyNode};
+use esr::ast::expression::{MemberExpression, CallExpression};
 
 use crate::{ToCode, Generator};
 
@@ -52,6 +56,14 @@ impl<G: Generator> ClassFunctionDeclaration<G> for EmptyName {
 impl<'ast, G: Generator> ClassFunctionDeclaration<G> for OptionalName<'ast> {}
 impl<'ast, G: Generator> ClassFunctionDeclaration<G> for MandatoryName<'ast> {}
 
+thread_local! {
+    /// Names of the TypeScript parameter properties of the constructor of a
+    /// derived class being written, which are assigned to `this` right after
+    /// every `super(...)` call nested in its body. `ToCode` has no way to
+    /// pass them down to the calls.
+    static PARAMETER_PROPERTIES: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
 impl<'ast, G, N> ToCode<G> for Function<'ast, N> where
     G: Generator,
     N: Name<'ast> + ToCode<G> + ClassFunctionDeclaration<G>,
@@ -64,8 +76,192 @@ impl<'ast, G, N> ToCode<G> for Function<'ast, N> where
         gen.write_list(&self.params);
         gen.write_byte(b')');
         gen.write_pretty(b' ');
-        gen.write(&self.body);
+
+        // `super` calls in nested functions are not the constructor's
+        let outer = PARAMETER_PROPERTIES.with(|properties| properties.take());
+
+        if self.params.iter().any(|param| parameter_property_name(param).is_some()) {
+            write_parameter_properties(gen, self);
+        } else {
+            gen.write(&self.body);
+        }
+
+        PARAMETER_PROPERTIES.with(|properties| properties.replace(outer));
+    }
+}
+
+/// Name of the binding declared by a TypeScript parameter property.
+fn parameter_property_name<'ast>(pattern: &Pattern<'ast>) -> Option<&'ast str> {
+    let mut pattern = match *pattern {
+        Pattern::ParameterProperty { parameter, .. } => parameter,
+        _                                            => return None,
+    };
+
+    loop {
+        match pattern.item {
+            Pattern::Identifier(name)             => return Some(name.item),
+            Pattern::Annotated { pattern: inner, .. }         |
+            Pattern::AssignmentPattern { left: inner, .. }    => pattern = inner,
+            _                                     => return None,
+        }
+    }
+}
+
+/// Whether the statement is a `super(...)` call.
+fn is_super_call(statement: &Statement) -> bool {
+    match *statement {
+        Statement::Expression(expression) => match expression.item {
+            Expression::Call(CallExpression { callee, .. }) => matches!(callee.item, Expression::Super(_)),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Whether the statement is a directive, such as `'use strict';`.
+fn is_directive(statement: &Statement) -> bool {
+    match *statement {
+        Statement::Expression(expression) => matches!(expression.item, Expression::Literal(Literal::String(_))),
+        _ => false,
+    }
+}
+
+/// Whether the body of a function calls `super(...)` anywhere, other than
+/// in the functions nested in it.
+fn calls_super(body: StatementList<'_>) -> bool {
+    let mut nodes = body.iter().map(AnyNode::from).collect::<Vec<_>>();
+
+    while let Some(node) = nodes.pop() {
+        match node {
+            AnyNode::Expression(expression) => match expression.item {
+                Expression::Call(CallExpression { callee, .. }) if matches!(callee.item, Expression::Super(_)) => return true,
+                Expression::Function(_) => continue,
+                _ => {},
+            },
+            AnyNode::Statement(statement) if matches!(statement.item, Statement::Function(_)) => continue,
+            AnyNode::Method(_) => continue,
+            _ => {},
+        }
+
+        nodes.extend(node.children());
+    }
+
+    false
+}
+
+/// Write the body of a constructor with TypeScript parameter properties,
+/// which, like TypeScript does, assign the parameters to `this` right
+/// after every `super(...)` call of a derived class, or after the
+/// directives otherwise.
+fn write_parameter_properties<'ast, G, N>(gen: &mut G, function: &Function<'ast, N>) where
+    G: Generator,
+    N: Name<'ast>,
+{
+    let body = function.body.body.iter()
+        .filter(|statement| !statement.is_type_declaration())
+        .collect::<Vec<_>>();
+
+    gen.write_byte(b'{');
+    gen.indent();
+
+    if calls_super(function.body.body) {
+        let properties = function.params.iter()
+            .filter_map(|param| parameter_property_name(param))
+            .map(String::from)
+            .collect::<Vec<_>>();
+
+        for statement in body {
+            gen.new_line();
+
+            // Calls that are statements of their own are followed by
+            // statements of their own, nested ones by a sequence.
+            if is_super_call(statement) {
+                gen.write(statement);
+                write_property_assignments(gen, function);
+            } else {
+                PARAMETER_PROPERTIES.with(|current| current.replace(properties.clone()));
+                gen.write(statement);
+                PARAMETER_PROPERTIES.with(|current| current.take());
+            }
+        }
+    } else {
+        let directives = body.iter().take_while(|statement| is_directive(statement)).count();
+        let (before, after) = body.split_at(directives);
+
+        for statement in before {
+            gen.new_line();
+            gen.write(*statement);
+        }
+
+        write_property_assignments(gen, function);
+
+        for statement in after {
+            gen.new_line();
+            gen.write(*statement);
+        }
+    }
+
+    gen.dedent();
+    gen.new_line();
+    gen.write_byte(b'}');
+}
+
+fn write_property_assignments<'ast, G, N>(gen: &mut G, function: &Function<'ast, N>) where
+    G: Generator,
+    N: Name<'ast>,
+{
+    for param in function.params.iter() {
+        if let Some(name) = parameter_property_name(param) {
+            gen.new_line();
+            gen.write_bytes(b"this.");
+            gen.write(&name);
+            gen.write_pretty(b' ');
+            gen.write_byte(b'=');
+            gen.write_pretty(b' ');
+            gen.write(&name);
+            gen.write_byte(b';');
+        }
+    }
+}
+
+/// Write a `super(...)` call nested in the body of a constructor with
+/// parameter properties, followed by their assignments to `this`. Calls
+/// within expressions still evaluate to `this`, `(super(a), this.a = a, this)`,
+/// while calls that make up a statement go without, `super(a), this.a = a`.
+/// Returns `false`, writing nothing, outside of such constructors.
+pub(crate) fn write_super_call<G: Generator>(gen: &mut G, call: &CallExpression<'_>, statement: bool) -> bool {
+    let properties = PARAMETER_PROPERTIES.with(|properties| properties.borrow().clone());
+
+    if properties.is_empty() {
+        return false;
+    }
+
+    if !statement {
+        gen.write_byte(b'(');
+    }
+
+    gen.write_bytes(b"super(");
+    gen.write_list(call.arguments);
+    gen.write_byte(b')');
+
+    for name in properties.iter() {
+        gen.write_byte(b',');
+        gen.write_pretty(b' ');
+        gen.write_bytes(b"this.");
+        gen.write_bytes(name.as_bytes());
+        gen.write_pretty(b' ');
+        gen.write_byte(b'=');
+        gen.write_pretty(b' ');
+        gen.write_bytes(name.as_bytes());
     }
+
+    if !statement {
+        gen.write_byte(b',');
+        gen.write_pretty(b' ');
+        gen.write_bytes(b"this)");
+    }
+
+    true
 }
 
 impl<'ast, G: Generator> ToCode<G> for ClassMember<'ast> {
@@ -106,7 +302,104 @@ impl<'ast, G: Generator> ToCode<G> for ClassMember<'ast> {
                 gen.write_pretty(b' ');
                 gen.write(value);
                 gen.write_byte(b';');
-            }
+            },
+            Property {
+                is_static,
+                ref key,
+                ref value,
+                ..
+            } => {
+                if let Some(ref value) = *value {
+                    if is_static {
+                        gen.write_bytes(b"static ");
+                    }
+                    gen.write(key);
+                    gen.write_pretty(b' ');
+                    gen.write_byte(b'=');
+                    gen.write_pretty(b' ');
+                    gen.write(value);
+                    gen.write_byte(b';');
+                }
+            },
+            Accessor {
+                is_static,
+                ref key,
+                ref value,
+                ..
+            } => {
+                if is_static {
+                    gen.write_bytes(b"static ");
+                }
+                gen.write_bytes(b"accessor ");
+                gen.write(key);
+                if let Some(ref value) = *value {
+                    gen.write_pretty(b' ');
+                    gen.write_byte(b'=');
+                    gen.write_pretty(b' ');
+                    gen.write(value);
+                }
+                gen.write_byte(b';');
+            },
+            Signature { .. } => {},
+            Modified {
+                ref member,
+                ..
+            } => gen.write(member),
+            Decorated {
+                decorators,
+                ref member,
+            } => {
+                for decorator in decorators.iter() {
+                    gen.write(decorator);
+                    gen.write_byte(b' ');
+                }
+                gen.write(member);
+            },
+        }
+    }
+
+    #[inline]
+    fn is_erased(&self) -> bool {
+        use esr::ast::ClassMember::*;
+
+        match *self {
+            Property { value, .. }         => value.is_none(),
+            Signature { .. }               => true,
+            Modified { modifiers, member } => modifiers.declare || ToCode::<G>::is_erased(&member.item),
+            Decorated { member, .. }       => ToCode::<G>::is_erased(&member.item),
+            _                              => false,
+        }
+    }
+}
+
+/// Decorators that don't need parentheses, `@foo.bar` or `@foo.bar()`
+fn is_plain_decorator(expression: &ExpressionNode) -> bool {
+    match expression.item {
+        Expression::Identifier(_)                          => true,
+        Expression::Member(MemberExpression { object, .. }) => is_plain_member(&object),
+        Expression::Call(CallExpression { callee, .. })     => is_plain_member(&callee),
+        _                                                  => false,
+    }
+}
+
+fn is_plain_member(expression: &ExpressionNode) -> bool {
+    match expression.item {
+        Expression::Identifier(_)                          => true,
+        Expression::Member(MemberExpression { object, .. }) => is_plain_member(&object),
+        _                                                  => false,
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for Decorator<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write_byte(b'@');
+        if is_plain_decorator(&self.expression) {
+            gen.write(&self.expression);
+        } else {
+            gen.write_byte(b'(');
+            gen.write(&self.expression);
+            gen.write_byte(b')');
         }
     }
 }
@@ -117,6 +410,10 @@ impl<'ast, G, N> ToCode<G> for Class<'ast, N> where
 {
     #[inline]
     fn to_code(&self, gen: &mut G) {
+        for decorator in self.decorators.iter() {
+            gen.write(decorator);
+            gen.write_byte(b' ');
+        }
         N::write_class(gen);
         gen.write(&self.name);
         if let Some(ref super_class) = self.extends {
@@ -156,4 +453,14 @@ mod test {
         assert_min("class Foo { a = 10; b = 20; }", "class Foo{a=10;b=20;}");
         assert_min("class Foo { static a = 10; b = 20; }", "class Foo{static a=10;b=20;}");
     }
+
+    #[test]
+    fn decorators() {
+        assert_min("@foo @bar.baz() class Foo {}", "@foo @bar.baz() class Foo{}");
+        assert_min("@a.b.c class A {}", "@a.b.c class A{}");
+        assert_min("@a.b.c() class A {}", "@a.b.c() class A{}");
+        assert_min("const Foo = @(foo[0]) class {};", "const Foo=@(foo[0]) class{};");
+        assert_min("class Foo { @foo static bar() {} @baz qux = 1; @get get a() {} }", "class Foo{@foo static bar(){}@baz qux=1;@get get a(){}}");
+        assert_min("class Foo { accessor a; @bar static accessor b = 1; accessor = 2; }", "class Foo{accessor a;@bar static accessor b=1;accessor=2;}");
+    }
 }
@@ -0,0 +1,204 @@
+use esr::ast::jsx::*;
+
+use crate::{ToCode, Generator};
+
+
+impl<'ast, G: Generator> ToCode<G> for JSXElementName<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        match *self {
+            JSXElementName::Identifier(ref name) => gen.write(name),
+            JSXElementName::Namespaced {
+                ref namespace,
+                ref name,
+            } => {
+                gen.write(namespace);
+                gen.write_byte(b':');
+                gen.write(name);
+            },
+            JSXElementName::Member(ref member) => gen.write(member),
+        }
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for JSXMemberExpression<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write(&self.object);
+        gen.write_byte(b'.');
+        gen.write(&self.property);
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for JSXExpressionContainer<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write_byte(b'{');
+        gen.write(&self.expression);
+        gen.write_byte(b'}');
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for JSXAttributeValue<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        match *self {
+            JSXAttributeValue::String(ref value)       => gen.write(value),
+            JSXAttributeValue::Expression(ref value)   => gen.write(value),
+            JSXAttributeValue::Element(ref element)    => gen.write(element),
+            JSXAttributeValue::Fragment(ref fragment)  => gen.write(fragment),
+        }
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for JSXAttributeItem<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        match *self {
+            JSXAttributeItem::Attribute(JSXAttribute {
+                ref name,
+                ref value,
+            }) => {
+                gen.write(name);
+
+                if let Some(ref value) = *value {
+                    gen.write_byte(b'=');
+                    gen.write(value);
+                }
+            },
+            JSXAttributeItem::Spread(JSXSpreadAttribute {
+                ref argument,
+            }) => {
+                gen.write_bytes(b"{...");
+                gen.write(argument);
+                gen.write_byte(b'}');
+            },
+        }
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for JSXChild<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        match *self {
+            JSXChild::Text(ref text)           => gen.write(text),
+            JSXChild::Expression(ref value)    => gen.write(value),
+            JSXChild::Element(ref element)     => gen.write(element),
+            JSXChild::Fragment(ref fragment)   => gen.write(fragment),
+        }
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for JSXOpeningElement<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write_byte(b'<');
+        gen.write(&self.name);
+
+        for attribute in &self.attributes {
+            gen.write_byte(b' ');
+            gen.write(attribute);
+        }
+
+        if self.self_closing {
+            gen.write_byte(b'/');
+        }
+
+        gen.write_byte(b'>');
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for JSXClosingElement<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write_bytes(b"</");
+        gen.write(&self.name);
+        gen.write_byte(b'>');
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for JSXElement<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write(&self.opening);
+
+        for child in &self.children {
+            gen.write(child);
+        }
+
+        gen.write(&self.closing);
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for JSXFragment<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write_bytes(b"<>");
+
+        for child in &self.children {
+            gen.write(child);
+        }
+
+        gen.write_bytes(b"</>");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use esr::{parse_with_options, ParseOptions};
+    use crate::codegen;
+
+    fn generate(source: &str, minify: bool) -> String {
+        let options = ParseOptions { jsx: true, ..ParseOptions::default() };
+        let module = parse_with_options(source, options).unwrap();
+
+        codegen(&module, minify)
+    }
+
+    fn assert_jsx(source: &str, expected: &str) {
+        assert_eq!(generate(source, true).as_str(), expected);
+    }
+
+    #[test]
+    fn jsx_element() {
+        assert_jsx("<div/>", "<div/>;");
+        assert_jsx("<div />;", "<div/>;");
+        assert_jsx("<div></div>", "<div></div>;");
+        assert_jsx("<Foo.Bar.Baz/>", "<Foo.Bar.Baz/>;");
+        assert_jsx("<svg:rect/>", "<svg:rect/>;");
+        assert_jsx("<my-element/>", "<my-element/>;");
+    }
+
+    #[test]
+    fn jsx_attributes() {
+        assert_jsx(
+            r#"<a href="/foo" b='bar' c={ 1 + 2 } data-d disabled {...props} />"#,
+            r#"<a href="/foo" b='bar' c={1+2} data-d disabled {...props}/>;"#
+        );
+        assert_jsx(r#"<a b="\" c="&amp;"/>"#, r#"<a b="\" c="&amp;"/>;"#);
+        assert_jsx("<a b=<c/>/>", "<a b=<c/>/>;");
+        assert_jsx("<a xlink:href='#foo'/>", "<a xlink:href='#foo'/>;");
+    }
+
+    #[test]
+    fn jsx_children_are_verbatim() {
+        let source = "<div>\n  Hello, {name}!&nbsp;<br/>\n  <b>{/* empty */}</b>\n</div>";
+        let expected = "<div>\n  Hello, {name}!&nbsp;<br/>\n  <b>{}</b>\n</div>;";
+
+        assert_eq!(generate(source, true).as_str(), expected);
+        assert_eq!(generate(source, false).as_str(), expected);
+    }
+
+    #[test]
+    fn jsx_fragment() {
+        assert_jsx("<></>", "<></>;");
+        assert_jsx("<><a/>text<></></>", "<><a/>text<></></>;");
+    }
+
+    #[test]
+    fn jsx_in_expressions() {
+        assert_jsx("const a = <div/>;", "const a=<div/>;");
+        assert_jsx("render(<App/>, root)", "render(<App/>,root);");
+        assert_jsx("x = a ? <a/> : <b/>", "x=a?<a/>:<b/>;");
+    }
+}
@@ -9,6 +9,8 @@ use esr::Module;
 mod expression;
 mod statement;
 mod function;
+mod jsx;
+mod types;
 
 pub trait Generator: Sized {
     type Output;
@@ -60,7 +62,7 @@ pub trait Generator: Sized {
         T: ToCode<Self> + 'a,
         I: IntoIterator<Item = &'a Node<'a, T>>,
     {
-        let mut items = items.into_iter();
+        let mut items = items.into_iter().filter(|item| !item.is_erased());
 
         match items.next() {
             Some(item) => {
@@ -177,18 +179,24 @@ impl Generator for PrettyGenerator {
     }
 }
 
+/// Generate JavaScript source for the `module`.
+///
+/// Code is generated recursively, modules produced by `esr::parse` are
+/// guaranteed not to nest deeper than `esr::ParseOptions::max_depth`.
+/// Chains of operators, member accesses and calls, which that limit
+/// doesn't apply to, are generated in a loop.
 pub fn codegen(module: &Module<'_>, minify: bool) -> String {
     if minify {
         let mut gen = MinifyingGenerator::new();
 
-        for statement in module.body() {
+        for statement in module.body().iter().filter(|statement| !statement.is_type_declaration()) {
             gen.write(statement);
         }
 
         gen.consume()
     } else {
         let mut gen = PrettyGenerator::new();
-        let mut body = module.body().iter();
+        let mut body = module.body().iter().filter(|statement| !statement.is_type_declaration());
 
         gen.write(&body.next().cloned());
 
@@ -205,6 +213,13 @@ pub fn codegen(module: &Module<'_>, minify: bool) -> String {
 /// to efficiently write characters and string slices to the code `Generator`.
 pub trait ToCode<G: Generator> {
     fn to_code(&self, gen: &mut G);
+
+    /// Whether the item produces no code at all, such as TypeScript
+    /// declarations. Erased items are skipped in blocks.
+    #[inline]
+    fn is_erased(&self) -> bool {
+        false
+    }
 }
 
 impl<'ast, G, T> ToCode<G> for Node<'ast, T> where
@@ -215,6 +230,11 @@ impl<'ast, G, T> ToCode<G> for Node<'ast, T> where
     fn to_code(&self, gen: &mut G) {
         (**self).to_code(gen)
     }
+
+    #[inline]
+    fn is_erased(&self) -> bool {
+        (**self).is_erased()
+    }
 }
 
 impl<G, T> ToCode<G> for Loc<T> where
@@ -225,6 +245,11 @@ impl<G, T> ToCode<G> for Loc<T> where
     fn to_code(&self, gen: &mut G) {
         self.item.to_code(gen)
     }
+
+    #[inline]
+    fn is_erased(&self) -> bool {
+        self.item.is_erased()
+    }
 }
 
 impl<'a, G, T> ToCode<G> for &'a Loc<T> where
@@ -235,6 +260,11 @@ impl<'a, G, T> ToCode<G> for &'a Loc<T> where
     fn to_code(&self, gen: &mut G) {
         self.item.to_code(gen)
     }
+
+    #[inline]
+    fn is_erased(&self) -> bool {
+        self.item.is_erased()
+    }
 }
 
 impl<'a, G> ToCode<G> for &'a str where
@@ -314,7 +344,15 @@ impl<'ast, G: Generator> ToCode<G> for Pattern<'ast> {
                 gen.write_byte(b'=');
                 gen.write_pretty(b' ');
                 gen.write(right);
-            }
+            },
+            Annotated {
+                ref pattern,
+                ..
+            } => gen.write(pattern),
+            ParameterProperty {
+                ref parameter,
+                ..
+            } => gen.write(parameter),
         }
     }
 }
@@ -358,3 +396,79 @@ fn assert_pretty(source: &str, expected: &str) {
 
     assert_eq!(codegen(&module, false).as_str(), expected);
 }
+
+#[cfg(test)]
+mod test {
+    use esr::{parse, parse_with_options, Module, ParseOptions, Flavor};
+    use crate::codegen;
+
+    /// Generate the same code from a module read back from its ESTree.
+    fn assert_estree_round_trip(source: &str, options: ParseOptions) {
+        let module = parse_with_options(source, options).unwrap();
+        let json = serde_json::to_string(&module).unwrap();
+        let read = match Module::from_estree(&json) {
+            Ok(read)   => read,
+            Err(error) => panic!("{}: {}", source, error),
+        };
+
+        assert_eq!(codegen(&read, true), codegen(&module, true), "{}", source);
+        assert_eq!(codegen(&read, false), codegen(&module, false), "{}", source);
+    }
+
+    #[test]
+    fn estree_round_trip() {
+        let sources = [
+            "'use strict'; var a = 1, b; let { c, d: [e, f = 2] } = g;",
+            "function foo(a, b = 1, ...c) { if (a) return b; else throw c; }",
+            "x => x * 2; (a, b) => { return a + b; }; new Foo(); new Bar(1, 2);",
+            "a = b ? c : d; a += -b; x = a || b && !c; typeof a === 'string';",
+            "({ a, b: 1, [c]: 2, d() {}, ...e }); [1, , 2, ...f];",
+            "`a${b}c`; tag`x${y}`; /ab+c/gi; 0b101; 0x1F; 1e3;",
+            "class A extends B { constructor() { this.a = 1; } static b() {} [c]() {} get d() { return 1; } e = 2; }",
+            "for (var i = 0; i < 10; i++) { continue; } for (a in b); for (const c of d) break;",
+            "outer: while (a) { do { break outer; } while (b); }",
+            "try { a(); } catch (e) { b(); } finally { c(); } switch (a) { case 1: b; default: c; }",
+            "@a.b.c export class A { @d accessor e = 1; static accessor f; } export const g = 1;",
+        ];
+
+        for source in sources.iter() {
+            assert_estree_round_trip(source, ParseOptions::default());
+        }
+    }
+
+    #[test]
+    fn tree_at_max_depth() {
+        // The deepest nesting the parser accepts by default, with a long
+        // chain at the bottom, which doesn't count towards the depth.
+        let chain = format!("a{}", ".b(c)[d]+e".repeat(10_000));
+        let source = format!("{}{}{};", "[".repeat(1019), chain, "]".repeat(1019));
+        let module = parse(&source).unwrap();
+
+        assert_eq!(codegen(&module, true), source);
+    }
+
+    #[test]
+    fn babel_round_trip_parentheses() {
+        let options = ParseOptions { parenthesized: true, ..ParseOptions::default() };
+        let sources = [
+            ("((a)) + (b, c);", "(a)+(b,c);"),
+            ("(a) = 1;", "(a)=1;"),
+            ("x = (function(){})();", "x=(function(){})();"),
+        ];
+
+        for &(source, expected) in sources.iter() {
+            let module = parse_with_options(source, options).unwrap();
+            let json = serde_json::to_string(&module.flavored(Flavor::Babel)).unwrap();
+            let read = Module::from_estree(&json).unwrap();
+
+            assert_eq!(codegen(&read, true), expected, "{}", source);
+        }
+    }
+
+    #[test]
+    fn estree_round_trip_jsx() {
+        let options = ParseOptions { jsx: true, ..ParseOptions::default() };
+
+        assert_estree_round_trip("<a:b c=\"d\" {...e}>f &amp; g{h}<I.J /><></></a:b>;", options);
+    }
+}
@@ -2,6 +2,7 @@ use esr::ast::{Statement, Declarator, DeclarationKind};
 use esr::ast::statement::*;
 
 use crate::{ToCode, Generator};
+use crate::function;
 
 
 impl<'ast, G: Generator> ToCode<G> for Statement<'ast> {
@@ -12,6 +13,15 @@ impl<'ast, G: Generator> ToCode<G> for Statement<'ast> {
         match *self {
             Empty => {},
             Expression(ref expression) => {
+                if let esr::ast::Expression::Call(ref call) = expression.item {
+                    if let esr::ast::Expression::Super(_) = call.callee.item {
+                        if function::write_super_call(gen, call, true) {
+                            gen.write_byte(b';');
+                            return;
+                        }
+                    }
+                }
+
                 if expression.is_allowed_as_bare_statement() {
                     gen.write(expression);
                 } else {
@@ -40,9 +50,26 @@ impl<'ast, G: Generator> ToCode<G> for Statement<'ast> {
             Function(ref function)       => gen.write(function),
             Class(ref class)             => gen.write(class),
             Continue(ref cont)           => gen.write(cont),
-            Switch(ref switch)           => gen.write(switch)
+            Switch(ref switch)           => gen.write(switch),
+            Enum(ref enumeration)        => gen.write(enumeration),
+            Namespace(ref namespace)     => gen.write(namespace),
+            Export(ref export) if export.declaration.is_type_declaration() => gen.write_byte(b';'),
+            Export(ref export)           => gen.write(export),
+            // Type declarations are skipped in lists of statements, a lone
+            // one, such as the body of an `if`, leaves an empty statement.
+            Interface(_)                 |
+            TypeAlias(_)                 |
+            FunctionSignature(_)         |
+            Declare(_)                   |
+            OpaqueType(_)                |
+            TypeImport(_)                => gen.write_byte(b';'),
         }
     }
+
+    #[inline]
+    fn is_erased(&self) -> bool {
+        self.is_type_declaration()
+    }
 }
 
 impl<G: Generator> ToCode<G> for DeclarationKind {
@@ -80,6 +107,46 @@ impl<'ast, G: Generator> ToCode<G> for DeclarationStatement<'ast> {
     }
 }
 
+impl<'ast, G: Generator> ToCode<G> for ExportDeclaration<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write_bytes(b"export ");
+
+        if !self.is_default {
+            return gen.write(&self.declaration);
+        }
+
+        gen.write_bytes(b"default ");
+
+        let expression = match self.declaration.item {
+            Statement::Expression(ref expression) => expression,
+            _ => return gen.write(&self.declaration),
+        };
+
+        // Anonymous classes and functions are declarations here, named
+        // ones must stay expressions so as not to declare their name
+        let bare = match expression.item {
+            esr::ast::Expression::Class(ref class) if class.name.0.is_none() => {
+                return gen.write(expression);
+            },
+            esr::ast::Expression::Function(ref function) if function.name.0.is_none() => {
+                return gen.write(expression);
+            },
+            esr::ast::Expression::Sequence(_) => false,
+            _ => expression.is_allowed_as_bare_statement(),
+        };
+
+        if bare {
+            gen.write(expression);
+        } else {
+            gen.write_byte(b'(');
+            gen.write(expression);
+            gen.write_byte(b')');
+        }
+        gen.write_byte(b';');
+    }
+}
+
 impl<'ast, G: Generator> ToCode<G> for ReturnStatement<'ast> {
     #[inline]
     fn to_code(&self, gen: &mut G) {
@@ -258,6 +325,13 @@ impl<'ast, G: Generator> ToCode<G> for TryStatement<'ast> {
     }
 }
 
+impl<'ast, G: Generator> ToCode<G> for Label<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write(&self.as_str());
+    }
+}
+
 impl<'ast, G: Generator> ToCode<G> for LabeledStatement<'ast> {
     #[inline]
     fn to_code(&self, gen: &mut G) {
@@ -330,6 +404,16 @@ mod test {
         assert_min("function foo() {}", "function foo(){}");
     }
 
+    #[test]
+    fn export_default_statement() {
+        assert_min("export default class {}", "export default class{}");
+        assert_min("export default function foo() {}", "export default function foo(){}");
+        assert_min("export default a + b;", "export default a+b;");
+        assert_min("export default (a, b);", "export default (a,b);");
+        assert_min("export default (function foo() {});", "export default (function foo(){});");
+        assert_min("export default { a };", "export default ({a});");
+    }
+
     #[test]
     fn declaration_statement() {
         assert_min("var foo;", "var foo;");
@@ -0,0 +1,195 @@
+use esr::ast::expression::{AsExpression, SatisfiesExpression, TypeCastExpression, NonNullExpression};
+use esr::ast::expression::{TypeAssertion, InstantiationExpression};
+use esr::ast::types::{EnumDeclaration, EnumMember, NamespaceDeclaration};
+
+use crate::{ToCode, Generator};
+
+
+// Types are erased, only the expressions they annotate are written.
+
+impl<'ast, G: Generator> ToCode<G> for AsExpression<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write(&self.expression);
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for SatisfiesExpression<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write(&self.expression);
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for TypeCastExpression<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write(&self.expression);
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for NonNullExpression<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write(&self.expression);
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for TypeAssertion<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write(&self.expression);
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for InstantiationExpression<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write(&self.expression);
+    }
+}
+
+// Enums and namespaces exist at runtime, they are written as they are
+// and left for the transformer to lower to JavaScript.
+
+impl<'ast, G: Generator> ToCode<G> for EnumDeclaration<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        if self.is_const {
+            gen.write_bytes(b"const ");
+        }
+        gen.write_bytes(b"enum ");
+        gen.write(&self.name);
+        gen.write_pretty(b' ');
+        gen.write_byte(b'{');
+        gen.write_list(self.members);
+        gen.write_byte(b'}');
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for EnumMember<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write(&self.name);
+
+        if let Some(ref value) = self.value {
+            gen.write_pretty(b' ');
+            gen.write_byte(b'=');
+            gen.write_pretty(b' ');
+            gen.write(value);
+        }
+    }
+}
+
+impl<'ast, G: Generator> ToCode<G> for NamespaceDeclaration<'ast> {
+    #[inline]
+    fn to_code(&self, gen: &mut G) {
+        gen.write_bytes(b"namespace ");
+        gen.write(&self.name);
+        gen.write_pretty(b' ');
+        gen.write(&self.body);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use esr::{parse_with_options, ParseOptions};
+    use crate::codegen;
+
+    fn generate(source: &str, minify: bool) -> String {
+        let options = ParseOptions { typescript: true, ..ParseOptions::default() };
+        let module = parse_with_options(source, options).unwrap();
+
+        codegen(&module, minify)
+    }
+
+    fn assert_ts(source: &str, expected: &str) {
+        assert_eq!(generate(source, true).as_str(), expected);
+    }
+
+    #[test]
+    fn annotations_are_erased() {
+        assert_ts("let a: number = 1;", "let a=1;");
+        assert_ts("function foo<T>(a?: T, ...b: T[]): void {}", "function foo(a,...b){}");
+        assert_ts("const f = <T,>(a: T): T => a;", "const f=(a)=>a;");
+        assert_ts("const f = (a: number) => a;", "const f=(a)=>a;");
+    }
+
+    #[test]
+    fn type_expressions_are_erased() {
+        assert_ts("foo! as any;", "foo;");
+        assert_ts("(a + b as number) * c;", "(a+b)*c;");
+        assert_ts("<Foo>bar satisfies Baz;", "bar;");
+        assert_ts("foo<number>(1);", "foo(1);");
+    }
+
+    #[test]
+    fn declarations_are_erased() {
+        assert_ts("interface Foo { a: number } foo();", "foo();");
+        assert_ts("type Foo = string | number; foo();", "foo();");
+        assert_ts("declare const foo: number; foo();", "foo();");
+        assert_ts("function foo(a: string): string; function foo(a) {}", "function foo(a){}");
+        assert_ts("function foo() { type A = B; return 1; }", "function foo(){return 1;}");
+    }
+
+    #[test]
+    fn lone_declarations_leave_empty_statements() {
+        assert_ts("if (a) type T = 1; else b();", "if(a); else b();");
+        assert_ts("if (a) b(); else interface I {}", "if(a)b(); else ;");
+        assert_ts("for (;;) declare const a: number;", "for(;;);");
+        assert_ts("while (a) type T = 1;", "while(a);");
+        assert_ts("a: type T = 1;", "a:;");
+    }
+
+    #[test]
+    fn class_members() {
+        assert_ts(
+            "class Foo<T> extends Bar<T> implements Baz { private a: number; static b = 1; declare c: string; foo(): void; foo() {} }",
+            "class Foo extends Bar{static b=1;foo(){}}"
+        );
+        assert_ts(
+            "class Foo { constructor(private readonly a: number, b, public c = 1) { foo(); } }",
+            "class Foo{constructor(a,b,c=1){this.a=a;this.c=c;foo();}}"
+        );
+        assert_ts(
+            "class Foo extends Bar { constructor(private a) { foo(); super(a); bar(); } }",
+            "class Foo extends Bar{constructor(a){foo();super(a);this.a=a;bar();}}"
+        );
+        assert_ts(
+            "class Foo { constructor(private a) { 'use strict'; foo(); } }",
+            "class Foo{constructor(a){'use strict';this.a=a;foo();}}"
+        );
+    }
+
+    #[test]
+    fn parameter_properties_after_nested_super_calls() {
+        assert_ts(
+            "class Foo extends Bar { constructor(private a, public b) { if (a) super(a); else { super(); } } }",
+            "class Foo extends Bar{constructor(a,b){if(a)super(a),this.a=a,this.b=b; else{super(),this.a=a,this.b=b;}}}"
+        );
+        assert_ts(
+            "class Foo extends Bar { constructor(private a) { const f = () => super().foo; f(); } }",
+            "class Foo extends Bar{constructor(a){const f=()=>(super(),this.a=a,this).foo;f();}}"
+        );
+        assert_ts(
+            "class Foo extends Bar { constructor(private a) { super(); class Baz extends Qux { constructor() { super(); } } } }",
+            "class Foo extends Bar{constructor(a){super();this.a=a;class Baz extends Qux{constructor(){super();}}}}"
+        );
+    }
+
+    #[test]
+    fn pretty_parameter_properties() {
+        assert_eq!(
+            generate("class Foo { constructor(private a) {} }", false).as_str(),
+            "class Foo {\n    constructor(a) {\n        this.a = a;\n    }\n}"
+        );
+    }
+
+    #[test]
+    fn enums_and_namespaces() {
+        assert_ts("enum Foo { A, B = 2, 'c-d' }", "enum Foo{A,B=2,'c-d'}");
+        assert_ts("const enum Foo { A = 1 << 2 }", "const enum Foo{A=1<<2}");
+        assert_ts("namespace Foo { export const a = 1; type B = C; }", "namespace Foo{export const a=1;}");
+        assert_ts("declare namespace Foo { const a: number; } foo();", "foo();");
+    }
+}
@@ -0,0 +1,817 @@
+use esr::ast::{Node, NodeList, Block, Pattern, Function, Class, ClassMember, Name, MethodKind};
+use esr::ast::{Statement, StatementNode, ExpressionNode, Expression, Declarator, Literal, AnyNode};
+use esr::ast::{PropertyKey, DecoratorList, EmptyName, OptionalName, DeclarationKind, OperatorKind};
+use esr::ast::expression::{ArrayExpression, CallExpression, SpreadExpression, ThisExpression, SuperExpression};
+use esr::ast::expression::{ClassExpression, ComputedMemberExpression, BinaryExpression, SequenceExpression};
+use esr::ast::expression::{ArrowExpression, ArrowBody};
+use esr::ast::statement::{DeclarationStatement, ReturnStatement, ClassStatement};
+use esr_visitor::Visitor;
+
+use crate::TransformerCtxt;
+
+/// Runtime helpers called by the lowered classes, prepended to the module
+/// whenever a class with decorators was lowered.
+pub const HELPERS: &str = r#"
+var __decorators = new WeakMap();
+function __decorateClass(target, decorators, members) {
+    var state = { fields: {}, initializers: [], storage: new WeakMap() };
+    var statics = [];
+    __decorators.set(target, state);
+    members.forEach(function (member) {
+        var kind = member[0], name = member[1], isStatic = member[2];
+        var initializers = isStatic ? statics : state.initializers;
+        var context = {
+            kind: kind,
+            name: name,
+            "static": isStatic,
+            "private": false,
+            addInitializer: function (initializer) { initializers.push(initializer); }
+        };
+        if (kind === "field") {
+            var key = (isStatic ? "static " : "") + name;
+            state.fields[key] = member[3].reverse().map(function (decorator) {
+                return decorator(undefined, context);
+            }).filter(Boolean);
+            if (isStatic) __defineField(target, name, __initField(target, target, key, member[4].call(target)));
+            return;
+        }
+        var object = isStatic ? target : target.prototype;
+        if (kind === "accessor") {
+            var key = (isStatic ? "static " : "") + name;
+            var descriptor = Object.getOwnPropertyDescriptor(object, name);
+            var set = descriptor.set;
+            state.fields[key] = [];
+            member[3].reverse().forEach(function (decorator) {
+                var value = decorator({ get: descriptor.get, set: descriptor.set }, context);
+                if (value === undefined) return;
+                if (value.get) descriptor.get = value.get;
+                if (value.set) descriptor.set = value.set;
+                if (value.init) state.fields[key].push(value.init);
+            });
+            Object.defineProperty(object, name, descriptor);
+            if (isStatic) set.call(target, __initField(target, target, key, member[4].call(target)));
+            return;
+        }
+        var property = kind === "getter" ? "get" : kind === "setter" ? "set" : "value";
+        var descriptor = Object.getOwnPropertyDescriptor(object, name);
+        member[3].reverse().forEach(function (decorator) {
+            var value = decorator(descriptor[property], context);
+            if (value !== undefined) descriptor[property] = value;
+        });
+        Object.defineProperty(object, name, descriptor);
+    });
+    var initializers = [];
+    var context = {
+        kind: "class",
+        name: target.name,
+        addInitializer: function (initializer) { initializers.push(initializer); }
+    };
+    var decorated = decorators.reverse().reduce(function (value, decorator) {
+        var result = decorator(value, context);
+        return result === undefined ? value : result;
+    }, target);
+    statics.forEach(function (initializer) { initializer.call(target); });
+    initializers.forEach(function (initializer) { initializer.call(decorated); });
+    return decorated;
+}
+function __initField(target, instance, key, value) {
+    return __decorators.get(target).fields[key].reduce(function (value, initializer) {
+        return initializer.call(instance, value);
+    }, value);
+}
+function __defineField(object, key, value) {
+    Object.defineProperty(object, key, { value: value, writable: true, enumerable: true, configurable: true });
+}
+function __storage(target, instance) {
+    var storage = __decorators.get(target).storage;
+    if (!storage.has(instance)) storage.set(instance, Object.create(null));
+    return storage.get(instance);
+}
+function __runInitializers(target, instance) {
+    __decorators.get(target).initializers.forEach(function (initializer) { initializer.call(instance); });
+}
+"#;
+
+/// Name given to anonymous classes that need to refer to themselves.
+const ANONYMOUS_CLASS: &str = "_class";
+
+/// The reason a class couldn't be lowered.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DecoratorErrorKind {
+    /// Decorators of a constructor, which only the class itself can have.
+    DecoratedConstructor,
+}
+
+/// Error type returned when lowering decorators, the class it is about
+/// is left as it was.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DecoratorError {
+    pub kind: DecoratorErrorKind,
+    pub start: u32,
+    pub end: u32,
+}
+
+/// Class lowered to a call to `__decorateClass`, along with the names
+/// and values of the temporaries holding its computed keys.
+struct Lowered<'ast> {
+    call: ExpressionNode<'ast>,
+    temps: Vec<(&'ast str, ExpressionNode<'ast>)>,
+}
+
+/// Lowers decorators to calls to the `HELPERS`. A decorated class becomes
+/// a call to `__decorateClass` with the undecorated class, its decorators,
+/// and a list of members, `[kind, key, isStatic, decorators]`. Static
+/// fields and accessors are always listed, with a function returning their
+/// initial value, and are defined by the helper once all decorators have
+/// been applied.
+///
+/// So that the output is ES2015, instance fields are defined by the
+/// constructor, and auto-accessors become a getter and setter pair over
+/// storage kept by the helpers. Computed keys are evaluated once, before
+/// the class, into temporaries declared next to the class binding, or in
+/// an arrow function wrapped around a class expression.
+pub struct LowerDecorators<'ast> {
+    ctx: TransformerCtxt<'ast>,
+    /// Whether any class was lowered, and the helpers are needed.
+    pub lowered: bool,
+    /// Errors for the classes that couldn't be lowered.
+    pub errors: Vec<DecoratorError>,
+}
+
+impl<'ast> LowerDecorators<'ast> {
+    pub fn new(ctx: TransformerCtxt<'ast>) -> LowerDecorators<'ast> {
+        LowerDecorators {
+            ctx,
+            lowered: false,
+            errors: Vec::new(),
+        }
+    }
+
+    fn identifier(&self, name: &'ast str) -> ExpressionNode<'ast> {
+        self.ctx.alloc(Expression::Identifier(name))
+    }
+
+    fn this(&self) -> ExpressionNode<'ast> {
+        self.ctx.alloc(ThisExpression)
+    }
+
+    fn string(&self, value: &str) -> ExpressionNode<'ast> {
+        let raw = self.ctx.arena.alloc_string(format!("\"{}\"", value));
+
+        self.ctx.alloc(Literal::String(raw))
+    }
+
+    fn boolean(&self, value: bool) -> ExpressionNode<'ast> {
+        self.ctx.alloc(match value {
+            true  => Literal::True,
+            false => Literal::False,
+        })
+    }
+
+    fn array(&mut self, body: Vec<ExpressionNode<'ast>>) -> ExpressionNode<'ast> {
+        let body = self.ctx.list(body);
+
+        self.ctx.alloc(ArrayExpression { body })
+    }
+
+    fn call(&mut self, callee: &'ast str, arguments: Vec<ExpressionNode<'ast>>) -> ExpressionNode<'ast> {
+        let arguments = self.ctx.list(arguments);
+
+        self.ctx.alloc(CallExpression {
+            callee: self.identifier(callee),
+            arguments,
+        })
+    }
+
+    /// `__storage(Foo, this)[key]`, where an auto-accessor keeps its value.
+    fn storage(&mut self, name: &'ast str, key: Node<'ast, PropertyKey<'ast>>) -> ExpressionNode<'ast> {
+        let storage = self.call("__storage", vec![self.identifier(name), self.this()]);
+
+        self.ctx.alloc(ComputedMemberExpression {
+            object: storage,
+            property: self.key(key),
+        })
+    }
+
+    fn assign(&self, left: ExpressionNode<'ast>, right: ExpressionNode<'ast>) -> ExpressionNode<'ast> {
+        self.ctx.alloc(BinaryExpression {
+            operator: OperatorKind::Assign,
+            left,
+            right,
+        })
+    }
+
+    fn decorators(&mut self, decorators: DecoratorList<'ast>) -> ExpressionNode<'ast> {
+        let expressions = decorators.iter().map(|decorator| decorator.expression).collect();
+
+        self.array(expressions)
+    }
+
+    fn key(&self, key: Node<'ast, PropertyKey<'ast>>) -> ExpressionNode<'ast> {
+        match key.item {
+            PropertyKey::Computed(expression) => self.ctx.deep_clone(&expression),
+            PropertyKey::Binary(raw)          => self.ctx.alloc_as_loc(&key, Literal::Binary(raw)),
+            PropertyKey::Literal(raw)         => match raw.as_bytes().first() {
+                Some(b'"') | Some(b'\'') => self.ctx.alloc_as_loc(&key, Literal::String(raw)),
+                Some(b'0'..=b'9') | Some(b'.') => self.ctx.alloc_as_loc(&key, Literal::Number(raw)),
+                _ => self.string(raw),
+            },
+        }
+    }
+
+    /// Move a computed key into a new temporary, `[_key0]`, so that the
+    /// key is evaluated once for the class body and the helpers.
+    fn hoist_key(&mut self, key: &Node<'ast, PropertyKey<'ast>>, temps: &mut Vec<(&'ast str, ExpressionNode<'ast>)>) {
+        if let PropertyKey::Computed(expression) = key.item {
+            let name = self.ctx.arena.alloc_string(format!("_key{}", temps.len()));
+
+            temps.push((name, expression));
+            self.ctx.swap(key, PropertyKey::Computed(self.identifier(name)));
+        }
+    }
+
+    fn function<N: Name<'ast>>(&mut self, name: N, params: NodeList<'ast, Pattern<'ast>>, body: Vec<StatementNode<'ast>>) -> Function<'ast, N> {
+        let body = self.ctx.list(body);
+
+        Function {
+            name,
+            generator: false,
+            type_parameters: NodeList::empty(),
+            params,
+            return_type: None,
+            body: self.ctx.alloc(Block { body }),
+        }
+    }
+
+    /// `function () { return value; }`
+    fn thunk(&mut self, value: ExpressionNode<'ast>) -> ExpressionNode<'ast> {
+        let ret = self.ctx.alloc_as_loc(&value, ReturnStatement {
+            value: Some(value),
+        });
+        let function = self.function(OptionalName::empty(), NodeList::empty(), vec![ret]);
+
+        self.ctx.alloc(function)
+    }
+
+    /// Getter and setter of an auto-accessor, over its storage,
+    /// `get a() { return __storage(Foo, this)["a"]; }`.
+    fn accessor(&mut self, name: &'ast str, member: &Node<'ast, ClassMember<'ast>>, is_static: bool, key: Node<'ast, PropertyKey<'ast>>) -> [Node<'ast, ClassMember<'ast>>; 2] {
+        let storage = self.storage(name, key);
+        let ret = self.ctx.alloc(ReturnStatement { value: Some(storage) });
+        let get = self.function(EmptyName, NodeList::empty(), vec![ret]);
+
+        let storage = self.storage(name, key);
+        let assign = self.assign(storage, self.identifier("value"));
+        let value = self.ctx.alloc(Pattern::Identifier(self.ctx.alloc("value")));
+        let params = self.ctx.list([value]);
+        let set = self.function(EmptyName, params, vec![self.ctx.alloc(assign)]);
+
+        [
+            self.ctx.alloc_as_loc(member, ClassMember::Method {
+                is_static,
+                key,
+                kind: MethodKind::Get,
+                value: self.ctx.alloc(get),
+            }),
+            self.ctx.alloc_as_loc(member, ClassMember::Method {
+                is_static,
+                key: self.ctx.deep_clone(&key),
+                kind: MethodKind::Set,
+                value: self.ctx.alloc(set),
+            }),
+        ]
+    }
+
+    /// Replace the `super(...)` calls nested in a statement, outside of
+    /// nested functions, with a sequence running `init` right after them,
+    /// `(super(), __runInitializers(Foo, this), this)`.
+    fn sequence_super_calls(&mut self, statement: &'ast StatementNode<'ast>, init: &[ExpressionNode<'ast>]) {
+        let mut nodes = vec![AnyNode::from(statement)];
+
+        while let Some(node) = nodes.pop() {
+            match node {
+                AnyNode::Expression(expression) => match expression.item {
+                    Expression::Call(CallExpression { callee, .. }) if matches!(callee.item, Expression::Super(_)) => {
+                        let mut body = vec![self.ctx.alloc_as_loc(expression, expression.item)];
+
+                        body.extend(init.iter().map(|expression| self.ctx.deep_clone(expression)));
+                        body.push(self.this());
+
+                        let body = self.ctx.list(body);
+
+                        self.ctx.swap(expression, SequenceExpression { body });
+                        continue;
+                    },
+                    Expression::Function(_) | Expression::Class(_) => continue,
+                    _ => {},
+                },
+                AnyNode::Statement(statement) if matches!(statement.item, Statement::Function(_) | Statement::Class(_)) => continue,
+                _ => {},
+            }
+
+            nodes.extend(node.children());
+        }
+    }
+
+    /// Constructor running `init`, which calls the initializers added by
+    /// decorators of instance members and defines the instance fields,
+    /// `constructor() { __runInitializers(Foo, this); }`. In an existing
+    /// constructor of a derived class `init` follows every `super(...)`
+    /// call, otherwise it precedes the body, after any directives.
+    fn constructor(&mut self, existing: Option<Node<'ast, ClassMember<'ast>>>, init: Vec<ExpressionNode<'ast>>, derived: bool) -> Node<'ast, ClassMember<'ast>> {
+        if let Some(existing) = existing {
+            if let ClassMember::Method { is_static, key, kind, value } = existing.item {
+                let statements = value.body.body;
+                let mut body = Vec::new();
+
+                if derived {
+                    for statement in statements.iter() {
+                        body.push(*statement);
+
+                        match statement.item {
+                            Statement::Expression(expression) if is_super_call(expression) => {
+                                body.extend(init.iter().map(|expression| {
+                                    let expression = self.ctx.deep_clone(expression);
+
+                                    self.ctx.alloc(expression)
+                                }));
+                            },
+                            _ => self.sequence_super_calls(statement, &init),
+                        }
+                    }
+                } else {
+                    let directives = statements.iter().take_while(|statement| is_directive(statement)).count();
+
+                    body.extend(statements.iter().take(directives).cloned());
+                    body.extend(init.iter().map(|expression| self.ctx.alloc(*expression)));
+                    body.extend(statements.iter().skip(directives).cloned());
+                }
+
+                let body = self.ctx.list(body);
+                let value = self.ctx.alloc_as_loc(&value, Function {
+                    body: self.ctx.alloc_as_loc(&value.body, Block { body }),
+                    ..value.item
+                });
+
+                return self.ctx.alloc_as_loc(&existing, ClassMember::Method { is_static, key, kind, value });
+            }
+        }
+
+        let init: Vec<StatementNode<'ast>> = init.into_iter().map(|expression| self.ctx.alloc(expression)).collect();
+
+        let (params, body) = match derived {
+            // `constructor(...args) { super(...args); }`
+            true => {
+                let args = self.ctx.alloc("args");
+                let rest = self.ctx.alloc(Pattern::RestElement { argument: args });
+                let spread = self.ctx.alloc(SpreadExpression { argument: self.identifier("args") });
+                let call = self.ctx.list([spread]);
+                let call = self.ctx.alloc(CallExpression {
+                    callee: self.ctx.alloc(SuperExpression),
+                    arguments: call,
+                });
+
+                let mut body = vec![self.ctx.alloc(call)];
+
+                body.extend(init);
+
+                (self.ctx.list([rest]), body)
+            },
+            false => (NodeList::empty(), init),
+        };
+
+        let value = self.function(EmptyName, params, body);
+
+        self.ctx.alloc(ClassMember::Method {
+            is_static: false,
+            key: self.ctx.alloc(PropertyKey::Literal("constructor")),
+            kind: MethodKind::Constructor,
+            value: self.ctx.alloc(value),
+        })
+    }
+
+    /// Returns the call decorating the class, or `None` if neither the class
+    /// nor any of its members are decorated, or the class can't be lowered.
+    fn lower<N: Name<'ast>>(&mut self, class: &Class<'ast, N>, name: Option<&'ast str>) -> Option<Lowered<'ast>> {
+        let decorated = class.body.body.iter().any(|member| matches!(member.item, ClassMember::Decorated { .. }));
+
+        if class.decorators.is_empty() && !decorated {
+            return None;
+        }
+
+        let name = name.unwrap_or(ANONYMOUS_CLASS);
+        let mut temps = Vec::new();
+        let mut members = Vec::new();
+        let mut entries = Vec::new();
+        let mut init = Vec::new();
+        let mut constructor = None;
+        let mut instance = false;
+
+        for member in class.body.body.iter() {
+            let (decorators, inner) = match member.item {
+                ClassMember::Decorated { decorators, member } => (decorators, member),
+                _ => (NodeList::empty(), *member),
+            };
+
+            let unwrapped = match inner.item {
+                // Ambient fields only declare types
+                ClassMember::Modified { modifiers, .. } if modifiers.declare => {
+                    members.push(inner);
+                    continue;
+                },
+                ClassMember::Modified { member, .. } => member,
+                _                                    => inner,
+            };
+
+            let (kind, is_static, key, value) = match unwrapped.item {
+                ClassMember::Method { kind: MethodKind::Constructor, .. } => {
+                    if !decorators.is_empty() {
+                        self.errors.push(DecoratorError {
+                            kind: DecoratorErrorKind::DecoratedConstructor,
+                            start: member.start,
+                            end: member.end,
+                        });
+
+                        return None;
+                    }
+
+                    constructor = Some(unwrapped);
+                    continue;
+                },
+                ClassMember::Method { is_static, ref key, kind, .. } => {
+                    let kind = match kind {
+                        MethodKind::Get => "getter",
+                        MethodKind::Set => "setter",
+                        _               => "method",
+                    };
+
+                    (kind, is_static, key, None)
+                },
+                ClassMember::Literal { is_static, ref key, value } => ("field", is_static, key, Some(value)),
+                ClassMember::Property { is_static, ref key, value, .. } => {
+                    let value = value.unwrap_or_else(|| self.ctx.alloc(Literal::Undefined));
+
+                    ("field", is_static, key, Some(value))
+                },
+                ClassMember::Accessor { is_static, ref key, value, .. } => {
+                    let value = value.unwrap_or_else(|| self.ctx.alloc(Literal::Undefined));
+
+                    ("accessor", is_static, key, Some(value))
+                },
+                // Nothing to decorate at runtime
+                _ => {
+                    members.push(inner);
+                    continue;
+                },
+            };
+
+            // Keys of all members are hoisted, so that they are still
+            // evaluated in order
+            self.hoist_key(key, &mut temps);
+
+            let key = *key;
+            let decorated = !decorators.is_empty();
+
+            instance |= decorated && !is_static;
+
+            if kind == "accessor" {
+                members.extend(self.accessor(name, member, is_static, key).iter().cloned());
+            }
+
+            let mut entry = vec![
+                self.string(kind),
+                self.key(key),
+                self.boolean(is_static),
+                self.decorators(decorators),
+            ];
+
+            match value {
+                None => members.push(inner),
+                Some(value) if is_static => entry.push(self.thunk(value)),
+                Some(value) => {
+                    // `__initField(Foo, this, "a", 1)`
+                    let value = match decorated {
+                        true  => self.call("__initField", vec![self.identifier(name), self.this(), self.key(key), value]),
+                        false => value,
+                    };
+
+                    init.push(match kind {
+                        // `__storage(Foo, this)["a"] = value`
+                        "accessor" => {
+                            let storage = self.storage(name, key);
+
+                            self.assign(storage, value)
+                        },
+                        // `__defineField(this, "a", value)`
+                        _ => self.call("__defineField", vec![self.this(), self.key(key), value]),
+                    });
+                },
+            }
+
+            if decorated || (is_static && value.is_some()) {
+                entries.push(self.array(entry));
+            }
+        }
+
+        if instance {
+            let run = self.call("__runInitializers", vec![self.identifier(name), self.this()]);
+
+            init.insert(0, run);
+        }
+
+        if !init.is_empty() {
+            constructor = Some(self.constructor(constructor, init, class.extends.is_some()));
+        }
+
+        if let Some(constructor) = constructor {
+            members.insert(0, constructor);
+        }
+
+        let body = self.ctx.list(members);
+        let undecorated = self.ctx.alloc(Class {
+            decorators: NodeList::empty(),
+            name: OptionalName(Some(self.ctx.alloc(name))),
+            type_parameters: class.type_parameters,
+            extends: class.extends,
+            super_type_arguments: class.super_type_arguments,
+            implements: class.implements,
+            body: self.ctx.alloc_as_loc(&class.body, Block { body }),
+        });
+
+        let decorators = self.decorators(class.decorators);
+        let entries = self.array(entries);
+
+        self.lowered = true;
+
+        Some(Lowered {
+            call: self.call("__decorateClass", vec![undecorated, decorators, entries]),
+            temps,
+        })
+    }
+
+    /// `_key0 = key` for every temporary.
+    fn declarators(&mut self, temps: Vec<(&'ast str, ExpressionNode<'ast>)>) -> Vec<Node<'ast, Declarator<'ast>>> {
+        temps.into_iter().map(|(name, key)| {
+            self.ctx.alloc_as_loc(&key, Declarator {
+                id: self.ctx.alloc(Pattern::Identifier(self.ctx.alloc(name))),
+                init: Some(key),
+            })
+        }).collect()
+    }
+}
+
+/// Whether the expression is a `super(...)` call.
+fn is_super_call(expression: ExpressionNode<'_>) -> bool {
+    match expression.item {
+        Expression::Call(CallExpression { callee, .. }) => matches!(callee.item, Expression::Super(_)),
+        _ => false,
+    }
+}
+
+/// Whether the statement is a directive, such as `"use strict";`.
+fn is_directive(statement: &StatementNode<'_>) -> bool {
+    match statement.item {
+        Statement::Expression(expression) => matches!(expression.item, Expression::Literal(Literal::String(_))),
+        _ => false,
+    }
+}
+
+impl<'ast> Visitor<'ast> for LowerDecorators<'ast> {
+    fn on_class_statement(&mut self, item: &ClassStatement<'ast>, node: &'ast StatementNode<'ast>) {
+        let name = item.name.0.item;
+
+        if let Some(Lowered { call, temps }) = self.lower(item, Some(name)) {
+            // `let _key0 = key, Foo = __decorateClass(class Foo {}, ...);`
+            let mut declarators = self.declarators(temps);
+
+            declarators.push(self.ctx.alloc_as_loc(node, Declarator {
+                id: self.ctx.alloc_as_loc(&item.name.0, Pattern::Identifier(item.name.0)),
+                init: Some(call),
+            }));
+
+            let declarators = self.ctx.list(declarators);
+
+            self.ctx.swap(node, DeclarationStatement {
+                kind: DeclarationKind::Let,
+                declarators,
+            });
+        }
+    }
+
+    fn on_class_expression(&mut self, item: &ClassExpression<'ast>, node: &'ast ExpressionNode<'ast>) {
+        let name = item.name.0.map(|name| name.item);
+
+        let Lowered { call, temps } = match self.lower(item, name) {
+            Some(lowered) => lowered,
+            None          => return,
+        };
+
+        if temps.is_empty() {
+            return self.ctx.swap(node, call.item);
+        }
+
+        // `(() => { let _key0 = key; return __decorateClass(...); })()`
+        let declarators = self.declarators(temps);
+        let declarators = self.ctx.list(declarators);
+        let declaration = self.ctx.alloc(DeclarationStatement {
+            kind: DeclarationKind::Let,
+            declarators,
+        });
+        let ret = self.ctx.alloc(ReturnStatement { value: Some(call) });
+        let body = self.ctx.list([declaration, ret]);
+        let arrow = self.ctx.alloc(ArrowExpression {
+            type_parameters: NodeList::empty(),
+            params: NodeList::empty(),
+            return_type: None,
+            body: ArrowBody::Block(self.ctx.alloc(Block { body })),
+        });
+
+        self.ctx.swap(node, CallExpression {
+            callee: arrow,
+            arguments: NodeList::empty(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use esr::parse;
+    use crate::lower_decorators;
+    use super::{HELPERS, DecoratorError, DecoratorErrorKind};
+
+    fn assert_lowered(source: &str, expected: &str) {
+        let module = parse(source).unwrap();
+        let expected = format!("{}{}", HELPERS, expected);
+        let expected = parse(&expected).unwrap();
+
+        lower_decorators(&module).unwrap();
+
+        assert_eq!(module.body(), expected.body());
+    }
+
+    #[test]
+    fn class_decorators() {
+        assert_lowered(
+            "@foo @bar.baz() class Foo { qux() {} }",
+            "let Foo = __decorateClass(class Foo { qux() {} }, [foo, bar.baz()], []);"
+        );
+        assert_lowered(
+            "const Foo = @foo class {};",
+            "const Foo = __decorateClass(class _class {}, [foo], []);"
+        );
+    }
+
+    #[test]
+    fn default_exports() {
+        assert_lowered(
+            "export default @foo class {}",
+            "export default __decorateClass(class _class {}, [foo], []);"
+        );
+        assert_lowered(
+            "@foo export default class Foo {}",
+            "let Foo = __decorateClass(class Foo {}, [foo], []); export default Foo;"
+        );
+    }
+
+    #[test]
+    fn method_decorators() {
+        assert_lowered(
+            "class Foo { @bar static baz() {} @qux get a() {} b() {} }",
+            r#"let Foo = __decorateClass(class Foo {
+                constructor() { __runInitializers(Foo, this); }
+                static baz() {}
+                get a() {}
+                b() {}
+            }, [], [["method", "baz", true, [bar]], ["getter", "a", false, [qux]]]);"#
+        );
+    }
+
+    #[test]
+    fn field_decorators() {
+        assert_lowered(
+            "class Foo { constructor() { init(); } @bar a = 1; @baz static b = 2; c = 3; }",
+            r#"let Foo = __decorateClass(class Foo {
+                constructor() {
+                    __runInitializers(Foo, this);
+                    __defineField(this, "a", __initField(Foo, this, "a", 1));
+                    __defineField(this, "c", 3);
+                    init();
+                }
+            }, [], [
+                ["field", "a", false, [bar]],
+                ["field", "b", true, [baz], function () { return 2; }]
+            ]);"#
+        );
+    }
+
+    #[test]
+    fn accessor_decorators() {
+        assert_lowered(
+            "class Foo { @bar accessor a = 1; @baz static accessor b = 2; accessor c; }",
+            r#"let Foo = __decorateClass(class Foo {
+                constructor() {
+                    __runInitializers(Foo, this);
+                    __storage(Foo, this)["a"] = __initField(Foo, this, "a", 1);
+                    __storage(Foo, this)["c"] = undefined;
+                }
+                get a() { return __storage(Foo, this)["a"]; }
+                set a(value) { __storage(Foo, this)["a"] = value; }
+                static get b() { return __storage(Foo, this)["b"]; }
+                static set b(value) { __storage(Foo, this)["b"] = value; }
+                get c() { return __storage(Foo, this)["c"]; }
+                set c(value) { __storage(Foo, this)["c"] = value; }
+            }, [], [
+                ["accessor", "a", false, [bar]],
+                ["accessor", "b", true, [baz], function () { return 2; }]
+            ]);"#
+        );
+    }
+
+    #[test]
+    fn derived_classes() {
+        assert_lowered(
+            "class Foo extends Bar { @baz a = 1; }",
+            r#"let Foo = __decorateClass(class Foo extends Bar {
+                constructor(...args) {
+                    super(...args);
+                    __runInitializers(Foo, this);
+                    __defineField(this, "a", __initField(Foo, this, "a", 1));
+                }
+            }, [], [["field", "a", false, [baz]]]);"#
+        );
+        assert_lowered(
+            "class Foo extends Bar { constructor(x) { check(x); super(x); done(); } @baz m() {} }",
+            r#"let Foo = __decorateClass(class Foo extends Bar {
+                constructor(x) { check(x); super(x); __runInitializers(Foo, this); done(); }
+                m() {}
+            }, [], [["method", "m", false, [baz]]]);"#
+        );
+        assert_lowered(
+            "class Foo extends Bar { constructor(x) { if (x) super(1); else { super(2); } () => super(3); } @baz m() {} }",
+            r#"let Foo = __decorateClass(class Foo extends Bar {
+                constructor(x) {
+                    if (x) super(1), __runInitializers(Foo, this), this;
+                    else { super(2), __runInitializers(Foo, this), this; }
+                    () => (super(3), __runInitializers(Foo, this), this);
+                }
+                m() {}
+            }, [], [["method", "m", false, [baz]]]);"#
+        );
+    }
+
+    #[test]
+    fn directives_stay_first() {
+        assert_lowered(
+            "class Foo { constructor() { 'use strict'; init(); } @bar m() {} }",
+            r#"let Foo = __decorateClass(class Foo {
+                constructor() { 'use strict'; __runInitializers(Foo, this); init(); }
+                m() {}
+            }, [], [["method", "m", false, [bar]]]);"#
+        );
+    }
+
+    #[test]
+    fn computed_keys() {
+        assert_lowered(
+            "class Foo { @bar [a()]() {} [b()] = 1; static [c()] = 2; }",
+            r#"let _key0 = a(), _key1 = b(), _key2 = c(), Foo = __decorateClass(class Foo {
+                constructor() { __runInitializers(Foo, this); __defineField(this, _key1, 1); }
+                [_key0]() {}
+            }, [], [
+                ["method", _key0, false, [bar]],
+                ["field", _key2, true, [], function () { return 2; }]
+            ]);"#
+        );
+        assert_lowered(
+            "const Foo = @bar class { [a()]() {} };",
+            r#"const Foo = (() => {
+                let _key0 = a();
+                return __decorateClass(class _class { [_key0]() {} }, [bar], []);
+            })();"#
+        );
+    }
+
+    #[test]
+    fn decorated_constructors() {
+        let source = "class Foo { @bar constructor() {} }";
+        let module = parse(source).unwrap();
+        let expected = parse(source).unwrap();
+
+        assert_eq!(lower_decorators(&module), Err(vec![DecoratorError {
+            kind: DecoratorErrorKind::DecoratedConstructor,
+            start: 12,
+            end: 33,
+        }]));
+        assert_eq!(module.body(), expected.body());
+    }
+
+    #[test]
+    fn undecorated_classes() {
+        let module = parse("class Foo { bar() {} }").unwrap();
+        let expected = parse("class Foo { bar() {} }").unwrap();
+
+        lower_decorators(&module).unwrap();
+
+        assert_eq!(module.body(), expected.body());
+    }
+}
@@ -32,10 +32,12 @@ impl<'ast> Visitor<'ast> for TransformArrow<'ast> {
             }
         };
 
-        self.ctx.swap(*ptr, Function {
+        self.ctx.swap(ptr, Function {
             name: OptionalName::empty(),
             generator: false,
+            type_parameters: node.type_parameters,
             params: node.params,
+            return_type: node.return_type,
             body,
         });
     }
@@ -1,50 +1,50 @@
-use esr::ast::{Node, Loc, Expression, ExpressionNode, OperatorKind};
-use esr::ast::expression::{BinaryExpression, MemberExpression, CallExpression};
-use esr_visitor::Visitor;
+use esr::ast::{Expression, ExpressionNode, OperatorKind};
+use esr::ast::expression::{BinaryExpression, CallExpression};
+use esr_visitor::{Visitor, Visitable};
 
-use TransformerCtxt;
+use crate::TransformerCtxt;
 
 pub struct PresetES2016<'ast> {
     ctx: TransformerCtxt<'ast>
 }
 
-static MATH: &Loc<Expression> = &Loc {
-    start: 0,
-    end: 0,
-    item: Expression::Identifier("Math")
-};
-static POW: &Loc<&str> = &Loc {
-    start: 0,
-    end: 0,
-    item: "pow"
-};
+impl<'ast> PresetES2016<'ast> {
+    pub fn new(ctx: TransformerCtxt<'ast>) -> PresetES2016<'ast> {
+        PresetES2016 {
+            ctx
+        }
+    }
+
+    fn math_pow(&self, left: ExpressionNode<'ast>, right: ExpressionNode<'ast>) -> CallExpression<'ast> {
+        CallExpression {
+            callee: self.ctx.member_expression(self.ctx.identifier_expression("Math"), "pow"),
+            arguments: self.ctx.builder.list([left, right]),
+        }
+    }
+}
 
 impl<'ast> Visitor<'ast> for PresetES2016<'ast> {
-    fn on_binary_expression(&mut self, node: &BinaryExpression<'ast>, ptr: &ExpressionNode<'ast>) {
+    fn on_binary_expression(&mut self, node: &BinaryExpression<'ast>, ptr: &'ast ExpressionNode<'ast>) {
+        if !matches!(node.operator, OperatorKind::Exponent | OperatorKind::ExponentAssign) {
+            return;
+        }
+
+        // The operands are moved into new nodes the visitor doesn't descend
+        // into, so any exponents within them have to be lowered first
+        if let Expression::Binary(ref binary) = ptr.item {
+            binary.left.visit_with(self);
+            binary.right.visit_with(self);
+        }
+
         match node.operator {
             OperatorKind::Exponent => {
-                let callee = self.ctx.alloc(MemberExpression {
-                    object: Node::new(MATH),
-                    property: Node::new(POW),
-                });
-                let arguments = self.ctx.list([node.left, node.right]);
-
-                self.ctx.swap(ptr, CallExpression {
-                    callee,
-                    arguments
-                });
+                self.ctx.swap(ptr, self.math_pow(node.left, node.right));
             },
 
             OperatorKind::ExponentAssign => {
-                let callee = self.ctx.alloc(MemberExpression {
-                    object: Node::new(MATH),
-                    property: Node::new(POW),
-                });
-                let arguments = self.ctx.list([node.left, node.right]);
-                let right = self.ctx.alloc(CallExpression {
-                    callee,
-                    arguments
-                });
+                // The target is both assigned and read, each needs its own node
+                let target = self.ctx.deep_clone(&node.left);
+                let right = self.ctx.alloc(self.math_pow(target, node.right));
 
                 self.ctx.swap(ptr, BinaryExpression {
                     operator: OperatorKind::Assign,
@@ -57,3 +57,67 @@ impl<'ast> Visitor<'ast> for PresetES2016<'ast> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use esr::parse;
+    use esr::ast::{Statement, Expression, AstBuilder};
+    use esr::ast::expression::{BinaryExpression, CallExpression};
+    use crate::lower_exponents;
+
+    fn assert_lowered(source: &str, expected: &str) {
+        let module = parse(source).unwrap();
+        let expected = parse(expected).unwrap();
+
+        lower_exponents(&module);
+
+        assert_eq!(module.body(), expected.body());
+    }
+
+    #[test]
+    fn exponent() {
+        assert_lowered("a ** b;", "Math.pow(a, b);");
+        assert_lowered("a ** b ** c;", "Math.pow(a, Math.pow(b, c));");
+        assert_lowered("(a + b) ** 2;", "Math.pow(a + b, 2);");
+    }
+
+    #[test]
+    fn exponent_assign() {
+        assert_lowered("a **= 2;", "a = Math.pow(a, 2);");
+        assert_lowered("a.b[c] **= d ** 2;", "a.b[c] = Math.pow(a.b[c], Math.pow(d, 2));");
+        assert_lowered("a[b ** 2] **= 3;", "a[Math.pow(b, 2)] = Math.pow(a[Math.pow(b, 2)], 3);");
+        assert_lowered("a **= b **= 2;", "a = Math.pow(a, b = Math.pow(b, 2));");
+    }
+
+    #[test]
+    fn exponent_assign_target_is_not_aliased() {
+        let module = parse("a.b **= 2;").unwrap();
+
+        lower_exponents(&module);
+
+        let (left, read) = match module.body().only_element().unwrap().item {
+            Statement::Expression(expression) => match expression.item {
+                Expression::Binary(BinaryExpression { left, right, .. }) => match right.item {
+                    Expression::Call(CallExpression { arguments, .. }) => (left, *arguments.first_element().unwrap()),
+                    _ => panic!("Expected a call to Math.pow"),
+                },
+                _ => panic!("Expected an assignment"),
+            },
+            _ => panic!("Expected an expression statement"),
+        };
+
+        assert!(!std::ptr::eq(&*left, &*read));
+
+        // Renaming the assigned object must leave the read one intact
+        let b = AstBuilder::new(module.arena());
+
+        match left.item {
+            Expression::Member(ref member) => b.swap(&member.object, Expression::Identifier("x")),
+            _ => panic!("Expected a member expression"),
+        }
+
+        let expected = parse("x.b = Math.pow(a.b, 2);").unwrap();
+
+        assert_eq!(module.body(), expected.body());
+    }
+}
@@ -0,0 +1,298 @@
+use esr::ast::{Node, NodeList, Block, Pattern, Function, Name, Class, ClassMember, Property};
+use esr::ast::{Statement, StatementList, StatementNode, ExpressionNode, Expression, Declarator};
+use esr::ast::expression::{ArrowExpression, CallExpression, ObjectExpression, InstantiationExpression};
+use esr::ast::statement::{DeclarationStatement, ForStatement, ForInStatement, ForOfStatement, ForInit};
+use esr::ast::statement::{FunctionStatement, ClassStatement};
+use esr::ast::expression::{FunctionExpression, ClassExpression, TypeCastExpression};
+use esr_visitor::Visitor;
+
+use crate::TransformerCtxt;
+
+/// Removes Flow type annotations and declarations, leaving plain JavaScript.
+/// Nodes stripped of their types keep their original locations, so that
+/// the output can still be mapped back to the source.
+pub struct StripFlow<'ast> {
+    ctx: TransformerCtxt<'ast>
+}
+
+impl<'ast> StripFlow<'ast> {
+    pub fn new(ctx: TransformerCtxt<'ast>) -> StripFlow<'ast> {
+        StripFlow {
+            ctx
+        }
+    }
+
+    fn strip_pattern(&self, pattern: &Node<'ast, Pattern<'ast>>) {
+        match pattern.item {
+            Pattern::Annotated { pattern: ref inner, .. } => {
+                self.strip_pattern(inner);
+
+                let mut inner = *inner;
+
+                pattern.set(*inner.get_mut());
+            },
+            Pattern::AssignmentPattern { ref left, .. } => self.strip_pattern(left),
+            _ => {},
+        }
+    }
+
+    fn strip_params(&self, params: NodeList<'ast, Pattern<'ast>>) {
+        for param in params.iter() {
+            self.strip_pattern(param);
+        }
+    }
+
+    fn strip_declarators(&self, declarators: NodeList<'ast, Declarator<'ast>>) {
+        for declarator in declarators.iter() {
+            self.strip_pattern(&declarator.id);
+        }
+    }
+
+    fn strip_for_init(&self, init: Node<'ast, ForInit<'ast>>) {
+        if let ForInit::Declaration(ref declaration) = init.item {
+            self.strip_declarators(declaration.declarators);
+        }
+    }
+
+    fn strip_function<N: Name<'ast>>(&self, function: &Function<'ast, N>) -> Function<'ast, N> {
+        self.strip_params(function.params);
+
+        Function {
+            type_parameters: NodeList::empty(),
+            return_type: None,
+            ..*function
+        }
+    }
+
+    fn strip_class<N: Name<'ast>>(&mut self, class: &Class<'ast, N>) -> Class<'ast, N> {
+        let mut members = Vec::new();
+
+        for member in class.body.body.iter() {
+            match member.item {
+                ClassMember::Method { ref value, .. } => {
+                    self.ctx.swap(value, self.strip_function(&value.item));
+                    members.push(*member);
+                },
+                // Uninitialized properties only declare types
+                ClassMember::Property { is_static, key, value: Some(value), .. } => {
+                    members.push(self.ctx.alloc_as_loc(member, ClassMember::Literal {
+                        is_static,
+                        key,
+                        value,
+                    }));
+                },
+                ClassMember::Property { .. } | ClassMember::Signature { .. } => {},
+                _ => members.push(*member),
+            }
+        }
+
+        let body = self.ctx.list(members);
+
+        Class {
+            type_parameters: NodeList::empty(),
+            super_type_arguments: NodeList::empty(),
+            implements: NodeList::empty(),
+            body: self.ctx.alloc_as_loc(&class.body, Block { body }),
+            ..*class
+        }
+    }
+}
+
+impl<'ast> Visitor<'ast> for StripFlow<'ast> {
+    fn on_statement_list(&mut self, body: StatementList<'ast>) {
+        for statement in body.iter() {
+            if statement.is_type_declaration() {
+                self.ctx.swap(statement, Statement::Empty);
+            }
+        }
+    }
+
+    // Statement hooks get items of an unrelated lifetime, the nodes
+    // to strip are taken from the statement itself instead.
+    fn on_declaration_statement(&mut self, _: &DeclarationStatement<'_>, node: &'ast StatementNode<'ast>) {
+        if let Statement::Declaration(ref declaration) = node.item {
+            self.strip_declarators(declaration.declarators);
+        }
+    }
+
+    fn on_for_statement(&mut self, _: &ForStatement<'_>, node: &'ast StatementNode<'ast>) {
+        if let Statement::For(ForStatement { init: Some(init), .. }) = node.item {
+            self.strip_for_init(init);
+        }
+    }
+
+    fn on_for_in_statement(&mut self, _: &ForInStatement<'_>, node: &'ast StatementNode<'ast>) {
+        if let Statement::ForIn(for_in) = node.item {
+            self.strip_for_init(for_in.left);
+        }
+    }
+
+    fn on_for_of_statement(&mut self, _: &ForOfStatement<'_>, node: &'ast StatementNode<'ast>) {
+        if let Statement::ForOf(for_of) = node.item {
+            self.strip_for_init(for_of.left);
+        }
+    }
+
+    fn on_function_statement(&mut self, item: &FunctionStatement<'ast>, node: &'ast StatementNode<'ast>) {
+        self.ctx.swap(node, self.strip_function(item));
+    }
+
+    fn on_function_expression(&mut self, item: &FunctionExpression<'ast>, node: &'ast ExpressionNode<'ast>) {
+        self.ctx.swap(node, self.strip_function(item));
+    }
+
+    fn on_arrow_expression(&mut self, item: &ArrowExpression<'ast>, node: &'ast ExpressionNode<'ast>) {
+        self.strip_params(item.params);
+
+        self.ctx.swap(node, ArrowExpression {
+            type_parameters: NodeList::empty(),
+            return_type: None,
+            ..*item
+        });
+    }
+
+    fn on_class_statement(&mut self, item: &ClassStatement<'ast>, node: &'ast StatementNode<'ast>) {
+        let class = self.strip_class(item);
+
+        self.ctx.swap(node, class);
+    }
+
+    fn on_class_expression(&mut self, item: &ClassExpression<'ast>, node: &'ast ExpressionNode<'ast>) {
+        let class = self.strip_class(item);
+
+        self.ctx.swap(node, class);
+    }
+
+    fn on_object_expression(&mut self, item: &ObjectExpression<'ast>, _: &'ast ExpressionNode<'ast>) {
+        for property in item.body.iter() {
+            if let Property::Method { ref value, .. } = property.item {
+                self.ctx.swap(value, self.strip_function(&value.item));
+            }
+        }
+    }
+
+    // The cast is replaced by the expression it wraps, which has been
+    // stripped already, parentheses are left for the generator to add.
+    fn on_type_cast_expression(&mut self, item: &TypeCastExpression<'ast>, node: &'ast ExpressionNode<'ast>) {
+        let mut expression = item.expression;
+
+        node.set(*expression.get_mut());
+    }
+
+    fn on_call_expression(&mut self, item: &CallExpression<'ast>, node: &'ast ExpressionNode<'ast>) {
+        if let Expression::Instantiation(InstantiationExpression { expression, .. }) = item.callee.item {
+            self.ctx.swap(node, CallExpression {
+                callee: expression,
+                arguments: item.arguments,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use esr::{parse, parse_with_options, ParseOptions};
+    use esr::ast::{Statement, Pattern, Expression, ClassMember};
+    use crate::strip_types;
+
+    fn assert_stripped(flow: &str, expected: &str) {
+        let module = parse_with_options(flow, ParseOptions { flow: true, ..ParseOptions::default() }).unwrap();
+        let expected = parse(expected).unwrap();
+
+        strip_types(&module);
+
+        assert_eq!(module.body(), expected.body());
+    }
+
+    #[test]
+    fn strip_declarations() {
+        assert_stripped(
+            "type A = ?string; opaque type B = number; import type { C } from 'c'; interface D {} foo;",
+            ";;;; foo;"
+        );
+        assert_stripped(
+            "declare var a: number; declare function b(): void; declare class C {} function d() { type E = F; }",
+            ";;; function d() { ; }"
+        );
+    }
+
+    #[test]
+    fn strip_annotations() {
+        assert_stripped(
+            "let a: number = 1, b: ?{| c: string |}; for (const d: string of e) {}",
+            "let a = 1, b; for (const d of e) {}"
+        );
+        assert_stripped(
+            "function f<T>(a: T, b?: number = 1): Array<T> { return [a]; }",
+            "function f(a, b = 1) { return [a]; }"
+        );
+        assert_stripped(
+            "const g = <T>(a: T): T => a; const h = { i(j: string): void {} };",
+            "const g = (a) => a; const h = { i(j) {} };"
+        );
+        assert_stripped("k<string>(l);", "k(l);");
+    }
+
+    #[test]
+    fn strip_type_casts() {
+        assert_stripped("let x = (y: any);", "let x = y;");
+        assert_stripped("a = b ? (c: d) : e;", "a = b ? c : e;");
+        assert_stripped("f(((g: any): string), (h, i: T));", "f(g, (h, i));");
+        assert_stripped("j = (function (k: T) {}: any);", "j = function (k) {};");
+    }
+
+    #[test]
+    fn strip_classes() {
+        assert_stripped(
+            "class A<T> extends B<T> implements C { d: T; static e: number = 1; f(g: T): void {} }",
+            "class A extends B { static e = 1; f(g) {} }"
+        );
+    }
+
+    #[test]
+    fn preserve_locations() {
+        let source = "// @flow\nfunction foo(bar: string): void { let baz: number = 1; }";
+        let module = parse(source).unwrap();
+
+        strip_types(&module);
+
+        let function = match module.body().only_element().unwrap().item {
+            Statement::Function(function) => function,
+            _ => panic!("Statement isn't a function!")
+        };
+
+        let param = function.params.only_element().unwrap();
+
+        match param.item {
+            Pattern::Identifier(name) => assert_eq!(name.item, "bar"),
+            _ => panic!("Parameter isn't an identifier!")
+        }
+        assert_eq!(&source[param.start as usize..param.end as usize], "bar");
+        assert_eq!(&source[function.body.start as usize..function.body.end as usize], "{ let baz: number = 1; }");
+
+        let declarator = match function.body.body.only_element().unwrap().item {
+            Statement::Declaration(declaration) => *declaration.declarators.only_element().unwrap(),
+            _ => panic!("Statement isn't a declaration!")
+        };
+
+        assert_eq!(&source[declarator.id.start as usize..declarator.id.end as usize], "baz");
+        assert_eq!(declarator.init.map(|init| init.item), Some(Expression::Literal(esr::ast::Literal::Number("1"))));
+    }
+
+    #[test]
+    fn keep_initialized_properties() {
+        let module = parse("// @flow\nclass A { b: number = 1; c: string; }").unwrap();
+
+        strip_types(&module);
+
+        let class = match module.body().only_element().unwrap().item {
+            Statement::Class(class) => class,
+            _ => panic!("Statement isn't a class!")
+        };
+
+        match class.body.body.only_element().unwrap().item {
+            ClassMember::Literal { .. } => {},
+            member => panic!("Unexpected member {:?}", member),
+        }
+    }
+}
@@ -5,40 +5,42 @@ extern crate pretty_assertions;
 
 
 
+use std::ops::Deref;
+
 use toolshed::Arena;
-use toolshed::list::ListBuilder;
-use esr::ast::{Loc, Node, NodeList};
+use esr::ast::{Node, NodeList, AstBuilder, Statement, StatementNode, Pattern};
+use esr::ast::statement::{ExportDeclaration, DeclarationStatement};
 use esr::Module;
 use esr_visitor::{ScopeKind, Visitable};
 
+pub mod decorators;
 pub mod es2015;
-// pub mod es2016;
+pub mod flow;
+pub mod typescript;
+pub mod es2016;
 
 pub mod scope;
 
 use self::scope::Scope;
+use self::decorators::DecoratorError;
 
+/// State shared by transformations. Nodes are built with the `AstBuilder`
+/// it dereferences to.
 #[derive(Copy, Clone)]
 pub struct TransformerCtxt<'ast> {
     pub arena: &'ast Arena,
     pub scope: &'ast Scope<'ast>,
+    pub builder: AstBuilder<'ast>,
 }
 
 impl<'ast> TransformerCtxt<'ast> {
     #[inline]
-    pub fn alloc<T, I>(&self, item: I) -> Node<'ast, T> where
-        T: Copy,
-        I: Into<T>,
-    {
-        Node::new(self.arena.alloc(Loc::new(0, 0, item.into())))
-    }
-
-    #[inline]
-    pub fn alloc_as_loc<T, I, L>(&self, loc: &Node<'ast, L>, item: I) -> Node<'ast, T> where
-        T: Copy + 'ast,
-        I: Into<T>,
-    {
-        Node::new(self.arena.alloc(Loc::new(loc.start, loc.end, item.into())))
+    pub fn new(arena: &'ast Arena, scope: &'ast Scope<'ast>) -> Self {
+        TransformerCtxt {
+            arena,
+            scope,
+            builder: AstBuilder::new(arena),
+        }
     }
 
     #[inline]
@@ -46,42 +48,134 @@ impl<'ast> TransformerCtxt<'ast> {
         T: 'ast + Copy,
         I: AsRef<[Node<'ast, T>]>
     {
-        let mut iter = source.as_ref().into_iter();
+        self.builder.list(source.as_ref().iter().cloned())
+    }
+}
 
-        let builder = match iter.next() {
-            Some(item) => ListBuilder::new(self.arena, *item),
-            None       => return NodeList::empty(),
-        };
+impl<'ast> Deref for TransformerCtxt<'ast> {
+    type Target = AstBuilder<'ast>;
+
+    #[inline]
+    fn deref(&self) -> &AstBuilder<'ast> {
+        &self.builder
+    }
+}
+
+/// Run all transformations. Classes whose decorators can't be lowered are
+/// reported once the decorators of all other classes have been lowered,
+/// and the remaining transformations are skipped.
+pub fn transform<'ast>(module: &'ast mut Module<'ast>) -> Result<(), Vec<DecoratorError>> {
+    let arena = module.arena();
+
+    let ctx = TransformerCtxt::new(arena, arena.alloc(Scope::new(ScopeKind::Function, None)));
+
+    if module.is_flow() {
+        module.visit_with(&mut flow::StripFlow::new(ctx));
+    }
+
+    let module: &'ast Module<'ast> = module;
+
+    decorate(ctx, module)?;
+    module.visit_with(&mut typescript::InlineConstEnums::new(ctx, module));
+    module.visit_with(&mut typescript::LowerTypeScript::new(ctx));
+    module.visit_with(&mut es2016::PresetES2016::new(ctx));
+    module.visit_with(&mut es2015::PresetES2015::new(ctx));
+
+    Ok(())
+}
 
-        for item in iter {
-            builder.push(self.arena, *item);
+fn decorate<'ast>(mut ctx: TransformerCtxt<'ast>, module: &'ast Module<'ast>) -> Result<(), Vec<DecoratorError>> {
+    let mut lower = decorators::LowerDecorators::new(ctx);
+
+    module.visit_with(&mut lower);
+
+    if lower.lowered {
+        let helpers = esr::parse_function_body(decorators::HELPERS, ctx.arena)
+            .expect("Decorator helpers must parse");
+        let mut body: Vec<_> = helpers.iter().cloned().collect();
+
+        for statement in module.body().iter() {
+            match default_export_binding(statement) {
+                Some((declaration, name)) => {
+                    let export = ctx.alloc_as_loc(statement, ExportDeclaration {
+                        is_default: true,
+                        declaration: ctx.expression_statement(ctx.identifier_expression(name)),
+                    });
+
+                    body.push(declaration);
+                    body.push(export);
+                },
+                None => body.push(*statement),
+            }
         }
 
-        builder.as_list()
+        module.set_body(ctx.list(body));
     }
 
-    #[inline]
-    pub fn swap<T, I>(&self, ptr: Node<'ast, T>, item: I) where
-        T: Copy + 'ast,
-        I: Into<T>,
-    {
-        let new = self.arena.alloc(Loc {
-            start: ptr.start,
-            end: ptr.end,
-            item: item.into()
-        });
+    match lower.errors.is_empty() {
+        true  => Ok(()),
+        false => Err(lower.errors),
+    }
+}
+
+/// A decorated class exported by default is lowered to a declaration of
+/// its binding, `export default let Foo = ...;`, which has to be declared
+/// ahead of exporting the binding.
+fn default_export_binding<'ast>(statement: &StatementNode<'ast>) -> Option<(StatementNode<'ast>, &'ast str)> {
+    let declaration = match statement.item {
+        Statement::Export(ExportDeclaration { is_default: true, declaration }) => declaration,
+        _ => return None,
+    };
 
-        ptr.set(new);
+    match declaration.item {
+        Statement::Declaration(DeclarationStatement { declarators, .. }) => {
+            match declarators.iter().last().map(|declarator| declarator.id.item) {
+                Some(Pattern::Identifier(name)) => Some((declaration, name.item)),
+                _ => None,
+            }
+        },
+        _ => None,
     }
 }
 
-pub fn transform<'ast>(module: &'ast mut Module<'ast>) {
+/// Remove Flow types from the module, regardless of whether it was
+/// parsed with Flow enabled.
+pub fn strip_types<'ast>(module: &'ast Module<'ast>) {
     let arena = module.arena();
 
-    let ctx = TransformerCtxt {
-        arena,
-        scope: arena.alloc(Scope::new(ScopeKind::Function, None)),
-    };
+    let ctx = TransformerCtxt::new(arena, arena.alloc(Scope::new(ScopeKind::Function, None)));
 
-    module.visit_with(&mut es2015::PresetES2015::new(ctx));
+    module.visit_with(&mut flow::StripFlow::new(ctx));
+}
+
+/// Lower TypeScript enums and namespaces to plain JavaScript, inlining
+/// members of `const enum`s.
+pub fn lower_typescript<'ast>(module: &'ast Module<'ast>) {
+    let arena = module.arena();
+
+    let ctx = TransformerCtxt::new(arena, arena.alloc(Scope::new(ScopeKind::Function, None)));
+
+    module.visit_with(&mut typescript::InlineConstEnums::new(ctx, module));
+    module.visit_with(&mut typescript::LowerTypeScript::new(ctx));
+}
+
+/// Lower the exponent operators `**` and `**=` to calls to `Math.pow`.
+pub fn lower_exponents<'ast>(module: &'ast Module<'ast>) {
+    let arena = module.arena();
+
+    let ctx = TransformerCtxt::new(arena, arena.alloc(Scope::new(ScopeKind::Function, None)));
+
+    module.visit_with(&mut es2016::PresetES2016::new(ctx));
+}
+
+/// Lower decorators to calls to runtime helpers, which are prepended to
+/// the module if any class was decorated. Classes that can't be lowered,
+/// such as those with a decorated constructor, are left as they are and
+/// reported.
+pub fn lower_decorators<'ast>(module: &'ast Module<'ast>) -> Result<(), Vec<DecoratorError>> {
+    let arena = module.arena();
+
+    let ctx = TransformerCtxt::new(arena, arena.alloc(Scope::new(ScopeKind::Function, None)));
+
+    decorate(ctx, module)
 }
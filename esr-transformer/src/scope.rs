@@ -4,15 +4,23 @@ use esr::Module;
 use esr::ast::{Identifier, ExpressionNode};
 use esr_visitor::{Visitable, ScopeKind, Visitor};
 use toolshed::{Arena, CopyCell};
-use toolshed::list::GrowableList;
+use toolshed::list::{GrowableList, ListIter};
 use toolshed::map::BloomMap;
 
 /// Traverse the AST and produce a tree of `Scope`s.
 #[inline]
 pub fn analyze<'ast>(module: &'ast Module<'ast>) -> &'ast Scope<'ast> {
-    let mut visitor = ScopeAnalyzer::new(module.arena());
+    analyze_node(module.arena(), module)
+}
+
+/// Traverse any part of the AST, such as the body of a namespace, and
+/// produce a tree of `Scope`s, rooted at a function scope.
+pub fn analyze_node<'ast, V>(arena: &'ast Arena, root: &'ast V) -> &'ast Scope<'ast> where
+    V: Visitable<'ast>,
+{
+    let mut visitor = ScopeAnalyzer::new(arena);
 
-    module.visit_with(&mut visitor);
+    root.visit_with(&mut visitor);
 
     visitor.current.get()
 }
@@ -90,6 +98,57 @@ impl<'ast> PartialEq for &'ast Scope<'ast> {
     }
 }
 
+/// Follows the `Scope`s produced by `analyze` while visiting the same AST
+/// again, so that names can be resolved to the scopes declaring them.
+/// Visitors forward `on_enter_scope` and `on_leave_scope` to it, and must
+/// not add or remove any scopes in the part of the AST they have yet to visit.
+pub struct ScopeCursor<'ast> {
+    stack: Vec<(&'ast Scope<'ast>, ListIter<'ast, &'ast Scope<'ast>>)>,
+}
+
+impl<'ast> ScopeCursor<'ast> {
+    pub fn new(root: &'ast Scope<'ast>) -> Self {
+        ScopeCursor {
+            stack: vec![(root, root.children.as_list().iter())],
+        }
+    }
+
+    /// Move into the next child of the current scope.
+    pub fn enter(&mut self) {
+        let child = self.stack
+            .last_mut()
+            .and_then(|(_, children)| children.next())
+            .copied()
+            .expect("Scopes must match the analyzed AST");
+
+        self.stack.push((child, child.children.as_list().iter()));
+    }
+
+    pub fn leave(&mut self) {
+        self.stack.pop();
+    }
+
+    #[inline]
+    pub fn current(&self) -> &'ast Scope<'ast> {
+        self.stack.last().expect("Can't leave the root scope").0
+    }
+
+    /// The innermost scope declaring `name`, if any.
+    pub fn resolve(&self, name: &'ast str) -> Option<&'ast Scope<'ast>> {
+        let mut scope = Some(self.current());
+
+        while let Some(current) = scope {
+            if current.declared_refs.contains_key(name) {
+                return Some(current);
+            }
+
+            scope = current.parent;
+        }
+
+        None
+    }
+}
+
 struct ScopeAnalyzer<'ast> {
     arena: &'ast Arena,
     pub current: CopyCell<&'ast Scope<'ast>>,
@@ -171,4 +230,44 @@ mod test {
         assert_eq!(moon.declared_refs.is_empty(), true);
         assert_eq!(moon.children.as_list().is_empty(), true);
     }
+
+    struct Resolver<'ast> {
+        cursor: ScopeCursor<'ast>,
+        resolved: Vec<(&'ast str, Option<&'ast Scope<'ast>>)>,
+    }
+
+    impl<'ast> Visitor<'ast> for Resolver<'ast> {
+        fn on_enter_scope(&mut self, _: ScopeKind) {
+            self.cursor.enter();
+        }
+
+        fn on_leave_scope(&mut self) {
+            self.cursor.leave();
+        }
+
+        fn on_reference_use(&mut self, ident: &Identifier<'ast>) {
+            self.resolved.push((*ident, self.cursor.resolve(ident)));
+        }
+    }
+
+    #[test]
+    fn scope_cursor() {
+        let module = parse("foo; function bar(foo) { foo; baz; { let baz; } } baz; var baz;").unwrap();
+        let root = analyze(&module);
+        let bar = *root.children.as_list().only_element().unwrap();
+        let mut resolver = Resolver {
+            cursor: ScopeCursor::new(root),
+            resolved: Vec::new(),
+        };
+
+        module.visit_with(&mut resolver);
+
+        assert_eq!(resolver.resolved, [
+            ("foo", None),
+            ("foo", Some(bar)),
+            ("baz", Some(root)),
+            ("baz", Some(root)),
+        ]);
+        assert_eq!(resolver.cursor.current(), root);
+    }
 }
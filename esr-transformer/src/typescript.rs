@@ -0,0 +1,836 @@
+use std::collections::{HashMap, HashSet};
+
+use esr::ast::{Node, NodeList, Block, Pattern, Function, OptionalName, Literal, OperatorKind, Identifier};
+use esr::ast::{Statement, StatementList, StatementNode, ExpressionNode, Expression, Declarator, PropertyNode};
+use esr::ast::expression::{BinaryExpression, PrefixExpression, CallExpression, ObjectExpression};
+use esr::ast::expression::{MemberExpression, ComputedMemberExpression, ArrayExpression, SpreadExpression};
+use esr::ast::expression::{Property, PropertyKey};
+use esr::ast::statement::{DeclarationStatement, ReturnStatement, ExportDeclaration};
+use esr::ast::types::{EnumDeclaration, NamespaceDeclaration};
+use esr::ast::DeclarationKind;
+use esr_visitor::{Visitor, Visitable, ScopeKind};
+
+use crate::TransformerCtxt;
+use crate::scope::{analyze_node, ScopeCursor};
+
+/// Constant value of an enum member. Strings keep their quotes.
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Value<'ast> {
+    Number(f64),
+    String(&'ast str),
+}
+
+type Members<'ast> = Vec<(&'ast str, Option<Value<'ast>>)>;
+
+fn unquote(name: &str) -> &str {
+    match name.as_bytes().first() {
+        Some(b'"') | Some(b'\'') => &name[1..name.len() - 1],
+        _                        => name,
+    }
+}
+
+#[inline]
+fn to_int32(value: f64) -> i32 {
+    if value.is_finite() {
+        value.trunc() as i64 as i32
+    } else {
+        0
+    }
+}
+
+fn evaluate<'ast>(expression: ExpressionNode<'ast>, enumeration: &str, members: &Members<'ast>) -> Option<Value<'ast>> {
+    use self::OperatorKind::*;
+
+    let lookup = |name: &str| members.iter().find(|(member, _)| *member == name).and_then(|(_, value)| *value);
+
+    match expression.item {
+        Expression::Literal(literal @ Literal::Number(_)) |
+        Expression::Literal(literal @ Literal::Binary(_)) => literal.number_value().map(Value::Number),
+        Expression::Literal(Literal::String(raw)) => Some(Value::String(raw)),
+        Expression::Identifier(name)              => lookup(name),
+        Expression::Member(MemberExpression { object, property }) => match object.item {
+            Expression::Identifier(name) if name == enumeration => lookup(property.item),
+            _ => None,
+        },
+        Expression::ComputedMember(ComputedMemberExpression { object, property }) => match (object.item, property.item) {
+            (Expression::Identifier(name), Expression::Literal(Literal::String(raw))) if name == enumeration => lookup(unquote(raw)),
+            _ => None,
+        },
+        Expression::Prefix(PrefixExpression { operator, operand }) => {
+            let value = match evaluate(operand, enumeration, members)? {
+                Value::Number(value) => value,
+                Value::String(_)     => return None,
+            };
+
+            match operator {
+                Addition    => Some(value),
+                Subtraction => Some(-value),
+                BitwiseNot  => Some(!to_int32(value) as f64),
+                _           => None,
+            }.map(Value::Number)
+        },
+        Expression::Binary(BinaryExpression { operator, left, right }) => {
+            let (left, right) = match (evaluate(left, enumeration, members)?, evaluate(right, enumeration, members)?) {
+                (Value::Number(left), Value::Number(right)) => (left, right),
+                _ => return None,
+            };
+
+            match operator {
+                Addition       => Some(left + right),
+                Subtraction    => Some(left - right),
+                Multiplication => Some(left * right),
+                Division       => Some(left / right),
+                Remainder      => Some(left % right),
+                Exponent       => Some(left.powf(right)),
+                BitShiftLeft   => Some(to_int32(left).wrapping_shl(to_int32(right) as u32 & 31) as f64),
+                BitShiftRight  => Some((to_int32(left) >> (to_int32(right) as u32 & 31)) as f64),
+                UBitShiftRight => Some(((to_int32(left) as u32) >> (to_int32(right) as u32 & 31)) as f64),
+                BitwiseAnd     => Some((to_int32(left) & to_int32(right)) as f64),
+                BitwiseXor     => Some((to_int32(left) ^ to_int32(right)) as f64),
+                BitwiseOr      => Some((to_int32(left) | to_int32(right)) as f64),
+                _              => None,
+            }.map(Value::Number)
+        },
+        _ => None,
+    }
+}
+
+/// Compute constant values of all members. Members without an initializer
+/// continue counting from the previous numeric member.
+fn evaluate_members<'ast>(enumeration: &EnumDeclaration<'ast>) -> Members<'ast> {
+    let mut members = Vec::new();
+    let mut next = Some(0.0);
+
+    for member in enumeration.members.iter() {
+        let value = match member.value {
+            Some(value) => evaluate(value, enumeration.name.item, &members),
+            None        => next.map(Value::Number),
+        };
+
+        next = match value {
+            Some(Value::Number(value)) => Some(value + 1.0),
+            _                          => None,
+        };
+
+        members.push((unquote(member.name.item), value));
+    }
+
+    members
+}
+
+/// Whether a namespace declares anything that exists at runtime.
+fn is_instantiated(namespace: &NamespaceDeclaration) -> bool {
+    namespace.body.body.iter().any(|statement| {
+        let statement = match statement.item {
+            Statement::Export(ExportDeclaration { declaration, .. }) => declaration,
+            _ => *statement,
+        };
+
+        match statement.item {
+            Statement::Empty                  => false,
+            Statement::Enum(ref enumeration)  => !enumeration.is_const,
+            Statement::Namespace(ref nested)  => is_instantiated(nested),
+            _                                 => !statement.is_type_declaration(),
+        }
+    })
+}
+
+/// Builds the expression for a constant value of an enum member.
+fn constant<'ast>(ctx: &TransformerCtxt<'ast>, value: Value<'ast>) -> ExpressionNode<'ast> {
+    match value {
+        Value::String(raw) => ctx.alloc(Literal::String(raw)),
+        Value::Number(value) if value.is_nan() => ctx.identifier_expression("NaN"),
+        Value::Number(value) if value.is_sign_negative() => ctx.alloc(PrefixExpression {
+            operator: OperatorKind::Subtraction,
+            operand: constant(ctx, Value::Number(-value)),
+        }),
+        Value::Number(value) if value.is_infinite() => ctx.identifier_expression("Infinity"),
+        Value::Number(value) => ctx.alloc(Literal::Number(ctx.arena.alloc_string(value.to_string()))),
+    }
+}
+
+/// Collects values of `const enum` members, keyed by the scope declaring
+/// the enum, so that they can be inlined.
+struct ConstEnums<'ast> {
+    cursor: ScopeCursor<'ast>,
+    enums: HashMap<(usize, &'ast str), Members<'ast>>,
+}
+
+impl<'ast> Visitor<'ast> for ConstEnums<'ast> {
+    fn on_enter_scope(&mut self, _: ScopeKind) {
+        self.cursor.enter();
+    }
+
+    fn on_leave_scope(&mut self) {
+        self.cursor.leave();
+    }
+
+    fn on_enum_statement(&mut self, item: &EnumDeclaration<'ast>, _: &'ast StatementNode<'ast>) {
+        if item.is_const {
+            self.enums.insert((self.cursor.current().as_usize(), item.name.item), evaluate_members(item));
+        }
+    }
+}
+
+/// Inlines members of `const enum`s at their use sites. Only references
+/// resolving to the enum itself are inlined, not bindings shadowing it.
+/// Has to run before `LowerTypeScript`, which removes the declarations.
+pub struct InlineConstEnums<'ast> {
+    ctx: TransformerCtxt<'ast>,
+    cursor: ScopeCursor<'ast>,
+    enums: HashMap<(usize, &'ast str), Members<'ast>>,
+}
+
+impl<'ast> InlineConstEnums<'ast> {
+    pub fn new<V>(ctx: TransformerCtxt<'ast>, root: &'ast V) -> InlineConstEnums<'ast> where
+        V: Visitable<'ast>,
+    {
+        let scope = analyze_node(ctx.arena, root);
+        let mut collector = ConstEnums {
+            cursor: ScopeCursor::new(scope),
+            enums: HashMap::new(),
+        };
+
+        root.visit_with(&mut collector);
+
+        InlineConstEnums {
+            ctx,
+            cursor: ScopeCursor::new(scope),
+            enums: collector.enums,
+        }
+    }
+
+    fn inline(&self, enumeration: &'ast str, member: &str, node: &'ast ExpressionNode<'ast>) {
+        let scope = match self.cursor.resolve(enumeration) {
+            Some(scope) => scope,
+            None        => return,
+        };
+
+        let value = self.enums
+            .get(&(scope.as_usize(), enumeration))
+            .and_then(|members| members.iter().find(|(name, _)| *name == member))
+            .and_then(|(_, value)| *value);
+
+        if let Some(value) = value {
+            self.ctx.swap(node, constant(&self.ctx, value).item);
+        }
+    }
+}
+
+impl<'ast> Visitor<'ast> for InlineConstEnums<'ast> {
+    fn on_enter_scope(&mut self, _: ScopeKind) {
+        self.cursor.enter();
+    }
+
+    fn on_leave_scope(&mut self) {
+        self.cursor.leave();
+    }
+
+    fn on_member_expression(&mut self, item: &MemberExpression<'ast>, node: &'ast ExpressionNode<'ast>) {
+        if let Expression::Identifier(enumeration) = item.object.item {
+            self.inline(enumeration, item.property.item, node);
+        }
+    }
+
+    fn on_computed_member_expression(&mut self, item: &ComputedMemberExpression<'ast>, node: &'ast ExpressionNode<'ast>) {
+        if let (Expression::Identifier(enumeration), Expression::Literal(Literal::String(raw))) = (item.object.item, item.property.item) {
+            self.inline(enumeration, unquote(raw), node);
+        }
+    }
+}
+
+/// Qualifies references to variables exported from a namespace, which
+/// only exist as members of the namespace object, `a = 2` to `Foo.a = 2`.
+struct QualifyExports<'ast, 'a> {
+    ctx: TransformerCtxt<'ast>,
+    cursor: ScopeCursor<'ast>,
+    namespace: &'ast str,
+    exported: &'a HashSet<&'ast str>,
+}
+
+impl<'ast, 'a> QualifyExports<'ast, 'a> {
+    /// Exported names aren't declared in the lowered body, so they only
+    /// resolve to a scope when shadowed.
+    fn is_exported(&self, name: &'ast str) -> bool {
+        self.exported.contains(name) && self.cursor.resolve(name).is_none()
+    }
+
+    fn member(&self, name: &'ast str) -> ExpressionNode<'ast> {
+        self.ctx.member_expression(self.ctx.identifier_expression(self.namespace), name)
+    }
+}
+
+impl<'ast, 'a> Visitor<'ast> for QualifyExports<'ast, 'a> {
+    fn on_enter_scope(&mut self, _: ScopeKind) {
+        self.cursor.enter();
+    }
+
+    fn on_leave_scope(&mut self) {
+        self.cursor.leave();
+    }
+
+    fn on_identifier_expression(&mut self, item: &Identifier<'ast>, node: &'ast ExpressionNode<'ast>) {
+        if self.is_exported(item) {
+            self.ctx.swap(node, self.member(item).item);
+        }
+    }
+
+    fn on_object_expression(&mut self, item: &ObjectExpression<'ast>, _: &'ast ExpressionNode<'ast>) {
+        for property in item.body.iter() {
+            if let Property::Shorthand(name) = property.item {
+                if self.is_exported(name.item) {
+                    self.ctx.swap(property, Property::Literal {
+                        key: self.ctx.alloc(PropertyKey::Literal(name.item)),
+                        value: self.member(name.item),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Lowers TypeScript enums and namespaces to plain JavaScript, using the
+/// same IIFE patterns as the TypeScript compiler. Declarations of `const
+/// enum`s are removed, their members are inlined by `InlineConstEnums`.
+pub struct LowerTypeScript<'ast> {
+    ctx: TransformerCtxt<'ast>,
+}
+
+impl<'ast> LowerTypeScript<'ast> {
+    pub fn new(ctx: TransformerCtxt<'ast>) -> LowerTypeScript<'ast> {
+        LowerTypeScript {
+            ctx,
+        }
+    }
+
+    fn identifier(&self, name: &'ast str) -> ExpressionNode<'ast> {
+        self.ctx.alloc(Expression::Identifier(name))
+    }
+
+    fn string(&self, name: &'ast str) -> ExpressionNode<'ast> {
+        let raw = match name.as_bytes().first() {
+            Some(b'"') | Some(b'\'') => name,
+            _                        => self.ctx.arena.alloc_string(format!("\"{}\"", name)),
+        };
+
+        self.ctx.alloc(Literal::String(raw))
+    }
+
+    fn binary(&self, operator: OperatorKind, left: ExpressionNode<'ast>, right: ExpressionNode<'ast>) -> ExpressionNode<'ast> {
+        self.ctx.alloc(BinaryExpression {
+            operator,
+            left,
+            right,
+        })
+    }
+
+    fn member(&self, object: &'ast str, property: &'ast str) -> ExpressionNode<'ast> {
+        self.ctx.alloc(MemberExpression {
+            object: self.identifier(object),
+            property: self.ctx.alloc(property),
+        })
+    }
+
+    fn computed_member(&self, object: &'ast str, property: ExpressionNode<'ast>) -> ExpressionNode<'ast> {
+        self.ctx.alloc(ComputedMemberExpression {
+            object: self.identifier(object),
+            property,
+        })
+    }
+
+    /// `Foo.bar = bar;`
+    fn export_assignment(&self, namespace: &'ast str, name: &'ast str) -> StatementNode<'ast> {
+        let assignment = self.binary(OperatorKind::Assign, self.member(namespace, name), self.identifier(name));
+
+        self.ctx.alloc(assignment)
+    }
+
+    /// Wraps the body in a function called with the object being extended,
+    /// `var Foo = function (Foo) { ...; return Foo; }(Foo || {});`, or when
+    /// the name is already declared, `(function (Foo) { ... })(Foo || (Foo = {}));`.
+    /// Objects exported from a namespace are also attached to the parent.
+    fn iife(&mut self, name: &'ast str, mut body: Vec<StatementNode<'ast>>, parent: Option<&'ast str>, merge: bool) -> Statement<'ast> {
+        let target = || match parent {
+            Some(parent) => self.member(parent, name),
+            None         => self.identifier(name),
+        };
+
+        let empty = self.ctx.alloc(ObjectExpression { body: NodeList::empty() });
+
+        let fallback = if merge || parent.is_some() {
+            self.binary(OperatorKind::Assign, target(), empty)
+        } else {
+            empty
+        };
+
+        let argument = self.binary(OperatorKind::LogicalOr, target(), fallback);
+
+        if !merge {
+            body.push(self.ctx.alloc(ReturnStatement {
+                value: Some(self.identifier(name)),
+            }));
+        }
+
+        let body = self.ctx.list(body);
+        let function = self.ctx.alloc(Function {
+            name: OptionalName(None),
+            generator: false,
+            type_parameters: NodeList::empty(),
+            params: NodeList::from(self.ctx.arena, self.ctx.alloc(Pattern::Identifier(self.ctx.alloc(name)))),
+            return_type: None,
+            body: self.ctx.alloc(Block { body }),
+        });
+
+        // Codegen parenthesizes the call when it starts a statement
+        let call = self.ctx.alloc(CallExpression {
+            callee: function,
+            arguments: NodeList::from(self.ctx.arena, argument),
+        });
+
+        if merge {
+            return Statement::Expression(call);
+        }
+
+        let declarator = self.ctx.alloc(Declarator {
+            id: self.ctx.alloc(Pattern::Identifier(self.ctx.alloc(name))),
+            init: Some(call),
+        });
+
+        DeclarationStatement {
+            kind: DeclarationKind::Var,
+            declarators: NodeList::from(self.ctx.arena, declarator),
+        }.into()
+    }
+
+    fn lower_enum(&mut self, enumeration: &EnumDeclaration<'ast>, parent: Option<&'ast str>, merge: bool) -> Statement<'ast> {
+        let name = enumeration.name.item;
+        let values = evaluate_members(enumeration);
+        let mut body = Vec::with_capacity(values.len());
+        let mut previous = None;
+
+        for (member, &(_, value)) in enumeration.members.iter().zip(values.iter()) {
+            let key = member.name.item;
+
+            let initializer = match (value, member.value) {
+                (Some(value), _)    => constant(&self.ctx, value),
+                (None, Some(value)) => value,
+                // Continue from a previous member that isn't a constant
+                (None, None) => match previous {
+                    Some(previous) => {
+                        let previous = self.computed_member(name, self.string(previous));
+                        let one = self.ctx.alloc(Literal::Number("1"));
+
+                        self.binary(OperatorKind::Addition, previous, one)
+                    },
+                    None => constant(&self.ctx, Value::Number(0.0)),
+                },
+            };
+
+            let assignment = self.binary(OperatorKind::Assign, self.computed_member(name, self.string(key)), initializer);
+
+            // String members don't get a reverse mapping
+            let statement = match value {
+                Some(Value::String(_)) => assignment,
+                _ => self.binary(OperatorKind::Assign, self.computed_member(name, assignment), self.string(key)),
+            };
+
+            body.push(self.ctx.alloc_as_loc(member, Statement::Expression(statement)));
+            previous = Some(key);
+        }
+
+        self.iife(name, body, parent, merge)
+    }
+
+    fn lower_namespace(&mut self, namespace: &NamespaceDeclaration<'ast>, parent: Option<&'ast str>, merge: bool) -> Statement<'ast> {
+        let name = namespace.name.item;
+        let mut declared = HashSet::new();
+        let mut exported = HashSet::new();
+        let mut body = Vec::new();
+
+        for statement in namespace.body.body.iter() {
+            match statement.item {
+                Statement::Export(ExportDeclaration { ref declaration, .. }) => {
+                    self.lower_export(name, declaration, &mut body, &mut declared, &mut exported);
+                },
+                _ => body.push(*statement),
+            }
+        }
+
+        if !exported.is_empty() {
+            let statements = self.ctx.arena.alloc(self.ctx.list(&body));
+            let scope = analyze_node(self.ctx.arena, statements);
+
+            statements.visit_with(&mut QualifyExports {
+                ctx: self.ctx,
+                cursor: ScopeCursor::new(scope),
+                namespace: name,
+                exported: &exported,
+            });
+        }
+
+        self.iife(name, body, parent, merge)
+    }
+
+    /// Lowers a declaration exported from a namespace, so that the exported
+    /// names are also assigned to the namespace object.
+    fn lower_export(
+        &mut self,
+        namespace: &'ast str,
+        declaration: &StatementNode<'ast>,
+        body: &mut Vec<StatementNode<'ast>>,
+        declared: &mut HashSet<&'ast str>,
+        exported: &mut HashSet<&'ast str>,
+    ) {
+        match declaration.item {
+            Statement::Declaration(ref variables) => {
+                // Exported variables only exist on the namespace object,
+                // `export const { a, b: [c] } = d` becomes `({ a: Foo.a, b: [Foo.c] } = d)`
+                for declarator in variables.declarators.iter() {
+                    let target = self.qualify_pattern(namespace, declarator.id, exported);
+
+                    if let Some(init) = declarator.init {
+                        let assignment = self.binary(OperatorKind::Assign, target, init);
+
+                        body.push(self.ctx.alloc_as_loc(declarator, Statement::Expression(assignment)));
+                    }
+                }
+            },
+            Statement::Function(ref function) => {
+                body.push(*declaration);
+                body.push(self.export_assignment(namespace, function.name.0.item));
+            },
+            Statement::Class(ref class) => {
+                body.push(*declaration);
+                body.push(self.export_assignment(namespace, class.name.0.item));
+            },
+            Statement::Enum(ref enumeration) if !enumeration.is_const => {
+                let merge = !declared.insert(enumeration.name.item);
+                let lowered = self.lower_enum(enumeration, Some(namespace), merge);
+
+                self.ctx.swap(declaration, lowered);
+                body.push(*declaration);
+            },
+            Statement::Namespace(ref nested) if is_instantiated(nested) => {
+                let merge = !declared.insert(nested.name.item);
+                let lowered = self.lower_namespace(nested, Some(namespace), merge);
+
+                self.ctx.swap(declaration, lowered);
+                body.push(*declaration);
+            },
+            Statement::Enum(_) | Statement::Namespace(_) => {},
+            _ if declaration.is_type_declaration() => {},
+            _ => body.push(*declaration),
+        }
+    }
+
+    /// Turns a declared pattern into an assignment target with every bound
+    /// name replaced by a member of the namespace, collecting the names.
+    fn qualify_pattern(&self, namespace: &'ast str, pattern: Node<'ast, Pattern<'ast>>, names: &mut HashSet<&'ast str>) -> ExpressionNode<'ast> {
+        match pattern.item {
+            Pattern::Void => self.ctx.alloc(Expression::Void),
+            Pattern::Identifier(name) => {
+                names.insert(name.item);
+
+                self.member(namespace, name.item)
+            },
+            Pattern::ObjectPattern { properties } => {
+                let body = properties.iter().map(|property| self.qualify_property(namespace, *property, names));
+
+                self.ctx.alloc(ObjectExpression { body: self.ctx.builder.list(body.collect::<Vec<_>>()) })
+            },
+            Pattern::ArrayPattern { elements } => {
+                let body = elements.iter().map(|element| self.qualify_pattern(namespace, *element, names));
+
+                self.ctx.alloc(ArrayExpression { body: self.ctx.builder.list(body.collect::<Vec<_>>()) })
+            },
+            Pattern::RestElement { argument } => {
+                names.insert(argument.item);
+
+                self.ctx.alloc(SpreadExpression { argument: self.member(namespace, argument.item) })
+            },
+            Pattern::AssignmentPattern { left, right } => {
+                self.binary(OperatorKind::Assign, self.qualify_pattern(namespace, left, names), right)
+            },
+            Pattern::Annotated { pattern, .. } => self.qualify_pattern(namespace, pattern, names),
+            Pattern::ParameterProperty { parameter, .. } => self.qualify_pattern(namespace, parameter, names),
+        }
+    }
+
+    /// Properties of object patterns hold their patterns as expressions.
+    fn qualify_property(&self, namespace: &'ast str, property: PropertyNode<'ast>, names: &mut HashSet<&'ast str>) -> PropertyNode<'ast> {
+        match property.item {
+            Property::Shorthand(name) => {
+                names.insert(name.item);
+
+                self.ctx.alloc(Property::Literal {
+                    key: self.ctx.alloc(PropertyKey::Literal(name.item)),
+                    value: self.member(namespace, name.item),
+                })
+            },
+            Property::Literal { key, value } => self.ctx.alloc(Property::Literal {
+                key,
+                value: self.qualify_expression(namespace, value, names),
+            }),
+            Property::Spread { argument } => self.ctx.alloc(Property::Spread {
+                argument: self.qualify_expression(namespace, argument, names),
+            }),
+            Property::Method { .. } => property,
+        }
+    }
+
+    fn qualify_expression(&self, namespace: &'ast str, expression: ExpressionNode<'ast>, names: &mut HashSet<&'ast str>) -> ExpressionNode<'ast> {
+        match expression.item {
+            Expression::Identifier(name) => {
+                names.insert(name);
+
+                self.member(namespace, name)
+            },
+            Expression::Object(ObjectExpression { body }) => {
+                let body = body.iter().map(|property| self.qualify_property(namespace, *property, names));
+
+                self.ctx.alloc(ObjectExpression { body: self.ctx.builder.list(body.collect::<Vec<_>>()) })
+            },
+            Expression::Array(ArrayExpression { body }) => {
+                let body = body.iter().map(|element| self.qualify_expression(namespace, *element, names));
+
+                self.ctx.alloc(ArrayExpression { body: self.ctx.builder.list(body.collect::<Vec<_>>()) })
+            },
+            Expression::Spread(SpreadExpression { argument }) => self.ctx.alloc(SpreadExpression {
+                argument: self.qualify_expression(namespace, argument, names),
+            }),
+            Expression::Binary(BinaryExpression { operator: OperatorKind::Assign, left, right }) => {
+                self.binary(OperatorKind::Assign, self.qualify_expression(namespace, left, names), right)
+            },
+            _ => expression,
+        }
+    }
+}
+
+impl<'ast> Visitor<'ast> for LowerTypeScript<'ast> {
+    fn on_statement_list(&mut self, body: StatementList<'ast>) {
+        let mut declared = HashSet::new();
+
+        for statement in body.iter() {
+            match statement.item {
+                Statement::Function(ref function) => {
+                    declared.insert(function.name.0.item);
+                },
+                Statement::Class(ref class) => {
+                    declared.insert(class.name.0.item);
+                },
+                Statement::Enum(ref enumeration) if enumeration.is_const => {
+                    self.ctx.swap(statement, Statement::Empty);
+                },
+                Statement::Enum(ref enumeration) => {
+                    let merge = !declared.insert(enumeration.name.item);
+                    let lowered = self.lower_enum(enumeration, None, merge);
+
+                    self.ctx.swap(statement, lowered);
+                },
+                Statement::Namespace(ref namespace) if is_instantiated(namespace) => {
+                    let merge = !declared.insert(namespace.name.item);
+                    let lowered = self.lower_namespace(namespace, None, merge);
+
+                    self.ctx.swap(statement, lowered);
+                },
+                Statement::Namespace(_) => {
+                    self.ctx.swap(statement, Statement::Empty);
+                },
+                _ => {},
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use esr::{parse, parse_with_options, ParseOptions};
+    use esr_codegen::codegen;
+    use crate::lower_typescript;
+
+    fn assert_lowered(typescript: &str, expected: &str) {
+        let options = ParseOptions { typescript: true, ..ParseOptions::default() };
+        let module = parse_with_options(typescript, options).unwrap();
+        let expected = parse_with_options(expected, options).unwrap();
+
+        lower_typescript(&module);
+
+        let lowered = codegen(&module, true);
+
+        assert_eq!(lowered, codegen(&expected, true));
+        assert!(parse(&lowered).is_ok(), "Lowered code must parse: {}", lowered);
+    }
+
+    #[test]
+    fn numeric_enums() {
+        assert_lowered(
+            "enum Color { Red, Green = 1 << 2, Blue }",
+            r#"var Color = function (Color) {
+                Color[Color["Red"] = 0] = "Red";
+                Color[Color["Green"] = 4] = "Green";
+                Color[Color["Blue"] = 5] = "Blue";
+                return Color;
+            }(Color || {});"#
+        );
+        assert_lowered(
+            "enum A { B = -1, C = B * 2, D = foo(), E }",
+            r#"var A = function (A) {
+                A[A["B"] = -1] = "B";
+                A[A["C"] = -2] = "C";
+                A[A["D"] = foo()] = "D";
+                A[A["E"] = A["D"] + 1] = "E";
+                return A;
+            }(A || {});"#
+        );
+    }
+
+    #[test]
+    fn string_enums() {
+        assert_lowered(
+            "enum Direction { Up = 'UP', 'down-left' = \"DOWN\", Zero = 0 }",
+            r#"var Direction = function (Direction) {
+                Direction["Up"] = 'UP';
+                Direction['down-left'] = "DOWN";
+                Direction[Direction["Zero"] = 0] = "Zero";
+                return Direction;
+            }(Direction || {});"#
+        );
+    }
+
+    #[test]
+    fn merged_enums() {
+        assert_lowered(
+            "enum A { B } enum A { C = 2 }",
+            r#"var A = function (A) {
+                A[A["B"] = 0] = "B";
+                return A;
+            }(A || {});
+            (function (A) {
+                A[A["C"] = 2] = "C";
+            })(A || (A = {}));"#
+        );
+    }
+
+    #[test]
+    fn const_enums() {
+        assert_lowered(
+            "const enum Flags { None, A = 1 << 0, B = 1 << 1, AB = A | B, Name = 'name' } let x = Flags.AB, y = Flags['B'], z = Flags.Name;",
+            "; let x = 3, y = 2, z = 'name';"
+        );
+        assert_lowered(
+            "declare const enum Sign { Minus = -1 } let x = a * Sign.Minus;",
+            "declare const enum Sign { Minus = -1 } let x = a * -1;"
+        );
+    }
+
+    #[test]
+    fn const_enums_are_resolved_through_scopes() {
+        assert_lowered(
+            "const enum E { A = 1 } function f(E) { return E.A; } const g = E => E['A']; E.A;",
+            "; function f(E) { return E.A; } const g = E => E['A']; 1;"
+        );
+        assert_lowered(
+            "function f() { const enum E { A = 1 } return E.A; } E.A;",
+            "function f() { ; return 1; } E.A;"
+        );
+        assert_lowered(
+            "const enum E { A = 1 } { let E = {}; E.A; } namespace N { E.A; }",
+            "; { let E = {}; E.A; } var N = function (N) { 1; return N; }(N || {});"
+        );
+    }
+
+    #[test]
+    fn namespaces() {
+        assert_lowered(
+            "namespace Foo { export const a = 1, b; const c = 2; export function d() {} export class E {} export interface F {} }",
+            r#"var Foo = function (Foo) {
+                Foo.a = 1;
+                const c = 2;
+                function d() {}
+                Foo.d = d;
+                class E {}
+                Foo.E = E;
+                return Foo;
+            }(Foo || {});"#
+        );
+        assert_lowered(
+            "namespace Types { export type A = string; } declare namespace Ambient { const a: number; }",
+            "; declare namespace Ambient { const a: number; }"
+        );
+    }
+
+    #[test]
+    fn nested_namespaces() {
+        assert_lowered(
+            "namespace A.B { export enum C { D } }",
+            r#"var A = function (A) {
+                var B = function (B) {
+                    var C = function (C) {
+                        C[C["D"] = 0] = "D";
+                        return C;
+                    }(B.C || (B.C = {}));
+                    return B;
+                }(A.B || (A.B = {}));
+                return A;
+            }(A || {});"#
+        );
+    }
+
+    #[test]
+    fn namespace_merged_with_class() {
+        assert_lowered(
+            "class A {} namespace A { export const b = 1; }",
+            r#"class A {}
+            (function (A) {
+                A.b = 1;
+            })(A || (A = {}));"#
+        );
+        assert_lowered(
+            "function A() {} namespace A { export function b() {} }",
+            r#"function A() {}
+            (function (A) {
+                function b() {}
+                A.b = b;
+            })(A || (A = {}));"#
+        );
+    }
+
+    #[test]
+    fn namespace_exports_destructuring() {
+        assert_lowered(
+            "namespace Foo { export const { a, b: [c, , ...d], e: f = 1 } = g, [h = 2] = i; }",
+            r#"var Foo = function (Foo) {
+                ({ a: Foo.a, b: [Foo.c, , ...Foo.d], e: Foo.f = 1 } = g);
+                [Foo.h = 2] = i;
+                return Foo;
+            }(Foo || {});"#
+        );
+    }
+
+    #[test]
+    fn namespace_references_to_exports() {
+        assert_lowered(
+            "namespace Foo { export var a = 1, b; a = 2; b++; let c = { a, b: a }; function d(a) { return a + b; } const e = b => b; }",
+            r#"var Foo = function (Foo) {
+                Foo.a = 1;
+                Foo.a = 2;
+                Foo.b++;
+                let c = { a: Foo.a, b: Foo.a };
+                function d(a) { return a + Foo.b; }
+                const e = b => b;
+                return Foo;
+            }(Foo || {});"#
+        );
+        assert_lowered(
+            "namespace A { export let b = 1; export namespace C { export const d = b; } }",
+            r#"var A = function (A) {
+                A.b = 1;
+                var C = function (C) {
+                    C.d = A.b;
+                    return C;
+                }(A.C || (A.C = {}));
+                return A;
+            }(A || {});"#
+        );
+    }
+}
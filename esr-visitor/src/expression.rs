@@ -1,7 +1,8 @@
 use esr::ast::{Identifier, Expression, ExpressionNode, StatementNode, Literal};
 use esr::ast::expression::*;
 
-use crate::{Visitor, Visitable, ParentNode};
+use crate::{Visitor, Visitable, ParentNode, ScopeKind};
+use crate::jump;
 
 
 impl<'ast> Visitable<'ast> for ExpressionNode<'ast> {
@@ -23,6 +24,10 @@ impl<'ast> Visitable<'ast> for ExpressionNode<'ast> {
                 visitor.on_this_expression(&self);
                 return;
             },
+            Super(_) => {
+                visitor.on_super_expression(self);
+                return;
+            },
             Identifier(ref ident) => {
                 visitor.on_identifier_expression(ident, self);
                 visitor.push_parent(ParentNode::from(self));
@@ -37,35 +42,28 @@ impl<'ast> Visitable<'ast> for ExpressionNode<'ast> {
                 visitor.push_parent(ParentNode::from(self));
                 sequence.visit_with(visitor);
             },
+            Parenthesized(ref parenthesized) => {
+                visitor.on_parenthesized_expression(parenthesized, self);
+                visitor.push_parent(ParentNode::from(self));
+                parenthesized.visit_with(visitor);
+            },
             Array(ref array) => {
                 visitor.on_array_expression(array, self);
                 visitor.push_parent(ParentNode::from(self));
                 array.visit_with(visitor);
             },
-            Member(ref member) => {
-                visitor.on_member_expression(member, self);
-                visitor.push_parent(ParentNode::from(self));
-                member.visit_with(visitor);
-            },
-            ComputedMember(ref computed) => {
-                visitor.on_computed_member_expression(computed, self);
-                visitor.push_parent(ParentNode::from(self));
-                computed.visit_with(visitor);
+            Member(_) | ComputedMember(_) => {
+                visit_chain(self, visitor);
+                return;
             },
             MetaProperty(ref property) => {
                 visitor.on_meta_property(property, self);
                 visitor.push_parent(ParentNode::from(self));
                 property.visit_with(visitor);
             },
-            Call(ref call) => {
-                visitor.on_call_expression(call, self);
-                visitor.push_parent(ParentNode::from(self));
-                call.visit_with(visitor);
-            },
-            Binary(ref binary) => {
-                visitor.on_binary_expression(binary, self);
-                visitor.push_parent(ParentNode::from(self));
-                binary.visit_with(visitor);
+            Call(_) | Binary(_) => {
+                visit_chain(self, visitor);
+                return;
             },
             Prefix(ref prefix) => {
                 visitor.on_prefix_expression(prefix, self);
@@ -97,6 +95,11 @@ impl<'ast> Visitable<'ast> for ExpressionNode<'ast> {
                 visitor.push_parent(ParentNode::from(self));
                 spread.visit_with(visitor);
             },
+            Await(ref await_expression) => {
+                visitor.on_await_expression(await_expression, self);
+                visitor.push_parent(ParentNode::from(self));
+                await_expression.visit_with(visitor);
+            },
             Arrow(ref arrow) => {
                 visitor.on_arrow_expression(arrow, self);
                 visitor.push_parent(ParentNode::from(self));
@@ -116,12 +119,89 @@ impl<'ast> Visitable<'ast> for ExpressionNode<'ast> {
                 visitor.on_class_expression(class, self);
                 visitor.push_parent(ParentNode::from(self));
                 class.visit_with(visitor);
+            },
+            JSXElement(ref element) => {
+                visitor.push_parent(ParentNode::from(self));
+                element.visit_with(visitor);
+            },
+            JSXFragment(ref fragment) => {
+                visitor.push_parent(ParentNode::from(self));
+                fragment.visit_with(visitor);
+            },
+            TypeCast(ref cast) => {
+                visitor.push_parent(ParentNode::from(self));
+                cast.expression.visit_with(visitor);
+                visitor.pop_parent();
+                visitor.on_type_cast_expression(cast, self);
+                return;
+            },
+            // Types are erased, only the wrapped expressions are visited
+            As(AsExpression { ref expression, .. })                       |
+            Satisfies(SatisfiesExpression { ref expression, .. })         |
+            NonNull(NonNullExpression { ref expression })                 |
+            TypeAssertion(self::TypeAssertion { ref expression, .. })     |
+            Instantiation(InstantiationExpression { ref expression, .. }) => {
+                visitor.push_parent(ParentNode::from(self));
+                expression.visit_with(visitor);
             }
         }
         visitor.pop_parent();
     }
 }
 
+/// Visit a chain of binary operators, member accesses and calls, such as
+/// `a.b(c) + d`, in a loop, since such chains can be too long to recurse
+/// into. Links are entered from the outermost in, and left from the
+/// innermost out, just like they would be if visited recursively.
+fn visit_chain<'ast, V>(node: &'ast ExpressionNode<'ast>, visitor: &mut V)
+where
+    V: Visitor<'ast>,
+{
+    use self::Expression::*;
+
+    let mut links = Vec::new();
+    let mut node = node;
+
+    loop {
+        let next = match node.item {
+            Member(ref member) => {
+                visitor.on_member_expression(member, node);
+                &member.object
+            },
+            ComputedMember(ref computed) => {
+                visitor.on_computed_member_expression(computed, node);
+                &computed.object
+            },
+            Call(ref call) => {
+                visitor.on_call_expression(call, node);
+                &call.callee
+            },
+            Binary(ref binary) => {
+                visitor.on_binary_expression(binary, node);
+                &binary.left
+            },
+            _ => break,
+        };
+
+        visitor.push_parent(ParentNode::from(node));
+        links.push(node);
+        node = next;
+    }
+
+    node.visit_with(visitor);
+
+    for link in links.iter().rev() {
+        match link.item {
+            ComputedMember(ref computed) => computed.property.visit_with(visitor),
+            Call(ref call)               => call.arguments.visit_with(visitor),
+            Binary(ref binary)           => binary.right.visit_with(visitor),
+            _                            => {},
+        }
+
+        visitor.pop_parent();
+    }
+}
+
 impl<'ast> Visitable<'ast> for ThisExpression {
     type Parent = ExpressionNode<'ast>;
 
@@ -129,6 +209,13 @@ impl<'ast> Visitable<'ast> for ThisExpression {
     fn visit_with<V: Visitor<'ast>>(&self, _: &mut V) {}
 }
 
+impl<'ast> Visitable<'ast> for SuperExpression {
+    type Parent = ExpressionNode<'ast>;
+
+    #[inline]
+    fn visit_with<V: Visitor<'ast>>(&self, _: &mut V) {}
+}
+
 impl<'ast> Visitable<'ast> for Identifier<'ast> {
     type Parent = ExpressionNode<'ast>;
 
@@ -157,6 +244,18 @@ impl<'ast> Visitable<'ast> for SequenceExpression<'ast> {
     }
 }
 
+impl<'ast> Visitable<'ast> for ParenthesizedExpression<'ast> {
+    type Parent = ExpressionNode<'ast>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        self.expression.visit_with(visitor);
+    }
+}
+
 impl<'ast> Visitable<'ast> for ArrayExpression<'ast> {
     type Parent = ExpressionNode<'ast>;
 
@@ -308,6 +407,18 @@ impl<'ast> Visitable<'ast> for SpreadExpression<'ast> {
     }
 }
 
+impl<'ast> Visitable<'ast> for AwaitExpression<'ast> {
+    type Parent = ExpressionNode<'ast>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        self.argument.visit_with(visitor);
+    }
+}
+
 impl<'ast> Visitable<'ast> for ArrowBody<'ast> {
     type Parent = ExpressionNode<'ast>;
 
@@ -318,7 +429,11 @@ impl<'ast> Visitable<'ast> for ArrowBody<'ast> {
     {
         match *self {
             ArrowBody::Expression(ref expression) => expression.visit_with(visitor),
-            ArrowBody::Block(ref block)           => block.body.visit_with(visitor),
+            ArrowBody::Block(ref block)           => {
+                jump::enter_function(visitor);
+                block.body.visit_with(visitor);
+                jump::leave(visitor);
+            },
         }
     }
 }
@@ -331,8 +446,10 @@ impl<'ast> Visitable<'ast> for ArrowExpression<'ast> {
     where
         V: Visitor<'ast>,
     {
+        visitor.on_enter_scope(ScopeKind::Function);
         self.params.visit_with(visitor);
         self.body.visit_with(visitor);
+        visitor.on_leave_scope();
     }
 }
 
@@ -1,7 +1,8 @@
-use esr::ast::{Function, Class, ClassMember, Name, EmptyName, OptionalName, MandatoryName};
+use esr::ast::{Function, Class, ClassMember, Name, EmptyName, OptionalName, MandatoryName, Decorator};
 use esr::ast::{Node, ExpressionNode, StatementNode};
 
 use crate::{Visitable, Visitor, ScopeKind, NoParent};
+use crate::jump;
 
 
 impl<'ast> Visitable<'ast> for EmptyName {
@@ -52,8 +53,10 @@ where
         // Call visit on the StatementList instead of BlockNode since we
         // need to make sure that function parameters end up inside the block
         visitor.on_enter_scope(ScopeKind::Function);
+        jump::enter_function(visitor);
         self.params.visit_with(visitor);
         self.body.body.visit_with(visitor);
+        jump::leave(visitor);
         visitor.on_leave_scope();
     }
 }
@@ -86,10 +89,45 @@ impl<'ast> Visitable<'ast> for ClassMember<'ast> {
                 key.visit_with(visitor);
                 value.visit_with(visitor);
             },
+            Property {
+                ref key,
+                ref value,
+                ..
+            } |
+            Accessor {
+                ref key,
+                ref value,
+                ..
+            } => {
+                key.visit_with(visitor);
+                value.visit_with(visitor);
+            },
+            Signature { ref key, .. } => {
+                key.visit_with(visitor);
+            },
+            Modified { ref member, .. } => {
+                member.visit_with(visitor);
+            },
+            Decorated { ref decorators, ref member } => {
+                decorators.visit_with(visitor);
+                member.visit_with(visitor);
+            },
         }
     }
 }
 
+impl<'ast> Visitable<'ast> for Decorator<'ast> {
+    type Parent = Node<'ast, Self>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        self.expression.visit_with(visitor);
+    }
+}
+
 impl<'ast, N> Visitable<'ast> for Class<'ast, N>
 where
     N: Visitable<'ast> + Name<'ast>,
@@ -101,6 +139,7 @@ where
     where
         V: Visitor<'ast>,
     {
+        self.decorators.visit_with(visitor);
         self.name.visit_with(visitor);
         self.extends.visit_with(visitor);
         self.body.body.visit_with(visitor);
@@ -0,0 +1,140 @@
+use esr::ast::{ExpressionNode, Node};
+use esr::ast::jsx::*;
+
+use crate::{Visitor, Visitable};
+
+
+impl<'ast> Visitable<'ast> for JSXElementName<'ast> {
+    type Parent = Node<'ast, Self>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        match *self {
+            // Lowercase names are intrinsic elements, such as `div`
+            JSXElementName::Identifier(ref name) => {
+                if !name.starts_with(|ch: char| ch.is_ascii_lowercase()) {
+                    visitor.on_reference_use(name);
+                }
+            },
+            JSXElementName::Namespaced { .. } => {},
+            JSXElementName::Member(ref member) => {
+                visitor.on_jsx_member_expression(member);
+                member.visit_with(visitor);
+            },
+        }
+    }
+}
+
+impl<'ast> Visitable<'ast> for JSXMemberExpression<'ast> {
+    type Parent = Node<'ast, JSXElementName<'ast>>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        match self.object.item {
+            JSXElementName::Identifier(ref name) => visitor.on_reference_use(name),
+            _                                    => self.object.visit_with(visitor),
+        }
+    }
+}
+
+impl<'ast> Visitable<'ast> for JSXAttributeValue<'ast> {
+    type Parent = Node<'ast, Self>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        match *self {
+            JSXAttributeValue::String(_)               => {},
+            JSXAttributeValue::Expression(ref value)   => value.visit_with(visitor),
+            JSXAttributeValue::Element(ref element)    => element.visit_with(visitor),
+            JSXAttributeValue::Fragment(ref fragment)  => fragment.visit_with(visitor),
+        }
+    }
+}
+
+impl<'ast> Visitable<'ast> for JSXAttributeItem<'ast> {
+    type Parent = Node<'ast, Self>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        match *self {
+            JSXAttributeItem::Attribute(ref attribute) => {
+                visitor.on_jsx_attribute(attribute);
+                attribute.value.visit_with(visitor);
+            },
+            JSXAttributeItem::Spread(ref attribute) => {
+                visitor.on_jsx_spread_attribute(attribute);
+                attribute.argument.visit_with(visitor);
+            },
+        }
+    }
+}
+
+impl<'ast> Visitable<'ast> for JSXExpressionContainer<'ast> {
+    type Parent = Node<'ast, JSXChild<'ast>>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        visitor.on_jsx_expression_container(self);
+        self.expression.visit_with(visitor);
+    }
+}
+
+impl<'ast> Visitable<'ast> for JSXChild<'ast> {
+    type Parent = Node<'ast, Self>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        match *self {
+            JSXChild::Text(_)                 => {},
+            JSXChild::Expression(ref value)   => value.visit_with(visitor),
+            JSXChild::Element(ref element)    => element.visit_with(visitor),
+            JSXChild::Fragment(ref fragment)  => fragment.visit_with(visitor),
+        }
+    }
+}
+
+impl<'ast> Visitable<'ast> for JSXElement<'ast> {
+    type Parent = ExpressionNode<'ast>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        visitor.on_jsx_element(self);
+        self.opening.name.visit_with(visitor);
+        self.opening.attributes.visit_with(visitor);
+        self.children.visit_with(visitor);
+    }
+}
+
+impl<'ast> Visitable<'ast> for JSXFragment<'ast> {
+    type Parent = ExpressionNode<'ast>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        visitor.on_jsx_fragment(self);
+        self.children.visit_with(visitor);
+    }
+}
@@ -0,0 +1,121 @@
+use esr::ast::{Statement, StatementNode};
+
+use crate::Visitor;
+
+
+#[derive(Debug, Clone, Copy)]
+enum JumpTarget<'ast> {
+    /// Jumps never cross function boundaries
+    Function,
+    Labeled(&'ast str, &'ast StatementNode<'ast>),
+    Loop(&'ast StatementNode<'ast>),
+    Switch(&'ast StatementNode<'ast>),
+}
+
+/// Statements enclosing the one being visited, that a `break` or `continue`
+/// can jump to. Visitors exposing them through `Visitor::jump_targets` get
+/// every jump resolved to its target statement.
+#[derive(Debug, Default)]
+pub struct JumpTargets<'ast> {
+    stack: Vec<JumpTarget<'ast>>,
+}
+
+impl<'ast> JumpTargets<'ast> {
+    pub fn new() -> Self {
+        JumpTargets::default()
+    }
+
+    /// Statement a `break` with an optional label jumps out of. That is the
+    /// labeled statement itself for labeled jumps, or the innermost loop or
+    /// `switch` otherwise.
+    pub fn break_target(&self, label: Option<&str>) -> Option<&'ast StatementNode<'ast>> {
+        let scope = self.stack.iter().rev().take_while(|target| !matches!(target, JumpTarget::Function));
+
+        for target in scope {
+            match (*target, label) {
+                (JumpTarget::Labeled(name, node), Some(label)) if name == label => return Some(node),
+                (JumpTarget::Loop(node), None) |
+                (JumpTarget::Switch(node), None) => return Some(node),
+                _ => {},
+            }
+        }
+
+        None
+    }
+
+    /// Loop a `continue` with an optional label jumps to the next iteration of.
+    /// For labeled jumps that is the body of the labeled statement, which
+    /// has to be a loop.
+    pub fn continue_target(&self, label: Option<&str>) -> Option<&'ast StatementNode<'ast>> {
+        let scope = self.stack.iter().rev().take_while(|target| !matches!(target, JumpTarget::Function));
+
+        for target in scope {
+            match (*target, label) {
+                (JumpTarget::Labeled(name, node), Some(label)) if name == label => {
+                    return labeled_loop(node);
+                },
+                (JumpTarget::Loop(node), None) => return Some(node),
+                _ => {},
+            }
+        }
+
+        None
+    }
+
+    fn enter(&mut self, node: &'ast StatementNode<'ast>) -> bool {
+        let target = match node.item {
+            Statement::Labeled(ref labeled) => JumpTarget::Labeled(labeled.label.item.as_str(), node),
+            Statement::While(_)  |
+            Statement::Do(_)     |
+            Statement::For(_)    |
+            Statement::ForIn(_)  |
+            Statement::ForOf(_)  => JumpTarget::Loop(node),
+            Statement::Switch(_) => JumpTarget::Switch(node),
+            _                    => return false,
+        };
+
+        self.stack.push(target);
+
+        true
+    }
+}
+
+/// Loop labeled by a labeled statement, seeing through chained labels.
+fn labeled_loop<'ast>(mut node: &'ast StatementNode<'ast>) -> Option<&'ast StatementNode<'ast>> {
+    while let Statement::Labeled(ref labeled) = node.item {
+        node = &labeled.body;
+    }
+
+    match node.item {
+        Statement::While(_) |
+        Statement::Do(_)    |
+        Statement::For(_)   |
+        Statement::ForIn(_) |
+        Statement::ForOf(_) => Some(node),
+        _                   => None,
+    }
+}
+
+/// Push a statement to the jump targets of the visitor, if it is one.
+/// Returns whether it was pushed and has to be left afterwards.
+#[inline]
+pub(crate) fn enter_statement<'ast, V: Visitor<'ast>>(visitor: &mut V, node: &'ast StatementNode<'ast>) -> bool {
+    match visitor.jump_targets() {
+        Some(targets) => targets.enter(node),
+        None          => false,
+    }
+}
+
+#[inline]
+pub(crate) fn enter_function<'ast, V: Visitor<'ast>>(visitor: &mut V) {
+    if let Some(targets) = visitor.jump_targets() {
+        targets.stack.push(JumpTarget::Function);
+    }
+}
+
+#[inline]
+pub(crate) fn leave<'ast, V: Visitor<'ast>>(visitor: &mut V) {
+    if let Some(targets) = visitor.jump_targets() {
+        targets.stack.pop();
+    }
+}
@@ -5,14 +5,20 @@ extern crate pretty_assertions;
 
 use esr::ast::expression::*;
 use esr::ast::statement::*;
+use esr::ast::jsx::*;
+use esr::ast::types::{EnumDeclaration, NamespaceDeclaration};
 use esr::ast::{ExpressionList, ExpressionNode, StatementList, StatementNode};
-use esr::ast::{Identifier, Literal, Node, NodeList, Pattern};
+use esr::ast::{Identifier, Literal, Node, NodeList, Pattern, Expression, OperatorKind};
 
 use esr::Module;
 
 mod expression;
 mod function;
 mod statement;
+mod jsx;
+mod jump;
+
+pub use crate::jump::JumpTargets;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ScopeKind {
@@ -45,11 +51,23 @@ pub trait Visitor<'ast> {
     /// A reference has been declared within the current scope
     fn on_reference_declaration(&mut self, ident: &Identifier<'ast>) {}
 
+    /// Statements that jumps can target, kept up to date while visiting.
+    /// Unless this returns `Some`, jumps are not resolved.
+    fn jump_targets(&mut self) -> Option<&mut JumpTargets<'ast>> { None }
+
+    /// A `break` statement has been resolved to the statement it jumps out of
+    fn on_break_target(&mut self, node: &'ast StatementNode<'ast>, target: &'ast StatementNode<'ast>) {}
+
+    /// A `continue` statement has been resolved to the loop it continues
+    fn on_continue_target(&mut self, node: &'ast StatementNode<'ast>, target: &'ast StatementNode<'ast>) {}
+
     // expressions
     fn on_this_expression(&mut self, node: &'ast ExpressionNode<'ast>) {}
+    fn on_super_expression(&mut self, node: &'ast ExpressionNode<'ast>) {}
     fn on_identifier_expression(&mut self, item: &Identifier<'ast>, node: &'ast ExpressionNode<'ast>) {}
     fn on_literal_expression(&mut self, item: &Literal<'ast>, node: &'ast ExpressionNode<'ast>) {}
     fn on_sequence_expression(&mut self, item: &SequenceExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
+    fn on_parenthesized_expression(&mut self, item: &ParenthesizedExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
     fn on_array_expression(&mut self, item: &ArrayExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
     fn on_member_expression(&mut self, item: &MemberExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
     fn on_computed_member_expression(&mut self, item: &ComputedMemberExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
@@ -62,10 +80,13 @@ pub trait Visitor<'ast> {
     fn on_template_literal(&mut self, item: &TemplateLiteral<'ast>, node: &'ast ExpressionNode<'ast>) {}
     fn on_tagged_template_expression(&mut self, item: &TaggedTemplateExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
     fn on_spread_expression(&mut self, item: &SpreadExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
+    fn on_await_expression(&mut self, item: &AwaitExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
     fn on_arrow_expression(&mut self, item: &ArrowExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
     fn on_object_expression(&mut self, item: &ObjectExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
     fn on_function_expression(&mut self, item: &FunctionExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
     fn on_class_expression(&mut self, item: &ClassExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
+    /// Called once the expression being cast has been visited
+    fn on_type_cast_expression(&mut self, item: &TypeCastExpression<'ast>, node: &'ast ExpressionNode<'ast>) {}
 
     // statements
     fn on_expression_statement(&mut self, item: &'ast ExpressionNode<'ast>, node: &'ast StatementNode<'ast>) {}
@@ -86,6 +107,16 @@ pub trait Visitor<'ast> {
     fn on_switch_statement(&mut self, item: &SwitchStatement<'_>, node: &'ast StatementNode<'ast>) {}
     fn on_function_statement(&mut self, item: &FunctionStatement<'ast>, node: &'ast StatementNode<'ast>) {}
     fn on_class_statement(&mut self, item:&ClassStatement<'ast>, node: &'ast StatementNode<'ast>) {}
+    fn on_enum_statement(&mut self, item: &EnumDeclaration<'ast>, node: &'ast StatementNode<'ast>) {}
+    fn on_namespace_statement(&mut self, item: &NamespaceDeclaration<'ast>, node: &'ast StatementNode<'ast>) {}
+
+    // jsx, elements and fragments are also visited when nested in other elements
+    fn on_jsx_element(&mut self, item: &JSXElement<'ast>) {}
+    fn on_jsx_fragment(&mut self, item: &JSXFragment<'ast>) {}
+    fn on_jsx_attribute(&mut self, item: &JSXAttribute<'ast>) {}
+    fn on_jsx_spread_attribute(&mut self, item: &JSXSpreadAttribute<'ast>) {}
+    fn on_jsx_expression_container(&mut self, item: &JSXExpressionContainer<'ast>) {}
+    fn on_jsx_member_expression(&mut self, item: &JSXMemberExpression<'ast>) {}
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -125,6 +156,8 @@ impl<'ast> Visitable<'ast> for Module<'ast> {
         V: Visitor<'ast>,
     {
         let body = self.body();
+
+        visitor.on_statement_list(body);
         for item in body {
             item.visit_with(visitor);
         }
@@ -143,14 +176,14 @@ impl<'ast> Visitable<'ast> for Pattern<'ast> {
             Pattern::Void => {}
             Pattern::Identifier(ref ident) => visitor.on_reference_declaration(ident),
             Pattern::ObjectPattern { ref properties } => {
-                properties.visit_with(visitor);
+                for property in properties.iter() {
+                    visit_binding_property(property, visitor);
+                }
             }
             Pattern::ArrayPattern { ref elements } => {
                 elements.visit_with(visitor);
             }
-            Pattern::RestElement { ref argument } => {
-                argument.visit_with(visitor);
-            }
+            Pattern::RestElement { ref argument } => visitor.on_reference_declaration(&argument.item),
             Pattern::AssignmentPattern {
                 ref left,
                 ref right,
@@ -158,7 +191,57 @@ impl<'ast> Visitable<'ast> for Pattern<'ast> {
                 left.visit_with(visitor);
                 right.visit_with(visitor);
             }
+            Pattern::Annotated { ref pattern, .. } => {
+                pattern.visit_with(visitor);
+            }
+            Pattern::ParameterProperty { ref parameter, .. } => {
+                parameter.visit_with(visitor);
+            }
+        }
+    }
+}
+
+/// Visit a property of an object pattern, the values of which are
+/// expressions standing for the patterns.
+fn visit_binding_property<'ast, V>(property: &'ast Node<'ast, Property<'ast>>, visitor: &mut V)
+where
+    V: Visitor<'ast>,
+{
+    match property.item {
+        Property::Shorthand(ref ident) => visitor.on_reference_declaration(ident),
+        Property::Literal { ref key, ref value } => {
+            key.visit_with(visitor);
+            visit_binding(value, visitor);
+        }
+        Property::Spread { ref argument } => visit_binding(argument, visitor),
+        Property::Method { .. } => property.visit_with(visitor),
+    }
+}
+
+/// Visit an expression standing for a pattern, the identifiers in which
+/// are declarations rather than uses.
+fn visit_binding<'ast, V>(target: &'ast ExpressionNode<'ast>, visitor: &mut V)
+where
+    V: Visitor<'ast>,
+{
+    match target.item {
+        Expression::Identifier(ref ident) => visitor.on_reference_declaration(ident),
+        Expression::Object(ObjectExpression { ref body }) => {
+            for property in body.iter() {
+                visit_binding_property(property, visitor);
+            }
+        }
+        Expression::Array(ArrayExpression { ref body }) => {
+            for element in body.iter() {
+                visit_binding(element, visitor);
+            }
+        }
+        Expression::Spread(SpreadExpression { ref argument }) => visit_binding(argument, visitor),
+        Expression::Binary(BinaryExpression { operator: OperatorKind::Assign, ref left, ref right }) => {
+            visit_binding(left, visitor);
+            right.visit_with(visitor);
         }
+        _ => target.visit_with(visitor),
     }
 }
 
@@ -285,7 +368,7 @@ impl<'ast> Visitable<'ast> for StatementList<'ast> {
 #[cfg(test)]
 mod test {
     use super::*;
-    use esr::parse;
+    use esr::{parse, parse_with_options, ParseOptions};
     use crate::ScopeKind::*;
 
     struct ScopeTest<'ast> {
@@ -404,6 +487,35 @@ mod test {
         assert_eq!(visitor.declared_vars, &[]);
     }
 
+    #[test]
+    fn arrow_functions_have_their_own_scope() {
+        let module = parse("foo => { let bar; }; baz => baz;").unwrap();
+        let mut visitor = ScopeTest::new();
+
+        module.visit_with(&mut visitor);
+
+        assert_eq!(visitor.scopes, &[Function, Function]);
+        assert_eq!(visitor.depth, 0);
+        assert_eq!(visitor.max_depth, 1);
+        assert_eq!(visitor.used_vars, &[("baz", 1)]);
+        assert_eq!(visitor.declared_vars, &[("foo", 1), ("bar", 1), ("baz", 1)]);
+    }
+
+    #[test]
+    fn destructured_names_are_declarations() {
+        let module = parse("let { a, b: [c, ...d], e: f = g, ...h } = i;").unwrap();
+        let mut visitor = ScopeTest::new();
+
+        module.visit_with(&mut visitor);
+
+        assert_eq!(visitor.scopes, &[]);
+        assert_eq!(visitor.used_vars, &[("g", 0), ("i", 0)]);
+        assert_eq!(
+            visitor.declared_vars,
+            &[("a", 0), ("c", 0), ("d", 0), ("f", 0), ("h", 0)]
+        );
+    }
+
     #[test]
     fn function_and_class_are_declarations() {
         let module = parse("function foo() {} class Bar {}").unwrap();
@@ -504,6 +616,83 @@ mod test {
         );
     }
 
+    #[test]
+    fn jsx_component_names_are_uses() {
+        let options = ParseOptions { jsx: true, ..ParseOptions::default() };
+        let module = parse_with_options(
+            "<Foo.Bar x={y} {...z}><div class='a'>{w}</div><Baz/><svg:rect/></Foo.Bar>",
+            options,
+        ).unwrap();
+
+        let mut visitor = ScopeTest::new();
+
+        module.visit_with(&mut visitor);
+
+        assert_eq!(visitor.scopes, &[]);
+        assert_eq!(
+            visitor.used_vars,
+            &[("Foo", 0), ("y", 0), ("z", 0), ("w", 0), ("Baz", 0)]
+        );
+        assert_eq!(visitor.declared_vars, &[]);
+    }
+
+    #[test]
+    fn typescript_types_are_not_visited() {
+        let options = ParseOptions { typescript: true, ..ParseOptions::default() };
+        let module = parse_with_options(
+            "type Foo = Bar; function doge(to: Foo): Baz { return to! as Moon; }",
+            options,
+        ).unwrap();
+
+        let mut visitor = ScopeTest::new();
+
+        module.visit_with(&mut visitor);
+
+        assert_eq!(visitor.scopes, &[Function]);
+        assert_eq!(visitor.used_vars, &[("to", 1)]);
+        assert_eq!(visitor.declared_vars, &[("doge", 0), ("to", 1)]);
+    }
+
+    #[derive(Default)]
+    struct JSXTest {
+        elements: u32,
+        fragments: u32,
+        attributes: u32,
+        containers: u32,
+    }
+
+    impl<'ast> Visitor<'ast> for JSXTest {
+        fn on_jsx_element(&mut self, _: &JSXElement<'ast>) {
+            self.elements += 1;
+        }
+
+        fn on_jsx_fragment(&mut self, _: &JSXFragment<'ast>) {
+            self.fragments += 1;
+        }
+
+        fn on_jsx_attribute(&mut self, _: &JSXAttribute<'ast>) {
+            self.attributes += 1;
+        }
+
+        fn on_jsx_expression_container(&mut self, _: &JSXExpressionContainer<'ast>) {
+            self.containers += 1;
+        }
+    }
+
+    #[test]
+    fn visits_nested_jsx() {
+        let options = ParseOptions { jsx: true, ..ParseOptions::default() };
+        let module = parse_with_options("<><a b={<c/>} d>{<></>}{}</a></>", options).unwrap();
+        let mut visitor = JSXTest::default();
+
+        module.visit_with(&mut visitor);
+
+        assert_eq!(visitor.elements, 2);
+        assert_eq!(visitor.fragments, 2);
+        assert_eq!(visitor.attributes, 2);
+        assert_eq!(visitor.containers, 3);
+    }
+
     struct ParentsTest<'ast> {
         count: u32,
         parents: Vec<ParentNode<'ast>>,
@@ -541,4 +730,59 @@ mod test {
         assert_eq!(visitor.count, 5);
         assert_eq!(visitor.parents.len(), 0);
     }
+
+    #[test]
+    fn visits_tree_at_max_depth() {
+        // The deepest nesting the parser accepts by default, with a long
+        // chain at the bottom, which doesn't count towards the depth.
+        let chain = format!("a{}", ".b(c)[d]+e".repeat(10_000));
+        let source = format!("{}{}{};", "[".repeat(1019), chain, "]".repeat(1019));
+        let module = parse(&source).unwrap();
+        let mut visitor = ParentsTest::new();
+
+        module.visit_with(&mut visitor);
+
+        // 1 ExpressionStatement + 1019 ArrayExpressions + 4 links and 3 used
+        // Identifiers in every repetition + the leading Identifier
+        assert_eq!(visitor.count, 1 + 1019 + 7 * 10_000 + 1);
+        assert_eq!(visitor.parents.len(), 0);
+    }
+
+    struct JumpTest<'ast> {
+        targets: JumpTargets<'ast>,
+        jumps: Vec<(&'static str, u32, u32)>,
+    }
+
+    impl<'ast> Visitor<'ast> for JumpTest<'ast> {
+        fn jump_targets(&mut self) -> Option<&mut JumpTargets<'ast>> {
+            Some(&mut self.targets)
+        }
+
+        fn on_break_target(&mut self, node: &'ast StatementNode<'ast>, target: &'ast StatementNode<'ast>) {
+            self.jumps.push(("break", node.start, target.start));
+        }
+
+        fn on_continue_target(&mut self, node: &'ast StatementNode<'ast>, target: &'ast StatementNode<'ast>) {
+            self.jumps.push(("continue", node.start, target.start));
+        }
+    }
+
+    #[test]
+    fn resolves_jumps_to_their_targets() {
+        let module = parse("a: for (;;) { b: while (x) { if (y) break; else continue a; break b; } continue; () => { for (;;) break; }; }").unwrap();
+        let mut visitor = JumpTest {
+            targets: JumpTargets::new(),
+            jumps: Vec::new(),
+        };
+
+        module.visit_with(&mut visitor);
+
+        assert_eq!(visitor.jumps, &[
+            ("break", 36, 17),
+            ("continue", 48, 3),
+            ("break", 60, 14),
+            ("continue", 71, 3),
+            ("break", 98, 89),
+        ]);
+    }
 }
@@ -1,7 +1,9 @@
 use esr::ast::{Node, Statement, StatementNode};
 use esr::ast::statement::*;
+use esr::ast::types::{EnumDeclaration, EnumMember, NamespaceDeclaration};
 
 use crate::{Visitor, Visitable, ParentNode, ScopeKind, NoParent};
+use crate::jump;
 
 
 impl<'ast> Visitable<'ast> for StatementNode<'ast> {
@@ -14,6 +16,8 @@ impl<'ast> Visitable<'ast> for StatementNode<'ast> {
     {
         use self::Statement::*;
 
+        let jump_target = jump::enter_statement(visitor, self);
+
         match self.item {
             Empty => { 
                 // EmptyStatement doesn't have children, we return early to avoid calling pop_parent
@@ -36,11 +40,19 @@ impl<'ast> Visitable<'ast> for StatementNode<'ast> {
             },
             Break(ref break_statement) => {
                 visitor.on_break_statement(break_statement, self);
+                let label = break_statement.label.map(|label| label.item.as_str());
+                if let Some(target) = visitor.jump_targets().and_then(|targets| targets.break_target(label)) {
+                    visitor.on_break_target(self, target);
+                }
                 visitor.push_parent(ParentNode::from(self));
                 break_statement.visit_with(visitor);
             },
             Continue(ref continue_statement) => {
                 visitor.on_continue_statement(continue_statement, self);
+                let label = continue_statement.label.map(|label| label.item.as_str());
+                if let Some(target) = visitor.jump_targets().and_then(|targets| targets.continue_target(label)) {
+                    visitor.on_continue_target(self, target);
+                }
                 visitor.push_parent(ParentNode::from(self));
                 continue_statement.visit_with(visitor);
             },
@@ -108,8 +120,33 @@ impl<'ast> Visitable<'ast> for StatementNode<'ast> {
                 visitor.on_class_statement(class, self);
                 visitor.push_parent(ParentNode::from(self));
                 class.visit_with(visitor);
+            },
+            Declare(ref declare) => {
+                visitor.push_parent(ParentNode::from(self));
+                declare.body.visit_with(visitor);
+            },
+            Enum(ref enumeration) => {
+                visitor.on_enum_statement(enumeration, self);
+                visitor.push_parent(ParentNode::from(self));
+                enumeration.visit_with(visitor);
+            },
+            Namespace(ref namespace) => {
+                visitor.on_namespace_statement(namespace, self);
+                visitor.push_parent(ParentNode::from(self));
+                namespace.visit_with(visitor);
+            },
+            Export(ref export) => {
+                visitor.push_parent(ParentNode::from(self));
+                export.declaration.visit_with(visitor);
+            },
+            Interface(_) | TypeAlias(_) | FunctionSignature(_) | OpaqueType(_) | TypeImport(_) => {
+                // Type declarations don't have runtime children
+                return;
             }
         }
+        if jump_target {
+            jump::leave(visitor);
+        }
         visitor.pop_parent();
     }
 }
@@ -140,6 +177,46 @@ impl<'ast> Visitable<'ast> for Node<'ast, BlockStatement<'ast>> {
     }
 }
 
+impl<'ast> Visitable<'ast> for EnumMember<'ast> {
+    type Parent = Node<'ast, Self>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        self.value.visit_with(visitor);
+    }
+}
+
+impl<'ast> Visitable<'ast> for EnumDeclaration<'ast> {
+    type Parent = StatementNode<'ast>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        visitor.on_reference_declaration(&self.name.item);
+        self.members.visit_with(visitor);
+    }
+}
+
+impl<'ast> Visitable<'ast> for NamespaceDeclaration<'ast> {
+    type Parent = StatementNode<'ast>;
+
+    #[inline]
+    fn visit_with<V>(&'ast self, visitor: &mut V)
+    where
+        V: Visitor<'ast>,
+    {
+        visitor.on_reference_declaration(&self.name.item);
+        visitor.on_enter_scope(ScopeKind::Block);
+        self.body.body.visit_with(visitor);
+        visitor.on_leave_scope();
+    }
+}
+
 impl<'ast> Visitable<'ast> for Declarator<'ast> {
     type Parent = Node<'ast, Self>;
 
@@ -182,9 +259,8 @@ impl<'ast> Visitable<'ast> for BreakStatement<'ast> {
 
     #[inline]
     fn visit_with<V: Visitor<'ast>>(&self, _visitor: &mut V) {
-        // FIXME:
-        // INTENTIONALLY KEPT EMPTY FOR NOW!
-        // The identifier here is a label reference, _not_ a variable!
+        // Labels are not references, jumps are resolved by the
+        // statement node instead, see `Visitor::jump_targets`.
     }
 }
 
@@ -193,9 +269,8 @@ impl<'ast> Visitable<'ast> for ContinueStatement<'ast> {
 
     #[inline]
     fn visit_with<V: Visitor<'ast>>(&self, _visitor: &mut V) {
-        // FIXME:
-        // INTENTIONALLY KEPT EMPTY FOR NOW!
-        // The identifier here is a label reference, _not_ a variable!
+        // Labels are not references, jumps are resolved by the
+        // statement node instead, see `Visitor::jump_targets`.
     }
 }
 
@@ -344,7 +419,6 @@ impl<'ast> Visitable<'ast> for LabeledStatement<'ast> {
     where
         V: Visitor<'ast>,
     {
-        // FIXME: newtype for label
         self.body.visit_with(visitor);
     }
 }
@@ -10,10 +10,11 @@
   var valueTimer = 0;
   var output = null;
   var minify = false;
+  var estreeOptions = JSON.stringify({});
 
   Module.onRuntimeInitialized = bindUpdate(function (a) {
     generateAST = Module.cwrap('generate_ast', 'string', ['string', 'number']);
-    generateASTEstree = Module.cwrap('generate_ast_estree', 'string', ['string', 'number']);
+    generateASTEstree = Module.cwrap('generate_ast_estree', 'string', ['string', 'number', 'string']);
     transform = Module.cwrap('transform', 'string', ['string', 'number']);
 
     if (Clipboard.isSupported()) {
@@ -69,7 +70,7 @@
       } else if (mode === 1) {
         output = generateAST(value, minify);
       } else if (mode === 2) {
-        output = generateASTEstree(value, minify);
+        output = generateASTEstree(value, minify, estreeOptions);
       }
       ast_output.textContent = output;
     }, immediate ? 0 : DISPLAY_TIMEOUT);
@@ -6,6 +6,7 @@ use std::os::raw::c_char;
 use std::ffi::CStr;
 use std::ffi::CString;
 use esr::error::{Error, ParseError};
+use esr::EstreeOptions;
 
 fn main() {}
 
@@ -72,17 +73,35 @@ pub fn generate_ast(i: *const c_char, minify: bool) -> *const c_char {
 #[cfg_attr(feature = "cargo-clippy", allow(not_unsafe_ptr_arg_deref))]
 
 #[no_mangle]
-pub fn generate_ast_estree(i: *const c_char, minify: bool) -> *const c_char {
+pub fn generate_ast_estree(i: *const c_char, minify: bool, options: *const c_char) -> *const c_char {
 	let data = unsafe {
         CStr::from_ptr(i).to_str().unwrap()
     };
 
+	// `null` from JavaScript stands for the default options
+	let options: EstreeOptions = if options.is_null() {
+		EstreeOptions::default()
+	} else {
+		let options = unsafe {
+			CStr::from_ptr(options).to_str().unwrap()
+		};
+
+		match serde_json::from_str(options) {
+			Ok(options) => options,
+			Err(error) => {
+				let result = format!("Error: Invalid options, {}", error);
+
+				return CString::new(result.as_str()).unwrap().into_raw();
+			}
+		}
+	};
+
 	let result = match esr::parse(&data) {
 		Ok(module) => {
 			if minify {
-				serde_json::to_string(&module).unwrap()
+				serde_json::to_string(&module.estree(data, options)).unwrap()
 			} else {
-				serde_json::to_string_pretty(&module).unwrap()
+				serde_json::to_string_pretty(&module.estree(data, options)).unwrap()
 			}
 		},
 		Err(errors) => format_errors(errors, data)
@@ -0,0 +1,440 @@
+use std::vec;
+
+use crate::ast::{Node, NodeId, Block, Pattern, Label, Slot};
+use crate::ast::{Statement, Expression, Property, PropertyKey};
+use crate::ast::{Function, ClassMember, EmptyName, Decorator};
+use crate::ast::walk::{Walk, Walker};
+use crate::ast::structure::{Structure, Sink, Token};
+use crate::ast::expression::TemplateLiteral;
+use crate::ast::statement::{Declarator, ForInit, CatchClause, SwitchCase};
+use crate::ast::jsx::*;
+use crate::ast::types::*;
+
+macro_rules! any_node {
+    ($( $( #[$attr:meta] )* $variant:ident($type:ty) ),* $(,)*) => {
+        /// Reference to a node of any kind, for algorithms that treat all
+        /// nodes alike, such as counting nodes or finding the node at some
+        /// offset in the source.
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum AnyNode<'ast> {
+            $( $( #[$attr] )* $variant(&'ast Node<'ast, $type>), )*
+        }
+
+        $(
+            impl<'ast> From<&'ast Node<'ast, $type>> for AnyNode<'ast> {
+                #[inline]
+                fn from(node: &'ast Node<'ast, $type>) -> Self {
+                    AnyNode::$variant(node)
+                }
+            }
+        )*
+
+        impl<'ast> AnyNode<'ast> {
+            /// Identity of the node, see `NodeId`.
+            #[inline]
+            pub fn id(&self) -> NodeId {
+                match *self {
+                    $( AnyNode::$variant(node) => node.id(), )*
+                }
+            }
+
+            /// Start and end of the source spanned, `None` if synthetic.
+            #[inline]
+            pub fn span(&self) -> Option<(u32, u32)> {
+                match *self {
+                    $( AnyNode::$variant(node) => node.span(), )*
+                }
+            }
+
+            #[inline]
+            fn walk_item<W: Walker<'ast>>(&self, walker: &mut W) {
+                // Items of nodes never pass the slot on to other nodes
+                let slot = Slot::field("item");
+
+                match *self {
+                    $( AnyNode::$variant(node) => node.item.walk(walker, slot), )*
+                }
+            }
+
+            pub(crate) fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+                match *self {
+                    $( AnyNode::$variant(node) => {
+                        sink.token(Token::Tag(stringify!($variant)));
+                        node.tokens(sink);
+                    }, )*
+                }
+            }
+        }
+    };
+}
+
+/// Name of the variant of an enum, as `Enum::Variant`.
+macro_rules! variant {
+    ($value:expr, $enum:ident { $( $variant:ident ),* $(,)* }) => {
+        match $value {
+            $( $enum::$variant { .. } => concat!(stringify!($enum), "::", stringify!($variant)), )*
+        }
+    };
+}
+
+any_node! {
+    Expression(Expression<'ast>),
+    Statement(Statement<'ast>),
+    Pattern(Pattern<'ast>),
+    Property(Property<'ast>),
+    PropertyKey(PropertyKey<'ast>),
+    /// Node holding a bare string: identifiers, but also the quasis of
+    /// template literals and the source of type imports.
+    Identifier(&'ast str),
+    Label(Label<'ast>),
+    Block(Block<'ast, Statement<'ast>>),
+    Method(Function<'ast, EmptyName>),
+    ClassBody(Block<'ast, ClassMember<'ast>>),
+    ClassMember(ClassMember<'ast>),
+    Decorator(Decorator<'ast>),
+    Declarator(Declarator<'ast>),
+    ForInit(ForInit<'ast>),
+    CatchClause(CatchClause<'ast>),
+    SwitchCase(SwitchCase<'ast>),
+    TemplateLiteral(TemplateLiteral<'ast>),
+    Type(Type<'ast>),
+    TypeName(TypeName<'ast>),
+    TypeParameter(TypeParameter<'ast>),
+    TypeMember(TypeMember<'ast>),
+    FunctionType(FunctionType<'ast>),
+    EnumMember(EnumMember<'ast>),
+    TypeImportSpecifier(TypeImportSpecifier<'ast>),
+    JSXElementName(JSXElementName<'ast>),
+    JSXAttributeItem(JSXAttributeItem<'ast>),
+    JSXAttributeValue(JSXAttributeValue<'ast>),
+    JSXOpeningElement(JSXOpeningElement<'ast>),
+    JSXClosingElement(JSXClosingElement<'ast>),
+    JSXOpeningFragment(JSXOpeningFragment),
+    JSXClosingFragment(JSXClosingFragment),
+    JSXChild(JSXChild<'ast>),
+}
+
+/// Order in which `AnyNode::descendants` yields nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    /// Parents before their children.
+    Pre,
+    /// Children before their parents.
+    Post,
+}
+
+impl<'ast> AnyNode<'ast> {
+    /// Kind of the node. That's the variant for nodes holding an enum,
+    /// such as `Expression::Call`, or the name of the variant of `AnyNode`
+    /// otherwise.
+    pub fn kind(&self) -> &'static str {
+        use self::AnyNode as Any;
+
+        match *self {
+            Any::Expression(node) => variant!(node.item, Expression {
+                Void, This, Super, Identifier, Literal, Sequence, Parenthesized, Array, Member,
+                ComputedMember, MetaProperty, Call, Binary, Prefix, Postfix, Conditional,
+                Template, TaggedTemplate, Spread, Await, Arrow, Object, Function, Class,
+                JSXElement, JSXFragment, As, Satisfies, TypeCast, NonNull, TypeAssertion, Instantiation,
+            }),
+            Any::Statement(node) => variant!(node.item, Statement {
+                Empty, Expression, Declaration, Return, Break, Continue, Throw, If, While,
+                Do, For, ForIn, ForOf, Try, Block, Labeled, Function, Class, Switch,
+                Interface, TypeAlias, FunctionSignature, Declare, OpaqueType, TypeImport,
+                Enum, Namespace, Export,
+            }),
+            Any::Pattern(node) => variant!(node.item, Pattern {
+                Void, Identifier, ObjectPattern, ArrayPattern, RestElement,
+                AssignmentPattern, Annotated, ParameterProperty,
+            }),
+            Any::Property(node) => variant!(node.item, Property {
+                Shorthand, Literal, Method, Spread,
+            }),
+            Any::PropertyKey(node) => variant!(node.item, PropertyKey {
+                Computed, Literal, Binary,
+            }),
+            Any::ClassMember(node) => variant!(node.item, ClassMember {
+                Error, Method, Literal, Property, Accessor, Signature, Modified, Decorated,
+            }),
+            Any::ForInit(node) => variant!(node.item, ForInit {
+                Declaration, Expression,
+            }),
+            Any::Type(node) => variant!(node.item, Type {
+                Any, Unknown, Never, Void, Object, This, Primitive, Literal, Reference,
+                Query, Array, Tuple, Optional, Rest, Union, Intersection, Function,
+                Constructor, ObjectLiteral, Mapped, Operator, IndexedAccess, Conditional,
+                Infer, Predicate, Nullable,
+            }),
+            Any::TypeName(node) => variant!(node.item, TypeName {
+                Identifier, Qualified,
+            }),
+            Any::TypeMember(node) => variant!(node.item, TypeMember {
+                Property, Method, Index, Call, Construct, Spread,
+            }),
+            Any::TypeImportSpecifier(node) => variant!(node.item, TypeImportSpecifier {
+                Default, Namespace, Named,
+            }),
+            Any::JSXElementName(node) => variant!(node.item, JSXElementName {
+                Identifier, Namespaced, Member,
+            }),
+            Any::JSXAttributeItem(node) => variant!(node.item, JSXAttributeItem {
+                Attribute, Spread,
+            }),
+            Any::JSXAttributeValue(node) => variant!(node.item, JSXAttributeValue {
+                String, Expression, Element, Fragment,
+            }),
+            Any::JSXChild(node) => variant!(node.item, JSXChild {
+                Text, Expression, Element, Fragment,
+            }),
+            Any::Identifier(_)         => "Identifier",
+            Any::Label(_)              => "Label",
+            Any::Block(_)              => "Block",
+            Any::Method(_)             => "Method",
+            Any::ClassBody(_)          => "ClassBody",
+            Any::Decorator(_)          => "Decorator",
+            Any::Declarator(_)         => "Declarator",
+            Any::CatchClause(_)        => "CatchClause",
+            Any::SwitchCase(_)         => "SwitchCase",
+            Any::TemplateLiteral(_)    => "TemplateLiteral",
+            Any::TypeParameter(_)      => "TypeParameter",
+            Any::FunctionType(_)       => "FunctionType",
+            Any::EnumMember(_)         => "EnumMember",
+            Any::JSXOpeningElement(_)  => "JSXOpeningElement",
+            Any::JSXClosingElement(_)  => "JSXClosingElement",
+            Any::JSXOpeningFragment(_) => "JSXOpeningFragment",
+            Any::JSXClosingFragment(_) => "JSXClosingFragment",
+        }
+    }
+
+    /// Nodes directly contained in this one, in source order.
+    pub fn children(&self) -> Children<'ast> {
+        let mut collector = Collector {
+            nodes: Vec::new(),
+        };
+
+        self.walk_item(&mut collector);
+
+        Children {
+            inner: collector.nodes.into_iter(),
+        }
+    }
+
+    /// All nodes contained in this one, excluding itself, either in
+    /// pre-order or in post-order.
+    pub fn descendants(&self, order: Order) -> Descendants<'ast> {
+        let mut descendants = Descendants {
+            order,
+            stack: Vec::new(),
+        };
+
+        descendants.push_children(*self);
+
+        descendants
+    }
+}
+
+/// Iterator over the children of a node, see `AnyNode::children`.
+#[derive(Debug, Clone)]
+pub struct Children<'ast> {
+    inner: vec::IntoIter<AnyNode<'ast>>,
+}
+
+impl<'ast> Iterator for Children<'ast> {
+    type Item = AnyNode<'ast>;
+
+    #[inline]
+    fn next(&mut self) -> Option<AnyNode<'ast>> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'ast> DoubleEndedIterator for Children<'ast> {
+    #[inline]
+    fn next_back(&mut self) -> Option<AnyNode<'ast>> {
+        self.inner.next_back()
+    }
+}
+
+impl<'ast> ExactSizeIterator for Children<'ast> {}
+
+/// Iterator over the descendants of a node, see `AnyNode::descendants`.
+#[derive(Debug, Clone)]
+pub struct Descendants<'ast> {
+    order: Order,
+    /// Nodes left to visit, last one first, along with whether their
+    /// children have already been pushed.
+    stack: Vec<(AnyNode<'ast>, bool)>,
+}
+
+impl<'ast> Descendants<'ast> {
+    #[inline]
+    fn push_children(&mut self, node: AnyNode<'ast>) {
+        self.stack.extend(node.children().rev().map(|child| (child, false)));
+    }
+}
+
+impl<'ast> Iterator for Descendants<'ast> {
+    type Item = AnyNode<'ast>;
+
+    fn next(&mut self) -> Option<AnyNode<'ast>> {
+        loop {
+            let (node, expanded) = self.stack.pop()?;
+
+            match self.order {
+                Order::Pre => {
+                    self.push_children(node);
+
+                    return Some(node);
+                },
+                Order::Post if expanded => return Some(node),
+                Order::Post => {
+                    self.stack.push((node, true));
+                    self.push_children(node);
+                },
+            }
+        }
+    }
+}
+
+struct Collector<'ast> {
+    nodes: Vec<AnyNode<'ast>>,
+}
+
+impl<'ast> Walker<'ast> for Collector<'ast> {
+    #[inline]
+    fn enter(&mut self, node: AnyNode<'ast>, _: Slot) -> bool {
+        self.nodes.push(node);
+
+        false
+    }
+
+    #[inline]
+    fn leave(&mut self) {}
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{parse, parse_with_options, ParseOptions};
+    use crate::module::Module;
+
+    fn nodes<'ast>(module: &'ast Module<'ast>, order: Order) -> Vec<AnyNode<'ast>> {
+        module.body().iter().flat_map(|node| {
+            let node = AnyNode::from(node);
+            let descendants = node.descendants(order);
+
+            match order {
+                Order::Pre  => Some(node).into_iter().chain(descendants).collect::<Vec<_>>(),
+                Order::Post => descendants.chain(Some(node)).collect(),
+            }
+        }).collect()
+    }
+
+    fn kinds(nodes: &[AnyNode<'_>]) -> Vec<&'static str> {
+        nodes.iter().map(AnyNode::kind).collect()
+    }
+
+    /// Smallest node spanning the offset, the kind of tool `AnyNode` is for
+    fn node_at<'ast>(module: &'ast Module<'ast>, offset: u32) -> Option<AnyNode<'ast>> {
+        let mut found = None;
+        let mut candidates = module.body().iter().map(AnyNode::from).collect::<Vec<_>>();
+
+        while let Some(node) = candidates.pop() {
+            match node.span() {
+                Some((start, end)) if start <= offset && offset < end => {
+                    found = Some(node);
+                    candidates = node.children().collect();
+                },
+                _ => {},
+            }
+        }
+
+        found
+    }
+
+    #[test]
+    fn children() {
+        let module = parse("foo(bar, 1);").unwrap();
+        let statement = AnyNode::from(module.body().iter().next().unwrap());
+        let call = statement.children().next().unwrap();
+
+        assert_eq!(statement.kind(), "Statement::Expression");
+        assert_eq!(statement.children().len(), 1);
+        assert_eq!(call.kind(), "Expression::Call");
+        assert_eq!(kinds(&call.children().collect::<Vec<_>>()), [
+            "Expression::Identifier",
+            "Expression::Identifier",
+            "Expression::Literal",
+        ]);
+        assert_eq!(call.children().nth(2).unwrap().span(), Some((9, 10)));
+    }
+
+    #[test]
+    fn descendants_pre_and_post_order() {
+        let module = parse("let a = [b, -c];").unwrap();
+
+        assert_eq!(kinds(&nodes(&module, Order::Pre)), [
+            "Statement::Declaration",
+            "Declarator",
+            "Pattern::Identifier",
+            "Identifier",
+            "Expression::Array",
+            "Expression::Identifier",
+            "Expression::Prefix",
+            "Expression::Identifier",
+        ]);
+
+        assert_eq!(kinds(&nodes(&module, Order::Post)), [
+            "Identifier",
+            "Pattern::Identifier",
+            "Expression::Identifier",
+            "Expression::Identifier",
+            "Expression::Prefix",
+            "Expression::Array",
+            "Declarator",
+            "Statement::Declaration",
+        ]);
+    }
+
+    #[test]
+    fn covers_every_node() {
+        let options = ParseOptions { typescript: true, ..ParseOptions::default() };
+        let module = parse_with_options(r#"
+            function foo<T>(a: T, { b }, c: Bar.Baz, ...d): void {
+                label: for (let i in a) { if (i) continue label; }
+                try { foo`a${b}c`; } catch (e) {} finally {}
+                switch (a) { case 1: break; }
+            }
+            class Foo { @dec bar() {} static baz: number = 1; }
+            enum Qux { A = 1 }
+        "#, options).unwrap();
+
+        let pre = nodes(&module, Order::Pre);
+        let post = nodes(&module, Order::Post);
+
+        assert_eq!(pre.len(), post.len());
+        assert_eq!(pre.len(), crate::ast::ParentMap::new(&module).len());
+
+        for kind in &["Label", "Block", "Method", "ClassBody", "Decorator", "ForInit::Declaration",
+                      "CatchClause", "SwitchCase", "TemplateLiteral", "TypeParameter",
+                      "TypeName::Qualified", "EnumMember", "Type::Primitive", "Type::Void"] {
+            assert!(kinds(&pre).contains(kind), "Missing {}", kind);
+        }
+    }
+
+    #[test]
+    fn smallest_node_at_offset() {
+        let module = parse("if (a) { foo(bar.baz); }").unwrap();
+
+        assert_eq!(node_at(&module, 1).map(|node| node.kind()), Some("Statement::If"));
+        assert_eq!(node_at(&module, 4).map(|node| node.kind()), Some("Expression::Identifier"));
+        assert_eq!(node_at(&module, 14).map(|node| node.kind()), Some("Expression::Identifier"));
+        assert_eq!(node_at(&module, 14).and_then(|node| node.span()), Some((13, 16)));
+        assert_eq!(node_at(&module, 30), None);
+    }
+}
@@ -0,0 +1,898 @@
+use toolshed::Arena;
+
+use crate::ast::{Node, NodeList, Loc, Block, BlockNode, Literal, OperatorKind, DeclarationKind};
+use crate::ast::{Identifier, IdentifierNode, LabelNode, Label, PropertyKey, Property, PropertyNode, Pattern};
+use crate::ast::{Expression, ExpressionNode, Statement, StatementNode, Declarator};
+use crate::ast::{Function, Class, ClassMember, MethodKind, Name, EmptyName, OptionalName, MandatoryName};
+use crate::ast::DeepClone;
+use crate::ast::expression::*;
+use crate::ast::statement::*;
+use crate::ast::jsx::*;
+
+/// Creates nodes in an `Arena`, for use outside of the parser. Nodes are
+/// synthetic, see `Loc::synthetic`, unless allocated with `alloc_as_loc`.
+///
+/// Lists can be passed as anything iterable, and are collected into the arena.
+#[derive(Clone, Copy)]
+pub struct AstBuilder<'ast> {
+    pub arena: &'ast Arena,
+}
+
+impl<'ast> AstBuilder<'ast> {
+    #[inline]
+    pub fn new(arena: &'ast Arena) -> Self {
+        AstBuilder { arena }
+    }
+
+    /// Allocate a synthetic node.
+    #[inline]
+    pub fn alloc<T, I>(&self, item: I) -> Node<'ast, T> where
+        T: Copy,
+        I: Into<T>,
+    {
+        Node::new(self.arena.alloc(Loc::synthetic(item.into())))
+    }
+
+    /// Allocate a node spanning the same part of the source as `loc`.
+    #[inline]
+    pub fn alloc_as_loc<T, I, L>(&self, loc: &Node<'ast, L>, item: I) -> Node<'ast, T> where
+        T: Copy + 'ast,
+        I: Into<T>,
+    {
+        Node::new(self.arena.alloc(Loc::new(loc.start, loc.end, item.into())))
+    }
+
+    /// Replace the node behind `ptr`, keeping its position.
+    #[inline]
+    pub fn swap<T, I>(&self, ptr: &Node<'ast, T>, item: I) where
+        T: Copy + 'ast,
+        I: Into<T>,
+    {
+        ptr.set(self.arena.alloc(Loc::new(ptr.start, ptr.end, item.into())));
+    }
+
+    #[inline]
+    pub fn list<T, I>(&self, items: I) -> NodeList<'ast, T> where
+        T: Copy + 'ast,
+        I: IntoIterator<Item = Node<'ast, T>>,
+    {
+        NodeList::from_iter(self.arena, items)
+    }
+
+    /// Deep copy of a node or list into the arena, see `DeepClone`.
+    #[inline]
+    pub fn deep_clone<T: DeepClone<'ast>>(&self, node: &T) -> T::Cloned {
+        node.clone_in(self.arena)
+    }
+
+    /// Copy a string into the arena, for names not borrowed from the source.
+    #[inline]
+    pub fn str(&self, value: &str) -> &'ast str {
+        self.arena.alloc_str(value)
+    }
+
+    #[inline]
+    pub fn block<T, I>(&self, body: I) -> BlockNode<'ast, T> where
+        T: Copy + 'ast,
+        I: IntoIterator<Item = Node<'ast, T>>,
+    {
+        self.alloc(Block { body: self.list(body) })
+    }
+
+    #[inline]
+    pub fn identifier(&self, name: Identifier<'ast>) -> IdentifierNode<'ast> {
+        self.alloc(name)
+    }
+
+    #[inline]
+    pub fn label(&self, name: &'ast str) -> LabelNode<'ast> {
+        self.alloc(Label(name))
+    }
+
+    /// Plain property or method name, `foo` in `{ foo: 1 }`.
+    #[inline]
+    pub fn property_key(&self, name: &'ast str) -> Node<'ast, PropertyKey<'ast>> {
+        self.alloc(PropertyKey::Literal(name))
+    }
+
+    /// `[foo]` in `{ [foo]: 1 }`
+    #[inline]
+    pub fn computed_key(&self, key: ExpressionNode<'ast>) -> Node<'ast, PropertyKey<'ast>> {
+        self.alloc(PropertyKey::Computed(key))
+    }
+
+    // Expressions
+
+    #[inline]
+    pub fn this_expression(&self) -> ExpressionNode<'ast> {
+        self.alloc(ThisExpression)
+    }
+
+    #[inline]
+    pub fn super_expression(&self) -> ExpressionNode<'ast> {
+        self.alloc(SuperExpression)
+    }
+
+    #[inline]
+    pub fn identifier_expression(&self, name: Identifier<'ast>) -> ExpressionNode<'ast> {
+        self.alloc(Expression::Identifier(name))
+    }
+
+    #[inline]
+    pub fn literal(&self, literal: Literal<'ast>) -> ExpressionNode<'ast> {
+        self.alloc(literal)
+    }
+
+    #[inline]
+    pub fn boolean_literal(&self, value: bool) -> ExpressionNode<'ast> {
+        self.literal(if value { Literal::True } else { Literal::False })
+    }
+
+    /// Number literal from its source, `1`, `0xff` or `1e3`.
+    #[inline]
+    pub fn number_literal(&self, raw: &'ast str) -> ExpressionNode<'ast> {
+        self.literal(Literal::Number(raw))
+    }
+
+    /// Double quoted string literal with the given value, escaped as needed.
+    pub fn string_literal(&self, value: &str) -> ExpressionNode<'ast> {
+        let mut raw = String::with_capacity(value.len() + 2);
+
+        raw.push('"');
+
+        for ch in value.chars() {
+            match ch {
+                '"'        => raw.push_str("\\\""),
+                '\\'       => raw.push_str("\\\\"),
+                '\n'       => raw.push_str("\\n"),
+                '\r'       => raw.push_str("\\r"),
+                '\u{2028}' => raw.push_str("\\u2028"),
+                '\u{2029}' => raw.push_str("\\u2029"),
+                _          => raw.push(ch),
+            }
+        }
+
+        raw.push('"');
+
+        self.literal(Literal::String(self.arena.alloc_string(raw)))
+    }
+
+    /// `a, b`
+    #[inline]
+    pub fn sequence_expression<I>(&self, body: I) -> ExpressionNode<'ast> where
+        I: IntoIterator<Item = ExpressionNode<'ast>>,
+    {
+        self.alloc(SequenceExpression { body: self.list(body) })
+    }
+
+    /// `[a, b]`, `Expression::Void` stands for holes.
+    #[inline]
+    pub fn array_expression<I>(&self, body: I) -> ExpressionNode<'ast> where
+        I: IntoIterator<Item = ExpressionNode<'ast>>,
+    {
+        self.alloc(ArrayExpression { body: self.list(body) })
+    }
+
+    /// `{ a, b: 1 }`
+    #[inline]
+    pub fn object_expression<I>(&self, body: I) -> ExpressionNode<'ast> where
+        I: IntoIterator<Item = PropertyNode<'ast>>,
+    {
+        self.alloc(ObjectExpression { body: self.list(body) })
+    }
+
+    /// `foo.bar`
+    #[inline]
+    pub fn member_expression(&self, object: ExpressionNode<'ast>, property: &'ast str) -> ExpressionNode<'ast> {
+        self.alloc(MemberExpression {
+            object,
+            property: self.identifier(property),
+        })
+    }
+
+    /// `foo[bar]`
+    #[inline]
+    pub fn computed_member_expression(&self, object: ExpressionNode<'ast>, property: ExpressionNode<'ast>) -> ExpressionNode<'ast> {
+        self.alloc(ComputedMemberExpression { object, property })
+    }
+
+    /// `foo(a, b)`
+    #[inline]
+    pub fn call_expression<I>(&self, callee: ExpressionNode<'ast>, arguments: I) -> ExpressionNode<'ast> where
+        I: IntoIterator<Item = ExpressionNode<'ast>>,
+    {
+        self.alloc(CallExpression {
+            callee,
+            arguments: self.list(arguments),
+        })
+    }
+
+    /// `new Foo(a, b)`
+    #[inline]
+    pub fn new_expression<I>(&self, callee: ExpressionNode<'ast>, arguments: I) -> ExpressionNode<'ast> where
+        I: IntoIterator<Item = ExpressionNode<'ast>>,
+    {
+        self.prefix_expression(OperatorKind::New, self.call_expression(callee, arguments))
+    }
+
+    /// Binary, logical or assignment expression, `a + b`.
+    #[inline]
+    pub fn binary_expression(&self, operator: OperatorKind, left: ExpressionNode<'ast>, right: ExpressionNode<'ast>) -> ExpressionNode<'ast> {
+        self.alloc(BinaryExpression { operator, left, right })
+    }
+
+    /// `a = b`
+    #[inline]
+    pub fn assignment_expression(&self, left: ExpressionNode<'ast>, right: ExpressionNode<'ast>) -> ExpressionNode<'ast> {
+        self.binary_expression(OperatorKind::Assign, left, right)
+    }
+
+    /// `!a`, `typeof a`
+    #[inline]
+    pub fn prefix_expression(&self, operator: OperatorKind, operand: ExpressionNode<'ast>) -> ExpressionNode<'ast> {
+        self.alloc(PrefixExpression { operator, operand })
+    }
+
+    /// `a++`
+    #[inline]
+    pub fn postfix_expression(&self, operator: OperatorKind, operand: ExpressionNode<'ast>) -> ExpressionNode<'ast> {
+        self.alloc(PostfixExpression { operator, operand })
+    }
+
+    /// `a ? b : c`
+    #[inline]
+    pub fn conditional_expression(
+        &self,
+        test: ExpressionNode<'ast>,
+        consequent: ExpressionNode<'ast>,
+        alternate: ExpressionNode<'ast>,
+    ) -> ExpressionNode<'ast> {
+        self.alloc(ConditionalExpression { test, consequent, alternate })
+    }
+
+    /// Template literal from its raw quasis, which have to outnumber the
+    /// expressions by one.
+    #[inline]
+    pub fn template_literal<Q, E>(&self, quasis: Q, expressions: E) -> Node<'ast, TemplateLiteral<'ast>> where
+        Q: IntoIterator<Item = &'ast str>,
+        E: IntoIterator<Item = ExpressionNode<'ast>>,
+    {
+        self.alloc(TemplateLiteral {
+            expressions: self.list(expressions),
+            quasis: self.list(quasis.into_iter().map(|quasi| self.alloc(quasi))),
+        })
+    }
+
+    /// `` foo`bar` ``
+    #[inline]
+    pub fn tagged_template_expression(&self, tag: ExpressionNode<'ast>, quasi: Node<'ast, TemplateLiteral<'ast>>) -> ExpressionNode<'ast> {
+        self.alloc(TaggedTemplateExpression { tag, quasi })
+    }
+
+    /// `(foo)`
+    #[inline]
+    pub fn parenthesized_expression(&self, expression: ExpressionNode<'ast>) -> ExpressionNode<'ast> {
+        self.alloc(ParenthesizedExpression { expression })
+    }
+
+    /// `new.target` or `import.meta`
+    #[inline]
+    pub fn meta_property_expression(&self, meta: &'ast str, property: &'ast str) -> ExpressionNode<'ast> {
+        self.alloc(MetaPropertyExpression {
+            meta: self.identifier(meta),
+            property: self.identifier(property),
+        })
+    }
+
+    /// `...foo`
+    #[inline]
+    pub fn spread_expression(&self, argument: ExpressionNode<'ast>) -> ExpressionNode<'ast> {
+        self.alloc(SpreadExpression { argument })
+    }
+
+    /// `await foo`
+    #[inline]
+    pub fn await_expression(&self, argument: ExpressionNode<'ast>) -> ExpressionNode<'ast> {
+        self.alloc(AwaitExpression { argument })
+    }
+
+    /// `(a, b) => a + b`
+    #[inline]
+    pub fn arrow_expression<I>(&self, params: I, body: ArrowBody<'ast>) -> ExpressionNode<'ast> where
+        I: IntoIterator<Item = Node<'ast, Pattern<'ast>>>,
+    {
+        self.alloc(ArrowExpression {
+            type_parameters: NodeList::empty(),
+            params: self.list(params),
+            return_type: None,
+            body,
+        })
+    }
+
+    /// `function foo(a) {}`, with an optional name.
+    #[inline]
+    pub fn function_expression<P, B>(&self, name: Option<&'ast str>, params: P, body: B) -> ExpressionNode<'ast> where
+        P: IntoIterator<Item = Node<'ast, Pattern<'ast>>>,
+        B: IntoIterator<Item = StatementNode<'ast>>,
+    {
+        let name = OptionalName(name.map(|name| self.identifier(name)));
+
+        self.alloc(self.function(name, params, body))
+    }
+
+    /// `class Foo extends Bar {}`, with an optional name.
+    #[inline]
+    pub fn class_expression<I>(&self, name: Option<&'ast str>, extends: Option<ExpressionNode<'ast>>, body: I) -> ExpressionNode<'ast> where
+        I: IntoIterator<Item = Node<'ast, ClassMember<'ast>>>,
+    {
+        let name = OptionalName(name.map(|name| self.identifier(name)));
+
+        self.alloc(self.class(name, extends, body))
+    }
+
+    // Object properties
+
+    /// `foo` in `{ foo }`
+    #[inline]
+    pub fn shorthand_property(&self, name: &'ast str) -> PropertyNode<'ast> {
+        self.alloc(Property::Shorthand(self.identifier(name)))
+    }
+
+    /// `foo: bar` in `{ foo: bar }`
+    #[inline]
+    pub fn property(&self, key: Node<'ast, PropertyKey<'ast>>, value: ExpressionNode<'ast>) -> PropertyNode<'ast> {
+        self.alloc(Property::Literal { key, value })
+    }
+
+    /// `foo() {}` in `{ foo() {} }`
+    #[inline]
+    pub fn method_property(&self, key: Node<'ast, PropertyKey<'ast>>, value: Node<'ast, Function<'ast, EmptyName>>) -> PropertyNode<'ast> {
+        self.alloc(Property::Method { key, value })
+    }
+
+    /// `...foo` in `{ ...foo }`
+    #[inline]
+    pub fn spread_property(&self, argument: ExpressionNode<'ast>) -> PropertyNode<'ast> {
+        self.alloc(Property::Spread { argument })
+    }
+
+    // Patterns
+
+    #[inline]
+    pub fn identifier_pattern(&self, name: &'ast str) -> Node<'ast, Pattern<'ast>> {
+        self.alloc(Pattern::Identifier(self.identifier(name)))
+    }
+
+    /// `{ a, b: c }`, properties hold patterns as their values.
+    #[inline]
+    pub fn object_pattern<I>(&self, properties: I) -> Node<'ast, Pattern<'ast>> where
+        I: IntoIterator<Item = PropertyNode<'ast>>,
+    {
+        self.alloc(Pattern::ObjectPattern { properties: self.list(properties) })
+    }
+
+    /// `[a, b]`, `Pattern::Void` stands for holes.
+    #[inline]
+    pub fn array_pattern<I>(&self, elements: I) -> Node<'ast, Pattern<'ast>> where
+        I: IntoIterator<Item = Node<'ast, Pattern<'ast>>>,
+    {
+        self.alloc(Pattern::ArrayPattern { elements: self.list(elements) })
+    }
+
+    /// `...rest`
+    #[inline]
+    pub fn rest_element(&self, name: &'ast str) -> Node<'ast, Pattern<'ast>> {
+        self.alloc(Pattern::RestElement { argument: self.identifier(name) })
+    }
+
+    /// `a = 1`
+    #[inline]
+    pub fn assignment_pattern(&self, left: Node<'ast, Pattern<'ast>>, right: ExpressionNode<'ast>) -> Node<'ast, Pattern<'ast>> {
+        self.alloc(Pattern::AssignmentPattern { left, right })
+    }
+
+    // Functions and classes
+
+    /// Plain function, without type annotations, for any kind of name.
+    #[inline]
+    pub fn function<N, P, B>(&self, name: N, params: P, body: B) -> Function<'ast, N> where
+        N: Name<'ast>,
+        P: IntoIterator<Item = Node<'ast, Pattern<'ast>>>,
+        B: IntoIterator<Item = StatementNode<'ast>>,
+    {
+        Function {
+            name,
+            generator: false,
+            type_parameters: NodeList::empty(),
+            params: self.list(params),
+            return_type: None,
+            body: self.block(body),
+        }
+    }
+
+    /// Function without a name, as used by object and class methods.
+    #[inline]
+    pub fn method<P, B>(&self, params: P, body: B) -> Node<'ast, Function<'ast, EmptyName>> where
+        P: IntoIterator<Item = Node<'ast, Pattern<'ast>>>,
+        B: IntoIterator<Item = StatementNode<'ast>>,
+    {
+        self.alloc(self.function(EmptyName, params, body))
+    }
+
+    /// Plain class, without decorators or type annotations, for any kind of name.
+    #[inline]
+    pub fn class<N, I>(&self, name: N, extends: Option<ExpressionNode<'ast>>, body: I) -> Class<'ast, N> where
+        N: Name<'ast>,
+        I: IntoIterator<Item = Node<'ast, ClassMember<'ast>>>,
+    {
+        Class {
+            decorators: NodeList::empty(),
+            name,
+            type_parameters: NodeList::empty(),
+            extends,
+            super_type_arguments: NodeList::empty(),
+            implements: NodeList::empty(),
+            body: self.block(body),
+        }
+    }
+
+    /// Method, getter, setter or constructor of a class.
+    #[inline]
+    pub fn class_method(
+        &self,
+        kind: MethodKind,
+        is_static: bool,
+        key: Node<'ast, PropertyKey<'ast>>,
+        value: Node<'ast, Function<'ast, EmptyName>>,
+    ) -> Node<'ast, ClassMember<'ast>> {
+        self.alloc(ClassMember::Method { is_static, key, kind, value })
+    }
+
+    /// `foo = 1;` in a class body, with an optional value.
+    #[inline]
+    pub fn class_property(
+        &self,
+        is_static: bool,
+        key: Node<'ast, PropertyKey<'ast>>,
+        value: Option<ExpressionNode<'ast>>,
+    ) -> Node<'ast, ClassMember<'ast>> {
+        self.alloc(ClassMember::Property {
+            is_static,
+            key,
+            optional: false,
+            annotation: None,
+            value,
+        })
+    }
+
+    // JSX
+
+    /// `div` or `Foo` as the name of an element or attribute.
+    #[inline]
+    pub fn jsx_name(&self, name: &'ast str) -> Node<'ast, JSXElementName<'ast>> {
+        self.alloc(JSXElementName::Identifier(name))
+    }
+
+    /// `foo.Bar`
+    #[inline]
+    pub fn jsx_member_name(&self, object: Node<'ast, JSXElementName<'ast>>, property: &'ast str) -> Node<'ast, JSXElementName<'ast>> {
+        self.alloc(JSXElementName::Member(JSXMemberExpression {
+            object,
+            property: self.identifier(property),
+        }))
+    }
+
+    /// `<foo {...bar}>baz</foo>`, the closing tag gets its own copy of the `name`.
+    #[inline]
+    pub fn jsx_element<A, C>(&self, name: Node<'ast, JSXElementName<'ast>>, attributes: A, children: C) -> JSXElement<'ast> where
+        A: IntoIterator<Item = Node<'ast, JSXAttributeItem<'ast>>>,
+        C: IntoIterator<Item = Node<'ast, JSXChild<'ast>>>,
+    {
+        JSXElement {
+            opening: self.alloc(JSXOpeningElement {
+                name,
+                attributes: self.list(attributes),
+                self_closing: false,
+            }),
+            children: self.list(children),
+            closing: Some(self.alloc(JSXClosingElement { name: self.deep_clone(&name) })),
+        }
+    }
+
+    /// `<foo {...bar} />`
+    #[inline]
+    pub fn jsx_self_closing_element<A>(&self, name: Node<'ast, JSXElementName<'ast>>, attributes: A) -> JSXElement<'ast> where
+        A: IntoIterator<Item = Node<'ast, JSXAttributeItem<'ast>>>,
+    {
+        JSXElement {
+            opening: self.alloc(JSXOpeningElement {
+                name,
+                attributes: self.list(attributes),
+                self_closing: true,
+            }),
+            children: NodeList::empty(),
+            closing: None,
+        }
+    }
+
+    /// `<>foo</>`
+    #[inline]
+    pub fn jsx_fragment<C>(&self, children: C) -> JSXFragment<'ast> where
+        C: IntoIterator<Item = Node<'ast, JSXChild<'ast>>>,
+    {
+        JSXFragment {
+            opening: self.alloc(JSXOpeningFragment),
+            children: self.list(children),
+            closing: self.alloc(JSXClosingFragment),
+        }
+    }
+
+    /// `foo="bar"`, or just `foo` without a value.
+    #[inline]
+    pub fn jsx_attribute(&self, name: &'ast str, value: Option<JSXAttributeValue<'ast>>) -> Node<'ast, JSXAttributeItem<'ast>> {
+        self.alloc(JSXAttribute {
+            name: self.jsx_name(name),
+            value: value.map(|value| self.alloc(value)),
+        })
+    }
+
+    /// `{...foo}` among the attributes.
+    #[inline]
+    pub fn jsx_spread_attribute(&self, argument: ExpressionNode<'ast>) -> Node<'ast, JSXAttributeItem<'ast>> {
+        self.alloc(JSXSpreadAttribute { argument })
+    }
+
+    /// `{foo}`, or `{}` without an expression.
+    #[inline]
+    pub fn jsx_expression_container(&self, expression: Option<ExpressionNode<'ast>>) -> JSXExpressionContainer<'ast> {
+        JSXExpressionContainer { expression }
+    }
+
+    /// Text child from its source, which keeps any character references.
+    #[inline]
+    pub fn jsx_text(&self, raw: &'ast str) -> Node<'ast, JSXChild<'ast>> {
+        self.alloc(JSXChild::Text(raw))
+    }
+
+    /// `{foo}` among the children.
+    #[inline]
+    pub fn jsx_expression_child(&self, expression: Option<ExpressionNode<'ast>>) -> Node<'ast, JSXChild<'ast>> {
+        self.alloc(JSXChild::Expression(self.jsx_expression_container(expression)))
+    }
+
+    /// Nested element among the children.
+    #[inline]
+    pub fn jsx_element_child(&self, element: JSXElement<'ast>) -> Node<'ast, JSXChild<'ast>> {
+        self.alloc(JSXChild::Element(element))
+    }
+
+    // Statements
+
+    #[inline]
+    pub fn empty_statement(&self) -> StatementNode<'ast> {
+        self.alloc(Statement::Empty)
+    }
+
+    #[inline]
+    pub fn expression_statement(&self, expression: ExpressionNode<'ast>) -> StatementNode<'ast> {
+        self.alloc(expression)
+    }
+
+    /// `a = 1` in `let a = 1, b;`
+    #[inline]
+    pub fn declarator(&self, id: Node<'ast, Pattern<'ast>>, init: Option<ExpressionNode<'ast>>) -> Node<'ast, Declarator<'ast>> {
+        self.alloc(Declarator { id, init })
+    }
+
+    /// `let a = 1, b;`
+    #[inline]
+    pub fn declaration_statement<I>(&self, kind: DeclarationKind, declarators: I) -> StatementNode<'ast> where
+        I: IntoIterator<Item = Node<'ast, Declarator<'ast>>>,
+    {
+        self.alloc(DeclarationStatement {
+            kind,
+            declarators: self.list(declarators),
+        })
+    }
+
+    /// Declaration of a single variable, `const foo = bar;`.
+    #[inline]
+    pub fn variable_declaration(&self, kind: DeclarationKind, name: &'ast str, init: Option<ExpressionNode<'ast>>) -> StatementNode<'ast> {
+        self.declaration_statement(kind, Some(self.declarator(self.identifier_pattern(name), init)))
+    }
+
+    #[inline]
+    pub fn return_statement(&self, value: Option<ExpressionNode<'ast>>) -> StatementNode<'ast> {
+        self.alloc(ReturnStatement { value })
+    }
+
+    #[inline]
+    pub fn break_statement(&self, label: Option<&'ast str>) -> StatementNode<'ast> {
+        self.alloc(BreakStatement { label: label.map(|label| self.label(label)) })
+    }
+
+    #[inline]
+    pub fn continue_statement(&self, label: Option<&'ast str>) -> StatementNode<'ast> {
+        self.alloc(ContinueStatement { label: label.map(|label| self.label(label)) })
+    }
+
+    #[inline]
+    pub fn throw_statement(&self, value: ExpressionNode<'ast>) -> StatementNode<'ast> {
+        self.alloc(ThrowStatement { value })
+    }
+
+    #[inline]
+    pub fn if_statement(
+        &self,
+        test: ExpressionNode<'ast>,
+        consequent: StatementNode<'ast>,
+        alternate: Option<StatementNode<'ast>>,
+    ) -> StatementNode<'ast> {
+        self.alloc(IfStatement { test, consequent, alternate })
+    }
+
+    #[inline]
+    pub fn while_statement(&self, test: ExpressionNode<'ast>, body: StatementNode<'ast>) -> StatementNode<'ast> {
+        self.alloc(WhileStatement { test, body })
+    }
+
+    #[inline]
+    pub fn do_statement(&self, body: StatementNode<'ast>, test: ExpressionNode<'ast>) -> StatementNode<'ast> {
+        self.alloc(DoStatement { body, test })
+    }
+
+    /// `for (init; test; update) body`, see `for_init`.
+    #[inline]
+    pub fn for_statement(
+        &self,
+        init: Option<Node<'ast, ForInit<'ast>>>,
+        test: Option<ExpressionNode<'ast>>,
+        update: Option<ExpressionNode<'ast>>,
+        body: StatementNode<'ast>,
+    ) -> StatementNode<'ast> {
+        self.alloc(ForStatement { init, test, update, body })
+    }
+
+    /// `for (left in right) body`
+    #[inline]
+    pub fn for_in_statement(&self, left: Node<'ast, ForInit<'ast>>, right: ExpressionNode<'ast>, body: StatementNode<'ast>) -> StatementNode<'ast> {
+        self.alloc(ForInStatement { left, right, body })
+    }
+
+    /// `for (left of right) body`
+    #[inline]
+    pub fn for_of_statement(&self, left: Node<'ast, ForInit<'ast>>, right: ExpressionNode<'ast>, body: StatementNode<'ast>) -> StatementNode<'ast> {
+        self.alloc(ForOfStatement { left, right, body })
+    }
+
+    /// Initializer or left side of a `for` loop from a declaration statement,
+    /// or any other statement holding an expression.
+    pub fn for_init(&self, statement: StatementNode<'ast>) -> Option<Node<'ast, ForInit<'ast>>> {
+        match statement.item {
+            Statement::Declaration(declaration) => Some(self.alloc(ForInit::Declaration(declaration))),
+            Statement::Expression(expression)   => Some(self.alloc(ForInit::Expression(expression))),
+            _                                   => None,
+        }
+    }
+
+    /// `try {} catch (err) {} finally {}`
+    #[inline]
+    pub fn try_statement(
+        &self,
+        block: BlockNode<'ast, Statement<'ast>>,
+        handler: Option<Node<'ast, CatchClause<'ast>>>,
+        finalizer: Option<BlockNode<'ast, Statement<'ast>>>,
+    ) -> StatementNode<'ast> {
+        self.alloc(TryStatement { block, handler, finalizer })
+    }
+
+    /// `catch (param) {}`
+    #[inline]
+    pub fn catch_clause<I>(&self, param: Node<'ast, Pattern<'ast>>, body: I) -> Node<'ast, CatchClause<'ast>> where
+        I: IntoIterator<Item = StatementNode<'ast>>,
+    {
+        self.alloc(CatchClause { param, body: self.block(body) })
+    }
+
+    #[inline]
+    pub fn block_statement<I>(&self, body: I) -> StatementNode<'ast> where
+        I: IntoIterator<Item = StatementNode<'ast>>,
+    {
+        self.alloc(BlockStatement { body: self.list(body) })
+    }
+
+    #[inline]
+    pub fn labeled_statement(&self, label: &'ast str, body: StatementNode<'ast>) -> StatementNode<'ast> {
+        self.alloc(LabeledStatement {
+            label: self.label(label),
+            body,
+        })
+    }
+
+    #[inline]
+    pub fn switch_statement<I>(&self, discriminant: ExpressionNode<'ast>, cases: I) -> StatementNode<'ast> where
+        I: IntoIterator<Item = Node<'ast, SwitchCase<'ast>>>,
+    {
+        self.alloc(SwitchStatement {
+            discriminant,
+            cases: self.block(cases),
+        })
+    }
+
+    /// `case test:`, or `default:` without a test.
+    #[inline]
+    pub fn switch_case<I>(&self, test: Option<ExpressionNode<'ast>>, consequent: I) -> Node<'ast, SwitchCase<'ast>> where
+        I: IntoIterator<Item = StatementNode<'ast>>,
+    {
+        self.alloc(SwitchCase {
+            test,
+            consequent: self.list(consequent),
+        })
+    }
+
+    /// `function foo(a) {}`
+    #[inline]
+    pub fn function_statement<P, B>(&self, name: &'ast str, params: P, body: B) -> StatementNode<'ast> where
+        P: IntoIterator<Item = Node<'ast, Pattern<'ast>>>,
+        B: IntoIterator<Item = StatementNode<'ast>>,
+    {
+        let name = MandatoryName(self.identifier(name));
+
+        self.alloc(self.function(name, params, body))
+    }
+
+    /// `class Foo extends Bar {}`
+    #[inline]
+    pub fn class_statement<I>(&self, name: &'ast str, extends: Option<ExpressionNode<'ast>>, body: I) -> StatementNode<'ast> where
+        I: IntoIterator<Item = Node<'ast, ClassMember<'ast>>>,
+    {
+        let name = MandatoryName(self.identifier(name));
+
+        self.alloc(self.class(name, extends, body))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{parse_expression, parse_statement, parse_with_options, ParseOptions};
+
+    #[test]
+    fn builds_expressions() {
+        let arena = Arena::new();
+        let b = AstBuilder::new(&arena);
+
+        let math_pow = b.member_expression(b.identifier_expression("Math"), "pow");
+        let expected = parse_expression("Math.pow(a, 2) + b[0]", &arena).unwrap();
+
+        assert_eq!(b.binary_expression(
+            OperatorKind::Addition,
+            b.call_expression(math_pow, vec![b.identifier_expression("a"), b.number_literal("2")]),
+            b.computed_member_expression(b.identifier_expression("b"), b.number_literal("0")),
+        ), expected);
+
+        assert_eq!(
+            b.new_expression(b.identifier_expression("Foo"), None),
+            parse_expression("new Foo()", &arena).unwrap(),
+        );
+
+        assert_eq!(
+            b.object_expression(vec![
+                b.shorthand_property("a"),
+                b.property(b.property_key("b"), b.boolean_literal(true)),
+                b.spread_property(b.identifier_expression("c")),
+            ]),
+            parse_expression("{ a, b: true, ...c }", &arena).unwrap(),
+        );
+    }
+
+    #[test]
+    fn builds_statements() {
+        let arena = Arena::new();
+        let b = AstBuilder::new(&arena);
+
+        let loop_body = b.block_statement(vec![
+            b.if_statement(b.identifier_expression("done"), b.break_statement(Some("outer")), None),
+        ]);
+
+        assert_eq!(
+            b.labeled_statement("outer", b.while_statement(b.boolean_literal(true), loop_body)),
+            parse_statement("outer: while (true) { if (done) break outer; }", &arena).unwrap(),
+        );
+
+        assert_eq!(
+            b.variable_declaration(DeclarationKind::Const, "foo", Some(b.string_literal("bar"))),
+            parse_statement("const foo = \"bar\";", &arena).unwrap(),
+        );
+    }
+
+    #[test]
+    fn builds_functions_and_classes() {
+        let arena = Arena::new();
+        let b = AstBuilder::new(&arena);
+
+        let params = vec![
+            b.identifier_pattern("a"),
+            b.assignment_pattern(b.identifier_pattern("b"), b.number_literal("1")),
+        ];
+        let body = Some(b.return_statement(Some(b.binary_expression(
+            OperatorKind::Addition,
+            b.identifier_expression("a"),
+            b.identifier_expression("b"),
+        ))));
+
+        assert_eq!(
+            b.function_statement("add", params, body),
+            parse_statement("function add(a, b = 1) { return a + b; }", &arena).unwrap(),
+        );
+
+        let constructor = b.class_method(MethodKind::Constructor, false, b.property_key("constructor"), b.method(None, None));
+
+        assert_eq!(
+            b.class_statement("Foo", Some(b.identifier_expression("Bar")), Some(constructor)),
+            parse_statement("class Foo extends Bar { constructor() {} }", &arena).unwrap(),
+        );
+    }
+
+    #[test]
+    fn builds_parentheses_and_meta_properties() {
+        let arena = Arena::new();
+        let b = AstBuilder::new(&arena);
+        let options = ParseOptions { parenthesized: true, ..ParseOptions::default() };
+        let module = parse_with_options("(function () { return new.target; })();", options).unwrap();
+
+        let function = b.function_expression(None, None, Some(
+            b.return_statement(Some(b.meta_property_expression("new", "target"))),
+        ));
+
+        assert_eq!(
+            b.expression_statement(b.call_expression(b.parenthesized_expression(function), None)),
+            *module.body().only_element().unwrap(),
+        );
+    }
+
+    #[test]
+    fn builds_jsx() {
+        let arena = Arena::new();
+        let b = AstBuilder::new(&arena);
+        let options = ParseOptions { jsx: true, ..ParseOptions::default() };
+        let module = parse_with_options("<foo.Bar baz=\"qux\" {...props}>a{b}<br /></foo.Bar>;", options).unwrap();
+
+        let name = b.jsx_member_name(b.jsx_name("foo"), "Bar");
+        let element = b.jsx_element(
+            name,
+            vec![
+                b.jsx_attribute("baz", Some(JSXAttributeValue::String("\"qux\""))),
+                b.jsx_spread_attribute(b.identifier_expression("props")),
+            ],
+            vec![
+                b.jsx_text("a"),
+                b.jsx_expression_child(Some(b.identifier_expression("b"))),
+                b.jsx_element_child(b.jsx_self_closing_element(b.jsx_name("br"), None)),
+            ],
+        );
+
+        assert_eq!(b.expression_statement(b.alloc(element)), *module.body().only_element().unwrap());
+
+        let closing = element.closing.unwrap();
+
+        assert_eq!(closing.name, name);
+        assert!(!std::ptr::eq(&*closing.name, &*name));
+    }
+
+    #[test]
+    fn escapes_string_literals() {
+        let arena = Arena::new();
+        let b = AstBuilder::new(&arena);
+
+        assert_eq!(b.string_literal("a \"b\"\n").item, Expression::Literal(Literal::String("\"a \\\"b\\\"\\n\"")));
+    }
+
+    #[test]
+    fn nodes_are_synthetic() {
+        let arena = Arena::new();
+        let b = AstBuilder::new(&arena);
+
+        assert!(b.identifier_expression("foo").is_synthetic());
+    }
+}
@@ -0,0 +1,951 @@
+use toolshed::Arena;
+
+use crate::ast::{Node, NodeList, Loc, Block, Pattern, Literal, OperatorKind, DeclarationKind, Label};
+use crate::ast::{Statement, Expression, Property, PropertyKey, Decorator};
+use crate::ast::{Function, Class, ClassMember, MethodKind, Name, EmptyName, OptionalName, MandatoryName};
+use crate::ast::expression::*;
+use crate::ast::statement::*;
+use crate::ast::jsx::*;
+use crate::ast::types::*;
+use crate::template::{Substitution, placeholder_name};
+use crate::error::TemplateErrorKind;
+
+/// Deep copy of an AST node, with all of its children, allocated in an
+/// arena. Implemented for all nodes, lists of nodes and their items.
+///
+/// Nodes are `Copy` handles, copying one aliases the same node, so that
+/// swapping one copy changes all of them. Clones are independent of the
+/// original, and can be made into the arena of another `Module`, even
+/// after the original has been dropped. Clones keep the positions of
+/// the original nodes, which refer to its source.
+pub trait DeepClone<'ast> {
+    type Cloned;
+
+    fn clone_in(&self, arena: &'ast Arena) -> Self::Cloned;
+}
+
+impl<'ast, T: CloneWith<'ast>> DeepClone<'ast> for T {
+    type Cloned = T::Cloned;
+
+    #[inline]
+    fn clone_in(&self, arena: &'ast Arena) -> T::Cloned {
+        self.clone_with(&mut Cloner::new(arena))
+    }
+}
+
+/// Implementation of `DeepClone`, which also replaces placeholders of
+/// templates with their substitutions.
+pub trait CloneWith<'ast>: Sized {
+    type Cloned: Copy + 'ast;
+
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned;
+
+    #[inline]
+    fn clone_node(node: &Node<'_, Self>, cloner: &mut Cloner<'ast, '_>) -> Node<'ast, Self::Cloned> where
+        Self: Copy,
+    {
+        let item = node.item.clone_with(cloner);
+
+        cloner.alloc(node, item)
+    }
+
+    #[inline]
+    fn clone_list(list: &NodeList<'_, Self>, cloner: &mut Cloner<'ast, '_>) -> NodeList<'ast, Self::Cloned> where
+        Self: Copy,
+    {
+        let arena = cloner.arena;
+
+        NodeList::from_iter(arena, list.iter().map(|node| Self::clone_node(node, cloner)))
+    }
+}
+
+pub struct Cloner<'ast, 's> {
+    pub arena: &'ast Arena,
+    /// Substitutions when cloning a template, `None` otherwise
+    substitutions: Option<&'s [(&'s str, Substitution<'ast>)]>,
+    used: Vec<bool>,
+    /// First error found while cloning a template
+    error: Option<TemplateErrorKind>,
+}
+
+impl<'ast, 's> Cloner<'ast, 's> {
+    pub fn new(arena: &'ast Arena) -> Self {
+        Cloner {
+            arena,
+            substitutions: None,
+            used: Vec::new(),
+            error: None,
+        }
+    }
+
+    pub fn template(arena: &'ast Arena, substitutions: &'s [(&'s str, Substitution<'ast>)]) -> Self {
+        Cloner {
+            arena,
+            substitutions: Some(substitutions),
+            used: vec![false; substitutions.len()],
+            error: None,
+        }
+    }
+
+    /// Clones of templates are synthetic, they don't come from the source
+    /// of the arena they are cloned to.
+    #[inline]
+    fn alloc<T: Copy, L>(&self, node: &Node<'_, L>, item: T) -> Node<'ast, T> {
+        let loc = match self.substitutions {
+            Some(_) => Loc::synthetic(item),
+            None    => Loc::new(node.start, node.end, item),
+        };
+
+        Node::new(self.arena.alloc(loc))
+    }
+
+    /// Substitution of the placeholder `name` stands for, if any. A missing
+    /// substitution is recorded as an error, the placeholder is kept.
+    fn substitute<'n>(&mut self, name: &'n str) -> Option<(&'n str, Substitution<'ast>)> {
+        let substitutions = self.substitutions?;
+        let name = placeholder_name(name)?;
+
+        match substitutions.iter().position(|&(key, _)| key == name) {
+            Some(index) => {
+                let substitution = substitutions[index].1;
+
+                // Placeholders used more than once get clones, so that
+                // they don't alias the same node
+                if self.used[index] {
+                    return Some((name, substitution.clone_in(self.arena)));
+                }
+
+                self.used[index] = true;
+
+                Some((name, substitution))
+            },
+            None => {
+                self.fail(TemplateErrorKind::MissingSubstitution(name.to_owned()));
+
+                None
+            },
+        }
+    }
+
+    /// Unwrap the result of a substitution, recording the error if any.
+    fn check<T>(&mut self, result: Result<T, TemplateErrorKind>) -> Option<T> {
+        match result {
+            Ok(item) => Some(item),
+            Err(error) => {
+                self.fail(error);
+
+                None
+            },
+        }
+    }
+
+    #[inline]
+    fn fail(&mut self, error: TemplateErrorKind) {
+        if self.error.is_none() {
+            self.error = Some(error);
+        }
+    }
+
+    /// First error found while cloning a template.
+    pub fn error(&mut self) -> Option<TemplateErrorKind> {
+        self.error.take()
+    }
+
+    /// Name of a substitution that didn't replace any placeholder.
+    pub fn unused(&self) -> Option<&'s str> {
+        let substitutions = self.substitutions?;
+
+        self.used.iter()
+            .position(|used| !used)
+            .map(|index| substitutions[index].0)
+    }
+}
+
+impl<'a, 'ast, T> CloneWith<'ast> for Node<'a, T> where
+    T: CloneWith<'ast> + Copy,
+{
+    type Cloned = Node<'ast, T::Cloned>;
+
+    #[inline]
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned {
+        T::clone_node(self, cloner)
+    }
+}
+
+impl<'a, 'ast, T> CloneWith<'ast> for NodeList<'a, T> where
+    T: CloneWith<'ast> + Copy,
+{
+    type Cloned = NodeList<'ast, T::Cloned>;
+
+    #[inline]
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned {
+        T::clone_list(self, cloner)
+    }
+}
+
+impl<'ast, T: CloneWith<'ast>> CloneWith<'ast> for Option<T> {
+    type Cloned = Option<T::Cloned>;
+
+    #[inline]
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned {
+        self.as_ref().map(|item| item.clone_with(cloner))
+    }
+}
+
+impl<'ast> CloneWith<'ast> for &str {
+    type Cloned = &'ast str;
+
+    #[inline]
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> &'ast str {
+        cloner.arena.alloc_str(self)
+    }
+
+    #[inline]
+    fn clone_node(node: &Node<'_, Self>, cloner: &mut Cloner<'ast, '_>) -> Node<'ast, &'ast str> {
+        let substituted = cloner.substitute(node.item)
+            .and_then(|(name, substitution)| cloner.check(substitution.name(name)));
+
+        let name = match substituted {
+            Some(name) => name,
+            None       => node.item.clone_with(cloner),
+        };
+
+        cloner.alloc(node, name)
+    }
+}
+
+impl<'a, 'ast> CloneWith<'ast> for Literal<'a> {
+    type Cloned = Literal<'ast>;
+
+    #[inline]
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Literal<'ast> {
+        match *self {
+            Literal::Undefined     => Literal::Undefined,
+            Literal::Null          => Literal::Null,
+            Literal::True          => Literal::True,
+            Literal::False         => Literal::False,
+            Literal::Number(value) => Literal::Number(value.clone_with(cloner)),
+            Literal::Binary(value) => Literal::Binary(value.clone_with(cloner)),
+            Literal::String(value) => Literal::String(value.clone_with(cloner)),
+            Literal::RegEx(value)  => Literal::RegEx(value.clone_with(cloner)),
+        }
+    }
+}
+
+impl<'a, 'ast> CloneWith<'ast> for Label<'a> {
+    type Cloned = Label<'ast>;
+
+    #[inline]
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Label<'ast> {
+        Label(self.0.clone_with(cloner))
+    }
+}
+
+macro_rules! clone_copy {
+    ($( $type:ty ),*) => ($(
+        impl<'ast> CloneWith<'ast> for $type {
+            type Cloned = $type;
+
+            #[inline]
+            fn clone_with(&self, _: &mut Cloner<'ast, '_>) -> Self {
+                *self
+            }
+        }
+    )*)
+}
+
+macro_rules! clone_struct {
+    ($( $type:ident { $( $field:ident ),* } )*) => ($(
+        impl<'a, 'ast> CloneWith<'ast> for $type<'a> {
+            type Cloned = $type<'ast>;
+
+            #[inline]
+            fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> $type<'ast> {
+                $type {
+                    $( $field: self.$field.clone_with(cloner), )*
+                }
+            }
+        }
+    )*)
+}
+
+clone_copy! {
+    bool, OperatorKind, DeclarationKind, MethodKind, EmptyName, ThisExpression, SuperExpression,
+    JSXOpeningFragment, JSXClosingFragment, Primitive, TypeOperator, Modifiers, TypeImportKind
+}
+
+clone_struct! {
+    Decorator { expression }
+    SequenceExpression { body }
+    ParenthesizedExpression { expression }
+    ArrayExpression { body }
+    MemberExpression { object, property }
+    MetaPropertyExpression { meta, property }
+    ComputedMemberExpression { object, property }
+    CallExpression { callee, arguments }
+    BinaryExpression { operator, left, right }
+    PrefixExpression { operator, operand }
+    PostfixExpression { operator, operand }
+    ConditionalExpression { test, consequent, alternate }
+    TemplateLiteral { expressions, quasis }
+    TaggedTemplateExpression { tag, quasi }
+    SpreadExpression { argument }
+    AwaitExpression { argument }
+    ArrowExpression { type_parameters, params, return_type, body }
+    AsExpression { expression, annotation }
+    SatisfiesExpression { expression, annotation }
+    TypeCastExpression { expression, annotation }
+    NonNullExpression { expression }
+    TypeAssertion { annotation, expression }
+    InstantiationExpression { expression, arguments }
+    ObjectExpression { body }
+    Declarator { id, init }
+    DeclarationStatement { kind, declarators }
+    ReturnStatement { value }
+    BreakStatement { label }
+    ContinueStatement { label }
+    ThrowStatement { value }
+    IfStatement { test, consequent, alternate }
+    WhileStatement { test, body }
+    DoStatement { body, test }
+    ForStatement { init, test, update, body }
+    ForInStatement { left, right, body }
+    ForOfStatement { left, right, body }
+    CatchClause { param, body }
+    TryStatement { block, handler, finalizer }
+    LabeledStatement { label, body }
+    SwitchStatement { discriminant, cases }
+    SwitchCase { test, consequent }
+    JSXMemberExpression { object, property }
+    JSXAttribute { name, value }
+    JSXSpreadAttribute { argument }
+    JSXExpressionContainer { expression }
+    JSXOpeningElement { name, attributes, self_closing }
+    JSXClosingElement { name }
+    JSXElement { opening, children, closing }
+    JSXFragment { opening, children, closing }
+    TypeParameter { name, constraint, default }
+    FunctionType { type_parameters, params, return_type }
+    InterfaceDeclaration { name, type_parameters, extends, body }
+    TypeAliasDeclaration { name, type_parameters, value }
+    FunctionSignature { name, signature }
+    DeclareStatement { body }
+    OpaqueTypeDeclaration { name, type_parameters, supertype, value }
+    TypeImportDeclaration { kind, specifiers, source }
+    EnumMember { name, value }
+    EnumDeclaration { is_const, name, members }
+    NamespaceDeclaration { name, body }
+    ExportDeclaration { is_default, declaration }
+}
+
+impl<'a, 'ast> CloneWith<'ast> for MandatoryName<'a> {
+    type Cloned = MandatoryName<'ast>;
+
+    #[inline]
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned {
+        MandatoryName(self.0.clone_with(cloner))
+    }
+}
+
+impl<'a, 'ast> CloneWith<'ast> for OptionalName<'a> {
+    type Cloned = OptionalName<'ast>;
+
+    #[inline]
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned {
+        OptionalName(self.0.clone_with(cloner))
+    }
+}
+
+impl<'a, 'ast, T> CloneWith<'ast> for Block<'a, T> where
+    T: CloneWith<'ast> + Copy,
+{
+    type Cloned = Block<'ast, T::Cloned>;
+
+    #[inline]
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned {
+        Block {
+            body: self.body.clone_with(cloner),
+        }
+    }
+}
+
+impl<'a, 'ast, N> CloneWith<'ast> for Function<'a, N> where
+    N: Name<'a> + CloneWith<'ast>,
+    N::Cloned: Name<'ast>,
+{
+    type Cloned = Function<'ast, N::Cloned>;
+
+    #[inline]
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned {
+        Function {
+            name: self.name.clone_with(cloner),
+            generator: self.generator,
+            type_parameters: self.type_parameters.clone_with(cloner),
+            params: self.params.clone_with(cloner),
+            return_type: self.return_type.clone_with(cloner),
+            body: self.body.clone_with(cloner),
+        }
+    }
+}
+
+impl<'a, 'ast, N> CloneWith<'ast> for Class<'a, N> where
+    N: Name<'a> + CloneWith<'ast>,
+    N::Cloned: Name<'ast>,
+{
+    type Cloned = Class<'ast, N::Cloned>;
+
+    #[inline]
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned {
+        Class {
+            decorators: self.decorators.clone_with(cloner),
+            name: self.name.clone_with(cloner),
+            type_parameters: self.type_parameters.clone_with(cloner),
+            extends: self.extends.clone_with(cloner),
+            super_type_arguments: self.super_type_arguments.clone_with(cloner),
+            implements: self.implements.clone_with(cloner),
+            body: self.body.clone_with(cloner),
+        }
+    }
+}
+
+impl<'a, 'ast> CloneWith<'ast> for ClassMember<'a> {
+    type Cloned = ClassMember<'ast>;
+
+    #[inline]
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned {
+        match *self {
+            ClassMember::Error => ClassMember::Error,
+            ClassMember::Method { is_static, key, kind, value } => ClassMember::Method {
+                is_static,
+                key: key.clone_with(cloner),
+                kind,
+                value: value.clone_with(cloner),
+            },
+            ClassMember::Literal { is_static, key, value } => ClassMember::Literal {
+                is_static,
+                key: key.clone_with(cloner),
+                value: value.clone_with(cloner),
+            },
+            ClassMember::Property { is_static, key, optional, annotation, value } => ClassMember::Property {
+                is_static,
+                key: key.clone_with(cloner),
+                optional,
+                annotation: annotation.clone_with(cloner),
+                value: value.clone_with(cloner),
+            },
+            ClassMember::Accessor { is_static, key, annotation, value } => ClassMember::Accessor {
+                is_static,
+                key: key.clone_with(cloner),
+                annotation: annotation.clone_with(cloner),
+                value: value.clone_with(cloner),
+            },
+            ClassMember::Signature { is_static, key, kind, signature } => ClassMember::Signature {
+                is_static,
+                key: key.clone_with(cloner),
+                kind,
+                signature: signature.clone_with(cloner),
+            },
+            ClassMember::Modified { modifiers, member } => ClassMember::Modified {
+                modifiers,
+                member: member.clone_with(cloner),
+            },
+            ClassMember::Decorated { decorators, member } => ClassMember::Decorated {
+                decorators: decorators.clone_with(cloner),
+                member: member.clone_with(cloner),
+            },
+        }
+    }
+}
+
+impl<'a, 'ast> CloneWith<'ast> for PropertyKey<'a> {
+    type Cloned = PropertyKey<'ast>;
+
+    #[inline]
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned {
+        match *self {
+            PropertyKey::Computed(expression) => PropertyKey::Computed(expression.clone_with(cloner)),
+            PropertyKey::Literal(key)         => PropertyKey::Literal(key.clone_with(cloner)),
+            PropertyKey::Binary(key)          => PropertyKey::Binary(key.clone_with(cloner)),
+        }
+    }
+}
+
+impl<'a, 'ast> CloneWith<'ast> for Property<'a> {
+    type Cloned = Property<'ast>;
+
+    #[inline]
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned {
+        match *self {
+            Property::Shorthand(name) => Property::Shorthand(name.clone_with(cloner)),
+            Property::Literal { key, value } => Property::Literal {
+                key: key.clone_with(cloner),
+                value: value.clone_with(cloner),
+            },
+            Property::Method { key, value } => Property::Method {
+                key: key.clone_with(cloner),
+                value: value.clone_with(cloner),
+            },
+            Property::Spread { argument } => Property::Spread {
+                argument: argument.clone_with(cloner),
+            },
+        }
+    }
+}
+
+impl<'a, 'ast> CloneWith<'ast> for Pattern<'a> {
+    type Cloned = Pattern<'ast>;
+
+    #[inline]
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned {
+        match *self {
+            Pattern::Void => Pattern::Void,
+            Pattern::Identifier(name) => Pattern::Identifier(name.clone_with(cloner)),
+            Pattern::ObjectPattern { properties } => Pattern::ObjectPattern {
+                properties: properties.clone_with(cloner),
+            },
+            Pattern::ArrayPattern { elements } => Pattern::ArrayPattern {
+                elements: elements.clone_with(cloner),
+            },
+            Pattern::RestElement { argument } => Pattern::RestElement {
+                argument: argument.clone_with(cloner),
+            },
+            Pattern::AssignmentPattern { left, right } => Pattern::AssignmentPattern {
+                left: left.clone_with(cloner),
+                right: right.clone_with(cloner),
+            },
+            Pattern::Annotated { pattern, optional, annotation } => Pattern::Annotated {
+                pattern: pattern.clone_with(cloner),
+                optional,
+                annotation: annotation.clone_with(cloner),
+            },
+            Pattern::ParameterProperty { modifiers, parameter } => Pattern::ParameterProperty {
+                modifiers,
+                parameter: parameter.clone_with(cloner),
+            },
+        }
+    }
+}
+
+impl<'a, 'ast> CloneWith<'ast> for ArrowBody<'a> {
+    type Cloned = ArrowBody<'ast>;
+
+    #[inline]
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned {
+        match *self {
+            ArrowBody::Expression(expression) => ArrowBody::Expression(expression.clone_with(cloner)),
+            ArrowBody::Block(block)           => ArrowBody::Block(block.clone_with(cloner)),
+        }
+    }
+}
+
+impl<'a, 'ast> CloneWith<'ast> for ForInit<'a> {
+    type Cloned = ForInit<'ast>;
+
+    #[inline]
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned {
+        match *self {
+            ForInit::Declaration(declaration) => ForInit::Declaration(declaration.clone_with(cloner)),
+            ForInit::Expression(expression)   => ForInit::Expression(expression.clone_with(cloner)),
+        }
+    }
+}
+
+impl<'a, 'ast> CloneWith<'ast> for JSXElementName<'a> {
+    type Cloned = JSXElementName<'ast>;
+
+    #[inline]
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned {
+        match *self {
+            JSXElementName::Identifier(name) => JSXElementName::Identifier(name.clone_with(cloner)),
+            JSXElementName::Namespaced { namespace, name } => JSXElementName::Namespaced {
+                namespace: namespace.clone_with(cloner),
+                name: name.clone_with(cloner),
+            },
+            JSXElementName::Member(ref member) => JSXElementName::Member(member.clone_with(cloner)),
+        }
+    }
+}
+
+impl<'a, 'ast> CloneWith<'ast> for JSXAttributeValue<'a> {
+    type Cloned = JSXAttributeValue<'ast>;
+
+    #[inline]
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned {
+        match *self {
+            JSXAttributeValue::String(value)        => JSXAttributeValue::String(value.clone_with(cloner)),
+            JSXAttributeValue::Expression(ref expr) => JSXAttributeValue::Expression(expr.clone_with(cloner)),
+            JSXAttributeValue::Element(ref element) => JSXAttributeValue::Element(element.clone_with(cloner)),
+            JSXAttributeValue::Fragment(ref frag)   => JSXAttributeValue::Fragment(frag.clone_with(cloner)),
+        }
+    }
+}
+
+impl<'a, 'ast> CloneWith<'ast> for JSXAttributeItem<'a> {
+    type Cloned = JSXAttributeItem<'ast>;
+
+    #[inline]
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned {
+        match *self {
+            JSXAttributeItem::Attribute(ref attribute) => JSXAttributeItem::Attribute(attribute.clone_with(cloner)),
+            JSXAttributeItem::Spread(ref attribute)    => JSXAttributeItem::Spread(attribute.clone_with(cloner)),
+        }
+    }
+}
+
+impl<'a, 'ast> CloneWith<'ast> for JSXChild<'a> {
+    type Cloned = JSXChild<'ast>;
+
+    #[inline]
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned {
+        match *self {
+            JSXChild::Text(text)            => JSXChild::Text(text.clone_with(cloner)),
+            JSXChild::Expression(ref expr)  => JSXChild::Expression(expr.clone_with(cloner)),
+            JSXChild::Element(ref element)  => JSXChild::Element(element.clone_with(cloner)),
+            JSXChild::Fragment(ref frag)    => JSXChild::Fragment(frag.clone_with(cloner)),
+        }
+    }
+}
+
+impl<'a, 'ast> CloneWith<'ast> for Expression<'a> {
+    type Cloned = Expression<'ast>;
+
+    #[inline]
+    fn clone_node(node: &Node<'_, Self>, cloner: &mut Cloner<'ast, '_>) -> Node<'ast, Expression<'ast>> {
+        if let Expression::Identifier(name) = node.item {
+            let arena = cloner.arena;
+            let substituted = cloner.substitute(name)
+                .and_then(|(name, substitution)| cloner.check(substitution.expression(name, arena)));
+
+            if let Some(expression) = substituted {
+                return expression;
+            }
+        }
+
+        let item = node.item.clone_with(cloner);
+
+        cloner.alloc(node, item)
+    }
+
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned {
+        use self::Expression::*;
+
+        match *self {
+            Void                       => Void,
+            This(this)                 => This(this),
+            Super(this)                => Super(this),
+            Identifier(name)           => Identifier(name.clone_with(cloner)),
+            Literal(literal)           => Literal(literal.clone_with(cloner)),
+            Sequence(ref expr)         => Sequence(expr.clone_with(cloner)),
+            Parenthesized(ref expr)    => Parenthesized(expr.clone_with(cloner)),
+            Array(ref expr)            => Array(expr.clone_with(cloner)),
+            Member(ref expr)           => Member(expr.clone_with(cloner)),
+            ComputedMember(ref expr)   => ComputedMember(expr.clone_with(cloner)),
+            MetaProperty(ref expr)     => MetaProperty(expr.clone_with(cloner)),
+            Call(ref expr)             => Call(expr.clone_with(cloner)),
+            Binary(ref expr)           => Binary(expr.clone_with(cloner)),
+            Prefix(ref expr)           => Prefix(expr.clone_with(cloner)),
+            Postfix(ref expr)          => Postfix(expr.clone_with(cloner)),
+            Conditional(ref expr)      => Conditional(expr.clone_with(cloner)),
+            Template(ref expr)         => Template(expr.clone_with(cloner)),
+            TaggedTemplate(ref expr)   => TaggedTemplate(expr.clone_with(cloner)),
+            Spread(ref expr)           => Spread(expr.clone_with(cloner)),
+            Await(ref expr)            => Await(expr.clone_with(cloner)),
+            Arrow(ref expr)            => Arrow(expr.clone_with(cloner)),
+            Object(ref expr)           => Object(expr.clone_with(cloner)),
+            Function(ref expr)         => Function(expr.clone_with(cloner)),
+            Class(ref expr)            => Class(expr.clone_with(cloner)),
+            JSXElement(ref expr)       => JSXElement(expr.clone_with(cloner)),
+            JSXFragment(ref expr)      => JSXFragment(expr.clone_with(cloner)),
+            As(ref expr)               => As(expr.clone_with(cloner)),
+            Satisfies(ref expr)        => Satisfies(expr.clone_with(cloner)),
+            TypeCast(ref expr)         => TypeCast(expr.clone_with(cloner)),
+            NonNull(ref expr)          => NonNull(expr.clone_with(cloner)),
+            TypeAssertion(ref expr)    => TypeAssertion(expr.clone_with(cloner)),
+            Instantiation(ref expr)    => Instantiation(expr.clone_with(cloner)),
+        }
+    }
+}
+
+impl<'a, 'ast> CloneWith<'ast> for Statement<'a> {
+    type Cloned = Statement<'ast>;
+
+    #[inline]
+    fn clone_node(node: &Node<'_, Self>, cloner: &mut Cloner<'ast, '_>) -> Node<'ast, Statement<'ast>> {
+        let arena = cloner.arena;
+        let substituted = statement_placeholder(node, cloner)
+            .and_then(|(name, substitution)| cloner.check(substitution.statement(name, arena)));
+
+        if let Some(statement) = substituted {
+            return statement;
+        }
+
+        let item = node.item.clone_with(cloner);
+
+        cloner.alloc(node, item)
+    }
+
+    /// Placeholders in statement lists can be substituted with any
+    /// number of statements.
+    fn clone_list(list: &NodeList<'_, Self>, cloner: &mut Cloner<'ast, '_>) -> NodeList<'ast, Statement<'ast>> {
+        let mut body = Vec::new();
+
+        for node in list.iter() {
+            match statement_placeholder(node, cloner) {
+                Some((_, Substitution::Statements(statements))) => body.extend(statements.iter().cloned()),
+                Some((name, substitution)) => {
+                    let arena = cloner.arena;
+
+                    if let Some(statement) = cloner.check(substitution.statement(name, arena)) {
+                        body.push(statement);
+                    }
+                },
+                None => body.push(Self::clone_node(node, cloner)),
+            }
+        }
+
+        NodeList::from_iter(cloner.arena, body)
+    }
+
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned {
+        use self::Statement::*;
+
+        match *self {
+            Empty                   => Empty,
+            Expression(ref stmt)    => Expression(stmt.clone_with(cloner)),
+            Declaration(ref stmt)   => Declaration(stmt.clone_with(cloner)),
+            Return(ref stmt)        => Return(stmt.clone_with(cloner)),
+            Break(ref stmt)         => Break(stmt.clone_with(cloner)),
+            Continue(ref stmt)      => Continue(stmt.clone_with(cloner)),
+            Throw(ref stmt)         => Throw(stmt.clone_with(cloner)),
+            If(ref stmt)            => If(stmt.clone_with(cloner)),
+            While(ref stmt)         => While(stmt.clone_with(cloner)),
+            Do(ref stmt)            => Do(stmt.clone_with(cloner)),
+            For(ref stmt)           => For(stmt.clone_with(cloner)),
+            ForIn(ref stmt)         => ForIn(stmt.clone_with(cloner)),
+            ForOf(ref stmt)         => ForOf(stmt.clone_with(cloner)),
+            Try(ref stmt)           => Try(stmt.clone_with(cloner)),
+            Block(ref stmt)         => Block(stmt.clone_with(cloner)),
+            Labeled(ref stmt)       => Labeled(stmt.clone_with(cloner)),
+            Function(ref stmt)      => Function(stmt.clone_with(cloner)),
+            Class(ref stmt)         => Class(stmt.clone_with(cloner)),
+            Switch(ref stmt)        => Switch(stmt.clone_with(cloner)),
+            Interface(ref stmt)     => Interface(stmt.clone_with(cloner)),
+            TypeAlias(ref stmt)     => TypeAlias(stmt.clone_with(cloner)),
+            FunctionSignature(ref stmt) => FunctionSignature(stmt.clone_with(cloner)),
+            Declare(ref stmt)       => Declare(stmt.clone_with(cloner)),
+            OpaqueType(ref stmt)    => OpaqueType(stmt.clone_with(cloner)),
+            TypeImport(ref stmt)    => TypeImport(stmt.clone_with(cloner)),
+            Enum(ref stmt)          => Enum(stmt.clone_with(cloner)),
+            Namespace(ref stmt)     => Namespace(stmt.clone_with(cloner)),
+            Export(ref stmt)        => Export(stmt.clone_with(cloner)),
+        }
+    }
+}
+
+/// Placeholder standing for a statement, `%foo;`.
+#[inline]
+fn statement_placeholder<'n, 'ast>(node: &Node<'_, Statement<'n>>, cloner: &mut Cloner<'ast, '_>) -> Option<(&'n str, Substitution<'ast>)> {
+    match node.item {
+        Statement::Expression(expression) => match expression.item {
+            Expression::Identifier(name) => cloner.substitute(name),
+            _                            => None,
+        },
+        _ => None,
+    }
+}
+
+impl<'a, 'ast> CloneWith<'ast> for TypeImportSpecifier<'a> {
+    type Cloned = TypeImportSpecifier<'ast>;
+
+    #[inline]
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned {
+        match *self {
+            TypeImportSpecifier::Default(local)   => TypeImportSpecifier::Default(local.clone_with(cloner)),
+            TypeImportSpecifier::Namespace(local) => TypeImportSpecifier::Namespace(local.clone_with(cloner)),
+            TypeImportSpecifier::Named { imported, local } => TypeImportSpecifier::Named {
+                imported: imported.clone_with(cloner),
+                local: local.clone_with(cloner),
+            },
+        }
+    }
+}
+
+impl<'a, 'ast> CloneWith<'ast> for TypeName<'a> {
+    type Cloned = TypeName<'ast>;
+
+    #[inline]
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned {
+        match *self {
+            TypeName::Identifier(name) => TypeName::Identifier(name.clone_with(cloner)),
+            TypeName::Qualified { left, right } => TypeName::Qualified {
+                left: left.clone_with(cloner),
+                right: right.clone_with(cloner),
+            },
+        }
+    }
+}
+
+impl<'a, 'ast> CloneWith<'ast> for TypeMember<'a> {
+    type Cloned = TypeMember<'ast>;
+
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned {
+        match *self {
+            TypeMember::Property { readonly, key, optional, annotation } => TypeMember::Property {
+                readonly,
+                key: key.clone_with(cloner),
+                optional,
+                annotation: annotation.clone_with(cloner),
+            },
+            TypeMember::Method { key, optional, signature } => TypeMember::Method {
+                key: key.clone_with(cloner),
+                optional,
+                signature: signature.clone_with(cloner),
+            },
+            TypeMember::Index { readonly, parameter, key, annotation } => TypeMember::Index {
+                readonly,
+                parameter: parameter.clone_with(cloner),
+                key: key.clone_with(cloner),
+                annotation: annotation.clone_with(cloner),
+            },
+            TypeMember::Call { signature } => TypeMember::Call {
+                signature: signature.clone_with(cloner),
+            },
+            TypeMember::Construct { signature } => TypeMember::Construct {
+                signature: signature.clone_with(cloner),
+            },
+            TypeMember::Spread { argument } => TypeMember::Spread {
+                argument: argument.clone_with(cloner),
+            },
+        }
+    }
+}
+
+impl<'a, 'ast> CloneWith<'ast> for Type<'a> {
+    type Cloned = Type<'ast>;
+
+    fn clone_with(&self, cloner: &mut Cloner<'ast, '_>) -> Self::Cloned {
+        use self::Type::*;
+
+        match *self {
+            Any                        => Any,
+            Unknown                    => Unknown,
+            Never                      => Never,
+            Void                       => Void,
+            Object                     => Object,
+            This                       => This,
+            Primitive(primitive)       => Primitive(primitive),
+            Literal(literal)           => Literal(literal.clone_with(cloner)),
+            Reference { name, arguments } => Reference {
+                name: name.clone_with(cloner),
+                arguments: arguments.clone_with(cloner),
+            },
+            Query { name } => Query {
+                name: name.clone_with(cloner),
+            },
+            Array { element } => Array {
+                element: element.clone_with(cloner),
+            },
+            Tuple { elements } => Tuple {
+                elements: elements.clone_with(cloner),
+            },
+            Optional { argument } => Optional {
+                argument: argument.clone_with(cloner),
+            },
+            Rest { argument } => Rest {
+                argument: argument.clone_with(cloner),
+            },
+            Union { variants } => Union {
+                variants: variants.clone_with(cloner),
+            },
+            Intersection { variants } => Intersection {
+                variants: variants.clone_with(cloner),
+            },
+            Function(ref signature)    => Function(signature.clone_with(cloner)),
+            Constructor(ref signature) => Constructor(signature.clone_with(cloner)),
+            ObjectLiteral { members, exact } => ObjectLiteral {
+                members: members.clone_with(cloner),
+                exact,
+            },
+            Mapped { readonly, parameter, constraint, optional, annotation } => Mapped {
+                readonly,
+                parameter: parameter.clone_with(cloner),
+                constraint: constraint.clone_with(cloner),
+                optional,
+                annotation: annotation.clone_with(cloner),
+            },
+            Operator { operator, argument } => Operator {
+                operator,
+                argument: argument.clone_with(cloner),
+            },
+            IndexedAccess { object, index } => IndexedAccess {
+                object: object.clone_with(cloner),
+                index: index.clone_with(cloner),
+            },
+            Conditional { check, extends, consequent, alternate } => Conditional {
+                check: check.clone_with(cloner),
+                extends: extends.clone_with(cloner),
+                consequent: consequent.clone_with(cloner),
+                alternate: alternate.clone_with(cloner),
+            },
+            Infer { parameter } => Infer {
+                parameter: parameter.clone_with(cloner),
+            },
+            Nullable { argument } => Nullable {
+                argument: argument.clone_with(cloner),
+            },
+            Predicate { parameter, annotation } => Predicate {
+                parameter: parameter.clone_with(cloner),
+                annotation: annotation.clone_with(cloner),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ast::{AstBuilder, ExpressionNode};
+    use crate::{parse, parse_expression};
+
+    #[test]
+    fn clones_are_independent() {
+        let arena = Arena::new();
+        let original = parse_expression("a + b", &arena).unwrap();
+        let clone: ExpressionNode = original.clone_in(&arena);
+
+        assert_eq!(clone, original);
+
+        match clone.item {
+            Expression::Binary(ref binary) => AstBuilder::new(&arena).swap(&binary.left, Expression::Identifier("c")),
+            _ => panic!("Expected a binary expression"),
+        }
+
+        assert_eq!(original, parse_expression("a + b", &arena).unwrap());
+        assert_eq!(clone, parse_expression("c + b", &arena).unwrap());
+    }
+
+    #[test]
+    fn clones_keep_positions() {
+        let arena = Arena::new();
+        let original = parse_expression("foo(bar)", &arena).unwrap();
+        let clone = original.clone_in(&arena);
+
+        // `Loc`s are ignored by `PartialEq`, but not by `Debug`
+        assert_eq!(format!("{:?}", clone), format!("{:?}", original));
+    }
+
+    #[test]
+    fn clones_between_modules() {
+        let target = parse("bar();").unwrap();
+
+        let body = {
+            let source = parse("foo(1, 'a'); class A { b() { return this; } }").unwrap();
+
+            source.body().clone_in(target.arena())
+        };
+
+        target.set_body(body);
+
+        assert_eq!(target.body(), parse("foo(1, 'a'); class A { b() { return this; } }").unwrap().body());
+    }
+}
@@ -1,5 +1,9 @@
-use crate::ast::{Node, NodeList, Literal, OperatorKind, Function, Class, EmptyName, OptionalName};
+use std::borrow::Cow;
+use crate::ast::{Node, NodeList, Literal, template_value, OperatorKind, Function, Class, EmptyName, OptionalName};
 use crate::ast::{Identifier, IdentifierNode, BlockNode, ExpressionNode, Statement, ExpressionList, Pattern};
+use crate::ast::{TypeNode, TypeList};
+use crate::ast::jsx::{JSXElement, JSXFragment};
+use crate::ast::types::TypeParameterList;
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum PropertyKey<'ast> {
@@ -10,7 +14,7 @@ pub enum PropertyKey<'ast> {
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Property<'ast> {
-    Shorthand(&'ast str),
+    Shorthand(IdentifierNode<'ast>),
     Literal {
         key: Node<'ast, PropertyKey<'ast>>,
         value: ExpressionNode<'ast>,
@@ -29,11 +33,22 @@ pub enum Property<'ast> {
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct ThisExpression;
 
+/// `super`, only valid as a callee or object of a member expression.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SuperExpression;
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct SequenceExpression<'ast> {
     pub body: ExpressionList<'ast>
 }
 
+/// `(foo)`, only produced with `ParseOptions::parenthesized` enabled.
+/// Otherwise parentheses leave no trace in the AST.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ParenthesizedExpression<'ast> {
+    pub expression: ExpressionNode<'ast>
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct ArrayExpression<'ast> {
     pub body: ExpressionList<'ast>
@@ -95,6 +110,13 @@ pub struct TemplateLiteral<'ast> {
     pub quasis: NodeList<'ast, &'ast str>,
 }
 
+impl<'ast> TemplateLiteral<'ast> {
+    /// Cooked values of all `quasis`, see `ast::template_value`.
+    pub fn cooked(&self) -> impl Iterator<Item = Option<Cow<'ast, str>>> + 'ast {
+        self.quasis.iter().map(|quasi| template_value(quasi.item))
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct TaggedTemplateExpression<'ast> {
     pub tag: ExpressionNode<'ast>,
@@ -106,6 +128,13 @@ pub struct SpreadExpression<'ast> {
     pub argument: ExpressionNode<'ast>
 }
 
+/// `await foo`, only produced at the top level with
+/// `ParseOptions::top_level_await` enabled.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct AwaitExpression<'ast> {
+    pub argument: ExpressionNode<'ast>
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum ArrowBody<'ast> {
     Expression(ExpressionNode<'ast>),
@@ -114,7 +143,9 @@ pub enum ArrowBody<'ast> {
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct ArrowExpression<'ast> {
+    pub type_parameters: TypeParameterList<'ast>,
     pub params: NodeList<'ast, Pattern<'ast>>,
+    pub return_type: Option<TypeNode<'ast>>,
     pub body: ArrowBody<'ast>,
 }
 
@@ -123,6 +154,47 @@ pub struct ObjectExpression<'ast> {
     pub body: NodeList<'ast, Property<'ast>>,
 }
 
+/// `foo as Bar`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct AsExpression<'ast> {
+    pub expression: ExpressionNode<'ast>,
+    pub annotation: TypeNode<'ast>,
+}
+
+/// `foo satisfies Bar`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SatisfiesExpression<'ast> {
+    pub expression: ExpressionNode<'ast>,
+    pub annotation: TypeNode<'ast>,
+}
+
+/// `(foo: Bar)`, a Flow type cast including its parentheses
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TypeCastExpression<'ast> {
+    pub expression: ExpressionNode<'ast>,
+    pub annotation: TypeNode<'ast>,
+}
+
+/// `foo!`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct NonNullExpression<'ast> {
+    pub expression: ExpressionNode<'ast>,
+}
+
+/// `<Bar>foo`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TypeAssertion<'ast> {
+    pub annotation: TypeNode<'ast>,
+    pub expression: ExpressionNode<'ast>,
+}
+
+/// `foo<Bar>`, also used as the callee of calls with type arguments
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct InstantiationExpression<'ast> {
+    pub expression: ExpressionNode<'ast>,
+    pub arguments: TypeList<'ast>,
+}
+
 pub type FunctionExpression<'ast> = Function<'ast, OptionalName<'ast>>;
 pub type ClassExpression<'ast> = Class<'ast, OptionalName<'ast>>;
 
@@ -130,9 +202,11 @@ pub type ClassExpression<'ast> = Class<'ast, OptionalName<'ast>>;
 pub enum Expression<'ast> {
     Void,
     This(ThisExpression),
+    Super(SuperExpression),
     Identifier(Identifier<'ast>),
     Literal(Literal<'ast>),
     Sequence(SequenceExpression<'ast>),
+    Parenthesized(ParenthesizedExpression<'ast>),
     Array(ArrayExpression<'ast>),
     Member(MemberExpression<'ast>),
     ComputedMember(ComputedMemberExpression<'ast>),
@@ -145,10 +219,19 @@ pub enum Expression<'ast> {
     Template(TemplateLiteral<'ast>),
     TaggedTemplate(TaggedTemplateExpression<'ast>),
     Spread(SpreadExpression<'ast>),
+    Await(AwaitExpression<'ast>),
     Arrow(ArrowExpression<'ast>),
     Object(ObjectExpression<'ast>),
     Function(FunctionExpression<'ast>),
     Class(ClassExpression<'ast>),
+    JSXElement(JSXElement<'ast>),
+    JSXFragment(JSXFragment<'ast>),
+    As(AsExpression<'ast>),
+    Satisfies(SatisfiesExpression<'ast>),
+    TypeCast(TypeCastExpression<'ast>),
+    NonNull(NonNullExpression<'ast>),
+    TypeAssertion(TypeAssertion<'ast>),
+    Instantiation(InstantiationExpression<'ast>),
 }
 
 macro_rules! impl_from {
@@ -164,9 +247,11 @@ macro_rules! impl_from {
 
 impl_from! {
     ThisExpression => This,
+    SuperExpression => Super,
     Identifier<'ast> => Identifier,
     Literal<'ast> => Literal,
     SequenceExpression<'ast> => Sequence,
+    ParenthesizedExpression<'ast> => Parenthesized,
     ArrayExpression<'ast> => Array,
     MemberExpression<'ast> => Member,
     ComputedMemberExpression<'ast> => ComputedMember,
@@ -179,23 +264,70 @@ impl_from! {
     TemplateLiteral<'ast> => Template,
     TaggedTemplateExpression<'ast> => TaggedTemplate,
     SpreadExpression<'ast> => Spread,
+    AwaitExpression<'ast> => Await,
     ArrowExpression<'ast> => Arrow,
     ObjectExpression<'ast> => Object,
     FunctionExpression<'ast> => Function,
-    ClassExpression<'ast> => Class
+    ClassExpression<'ast> => Class,
+    JSXElement<'ast> => JSXElement,
+    JSXFragment<'ast> => JSXFragment,
+    AsExpression<'ast> => As,
+    SatisfiesExpression<'ast> => Satisfies,
+    TypeCastExpression<'ast> => TypeCast,
+    NonNullExpression<'ast> => NonNull,
+    TypeAssertion<'ast> => TypeAssertion,
+    InstantiationExpression<'ast> => Instantiation
 }
 
 impl<'ast> Expression<'ast> {
+    /// Expression wrapped by a TypeScript or Flow construct, which is all that
+    /// remains of it once types are erased.
+    #[inline]
+    pub fn type_erased(&self) -> Option<&ExpressionNode<'ast>> {
+        use self::Expression::*;
+
+        match *self {
+            As(AsExpression { ref expression, .. })                     |
+            Satisfies(SatisfiesExpression { ref expression, .. })       |
+            TypeCast(TypeCastExpression { ref expression, .. })         |
+            NonNull(NonNullExpression { ref expression })               |
+            TypeAssertion(self::TypeAssertion { ref expression, .. })   |
+            Instantiation(InstantiationExpression { ref expression, .. }) => Some(expression),
+            _ => None,
+        }
+    }
+
+    /// Left operand, object or callee of a link in a chain of binary
+    /// operators, member accesses or calls, such as `a + b`, `a.b`, `a[b]`
+    /// or `a(b)`. Such chains can grow arbitrarily long, so they should be
+    /// walked in a loop rather than recursively.
+    #[inline]
+    pub fn chained(&self) -> Option<&ExpressionNode<'ast>> {
+        use self::Expression::*;
+
+        match *self {
+            Binary(BinaryExpression { ref left, .. })                         |
+            Member(MemberExpression { object: ref left, .. })                 |
+            ComputedMember(ComputedMemberExpression { object: ref left, .. }) |
+            Call(CallExpression { callee: ref left, .. })                     => Some(left),
+            _ => None,
+        }
+    }
+
     #[inline]
     pub fn binding_power(&self) -> u8 {
         use self::Expression::*;
 
+        if let Some(expression) = self.type_erased() {
+            return expression.binding_power();
+        }
+
         match *self {
             Member(_) | MetaProperty(_) | Arrow(_) => 18,
 
             Call(_) => 17,
 
-            Prefix(_) => 15,
+            Prefix(_) | Await(_) => 15,
 
             Binary(BinaryExpression { ref operator, .. })   |
             Postfix(PostfixExpression { ref operator, .. }) => operator.binding_power(),
@@ -212,11 +344,36 @@ impl<'ast> Expression<'ast> {
     pub fn is_allowed_as_bare_statement(&self) -> bool {
         use self::Expression::*;
 
-        match *self {
-            Object(_)   |
-            Function(_) |
-            Class(_)    => false,
-            _           => true,
+        let mut expression = self;
+
+        // The statement starts with the leftmost operand, which is looked
+        // up in a loop since chains of operators can be arbitrarily long.
+        loop {
+            if let Some(inner) = expression.type_erased() {
+                expression = &inner.item;
+                continue;
+            }
+
+            expression = match *expression {
+                Object(_)   |
+                Function(_) |
+                Class(_)    => return false,
+
+                Binary(BinaryExpression { ref left, .. })                         |
+                Call(CallExpression { callee: ref left, .. })                     |
+                Member(MemberExpression { object: ref left, .. })                 |
+                ComputedMember(ComputedMemberExpression { object: ref left, .. }) |
+                Postfix(PostfixExpression { operand: ref left, .. })              |
+                Conditional(ConditionalExpression { test: ref left, .. })         |
+                TaggedTemplate(TaggedTemplateExpression { tag: ref left, .. })    => &left.item,
+
+                Sequence(SequenceExpression { ref body }) => match body.first_element() {
+                    Some(first) => &first.item,
+                    None        => return true,
+                },
+
+                _ => return true,
+            };
         }
     }
 
@@ -224,13 +381,27 @@ impl<'ast> Expression<'ast> {
     pub fn is_lvalue(&self) -> bool {
         use self::Expression::*;
 
+        if let Some(expression) = self.type_erased() {
+            return expression.is_lvalue();
+        }
+
         match *self {
-            Identifier(_) |
-            Member(_)     |
-            Object(_)     |
-            Array(_)      |
-            Spread(_)     => true,
-            _             => false
+            Identifier(_)     |
+            Member(_)         |
+            ComputedMember(_) |
+            Object(_)         |
+            Array(_)          |
+            Spread(_)         => true,
+
+            // `(foo) = bar` is fine, `([foo]) = bar` is not
+            Parenthesized(ParenthesizedExpression { ref expression }) => match expression.item {
+                Parenthesized(_)  |
+                Identifier(_)     |
+                Member(_)         |
+                ComputedMember(_) => expression.is_lvalue(),
+                _                 => false,
+            },
+            _                 => false
         }
     }
 }
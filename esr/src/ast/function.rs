@@ -1,5 +1,6 @@
-use crate::ast::{Node, Loc, IdentifierNode, ExpressionNode};
-use crate::ast::{BlockNode, Statement, PatternList, PropertyKey};
+use crate::ast::{Node, NodeList, Loc, IdentifierNode, ExpressionNode, SYNTHETIC};
+use crate::ast::{BlockNode, Statement, PatternList, PropertyKey, TypeNode, TypeList};
+use crate::ast::types::{TypeParameterList, FunctionType, Modifiers};
 
 pub trait Name<'ast>: Copy {
     fn empty() -> Self;
@@ -16,6 +17,14 @@ pub struct OptionalName<'ast>(pub Option<IdentifierNode<'ast>>);
 
 pub type Method<'ast> = Function<'ast, EmptyName>;
 
+/// Decorator applied to a class or a class member, `@foo`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Decorator<'ast> {
+    pub expression: ExpressionNode<'ast>,
+}
+
+pub type DecoratorList<'ast> = NodeList<'ast, Decorator<'ast>>;
+
 impl<'ast> Name<'ast> for EmptyName {
     fn empty() -> Self {
         EmptyName
@@ -25,8 +34,8 @@ impl<'ast> Name<'ast> for EmptyName {
 impl<'ast> Name<'ast> for MandatoryName<'ast> {
     fn empty() -> Self {
         MandatoryName(Node::new(&Loc {
-            start: 0,
-            end: 0,
+            start: SYNTHETIC,
+            end: SYNTHETIC,
             item: ""
         }))
     }
@@ -66,7 +75,9 @@ impl<'ast> From<Option<IdentifierNode<'ast>>> for OptionalName<'ast> {
 pub struct Function<'ast, N: Name<'ast>> {
     pub name: N,
     pub generator: bool,
+    pub type_parameters: TypeParameterList<'ast>,
     pub params: PatternList<'ast>,
+    pub return_type: Option<TypeNode<'ast>>,
     pub body: BlockNode<'ast, Statement<'ast>>,
 }
 
@@ -91,12 +102,46 @@ pub enum ClassMember<'ast> {
         is_static: bool,
         key: Node<'ast, PropertyKey<'ast>>,
         value: ExpressionNode<'ast>,
-    }
+    },
+    /// Property with a type annotation, or without a value
+    Property {
+        is_static: bool,
+        key: Node<'ast, PropertyKey<'ast>>,
+        optional: bool,
+        annotation: Option<TypeNode<'ast>>,
+        value: Option<ExpressionNode<'ast>>,
+    },
+    /// Auto-accessor, `accessor foo = 1`
+    Accessor {
+        is_static: bool,
+        key: Node<'ast, PropertyKey<'ast>>,
+        annotation: Option<TypeNode<'ast>>,
+        value: Option<ExpressionNode<'ast>>,
+    },
+    /// Method without a body, such as an overload signature
+    Signature {
+        is_static: bool,
+        key: Node<'ast, PropertyKey<'ast>>,
+        kind: MethodKind,
+        signature: Node<'ast, FunctionType<'ast>>,
+    },
+    Modified {
+        modifiers: Modifiers,
+        member: Node<'ast, ClassMember<'ast>>,
+    },
+    Decorated {
+        decorators: DecoratorList<'ast>,
+        member: Node<'ast, ClassMember<'ast>>,
+    },
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct Class<'ast, N: Name<'ast>> {
+    pub decorators: DecoratorList<'ast>,
     pub name: N,
+    pub type_parameters: TypeParameterList<'ast>,
     pub extends: Option<ExpressionNode<'ast>>,
+    pub super_type_arguments: TypeList<'ast>,
+    pub implements: TypeList<'ast>,
     pub body: BlockNode<'ast, ClassMember<'ast>>,
 }
@@ -0,0 +1,112 @@
+use crate::ast::{Node, NodeList, ExpressionNode, IdentifierNode};
+
+/// Name of a JSX element or attribute. Attribute names are never
+/// member expressions.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum JSXElementName<'ast> {
+    Identifier(&'ast str),
+    Namespaced {
+        namespace: IdentifierNode<'ast>,
+        name: IdentifierNode<'ast>,
+    },
+    Member(JSXMemberExpression<'ast>),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct JSXMemberExpression<'ast> {
+    pub object: Node<'ast, JSXElementName<'ast>>,
+    pub property: IdentifierNode<'ast>,
+}
+
+/// Value of a JSX attribute, strings are stored raw, including quotes.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum JSXAttributeValue<'ast> {
+    String(&'ast str),
+    Expression(JSXExpressionContainer<'ast>),
+    Element(JSXElement<'ast>),
+    Fragment(JSXFragment<'ast>),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct JSXAttribute<'ast> {
+    pub name: Node<'ast, JSXElementName<'ast>>,
+    pub value: Option<Node<'ast, JSXAttributeValue<'ast>>>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct JSXSpreadAttribute<'ast> {
+    pub argument: ExpressionNode<'ast>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum JSXAttributeItem<'ast> {
+    Attribute(JSXAttribute<'ast>),
+    Spread(JSXSpreadAttribute<'ast>),
+}
+
+/// Expression in braces, the `expression` is `None` for empty braces,
+/// such as `{/* comment */}`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct JSXExpressionContainer<'ast> {
+    pub expression: Option<ExpressionNode<'ast>>,
+}
+
+/// Child of a JSX element or fragment, text is stored raw, with
+/// all whitespace and character references intact.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum JSXChild<'ast> {
+    Text(&'ast str),
+    Expression(JSXExpressionContainer<'ast>),
+    Element(JSXElement<'ast>),
+    Fragment(JSXFragment<'ast>),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct JSXOpeningElement<'ast> {
+    pub name: Node<'ast, JSXElementName<'ast>>,
+    pub attributes: NodeList<'ast, JSXAttributeItem<'ast>>,
+    pub self_closing: bool,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct JSXClosingElement<'ast> {
+    pub name: Node<'ast, JSXElementName<'ast>>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct JSXElement<'ast> {
+    pub opening: Node<'ast, JSXOpeningElement<'ast>>,
+    pub children: NodeList<'ast, JSXChild<'ast>>,
+    /// `None` for self closing elements
+    pub closing: Option<Node<'ast, JSXClosingElement<'ast>>>,
+}
+
+/// While not technically necessary, having a type
+/// helps with keeping track of the location of `<>`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct JSXOpeningFragment;
+
+/// Same as above, for `</>`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct JSXClosingFragment;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct JSXFragment<'ast> {
+    pub opening: Node<'ast, JSXOpeningFragment>,
+    pub children: NodeList<'ast, JSXChild<'ast>>,
+    pub closing: Node<'ast, JSXClosingFragment>,
+}
+
+impl<'ast> From<JSXAttribute<'ast>> for JSXAttributeItem<'ast> {
+    #[inline]
+    fn from(val: JSXAttribute<'ast>) -> Self {
+        JSXAttributeItem::Attribute(val)
+    }
+}
+
+impl<'ast> From<JSXSpreadAttribute<'ast>> for JSXAttributeItem<'ast> {
+    #[inline]
+    fn from(val: JSXSpreadAttribute<'ast>) -> Self {
+        JSXAttributeItem::Spread(val)
+    }
+}
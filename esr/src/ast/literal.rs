@@ -1,3 +1,6 @@
+use std::borrow::Cow;
+use std::char;
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Literal<'ast> {
     Undefined,
@@ -9,3 +12,276 @@ pub enum Literal<'ast> {
     String(&'ast str),
     RegEx(&'ast str),
 }
+
+impl<'ast> Literal<'ast> {
+    /// Value of `Number` and `Binary` literals.
+    pub fn number_value(&self) -> Option<f64> {
+        match *self {
+            Literal::Number(raw) |
+            Literal::Binary(raw) => Some(number_value(raw)),
+            _                    => None,
+        }
+    }
+
+    /// Cooked value of `String` literals, with all escapes decoded.
+    pub fn string_value(&self) -> Option<Cow<'ast, str>> {
+        match *self {
+            Literal::String(raw) => Some(string_value(raw)),
+            _                    => None,
+        }
+    }
+}
+
+/// Value of a numeric literal in any of its forms: `10`, `1.5e3`, `.5`,
+/// `0x1F`, `0o17`, `0b101`, or legacy octal `017`.
+pub fn number_value(raw: &str) -> f64 {
+    if raw.contains('_') {
+        return number_value(&raw.replace('_', ""));
+    }
+
+    let radix = match raw.get(..2) {
+        Some("0x") | Some("0X") => 16,
+        Some("0o") | Some("0O") => 8,
+        Some("0b") | Some("0B") => 2,
+        _ => {
+            let legacy_octal = raw.len() > 1
+                && raw.starts_with('0')
+                && raw.bytes().all(|byte| (b'0'..=b'7').contains(&byte));
+
+            if legacy_octal {
+                return digits_value(&raw[1..], 8);
+            }
+
+            return raw.parse().unwrap_or(f64::NAN);
+        },
+    };
+
+    digits_value(&raw[2..], radix)
+}
+
+/// Folding over digits, rather than `u64::from_str_radix`, so that large
+/// values lose precision instead of overflowing.
+fn digits_value(digits: &str, radix: u32) -> f64 {
+    digits.chars().fold(0.0, |value, digit| {
+        match digit.to_digit(radix) {
+            Some(digit) => value * radix as f64 + digit as f64,
+            None        => f64::NAN,
+        }
+    })
+}
+
+/// Cooked value of a string literal, `raw` including the quotes. Lone
+/// surrogates, which can't be represented in a `str`, are replaced with
+/// U+FFFD.
+pub fn string_value(raw: &str) -> Cow<'_, str> {
+    let raw = match raw.len() {
+        0 | 1 => "",
+        len   => &raw[1..len - 1],
+    };
+
+    cook(raw, false).unwrap_or(Cow::Borrowed(raw))
+}
+
+/// Cooked value of a template element, `raw` excluding the delimiting
+/// `` ` ``, `}` and `${`. Line terminators are normalized to `\n`.
+///
+/// Returns `None` for elements with escapes that are invalid in templates,
+/// such as `\01` or `\unicode`, which are only allowed in tagged templates.
+pub fn template_value(raw: &str) -> Option<Cow<'_, str>> {
+    cook(raw, true)
+}
+
+fn cook(raw: &str, template: bool) -> Option<Cow<'_, str>> {
+    if !raw.contains('\\') && (!template || !raw.contains('\r')) {
+        return Some(Cow::Borrowed(raw));
+    }
+
+    let mut cooked = String::with_capacity(raw.len());
+    let mut chars = raw.char_indices().peekable();
+
+    while let Some((_, character)) = chars.next() {
+        match character {
+            '\\' => {},
+            '\r' if template => {
+                if let Some(&(_, '\n')) = chars.peek() {
+                    chars.next();
+                }
+
+                cooked.push('\n');
+                continue;
+            },
+            _ => {
+                cooked.push(character);
+                continue;
+            },
+        }
+
+        let (index, escaped) = match chars.next() {
+            Some(next) => next,
+            None       => break,
+        };
+
+        let next_is_digit = match chars.peek() {
+            Some(&(_, next)) => next.is_ascii_digit(),
+            None             => false,
+        };
+
+        match escaped {
+            'n' => cooked.push('\n'),
+            't' => cooked.push('\t'),
+            'r' => cooked.push('\r'),
+            'b' => cooked.push('\u{8}'),
+            'f' => cooked.push('\u{C}'),
+            'v' => cooked.push('\u{B}'),
+            '0' if !next_is_digit => cooked.push('\0'),
+            '0'..='9' if template => return None,
+            '8' | '9' => cooked.push(escaped),
+            '0'..='7' => {
+                // Legacy octal escape, up to `\377`
+                let mut value = escaped as u32 - '0' as u32;
+                let max_digits = if value <= 3 { 2 } else { 1 };
+
+                for _ in 0..max_digits {
+                    match chars.peek() {
+                        Some(&(_, digit @ '0'..='7')) => {
+                            chars.next();
+                            value = value * 8 + (digit as u32 - '0' as u32);
+                        },
+                        _ => break,
+                    }
+                }
+
+                cooked.push(char::from_u32(value).unwrap_or('\u{FFFD}'));
+            },
+            '\r' => {
+                // Line continuation, `\r\n` counts as a single line terminator
+                if let Some(&(_, '\n')) = chars.peek() {
+                    chars.next();
+                }
+            },
+            '\n' | '\u{2028}' | '\u{2029}' => {},
+            'x' | 'u' => {
+                let rest = &raw[index + 1..];
+                let escape = match escaped {
+                    'x' => hex(rest, 2).map(|value| (value, 2)),
+                    _   => unicode_escape(rest),
+                };
+
+                let (value, len) = match escape {
+                    Some(escape)       => escape,
+                    None if template   => return None,
+                    None               => {
+                        cooked.push(escaped);
+                        continue;
+                    },
+                };
+
+                for _ in 0..len {
+                    chars.next();
+                }
+
+                cooked.push(char::from_u32(value).unwrap_or('\u{FFFD}'));
+            },
+            _ => cooked.push(escaped),
+        }
+    }
+
+    Some(Cow::Owned(cooked))
+}
+
+/// Read exactly `len` hexadecimal digits.
+fn hex(source: &str, len: usize) -> Option<u32> {
+    let digits = source.get(..len)?;
+
+    if !digits.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    u32::from_str_radix(digits, 16).ok()
+}
+
+/// Read the rest of `\u{1F600}` or `\uXXXX`, the latter possibly followed
+/// by another escape completing a surrogate pair. Returns the code point
+/// and the number of bytes read.
+fn unicode_escape(source: &str) -> Option<(u32, usize)> {
+    if source.starts_with('{') {
+        let end = source.find('}')?;
+        let digits = &source[1..end];
+
+        if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        return match u32::from_str_radix(digits, 16) {
+            Ok(value) if value <= 0x10FFFF => Some((value, end + 1)),
+            _                              => None,
+        };
+    }
+
+    let lead = hex(source, 4)?;
+
+    if (0xD800..0xDC00).contains(&lead) && source[4..].starts_with("\\u") {
+        if let Some(trail) = hex(&source[6..], 4) {
+            if (0xDC00..0xE000).contains(&trail) {
+                return Some((0x10000 + ((lead - 0xD800) << 10) + (trail - 0xDC00), 10));
+            }
+        }
+    }
+
+    Some((lead, 4))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn numbers() {
+        assert_eq!(number_value("0"), 0.0);
+        assert_eq!(number_value("42"), 42.0);
+        assert_eq!(number_value("1.5"), 1.5);
+        assert_eq!(number_value(".5"), 0.5);
+        assert_eq!(number_value("1."), 1.0);
+        assert_eq!(number_value("1e3"), 1000.0);
+        assert_eq!(number_value("2.5E-1"), 0.25);
+        assert_eq!(number_value("0xFF"), 255.0);
+        assert_eq!(number_value("0o17"), 15.0);
+        assert_eq!(number_value("0b101"), 5.0);
+        assert_eq!(number_value("017"), 15.0);
+        assert_eq!(number_value("019"), 19.0);
+        assert_eq!(number_value("0xFFFFFFFFFFFFFFFFFF"), 4722366482869645213696.0);
+        assert_eq!(Literal::Binary("0b11").number_value(), Some(3.0));
+        assert_eq!(Literal::String("'1'").number_value(), None);
+    }
+
+    #[test]
+    fn strings() {
+        assert_eq!(string_value("'foo'"), "foo");
+        assert!(match string_value("\"foo\"") {
+            Cow::Borrowed(_) => true,
+            Cow::Owned(_)    => false,
+        });
+        assert_eq!(string_value(r"'a\nb\tc\\d\'e'"), "a\nb\tc\\d'e");
+        assert_eq!(string_value(r"'\x41B\u{43}\u{1F600}'"), "ABC😀");
+        assert_eq!(string_value(r"'😀'"), "😀");
+        assert_eq!(string_value(r"'\uD83D'"), "\u{FFFD}");
+        assert_eq!(string_value("'a\\\nb\\\r\nc'"), "abc");
+        assert_eq!(string_value(r"'\0\101\7\8'"), "\0A\u{7}8");
+        assert_eq!(string_value(r"'\q\xZ'"), "qxZ");
+        assert_eq!(Literal::String(r"'\n'").string_value(), Some(Cow::Borrowed("\n")));
+        assert_eq!(Literal::Null.string_value(), None);
+    }
+
+    #[test]
+    fn templates() {
+        assert_eq!(template_value("foo"), Some(Cow::Borrowed("foo")));
+        assert_eq!(template_value(r"a\nb\`c\${d}"), Some(Cow::Borrowed("a\nb`c${d}")));
+        assert_eq!(template_value("a\r\nb\rc"), Some(Cow::Borrowed("a\nb\nc")));
+        assert_eq!(template_value("a\\\r\nb"), Some(Cow::Borrowed("ab")));
+        assert_eq!(template_value(r"\0"), Some(Cow::Borrowed("\0")));
+        assert_eq!(template_value(r"\01"), None);
+        assert_eq!(template_value(r"\unicode"), None);
+        assert_eq!(template_value(r"\xZ"), None);
+        assert_eq!(template_value(r"\u{110000}"), None);
+    }
+}
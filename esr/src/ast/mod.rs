@@ -1,25 +1,40 @@
 #[macro_use]
 mod variable;
 mod operator;
-// mod types;
 mod function;
 mod literal;
 pub mod node;
 pub mod expression;
 pub mod statement;
+pub mod jsx;
+pub mod types;
+pub mod builder;
+mod clone;
+mod walk;
+mod any;
+mod parent;
+mod structure;
 
 use toolshed::list::List;
 use std::ops::Deref;
 
 pub use crate::ast::variable::*;
 pub use crate::ast::operator::*;
-pub use crate::ast::node::Node;
-// pub use ast::types::{Type, Primitive};
+pub use crate::ast::node::{Node, NodeId};
+pub use crate::ast::parent::{ParentMap, Ancestors, Slot};
+pub use crate::ast::any::{AnyNode, Order, Children, Descendants};
+pub use crate::ast::structure::alpha_equivalent;
+pub use crate::ast::types::{Type, Primitive, TypeName, TypeParameter, TypeParameterList};
+pub use crate::ast::types::{FunctionType, TypeMember, Modifiers, Accessibility};
 pub use crate::ast::expression::{Expression, Property, PropertyKey};
-pub use crate::ast::statement::{Statement, Declarator, BlockStatement};
+pub use crate::ast::statement::{Statement, Declarator, BlockStatement, Label};
 pub use crate::ast::function::{Function, Class, ClassMember, Method, MethodKind};
+pub use crate::ast::function::{Decorator, DecoratorList};
 pub use crate::ast::function::{Name, EmptyName, OptionalName, MandatoryName};
-pub use crate::ast::literal::Literal;
+pub use crate::ast::literal::{Literal, number_value, string_value, template_value};
+pub use crate::ast::builder::AstBuilder;
+pub use crate::ast::clone::DeepClone;
+pub(crate) use crate::ast::clone::{CloneWith, Cloner};
 
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -31,7 +46,7 @@ pub struct Block<'ast, T> {
 pub enum Pattern<'ast> {
     /// Only used inside ArrayPattern
     Void,
-    Identifier(Identifier<'ast>),
+    Identifier(IdentifierNode<'ast>),
     ObjectPattern {
         properties: NodeList<'ast, Property<'ast>>
     },
@@ -44,6 +59,17 @@ pub enum Pattern<'ast> {
     AssignmentPattern {
         left: Node<'ast, Pattern<'ast>>,
         right: ExpressionNode<'ast>,
+    },
+    /// Pattern with a type annotation, `foo?: number`
+    Annotated {
+        pattern: Node<'ast, Pattern<'ast>>,
+        optional: bool,
+        annotation: Option<TypeNode<'ast>>,
+    },
+    /// Constructor parameter declaring a class property, `private foo`
+    ParameterProperty {
+        modifiers: Modifiers,
+        parameter: Node<'ast, Pattern<'ast>>,
     }
 }
 
@@ -59,8 +85,14 @@ pub type StatementNode<'ast> = Node<'ast, Statement<'ast>>;
 pub type StatementList<'ast> = NodeList<'ast, Statement<'ast>>;
 pub type IdentifierNode<'ast> = Node<'ast, &'ast str>;
 pub type IdentifierList<'ast> = NodeList<'ast, &'ast str>;
-// pub type TypeNode<'ast> = NodeList<'ast, Type<'ast>>;
-// pub type TypeList<'ast> = NodeList<'ast, Type<'ast>>;
+pub type LabelNode<'ast> = Node<'ast, Label<'ast>>;
+pub type TypeNode<'ast> = Node<'ast, Type<'ast>>;
+pub type TypeList<'ast> = NodeList<'ast, Type<'ast>>;
+
+/// Value of both `start` and `end` of synthetic nodes, which don't come
+/// from the source. Those are created by transformations, or stand in for
+/// invalid syntax.
+pub const SYNTHETIC: u32 = u32::MAX;
 
 #[derive(Debug, Clone)]
 pub struct Loc<T> {
@@ -94,6 +126,27 @@ impl<T> Loc<T> {
             item,
         }
     }
+
+    /// Create a synthetic `Loc`, not spanning any part of the source.
+    #[inline]
+    pub fn synthetic(item: T) -> Self {
+        Loc::new(SYNTHETIC, SYNTHETIC, item)
+    }
+
+    /// Whether this is a synthetic `Loc`, see `Loc::synthetic`.
+    #[inline]
+    pub fn is_synthetic(&self) -> bool {
+        self.start == SYNTHETIC && self.end == SYNTHETIC
+    }
+
+    /// Start and end of the source spanned, `None` if synthetic.
+    #[inline]
+    pub fn span(&self) -> Option<(u32, u32)> {
+        match self.is_synthetic() {
+            true  => None,
+            false => Some((self.start, self.end)),
+        }
+    }
 }
 
 impl<T: PartialEq> PartialEq for Loc<T> {
@@ -27,8 +27,21 @@ impl<'ast, T: 'ast> Node<'ast, T> {
     pub fn get_mut(&mut self) -> &mut &'ast Loc<T> {
         self.inner.get_mut()
     }
+
+    /// Identity of the node, see `NodeId`.
+    #[inline]
+    pub fn id(&self) -> NodeId {
+        NodeId(self.inner.get() as *const Loc<T> as usize)
+    }
 }
 
+/// Identity of a node, which is the address of the `Loc` it points to.
+/// It is unique among the nodes on an `Arena` and stays the same as long
+/// as the node isn't replaced through `Node::set`, but doesn't survive
+/// a reparse that rebuilds it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(usize);
+
 impl<'ast, T: 'ast> Deref for Node<'ast, T> {
     type Target = Loc<T>;
 
@@ -72,4 +85,31 @@ mod test {
         assert_eq!(*one_ptr, Loc::new(0, 0, "one"));
         assert_eq!(*two_ptr, Loc::new(0, 0, "two"));
     }
+
+    #[test]
+    fn synthetic() {
+        let parsed = Loc::new(0, 0, "parsed");
+        let synthetic = Loc::synthetic("synthetic");
+
+        assert!(!parsed.is_synthetic());
+        assert!(synthetic.is_synthetic());
+        assert_eq!(parsed.span(), Some((0, 0)));
+        assert_eq!(synthetic.span(), None);
+    }
+
+    #[test]
+    fn id() {
+        let one = Loc::new(0, 0, "one");
+        let two = Loc::new(0, 0, "one");
+
+        let one_ptr = Node::new(&one);
+        let two_ptr = Node::new(&two);
+
+        assert_eq!(one_ptr.id(), Node::new(&one).id());
+        assert_ne!(one_ptr.id(), two_ptr.id());
+
+        one_ptr.set(&two);
+
+        assert_eq!(one_ptr.id(), two_ptr.id());
+    }
 }
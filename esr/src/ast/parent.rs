@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::ast::{AnyNode, NodeId};
+use crate::ast::walk::{Walk, Walker};
+use crate::module::Module;
+
+/// Field of its parent in which a node is found, along with its position
+/// for fields holding a list of nodes. Fields are named as in the structs
+/// and enum variants of `esr::ast`, top level statements of a module are
+/// in the `body` slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Slot {
+    pub field: &'static str,
+    pub index: Option<usize>,
+}
+
+impl Slot {
+    #[inline]
+    pub(crate) fn field(field: &'static str) -> Self {
+        Slot {
+            field,
+            index: None,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn at(self, index: usize) -> Self {
+        Slot {
+            field: self.field,
+            index: Some(index),
+        }
+    }
+}
+
+impl fmt::Display for Slot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.index {
+            Some(index) => write!(f, "{}[{}]", self.field, index),
+            None        => f.write_str(self.field),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    parent: Option<NodeId>,
+    slot: Slot,
+}
+
+/// Parent of every node in a module, built on demand by walking the whole
+/// tree. The map is a snapshot: nodes replaced or added afterwards are not
+/// in it. A `Loc` shared by several nodes is recorded where it's first
+/// reached, in source order.
+#[derive(Debug, Default)]
+pub struct ParentMap {
+    entries: HashMap<NodeId, Entry>,
+}
+
+impl ParentMap {
+    pub fn new(module: &Module<'_>) -> Self {
+        let mut builder = Builder {
+            map: ParentMap::default(),
+            stack: Vec::new(),
+        };
+
+        for (index, node) in module.body().iter().enumerate() {
+            node.walk(&mut builder, Slot::field("body").at(index));
+        }
+
+        builder.map
+    }
+
+    /// Parent of the node, `None` for top level statements and for nodes
+    /// that aren't in the map.
+    #[inline]
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.entries.get(&id).and_then(|entry| entry.parent)
+    }
+
+    /// Slot the node occupies in its parent, or in the module for top
+    /// level statements.
+    #[inline]
+    pub fn slot(&self, id: NodeId) -> Option<Slot> {
+        self.entries.get(&id).map(|entry| entry.slot)
+    }
+
+    /// Nodes containing the given one, starting with its parent and ending
+    /// with a top level statement.
+    #[inline]
+    pub fn ancestors(&self, id: NodeId) -> Ancestors<'_> {
+        Ancestors {
+            map: self,
+            next: self.parent(id),
+        }
+    }
+
+    #[inline]
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.entries.contains_key(&id)
+    }
+
+    /// Number of nodes in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Iterator over the ancestors of a node, see `ParentMap::ancestors`.
+#[derive(Debug, Clone)]
+pub struct Ancestors<'map> {
+    map: &'map ParentMap,
+    next: Option<NodeId>,
+}
+
+impl<'map> Iterator for Ancestors<'map> {
+    type Item = NodeId;
+
+    #[inline]
+    fn next(&mut self) -> Option<NodeId> {
+        let id = self.next?;
+
+        self.next = self.map.parent(id);
+
+        Some(id)
+    }
+}
+
+struct Builder {
+    map: ParentMap,
+    stack: Vec<NodeId>,
+}
+
+impl<'ast> Walker<'ast> for Builder {
+    #[inline]
+    fn enter(&mut self, node: AnyNode<'ast>, slot: Slot) -> bool {
+        let id = node.id();
+        let parent = self.stack.last().cloned();
+
+        self.map.entries.entry(id).or_insert(Entry { parent, slot });
+        self.stack.push(id);
+
+        true
+    }
+
+    #[inline]
+    fn leave(&mut self) {
+        self.stack.pop();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ast::{Statement, Expression, Pattern, StatementNode, ExpressionNode};
+    use crate::ast::expression::CallExpression;
+    use crate::parse;
+
+    fn first<'ast>(module: &Module<'ast>) -> StatementNode<'ast> {
+        *module.body().iter().next().unwrap()
+    }
+
+    fn call<'a, 'ast>(node: &'a ExpressionNode<'ast>) -> &'a CallExpression<'ast> {
+        match node.item {
+            Expression::Call(ref call) => call,
+            _ => panic!("Expected a call expression"),
+        }
+    }
+
+    #[test]
+    fn parents_and_slots() {
+        let module = parse("foo(bar, baz);").unwrap();
+        let map = ParentMap::new(&module);
+        let statement = first(&module);
+
+        let expression = match statement.item {
+            Statement::Expression(expression) => expression,
+            _ => panic!("Expected an expression statement"),
+        };
+        let call = call(&expression);
+        let baz = call.arguments.iter().nth(1).unwrap();
+
+        assert_eq!(map.len(), 5);
+        assert_eq!(map.parent(statement.id()), None);
+        assert_eq!(map.slot(statement.id()), Some(Slot::field("body").at(0)));
+        assert_eq!(map.parent(expression.id()), Some(statement.id()));
+        assert_eq!(map.slot(expression.id()), Some(Slot::field("expression")));
+        assert_eq!(map.parent(call.callee.id()), Some(expression.id()));
+        assert_eq!(map.slot(call.callee.id()), Some(Slot::field("callee")));
+        assert_eq!(map.parent(baz.id()), Some(expression.id()));
+        assert_eq!(map.slot(baz.id()).unwrap().to_string(), "arguments[1]");
+    }
+
+    #[test]
+    fn ancestors() {
+        let module = parse("function foo({ bar }) { if (bar) { return bar; } }").unwrap();
+        let map = ParentMap::new(&module);
+        let function = first(&module);
+
+        let (param, ret) = match function.item {
+            Statement::Function(ref function) => {
+                let param = function.params.iter().next().unwrap();
+                let block = match function.body.body.iter().next().unwrap().item {
+                    Statement::If(ref stmt) => match stmt.consequent.item {
+                        Statement::Block(ref block) => block,
+                        _ => panic!("Expected a block"),
+                    },
+                    _ => panic!("Expected an if statement"),
+                };
+
+                (param, block.body.iter().next().unwrap())
+            },
+            _ => panic!("Expected a function"),
+        };
+
+        let ancestors = map.ancestors(ret.id()).collect::<Vec<_>>();
+        let slots = ancestors.iter().map(|&id| map.slot(id).unwrap().to_string()).collect::<Vec<_>>();
+
+        assert_eq!(ancestors.len(), 4);
+        assert_eq!(ancestors.last(), Some(&function.id()));
+        assert_eq!(slots, ["consequent", "body[0]", "body", "body[0]"]);
+        assert_eq!(map.slot(ret.id()).unwrap().to_string(), "body[0]");
+
+        match param.item {
+            Pattern::ObjectPattern { properties } => {
+                let property = properties.iter().next().unwrap();
+
+                assert_eq!(map.parent(property.id()), Some(param.id()));
+                assert_eq!(map.slot(property.id()).unwrap().to_string(), "properties[0]");
+                assert_eq!(map.ancestors(property.id()).count(), 2);
+            },
+            _ => panic!("Expected an object pattern"),
+        }
+    }
+
+    #[test]
+    fn nodes_not_in_the_map() {
+        let module = parse("a;").unwrap();
+        let other = parse("b;").unwrap();
+        let map = ParentMap::new(&module);
+        let id = first(&other).id();
+
+        assert!(map.contains(first(&module).id()));
+        assert!(!map.contains(id));
+        assert_eq!(map.parent(id), None);
+        assert_eq!(map.slot(id), None);
+        assert_eq!(map.ancestors(id).count(), 0);
+    }
+}
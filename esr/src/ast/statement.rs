@@ -1,5 +1,8 @@
-use crate::ast::{Node, NodeList, DeclarationKind, Function, Class, MandatoryName, IdentifierNode};
+use crate::ast::{Node, NodeList, DeclarationKind, Function, Class, MandatoryName, LabelNode};
 use crate::ast::{ExpressionNode, StatementNode, StatementList, Block, BlockNode, Pattern};
+use crate::ast::types::{InterfaceDeclaration, TypeAliasDeclaration, FunctionSignature, DeclareStatement};
+use crate::ast::types::{OpaqueTypeDeclaration, TypeImportDeclaration};
+use crate::ast::types::{EnumDeclaration, NamespaceDeclaration};
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct Declarator<'ast> {
@@ -13,6 +16,34 @@ pub struct DeclarationStatement<'ast> {
     pub declarators: NodeList<'ast, Declarator<'ast>>,
 }
 
+/// Exported declaration, `export const foo = 1;`. A default export,
+/// `export default foo;`, holds an expression statement unless it exports
+/// a named class or function.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ExportDeclaration<'ast> {
+    pub is_default: bool,
+    pub declaration: StatementNode<'ast>,
+}
+
+/// Name of a `LabeledStatement`, or of the jump target of a `break` or
+/// `continue`. Labels live in their own namespace, apart from references.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Label<'ast>(pub &'ast str);
+
+impl<'ast> Label<'ast> {
+    #[inline]
+    pub fn as_str(&self) -> &'ast str {
+        self.0
+    }
+}
+
+impl<'ast> From<&'ast str> for Label<'ast> {
+    #[inline]
+    fn from(label: &'ast str) -> Self {
+        Label(label)
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct ReturnStatement<'ast> {
     pub value: Option<ExpressionNode<'ast>>,
@@ -20,14 +51,12 @@ pub struct ReturnStatement<'ast> {
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct BreakStatement<'ast> {
-    // TODO: This should be a `LabelNode`, with `Label` being a newtype for &str.
-    pub label: Option<IdentifierNode<'ast>>,
+    pub label: Option<LabelNode<'ast>>,
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct ContinueStatement<'ast> {
-    // TODO: This should be a `LabelNode`, with `Label` being a newtype for &str.
-    pub label: Option<IdentifierNode<'ast>>
+    pub label: Option<LabelNode<'ast>>
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -97,7 +126,7 @@ pub struct TryStatement<'ast> {
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct LabeledStatement<'ast> {
-    pub label: &'ast str,
+    pub label: LabelNode<'ast>,
     pub body: StatementNode<'ast>,
 }
 
@@ -137,7 +166,16 @@ pub enum Statement<'ast> {
     Labeled(LabeledStatement<'ast>),
     Function(FunctionStatement<'ast>),
     Class(ClassStatement<'ast>),
-    Switch(SwitchStatement<'ast>)
+    Switch(SwitchStatement<'ast>),
+    Interface(InterfaceDeclaration<'ast>),
+    TypeAlias(TypeAliasDeclaration<'ast>),
+    FunctionSignature(FunctionSignature<'ast>),
+    Declare(DeclareStatement<'ast>),
+    OpaqueType(OpaqueTypeDeclaration<'ast>),
+    TypeImport(TypeImportDeclaration<'ast>),
+    Enum(EnumDeclaration<'ast>),
+    Namespace(NamespaceDeclaration<'ast>),
+    Export(ExportDeclaration<'ast>),
 }
 
 macro_rules! impl_from {
@@ -169,7 +207,16 @@ impl_from! {
     ContinueStatement => Continue,
     FunctionStatement => Function,
     ClassStatement => Class,
-    SwitchStatement => Switch
+    SwitchStatement => Switch,
+    InterfaceDeclaration => Interface,
+    TypeAliasDeclaration => TypeAlias,
+    FunctionSignature => FunctionSignature,
+    DeclareStatement => Declare,
+    OpaqueTypeDeclaration => OpaqueType,
+    TypeImportDeclaration => TypeImport,
+    EnumDeclaration => Enum,
+    NamespaceDeclaration => Namespace,
+    ExportDeclaration => Export
 }
 
 impl<'ast> From<DeclarationStatement<'ast>> for ForInit<'ast> {
@@ -194,4 +241,38 @@ impl<'ast> Statement<'ast> {
             _                   => false,
         }
     }
+
+    /// Whether the statement declares a binding or a type, and can be
+    /// exported.
+    #[inline]
+    pub fn is_declaration(&self) -> bool {
+        matches!(*self,
+            Statement::Declaration(_)       |
+            Statement::Function(_)          |
+            Statement::Class(_)             |
+            Statement::Interface(_)         |
+            Statement::TypeAlias(_)         |
+            Statement::FunctionSignature(_) |
+            Statement::Declare(_)           |
+            Statement::OpaqueType(_)        |
+            Statement::Enum(_)              |
+            Statement::Namespace(_)
+        )
+    }
+
+    /// Whether the statement only declares types, and has no effect
+    /// once the types are erased.
+    #[inline]
+    pub fn is_type_declaration(&self) -> bool {
+        match *self {
+            Statement::Interface(_)         |
+            Statement::TypeAlias(_)         |
+            Statement::FunctionSignature(_) |
+            Statement::Declare(_)           |
+            Statement::OpaqueType(_)        |
+            Statement::TypeImport(_)        => true,
+            Statement::Export(ExportDeclaration { declaration, .. }) => declaration.is_type_declaration(),
+            _                               => false,
+        }
+    }
 }
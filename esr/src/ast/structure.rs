@@ -0,0 +1,938 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::ast::{Node, NodeList, Block, Pattern, Literal, Label, AnyNode};
+use crate::ast::{Statement, Expression, Property, PropertyKey, OperatorKind, DeclarationKind};
+use crate::ast::{Function, Class, ClassMember, MethodKind, Name, EmptyName, OptionalName, MandatoryName};
+use crate::ast::Decorator;
+use crate::ast::expression::*;
+use crate::ast::statement::*;
+use crate::ast::jsx::*;
+use crate::ast::types::*;
+
+/// Piece of the structure of a tree, ignoring locations. The tokens of
+/// a tree are an unambiguous encoding of it, so that two trees are equal
+/// if, and only if, they produce the same tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Token<'ast> {
+    /// Variant of an enum
+    Tag(&'static str),
+    Str(&'ast str),
+    /// Reference to a variable
+    Name(&'ast str),
+    /// Declaration of a variable in the current scope
+    Binding(&'ast str),
+    /// Declaration of a `var`, in the enclosing function scope
+    Hoisted(&'ast str),
+    Enter(ScopeKind),
+    Leave,
+    Int(u64),
+    Len(usize),
+    None,
+    Some,
+}
+
+/// Kind of the scope the tokens between `Token::Enter` and `Token::Leave`
+/// belong to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum ScopeKind {
+    Function,
+    Block,
+}
+
+pub(crate) trait Sink<'ast> {
+    fn token(&mut self, token: Token<'ast>);
+}
+
+impl<'ast> Sink<'ast> for Vec<Token<'ast>> {
+    #[inline]
+    fn token(&mut self, token: Token<'ast>) {
+        self.push(token);
+    }
+}
+
+struct Hashing<'h, H: Hasher>(&'h mut H);
+
+impl<'h, 'ast, H: Hasher> Sink<'ast> for Hashing<'h, H> {
+    #[inline]
+    fn token(&mut self, token: Token<'ast>) {
+        token.hash(self.0);
+    }
+}
+
+/// Feed the structure of a tree, without its locations, to a `Sink`.
+pub(crate) trait Structure<'ast> {
+    fn tokens<S: Sink<'ast>>(&self, sink: &mut S);
+}
+
+impl<'ast, T: Structure<'ast>> Structure<'ast> for Node<'ast, T> {
+    #[inline]
+    fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+        self.item.tokens(sink);
+    }
+}
+
+impl<'ast, T: Structure<'ast>> Structure<'ast> for NodeList<'ast, T> {
+    #[inline]
+    fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+        sink.token(Token::Len(self.iter().count()));
+
+        for node in self.iter() {
+            node.tokens(sink);
+        }
+    }
+}
+
+impl<'ast, T: Structure<'ast>> Structure<'ast> for Option<T> {
+    #[inline]
+    fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+        match *self {
+            Some(ref item) => {
+                sink.token(Token::Some);
+                item.tokens(sink);
+            },
+            None => sink.token(Token::None),
+        }
+    }
+}
+
+impl<'ast> Structure<'ast> for &'ast str {
+    #[inline]
+    fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+        sink.token(Token::Str(self));
+    }
+}
+
+impl<'ast> Structure<'ast> for bool {
+    #[inline]
+    fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+        sink.token(Token::Int(*self as u64));
+    }
+}
+
+#[inline]
+fn variant<'ast, S, T>(sink: &mut S, tag: &'static str, item: &T) where
+    S: Sink<'ast>,
+    T: Structure<'ast>,
+{
+    sink.token(Token::Tag(tag));
+    item.tokens(sink);
+}
+
+#[inline]
+fn binding<'ast, S: Sink<'ast>>(sink: &mut S, name: &Node<'ast, &'ast str>) {
+    sink.token(Token::Binding(name.item));
+}
+
+#[inline]
+fn scope<'ast, S, F>(sink: &mut S, kind: ScopeKind, tokens: F) where
+    S: Sink<'ast>,
+    F: FnOnce(&mut S),
+{
+    sink.token(Token::Enter(kind));
+    tokens(sink);
+    sink.token(Token::Leave);
+}
+
+/// Properties of object patterns, which hold their patterns as expressions.
+fn binding_property<'ast, S: Sink<'ast>>(sink: &mut S, property: &Property<'ast>) {
+    match *property {
+        Property::Shorthand(ref name) => {
+            sink.token(Token::Tag("Shorthand"));
+            sink.token(Token::Str(name.item));
+            binding(sink, name);
+        },
+        Property::Literal { ref key, ref value } => {
+            sink.token(Token::Tag("Literal"));
+            key.tokens(sink);
+            binding_target(sink, value);
+        },
+        Property::Spread { ref argument } => {
+            sink.token(Token::Tag("Spread"));
+            binding_target(sink, argument);
+        },
+        Property::Method { .. } => property.tokens(sink),
+    }
+}
+
+/// Expression standing for a pattern, its identifiers are declarations.
+fn binding_target<'ast, S: Sink<'ast>>(sink: &mut S, target: &Node<'ast, Expression<'ast>>) {
+    match target.item {
+        Expression::Identifier(name) => {
+            sink.token(Token::Tag("Identifier"));
+            sink.token(Token::Binding(name));
+        },
+        Expression::Object(ObjectExpression { ref body }) => {
+            sink.token(Token::Tag("Object"));
+            sink.token(Token::Len(body.iter().count()));
+
+            for property in body.iter() {
+                binding_property(sink, property);
+            }
+        },
+        Expression::Array(ArrayExpression { ref body }) => {
+            sink.token(Token::Tag("Array"));
+            sink.token(Token::Len(body.iter().count()));
+
+            for element in body.iter() {
+                binding_target(sink, element);
+            }
+        },
+        Expression::Spread(SpreadExpression { ref argument }) => {
+            sink.token(Token::Tag("Spread"));
+            binding_target(sink, argument);
+        },
+        Expression::Binary(BinaryExpression { operator: OperatorKind::Assign, ref left, ref right }) => {
+            sink.token(Token::Tag("Binary"));
+            OperatorKind::Assign.tokens(sink);
+            binding_target(sink, left);
+            right.tokens(sink);
+        },
+        _ => target.tokens(sink),
+    }
+}
+
+macro_rules! structure_unit {
+    ($( $type:ty ),*) => ($(
+        impl<'ast> Structure<'ast> for $type {
+            #[inline]
+            fn tokens<S: Sink<'ast>>(&self, _: &mut S) {}
+        }
+    )*)
+}
+
+macro_rules! structure_fieldless {
+    ($( $type:ty ),*) => ($(
+        impl<'ast> Structure<'ast> for $type {
+            #[inline]
+            fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+                sink.token(Token::Int(*self as u64));
+            }
+        }
+    )*)
+}
+
+macro_rules! structure_struct {
+    ($( $type:ident { $( $field:ident ),* } )*) => ($(
+        impl<'ast> Structure<'ast> for $type<'ast> {
+            #[inline]
+            fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+                $( self.$field.tokens(sink); )*
+            }
+        }
+    )*)
+}
+
+macro_rules! structure_enum {
+    ($( $type:ident {
+        $( $variant:ident $( ( $inner:ident ) )? $( { $( $field:ident ),* } )? ),* $(,)*
+    } )*) => ($(
+        impl<'ast> Structure<'ast> for $type<'ast> {
+            fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+                match *self {
+                    $(
+                        $type::$variant $( (ref $inner) )? $( { $( ref $field, )* } )? => {
+                            sink.token(Token::Tag(stringify!($variant)));
+                            $( $inner.tokens(sink); )?
+                            $( $( $field.tokens(sink); )* )?
+                        },
+                    )*
+                }
+            }
+        }
+    )*)
+}
+
+structure_unit! {
+    EmptyName, ThisExpression, SuperExpression, JSXOpeningFragment, JSXClosingFragment
+}
+
+structure_fieldless! {
+    OperatorKind, DeclarationKind, MethodKind, Primitive, TypeOperator, TypeImportKind, Accessibility
+}
+
+structure_struct! {
+    Decorator { expression }
+    SequenceExpression { body }
+    ParenthesizedExpression { expression }
+    ArrayExpression { body }
+    MemberExpression { object, property }
+    MetaPropertyExpression { meta, property }
+    ComputedMemberExpression { object, property }
+    CallExpression { callee, arguments }
+    BinaryExpression { operator, left, right }
+    PrefixExpression { operator, operand }
+    PostfixExpression { operator, operand }
+    ConditionalExpression { test, consequent, alternate }
+    TemplateLiteral { expressions, quasis }
+    TaggedTemplateExpression { tag, quasi }
+    SpreadExpression { argument }
+    AwaitExpression { argument }
+    AsExpression { expression, annotation }
+    SatisfiesExpression { expression, annotation }
+    TypeCastExpression { expression, annotation }
+    NonNullExpression { expression }
+    TypeAssertion { annotation, expression }
+    InstantiationExpression { expression, arguments }
+    ObjectExpression { body }
+    Declarator { id, init }
+    ReturnStatement { value }
+    BreakStatement { label }
+    ContinueStatement { label }
+    ThrowStatement { value }
+    IfStatement { test, consequent, alternate }
+    WhileStatement { test, body }
+    DoStatement { body, test }
+    TryStatement { block, handler, finalizer }
+    LabeledStatement { label, body }
+    SwitchStatement { discriminant, cases }
+    SwitchCase { test, consequent }
+    JSXMemberExpression { object, property }
+    JSXAttribute { name, value }
+    JSXSpreadAttribute { argument }
+    JSXExpressionContainer { expression }
+    JSXOpeningElement { name, attributes, self_closing }
+    JSXClosingElement { name }
+    JSXElement { opening, children, closing }
+    JSXFragment { opening, children, closing }
+    TypeParameter { name, constraint, default }
+    FunctionType { type_parameters, params, return_type }
+    InterfaceDeclaration { name, type_parameters, extends, body }
+    TypeAliasDeclaration { name, type_parameters, value }
+    FunctionSignature { name, signature }
+    DeclareStatement { body }
+    OpaqueTypeDeclaration { name, type_parameters, supertype, value }
+    TypeImportDeclaration { kind, specifiers, source }
+    EnumMember { name, value }
+    EnumDeclaration { is_const, name, members }
+    NamespaceDeclaration { name, body }
+    ExportDeclaration { is_default, declaration }
+}
+
+structure_enum! {
+    Literal {
+        Undefined, Null, True, False, Number(value), Binary(value), String(value), RegEx(value),
+    }
+    PropertyKey {
+        Computed(expression), Literal(value), Binary(value),
+    }
+    ClassMember {
+        Error,
+        Method { is_static, key, kind, value },
+        Literal { is_static, key, value },
+        Property { is_static, key, optional, annotation, value },
+        Accessor { is_static, key, annotation, value },
+        Signature { is_static, key, kind, signature },
+        Modified { modifiers, member },
+        Decorated { decorators, member },
+    }
+    ArrowBody {
+        Expression(expression), Block(block),
+    }
+    ForInit {
+        Declaration(declaration), Expression(expression),
+    }
+    Statement {
+        Empty, Expression(stmt), Declaration(stmt), Return(stmt), Break(stmt), Continue(stmt),
+        Throw(stmt), If(stmt), While(stmt), Do(stmt), For(stmt), ForIn(stmt), ForOf(stmt),
+        Try(stmt), Block(stmt), Labeled(stmt), Function(stmt), Class(stmt), Switch(stmt),
+        Interface(stmt), TypeAlias(stmt), FunctionSignature(stmt), Declare(stmt),
+        OpaqueType(stmt), TypeImport(stmt), Enum(stmt), Namespace(stmt), Export(stmt),
+    }
+    JSXElementName {
+        Identifier(name), Namespaced { namespace, name }, Member(member),
+    }
+    JSXAttributeValue {
+        String(value), Expression(expr), Element(element), Fragment(frag),
+    }
+    JSXAttributeItem {
+        Attribute(attribute), Spread(attribute),
+    }
+    JSXChild {
+        Text(text), Expression(expr), Element(element), Fragment(frag),
+    }
+    TypeImportSpecifier {
+        Default(local), Namespace(local), Named { imported, local },
+    }
+    TypeName {
+        Identifier(name), Qualified { left, right },
+    }
+    TypeMember {
+        Property { readonly, key, optional, annotation },
+        Method { key, optional, signature },
+        Index { readonly, parameter, key, annotation },
+        Call { signature },
+        Construct { signature },
+        Spread { argument },
+    }
+    Type {
+        Any, Unknown, Never, Void, Object, This, Primitive(primitive), Literal(literal),
+        Reference { name, arguments },
+        Query { name },
+        Array { element },
+        Tuple { elements },
+        Optional { argument },
+        Rest { argument },
+        Union { variants },
+        Intersection { variants },
+        Function(signature),
+        Constructor(signature),
+        ObjectLiteral { members, exact },
+        Mapped { readonly, parameter, constraint, optional, annotation },
+        Operator { operator, argument },
+        IndexedAccess { object, index },
+        Conditional { check, extends, consequent, alternate },
+        Infer { parameter },
+        Predicate { parameter, annotation },
+        Nullable { argument },
+    }
+}
+
+impl<'ast> Structure<'ast> for ArrowExpression<'ast> {
+    #[inline]
+    fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+        scope(sink, ScopeKind::Function, |sink| {
+            self.type_parameters.tokens(sink);
+            self.params.tokens(sink);
+            self.return_type.tokens(sink);
+            self.body.tokens(sink);
+        });
+    }
+}
+
+impl<'ast> Structure<'ast> for DeclarationStatement<'ast> {
+    fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+        self.kind.tokens(sink);
+        sink.token(Token::Len(self.declarators.iter().count()));
+
+        for declarator in self.declarators.iter() {
+            match self.kind {
+                // Declared in the function, rather than the block
+                DeclarationKind::Var => {
+                    let mut id = Vec::new();
+
+                    declarator.id.tokens(&mut id);
+
+                    for token in id {
+                        sink.token(match token {
+                            Token::Binding(name) => Token::Hoisted(name),
+                            token                => token,
+                        });
+                    }
+                },
+                _ => declarator.id.tokens(sink),
+            }
+
+            declarator.init.tokens(sink);
+        }
+    }
+}
+
+impl<'ast> Structure<'ast> for ForStatement<'ast> {
+    #[inline]
+    fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+        scope(sink, ScopeKind::Block, |sink| {
+            self.init.tokens(sink);
+            self.test.tokens(sink);
+            self.update.tokens(sink);
+            self.body.tokens(sink);
+        });
+    }
+}
+
+impl<'ast> Structure<'ast> for ForInStatement<'ast> {
+    #[inline]
+    fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+        scope(sink, ScopeKind::Block, |sink| {
+            self.left.tokens(sink);
+            self.right.tokens(sink);
+            self.body.tokens(sink);
+        });
+    }
+}
+
+impl<'ast> Structure<'ast> for ForOfStatement<'ast> {
+    #[inline]
+    fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+        scope(sink, ScopeKind::Block, |sink| {
+            self.left.tokens(sink);
+            self.right.tokens(sink);
+            self.body.tokens(sink);
+        });
+    }
+}
+
+impl<'ast> Structure<'ast> for CatchClause<'ast> {
+    #[inline]
+    fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+        scope(sink, ScopeKind::Block, |sink| {
+            self.param.tokens(sink);
+            self.body.tokens(sink);
+        });
+    }
+}
+
+impl<'ast> Structure<'ast> for Label<'ast> {
+    #[inline]
+    fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+        sink.token(Token::Str(self.0));
+    }
+}
+
+impl<'ast> Structure<'ast> for Modifiers {
+    #[inline]
+    fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+        self.accessibility.tokens(sink);
+        self.readonly.tokens(sink);
+        self.declare.tokens(sink);
+        self.is_override.tokens(sink);
+    }
+}
+
+/// Names of functions and classes. Declarations declare them in the
+/// enclosing scope, expressions only within themselves.
+trait ScopedName<'ast>: Name<'ast> + Structure<'ast> {
+    const OWN_SCOPE: bool;
+}
+
+impl<'ast> ScopedName<'ast> for EmptyName {
+    const OWN_SCOPE: bool = false;
+}
+
+impl<'ast> ScopedName<'ast> for MandatoryName<'ast> {
+    const OWN_SCOPE: bool = false;
+}
+
+impl<'ast> ScopedName<'ast> for OptionalName<'ast> {
+    const OWN_SCOPE: bool = true;
+}
+
+/// Tokens of a function or a class, within the scope of its name if
+/// it's an expression.
+#[inline]
+fn named<'ast, S, N, F>(sink: &mut S, name: &N, tokens: F) where
+    S: Sink<'ast>,
+    N: ScopedName<'ast>,
+    F: FnOnce(&mut S),
+{
+    if N::OWN_SCOPE {
+        scope(sink, ScopeKind::Block, |sink| {
+            name.tokens(sink);
+            tokens(sink);
+        });
+    } else {
+        name.tokens(sink);
+        tokens(sink);
+    }
+}
+
+impl<'ast> Structure<'ast> for MandatoryName<'ast> {
+    #[inline]
+    fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+        binding(sink, &self.0);
+    }
+}
+
+impl<'ast> Structure<'ast> for OptionalName<'ast> {
+    #[inline]
+    fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+        match self.0 {
+            Some(ref name) => {
+                sink.token(Token::Some);
+                binding(sink, name);
+            },
+            None => sink.token(Token::None),
+        }
+    }
+}
+
+impl<'ast, T: Structure<'ast>> Structure<'ast> for Block<'ast, T> {
+    #[inline]
+    fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+        scope(sink, ScopeKind::Block, |sink| self.body.tokens(sink));
+    }
+}
+
+impl<'ast, N: ScopedName<'ast>> Structure<'ast> for Function<'ast, N> {
+    #[inline]
+    fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+        named(sink, &self.name, |sink| {
+            scope(sink, ScopeKind::Function, |sink| {
+                self.generator.tokens(sink);
+                self.type_parameters.tokens(sink);
+                self.params.tokens(sink);
+                self.return_type.tokens(sink);
+                self.body.tokens(sink);
+            });
+        });
+    }
+}
+
+impl<'ast, N: ScopedName<'ast>> Structure<'ast> for Class<'ast, N> {
+    #[inline]
+    fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+        self.decorators.tokens(sink);
+
+        named(sink, &self.name, |sink| {
+            self.type_parameters.tokens(sink);
+            self.extends.tokens(sink);
+            self.super_type_arguments.tokens(sink);
+            self.implements.tokens(sink);
+            self.body.tokens(sink);
+        });
+    }
+}
+
+impl<'ast> Structure<'ast> for Property<'ast> {
+    fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+        match *self {
+            // Both the key and a variable, renaming it changes the key
+            Property::Shorthand(ref name) => {
+                sink.token(Token::Tag("Shorthand"));
+                sink.token(Token::Str(name.item));
+                sink.token(Token::Name(name.item));
+            },
+            Property::Literal { ref key, ref value } => {
+                sink.token(Token::Tag("Literal"));
+                key.tokens(sink);
+                value.tokens(sink);
+            },
+            Property::Method { ref key, ref value } => {
+                sink.token(Token::Tag("Method"));
+                key.tokens(sink);
+                value.tokens(sink);
+            },
+            Property::Spread { ref argument } => variant(sink, "Spread", argument),
+        }
+    }
+}
+
+impl<'ast> Structure<'ast> for Pattern<'ast> {
+    fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+        match *self {
+            Pattern::Void => sink.token(Token::Tag("Void")),
+            Pattern::Identifier(ref name) => {
+                sink.token(Token::Tag("Identifier"));
+                binding(sink, name);
+            },
+            Pattern::ObjectPattern { ref properties } => {
+                sink.token(Token::Tag("ObjectPattern"));
+                sink.token(Token::Len(properties.iter().count()));
+
+                for property in properties.iter() {
+                    binding_property(sink, property);
+                }
+            },
+            Pattern::ArrayPattern { ref elements } => variant(sink, "ArrayPattern", elements),
+            Pattern::RestElement { ref argument } => {
+                sink.token(Token::Tag("RestElement"));
+                binding(sink, argument);
+            },
+            Pattern::AssignmentPattern { ref left, ref right } => {
+                sink.token(Token::Tag("AssignmentPattern"));
+                left.tokens(sink);
+                right.tokens(sink);
+            },
+            Pattern::Annotated { ref pattern, optional, ref annotation } => {
+                sink.token(Token::Tag("Annotated"));
+                pattern.tokens(sink);
+                optional.tokens(sink);
+                annotation.tokens(sink);
+            },
+            Pattern::ParameterProperty { ref modifiers, ref parameter } => {
+                sink.token(Token::Tag("ParameterProperty"));
+                modifiers.tokens(sink);
+                parameter.tokens(sink);
+            },
+        }
+    }
+}
+
+impl<'ast> Structure<'ast> for Expression<'ast> {
+    fn tokens<S: Sink<'ast>>(&self, sink: &mut S) {
+        use self::Expression::*;
+
+        match *self {
+            Void                       => sink.token(Token::Tag("Void")),
+            This(_)                    => sink.token(Token::Tag("This")),
+            Super(_)                   => sink.token(Token::Tag("Super")),
+            Identifier(name)           => {
+                sink.token(Token::Tag("Identifier"));
+                sink.token(Token::Name(name));
+            },
+            Literal(ref literal)       => variant(sink, "Literal", literal),
+            Sequence(ref expr)         => variant(sink, "Sequence", expr),
+            Parenthesized(ref expr)    => variant(sink, "Parenthesized", expr),
+            Array(ref expr)            => variant(sink, "Array", expr),
+            Member(ref expr)           => variant(sink, "Member", expr),
+            ComputedMember(ref expr)   => variant(sink, "ComputedMember", expr),
+            MetaProperty(ref expr)     => variant(sink, "MetaProperty", expr),
+            Call(ref expr)             => variant(sink, "Call", expr),
+            Binary(ref expr)           => variant(sink, "Binary", expr),
+            Prefix(ref expr)           => variant(sink, "Prefix", expr),
+            Postfix(ref expr)          => variant(sink, "Postfix", expr),
+            Conditional(ref expr)      => variant(sink, "Conditional", expr),
+            Template(ref expr)         => variant(sink, "Template", expr),
+            TaggedTemplate(ref expr)   => variant(sink, "TaggedTemplate", expr),
+            Spread(ref expr)           => variant(sink, "Spread", expr),
+            Await(ref expr)            => variant(sink, "Await", expr),
+            Arrow(ref expr)            => variant(sink, "Arrow", expr),
+            Object(ref expr)           => variant(sink, "Object", expr),
+            Function(ref expr)         => variant(sink, "Function", expr),
+            Class(ref expr)            => variant(sink, "Class", expr),
+            JSXElement(ref expr)       => variant(sink, "JSXElement", expr),
+            JSXFragment(ref expr)      => variant(sink, "JSXFragment", expr),
+            As(ref expr)               => variant(sink, "As", expr),
+            Satisfies(ref expr)        => variant(sink, "Satisfies", expr),
+            TypeCast(ref expr)         => variant(sink, "TypeCast", expr),
+            NonNull(ref expr)          => variant(sink, "NonNull", expr),
+            TypeAssertion(ref expr)    => variant(sink, "TypeAssertion", expr),
+            Instantiation(ref expr)    => variant(sink, "Instantiation", expr),
+        }
+    }
+}
+
+impl<'ast> AnyNode<'ast> {
+    /// Feed the structure of the node and its descendants to a `Hasher`.
+    /// Locations are ignored, so nodes that compare equal hash the same.
+    #[inline]
+    pub fn hash_structure<H: Hasher>(&self, state: &mut H) {
+        self.tokens(&mut Hashing(state));
+    }
+
+    /// Hash of the structure of the node and its descendants, see
+    /// `AnyNode::hash_structure`. It's computed with the standard library's
+    /// `DefaultHasher`, hashes meant to be kept across builds of a program
+    /// should use a hasher of their own.
+    pub fn structural_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+
+        self.hash_structure(&mut hasher);
+
+        hasher.finish()
+    }
+}
+
+// `PartialEq` on nodes ignores locations, same as the hash
+impl<'ast> Eq for AnyNode<'ast> {}
+
+impl<'ast> Hash for AnyNode<'ast> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash_structure(state);
+    }
+}
+
+/// Whether two nodes are equal up to a consistent renaming of the
+/// variables they declare, such as `function (a) { return a }` and
+/// `function (b) { return b }`. References to variables declared within
+/// the nodes have to resolve to declarations in the same places, following
+/// the scopes of functions and blocks. Any other name, including those of
+/// variables declared elsewhere and property names, has to be the same
+/// in both. Locations are ignored.
+pub fn alpha_equivalent<'a, 'b, A, B>(a: A, b: B) -> bool where
+    A: Into<AnyNode<'a>>,
+    B: Into<AnyNode<'b>>,
+{
+    let mut left = Vec::new();
+    let mut right = Vec::new();
+
+    a.into().tokens(&mut left);
+    b.into().tokens(&mut right);
+
+    if left.len() != right.len() {
+        return false;
+    }
+
+    let left_scopes = Scopes::new(&left);
+    let right_scopes = Scopes::new(&right);
+
+    // Scopes are numbered in the order they are entered, which is the same
+    // on both sides for as long as all other tokens match
+    let mut stack = vec![0];
+    let mut entered = 0;
+
+    left.iter().zip(right.iter()).all(|(left, right)| match (*left, *right) {
+        (Token::Enter(left), Token::Enter(right)) => {
+            entered += 1;
+            stack.push(entered);
+
+            left == right
+        },
+        (Token::Leave, Token::Leave) => {
+            stack.pop();
+
+            true
+        },
+        (Token::Name(left), Token::Name(right))         |
+        (Token::Binding(left), Token::Binding(right))   |
+        (Token::Hoisted(left), Token::Hoisted(right))   => {
+            match (left_scopes.resolve(&stack, left), right_scopes.resolve(&stack, right)) {
+                // Free variables keep their names
+                (None, None) => left == right,
+                (left, right) => left == right,
+            }
+        },
+        (left, right) => left == right,
+    })
+}
+
+/// Names declared in each scope of a tree, in the order of their first
+/// declaration. Scopes are numbered in the order they are entered, the
+/// root scope being `0`.
+struct Scopes<'ast> {
+    scopes: Vec<(ScopeKind, Vec<&'ast str>)>,
+}
+
+impl<'ast> Scopes<'ast> {
+    fn new(tokens: &[Token<'ast>]) -> Self {
+        let mut scopes = vec![(ScopeKind::Function, Vec::new())];
+        let mut stack = vec![0];
+
+        for token in tokens {
+            let (scope, name) = match *token {
+                Token::Enter(kind) => {
+                    stack.push(scopes.len());
+                    scopes.push((kind, Vec::new()));
+                    continue;
+                },
+                Token::Leave => {
+                    stack.pop();
+                    continue;
+                },
+                Token::Binding(name) => (stack[stack.len() - 1], name),
+                Token::Hoisted(name) => {
+                    let function = stack.iter()
+                        .rev()
+                        .find(|&&scope| scopes[scope].0 == ScopeKind::Function)
+                        .copied()
+                        .unwrap_or(0);
+
+                    (function, name)
+                },
+                _ => continue,
+            };
+
+            let names = &mut scopes[scope].1;
+
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+
+        Scopes {
+            scopes,
+        }
+    }
+
+    /// Innermost scope on the stack declaring `name`, and the position of
+    /// the declaration within it.
+    fn resolve(&self, stack: &[usize], name: &str) -> Option<(usize, usize)> {
+        stack.iter().rev().find_map(|&scope| {
+            self.scopes[scope].1
+                .iter()
+                .position(|declared| *declared == name)
+                .map(|index| (scope, index))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+    use crate::ast::StatementNode;
+    use crate::parse;
+    use crate::module::Module;
+
+    fn first<'ast>(module: &'ast Module<'ast>) -> &'ast StatementNode<'ast> {
+        module.body().iter().next().unwrap()
+    }
+
+    fn equivalent(a: &str, b: &str) -> bool {
+        let a = parse(a).unwrap();
+        let b = parse(b).unwrap();
+
+        alpha_equivalent(first(&a), first(&b))
+    }
+
+    fn hash(source: &str) -> u64 {
+        let module = parse(source).unwrap();
+
+        AnyNode::from(first(&module)).structural_hash()
+    }
+
+    #[test]
+    fn hash_ignores_locations() {
+        assert_eq!(hash("foo(bar + 1);"), hash("foo  ( bar+1 )  ;"));
+        assert_eq!(hash("function f(a) { return a }"), hash("function f(a) {\n  return a;\n}"));
+    }
+
+    #[test]
+    fn hash_tells_trees_apart() {
+        let sources = [
+            "foo(bar + 1);", "foo(bar - 1);", "foo(baz + 1);", "foo(bar + 2);",
+            "foo(bar, 1);", "foo(1 + bar);", "foo.bar + 1;", "foo[bar + 1];",
+            "let a = 1;", "const a = 1;", "var a = [1];", "var a = [[1]];",
+            "var a = [[], 1];", "f(a => a);", "f(a => { a });", "f(function a() {});",
+        ];
+
+        let hashes = sources.iter().map(|source| hash(source)).collect::<HashSet<_>>();
+
+        assert_eq!(hashes.len(), sources.len());
+    }
+
+    #[test]
+    fn hash_and_eq_for_common_subexpressions() {
+        let module = parse("x = a * b + c; y = (a * b) + d; z = b * a;").unwrap();
+        let mut seen = HashSet::new();
+        let mut repeated = Vec::new();
+
+        for statement in module.body().iter() {
+            for node in AnyNode::from(statement).descendants(crate::ast::Order::Pre) {
+                if node.kind() == "Expression::Binary" && !seen.insert(node) {
+                    repeated.push(node.span());
+                }
+            }
+        }
+
+        assert_eq!(repeated.len(), 1);
+    }
+
+    #[test]
+    fn alpha_equivalence() {
+        assert!(equivalent("function f(a, b) { return a + b }", "function g(x, y) { return x + y }"));
+        assert!(equivalent("let a = 1; ", "let b = 1;"));
+        assert!(equivalent("[1, 2].map(x => x * 2);", "[1, 2].map(y => y * 2);"));
+        assert!(equivalent("try {} catch (e) { log(e) }", "try {} catch (err) { log(err) }"));
+        assert!(equivalent("function f({ a }) { return a }", "function g({ a }) { return a }"));
+        assert!(equivalent("function f({ k: [a] }) { return a }", "function f({ k: [b] }) { return b }"));
+        assert!(equivalent("[(a => a), (a => a)];", "[(x => x), (y => y)];"));
+        assert!(equivalent("x = function f() { return f };", "x = function g() { return g };"));
+        assert!(equivalent("{ let a = 1; { let a = 2; a; } a; }", "{ let x = 1; { let y = 2; y; } x; }"));
+        assert!(equivalent("function f() { g(a); { var a; } }", "function f() { g(b); { var b; } }"));
+        assert!(equivalent("for (let i of a) { i; }", "for (let j of a) { j; }"));
+    }
+
+    #[test]
+    fn not_alpha_equivalent() {
+        // Swapped parameters
+        assert!(!equivalent("function f(a, b) { return a - b }", "function f(a, b) { return b - a }"));
+        // Not a one-to-one renaming
+        assert!(!equivalent("function f(a, b) { return a + b }", "function f(x, y) { return x + x }"));
+        // Free variables, properties and shorthand keys are not renamed
+        assert!(!equivalent("foo(a);", "bar(a);"));
+        assert!(!equivalent("a.foo;", "a.bar;"));
+        assert!(!equivalent("function f(a) { return { a } }", "function f(b) { return { a } }"));
+        // Free variables, which happen to share a name with a bound one
+        assert!(!equivalent("[function (a) { return a }, a];", "[function (b) { return b }, b];"));
+        assert!(!equivalent("[function f() {}, f];", "[function g() {}, g];"));
+        assert!(!equivalent("{ { let a; } a; }", "{ { let b; } b; }"));
+        assert!(!equivalent("{ for (let i of a) {} i; }", "{ for (let j of a) {} j; }"));
+        // References resolving to different declarations
+        assert!(!equivalent("{ let a = 1; { let a = 2; a; } }", "{ let x = 1; { let y = 2; x; } }"));
+        // Different structure
+        assert!(!equivalent("function f(a) { return a }", "function f(a) { return a + 1 }"));
+        assert!(!equivalent("let a = 1;", "const a = 1;"));
+    }
+}
@@ -1,30 +1,272 @@
-use ast::{Loc, TypeNode, TypeList};
-
-static TYPE_ANY: &Loc<Type<'static>> = &Loc {
-    start: 0,
-    end: 0,
-    item: Type::Any
-};
+use crate::ast::{Node, NodeList, Literal, IdentifierNode, PatternList, PropertyKey};
+use crate::ast::{BlockNode, StatementNode, ExpressionNode, Statement, TypeNode, TypeList};
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Primitive {
     Number,
     String,
     Boolean,
+    BigInt,
+    Symbol,
     Null,
     Undefined,
 }
 
+/// Name of a type, either a single identifier or a path of
+/// namespaces leading to it, such as `Foo.Bar`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TypeName<'ast> {
+    Identifier(&'ast str),
+    Qualified {
+        left: Node<'ast, TypeName<'ast>>,
+        right: IdentifierNode<'ast>,
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TypeOperator {
+    KeyOf,
+    Unique,
+    Readonly,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TypeParameter<'ast> {
+    pub name: IdentifierNode<'ast>,
+    pub constraint: Option<TypeNode<'ast>>,
+    pub default: Option<TypeNode<'ast>>,
+}
+
+/// Signature of functions, methods and constructors, as used in
+/// function types, overloads and members of interfaces.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct FunctionType<'ast> {
+    pub type_parameters: TypeParameterList<'ast>,
+    pub params: PatternList<'ast>,
+    pub return_type: Option<TypeNode<'ast>>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TypeMember<'ast> {
+    Property {
+        readonly: bool,
+        key: Node<'ast, PropertyKey<'ast>>,
+        optional: bool,
+        annotation: Option<TypeNode<'ast>>,
+    },
+    Method {
+        key: Node<'ast, PropertyKey<'ast>>,
+        optional: bool,
+        signature: Node<'ast, FunctionType<'ast>>,
+    },
+    Index {
+        readonly: bool,
+        parameter: IdentifierNode<'ast>,
+        key: TypeNode<'ast>,
+        annotation: TypeNode<'ast>,
+    },
+    Call {
+        signature: Node<'ast, FunctionType<'ast>>,
+    },
+    Construct {
+        signature: Node<'ast, FunctionType<'ast>>,
+    },
+    /// Flow object type spread, `{ ...A, b: B }`
+    Spread {
+        argument: TypeNode<'ast>,
+    },
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Type<'ast> {
     Any,
+    Unknown,
+    Never,
+    Void,
+    Object,
+    This,
     Primitive(Primitive),
-    Identifier(&'ast str),
+    Literal(Literal<'ast>),
+    Reference {
+        name: Node<'ast, TypeName<'ast>>,
+        arguments: TypeList<'ast>,
+    },
+    Query {
+        name: Node<'ast, TypeName<'ast>>,
+    },
+    Array {
+        element: TypeNode<'ast>,
+    },
+    Tuple {
+        elements: TypeList<'ast>,
+    },
+    /// Only used inside Tuple
+    Optional {
+        argument: TypeNode<'ast>,
+    },
+    /// Only used inside Tuple
+    Rest {
+        argument: TypeNode<'ast>,
+    },
     Union {
         variants: TypeList<'ast>,
     },
-    Generic {
-        ident: &'ast str,
-        subtypes: TypeList<'ast>,
+    Intersection {
+        variants: TypeList<'ast>,
+    },
+    Function(FunctionType<'ast>),
+    Constructor(FunctionType<'ast>),
+    ObjectLiteral {
+        members: NodeList<'ast, TypeMember<'ast>>,
+        /// Flow exact object type, `{| a: number |}`
+        exact: bool,
+    },
+    Mapped {
+        readonly: bool,
+        parameter: IdentifierNode<'ast>,
+        constraint: TypeNode<'ast>,
+        optional: bool,
+        annotation: Option<TypeNode<'ast>>,
+    },
+    Operator {
+        operator: TypeOperator,
+        argument: TypeNode<'ast>,
+    },
+    IndexedAccess {
+        object: TypeNode<'ast>,
+        index: TypeNode<'ast>,
+    },
+    Conditional {
+        check: TypeNode<'ast>,
+        extends: TypeNode<'ast>,
+        consequent: TypeNode<'ast>,
+        alternate: TypeNode<'ast>,
+    },
+    Infer {
+        parameter: IdentifierNode<'ast>,
+    },
+    Predicate {
+        parameter: IdentifierNode<'ast>,
+        annotation: TypeNode<'ast>,
+    },
+    /// Flow maybe type, `?string`
+    Nullable {
+        argument: TypeNode<'ast>,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Accessibility {
+    Public,
+    Protected,
+    Private,
+}
+
+/// Modifiers of class members and constructor parameters.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct Modifiers {
+    pub accessibility: Option<Accessibility>,
+    pub readonly: bool,
+    pub declare: bool,
+    pub is_override: bool,
+}
+
+impl Modifiers {
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        *self == Modifiers::default()
     }
 }
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct InterfaceDeclaration<'ast> {
+    pub name: IdentifierNode<'ast>,
+    pub type_parameters: TypeParameterList<'ast>,
+    pub extends: TypeList<'ast>,
+    pub body: BlockNode<'ast, TypeMember<'ast>>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TypeAliasDeclaration<'ast> {
+    pub name: IdentifierNode<'ast>,
+    pub type_parameters: TypeParameterList<'ast>,
+    pub value: TypeNode<'ast>,
+}
+
+/// Overload signature of a function, `function foo(a: string): string;`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct FunctionSignature<'ast> {
+    pub name: IdentifierNode<'ast>,
+    pub signature: FunctionType<'ast>,
+}
+
+/// Flow opaque type alias, `opaque type Foo: Super = Bar;`. The value
+/// is only missing in declarations, `declare opaque type Foo;`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct OpaqueTypeDeclaration<'ast> {
+    pub name: IdentifierNode<'ast>,
+    pub type_parameters: TypeParameterList<'ast>,
+    pub supertype: Option<TypeNode<'ast>>,
+    pub value: Option<TypeNode<'ast>>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TypeImportKind {
+    /// `import type`
+    Type,
+    /// `import typeof`
+    Typeof,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TypeImportSpecifier<'ast> {
+    /// `import type Foo from 'foo'`
+    Default(IdentifierNode<'ast>),
+    /// `import type * as Foo from 'foo'`
+    Namespace(IdentifierNode<'ast>),
+    /// `import type { Foo as Bar } from 'foo'`
+    Named {
+        imported: IdentifierNode<'ast>,
+        local: IdentifierNode<'ast>,
+    },
+}
+
+/// Flow import of types only, `import type { Foo } from 'foo';`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TypeImportDeclaration<'ast> {
+    pub kind: TypeImportKind,
+    pub specifiers: NodeList<'ast, TypeImportSpecifier<'ast>>,
+    pub source: Node<'ast, &'ast str>,
+}
+
+/// Member of an enum, `Foo = 1`. Names written as strings keep their quotes.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct EnumMember<'ast> {
+    pub name: IdentifierNode<'ast>,
+    pub value: Option<ExpressionNode<'ast>>,
+}
+
+/// TypeScript enum, `const enum Foo { Bar, Baz = 2 }`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct EnumDeclaration<'ast> {
+    pub is_const: bool,
+    pub name: IdentifierNode<'ast>,
+    pub members: NodeList<'ast, EnumMember<'ast>>,
+}
+
+/// TypeScript namespace, `namespace Foo {}`, also declared with the `module`
+/// keyword. Dotted names, `namespace Foo.Bar {}`, are represented as nested
+/// namespaces, with all but the outermost one exported.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct NamespaceDeclaration<'ast> {
+    pub name: IdentifierNode<'ast>,
+    pub body: BlockNode<'ast, Statement<'ast>>,
+}
+
+/// Ambient declaration, `declare const foo: number;`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DeclareStatement<'ast> {
+    pub body: StatementNode<'ast>,
+}
+
+pub type TypeParameterList<'ast> = NodeList<'ast, TypeParameter<'ast>>;
@@ -0,0 +1,529 @@
+use crate::ast::{Node, NodeList, Block, Pattern, Literal, Label};
+use crate::ast::{Statement, Expression, Property, PropertyKey, Slot, AnyNode};
+use crate::ast::{Function, Class, ClassMember, Name, EmptyName, OptionalName, MandatoryName, Decorator};
+use crate::ast::expression::*;
+use crate::ast::statement::*;
+use crate::ast::jsx::*;
+use crate::ast::types::*;
+
+/// Receives every node of a tree walked with `Walk`, in source order.
+pub(crate) trait Walker<'ast> {
+    /// Entered a node found in the given slot of its parent. Its children
+    /// are walked next if this returns `true`, followed by a matching call
+    /// to `leave` either way.
+    fn enter(&mut self, node: AnyNode<'ast>, slot: Slot) -> bool;
+
+    fn leave(&mut self);
+}
+
+/// Walk all nodes in a tree. The `slot` is the one of the closest node,
+/// types that aren't nodes themselves either pass it on to the nodes they
+/// are made of or, for structs, replace it with the names of their fields.
+pub(crate) trait Walk<'ast> {
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, slot: Slot);
+}
+
+impl<'ast, T> Walk<'ast> for Node<'ast, T> where
+    T: Walk<'ast>,
+    AnyNode<'ast>: From<&'ast Node<'ast, T>>,
+{
+    #[inline]
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, slot: Slot) {
+        if walker.enter(AnyNode::from(self), slot) {
+            self.item.walk(walker, slot);
+        }
+        walker.leave();
+    }
+}
+
+impl<'ast, T> Walk<'ast> for NodeList<'ast, T> where
+    Node<'ast, T>: Walk<'ast>,
+{
+    #[inline]
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, slot: Slot) {
+        for (index, node) in self.iter().enumerate() {
+            node.walk(walker, slot.at(index));
+        }
+    }
+}
+
+impl<'ast, T: Walk<'ast>> Walk<'ast> for Option<T> {
+    #[inline]
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, slot: Slot) {
+        if let Some(ref item) = *self {
+            item.walk(walker, slot);
+        }
+    }
+}
+
+macro_rules! walk_leaf {
+    ($( $type:ty ),*) => ($(
+        impl<'ast> Walk<'ast> for $type {
+            #[inline]
+            fn walk<W: Walker<'ast>>(&'ast self, _: &mut W, _: Slot) {}
+        }
+    )*)
+}
+
+macro_rules! walk_struct {
+    ($( $type:ident { $( $field:ident ),* } )*) => ($(
+        impl<'ast> Walk<'ast> for $type<'ast> {
+            #[inline]
+            fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, _: Slot) {
+                $( self.$field.walk(walker, Slot::field(stringify!($field))); )*
+            }
+        }
+    )*)
+}
+
+walk_leaf! {
+    &'ast str, Literal<'ast>, Label<'ast>, EmptyName, JSXOpeningFragment, JSXClosingFragment
+}
+
+walk_struct! {
+    Decorator { expression }
+    SequenceExpression { body }
+    ParenthesizedExpression { expression }
+    ArrayExpression { body }
+    MemberExpression { object, property }
+    MetaPropertyExpression { meta, property }
+    ComputedMemberExpression { object, property }
+    CallExpression { callee, arguments }
+    BinaryExpression { left, right }
+    PrefixExpression { operand }
+    PostfixExpression { operand }
+    ConditionalExpression { test, consequent, alternate }
+    TemplateLiteral { expressions, quasis }
+    TaggedTemplateExpression { tag, quasi }
+    SpreadExpression { argument }
+    AwaitExpression { argument }
+    ArrowExpression { type_parameters, params, return_type, body }
+    AsExpression { expression, annotation }
+    SatisfiesExpression { expression, annotation }
+    TypeCastExpression { expression, annotation }
+    NonNullExpression { expression }
+    TypeAssertion { annotation, expression }
+    InstantiationExpression { expression, arguments }
+    ObjectExpression { body }
+    Declarator { id, init }
+    DeclarationStatement { declarators }
+    ReturnStatement { value }
+    BreakStatement { label }
+    ContinueStatement { label }
+    ThrowStatement { value }
+    IfStatement { test, consequent, alternate }
+    WhileStatement { test, body }
+    DoStatement { body, test }
+    ForStatement { init, test, update, body }
+    ForInStatement { left, right, body }
+    ForOfStatement { left, right, body }
+    CatchClause { param, body }
+    TryStatement { block, handler, finalizer }
+    LabeledStatement { label, body }
+    SwitchStatement { discriminant, cases }
+    SwitchCase { test, consequent }
+    JSXMemberExpression { object, property }
+    JSXAttribute { name, value }
+    JSXSpreadAttribute { argument }
+    JSXExpressionContainer { expression }
+    JSXOpeningElement { name, attributes }
+    JSXClosingElement { name }
+    JSXElement { opening, children, closing }
+    JSXFragment { opening, children, closing }
+    TypeParameter { name, constraint, default }
+    FunctionType { type_parameters, params, return_type }
+    InterfaceDeclaration { name, type_parameters, extends, body }
+    TypeAliasDeclaration { name, type_parameters, value }
+    FunctionSignature { name, signature }
+    DeclareStatement { body }
+    OpaqueTypeDeclaration { name, type_parameters, supertype, value }
+    TypeImportDeclaration { specifiers, source }
+    EnumMember { name, value }
+    EnumDeclaration { name, members }
+    NamespaceDeclaration { name, body }
+    ExportDeclaration { declaration }
+}
+
+impl<'ast> Walk<'ast> for MandatoryName<'ast> {
+    #[inline]
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, slot: Slot) {
+        self.0.walk(walker, slot);
+    }
+}
+
+impl<'ast> Walk<'ast> for OptionalName<'ast> {
+    #[inline]
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, slot: Slot) {
+        self.0.walk(walker, slot);
+    }
+}
+
+impl<'ast, T> Walk<'ast> for Block<'ast, T> where
+    Node<'ast, T>: Walk<'ast>,
+{
+    #[inline]
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, _: Slot) {
+        self.body.walk(walker, Slot::field("body"));
+    }
+}
+
+impl<'ast, N> Walk<'ast> for Function<'ast, N> where
+    N: Name<'ast> + Walk<'ast>,
+{
+    #[inline]
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, _: Slot) {
+        self.name.walk(walker, Slot::field("name"));
+        self.type_parameters.walk(walker, Slot::field("type_parameters"));
+        self.params.walk(walker, Slot::field("params"));
+        self.return_type.walk(walker, Slot::field("return_type"));
+        self.body.walk(walker, Slot::field("body"));
+    }
+}
+
+impl<'ast, N> Walk<'ast> for Class<'ast, N> where
+    N: Name<'ast> + Walk<'ast>,
+{
+    #[inline]
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, _: Slot) {
+        self.decorators.walk(walker, Slot::field("decorators"));
+        self.name.walk(walker, Slot::field("name"));
+        self.type_parameters.walk(walker, Slot::field("type_parameters"));
+        self.extends.walk(walker, Slot::field("extends"));
+        self.super_type_arguments.walk(walker, Slot::field("super_type_arguments"));
+        self.implements.walk(walker, Slot::field("implements"));
+        self.body.walk(walker, Slot::field("body"));
+    }
+}
+
+impl<'ast> Walk<'ast> for ClassMember<'ast> {
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, _: Slot) {
+        match *self {
+            ClassMember::Error => {},
+            ClassMember::Method { ref key, ref value, .. } => {
+                key.walk(walker, Slot::field("key"));
+                value.walk(walker, Slot::field("value"));
+            },
+            ClassMember::Literal { ref key, ref value, .. } => {
+                key.walk(walker, Slot::field("key"));
+                value.walk(walker, Slot::field("value"));
+            },
+            ClassMember::Property { ref key, ref annotation, ref value, .. } => {
+                key.walk(walker, Slot::field("key"));
+                annotation.walk(walker, Slot::field("annotation"));
+                value.walk(walker, Slot::field("value"));
+            },
+            ClassMember::Accessor { ref key, ref annotation, ref value, .. } => {
+                key.walk(walker, Slot::field("key"));
+                annotation.walk(walker, Slot::field("annotation"));
+                value.walk(walker, Slot::field("value"));
+            },
+            ClassMember::Signature { ref key, ref signature, .. } => {
+                key.walk(walker, Slot::field("key"));
+                signature.walk(walker, Slot::field("signature"));
+            },
+            ClassMember::Modified { ref member, .. } => {
+                member.walk(walker, Slot::field("member"));
+            },
+            ClassMember::Decorated { ref decorators, ref member } => {
+                decorators.walk(walker, Slot::field("decorators"));
+                member.walk(walker, Slot::field("member"));
+            },
+        }
+    }
+}
+
+impl<'ast> Walk<'ast> for PropertyKey<'ast> {
+    #[inline]
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, _: Slot) {
+        if let PropertyKey::Computed(ref expression) = *self {
+            expression.walk(walker, Slot::field("expression"));
+        }
+    }
+}
+
+impl<'ast> Walk<'ast> for Property<'ast> {
+    #[inline]
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, _: Slot) {
+        match *self {
+            Property::Shorthand(ref name) => name.walk(walker, Slot::field("name")),
+            Property::Literal { ref key, ref value } => {
+                key.walk(walker, Slot::field("key"));
+                value.walk(walker, Slot::field("value"));
+            },
+            Property::Method { ref key, ref value } => {
+                key.walk(walker, Slot::field("key"));
+                value.walk(walker, Slot::field("value"));
+            },
+            Property::Spread { ref argument } => argument.walk(walker, Slot::field("argument")),
+        }
+    }
+}
+
+impl<'ast> Walk<'ast> for Pattern<'ast> {
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, _: Slot) {
+        match *self {
+            Pattern::Void => {},
+            Pattern::Identifier(ref name) => name.walk(walker, Slot::field("name")),
+            Pattern::ObjectPattern { ref properties } => properties.walk(walker, Slot::field("properties")),
+            Pattern::ArrayPattern { ref elements } => elements.walk(walker, Slot::field("elements")),
+            Pattern::RestElement { ref argument } => argument.walk(walker, Slot::field("argument")),
+            Pattern::AssignmentPattern { ref left, ref right } => {
+                left.walk(walker, Slot::field("left"));
+                right.walk(walker, Slot::field("right"));
+            },
+            Pattern::Annotated { ref pattern, ref annotation, .. } => {
+                pattern.walk(walker, Slot::field("pattern"));
+                annotation.walk(walker, Slot::field("annotation"));
+            },
+            Pattern::ParameterProperty { ref parameter, .. } => {
+                parameter.walk(walker, Slot::field("parameter"));
+            },
+        }
+    }
+}
+
+impl<'ast> Walk<'ast> for ArrowBody<'ast> {
+    #[inline]
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, slot: Slot) {
+        match *self {
+            ArrowBody::Expression(ref expression) => expression.walk(walker, slot),
+            ArrowBody::Block(ref block)           => block.walk(walker, slot),
+        }
+    }
+}
+
+impl<'ast> Walk<'ast> for ForInit<'ast> {
+    #[inline]
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, slot: Slot) {
+        match *self {
+            ForInit::Declaration(ref declaration) => declaration.walk(walker, slot),
+            ForInit::Expression(ref expression)   => expression.walk(walker, Slot::field("expression")),
+        }
+    }
+}
+
+impl<'ast> Walk<'ast> for JSXElementName<'ast> {
+    #[inline]
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, slot: Slot) {
+        match *self {
+            JSXElementName::Identifier(_) => {},
+            JSXElementName::Namespaced { ref namespace, ref name } => {
+                namespace.walk(walker, Slot::field("namespace"));
+                name.walk(walker, Slot::field("name"));
+            },
+            JSXElementName::Member(ref member) => member.walk(walker, slot),
+        }
+    }
+}
+
+impl<'ast> Walk<'ast> for JSXAttributeValue<'ast> {
+    #[inline]
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, slot: Slot) {
+        match *self {
+            JSXAttributeValue::String(_)            => {},
+            JSXAttributeValue::Expression(ref expr) => expr.walk(walker, slot),
+            JSXAttributeValue::Element(ref element) => element.walk(walker, slot),
+            JSXAttributeValue::Fragment(ref frag)   => frag.walk(walker, slot),
+        }
+    }
+}
+
+impl<'ast> Walk<'ast> for JSXAttributeItem<'ast> {
+    #[inline]
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, slot: Slot) {
+        match *self {
+            JSXAttributeItem::Attribute(ref attribute) => attribute.walk(walker, slot),
+            JSXAttributeItem::Spread(ref attribute)    => attribute.walk(walker, slot),
+        }
+    }
+}
+
+impl<'ast> Walk<'ast> for JSXChild<'ast> {
+    #[inline]
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, slot: Slot) {
+        match *self {
+            JSXChild::Text(_)              => {},
+            JSXChild::Expression(ref expr) => expr.walk(walker, slot),
+            JSXChild::Element(ref element) => element.walk(walker, slot),
+            JSXChild::Fragment(ref frag)   => frag.walk(walker, slot),
+        }
+    }
+}
+
+impl<'ast> Walk<'ast> for Expression<'ast> {
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, slot: Slot) {
+        use self::Expression::*;
+
+        match *self {
+            Void                       |
+            This(_)                    |
+            Super(_)                   |
+            Identifier(_)              |
+            Literal(_)                 => {},
+            Sequence(ref expr)         => expr.walk(walker, slot),
+            Parenthesized(ref expr)    => expr.walk(walker, slot),
+            Array(ref expr)            => expr.walk(walker, slot),
+            Member(ref expr)           => expr.walk(walker, slot),
+            ComputedMember(ref expr)   => expr.walk(walker, slot),
+            MetaProperty(ref expr)     => expr.walk(walker, slot),
+            Call(ref expr)             => expr.walk(walker, slot),
+            Binary(ref expr)           => expr.walk(walker, slot),
+            Prefix(ref expr)           => expr.walk(walker, slot),
+            Postfix(ref expr)          => expr.walk(walker, slot),
+            Conditional(ref expr)      => expr.walk(walker, slot),
+            Template(ref expr)         => expr.walk(walker, slot),
+            TaggedTemplate(ref expr)   => expr.walk(walker, slot),
+            Spread(ref expr)           => expr.walk(walker, slot),
+            Await(ref expr)            => expr.walk(walker, slot),
+            Arrow(ref expr)            => expr.walk(walker, slot),
+            Object(ref expr)           => expr.walk(walker, slot),
+            Function(ref expr)         => expr.walk(walker, slot),
+            Class(ref expr)            => expr.walk(walker, slot),
+            JSXElement(ref expr)       => expr.walk(walker, slot),
+            JSXFragment(ref expr)      => expr.walk(walker, slot),
+            As(ref expr)               => expr.walk(walker, slot),
+            Satisfies(ref expr)        => expr.walk(walker, slot),
+            TypeCast(ref expr)         => expr.walk(walker, slot),
+            NonNull(ref expr)          => expr.walk(walker, slot),
+            TypeAssertion(ref expr)    => expr.walk(walker, slot),
+            Instantiation(ref expr)    => expr.walk(walker, slot),
+        }
+    }
+}
+
+impl<'ast> Walk<'ast> for Statement<'ast> {
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, slot: Slot) {
+        use self::Statement::*;
+
+        match *self {
+            Empty                   => {},
+            Expression(ref expr)    => expr.walk(walker, Slot::field("expression")),
+            Declaration(ref stmt)   => stmt.walk(walker, slot),
+            Return(ref stmt)        => stmt.walk(walker, slot),
+            Break(ref stmt)         => stmt.walk(walker, slot),
+            Continue(ref stmt)      => stmt.walk(walker, slot),
+            Throw(ref stmt)         => stmt.walk(walker, slot),
+            If(ref stmt)            => stmt.walk(walker, slot),
+            While(ref stmt)         => stmt.walk(walker, slot),
+            Do(ref stmt)            => stmt.walk(walker, slot),
+            For(ref stmt)           => stmt.walk(walker, slot),
+            ForIn(ref stmt)         => stmt.walk(walker, slot),
+            ForOf(ref stmt)         => stmt.walk(walker, slot),
+            Try(ref stmt)           => stmt.walk(walker, slot),
+            Block(ref stmt)         => stmt.walk(walker, slot),
+            Labeled(ref stmt)       => stmt.walk(walker, slot),
+            Function(ref stmt)      => stmt.walk(walker, slot),
+            Class(ref stmt)         => stmt.walk(walker, slot),
+            Switch(ref stmt)        => stmt.walk(walker, slot),
+            Interface(ref stmt)     => stmt.walk(walker, slot),
+            TypeAlias(ref stmt)     => stmt.walk(walker, slot),
+            FunctionSignature(ref stmt) => stmt.walk(walker, slot),
+            Declare(ref stmt)       => stmt.walk(walker, slot),
+            OpaqueType(ref stmt)    => stmt.walk(walker, slot),
+            TypeImport(ref stmt)    => stmt.walk(walker, slot),
+            Enum(ref stmt)          => stmt.walk(walker, slot),
+            Namespace(ref stmt)     => stmt.walk(walker, slot),
+            Export(ref stmt)        => stmt.walk(walker, slot),
+        }
+    }
+}
+
+impl<'ast> Walk<'ast> for TypeImportSpecifier<'ast> {
+    #[inline]
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, _: Slot) {
+        match *self {
+            TypeImportSpecifier::Default(ref local)   |
+            TypeImportSpecifier::Namespace(ref local) => local.walk(walker, Slot::field("local")),
+            TypeImportSpecifier::Named { ref imported, ref local } => {
+                imported.walk(walker, Slot::field("imported"));
+                local.walk(walker, Slot::field("local"));
+            },
+        }
+    }
+}
+
+impl<'ast> Walk<'ast> for TypeName<'ast> {
+    #[inline]
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, _: Slot) {
+        if let TypeName::Qualified { ref left, ref right } = *self {
+            left.walk(walker, Slot::field("left"));
+            right.walk(walker, Slot::field("right"));
+        }
+    }
+}
+
+impl<'ast> Walk<'ast> for TypeMember<'ast> {
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, _: Slot) {
+        match *self {
+            TypeMember::Property { ref key, ref annotation, .. } => {
+                key.walk(walker, Slot::field("key"));
+                annotation.walk(walker, Slot::field("annotation"));
+            },
+            TypeMember::Method { ref key, ref signature, .. } => {
+                key.walk(walker, Slot::field("key"));
+                signature.walk(walker, Slot::field("signature"));
+            },
+            TypeMember::Index { ref parameter, ref key, ref annotation, .. } => {
+                parameter.walk(walker, Slot::field("parameter"));
+                key.walk(walker, Slot::field("key"));
+                annotation.walk(walker, Slot::field("annotation"));
+            },
+            TypeMember::Call { ref signature } |
+            TypeMember::Construct { ref signature } => signature.walk(walker, Slot::field("signature")),
+            TypeMember::Spread { ref argument } => argument.walk(walker, Slot::field("argument")),
+        }
+    }
+}
+
+impl<'ast> Walk<'ast> for Type<'ast> {
+    fn walk<W: Walker<'ast>>(&'ast self, walker: &mut W, slot: Slot) {
+        use self::Type::*;
+
+        match *self {
+            Any                        |
+            Unknown                    |
+            Never                      |
+            Void                       |
+            Object                     |
+            This                       |
+            Primitive(_)               |
+            Literal(_)                 => {},
+            Reference { ref name, ref arguments } => {
+                name.walk(walker, Slot::field("name"));
+                arguments.walk(walker, Slot::field("arguments"));
+            },
+            Query { ref name } => name.walk(walker, Slot::field("name")),
+            Array { ref element } => element.walk(walker, Slot::field("element")),
+            Tuple { ref elements } => elements.walk(walker, Slot::field("elements")),
+            Optional { ref argument }  |
+            Rest { ref argument }      |
+            Operator { ref argument, .. } |
+            Nullable { ref argument }  => argument.walk(walker, Slot::field("argument")),
+            Union { ref variants }     |
+            Intersection { ref variants } => variants.walk(walker, Slot::field("variants")),
+            Function(ref signature)    |
+            Constructor(ref signature) => signature.walk(walker, slot),
+            ObjectLiteral { ref members, .. } => members.walk(walker, Slot::field("members")),
+            Mapped { ref parameter, ref constraint, ref annotation, .. } => {
+                parameter.walk(walker, Slot::field("parameter"));
+                constraint.walk(walker, Slot::field("constraint"));
+                annotation.walk(walker, Slot::field("annotation"));
+            },
+            IndexedAccess { ref object, ref index } => {
+                object.walk(walker, Slot::field("object"));
+                index.walk(walker, Slot::field("index"));
+            },
+            Conditional { ref check, ref extends, ref consequent, ref alternate } => {
+                check.walk(walker, Slot::field("check"));
+                extends.walk(walker, Slot::field("extends"));
+                consequent.walk(walker, Slot::field("consequent"));
+                alternate.walk(walker, Slot::field("alternate"));
+            },
+            Infer { ref parameter } => parameter.walk(walker, Slot::field("parameter")),
+            Predicate { ref parameter, ref annotation } => {
+                parameter.walk(walker, Slot::field("parameter"));
+                annotation.walk(walker, Slot::field("annotation"));
+            },
+        }
+    }
+}
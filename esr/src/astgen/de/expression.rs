@@ -0,0 +1,390 @@
+use serde_json::Value;
+
+use crate::ast::SYNTHETIC;
+use crate::astgen::de::{Reader, Result, Object, position};
+use crate::astgen::de::function::parameters_start;
+use crate::error::EstreeErrorKind;
+use crate::owned::{Node, Expression, ExpressionNode, Literal, OperatorKind, Property, PropertyKey, EmptyName, OptionalName};
+use crate::owned::expression::*;
+
+impl Reader {
+    /// Read an expression. Babel marks parenthesized expressions with
+    /// `extra.parenthesized` instead of a node, those are read as a single
+    /// `ParenthesizedExpression` however deeply they were nested, assuming
+    /// the closing parentheses mirror the opening ones.
+    pub(super) fn expression(&mut self, value: &Value) -> Result<ExpressionNode> {
+        let mut expression = self.node(value, |reader, kind, object| reader.expression_item(kind, object))?;
+
+        // Calls start with their callee in Babel, but with their arguments in esr
+        if let Expression::Call(CallExpression { ref callee, .. }) = *expression.item {
+            if !callee.is_synthetic() && expression.start == callee.start {
+                expression.start = callee.end;
+            }
+        }
+
+        let extra = value.get("extra");
+        let parenthesized = extra.and_then(|extra| extra.get("parenthesized")).and_then(Value::as_bool);
+        let paren_start = extra.and_then(|extra| extra.get("parenStart")).and_then(Value::as_u64);
+
+        if parenthesized != Some(true) {
+            return Ok(expression);
+        }
+
+        self.parenthesized = true;
+
+        let (start, end) = match paren_start {
+            Some(start) if !expression.is_synthetic() => {
+                let start = start as u32;
+
+                (start, expression.end + expression.start.saturating_sub(start))
+            },
+            _ => (SYNTHETIC, SYNTHETIC),
+        };
+
+        Ok(Node::new(start, end, Expression::Parenthesized(ParenthesizedExpression { expression })))
+    }
+
+    /// Read the item of an expression of the given type. Patterns are read
+    /// as the expressions they are parsed from, for assignments.
+    pub(super) fn expression_item(&mut self, kind: &str, object: &Object) -> Result<Expression> {
+        let expression = match kind {
+            "Identifier" => match self.string(object, "name")? {
+                // Parsed as a literal
+                ref name if name == "undefined" => Expression::Literal(Literal::Undefined),
+                name => Expression::Identifier(name),
+            },
+            "ThisExpression" => Expression::This(ThisExpression),
+            "Super"          => Expression::Super(SuperExpression),
+            "Literal"        |
+            "StringLiteral"  |
+            "NumericLiteral" |
+            "BooleanLiteral" |
+            "NullLiteral"    |
+            "RegExpLiteral"  => Expression::Literal(self.literal(kind, object)?),
+            "ArrayExpression" | "ArrayPattern" => {
+                let body = self.list(object, "elements", |reader, value| match *value {
+                    Value::Null => Ok(Node::synthetic(Expression::Void)),
+                    _           => reader.expression(value),
+                })?;
+
+                Expression::Array(ArrayExpression { body })
+            },
+            "ObjectExpression" | "ObjectPattern" => {
+                let field = if object.contains_key("keys") { "keys" } else { "properties" };
+
+                Expression::Object(ObjectExpression {
+                    body: self.list(object, field, Reader::property)?,
+                })
+            },
+            "SequenceExpression" => Expression::Sequence(SequenceExpression {
+                body: self.list(object, "expressions", Reader::expression)?,
+            }),
+            "ParenthesizedExpression" => {
+                self.parenthesized = true;
+
+                Expression::Parenthesized(ParenthesizedExpression {
+                    expression: self.field(object, "expression", Reader::expression)?,
+                })
+            },
+            "MemberExpression" => {
+                self.unsupported(object, &["optional"])?;
+
+                let target = self.field(object, "object", Reader::expression)?;
+
+                match self.flag(object, "computed")? {
+                    true => Expression::ComputedMember(ComputedMemberExpression {
+                        object: target,
+                        property: self.field(object, "property", Reader::expression)?,
+                    }),
+                    false => Expression::Member(MemberExpression {
+                        object: target,
+                        property: self.field(object, "property", Reader::identifier)?,
+                    }),
+                }
+            },
+            "MetaProperty" => Expression::MetaProperty(MetaPropertyExpression {
+                meta: self.field(object, "meta", Reader::identifier)?,
+                property: self.field(object, "property", Reader::identifier)?,
+            }),
+            "CallExpression" => {
+                self.unsupported(object, &["optional", "typeArguments", "typeParameters"])?;
+
+                Expression::Call(CallExpression {
+                    callee: self.field(object, "callee", Reader::expression)?,
+                    arguments: self.list(object, "arguments", Reader::expression)?,
+                })
+            },
+            "NewExpression" => {
+                self.unsupported(object, &["typeArguments", "typeParameters"])?;
+
+                let (_, end) = position(object);
+                let callee = self.field(object, "callee", Reader::expression)?;
+                let arguments = self.list(object, "arguments", Reader::expression)?;
+
+                // `new Foo` ends with its callee, `new Foo()` doesn't
+                if arguments.is_empty() && !callee.is_synthetic() && callee.end == end {
+                    return Ok(Expression::Prefix(PrefixExpression {
+                        operator: OperatorKind::New,
+                        operand: callee,
+                    }));
+                }
+
+                // The call node of `new Foo()` spans its arguments, as it does in the parser
+                let start = if callee.is_synthetic() { end } else { callee.end };
+
+                Expression::Prefix(PrefixExpression {
+                    operator: OperatorKind::New,
+                    operand: Node::new(start, end, Expression::Call(CallExpression {
+                        callee,
+                        arguments,
+                    })),
+                })
+            },
+            "UnaryExpression" | "UpdateExpression" => {
+                let operator = self.operator(object)?;
+                let operand = self.field(object, "argument", Reader::expression)?;
+
+                // Missing for unary expressions of Babel
+                match kind == "UnaryExpression" || self.flag(object, "prefix")? {
+                    true  => Expression::Prefix(PrefixExpression { operator, operand }),
+                    false => Expression::Postfix(PostfixExpression { operator, operand }),
+                }
+            },
+            "BinaryExpression" | "LogicalExpression" | "AssignmentExpression" => {
+                Expression::Binary(BinaryExpression {
+                    operator: self.operator(object)?,
+                    left: self.field(object, "left", Reader::expression)?,
+                    right: self.field(object, "right", Reader::expression)?,
+                })
+            },
+            // Default values of patterns read as expressions
+            "AssignmentPattern" => Expression::Binary(BinaryExpression {
+                operator: OperatorKind::Assign,
+                left: self.field(object, "left", Reader::expression)?,
+                right: self.field(object, "right", Reader::expression)?,
+            }),
+            "ConditionalExpression" => Expression::Conditional(ConditionalExpression {
+                test: self.field(object, "test", Reader::expression)?,
+                consequent: self.field(object, "consequent", Reader::expression)?,
+                alternate: self.field(object, "alternate", Reader::expression)?,
+            }),
+            "TemplateLiteral" => Expression::Template(self.template(object)?),
+            "TaggedTemplateExpression" => {
+                self.unsupported(object, &["typeArguments", "typeParameters"])?;
+
+                Expression::TaggedTemplate(TaggedTemplateExpression {
+                    tag: self.field(object, "tag", Reader::expression)?,
+                    quasi: self.field(object, "quasi", |reader, value| {
+                        reader.node(value, |reader, kind, object| match kind {
+                            "TemplateLiteral" => reader.template(object),
+                            _                 => reader.unexpected(kind),
+                        })
+                    })?,
+                })
+            },
+            "SpreadElement" | "RestElement" => Expression::Spread(SpreadExpression {
+                argument: self.field(object, "argument", Reader::expression)?,
+            }),
+            "AwaitExpression" => Expression::Await(AwaitExpression {
+                argument: self.field(object, "argument", Reader::expression)?,
+            }),
+            "ArrowFunctionExpression" => Expression::Arrow(self.arrow(object)?),
+            "FunctionExpression" => {
+                let name = OptionalName(self.optional(object, "id", Reader::identifier)?);
+
+                Expression::Function(self.function(object, name)?)
+            },
+            "ClassExpression" => {
+                self.unsupported(object, &["declare"])?;
+
+                let name = OptionalName(self.optional(object, "id", Reader::identifier)?);
+
+                Expression::Class(self.class(object, name)?)
+            },
+            "TSAsExpression" => Expression::As(AsExpression {
+                expression: self.field(object, "expression", Reader::expression)?,
+                annotation: self.field(object, "typeAnnotation", Reader::type_node)?,
+            }),
+            "TSSatisfiesExpression" => Expression::Satisfies(SatisfiesExpression {
+                expression: self.field(object, "expression", Reader::expression)?,
+                annotation: self.field(object, "typeAnnotation", Reader::type_node)?,
+            }),
+            "TypeCastExpression" => {
+                self.flow = true;
+
+                Expression::TypeCast(TypeCastExpression {
+                    expression: self.field(object, "expression", Reader::expression)?,
+                    annotation: self.field(object, "typeAnnotation", Reader::type_annotation)?,
+                })
+            },
+            "TSNonNullExpression" => {
+                self.typescript = true;
+
+                Expression::NonNull(NonNullExpression {
+                    expression: self.field(object, "expression", Reader::expression)?,
+                })
+            },
+            "TSTypeAssertion" => Expression::TypeAssertion(TypeAssertion {
+                annotation: self.field(object, "typeAnnotation", Reader::type_node)?,
+                expression: self.field(object, "expression", Reader::expression)?,
+            }),
+            "TSInstantiationExpression" => Expression::Instantiation(InstantiationExpression {
+                expression: self.field(object, "expression", Reader::expression)?,
+                arguments: self.field(object, "typeArguments", Reader::type_instantiation)?,
+            }),
+            "JSXElement" => {
+                self.jsx = true;
+
+                Expression::JSXElement(self.jsx_element(object)?)
+            },
+            "JSXFragment" => {
+                self.jsx = true;
+
+                Expression::JSXFragment(self.jsx_fragment(object)?)
+            },
+            _ => return self.unexpected(kind),
+        };
+
+        Ok(expression)
+    }
+
+    fn template(&mut self, object: &Object) -> Result<TemplateLiteral> {
+        let quasis = self.list(object, "quasis", |reader, value| {
+            let quasi = reader.node(value, |reader, kind, object| match kind {
+                "TemplateElement" => reader.field(object, "value", |reader, value| {
+                    match value.get("raw").or_else(|| value.get("cooked")).and_then(Value::as_str) {
+                        Some(raw) => Ok(raw.to_owned()),
+                        None      => Err(reader.error(EstreeErrorKind::InvalidField)),
+                    }
+                }),
+                _ => reader.unexpected(kind),
+            })?;
+
+            // Positions of quasis include the delimiters in esr, `${` unless it's the tail
+            let tail = value.get("tail").and_then(Value::as_bool).unwrap_or(true);
+
+            Ok(match quasi.is_synthetic() {
+                true  => quasi,
+                false => Node {
+                    start: quasi.start - 1,
+                    end: quasi.end + if tail { 1 } else { 2 },
+                    item: quasi.item,
+                },
+            })
+        })?;
+
+        Ok(TemplateLiteral {
+            expressions: self.list(object, "expressions", Reader::expression)?,
+            quasis,
+        })
+    }
+
+    fn arrow(&mut self, object: &Object) -> Result<ArrowExpression> {
+        self.unsupported(object, &["async", "generator"])?;
+
+        let type_parameters = self.type_parameters(object)?;
+        let params = self.list(object, "params", Reader::pattern)?;
+        let return_type = self.return_type(object)?;
+        let body = self.field(object, "body", |reader, value| {
+            match value.get("type").and_then(Value::as_str) {
+                Some("BlockStatement") => reader.block(value).map(ArrowBody::Block),
+                _                      => reader.expression(value).map(ArrowBody::Expression),
+            }
+        })?;
+
+        Ok(ArrowExpression {
+            type_parameters,
+            params,
+            return_type,
+            body,
+        })
+    }
+
+    /// Read the `key` of a property, method or class member.
+    pub(super) fn property_key(&mut self, object: &Object) -> Result<Node<PropertyKey>> {
+        // Computed keys span their brackets, assumed to directly wrap the expression
+        if self.flag(object, "computed")? {
+            let expression = self.field(object, "key", Reader::expression)?;
+
+            return Ok(match expression.is_synthetic() {
+                true  => Node::synthetic(PropertyKey::Computed(expression)),
+                false => Node::new(expression.start - 1, expression.end + 1, PropertyKey::Computed(expression)),
+            });
+        }
+
+        self.field(object, "key", |reader, value| {
+            reader.node(value, |reader, kind, object| match kind {
+                "Identifier" => reader.string(object, "name").map(PropertyKey::Literal),
+                "Literal" | "StringLiteral" | "NumericLiteral" => match reader.literal(kind, object)? {
+                    Literal::String(raw) |
+                    Literal::Number(raw) => Ok(PropertyKey::Literal(raw)),
+                    Literal::Binary(raw) => Ok(PropertyKey::Binary(raw)),
+                    _                    => reader.unexpected(kind),
+                },
+                _ => reader.unexpected(kind),
+            })
+        })
+    }
+
+    /// Read a property of an object expression or pattern. esr writes
+    /// shorthand properties as bare identifiers.
+    pub(super) fn property(&mut self, value: &Value) -> Result<Node<Property>> {
+        self.node(value, |reader, kind, object| {
+            let property = match kind {
+                "Property" | "ObjectProperty" => {
+                    if kind == "Property" {
+                        reader.field(object, "kind", |reader, value| match value.as_str() {
+                            Some("init") => Ok(()),
+                            Some(_)      => Err(reader.error(EstreeErrorKind::UnsupportedValue)),
+                            None         => Err(reader.error(EstreeErrorKind::InvalidField)),
+                        })?;
+                    }
+
+                    let key = reader.property_key(object)?;
+
+                    if reader.flag(object, "method")? {
+                        return Ok(Property::Method {
+                            key,
+                            value: reader.field(object, "value", Reader::method)?,
+                        });
+                    }
+
+                    let value = reader.field(object, "value", Reader::expression)?;
+
+                    // Shorthand with a default value, `{ a = 1 }`, keeps the key
+                    match (reader.flag(object, "shorthand")?, &*value.item) {
+                        (true, Expression::Identifier(name)) => {
+                            Property::Shorthand(Node::new(value.start, value.end, name.clone()))
+                        },
+                        _ => Property::Literal { key, value },
+                    }
+                },
+                "ObjectMethod" => {
+                    reader.field(object, "kind", |reader, value| match value.as_str() {
+                        Some("method") => Ok(()),
+                        Some(_)        => Err(reader.error(EstreeErrorKind::UnsupportedValue)),
+                        None           => Err(reader.error(EstreeErrorKind::InvalidField)),
+                    })?;
+
+                    let key = reader.property_key(object)?;
+                    let function = reader.function(object, EmptyName)?;
+                    let start = parameters_start(&key, false, &function.type_parameters);
+                    let value = Node::new(start, function.body.end, function);
+
+                    Property::Method { key, value }
+                },
+                "SpreadElement" | "SpreadProperty" | "RestElement" | "RestProperty" => Property::Spread {
+                    argument: reader.field(object, "argument", Reader::expression)?,
+                },
+                "Identifier" => {
+                    let (start, end) = position(object);
+
+                    Property::Shorthand(Node::new(start, end, reader.string(object, "name")?))
+                },
+                _ => return reader.unexpected(kind),
+            };
+
+            Ok(property)
+        })
+    }
+}
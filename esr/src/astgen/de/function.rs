@@ -0,0 +1,284 @@
+use serde_json::Value;
+
+use crate::ast::SYNTHETIC;
+use crate::astgen::de::{Reader, Result, Object, position};
+use crate::error::EstreeErrorKind;
+use crate::owned::{Node, Pattern, Function, Class, ClassMember, MethodKind, Decorator, EmptyName, Block, PropertyKey};
+use crate::owned::{Modifiers, Accessibility, TypeParameterList};
+
+impl Reader {
+    /// Read the fields of a function onto which `name` has already been
+    /// read, from a function node or from a Babel `ObjectMethod` or
+    /// `ClassMethod`.
+    pub(super) fn function<N>(&mut self, object: &Object, name: N) -> Result<Function<N>> {
+        self.unsupported(object, &["async", "predicate"])?;
+
+        Ok(Function {
+            name,
+            generator: self.flag(object, "generator")?,
+            type_parameters: self.type_parameters(object)?,
+            params: self.list(object, "params", Reader::pattern)?,
+            return_type: self.return_type(object)?,
+            body: self.field(object, "body", Reader::block)?,
+        })
+    }
+
+    /// Read the `FunctionExpression` of a method.
+    pub(super) fn method(&mut self, value: &Value) -> Result<Node<Function<EmptyName>>> {
+        self.node(value, |reader, kind, object| match kind {
+            "FunctionExpression" => reader.function(object, EmptyName),
+            _                    => reader.unexpected(kind),
+        })
+    }
+
+    pub(super) fn class<N>(&mut self, object: &Object, name: N) -> Result<Class<N>> {
+        self.unsupported(object, &[
+            "abstract", "superTypeParameters", "superTypeArguments",
+        ])?;
+
+        Ok(Class {
+            decorators: self.optional_list(object, "decorators", Reader::decorator)?,
+            name,
+            type_parameters: self.type_parameters(object)?,
+            extends: self.optional(object, "superClass", Reader::expression)?,
+            super_type_arguments: self.type_arguments(object)?,
+            implements: self.optional_list(object, "implements", Reader::heritage)?,
+            body: self.field(object, "body", |reader, value| {
+                reader.node(value, |reader, kind, object| match kind {
+                    "ClassBody" => Ok(Block {
+                        body: reader.list(object, "body", Reader::class_member)?,
+                    }),
+                    _ => reader.unexpected(kind),
+                })
+            })?,
+        })
+    }
+
+    fn class_member(&mut self, value: &Value) -> Result<Node<ClassMember>> {
+        self.node(value, |reader, kind, object| {
+            reader.unsupported(object, &["abstract", "definite"])?;
+
+            let member = match kind {
+                "MethodDefinition" | "ClassMethod" => {
+                    let is_static = reader.flag(object, "static")?;
+                    let key = reader.property_key(object)?;
+                    let kind = reader.method_kind(object)?;
+
+                    // Babel keeps the function on the method itself
+                    let value = match object.get("value") {
+                        Some(_) => reader.field(object, "value", Reader::method)?,
+                        None    => {
+                            let function = reader.function(object, EmptyName)?;
+                            let start = parameters_start(&key, false, &function.type_parameters);
+
+                            Node::new(start, function.body.end, function)
+                        },
+                    };
+
+                    ClassMember::Method { is_static, key, kind, value }
+                },
+                "TSDeclareMethod" => {
+                    let (_, end) = position(object);
+                    let is_static = reader.flag(object, "static")?;
+                    let key = reader.property_key(object)?;
+                    let kind = reader.method_kind(object)?;
+                    let signature = reader.signature(object)?;
+                    let start = parameters_start(&key, false, &signature.type_parameters);
+
+                    ClassMember::Signature {
+                        is_static,
+                        key,
+                        kind,
+                        signature: Node::new(start, end, signature),
+                    }
+                },
+                "PropertyDefinition" | "ClassProperty" => {
+                    let is_static = reader.flag(object, "static")?;
+                    let key = reader.property_key(object)?;
+                    let optional = reader.flag(object, "optional")?;
+                    let annotation = reader.optional(object, "typeAnnotation", Reader::type_annotation)?;
+                    let value = reader.optional(object, "value", Reader::expression)?;
+
+                    // Fields without a type nor a value are parsed as bare properties
+                    match (optional || annotation.is_some(), value) {
+                        (false, Some(value)) => ClassMember::Literal { is_static, key, value },
+                        (_, value)           => ClassMember::Property {
+                            is_static,
+                            key,
+                            optional,
+                            annotation,
+                            value,
+                        },
+                    }
+                },
+                "AccessorProperty" | "ClassAccessorProperty" => ClassMember::Accessor {
+                    is_static: reader.flag(object, "static")?,
+                    key: reader.property_key(object)?,
+                    annotation: reader.optional(object, "typeAnnotation", Reader::type_annotation)?,
+                    value: reader.optional(object, "value", Reader::expression)?,
+                },
+                _ => return reader.unexpected(kind),
+            };
+
+            let (start, end) = position(object);
+            let decorators = reader.optional_list(object, "decorators", Reader::decorator)?;
+
+            // Decorators and modifiers each wrap the member that follows them
+            let inner = match decorators.last() {
+                Some(decorator) if !decorator.is_synthetic() => decorator.end + 1,
+                _                                            => start,
+            };
+
+            let modifiers = reader.modifiers(object)?;
+            let member = match modifiers.is_empty() {
+                true  => member,
+                false => {
+                    let is_static = object.get("static").and_then(Value::as_bool) == Some(true);
+                    let start = modifiers_end(inner, &modifiers, is_static);
+
+                    ClassMember::Modified {
+                        modifiers,
+                        member: Node::new(start, end, member),
+                    }
+                },
+            };
+
+            Ok(match decorators.is_empty() {
+                true  => member,
+                false => ClassMember::Decorated {
+                    decorators,
+                    member: Node::new(inner, end, member),
+                },
+            })
+        })
+    }
+
+    fn method_kind(&mut self, object: &Object) -> Result<MethodKind> {
+        self.field(object, "kind", |reader, value| match value.as_str() {
+            Some("constructor") => Ok(MethodKind::Constructor),
+            Some("method")      => Ok(MethodKind::Method),
+            Some("get")         => Ok(MethodKind::Get),
+            Some("set")         => Ok(MethodKind::Set),
+            _                   => Err(reader.error(EstreeErrorKind::InvalidField)),
+        })
+    }
+
+    fn decorator(&mut self, value: &Value) -> Result<Node<Decorator>> {
+        self.node(value, |reader, kind, object| match kind {
+            "Decorator" => Ok(Decorator {
+                expression: reader.field(object, "expression", Reader::expression)?,
+            }),
+            _ => reader.unexpected(kind),
+        })
+    }
+
+    /// Read a binding pattern, as found in parameters and declarations.
+    /// esr writes the properties of object patterns under `keys`, and type
+    /// annotations on the annotated pattern itself.
+    pub(super) fn pattern(&mut self, value: &Value) -> Result<Node<Pattern>> {
+        self.node(value, |reader, kind, object| {
+            reader.unsupported(object, &["decorators"])?;
+
+            let pattern = match kind {
+                "Identifier" => {
+                    let (start, end) = position(object);
+
+                    Pattern::Identifier(Node::new(start, end, reader.string(object, "name")?))
+                },
+                "ObjectPattern" => {
+                    let field = if object.contains_key("keys") { "keys" } else { "properties" };
+
+                    Pattern::ObjectPattern {
+                        properties: reader.list(object, field, Reader::property)?,
+                    }
+                },
+                "ArrayPattern" => Pattern::ArrayPattern {
+                    elements: reader.list(object, "elements", |reader, value| match *value {
+                        Value::Null => Ok(Node::synthetic(Pattern::Void)),
+                        _           => reader.pattern(value),
+                    })?,
+                },
+                "AssignmentPattern" => Pattern::AssignmentPattern {
+                    left: reader.field(object, "left", Reader::pattern)?,
+                    right: reader.field(object, "right", Reader::expression)?,
+                },
+                "RestElement" => Pattern::RestElement {
+                    argument: reader.field(object, "argument", Reader::identifier)?,
+                },
+                "TSParameterProperty" => Pattern::ParameterProperty {
+                    modifiers: reader.modifiers(object)?,
+                    parameter: reader.field(object, "parameter", Reader::pattern)?,
+                },
+                _ => return reader.unexpected(kind),
+            };
+
+            let optional = reader.flag(object, "optional")?;
+            let annotation = reader.optional(object, "typeAnnotation", Reader::type_annotation)?;
+
+            if !optional && annotation.is_none() {
+                return Ok(pattern);
+            }
+
+            let (start, end) = position(object);
+
+            // Annotated identifiers and rest elements end with their name
+            let pattern = match pattern {
+                Pattern::Identifier(name) if start != SYNTHETIC => {
+                    let end = start + name.item.len() as u32;
+
+                    Node::new(start, end, Pattern::Identifier(Node::new(start, end, *name.item)))
+                },
+                Pattern::RestElement { argument } if !argument.is_synthetic() => {
+                    Node::new(start, argument.end, Pattern::RestElement { argument })
+                },
+                pattern => Node::new(start, end, pattern),
+            };
+
+            Ok(Pattern::Annotated {
+                pattern,
+                optional,
+                annotation,
+            })
+        })
+    }
+}
+
+/// Start of the parameters of a method kept on its member, as Babel does,
+/// which are assumed to directly follow its key or type parameters.
+pub(super) fn parameters_start(key: &Node<PropertyKey>, optional: bool, type_parameters: &TypeParameterList) -> u32 {
+    if let Some(parameter) = type_parameters.first().filter(|parameter| !parameter.is_synthetic()) {
+        return parameter.start - 1;
+    }
+
+    match key.is_synthetic() {
+        true  => SYNTHETIC,
+        false => key.end + optional as u32,
+    }
+}
+
+/// End of the `modifiers` of a member starting at `start`, assumed to be
+/// written in the usual order, each followed by a space.
+fn modifiers_end(start: u32, modifiers: &Modifiers, is_static: bool) -> u32 {
+    if start == SYNTHETIC {
+        return SYNTHETIC;
+    }
+
+    let accessibility = match modifiers.accessibility {
+        Some(Accessibility::Public)    => "public".len(),
+        Some(Accessibility::Protected) => "protected".len(),
+        Some(Accessibility::Private)   => "private".len(),
+        None                           => 0,
+    };
+
+    // `static` comes before `readonly` and `override`
+    let words = [
+        (modifiers.accessibility.is_some(), accessibility),
+        (modifiers.declare, "declare".len()),
+        (modifiers.readonly && !is_static, "readonly".len()),
+        (modifiers.is_override && !is_static, "override".len()),
+    ];
+
+    words.iter()
+        .filter(|(written, _)| *written)
+        .fold(start, |end, (_, len)| end + *len as u32 + 1)
+}
@@ -0,0 +1,150 @@
+use serde_json::Value;
+
+use crate::astgen::de::{Reader, Result, Object, raw};
+use crate::owned::{Node, IdentifierNode};
+use crate::owned::jsx::*;
+
+impl Reader {
+    pub(super) fn jsx_element(&mut self, object: &Object) -> Result<JSXElement> {
+        Ok(JSXElement {
+            opening: self.field(object, "openingElement", |reader, value| {
+                reader.node(value, |reader, kind, object| match kind {
+                    "JSXOpeningElement" => {
+                        reader.unsupported(object, &["typeArguments", "typeParameters"])?;
+
+                        Ok(JSXOpeningElement {
+                            name: reader.field(object, "name", Reader::jsx_name)?,
+                            attributes: reader.list(object, "attributes", Reader::jsx_attribute)?,
+                            self_closing: reader.flag(object, "selfClosing")?,
+                        })
+                    },
+                    _ => reader.unexpected(kind),
+                })
+            })?,
+            children: self.list(object, "children", Reader::jsx_child)?,
+            closing: self.optional(object, "closingElement", |reader, value| {
+                reader.node(value, |reader, kind, object| match kind {
+                    "JSXClosingElement" => Ok(JSXClosingElement {
+                        name: reader.field(object, "name", Reader::jsx_name)?,
+                    }),
+                    _ => reader.unexpected(kind),
+                })
+            })?,
+        })
+    }
+
+    pub(super) fn jsx_fragment(&mut self, object: &Object) -> Result<JSXFragment> {
+        Ok(JSXFragment {
+            opening: self.field(object, "openingFragment", |reader, value| {
+                reader.node(value, |reader, kind, _| match kind {
+                    "JSXOpeningFragment" => Ok(JSXOpeningFragment),
+                    _                    => reader.unexpected(kind),
+                })
+            })?,
+            children: self.list(object, "children", Reader::jsx_child)?,
+            closing: self.field(object, "closingFragment", |reader, value| {
+                reader.node(value, |reader, kind, _| match kind {
+                    "JSXClosingFragment" => Ok(JSXClosingFragment),
+                    _                    => reader.unexpected(kind),
+                })
+            })?,
+        })
+    }
+
+    fn jsx_identifier(&mut self, value: &Value) -> Result<IdentifierNode> {
+        self.node(value, |reader, kind, object| match kind {
+            "JSXIdentifier" => reader.string(object, "name"),
+            _               => reader.unexpected(kind),
+        })
+    }
+
+    fn jsx_name(&mut self, value: &Value) -> Result<Node<JSXElementName>> {
+        self.node(value, |reader, kind, object| {
+            let name = match kind {
+                "JSXIdentifier" => JSXElementName::Identifier(reader.string(object, "name")?),
+                "JSXNamespacedName" => JSXElementName::Namespaced {
+                    namespace: reader.field(object, "namespace", Reader::jsx_identifier)?,
+                    name: reader.field(object, "name", Reader::jsx_identifier)?,
+                },
+                "JSXMemberExpression" => JSXElementName::Member(JSXMemberExpression {
+                    object: reader.field(object, "object", Reader::jsx_name)?,
+                    property: reader.field(object, "property", Reader::jsx_identifier)?,
+                }),
+                _ => return reader.unexpected(kind),
+            };
+
+            Ok(name)
+        })
+    }
+
+    fn jsx_attribute(&mut self, value: &Value) -> Result<Node<JSXAttributeItem>> {
+        self.node(value, |reader, kind, object| {
+            let attribute = match kind {
+                "JSXAttribute" => JSXAttributeItem::Attribute(JSXAttribute {
+                    name: reader.field(object, "name", Reader::jsx_name)?,
+                    value: reader.optional(object, "value", Reader::jsx_attribute_value)?,
+                }),
+                "JSXSpreadAttribute" => JSXAttributeItem::Spread(JSXSpreadAttribute {
+                    argument: reader.field(object, "argument", Reader::expression)?,
+                }),
+                _ => return reader.unexpected(kind),
+            };
+
+            Ok(attribute)
+        })
+    }
+
+    fn jsx_attribute_value(&mut self, value: &Value) -> Result<Node<JSXAttributeValue>> {
+        self.node(value, |reader, kind, object| {
+            let value = match kind {
+                // Strings are kept as written, quotes included
+                "Literal" | "StringLiteral" => match raw(object) {
+                    Some(raw) => JSXAttributeValue::String(raw.to_owned()),
+                    None      => {
+                        let value = reader.string(object, "value")?;
+
+                        JSXAttributeValue::String(format!("\"{}\"", value.replace('"', "&quot;")))
+                    },
+                },
+                "JSXExpressionContainer" => JSXAttributeValue::Expression(reader.jsx_container(object)?),
+                "JSXElement"  => JSXAttributeValue::Element(reader.jsx_element(object)?),
+                "JSXFragment" => JSXAttributeValue::Fragment(reader.jsx_fragment(object)?),
+                _ => return reader.unexpected(kind),
+            };
+
+            Ok(value)
+        })
+    }
+
+    fn jsx_child(&mut self, value: &Value) -> Result<Node<JSXChild>> {
+        self.node(value, |reader, kind, object| {
+            let child = match kind {
+                "JSXText" => match raw(object) {
+                    Some(raw) => JSXChild::Text(raw.to_owned()),
+                    None      => JSXChild::Text(reader.string(object, "value")?),
+                },
+                "JSXExpressionContainer" => JSXChild::Expression(reader.jsx_container(object)?),
+                "JSXElement"  => JSXChild::Element(reader.jsx_element(object)?),
+                "JSXFragment" => JSXChild::Fragment(reader.jsx_fragment(object)?),
+                _ => return reader.unexpected(kind),
+            };
+
+            Ok(child)
+        })
+    }
+
+    /// Read an expression container, `{}` holding a `JSXEmptyExpression`.
+    fn jsx_container(&mut self, object: &Object) -> Result<JSXExpressionContainer> {
+        let is_empty = object.get("expression")
+            .and_then(|expression| expression.get("type"))
+            .and_then(Value::as_str) == Some("JSXEmptyExpression");
+
+        if is_empty {
+            return Ok(JSXExpressionContainer { expression: None });
+        }
+
+        Ok(JSXExpressionContainer {
+            expression: Some(self.field(object, "expression", Reader::expression)?),
+        })
+    }
+}
@@ -0,0 +1,779 @@
+//! Reading ESTree JSON back into an AST, the inverse of serializing a
+//! `Module`. Besides ESTree as produced by acorn, espree or esr itself,
+//! the reader accepts the nodes in which the Babel AST differs, such as
+//! `StringLiteral` or `ObjectProperty`. TypeScript and Flow syntax is read
+//! in the form esr writes it, with Flow types as TypeScript nodes.
+//!
+//! Nodes are read into the owned AST, which is then allocated on the
+//! `Arena` of a new `Module`.
+
+mod expression;
+mod statement;
+mod function;
+mod jsx;
+mod types;
+
+use std::fmt::Write;
+use serde_json::{Map, Value};
+
+use crate::ast::SYNTHETIC;
+use crate::error::{EstreeError, EstreeErrorKind};
+use crate::owned::{self, Node, IdentifierNode, Literal, OperatorKind, StatementList};
+use crate::parser::ParseOptions;
+
+type Object = Map<String, Value>;
+
+type Result<T> = ::std::result::Result<T, EstreeError>;
+
+/// Node types of ESTree and Babel, along with their JSX extensions. Nodes
+/// of these types, as well as TypeScript and Flow nodes, are reported as
+/// unsupported rather than unknown when they can't be read.
+const KNOWN: &[&str] = &[
+    "Program", "Identifier", "Literal", "ExpressionStatement", "BlockStatement", "StaticBlock",
+    "EmptyStatement", "DebuggerStatement", "WithStatement", "ReturnStatement", "LabeledStatement",
+    "BreakStatement", "ContinueStatement", "IfStatement", "SwitchStatement", "SwitchCase",
+    "ThrowStatement", "TryStatement", "CatchClause", "WhileStatement", "DoWhileStatement",
+    "ForStatement", "ForInStatement", "ForOfStatement", "FunctionDeclaration", "VariableDeclaration",
+    "VariableDeclarator", "ThisExpression", "Super", "ArrayExpression", "ObjectExpression", "Property",
+    "FunctionExpression", "UnaryExpression", "UpdateExpression", "BinaryExpression",
+    "AssignmentExpression", "LogicalExpression", "MemberExpression", "ConditionalExpression",
+    "CallExpression", "NewExpression", "SequenceExpression", "ArrowFunctionExpression",
+    "YieldExpression", "AwaitExpression", "TemplateLiteral", "TaggedTemplateExpression",
+    "TemplateElement", "ObjectPattern", "ArrayPattern", "RestElement", "AssignmentPattern",
+    "ClassBody", "MethodDefinition", "PropertyDefinition", "AccessorProperty", "ClassDeclaration",
+    "ClassExpression", "MetaProperty", "ImportDeclaration", "ImportSpecifier",
+    "ImportDefaultSpecifier", "ImportNamespaceSpecifier", "ImportExpression",
+    "ExportNamedDeclaration", "ExportSpecifier", "ExportDefaultDeclaration", "ExportAllDeclaration",
+    "ChainExpression", "PrivateIdentifier", "SpreadElement", "ParenthesizedExpression",
+
+    "JSXElement", "JSXFragment", "JSXOpeningElement", "JSXClosingElement", "JSXOpeningFragment",
+    "JSXClosingFragment", "JSXIdentifier", "JSXNamespacedName", "JSXMemberExpression",
+    "JSXAttribute", "JSXSpreadAttribute", "JSXExpressionContainer", "JSXEmptyExpression",
+    "JSXSpreadChild", "JSXText",
+
+    "File", "Directive", "DirectiveLiteral", "InterpreterDirective", "StringLiteral",
+    "NumericLiteral", "BooleanLiteral", "NullLiteral", "RegExpLiteral", "BigIntLiteral",
+    "DecimalLiteral", "ObjectProperty", "ObjectMethod", "ClassMethod", "ClassPrivateMethod",
+    "ClassProperty", "ClassPrivateProperty", "ClassAccessorProperty", "PrivateName",
+    "OptionalMemberExpression", "OptionalCallExpression", "Import", "Decorator", "SpreadProperty",
+    "RestProperty",
+];
+
+/// Flow nodes that neither start with `Declare` nor end with `TypeAnnotation`.
+const FLOW: &[&str] = &[
+    "TypeAlias", "OpaqueType", "InterfaceDeclaration", "TypeCastExpression", "TypeParameter",
+    "TypeParameterDeclaration", "TypeParameterInstantiation", "ObjectTypeProperty",
+    "ObjectTypeIndexer", "ObjectTypeCallProperty", "ObjectTypeSpreadProperty",
+    "ObjectTypeInternalSlot", "FunctionTypeParam", "QualifiedTypeIdentifier", "InterfaceExtends",
+    "ClassImplements", "Variance", "EnumDeclaration",
+];
+
+#[inline]
+fn is_known(kind: &str) -> bool {
+    KNOWN.contains(&kind) ||
+    FLOW.contains(&kind) ||
+    kind.starts_with("TS") ||
+    kind.starts_with("Declare") ||
+    kind.ends_with("TypeAnnotation")
+}
+
+/// Operators of ESTree, they are all written the same as in the source.
+const OPERATORS: &[OperatorKind] = {
+    use self::OperatorKind::*;
+
+    &[
+        New, Increment, Decrement, LogicalNot, BitwiseNot, Typeof, Void, Delete, Multiplication,
+        Division, Remainder, Exponent, Addition, Subtraction, BitShiftLeft, BitShiftRight,
+        UBitShiftRight, Lesser, LesserEquals, Greater, GreaterEquals, Instanceof, In,
+        StrictEquality, StrictInequality, Equality, Inequality, BitwiseAnd, BitwiseXor, BitwiseOr,
+        LogicalAnd, LogicalOr, Assign, AddAssign, SubtractAssign, ExponentAssign, MultiplyAssign,
+        DivideAssign, RemainderAssign, BSLAssign, BSRAssign, UBSRAssign, BitAndAssign,
+        BitXorAssign, BitOrAssign,
+    ]
+};
+
+/// Read a `Program`, or a Babel `File` wrapping one, into an owned `Module`.
+pub(crate) fn module(value: &Value) -> Result<owned::Module> {
+    let mut reader = Reader::default();
+    let body = reader.program(value)?;
+
+    Ok(owned::Module {
+        body,
+        options: ParseOptions {
+            jsx: reader.jsx,
+            typescript: reader.typescript && !reader.flow,
+            flow: reader.flow,
+            parenthesized: reader.parenthesized,
+            ..ParseOptions::default()
+        },
+    })
+}
+
+/// Error for input that isn't JSON at all.
+pub(crate) fn invalid_json(error: &serde_json::Error) -> EstreeError {
+    EstreeError {
+        kind: EstreeErrorKind::InvalidJson(error.to_string()),
+        path: "$".to_owned(),
+    }
+}
+
+/// Start and end of a node, from either the `start` and `end` fields or
+/// a `range`. Nodes without positions are synthetic.
+fn position(object: &Object) -> (u32, u32) {
+    let start = object.get("start").and_then(Value::as_u64);
+    let end = object.get("end").and_then(Value::as_u64);
+
+    if let (Some(start), Some(end)) = (start, end) {
+        return (start as u32, end as u32);
+    }
+
+    let range = object.get("range").and_then(Value::as_array).map(Vec::as_slice);
+
+    match range {
+        Some([start, end]) => match (start.as_u64(), end.as_u64()) {
+            (Some(start), Some(end)) => (start as u32, end as u32),
+            _                        => (SYNTHETIC, SYNTHETIC),
+        },
+        _ => (SYNTHETIC, SYNTHETIC),
+    }
+}
+
+/// Source of a literal as written, under `raw` in ESTree and `extra.raw`
+/// in Babel.
+fn raw(object: &Object) -> Option<&str> {
+    object.get("raw")
+        .or_else(|| object.get("extra").and_then(|extra| extra.get("raw")))
+        .and_then(Value::as_str)
+}
+
+/// Whether the value of an optional field is set, so that it can't be
+/// ignored.
+fn is_set(value: &Value) -> bool {
+    match *value {
+        Value::Null          => false,
+        Value::Bool(value)   => value,
+        Value::Array(ref a)  => !a.is_empty(),
+        _                    => true,
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Segment {
+    Field(&'static str),
+    Index(usize),
+}
+
+/// Reads nodes while keeping track of the JSON path to the current one,
+/// for errors.
+#[derive(Debug, Default)]
+pub(crate) struct Reader {
+    path: Vec<Segment>,
+
+    /// JSX nodes have been read
+    jsx: bool,
+
+    /// TypeScript nodes have been read, which Flow types are written as
+    typescript: bool,
+
+    /// Nodes only found in Flow have been read
+    flow: bool,
+
+    /// `ParenthesizedExpression` nodes have been read
+    parenthesized: bool,
+}
+
+impl Reader {
+    fn error(&self, kind: EstreeErrorKind) -> EstreeError {
+        let mut path = String::from("$");
+
+        for segment in &self.path {
+            match *segment {
+                Segment::Field(field) => {
+                    path.push('.');
+                    path.push_str(field);
+                },
+                Segment::Index(index) => {
+                    let _ = write!(path, "[{}]", index);
+                },
+            }
+        }
+
+        EstreeError { kind, path }
+    }
+
+    /// Report a node of the given type, found where it can't be read.
+    fn unexpected<T>(&self, kind: &str) -> Result<T> {
+        let kind = match is_known(kind) {
+            true  => EstreeErrorKind::UnsupportedNode(kind.to_owned()),
+            false => EstreeErrorKind::UnknownNode(kind.to_owned()),
+        };
+
+        Err(self.error(kind))
+    }
+
+    /// Read a node, handing its type and fields over to `read`.
+    fn node<T, F>(&mut self, value: &Value, read: F) -> Result<Node<T>> where
+        F: FnOnce(&mut Self, &str, &Object) -> Result<T>,
+    {
+        let object = match value.as_object() {
+            Some(object) => object,
+            None         => return Err(self.error(EstreeErrorKind::InvalidField)),
+        };
+
+        let kind = match object.get("type").and_then(Value::as_str) {
+            Some(kind) => kind,
+            None       => {
+                self.path.push(Segment::Field("type"));
+
+                return Err(self.error(EstreeErrorKind::InvalidField));
+            },
+        };
+
+        let (start, end) = position(object);
+        let item = read(self, kind, object)?;
+
+        Ok(Node::new(start, end, item))
+    }
+
+    /// Read a required field, which can't be `null`.
+    fn field<T, F>(&mut self, object: &Object, name: &'static str, read: F) -> Result<T> where
+        F: FnOnce(&mut Self, &Value) -> Result<T>,
+    {
+        self.path.push(Segment::Field(name));
+
+        let result = match object.get(name) {
+            Some(value) if !value.is_null() => read(self, value),
+            _ => Err(self.error(EstreeErrorKind::InvalidField)),
+        };
+
+        self.path.pop();
+
+        result
+    }
+
+    /// Read a field that can be missing or `null`.
+    fn optional<T, F>(&mut self, object: &Object, name: &'static str, read: F) -> Result<Option<T>> where
+        F: FnOnce(&mut Self, &Value) -> Result<T>,
+    {
+        match object.get(name) {
+            Some(value) if !value.is_null() => self.field(object, name, read).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Read a field holding an array, reading each element with `read`.
+    fn list<T, F>(&mut self, object: &Object, name: &'static str, mut read: F) -> Result<Vec<T>> where
+        F: FnMut(&mut Self, &Value) -> Result<T>,
+    {
+        self.field(object, name, |reader, value| {
+            let elements = match value.as_array() {
+                Some(elements) => elements,
+                None           => return Err(reader.error(EstreeErrorKind::InvalidField)),
+            };
+
+            elements.iter().enumerate().map(|(index, element)| {
+                reader.path.push(Segment::Index(index));
+
+                let result = read(reader, element);
+
+                reader.path.pop();
+
+                result
+            }).collect()
+        })
+    }
+
+    /// Same as `list`, but a missing or `null` field is an empty list.
+    fn optional_list<T, F>(&mut self, object: &Object, name: &'static str, read: F) -> Result<Vec<T>> where
+        F: FnMut(&mut Self, &Value) -> Result<T>,
+    {
+        match object.get(name) {
+            Some(value) if !value.is_null() => self.list(object, name, read),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    fn string(&mut self, object: &Object, name: &'static str) -> Result<String> {
+        self.field(object, name, |reader, value| match value.as_str() {
+            Some(value) => Ok(value.to_owned()),
+            None        => Err(reader.error(EstreeErrorKind::InvalidField)),
+        })
+    }
+
+    /// Read a boolean field, missing or `null` being `false`.
+    fn flag(&mut self, object: &Object, name: &'static str) -> Result<bool> {
+        self.optional(object, name, |reader, value| match value.as_bool() {
+            Some(value) => Ok(value),
+            None        => Err(reader.error(EstreeErrorKind::InvalidField)),
+        }).map(|flag| flag.unwrap_or(false))
+    }
+
+    /// Fail on any of the given fields being set, for those the esr AST has
+    /// no counterpart for. Nodes are reported by their type.
+    fn unsupported(&mut self, object: &Object, names: &[&'static str]) -> Result<()> {
+        for &name in names {
+            let value = match object.get(name) {
+                Some(value) if is_set(value) => value,
+                _ => continue,
+            };
+
+            self.path.push(Segment::Field(name));
+
+            let node = match *value {
+                Value::Array(ref elements) => elements.first(),
+                _                          => Some(value),
+            };
+
+            return match node.and_then(|node| node.get("type")).and_then(Value::as_str) {
+                Some(kind) => self.unexpected(kind),
+                None       => Err(self.error(EstreeErrorKind::UnsupportedValue)),
+            };
+        }
+
+        Ok(())
+    }
+
+    fn operator(&mut self, object: &Object) -> Result<OperatorKind> {
+        self.field(object, "operator", |reader, value| {
+            let operator = value.as_str().and_then(|value| {
+                OPERATORS.iter().cloned().find(|operator| operator.as_str() == value)
+            });
+
+            match operator {
+                Some(operator) => Ok(operator),
+                None           => Err(reader.error(EstreeErrorKind::InvalidField)),
+            }
+        })
+    }
+
+    fn identifier(&mut self, value: &Value) -> Result<IdentifierNode> {
+        self.node(value, |reader, kind, object| match kind {
+            "Identifier" => reader.string(object, "name"),
+            _            => reader.unexpected(kind),
+        })
+    }
+
+    /// Read a literal of ESTree, or any of the literals of Babel.
+    fn literal(&mut self, kind: &str, object: &Object) -> Result<Literal> {
+        let raw = raw(object).map(str::to_owned);
+
+        if kind == "RegExpLiteral" || object.get("regex").is_some_and(is_set) {
+            let regex = object.get("regex").and_then(Value::as_object).unwrap_or(object);
+
+            return match raw {
+                Some(raw) => Ok(Literal::RegEx(raw)),
+                None      => {
+                    let pattern = self.string(regex, "pattern")?;
+                    let flags = self.string(regex, "flags")?;
+
+                    Ok(Literal::RegEx(format!("/{}/{}", pattern, flags)))
+                },
+            };
+        }
+
+        self.unsupported(object, &["bigint"])?;
+
+        let literal = match object.get("value") {
+            Some(Value::Bool(true))  => Literal::True,
+            Some(Value::Bool(false)) => Literal::False,
+            Some(Value::String(value)) => match raw {
+                Some(raw) => Literal::String(raw),
+                None      => Literal::String(Value::String(value.clone()).to_string()),
+            },
+            Some(Value::Number(value)) => match raw {
+                Some(ref raw) if raw.starts_with("0b") || raw.starts_with("0B") => Literal::Binary(raw.clone()),
+                Some(raw) => Literal::Number(raw),
+                None      => Literal::Number(value.to_string()),
+            },
+            // esr writes `undefined` as a literal without a value
+            Some(Value::Null) | None => match raw.as_deref() {
+                Some("undefined") => Literal::Undefined,
+                _ if kind == "NullLiteral" || object.contains_key("value") => Literal::Null,
+                _ => {
+                    self.path.push(Segment::Field("value"));
+
+                    return Err(self.error(EstreeErrorKind::InvalidField));
+                },
+            },
+            Some(_) => return self.field(object, "value", |reader, _| {
+                Err(reader.error(EstreeErrorKind::InvalidField))
+            }),
+        };
+
+        Ok(literal)
+    }
+
+    fn program(&mut self, value: &Value) -> Result<StatementList> {
+        self.node(value, |reader, kind, object| match kind {
+            "Program" => reader.statements(object),
+            "File"    => reader.field(object, "program", Reader::program),
+            _         => reader.unexpected(kind),
+        }).map(|node| *node.item)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::{to_value, Value};
+    use crate::error::EstreeError;
+    use crate::error::EstreeErrorKind::*;
+    use crate::owned::ToOwnedAst;
+    use crate::parser::{parse, parse_with_options, ParseOptions};
+    use crate::{Module, Flavor};
+
+    fn round_trip(source: &str, options: ParseOptions) {
+        let module = parse_with_options(source, options).unwrap();
+        let json = to_value(&module).unwrap();
+        let read = Module::from_estree_value(&json).unwrap();
+
+        assert_eq!(to_value(&read).unwrap(), json, "{}", source);
+        assert_eq!(read.body().to_owned_ast(), module.body().to_owned_ast(), "{}", source);
+    }
+
+    fn read(json: Value) -> Module<'static> {
+        Module::from_estree_value(&json).unwrap()
+    }
+
+    fn same_as(json: Value, source: &str) {
+        let module = parse(source).unwrap();
+
+        assert_eq!(read(json).body().to_owned_ast(), module.body().to_owned_ast());
+    }
+
+    fn fail(result: Result<Module<'static>, EstreeError>) -> EstreeError {
+        match result {
+            Ok(_)      => panic!("Expected an error"),
+            Err(error) => error,
+        }
+    }
+
+    fn error(json: Value) -> (crate::error::EstreeErrorKind, String) {
+        let error = fail(Module::from_estree_value(&json));
+
+        (error.kind, error.path)
+    }
+
+    #[test]
+    fn round_trips() {
+        let sources = [
+            "",
+            "this; foo; undefined; null; true; false; 42; 0.5; 0b101; 'foo'; \"bar\"; /a+b/gi;",
+            "a = b + c * d; a += 1; x = a || b && !c; -a; typeof b; delete c.d; void 0; ++a; b--;",
+            "a ? b : c; a, b, c; a.b.c; a[b][c]; foo(a, ...b); new Foo(a); new.target;",
+            "[1, , 2, ...rest]; ({ a, b: 1, 'c': 2, 3: 4, [d]: 5, e() {}, ...f });",
+            "`foo`; `a${b}c${d}`; tag`x${y}`;",
+            "x => x; (a, b) => { return a + b; }; (a = 1, b) => a;",
+            "var a; let b = 1, c; const { d, e: f } = g; let [h, i = 2] = j;",
+            "function foo(a, b = 1, ...c) { return; } function* bar() {} (function () {});",
+            "class A extends B { constructor() { this.a = 1; } static b() {} get c() { return 1; } d = 2; }",
+            "if (a) b; else { c; } while (a) {} do {} while (b); for (;;) {} for (var i = 0; i < 1; i++) {}",
+            "for (a in b) {} for (let a in b) {} for (const a of b) break; foo: for (;;) continue foo;",
+            "try { a(); } catch (e) { throw e; } finally {} switch (a) { case 1: b; break; default: }",
+            "{ 'use strict'; } ;",
+        ];
+
+        for source in sources.iter() {
+            round_trip(source, ParseOptions::default());
+        }
+    }
+
+    #[test]
+    fn round_trips_default_exports() {
+        let sources = [
+            "export default class {} export default function* () {}",
+            "export default class A extends B {} export default function foo() {}",
+            "export default a + b; export default (function foo() {}); export default (class {});",
+            "@foo export default class A {} export default @bar class {}",
+        ];
+
+        for source in sources.iter() {
+            round_trip(source, ParseOptions::default());
+        }
+
+        let module = parse(sources[0]).unwrap();
+        let json = to_value(&module).unwrap();
+
+        assert_eq!(json["body"][0]["type"], "ExportDefaultDeclaration");
+        assert_eq!(json["body"][0]["declaration"]["type"], "ClassDeclaration");
+        assert_eq!(json["body"][0]["declaration"]["id"], Value::Null);
+        assert_eq!(json["body"][1]["declaration"]["type"], "FunctionDeclaration");
+    }
+
+    #[test]
+    fn round_trips_jsx() {
+        let options = ParseOptions { jsx: true, ..ParseOptions::default() };
+        let sources = [
+            "<div />;",
+            "<a:b c:d=\"e\" f='g' h {...i}>text &amp; more{foo}{}<j.k.l /></a:b>;",
+            "<><Foo bar={<baz />} qux=<></> />{[1]}</>;",
+        ];
+
+        for source in sources.iter() {
+            round_trip(source, options);
+        }
+    }
+
+    #[test]
+    fn round_trips_typescript() {
+        let options = ParseOptions { typescript: true, ..ParseOptions::default() };
+        let sources = [
+            "let a: number, b: string | null = null; let c: Foo.Bar<T>[] = [];",
+            "function foo<T extends U = V>(a?: T, ...b: any[]): void {} (x: unknown): x is Y => true;",
+            "type A = [never, bigint?, ...symbol[]] & { readonly a?: 1; b(c: 'd'): this; [e: string]: F };",
+            "type B = keyof typeof c; type C<T> = { [K in T]?: T[K] }; type D<T> = T extends (infer U)[] ? U : object;",
+            "type E = (a: boolean) => undefined; type F = new () => G;",
+            "interface H<T> extends I.J<T>, K { (): void; new (a: T): H<T>; l: T; }",
+            "class L<T> extends M<T> implements N { private readonly o: T; p?: number = 1; constructor(public q: T) { super(); } r(): void; }",
+            "a as B; c satisfies D; e!; <F>g; h<I>;",
+            "enum J { K, 'l' = 1 } const enum M {} namespace N { export const o = 1; }",
+            "declare let p: Q; declare function r<T>(s: T): T; declare class S {}",
+        ];
+
+        for source in sources.iter() {
+            round_trip(source, options);
+        }
+
+        let module = parse_with_options(sources[0], options).unwrap();
+        let read = Module::from_estree_value(&to_value(&module).unwrap()).unwrap();
+
+        assert!(read.options().typescript);
+        assert!(!read.options().flow);
+    }
+
+    #[test]
+    fn round_trips_flow() {
+        let options = ParseOptions { flow: true, ..ParseOptions::default() };
+        let sources = [
+            "let a: ?number = (b: any);",
+            "opaque type C: D = E; type F = {| g: H |};",
+            "import type I, { J as K } from 'l'; import typeof * as M from \"n\";",
+            "type O = { ...P, q: R }; declare export function s(): void;",
+        ];
+
+        for source in sources.iter() {
+            round_trip(source, options);
+        }
+
+        let module = parse_with_options(sources[0], options).unwrap();
+        let read = Module::from_estree_value(&to_value(&module).unwrap()).unwrap();
+
+        assert!(read.options().flow);
+        assert!(!read.options().typescript);
+    }
+
+    #[test]
+    fn options() {
+        let module = read(json!({
+            "type": "Program",
+            "body": [{
+                "type": "ExpressionStatement",
+                "expression": {
+                    "type": "JSXFragment",
+                    "openingFragment": { "type": "JSXOpeningFragment" },
+                    "children": [],
+                    "closingFragment": { "type": "JSXClosingFragment" },
+                },
+            }],
+        }));
+
+        assert!(module.options().jsx);
+        assert!(!module.options().parenthesized);
+    }
+
+    #[test]
+    fn reads_acorn() {
+        // Shorthand properties, `new` without arguments and literals without `raw`
+        same_as(json!({
+            "type": "Program",
+            "sourceType": "script",
+            "body": [{
+                "type": "ExpressionStatement",
+                "expression": {
+                    "type": "AssignmentExpression",
+                    "operator": "=",
+                    "left": {
+                        "type": "ObjectPattern",
+                        "properties": [{
+                            "type": "Property",
+                            "kind": "init",
+                            "method": false,
+                            "shorthand": true,
+                            "computed": false,
+                            "key": { "type": "Identifier", "name": "a" },
+                            "value": { "type": "Identifier", "name": "a" },
+                        }],
+                    },
+                    "right": {
+                        "type": "NewExpression",
+                        "callee": { "type": "Identifier", "name": "Foo" },
+                        "arguments": [
+                            { "type": "Literal", "value": "it's" },
+                            { "type": "Literal", "value": 1.5 },
+                            { "type": "Literal", "value": null },
+                        ],
+                    },
+                },
+            }],
+        }), "({ a } = new Foo(\"it's\", 1.5, null));");
+    }
+
+    #[test]
+    fn reads_babel() {
+        same_as(json!({
+            "type": "File",
+            "program": {
+                "type": "Program",
+                "directives": [{
+                    "type": "Directive",
+                    "value": { "type": "DirectiveLiteral", "value": "use strict", "extra": { "raw": "'use strict'" } },
+                }],
+                "body": [{
+                    "type": "ExpressionStatement",
+                    "expression": {
+                        "type": "ObjectExpression",
+                        "properties": [{
+                            "type": "ObjectProperty",
+                            "computed": false,
+                            "shorthand": false,
+                            "key": { "type": "StringLiteral", "value": "a", "extra": { "raw": "'a'" } },
+                            "value": { "type": "NumericLiteral", "value": 1, "extra": { "raw": "0x1" } },
+                        }, {
+                            "type": "ObjectMethod",
+                            "kind": "method",
+                            "computed": false,
+                            "key": { "type": "Identifier", "name": "b" },
+                            "generator": false,
+                            "async": false,
+                            "params": [{ "type": "Identifier", "name": "c" }],
+                            "body": { "type": "BlockStatement", "body": [], "directives": [] },
+                        }, {
+                            "type": "ObjectProperty",
+                            "computed": false,
+                            "shorthand": false,
+                            "key": { "type": "Identifier", "name": "d" },
+                            "value": { "type": "RegExpLiteral", "pattern": "e", "flags": "g" },
+                        }],
+                    },
+                }],
+            },
+        }), "'use strict'; ({ 'a': 0x1, b(c) {}, d: /e/g });");
+    }
+
+    #[test]
+    fn keeps_positions() {
+        let module = read(json!({
+            "type": "Program",
+            "body": [{
+                "type": "ExpressionStatement",
+                "range": [2, 5],
+                "expression": { "type": "Identifier", "name": "foo", "start": 2, "end": 5 },
+            }, {
+                "type": "EmptyStatement",
+            }],
+        }));
+
+        let body = module.body().iter().map(|statement| (statement.start, statement.end)).collect::<Vec<_>>();
+
+        assert_eq!(body, [(2, 5), (crate::ast::SYNTHETIC, crate::ast::SYNTHETIC)]);
+    }
+
+    #[test]
+    fn keeps_spans() {
+        let ts = ParseOptions { typescript: true, ..ParseOptions::default() };
+        let flow = ParseOptions { flow: true, ..ParseOptions::default() };
+        let parenthesized = ParseOptions { parenthesized: true, ..ParseOptions::default() };
+        let sources = [
+            ("new Error('x'); new A; new B.c(d)(); !a, typeof b; switch (a) { case 1: b; default: }", ParseOptions::default()),
+            ("class A { b() {} static c(d) {} get e() { return 1; } [f]() {} } ({ g() {}, [h]() {} });", ParseOptions::default()),
+            ("(a) + (b, c); (a) = 1; x = (function(){})(); ((a) => a)(1); (b[c]).d;", parenthesized),
+            ("declare let a: B; declare class C {} declare function f(a: number, ...b: any[]): void;", ts),
+            ("class A { constructor(private b: C) {} private d: E; @f private static readonly g = 1; h<T>(i) {} }", ts),
+            ("interface H { (): void; new (a: T): H; b?(c: 'd'): this; [e: string]: F }", ts),
+            ("declare export function s(): void; let a: ?number = (b: any);", flow),
+        ];
+
+        for &(source, options) in sources.iter() {
+            let module = parse_with_options(source, options).unwrap();
+            let expected = format!("{:#?}", module.body().to_owned_ast());
+
+            for &flavor in [Flavor::ESTree, Flavor::Babel].iter() {
+                // Parentheses only survive in Babel flavor
+                if options.parenthesized && flavor == Flavor::ESTree {
+                    continue;
+                }
+
+                let json = to_value(&module.flavored(flavor)).unwrap();
+                let read = Module::from_estree_value(&json).unwrap();
+
+                assert_eq!(format!("{:#?}", read.body().to_owned_ast()), expected, "{:?} {}", flavor, source);
+            }
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let statement = |statement: Value| json!({ "type": "Program", "body": [{ "type": "EmptyStatement" }, statement] });
+        let expression = |expression: Value| statement(json!({ "type": "ExpressionStatement", "expression": expression }));
+
+        assert_eq!(
+            error(statement(json!({ "type": "ImportDeclaration", "specifiers": [], "source": {} }))),
+            (UnsupportedNode("ImportDeclaration".into()), "$.body[1]".into())
+        );
+        assert_eq!(
+            error(expression(json!({ "type": "Frobnicate" }))),
+            (UnknownNode("Frobnicate".into()), "$.body[1].expression".into())
+        );
+        assert_eq!(
+            error(expression(json!({ "type": "ArrayExpression", "elements": [null, { "type": "YieldExpression", "argument": null, "delegate": false }] }))),
+            (UnsupportedNode("YieldExpression".into()), "$.body[1].expression.elements[1]".into())
+        );
+        assert_eq!(
+            error(statement(json!({ "type": "FunctionDeclaration", "async": true, "id": { "type": "Identifier", "name": "foo" }, "params": [], "body": {} }))),
+            (UnsupportedValue, "$.body[1].async".into())
+        );
+        assert_eq!(
+            error(statement(json!({
+                "type": "VariableDeclaration",
+                "kind": "let",
+                "declarations": [{
+                    "type": "VariableDeclarator",
+                    "id": {
+                        "type": "Identifier",
+                        "name": "a",
+                        "typeAnnotation": {
+                            "type": "TSTypeAnnotation",
+                            "typeAnnotation": { "type": "TSTemplateLiteralType" },
+                        },
+                    },
+                }],
+            }))),
+            (UnsupportedNode("TSTemplateLiteralType".into()), "$.body[1].declarations[0].id.typeAnnotation.typeAnnotation".into())
+        );
+        assert_eq!(
+            error(expression(json!({ "type": "CallExpression", "arguments": [] }))),
+            (InvalidField, "$.body[1].expression.callee".into())
+        );
+        assert_eq!(
+            error(expression(json!({ "name": "foo" }))),
+            (InvalidField, "$.body[1].expression.type".into())
+        );
+        assert_eq!(
+            error(json!({ "type": "ExpressionStatement" })),
+            (UnsupportedNode("ExpressionStatement".into()), "$".into())
+        );
+
+        let error = fail(Module::from_estree("{ \"type\": "));
+
+        match error.kind {
+            InvalidJson(_) => assert_eq!(error.path, "$"),
+            _ => panic!("Expected invalid JSON"),
+        }
+    }
+
+    #[test]
+    fn error_messages() {
+        let error = fail(Module::from_estree(r#"{ "type": "Program", "body": [{ "type": "WithStatement" }] }"#));
+
+        assert_eq!(error.to_string(), "Unsupported node type WithStatement at $.body[0]");
+    }
+}
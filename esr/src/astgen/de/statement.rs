@@ -0,0 +1,290 @@
+use serde_json::Value;
+
+use crate::astgen::de::{Reader, Result, Object, Segment, raw, position};
+use crate::ast::SYNTHETIC;
+use crate::error::EstreeErrorKind;
+use crate::owned::{Node, Block, BlockNode, Statement, StatementNode, StatementList, Expression, Literal};
+use crate::owned::{DeclarationKind, Declarator, Label, LabelNode, MandatoryName, OptionalName};
+use crate::owned::statement::*;
+use crate::owned::types::DeclareStatement;
+
+impl Reader {
+    pub(super) fn statement(&mut self, value: &Value) -> Result<StatementNode> {
+        self.node(value, |reader, kind, object| {
+            let statement = match kind {
+                "EmptyStatement" => Statement::Empty,
+                "ExpressionStatement" => Statement::Expression(reader.field(object, "expression", Reader::expression)?),
+                "VariableDeclaration" => Statement::Declaration(reader.declaration(object)?),
+                "FunctionDeclaration" => {
+                    let name = MandatoryName(reader.field(object, "id", Reader::identifier)?);
+
+                    Statement::Function(reader.function(object, name)?)
+                },
+                "ClassDeclaration" => {
+                    let name = MandatoryName(reader.field(object, "id", Reader::identifier)?);
+
+                    Statement::Class(reader.class(object, name)?)
+                },
+                "ReturnStatement" => Statement::Return(ReturnStatement {
+                    value: reader.optional(object, "argument", Reader::expression)?,
+                }),
+                "BreakStatement" => Statement::Break(BreakStatement {
+                    label: reader.optional(object, "label", Reader::label)?,
+                }),
+                "ContinueStatement" => Statement::Continue(ContinueStatement {
+                    label: reader.optional(object, "label", Reader::label)?,
+                }),
+                "ThrowStatement" => Statement::Throw(ThrowStatement {
+                    value: reader.field(object, "argument", Reader::expression)?,
+                }),
+                "IfStatement" => Statement::If(IfStatement {
+                    test: reader.field(object, "test", Reader::expression)?,
+                    consequent: reader.field(object, "consequent", Reader::statement)?,
+                    alternate: reader.optional(object, "alternate", Reader::statement)?,
+                }),
+                "WhileStatement" => Statement::While(WhileStatement {
+                    test: reader.field(object, "test", Reader::expression)?,
+                    body: reader.field(object, "body", Reader::statement)?,
+                }),
+                "DoWhileStatement" => Statement::Do(DoStatement {
+                    body: reader.field(object, "body", Reader::statement)?,
+                    test: reader.field(object, "test", Reader::expression)?,
+                }),
+                "ForStatement" => Statement::For(ForStatement {
+                    init: reader.optional(object, "init", Reader::for_init)?,
+                    test: reader.optional(object, "test", Reader::expression)?,
+                    update: reader.optional(object, "update", Reader::expression)?,
+                    body: reader.field(object, "body", Reader::statement)?,
+                }),
+                "ForInStatement" => Statement::ForIn(ForInStatement {
+                    left: reader.field(object, "left", Reader::for_init)?,
+                    right: reader.field(object, "right", Reader::expression)?,
+                    body: reader.field(object, "body", Reader::statement)?,
+                }),
+                "ForOfStatement" => {
+                    reader.unsupported(object, &["await"])?;
+
+                    Statement::ForOf(ForOfStatement {
+                        left: reader.field(object, "left", Reader::for_init)?,
+                        right: reader.field(object, "right", Reader::expression)?,
+                        body: reader.field(object, "body", Reader::statement)?,
+                    })
+                },
+                "TryStatement" => Statement::Try(TryStatement {
+                    block: reader.field(object, "block", Reader::block)?,
+                    handler: reader.optional(object, "handler", Reader::catch_clause)?,
+                    finalizer: reader.optional(object, "finalizer", Reader::block)?,
+                }),
+                "BlockStatement" => Statement::Block(Block {
+                    body: reader.statements(object)?,
+                }),
+                "LabeledStatement" => Statement::Labeled(LabeledStatement {
+                    label: reader.field(object, "label", Reader::label)?,
+                    body: reader.field(object, "body", Reader::statement)?,
+                }),
+                "SwitchStatement" => {
+                    let discriminant = reader.field(object, "discriminant", Reader::expression)?;
+                    let body = reader.list(object, "cases", Reader::switch_case)?;
+
+                    // ESTree has no node for the braces around the cases, assume
+                    // they open after `) ` and close with the statement
+                    let (_, end) = position(object);
+                    let cases = match discriminant.is_synthetic() || end == SYNTHETIC {
+                        true => Node::synthetic(Block { body }),
+                        false => Node::new(discriminant.end + 2, end, Block { body }),
+                    };
+
+                    Statement::Switch(SwitchStatement { discriminant, cases })
+                },
+                "TSInterfaceDeclaration" => Statement::Interface(reader.interface(object)?),
+                "TSTypeAliasDeclaration" => Statement::TypeAlias(reader.type_alias(object)?),
+                "TSDeclareFunction" => Statement::FunctionSignature(reader.function_signature(object)?),
+                "OpaqueType" => Statement::OpaqueType(reader.opaque_type(object)?),
+                "TSEnumDeclaration" => Statement::Enum(reader.enum_declaration(object)?),
+                "TSModuleDeclaration" => Statement::Namespace(reader.namespace(object)?),
+                // Imports of values have no counterpart in the esr AST
+                "ImportDeclaration" => match object.get("importKind").and_then(Value::as_str) {
+                    Some("type") | Some("typeof") => Statement::TypeImport(reader.type_import(object)?),
+                    _ => return reader.unexpected(kind),
+                },
+                "ExportNamedDeclaration" => {
+                    reader.unsupported(object, &["specifiers", "source"])?;
+
+                    Statement::Export(ExportDeclaration {
+                        is_default: false,
+                        declaration: reader.field(object, "declaration", Reader::statement)?,
+                    })
+                },
+                "ExportDefaultDeclaration" => Statement::Export(ExportDeclaration {
+                    is_default: true,
+                    declaration: reader.field(object, "declaration", Reader::default_declaration)?,
+                }),
+                _ => return reader.unexpected(kind),
+            };
+
+            // Ambient declarations are marked on the declaration itself,
+            // which is assumed to follow `declare ` in the source
+            match reader.flag(object, "declare")? {
+                true => {
+                    let (start, end) = position(object);
+                    let start = match start {
+                        SYNTHETIC => SYNTHETIC,
+                        start => start + "declare ".len() as u32,
+                    };
+
+                    Ok(Statement::Declare(DeclareStatement {
+                        body: Node::new(start, end, statement),
+                    }))
+                },
+                false => Ok(statement),
+            }
+        })
+    }
+
+    /// Read the `declaration` of an `ExportDefaultDeclaration`, which is
+    /// either a declaration or an expression. Anonymous classes and
+    /// functions are declarations without an `id`.
+    fn default_declaration(&mut self, value: &Value) -> Result<StatementNode> {
+        let kind = value.get("type").and_then(Value::as_str);
+        let anonymous = value.get("id").is_some_and(Value::is_null);
+
+        match kind {
+            Some("ClassDeclaration") | Some("FunctionDeclaration") if anonymous => {},
+            Some(kind) if kind.ends_with("Declaration") => return self.statement(value),
+            _ => {},
+        }
+
+        let expression = self.node(value, |reader, kind, object| match kind {
+            "ClassDeclaration" => {
+                reader.unsupported(object, &["declare"])?;
+
+                Ok(Expression::Class(reader.class(object, OptionalName(None))?))
+            },
+            "FunctionDeclaration" => Ok(Expression::Function(reader.function(object, OptionalName(None))?)),
+            _ => reader.expression_item(kind, object),
+        })?;
+
+        Ok(Node::new(expression.start, expression.end, Statement::Expression(expression)))
+    }
+
+    /// Read the `body` of a program or block, preceded by the `directives`
+    /// Babel keeps apart from it.
+    pub(super) fn statements(&mut self, object: &Object) -> Result<StatementList> {
+        let mut statements = self.optional_list(object, "directives", Reader::directive)?;
+
+        statements.extend(self.list(object, "body", Reader::statement)?);
+
+        Ok(statements)
+    }
+
+    fn directive(&mut self, value: &Value) -> Result<StatementNode> {
+        self.node(value, |reader, kind, object| match kind {
+            "Directive" => {
+                let value = reader.field(object, "value", |reader, value| {
+                    reader.node(value, |reader, kind, object| match kind {
+                        "DirectiveLiteral" => match raw(object) {
+                            Some(raw) => Ok(Expression::Literal(Literal::String(raw.to_owned()))),
+                            None      => {
+                                let value = reader.string(object, "value")?;
+
+                                Ok(Expression::Literal(Literal::String(Value::String(value).to_string())))
+                            },
+                        },
+                        _ => reader.unexpected(kind),
+                    })
+                })?;
+
+                Ok(Statement::Expression(value))
+            },
+            _ => reader.unexpected(kind),
+        })
+    }
+
+    pub(super) fn block(&mut self, value: &Value) -> Result<BlockNode<Statement>> {
+        self.node(value, |reader, kind, object| match kind {
+            "BlockStatement" => Ok(Block {
+                body: reader.statements(object)?,
+            }),
+            _ => reader.unexpected(kind),
+        })
+    }
+
+    fn declaration(&mut self, object: &Object) -> Result<DeclarationStatement> {
+        let kind = self.field(object, "kind", |reader, value| match value.as_str() {
+            Some("var")   => Ok(DeclarationKind::Var),
+            Some("let")   => Ok(DeclarationKind::Let),
+            Some("const") => Ok(DeclarationKind::Const),
+            Some(_)       => Err(reader.error(EstreeErrorKind::UnsupportedValue)),
+            None          => Err(reader.error(EstreeErrorKind::InvalidField)),
+        })?;
+
+        Ok(DeclarationStatement {
+            kind,
+            declarators: self.list(object, "declarations", Reader::declarator)?,
+        })
+    }
+
+    fn declarator(&mut self, value: &Value) -> Result<Node<Declarator>> {
+        self.node(value, |reader, kind, object| match kind {
+            "VariableDeclarator" => Ok(Declarator {
+                id: reader.field(object, "id", Reader::pattern)?,
+                init: reader.optional(object, "init", Reader::expression)?,
+            }),
+            _ => reader.unexpected(kind),
+        })
+    }
+
+    /// Read the `init` of a `for` statement, or the `left` of a `for-in`
+    /// or `for-of`.
+    fn for_init(&mut self, value: &Value) -> Result<Node<ForInit>> {
+        match value.get("type").and_then(Value::as_str) {
+            Some("VariableDeclaration") => self.node(value, |reader, _, object| {
+                reader.declaration(object).map(ForInit::Declaration)
+            }),
+            _ => {
+                let expression = self.expression(value)?;
+
+                Ok(Node::new(expression.start, expression.end, ForInit::Expression(expression)))
+            },
+        }
+    }
+
+    fn catch_clause(&mut self, value: &Value) -> Result<Node<CatchClause>> {
+        self.node(value, |reader, kind, object| match kind {
+            "CatchClause" => {
+                // Catch clauses without a binding can't be represented
+                let param = match object.get("param") {
+                    Some(param) if !param.is_null() => reader.field(object, "param", Reader::pattern)?,
+                    _ => {
+                        reader.path.push(Segment::Field("param"));
+
+                        return Err(reader.error(EstreeErrorKind::UnsupportedValue));
+                    },
+                };
+
+                Ok(CatchClause {
+                    param,
+                    body: reader.field(object, "body", Reader::block)?,
+                })
+            },
+            _ => reader.unexpected(kind),
+        })
+    }
+
+    fn switch_case(&mut self, value: &Value) -> Result<Node<SwitchCase>> {
+        self.node(value, |reader, kind, object| match kind {
+            "SwitchCase" => Ok(SwitchCase {
+                test: reader.optional(object, "test", Reader::expression)?,
+                consequent: reader.list(object, "consequent", Reader::statement)?,
+            }),
+            _ => reader.unexpected(kind),
+        })
+    }
+
+    fn label(&mut self, value: &Value) -> Result<LabelNode> {
+        self.node(value, |reader, kind, object| match kind {
+            "Identifier" => reader.string(object, "name").map(Label),
+            _            => reader.unexpected(kind),
+        })
+    }
+}
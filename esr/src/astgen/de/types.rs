@@ -0,0 +1,496 @@
+use serde_json::Value;
+
+use crate::ast::SYNTHETIC;
+use crate::astgen::de::{Reader, Result, Object, Segment, position};
+use crate::astgen::de::function::parameters_start;
+use crate::error::EstreeErrorKind;
+use crate::owned::{Node, NodeList, Block, BlockNode, IdentifierNode, Literal, TypeNode, TypeList};
+use crate::owned::{Type, Primitive, TypeName, TypeParameter, TypeParameterList, FunctionType, TypeMember};
+use crate::owned::{Modifiers, Accessibility, Statement};
+use crate::owned::types::*;
+
+impl Reader {
+    pub(super) fn type_node(&mut self, value: &Value) -> Result<TypeNode> {
+        self.node(value, |reader, kind, object| {
+            // Flow annotations are written as TypeScript nodes too
+            reader.typescript = true;
+
+            reader.type_item(kind, object)
+        })
+    }
+
+    fn type_item(&mut self, kind: &str, object: &Object) -> Result<Type> {
+        let item = match kind {
+            "TSAnyKeyword"       => Type::Any,
+            "TSUnknownKeyword"   => Type::Unknown,
+            "TSNeverKeyword"     => Type::Never,
+            "TSVoidKeyword"      => Type::Void,
+            "TSObjectKeyword"    => Type::Object,
+            "TSThisType"         => Type::This,
+            "TSNumberKeyword"    => Type::Primitive(Primitive::Number),
+            "TSStringKeyword"    => Type::Primitive(Primitive::String),
+            "TSBooleanKeyword"   => Type::Primitive(Primitive::Boolean),
+            "TSBigIntKeyword"    => Type::Primitive(Primitive::BigInt),
+            "TSSymbolKeyword"    => Type::Primitive(Primitive::Symbol),
+            "TSNullKeyword"      => Type::Primitive(Primitive::Null),
+            "TSUndefinedKeyword" => Type::Primitive(Primitive::Undefined),
+            "TSLiteralType" => Type::Literal(self.field(object, "literal", |reader, value| {
+                reader.node(value, |reader, kind, object| match kind {
+                    "Literal"        |
+                    "StringLiteral"  |
+                    "NumericLiteral" |
+                    "BooleanLiteral" |
+                    "NullLiteral"    => reader.literal(kind, object),
+                    _                => reader.unexpected(kind),
+                }).map(|node| *node.item)
+            })?),
+            "TSTypeReference" => Type::Reference {
+                name: self.field(object, "typeName", Reader::type_name)?,
+                arguments: self.type_arguments(object)?,
+            },
+            "TSTypeQuery" => Type::Query {
+                name: self.field(object, "exprName", Reader::type_name)?,
+            },
+            "TSArrayType" => Type::Array {
+                element: self.field(object, "elementType", Reader::type_node)?,
+            },
+            "TSTupleType" => Type::Tuple {
+                elements: self.list(object, "elementTypes", Reader::type_node)?,
+            },
+            "TSOptionalType" => Type::Optional {
+                argument: self.field(object, "typeAnnotation", Reader::type_node)?,
+            },
+            "TSRestType" => Type::Rest {
+                argument: self.field(object, "typeAnnotation", Reader::type_node)?,
+            },
+            "TSUnionType" => Type::Union {
+                variants: self.list(object, "types", Reader::type_node)?,
+            },
+            "TSIntersectionType" => Type::Intersection {
+                variants: self.list(object, "types", Reader::type_node)?,
+            },
+            "TSFunctionType"    => Type::Function(self.signature(object)?),
+            "TSConstructorType" => Type::Constructor(self.signature(object)?),
+            "TSTypeLiteral" => {
+                let exact = self.flag(object, "exact")?;
+
+                // Exact object types only exist in Flow
+                if exact {
+                    self.flow = true;
+                }
+
+                Type::ObjectLiteral {
+                    members: self.list(object, "members", Reader::type_member)?,
+                    exact,
+                }
+            },
+            "TSMappedType" => {
+                let readonly = self.flag(object, "readonly")?;
+                let (parameter, constraint) = self.field(object, "typeParameter", |reader, value| {
+                    reader.node(value, |reader, kind, object| match kind {
+                        "TSTypeParameter" => Ok((
+                            reader.field(object, "name", Reader::identifier)?,
+                            reader.field(object, "constraint", Reader::type_node)?,
+                        )),
+                        _ => reader.unexpected(kind),
+                    }).map(|node| *node.item)
+                })?;
+
+                Type::Mapped {
+                    readonly,
+                    parameter,
+                    constraint,
+                    optional: self.flag(object, "optional")?,
+                    annotation: self.optional(object, "typeAnnotation", Reader::type_node)?,
+                }
+            },
+            "TSTypeOperator" => Type::Operator {
+                operator: self.field(object, "operator", |reader, value| match value.as_str() {
+                    Some("keyof")    => Ok(TypeOperator::KeyOf),
+                    Some("unique")   => Ok(TypeOperator::Unique),
+                    Some("readonly") => Ok(TypeOperator::Readonly),
+                    Some(_)          => Err(reader.error(EstreeErrorKind::UnsupportedValue)),
+                    None             => Err(reader.error(EstreeErrorKind::InvalidField)),
+                })?,
+                argument: self.field(object, "typeAnnotation", Reader::type_node)?,
+            },
+            "TSIndexedAccessType" => Type::IndexedAccess {
+                object: self.field(object, "objectType", Reader::type_node)?,
+                index: self.field(object, "indexType", Reader::type_node)?,
+            },
+            "TSConditionalType" => Type::Conditional {
+                check: self.field(object, "checkType", Reader::type_node)?,
+                extends: self.field(object, "extendsType", Reader::type_node)?,
+                consequent: self.field(object, "trueType", Reader::type_node)?,
+                alternate: self.field(object, "falseType", Reader::type_node)?,
+            },
+            "TSInferType" => Type::Infer {
+                parameter: self.field(object, "typeParameter", |reader, value| {
+                    reader.node(value, |reader, kind, object| match kind {
+                        "TSTypeParameter" => reader.field(object, "name", Reader::identifier),
+                        _                 => reader.unexpected(kind),
+                    }).map(|node| *node.item)
+                })?,
+            },
+            "TSTypePredicate" => Type::Predicate {
+                parameter: self.field(object, "parameterName", Reader::identifier)?,
+                annotation: self.field(object, "typeAnnotation", Reader::type_annotation)?,
+            },
+            "NullableTypeAnnotation" => {
+                self.flow = true;
+
+                Type::Nullable {
+                    argument: self.field(object, "typeAnnotation", Reader::type_node)?,
+                }
+            },
+            _ => return self.unexpected(kind),
+        };
+
+        Ok(item)
+    }
+
+    /// Read the type of a `TSTypeAnnotation`, which wraps annotations of
+    /// bindings and return values.
+    pub(super) fn type_annotation(&mut self, value: &Value) -> Result<TypeNode> {
+        self.node(value, |reader, kind, object| match kind {
+            "TSTypeAnnotation" => reader.field(object, "typeAnnotation", Reader::type_node),
+            _                  => reader.unexpected(kind),
+        }).map(|node| *node.item)
+    }
+
+    fn type_name(&mut self, value: &Value) -> Result<Node<TypeName>> {
+        self.node(value, |reader, kind, object| match kind {
+            "Identifier" => reader.string(object, "name").map(TypeName::Identifier),
+            "TSQualifiedName" => Ok(TypeName::Qualified {
+                left: reader.field(object, "left", Reader::type_name)?,
+                right: reader.field(object, "right", Reader::identifier)?,
+            }),
+            _ => reader.unexpected(kind),
+        })
+    }
+
+    /// Read the `typeParameters` of a declaration, missing if there are none.
+    pub(super) fn type_parameters(&mut self, object: &Object) -> Result<TypeParameterList> {
+        let parameters = self.optional(object, "typeParameters", |reader, value| {
+            reader.node(value, |reader, kind, object| match kind {
+                "TSTypeParameterDeclaration" => reader.list(object, "params", Reader::type_parameter),
+                _                            => reader.unexpected(kind),
+            }).map(|node| *node.item)
+        })?;
+
+        Ok(parameters.unwrap_or_default())
+    }
+
+    fn type_parameter(&mut self, value: &Value) -> Result<Node<TypeParameter>> {
+        self.node(value, |reader, kind, object| match kind {
+            "TSTypeParameter" => Ok(TypeParameter {
+                name: reader.field(object, "name", Reader::identifier)?,
+                constraint: reader.optional(object, "constraint", Reader::type_node)?,
+                default: reader.optional(object, "default", Reader::type_node)?,
+            }),
+            _ => reader.unexpected(kind),
+        })
+    }
+
+    /// Read the `typeArguments` of a node, missing if there are none.
+    pub(super) fn type_arguments(&mut self, object: &Object) -> Result<TypeList> {
+        let arguments = self.optional(object, "typeArguments", Reader::type_instantiation)?;
+
+        Ok(arguments.unwrap_or_default())
+    }
+
+    pub(super) fn type_instantiation(&mut self, value: &Value) -> Result<TypeList> {
+        self.node(value, |reader, kind, object| match kind {
+            "TSTypeParameterInstantiation" => reader.list(object, "params", Reader::type_node),
+            _                              => reader.unexpected(kind),
+        }).map(|node| *node.item)
+    }
+
+    /// Read the `returnType` of a function or signature, if it has one.
+    pub(super) fn return_type(&mut self, object: &Object) -> Result<Option<TypeNode>> {
+        self.optional(object, "returnType", Reader::type_annotation)
+    }
+
+    /// Read the fields of a signature, written on function types, method
+    /// signatures and declared functions.
+    pub(super) fn signature(&mut self, object: &Object) -> Result<FunctionType> {
+        Ok(FunctionType {
+            type_parameters: self.type_parameters(object)?,
+            params: self.list(object, "params", Reader::pattern)?,
+            return_type: self.return_type(object)?,
+        })
+    }
+
+    fn type_member(&mut self, value: &Value) -> Result<Node<TypeMember>> {
+        self.node(value, |reader, kind, object| {
+            let member = match kind {
+                "TSPropertySignature" => TypeMember::Property {
+                    readonly: reader.flag(object, "readonly")?,
+                    key: reader.property_key(object)?,
+                    optional: reader.flag(object, "optional")?,
+                    annotation: reader.optional(object, "typeAnnotation", Reader::type_annotation)?,
+                },
+                "TSMethodSignature" => {
+                    let (_, end) = position(object);
+                    let key = reader.property_key(object)?;
+                    let optional = reader.flag(object, "optional")?;
+                    let signature = reader.signature(object)?;
+                    let start = parameters_start(&key, optional, &signature.type_parameters);
+
+                    TypeMember::Method {
+                        key,
+                        optional,
+                        signature: Node::new(start, end, signature),
+                    }
+                },
+                "TSIndexSignature" => {
+                    let readonly = reader.flag(object, "readonly")?;
+                    let mut parameters = reader.list(object, "parameters", |reader, value| {
+                        reader.node(value, |reader, kind, object| match kind {
+                            "Identifier" => Ok((
+                                reader.string(object, "name")?,
+                                reader.field(object, "typeAnnotation", Reader::type_annotation)?,
+                            )),
+                            _ => reader.unexpected(kind),
+                        })
+                    })?;
+
+                    // Index signatures have a single parameter
+                    if parameters.len() != 1 {
+                        reader.path.push(Segment::Field("parameters"));
+
+                        return Err(reader.error(EstreeErrorKind::UnsupportedValue));
+                    }
+
+                    let parameter = parameters.remove(0);
+                    let (name, key) = *parameter.item;
+
+                    // The parameter ends with its name, before its annotation
+                    let end = match parameter.start {
+                        SYNTHETIC => SYNTHETIC,
+                        start     => start + name.len() as u32,
+                    };
+
+                    TypeMember::Index {
+                        readonly,
+                        parameter: Node::new(parameter.start, end, name),
+                        key,
+                        annotation: reader.field(object, "typeAnnotation", Reader::type_annotation)?,
+                    }
+                },
+                "TSCallSignatureDeclaration" => {
+                    let (start, end) = position(object);
+
+                    TypeMember::Call {
+                        signature: Node::new(start, end, reader.signature(object)?),
+                    }
+                },
+                "TSConstructSignatureDeclaration" => {
+                    // The signature follows `new `
+                    let (start, end) = position(object);
+                    let start = match start {
+                        SYNTHETIC => SYNTHETIC,
+                        start     => start + "new ".len() as u32,
+                    };
+
+                    TypeMember::Construct {
+                        signature: Node::new(start, end, reader.signature(object)?),
+                    }
+                },
+                "ObjectTypeSpreadProperty" => {
+                    reader.flow = true;
+
+                    TypeMember::Spread {
+                        argument: reader.field(object, "argument", Reader::type_node)?,
+                    }
+                },
+                _ => return reader.unexpected(kind),
+            };
+
+            Ok(member)
+        })
+    }
+
+    /// Read an entry of the `extends` list of an interface or the
+    /// `implements` list of a class, as a reference to the type unless
+    /// esr wrote the type itself.
+    pub(super) fn heritage(&mut self, value: &Value) -> Result<TypeNode> {
+        self.node(value, |reader, kind, object| match kind {
+            "TSInterfaceHeritage" | "TSClassImplements" | "TSExpressionWithTypeArguments" => {
+                let expression = object.get("expression").and_then(|expression| expression.get("type"));
+
+                match expression.and_then(Value::as_str) {
+                    Some("Identifier") | Some("TSQualifiedName") => Ok(Type::Reference {
+                        name: reader.field(object, "expression", Reader::type_name)?,
+                        arguments: reader.type_arguments(object)?,
+                    }),
+                    _ => reader.field(object, "expression", Reader::type_node).map(|node| *node.item),
+                }
+            },
+            _ => reader.unexpected(kind),
+        })
+    }
+
+    /// Read the TypeScript modifiers of a class member or a parameter
+    /// property, written as fields of the node.
+    pub(super) fn modifiers(&mut self, object: &Object) -> Result<Modifiers> {
+        let accessibility = self.optional(object, "accessibility", |reader, value| match value.as_str() {
+            Some("public")    => Ok(Accessibility::Public),
+            Some("protected") => Ok(Accessibility::Protected),
+            Some("private")   => Ok(Accessibility::Private),
+            Some(_)           => Err(reader.error(EstreeErrorKind::UnsupportedValue)),
+            None              => Err(reader.error(EstreeErrorKind::InvalidField)),
+        })?;
+
+        let modifiers = Modifiers {
+            accessibility,
+            readonly: self.flag(object, "readonly")?,
+            declare: self.flag(object, "declare")?,
+            is_override: self.flag(object, "override")?,
+        };
+
+        if !modifiers.is_empty() {
+            self.typescript = true;
+        }
+
+        Ok(modifiers)
+    }
+
+    /// Read the name of an enum member or a namespace, which can be
+    /// a string kept as written.
+    fn declaration_name(&mut self, value: &Value) -> Result<IdentifierNode> {
+        self.node(value, |reader, kind, object| match kind {
+            "Identifier" => reader.string(object, "name"),
+            "Literal" | "StringLiteral" => match reader.literal(kind, object)? {
+                Literal::String(raw) => Ok(raw),
+                _                    => reader.unexpected(kind),
+            },
+            _ => reader.unexpected(kind),
+        })
+    }
+
+    pub(super) fn interface(&mut self, object: &Object) -> Result<InterfaceDeclaration> {
+        Ok(InterfaceDeclaration {
+            name: self.field(object, "id", Reader::identifier)?,
+            type_parameters: self.type_parameters(object)?,
+            extends: self.optional_list(object, "extends", Reader::heritage)?,
+            body: self.field(object, "body", |reader, value| {
+                reader.node(value, |reader, kind, object| match kind {
+                    "TSInterfaceBody" => Ok(Block {
+                        body: reader.list(object, "body", Reader::type_member)?,
+                    }),
+                    _ => reader.unexpected(kind),
+                })
+            })?,
+        })
+    }
+
+    pub(super) fn type_alias(&mut self, object: &Object) -> Result<TypeAliasDeclaration> {
+        Ok(TypeAliasDeclaration {
+            name: self.field(object, "id", Reader::identifier)?,
+            type_parameters: self.type_parameters(object)?,
+            value: self.field(object, "typeAnnotation", Reader::type_node)?,
+        })
+    }
+
+    pub(super) fn function_signature(&mut self, object: &Object) -> Result<FunctionSignature> {
+        self.unsupported(object, &["async", "generator"])?;
+
+        Ok(FunctionSignature {
+            name: self.field(object, "id", Reader::identifier)?,
+            signature: self.signature(object)?,
+        })
+    }
+
+    pub(super) fn opaque_type(&mut self, object: &Object) -> Result<OpaqueTypeDeclaration> {
+        self.flow = true;
+
+        Ok(OpaqueTypeDeclaration {
+            name: self.field(object, "id", Reader::identifier)?,
+            type_parameters: self.type_parameters(object)?,
+            supertype: self.optional(object, "supertype", Reader::type_node)?,
+            value: self.optional(object, "impltype", Reader::type_node)?,
+        })
+    }
+
+    /// Read an `import type` or `import typeof` declaration of Flow.
+    pub(super) fn type_import(&mut self, object: &Object) -> Result<TypeImportDeclaration> {
+        self.flow = true;
+
+        let kind = self.field(object, "importKind", |reader, value| match value.as_str() {
+            Some("type")   => Ok(TypeImportKind::Type),
+            Some("typeof") => Ok(TypeImportKind::Typeof),
+            Some(_)        => Err(reader.error(EstreeErrorKind::UnsupportedValue)),
+            None           => Err(reader.error(EstreeErrorKind::InvalidField)),
+        })?;
+
+        let specifiers: NodeList<TypeImportSpecifier> = self.list(object, "specifiers", |reader, value| {
+            reader.node(value, |reader, kind, object| match kind {
+                "ImportDefaultSpecifier" => {
+                    reader.field(object, "local", Reader::identifier).map(TypeImportSpecifier::Default)
+                },
+                "ImportNamespaceSpecifier" => {
+                    reader.field(object, "local", Reader::identifier).map(TypeImportSpecifier::Namespace)
+                },
+                "ImportSpecifier" => Ok(TypeImportSpecifier::Named {
+                    imported: reader.field(object, "imported", Reader::identifier)?,
+                    local: reader.field(object, "local", Reader::identifier)?,
+                }),
+                _ => reader.unexpected(kind),
+            })
+        })?;
+
+        // The source is kept as written
+        let source = self.field(object, "source", |reader, value| {
+            reader.node(value, |reader, kind, object| match kind {
+                "Literal" | "StringLiteral" => match reader.literal(kind, object)? {
+                    Literal::String(raw) => Ok(raw),
+                    _                    => reader.unexpected(kind),
+                },
+                _ => reader.unexpected(kind),
+            })
+        })?;
+
+        Ok(TypeImportDeclaration {
+            kind,
+            specifiers,
+            source,
+        })
+    }
+
+    pub(super) fn enum_declaration(&mut self, object: &Object) -> Result<EnumDeclaration> {
+        self.typescript = true;
+
+        Ok(EnumDeclaration {
+            is_const: self.flag(object, "const")?,
+            name: self.field(object, "id", Reader::identifier)?,
+            members: self.list(object, "members", |reader, value| {
+                reader.node(value, |reader, kind, object| match kind {
+                    "TSEnumMember" => Ok(EnumMember {
+                        name: reader.field(object, "id", Reader::declaration_name)?,
+                        value: reader.optional(object, "initializer", Reader::expression)?,
+                    }),
+                    _ => reader.unexpected(kind),
+                })
+            })?,
+        })
+    }
+
+    pub(super) fn namespace(&mut self, object: &Object) -> Result<NamespaceDeclaration> {
+        self.typescript = true;
+
+        Ok(NamespaceDeclaration {
+            name: self.field(object, "id", Reader::declaration_name)?,
+            body: self.field(object, "body", Reader::module_block)?,
+        })
+    }
+
+    fn module_block(&mut self, value: &Value) -> Result<BlockNode<Statement>> {
+        self.node(value, |reader, kind, object| match kind {
+            "TSModuleBlock" => Ok(Block {
+                body: reader.statements(object)?,
+            }),
+            _ => reader.unexpected(kind),
+        })
+    }
+}
@@ -1,7 +1,9 @@
 use serde::ser::{Serialize, Serializer, SerializeStruct};
 use crate::ast::{Expression, Loc, OperatorKind};
 use crate::ast::expression::*;
-use crate::astgen::SerializeInLoc;
+use crate::astgen::{SerializeInLoc, Extra, is_babel};
+use crate::astgen::statement::FunctionBody;
+use crate::astgen::types::{serialize_type_parameters, serialize_return_type};
 
 #[inline]
 fn expression_type(operator: OperatorKind, prefix: bool) -> &'static str {
@@ -48,6 +50,17 @@ impl<'ast> SerializeInLoc for SpreadExpression<'ast> {
     }
 }
 
+impl<'ast> SerializeInLoc for AwaitExpression<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "AwaitExpression", 1, |state| {
+            state.serialize_field("argument", &self.argument)
+        })
+    }
+}
+
 impl<'ast> SerializeInLoc for MemberExpression<'ast> {
     fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
     where
@@ -119,7 +132,7 @@ impl<'ast> Serialize for Loc<ArrowBody<'ast>> {
         use self::ArrowBody::*;
         match self.item {
             Expression(ref expression) => serializer.serialize_some(expression),
-            Block(ref expression) => serializer.serialize_some(expression),
+            Block(body) => serializer.serialize_some(&FunctionBody(body)),
         }
     }
 }
@@ -129,8 +142,15 @@ impl<'ast> SerializeInLoc for ArrowExpression<'ast> {
     where
         S: Serializer,
     {
-        self.in_loc(serializer, "ArrowFunctionExpression", 2, |state| {
+        self.in_loc(serializer, "ArrowFunctionExpression", 5, |state| {
+            if is_babel() {
+                state.serialize_field("id", &())?;
+                state.serialize_field("generator", &false)?;
+                state.serialize_field("async", &false)?;
+            }
+            serialize_type_parameters(state, self.type_parameters)?;
             state.serialize_field("params", &self.params)?;
+            serialize_return_type(state, self.return_type)?;
             state.serialize_field("body", &Loc::new(0, 0, self.body))
         })
     }
@@ -164,7 +184,8 @@ impl<'ast> SerializeInLoc for PrefixExpression<'ast> {
                         state.serialize_field("arguments", arguments)
                     })
                 },
-                Literal(_) => {
+                // `new Foo` without arguments
+                _ => {
                     self.in_loc(serializer, expr_type, 2, |state| {
                         // 0 byte array, will be optimized away
                         let arguments: [(); 0] = [];
@@ -172,7 +193,6 @@ impl<'ast> SerializeInLoc for PrefixExpression<'ast> {
                         state.serialize_field("arguments", &arguments)
                     })
                 },
-                _ => unimplemented!()
             }
         } else {
             self.in_loc(serializer, expr_type, 3, |state| {
@@ -251,7 +271,7 @@ impl<'ast> SerializeInLoc for BinaryExpression<'ast> {
     where
         S: Serializer,
     {
-        self.in_loc(serializer, "BinaryExpression", 3, |state| {
+        self.in_loc(serializer, expression_type(self.operator, false), 3, |state| {
             state.serialize_field("operator", &self.operator)?;
             state.serialize_field("left", &self.left)?;
             state.serialize_field("right", &self.right)
@@ -269,6 +289,7 @@ impl<'ast> SerializeInLoc for Expression<'ast> {
         match *self {
             Void => unreachable!(),
             This(_) => self.in_loc(serializer, "ThisExpression", 0, |_| Ok(())),
+            Super(_) => self.in_loc(serializer, "Super", 0, |_| Ok(())),
             Identifier(ref ident) => {
                 self.in_loc(serializer, "Identifier", 1, |state| {
                     state.serialize_field("name", ident)
@@ -277,6 +298,7 @@ impl<'ast> SerializeInLoc for Expression<'ast> {
             Literal(ref value)             => value.serialize(serializer),
             Array(ref value)               => value.serialize(serializer),
             Sequence(ref expression)       => expression.serialize(serializer),
+            Parenthesized(ref expression)  => expression.expression.item.serialize(serializer),
             Binary(ref expression)         => expression.serialize(serializer),
             Prefix(ref expression)         => expression.serialize(serializer),
             Postfix(ref expression)        => expression.serialize(serializer),
@@ -284,6 +306,7 @@ impl<'ast> SerializeInLoc for Expression<'ast> {
             Template(ref expression)       => expression.serialize(serializer),
             TaggedTemplate(ref expression) => expression.serialize(serializer),
             Spread(ref expression)         => expression.serialize(serializer),
+            Await(ref expression)          => expression.serialize(serializer),
             Member(ref expression)         => expression.serialize(serializer),
             ComputedMember(ref expression) => expression.serialize(serializer),
             MetaProperty(ref expression)   => expression.serialize(serializer),
@@ -292,6 +315,39 @@ impl<'ast> SerializeInLoc for Expression<'ast> {
             Arrow(ref expression)          => expression.serialize(serializer),
             Function(ref expression)       => expression.serialize(serializer),
             Class(ref expression)          => expression.serialize(serializer),
+            JSXElement(ref expression)     => expression.serialize(serializer),
+            JSXFragment(ref expression)    => expression.serialize(serializer),
+            As(ref expression)             => expression.serialize(serializer),
+            Satisfies(ref expression)      => expression.serialize(serializer),
+            TypeCast(ref expression)       => expression.serialize(serializer),
+            NonNull(ref expression)        => expression.serialize(serializer),
+            TypeAssertion(ref expression)  => expression.serialize(serializer),
+            Instantiation(ref expression)  => expression.serialize(serializer),
+        }
+    }
+
+    #[inline]
+    fn extra(&self) -> Extra<'_> {
+        match *self {
+            Expression::Literal(ref literal) => literal.extra(),
+            _                                => Extra::default(),
+        }
+    }
+
+    #[inline]
+    fn parenthesized(&self) -> Option<&Loc<Self>> {
+        match *self {
+            Expression::Parenthesized(ParenthesizedExpression { ref expression }) => Some(expression),
+            _ => None,
+        }
+    }
+
+    /// Calls start with their callee in Babel, rather than their arguments.
+    #[inline]
+    fn babel_span(&self, start: u32, end: u32) -> (u32, u32) {
+        match *self {
+            Expression::Call(CallExpression { callee, .. }) if !callee.is_synthetic() => (callee.start, end),
+            _ => (start, end),
         }
     }
 }
@@ -525,14 +581,14 @@ mod test {
                             },
                         ],
                         "start": 0,
-                        "end": 12
+                        "end": 11
                     },
                     "start": 0,
-                    "end": 12
+                    "end": 11
                 }
               ],
               "start": 0,
-              "end": 12,
+              "end": 11,
         });
     }
 
@@ -585,14 +641,14 @@ mod test {
                         },
                         "arguments": [],
                         "start": 0,
-                        "end": 10
+                        "end": 9
                     },
                     "start": 0,
-                    "end": 10
+                    "end": 9
                 }
               ],
               "start": 0,
-              "end": 10,
+              "end": 9,
         });
 
         expect_parse!("new Foo(0, true);", {
@@ -625,14 +681,14 @@ mod test {
                             },
                         ],
                         "start": 0,
-                        "end": 17
+                        "end": 16
                     },
                     "start": 0,
-                    "end": 17
+                    "end": 16
                 }
               ],
               "start": 0,
-              "end": 17,
+              "end": 16,
         });
 
         expect_parse!("new 'foo';", {
@@ -651,14 +707,60 @@ mod test {
                         },
                         "arguments": [],
                         "start": 0,
-                        "end": 10
+                        "end": 9
                     },
                     "start": 0,
-                    "end": 10
+                    "end": 9
                 }
               ],
               "start": 0,
-              "end": 10,
+              "end": 9,
+        });
+    }
+
+    #[test]
+    fn test_assignment_and_logical_expression() {
+        expect_parse!("a += b || c;", {
+            "type": "Program",
+            "body": [
+                {
+                    "type": "ExpressionStatement",
+                    "expression": {
+                        "type": "AssignmentExpression",
+                        "operator": "+=",
+                        "left": {
+                            "type": "Identifier",
+                            "name": "a",
+                            "start": 0,
+                            "end": 1
+                        },
+                        "right": {
+                            "type": "LogicalExpression",
+                            "operator": "||",
+                            "left": {
+                                "type": "Identifier",
+                                "name": "b",
+                                "start": 5,
+                                "end": 6
+                            },
+                            "right": {
+                                "type": "Identifier",
+                                "name": "c",
+                                "start": 10,
+                                "end": 11
+                            },
+                            "start": 5,
+                            "end": 11
+                        },
+                        "start": 0,
+                        "end": 11
+                    },
+                    "start": 0,
+                    "end": 11
+                }
+              ],
+              "start": 0,
+              "end": 11,
         });
     }
 
@@ -1036,7 +1138,7 @@ mod test {
                         "property": {
                             "type": "Identifier",
                             "name": "bar",
-                            "start": 7,
+                            "start": 4,
                             "end": 7
                         },
                         "computed": false,
@@ -1618,4 +1720,37 @@ mod test {
             "end": 29
         });
     }
+
+    #[test]
+    fn test_await_expression() {
+        use crate::parser::{parse_with_options, ParseOptions};
+        use serde_json::to_value;
+
+        let options = ParseOptions { top_level_await: true, ..ParseOptions::default() };
+        let module = parse_with_options("await foo;", options).unwrap();
+
+        assert_eq!(to_value(&module).unwrap(), json!({
+            "type": "Program",
+            "body": [
+                {
+                    "type": "ExpressionStatement",
+                    "expression": {
+                        "type": "AwaitExpression",
+                        "argument": {
+                            "type": "Identifier",
+                            "name": "foo",
+                            "start": 6,
+                            "end": 9
+                        },
+                        "start": 0,
+                        "end": 9
+                    },
+                    "start": 0,
+                    "end": 9
+                }
+            ],
+            "start": 0,
+            "end": 9
+        }));
+    }
 }
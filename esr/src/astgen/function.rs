@@ -1,7 +1,10 @@
 use serde::ser::{Serialize, Serializer, SerializeStruct};
-use crate::astgen::SerializeInLoc;
+use crate::astgen::{SerializeInLoc, is_babel};
+use crate::astgen::statement::{FunctionBody, Terminator};
 use crate::ast::{Function, Class, Name, MandatoryName, OptionalName, EmptyName, ClassMember, Block};
-use crate::ast::MethodKind;
+use crate::ast::{MethodKind, Decorator};
+use crate::astgen::types::{TypeAnnotation, serialize_modifiers, serialize_heritage, is_computed};
+use crate::astgen::types::{serialize_type_parameters, serialize_type_arguments, serialize_return_type};
 
 impl<'ast> Serialize for MethodKind {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -18,6 +21,37 @@ impl<'ast> Serialize for MethodKind {
     }
 }
 
+/// Class properties are still `ClassProperty` to Babel.
+fn property_type() -> &'static str {
+    match is_babel() {
+        true  => "ClassProperty",
+        false => "PropertyDefinition",
+    }
+}
+
+/// Auto-accessors are `ClassAccessorProperty` to Babel.
+fn accessor_type() -> &'static str {
+    match is_babel() {
+        true  => "ClassAccessorProperty",
+        false => "AccessorProperty",
+    }
+}
+
+/// Serialize the fields of the function of a method in Babel flavor, where
+/// they sit on the method itself.
+pub(crate) fn serialize_method<T>(state: &mut T, function: &Function<'_, EmptyName>) -> Result<(), T::Error>
+where
+    T: SerializeStruct,
+{
+    state.serialize_field("id", &function.name)?;
+    state.serialize_field("generator", &function.generator)?;
+    state.serialize_field("async", &false)?;
+    serialize_type_parameters(state, function.type_parameters)?;
+    state.serialize_field("params", &function.params)?;
+    serialize_return_type(state, function.return_type)?;
+    state.serialize_field("body", &FunctionBody(function.body))
+}
+
 impl<'ast> SerializeInLoc for ClassMember<'ast> {
     fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
     where
@@ -27,20 +61,111 @@ impl<'ast> SerializeInLoc for ClassMember<'ast> {
 
         match *self {
             Error { .. } => panic!("Module contains errors"),
+            Method { is_static, key, kind, value } if is_babel() => {
+                self.in_loc(serializer, "ClassMethod", 10, |state| {
+                    state.serialize_field("kind", &kind)?;
+                    state.serialize_field("static", &is_static)?;
+                    state.serialize_field("computed", &is_computed(key))?;
+                    state.serialize_field("key", &*key)?;
+                    serialize_method(state, &value)
+                })
+            },
             Method { is_static, key, kind, value } => {
                 self.in_loc(serializer, "MethodDefinition", 5, |state| {
                     state.serialize_field("kind", &kind)?;
                     state.serialize_field("static", &is_static)?;
-                    state.serialize_field("computed", &false)?;
+                    state.serialize_field("computed", &is_computed(key))?;
                     state.serialize_field("key", &*key)?;
                     state.serialize_field("value", &value)
                 })
             },
-            Literal { .. } => {
-                unimplemented!()
-            }
+            Literal { is_static, key, value } => {
+                self.in_loc(serializer, property_type(), 4, |state| {
+                    state.serialize_field("static", &is_static)?;
+                    state.serialize_field("computed", &is_computed(key))?;
+                    state.serialize_field("key", &*key)?;
+                    state.serialize_field("value", &value)
+                })
+            },
+            Property { is_static, key, optional, annotation, value } => {
+                self.in_loc(serializer, property_type(), 6, |state| {
+                    state.serialize_field("static", &is_static)?;
+                    state.serialize_field("computed", &is_computed(key))?;
+                    state.serialize_field("key", &*key)?;
+                    if optional {
+                        state.serialize_field("optional", &true)?;
+                    }
+                    if let Some(annotation) = annotation {
+                        state.serialize_field("typeAnnotation", &TypeAnnotation(annotation))?;
+                    }
+                    state.serialize_field("value", &value)
+                })
+            },
+            Accessor { is_static, key, annotation, value } => {
+                self.in_loc(serializer, accessor_type(), 5, |state| {
+                    state.serialize_field("static", &is_static)?;
+                    state.serialize_field("computed", &is_computed(key))?;
+                    state.serialize_field("key", &*key)?;
+                    if let Some(annotation) = annotation {
+                        state.serialize_field("typeAnnotation", &TypeAnnotation(annotation))?;
+                    }
+                    state.serialize_field("value", &value)
+                })
+            },
+            Signature { is_static, key, kind, signature } => {
+                self.in_loc(serializer, "TSDeclareMethod", 6, |state| {
+                    state.serialize_field("kind", &kind)?;
+                    state.serialize_field("static", &is_static)?;
+                    state.serialize_field("computed", &is_computed(key))?;
+                    state.serialize_field("key", &*key)?;
+                    serialize_type_parameters(state, signature.type_parameters)?;
+                    state.serialize_field("params", &signature.params)?;
+                    serialize_return_type(state, signature.return_type)
+                })
+            },
+            Modified { ref modifiers, member } => {
+                let mut state = member.item.serialize(serializer)?;
+
+                serialize_modifiers(&mut state, modifiers)?;
+
+                Ok(state)
+            },
+            Decorated { decorators, member } => {
+                let mut state = member.item.serialize(serializer)?;
+
+                state.serialize_field("decorators", &decorators)?;
+
+                Ok(state)
+            },
         }
     }
+
+    #[inline]
+    fn babel_span(&self, start: u32, end: u32) -> (u32, u32) {
+        (start, terminator(self).end(end))
+    }
+}
+
+/// Fields and signatures end with a `;`, methods with their body.
+fn terminator(member: &ClassMember<'_>) -> Terminator {
+    use self::ClassMember::*;
+
+    match *member {
+        Literal { .. } | Property { .. } | Accessor { .. } | Signature { .. } => Terminator::Semicolon,
+        Modified { member, .. } | Decorated { member, .. } => terminator(&member),
+        Method { .. } | Error { .. } => Terminator::None,
+    }
+}
+
+impl<'ast> SerializeInLoc for Decorator<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "Decorator", 1, |state| {
+            state.serialize_field("expression", &self.expression)
+        })
+    }
 }
 
 // TODO: DRY with BlockStatement
@@ -105,8 +230,16 @@ where
         S: Serializer,
     {
         self.in_loc(serializer, N::IN_CLASS, 3, |state| {
+            if !self.decorators.is_empty() {
+                state.serialize_field("decorators", &self.decorators)?;
+            }
             state.serialize_field("id", &self.name)?;
+            serialize_type_parameters(state, self.type_parameters)?;
             state.serialize_field("superClass", &self.extends)?;
+            serialize_type_arguments(state, self.super_type_arguments)?;
+            if !self.implements.is_empty() {
+                serialize_heritage(state, "implements", "TSClassImplements", self.implements)?;
+            }
             state.serialize_field("body", &self.body)
         })
     }
@@ -120,11 +253,65 @@ where
     where
         S: Serializer,
     {
-        self.in_loc(serializer, N::IN_FUNCTION, 3, |state| {
+        self.in_loc(serializer, N::IN_FUNCTION, 4, |state| {
             state.serialize_field("generator", &self.generator)?;
+            if is_babel() {
+                state.serialize_field("async", &false)?;
+            }
             state.serialize_field("id", &self.name)?;
+            serialize_type_parameters(state, self.type_parameters)?;
             state.serialize_field("params", &self.params)?;
-            state.serialize_field("body", &self.body)
+            serialize_return_type(state, self.return_type)?;
+            state.serialize_field("body", &FunctionBody(self.body))
         })
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod test {
+    use serde_json::to_value;
+    use crate::parser::parse;
+
+    #[test]
+    fn test_decorated_class() {
+        let module = parse("@foo class A {}").unwrap();
+
+        assert_eq!(to_value(&module).unwrap(), json!({
+            "type": "Program",
+            "body": [
+                {
+                    "type": "ClassDeclaration",
+                    "decorators": [
+                        {
+                            "type": "Decorator",
+                            "expression": {
+                                "type": "Identifier",
+                                "name": "foo",
+                                "start": 1,
+                                "end": 4,
+                            },
+                            "start": 0,
+                            "end": 4,
+                        }
+                    ],
+                    "id": {
+                        "type": "Identifier",
+                        "name": "A",
+                        "start": 11,
+                        "end": 12,
+                    },
+                    "superClass": null,
+                    "body": {
+                        "type": "ClassBody",
+                        "body": [],
+                        "start": 13,
+                        "end": 15,
+                    },
+                    "start": 0,
+                    "end": 15,
+                }
+            ],
+            "start": 0,
+            "end": 15,
+        }));
+    }
+}
@@ -0,0 +1,433 @@
+use serde::ser::{Serialize, Serializer, SerializeSeq, SerializeStruct};
+use crate::ast::{Loc, NodeList, IdentifierNode};
+use crate::ast::jsx::*;
+use crate::astgen::{SerializeInLoc, Extra, is_babel, serialize_raw};
+use crate::astgen::value::RawValue;
+use crate::lexer::decode_jsx_entities;
+
+#[derive(Debug)]
+pub struct JSXIdentifier<'ast>(&'ast str);
+
+impl<'ast> JSXIdentifier<'ast> {
+    #[inline]
+    fn in_node(node: IdentifierNode<'ast>) -> Loc<Self> {
+        Loc::new(node.start, node.end, JSXIdentifier(node.item))
+    }
+}
+
+impl<'ast> SerializeInLoc for JSXIdentifier<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "JSXIdentifier", 1, |state| {
+            state.serialize_field("name", self.0)
+        })
+    }
+}
+
+/// Empty braces in children of an element, the `JSXEmptyExpression`
+/// spans the space between the braces.
+#[derive(Debug)]
+pub struct JSXEmptyContainer {
+    start: u32,
+    end: u32,
+}
+
+#[derive(Debug)]
+pub struct JSXEmptyExpression;
+
+impl SerializeInLoc for JSXEmptyExpression {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "JSXEmptyExpression", 0, |_| Ok(()))
+    }
+}
+
+impl SerializeInLoc for JSXEmptyContainer {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "JSXExpressionContainer", 1, |state| {
+            state.serialize_field("expression", &Loc::new(self.start + 1, self.end - 1, JSXEmptyExpression))
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct JSXChildren<'ast>(NodeList<'ast, JSXChild<'ast>>);
+
+impl<'ast> Serialize for JSXChildren<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(None)?;
+
+        for child in self.0.iter() {
+            match child.item {
+                JSXChild::Expression(JSXExpressionContainer { expression: None }) => {
+                    seq.serialize_element(&Loc::new(child.start, child.end, JSXEmptyContainer {
+                        start: child.start,
+                        end: child.end,
+                    }))?;
+                },
+                _ => seq.serialize_element(child)?,
+            }
+        }
+
+        seq.end()
+    }
+}
+
+impl<'ast> SerializeInLoc for JSXElementName<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            JSXElementName::Identifier(name) => JSXIdentifier(name).serialize(serializer),
+            JSXElementName::Namespaced { namespace, name } => {
+                self.in_loc(serializer, "JSXNamespacedName", 2, |state| {
+                    state.serialize_field("namespace", &JSXIdentifier::in_node(namespace))?;
+                    state.serialize_field("name", &JSXIdentifier::in_node(name))
+                })
+            },
+            JSXElementName::Member(ref member) => member.serialize(serializer),
+        }
+    }
+}
+
+impl<'ast> SerializeInLoc for JSXMemberExpression<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "JSXMemberExpression", 2, |state| {
+            state.serialize_field("object", &self.object)?;
+            state.serialize_field("property", &JSXIdentifier::in_node(self.property))
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for JSXAttributeValue<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            JSXAttributeValue::String(raw) if is_babel() => {
+                self.in_loc(serializer, "StringLiteral", 1, |state| {
+                    state.serialize_field("value", &*decode_jsx_entities(&raw[1..raw.len() - 1]))
+                })
+            },
+            JSXAttributeValue::String(raw) => {
+                self.in_loc(serializer, "Literal", 2, |state| {
+                    let value = decode_jsx_entities(&raw[1..raw.len() - 1]);
+
+                    state.serialize_field("value", &*value)?;
+                    serialize_raw(state, raw)
+                })
+            },
+            JSXAttributeValue::Expression(ref container) => container.serialize(serializer),
+            JSXAttributeValue::Element(ref element)      => element.serialize(serializer),
+            JSXAttributeValue::Fragment(ref fragment)    => fragment.serialize(serializer),
+        }
+    }
+
+    fn extra(&self) -> Extra<'_> {
+        match *self {
+            JSXAttributeValue::String(raw) => {
+                Extra::literal(raw, Some(RawValue::String(decode_jsx_entities(&raw[1..raw.len() - 1]))))
+            },
+            _ => Extra::default(),
+        }
+    }
+}
+
+impl<'ast> SerializeInLoc for JSXAttributeItem<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            JSXAttributeItem::Attribute(JSXAttribute { name, value }) => {
+                self.in_loc(serializer, "JSXAttribute", 2, |state| {
+                    state.serialize_field("name", &name)?;
+                    state.serialize_field("value", &value)
+                })
+            },
+            JSXAttributeItem::Spread(JSXSpreadAttribute { argument }) => {
+                self.in_loc(serializer, "JSXSpreadAttribute", 1, |state| {
+                    state.serialize_field("argument", &argument)
+                })
+            },
+        }
+    }
+}
+
+impl<'ast> SerializeInLoc for JSXExpressionContainer<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "JSXExpressionContainer", 1, |state| {
+            state.serialize_field("expression", &self.expression)
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for JSXChild<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            JSXChild::Text(raw) => {
+                self.in_loc(serializer, "JSXText", 2, |state| {
+                    state.serialize_field("value", &*decode_jsx_entities(raw))?;
+
+                    match is_babel() {
+                        true  => Ok(()),
+                        false => serialize_raw(state, raw),
+                    }
+                })
+            },
+            JSXChild::Expression(ref container) => container.serialize(serializer),
+            JSXChild::Element(ref element)      => element.serialize(serializer),
+            JSXChild::Fragment(ref fragment)    => fragment.serialize(serializer),
+        }
+    }
+
+    fn extra(&self) -> Extra<'_> {
+        match *self {
+            JSXChild::Text(raw) => Extra::literal(raw, Some(RawValue::String(decode_jsx_entities(raw)))),
+            _                   => Extra::default(),
+        }
+    }
+}
+
+impl<'ast> SerializeInLoc for JSXOpeningElement<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "JSXOpeningElement", 3, |state| {
+            state.serialize_field("name", &self.name)?;
+            state.serialize_field("attributes", &self.attributes)?;
+            state.serialize_field("selfClosing", &self.self_closing)
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for JSXClosingElement<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "JSXClosingElement", 1, |state| {
+            state.serialize_field("name", &self.name)
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for JSXElement<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "JSXElement", 3, |state| {
+            state.serialize_field("openingElement", &self.opening)?;
+            state.serialize_field("closingElement", &self.closing)?;
+            state.serialize_field("children", &JSXChildren(self.children))
+        })
+    }
+}
+
+impl SerializeInLoc for JSXOpeningFragment {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "JSXOpeningFragment", 0, |_| Ok(()))
+    }
+}
+
+impl SerializeInLoc for JSXClosingFragment {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "JSXClosingFragment", 0, |_| Ok(()))
+    }
+}
+
+impl<'ast> SerializeInLoc for JSXFragment<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "JSXFragment", 3, |state| {
+            state.serialize_field("openingFragment", &self.opening)?;
+            state.serialize_field("closingFragment", &self.closing)?;
+            state.serialize_field("children", &JSXChildren(self.children))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::{to_value, Value};
+    use crate::parser::{parse_with_options, ParseOptions};
+
+    fn estree(source: &str) -> Value {
+        let options = ParseOptions { jsx: true, ..ParseOptions::default() };
+        let module = parse_with_options(source, options).unwrap();
+
+        to_value(&module).unwrap()["body"][0]["expression"].clone()
+    }
+
+    #[test]
+    fn test_jsx_element() {
+        assert_eq!(estree("<a b='&amp;' {...c}>d&lt;{}</a>"), json!({
+            "type": "JSXElement",
+            "openingElement": {
+                "type": "JSXOpeningElement",
+                "name": {
+                    "type": "JSXIdentifier",
+                    "name": "a",
+                    "start": 1,
+                    "end": 2
+                },
+                "attributes": [
+                    {
+                        "type": "JSXAttribute",
+                        "name": {
+                            "type": "JSXIdentifier",
+                            "name": "b",
+                            "start": 3,
+                            "end": 4
+                        },
+                        "value": {
+                            "type": "Literal",
+                            "value": "&",
+                            "raw": "'&amp;'",
+                            "start": 5,
+                            "end": 12
+                        },
+                        "start": 3,
+                        "end": 12
+                    },
+                    {
+                        "type": "JSXSpreadAttribute",
+                        "argument": {
+                            "type": "Identifier",
+                            "name": "c",
+                            "start": 17,
+                            "end": 18
+                        },
+                        "start": 13,
+                        "end": 19
+                    }
+                ],
+                "selfClosing": false,
+                "start": 0,
+                "end": 20
+            },
+            "closingElement": {
+                "type": "JSXClosingElement",
+                "name": {
+                    "type": "JSXIdentifier",
+                    "name": "a",
+                    "start": 29,
+                    "end": 30
+                },
+                "start": 27,
+                "end": 31
+            },
+            "children": [
+                {
+                    "type": "JSXText",
+                    "value": "d<",
+                    "raw": "d&lt;",
+                    "start": 20,
+                    "end": 25
+                },
+                {
+                    "type": "JSXExpressionContainer",
+                    "expression": {
+                        "type": "JSXEmptyExpression",
+                        "start": 26,
+                        "end": 26
+                    },
+                    "start": 25,
+                    "end": 27
+                }
+            ],
+            "start": 0,
+            "end": 31
+        }));
+    }
+
+    #[test]
+    fn test_jsx_names() {
+        assert_eq!(estree("<A.B/>")["openingElement"]["name"], json!({
+            "type": "JSXMemberExpression",
+            "object": {
+                "type": "JSXIdentifier",
+                "name": "A",
+                "start": 1,
+                "end": 2
+            },
+            "property": {
+                "type": "JSXIdentifier",
+                "name": "B",
+                "start": 3,
+                "end": 4
+            },
+            "start": 1,
+            "end": 4
+        }));
+
+        assert_eq!(estree("<a:b/>")["openingElement"]["name"], json!({
+            "type": "JSXNamespacedName",
+            "namespace": {
+                "type": "JSXIdentifier",
+                "name": "a",
+                "start": 1,
+                "end": 2
+            },
+            "name": {
+                "type": "JSXIdentifier",
+                "name": "b",
+                "start": 3,
+                "end": 4
+            },
+            "start": 1,
+            "end": 4
+        }));
+    }
+
+    #[test]
+    fn test_jsx_fragment() {
+        assert_eq!(estree("<></>"), json!({
+            "type": "JSXFragment",
+            "openingFragment": {
+                "type": "JSXOpeningFragment",
+                "start": 0,
+                "end": 2
+            },
+            "closingFragment": {
+                "type": "JSXClosingFragment",
+                "start": 2,
+                "end": 5
+            },
+            "children": [],
+            "start": 0,
+            "end": 5
+        }));
+    }
+}
@@ -4,10 +4,378 @@ mod statement;
 mod expression;
 mod function;
 mod value;
+mod jsx;
+mod types;
+mod tokens;
+pub(crate) mod de;
 
+use std::cell::{Cell, RefCell};
 use serde::ser::{Serialize, Serializer, SerializeStruct};
-use crate::ast::{Loc, Node};
+use crate::ast::{Loc, Node, SYNTHETIC};
 use crate::module::Module;
+use crate::astgen::value::RawValue;
+
+/// Flavour of the AST produced by serializing a `Module`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Flavor {
+    /// ESTree, as produced by acorn or espree.
+    #[default]
+    ESTree,
+
+    /// Babel AST, as produced by `@babel/parser`.
+    Babel,
+}
+
+/// `sourceType` of a program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceType {
+    Script,
+    Module,
+}
+
+/// Fields to add to the AST produced by serializing a `Module`, on top of
+/// `start` and `end`, see `Module::estree`. Deserializes from the options
+/// of the same name given to acorn or espree, such as `{ "loc": true }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct EstreeOptions {
+    pub flavor: Flavor,
+
+    /// Add the line and column of the start and end of each node, as
+    /// `loc: { start: { line, column }, end: { line, column } }`. Lines
+    /// start at 1 and columns at 0, columns counting UTF-16 code units as
+    /// they do in JavaScript.
+    pub loc: bool,
+
+    /// Add the start and end of each node as `range: [start, end]`.
+    pub range: bool,
+
+    /// Add the `raw` source of literals, on by default.
+    pub raw: bool,
+
+    /// Add the `tokens` of the source to the program, shaped after the ones
+    /// of espree in either flavor.
+    pub tokens: bool,
+
+    /// Add the `comments` of the source to the program.
+    pub comments: bool,
+
+    /// `sourceType` of the program, left out if `None`.
+    pub source_type: Option<SourceType>,
+}
+
+impl Default for EstreeOptions {
+    fn default() -> Self {
+        EstreeOptions {
+            flavor: Flavor::ESTree,
+            loc: false,
+            range: false,
+            raw: true,
+            tokens: false,
+            comments: false,
+            source_type: None,
+        }
+    }
+}
+
+thread_local! {
+    /// Flavor of the `Module` currently being serialized. Serde gives us no
+    /// way to pass it down to the nodes.
+    static FLAVOR: Cell<Flavor> = const { Cell::new(Flavor::ESTree) };
+
+    /// Same as above, for the options of `Module::estree`.
+    static OPTIONS: Cell<EstreeOptions> = Cell::new(EstreeOptions::default());
+
+    /// Lines of the source, for `loc`.
+    static LINES: RefCell<Lines> = const { RefCell::new(Lines { starts: Vec::new(), savings: Vec::new() }) };
+
+    /// Source of the `Module` serialized in Babel flavor by `Module::estree`,
+    /// for the semicolons Babel ends statements with.
+    static SOURCE: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// A `Module` serialized in a given `Flavor`, see `Module::flavored`.
+pub struct Flavored<'a, 'ast> {
+    pub(crate) module: &'a Module<'ast>,
+    pub(crate) flavor: Flavor,
+}
+
+impl<'a, 'ast> Serialize for Flavored<'a, 'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        let previous = FLAVOR.with(|flavor| flavor.replace(self.flavor));
+        let result = self.module.serialize(serializer);
+
+        FLAVOR.with(|flavor| flavor.set(previous));
+
+        result
+    }
+}
+
+/// A `Module` serialized with `EstreeOptions`, see `Module::estree`.
+pub struct Estree<'a, 'ast> {
+    pub(crate) module: &'a Module<'ast>,
+    pub(crate) source: &'a str,
+    pub(crate) options: EstreeOptions,
+}
+
+impl<'a, 'ast> Serialize for Estree<'a, 'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        let lines = Lines::new(self.source);
+        let source = match self.options.flavor {
+            Flavor::Babel  => self.source.to_owned(),
+            Flavor::ESTree => String::new(),
+        };
+
+        let previous_flavor = FLAVOR.with(|flavor| flavor.replace(self.options.flavor));
+        let previous_options = OPTIONS.with(|options| options.replace(self.options));
+        let previous_lines = LINES.with(|cell| cell.replace(lines));
+        let previous_source = SOURCE.with(|cell| cell.replace(source));
+
+        let result = serialize_program(self.module, Some(self.source), serializer);
+
+        FLAVOR.with(|flavor| flavor.set(previous_flavor));
+        OPTIONS.with(|options| options.set(previous_options));
+        LINES.with(|cell| cell.replace(previous_lines));
+        SOURCE.with(|cell| cell.replace(previous_source));
+
+        result
+    }
+}
+
+#[derive(Serialize)]
+struct Position {
+    line: usize,
+    column: u32,
+}
+
+#[derive(Serialize)]
+struct SourceLocation {
+    start: Position,
+    end: Position,
+}
+
+/// Lines of the source, mapping byte indexes to UTF-16 offsets, lines and
+/// columns.
+#[derive(Default)]
+struct Lines {
+    /// Byte index at which each line starts.
+    starts: Vec<u32>,
+
+    /// Byte index following each character that takes fewer UTF-16 code
+    /// units than bytes, along with the total of the difference so far.
+    savings: Vec<(u32, u32)>,
+}
+
+impl Lines {
+    fn new(source: &str) -> Self {
+        let mut starts = vec![0];
+        let mut savings = Vec::new();
+        let mut saved = 0;
+        let mut chars = source.char_indices().peekable();
+
+        while let Some((index, ch)) = chars.next() {
+            let end = (index + ch.len_utf8()) as u32;
+
+            if !ch.is_ascii() {
+                saved += (ch.len_utf8() - ch.len_utf16()) as u32;
+                savings.push((end, saved));
+            }
+
+            match ch {
+                '\r' if chars.peek().map(|&(_, ch)| ch) == Some('\n') => continue,
+                '\n' | '\r' | '\u{2028}' | '\u{2029}' => starts.push(end),
+                _ => {},
+            }
+        }
+
+        Lines { starts, savings }
+    }
+
+    /// Difference between bytes and UTF-16 code units up to a byte index.
+    fn saved(&self, index: u32) -> u32 {
+        match self.savings.binary_search_by_key(&index, |&(end, _)| end) {
+            Ok(found)  => self.savings[found].1,
+            Err(0)     => 0,
+            Err(found) => self.savings[found - 1].1,
+        }
+    }
+
+    /// UTF-16 offset of a byte index, as JavaScript counts them.
+    fn offset(&self, index: u32) -> u32 {
+        index - self.saved(index)
+    }
+
+    fn position(&self, index: u32) -> Position {
+        let line = match self.starts.binary_search(&index) {
+            Ok(line)  => line,
+            Err(line) => line - 1,
+        };
+        let start = self.starts[line];
+
+        Position {
+            line: line + 1,
+            column: index - start - (self.saved(index) - self.saved(start)),
+        }
+    }
+}
+
+/// UTF-16 offset of a byte index of the source being serialized, unchanged
+/// if the source isn't known, see `Module::estree`.
+pub(crate) fn offset(index: u32) -> u32 {
+    LINES.with(|lines| lines.borrow().offset(index))
+}
+
+/// Serialize the `start` and `end` of a node, along with its `loc` and
+/// `range` if asked for by the `EstreeOptions` in use. All of them count
+/// UTF-16 code units, given byte indexes.
+pub(crate) fn serialize_location<T>(state: &mut T, start: Option<u32>, end: Option<u32>) -> Result<(), T::Error>
+where
+    T: SerializeStruct,
+{
+    let offsets = (start.map(offset), end.map(offset));
+
+    state.serialize_field("start", &offsets.0)?;
+    state.serialize_field("end", &offsets.1)?;
+
+    let options = OPTIONS.with(Cell::get);
+
+    if let (true, Some(start), Some(end)) = (options.loc, start, end) {
+        let loc = LINES.with(|lines| {
+            let lines = lines.borrow();
+
+            SourceLocation {
+                start: lines.position(start),
+                end: lines.position(end),
+            }
+        });
+
+        state.serialize_field("loc", &loc)?;
+    }
+
+    if options.range {
+        match offsets {
+            (Some(start), Some(end)) => state.serialize_field("range", &[start, end])?,
+            _                        => state.serialize_field("range", &())?,
+        }
+    }
+
+    Ok(())
+}
+
+/// End of a node followed by the `;` that ends it, which Babel includes,
+/// skipping whitespace and comments. With `same_line`, the `;` has to be
+/// on the same line, as it is otherwise a statement of its own. Unchanged
+/// if there is no such `;`, or if the source isn't known.
+pub(crate) fn semicolon_end(end: u32, same_line: bool) -> u32 {
+    if end == SYNTHETIC {
+        return end;
+    }
+
+    SOURCE.with(|source| {
+        let source = source.borrow();
+        let rest = match source.get(end as usize..) {
+            Some(rest) => rest.as_bytes(),
+            None       => return end,
+        };
+        let mut index = 0;
+
+        while let Some(&byte) = rest.get(index) {
+            match byte {
+                b';' => return end + index as u32 + 1,
+                b' ' | b'\t' | 0x0B | 0x0C => index += 1,
+                b'\n' | b'\r' if !same_line => index += 1,
+                b'/' if rest.get(index + 1) == Some(&b'/') => {
+                    index += rest[index..].iter().position(|&byte| byte == b'\n' || byte == b'\r').unwrap_or(rest.len() - index);
+                },
+                b'/' if rest.get(index + 1) == Some(&b'*') => {
+                    let length = match rest[index + 2..].windows(2).position(|window| window == b"*/") {
+                        Some(length) => length,
+                        None         => return end,
+                    };
+                    let comment = &rest[index..index + length + 4];
+
+                    if same_line && comment.iter().any(|&byte| byte == b'\n' || byte == b'\r') {
+                        return end;
+                    }
+
+                    index += comment.len();
+                },
+                _ => return end,
+            }
+        }
+
+        end
+    })
+}
+
+/// Whether the `Module` is being serialized in Babel flavor.
+#[inline]
+pub(crate) fn is_babel() -> bool {
+    FLAVOR.with(Cell::get) == Flavor::Babel
+}
+
+/// Serialize the `raw` source of a literal, unless left out by the
+/// `EstreeOptions` in use.
+#[inline]
+pub(crate) fn serialize_raw<T>(state: &mut T, raw: &str) -> Result<(), T::Error>
+where
+    T: SerializeStruct,
+{
+    match OPTIONS.with(Cell::get).raw {
+        true  => state.serialize_field("raw", raw),
+        false => Ok(()),
+    }
+}
+
+/// `extra` of a node in Babel flavor: the source of a literal, and whether
+/// the node is wrapped in parentheses.
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Extra<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_value: Option<RawValue<'a>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw: Option<&'a str>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parenthesized: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paren_start: Option<u32>,
+}
+
+impl<'a> Extra<'a> {
+    /// `extra` of a literal written as `raw`.
+    pub fn literal(raw: &'a str, raw_value: Option<RawValue<'a>>) -> Self {
+        Extra {
+            raw_value,
+            raw: Some(raw),
+            ..Extra::default()
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.raw.is_none() && self.parenthesized.is_none()
+    }
+}
+
+/// Positions of synthetic nodes are serialized as `null`.
+#[inline]
+pub(crate) fn position(position: u32) -> Option<u32> {
+    match position {
+        SYNTHETIC => None,
+        _         => Some(position),
+    }
+}
 
 pub trait SerializeInLoc {
     #[inline]
@@ -23,6 +391,28 @@ pub trait SerializeInLoc {
 
     fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
     where S: Serializer;
+
+    /// `extra` of the node in Babel flavor, other than for parentheses.
+    #[inline]
+    fn extra(&self) -> Extra<'_> {
+        Extra::default()
+    }
+
+    /// Node wrapped in parentheses by this one. Only the wrapped node is
+    /// serialized, in Babel flavor marked with `extra.parenthesized`.
+    #[inline]
+    fn parenthesized(&self) -> Option<&Loc<Self>>
+    where Self: Sized
+    {
+        None
+    }
+
+    /// Start and end of the node in Babel flavor, given those of esr. Babel
+    /// ends statements after their `;`, see `semicolon_end`.
+    #[inline]
+    fn babel_span(&self, start: u32, end: u32) -> (u32, u32) {
+        (start, end)
+    }
 }
 
 impl<'ast, T: SerializeInLoc> Serialize for Loc<T> {
@@ -30,9 +420,38 @@ impl<'ast, T: SerializeInLoc> Serialize for Loc<T> {
     where
         S: Serializer
     {
-        let mut state = self.item.serialize(serializer)?;
-        state.serialize_field("start", &self.start)?;
-        state.serialize_field("end", &self.end)?;
+        let mut inner = self;
+        let mut paren_start = None;
+
+        while let Some(next) = inner.item.parenthesized() {
+            inner = next;
+            paren_start = Some(self.start);
+        }
+
+        let mut state = inner.item.serialize(serializer)?;
+
+        if is_babel() {
+            let mut extra = match OPTIONS.with(Cell::get).raw {
+                true  => inner.item.extra(),
+                false => Extra::default(),
+            };
+
+            if let Some(start) = paren_start {
+                extra.parenthesized = Some(true);
+                extra.paren_start = position(start).map(offset);
+            }
+
+            if !extra.is_empty() {
+                state.serialize_field("extra", &extra)?;
+            }
+        }
+
+        let (start, end) = match is_babel() {
+            true  => inner.item.babel_span(inner.start, inner.end),
+            false => (inner.start, inner.end),
+        };
+
+        serialize_location(&mut state, position(start), position(end))?;
         state.end()
     }
 }
@@ -51,33 +470,73 @@ impl<'ast> Serialize for Module<'ast> {
     where
         S: Serializer
     {
-        let body = self.body();
+        serialize_program(self, None, serializer)
+    }
+}
 
-        let mut start = 0;
-        let mut end = 0;
-        let mut iter = body.iter();
+/// Serialize a `Module` as a `Program`, with the tokens and comments of its
+/// source if asked for by the `EstreeOptions` in use.
+fn serialize_program<S>(module: &Module<'_>, source: Option<&str>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer
+{
+    let body = module.body();
+    let options = OPTIONS.with(Cell::get);
 
-        if let Some(node) = iter.next() {
-            start = node.start;
-            end = node.end;
-        }
+    let mut start = 0;
+    let mut end = 0;
+    let mut iter = body.iter();
 
-        if let Some(node) = iter.last() {
-            end = node.end;
-        }
+    if let Some(node) = iter.next() {
+        start = node.start;
+        end = node.end;
+    }
 
-        let name = "Program";
-        let mut state = serializer.serialize_struct(name, 4)?;
-        state.serialize_field("type", &name)?;
-        state.serialize_field("body", &body)?;
-        state.serialize_field("start", &start)?;
-        state.serialize_field("end", &end)?;
-        state.end()
+    if let Some(node) = iter.last() {
+        end = node.end;
+    }
+
+    // Babel spans the whole source, comments and whitespace included
+    if let (Some(source), true) = (source, is_babel()) {
+        start = 0;
+        end = source.len() as u32;
+    }
+
+    let name = "Program";
+    let mut state = serializer.serialize_struct(name, 9)?;
+    state.serialize_field("type", &name)?;
+
+    if let Some(source_type) = options.source_type {
+        state.serialize_field("sourceType", &source_type)?;
+    }
+
+    if is_babel() {
+        state.serialize_field("interpreter", &())?;
+    }
+
+    statement::serialize_body(&mut state, body, true)?;
+
+    if let (Some(source), true) = (source, options.tokens || options.comments) {
+        let (tokens, comments) = tokens::tokenize(source);
+
+        if options.tokens {
+            state.serialize_field("tokens", &tokens)?;
+        }
+        if options.comments {
+            state.serialize_field("comments", &comments)?;
+        }
     }
+
+    serialize_location(&mut state, Some(start), Some(end))?;
+    state.end()
 }
 
 #[cfg(test)]
 mod test {
+    use serde_json::{to_value, Value};
+    use crate::parser::{parse, parse_with_options, ParseOptions};
+    use super::{Flavor, EstreeOptions, SourceType};
+
     #[test]
     fn test_generate_ast_empty() {
         expect_parse!("", {
@@ -107,4 +566,214 @@ mod test {
               "end": 4,
         });
     }
+
+    #[test]
+    fn test_parenthesized_expression() {
+        let source = "((a)) + b;";
+        let options = ParseOptions { parenthesized: true, ..ParseOptions::default() };
+        let module = parse_with_options(source, options).unwrap();
+
+        let mut expected = json!({
+            "type": "Program",
+            "interpreter": null,
+            "body": [
+                {
+                    "type": "ExpressionStatement",
+                    "expression": {
+                        "type": "BinaryExpression",
+                        "operator": "+",
+                        "left": {
+                            "type": "Identifier",
+                            "name": "a",
+                            "extra": {
+                                "parenthesized": true,
+                                "parenStart": 0,
+                            },
+                            "start": 2,
+                            "end": 3,
+                        },
+                        "right": {
+                            "type": "Identifier",
+                            "name": "b",
+                            "start": 8,
+                            "end": 9,
+                        },
+                        "start": 0,
+                        "end": 9,
+                    },
+                    "start": 0,
+                    "end": 9,
+                }
+            ],
+            "directives": [],
+            "start": 0,
+            "end": 9,
+        });
+
+        assert_eq!(to_value(&module.flavored(Flavor::Babel)).unwrap(), expected);
+
+        // Only the wrapped expression shows up in ESTree
+        expected["body"][0]["expression"]["left"].as_object_mut().unwrap().remove("extra");
+        expected.as_object_mut().unwrap().remove("interpreter");
+        expected.as_object_mut().unwrap().remove("directives");
+
+        assert_eq!(to_value(&module).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_loc_and_range() {
+        let source = "a;\r\n\n  b;";
+        let module = parse(source).unwrap();
+        let options = EstreeOptions { loc: true, range: true, ..EstreeOptions::default() };
+        let ast = to_value(&module.estree(source, options)).unwrap();
+
+        assert_eq!(ast["body"][1]["expression"], json!({
+            "type": "Identifier",
+            "name": "b",
+            "start": 7,
+            "end": 8,
+            "loc": {
+                "start": { "line": 3, "column": 2 },
+                "end": { "line": 3, "column": 3 },
+            },
+            "range": [7, 8],
+        }));
+        assert_eq!(ast["loc"], json!({
+            "start": { "line": 1, "column": 0 },
+            "end": { "line": 3, "column": 3 },
+        }));
+    }
+
+    #[test]
+    fn test_positions_count_utf16() {
+        let source = "'é😀'; a;\n'€';\r\nb; // ü\n(c);";
+        let module = parse(source).unwrap();
+        let options = EstreeOptions { loc: true, range: true, tokens: true, comments: true, ..EstreeOptions::default() };
+        let ast = to_value(&module.estree(source, options)).unwrap();
+        let text = source.encode_utf16().collect::<Vec<_>>();
+        let slice = |node: &Value| {
+            let range = (node["range"][0].as_u64().unwrap() as usize, node["range"][1].as_u64().unwrap() as usize);
+
+            assert_eq!((&node["start"], &node["end"]), (&node["range"][0], &node["range"][1]));
+
+            String::from_utf16(&text[range.0..range.1]).unwrap()
+        };
+
+        assert_eq!(ast["body"][1]["expression"]["start"], json!(7));
+        assert_eq!(slice(&ast["body"][0]["expression"]), "'é😀'");
+        assert_eq!(slice(&ast["body"][1]["expression"]), "a");
+        assert_eq!(slice(&ast["body"][2]["expression"]), "'€'");
+        assert_eq!(slice(&ast["body"][3]["expression"]), "b");
+        assert_eq!(slice(&ast["body"][4]["expression"]), "c");
+        assert_eq!(slice(&ast["tokens"][2]), "a");
+        assert_eq!(slice(&ast["tokens"][4]), "'€'");
+        assert_eq!(slice(&ast["comments"][0]), "// ü");
+        assert_eq!(ast["comments"][0]["loc"], json!({
+            "start": { "line": 3, "column": 3 },
+            "end": { "line": 3, "column": 7 },
+        }));
+        assert_eq!(ast["body"][1]["expression"]["loc"], json!({
+            "start": { "line": 1, "column": 7 },
+            "end": { "line": 1, "column": 8 },
+        }));
+        assert_eq!(ast["body"][2]["expression"]["loc"], json!({
+            "start": { "line": 2, "column": 0 },
+            "end": { "line": 2, "column": 3 },
+        }));
+        assert_eq!(ast["body"][3]["expression"]["loc"], json!({
+            "start": { "line": 3, "column": 0 },
+            "end": { "line": 3, "column": 1 },
+        }));
+
+        let module = parse_with_options(source, ParseOptions { parenthesized: true, ..ParseOptions::default() }).unwrap();
+        let options = EstreeOptions { flavor: Flavor::Babel, ..EstreeOptions::default() };
+        let ast = to_value(&module.estree(source, options)).unwrap();
+
+        // The leading string is a directive in Babel flavor
+        assert_eq!(ast["body"][3]["expression"]["extra"]["parenStart"], json!(24));
+    }
+
+    #[test]
+    fn test_babel_semicolons() {
+        let source = "a /* b */ ;\nfunction f() { return /* c */; }\nfor (var i = 0;;);\nclass C { d = 1 ; }\ndo ; while (e)\n;";
+        let module = parse(source).unwrap();
+        let options = EstreeOptions { flavor: Flavor::Babel, ..EstreeOptions::default() };
+        let ast = to_value(&module.estree(source, options)).unwrap();
+        let span = |node: &Value| (node["start"].clone(), node["end"].clone());
+
+        assert_eq!(span(&ast["body"][0]), (json!(0), json!(11)));
+        assert_eq!(span(&ast["body"][1]["body"]["body"][0]), (json!(27), json!(42)));
+
+        // Declarations in `for` end before the `;`
+        assert_eq!(span(&ast["body"][2]["init"]), (json!(50), json!(59)));
+        assert_eq!(span(&ast["body"][3]["body"]["body"][0]), (json!(74), json!(81)));
+        assert_eq!(span(&ast["body"][4]), (json!(84), json!(100)));
+    }
+
+    #[test]
+    fn test_without_raw() {
+        let source = "'a';";
+        let module = parse(source).unwrap();
+        let options = EstreeOptions { raw: false, ..EstreeOptions::default() };
+        let ast = to_value(&module.estree(source, options)).unwrap();
+
+        assert_eq!(ast["body"][0]["expression"], json!({
+            "type": "Literal",
+            "value": "a",
+            "start": 0,
+            "end": 3,
+        }));
+    }
+
+    #[test]
+    fn test_tokens_comments_and_source_type() {
+        let source = "/* a */ b; // c";
+        let module = parse(source).unwrap();
+        let options = EstreeOptions {
+            tokens: true,
+            comments: true,
+            source_type: Some(SourceType::Module),
+            ..EstreeOptions::default()
+        };
+
+        assert_eq!(to_value(&module.estree(source, options)).unwrap(), json!({
+            "type": "Program",
+            "sourceType": "module",
+            "body": [
+                {
+                    "type": "ExpressionStatement",
+                    "expression": {
+                        "type": "Identifier",
+                        "name": "b",
+                        "start": 8,
+                        "end": 9,
+                    },
+                    "start": 8,
+                    "end": 9,
+                }
+            ],
+            "tokens": [
+                { "type": "Identifier", "value": "b", "start": 8, "end": 9 },
+                { "type": "Punctuator", "value": ";", "start": 9, "end": 10 },
+            ],
+            "comments": [
+                { "type": "Block", "value": " a ", "start": 0, "end": 7 },
+                { "type": "Line", "value": " c", "start": 11, "end": 15 },
+            ],
+            "start": 8,
+            "end": 9,
+        }));
+    }
+
+    #[test]
+    fn test_deserialize_options() {
+        let options: EstreeOptions = serde_json::from_str(r#"{ "range": true, "sourceType": "script", "flavor": "babel" }"#).unwrap();
+
+        assert_eq!(options, EstreeOptions {
+            flavor: Flavor::Babel,
+            range: true,
+            source_type: Some(SourceType::Script),
+            ..EstreeOptions::default()
+        });
+    }
 }
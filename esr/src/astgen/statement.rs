@@ -1,8 +1,163 @@
+use std::borrow::Cow;
 use crate::ast::statement::*;
-use crate::ast::{Block, DeclarationKind, Declarator, Loc, Statement};
-use crate::astgen::SerializeInLoc;
+use crate::ast::{Block, BlockNode, DeclarationKind, Declarator, Loc, Statement, StatementNode, StatementList};
+use crate::ast::types::DeclareStatement;
+use crate::ast::{Expression, ExpressionNode, Literal, Class, Function, Name, OptionalName};
+use crate::astgen::value::RawValue;
+use crate::astgen::function::NameType;
+use crate::astgen::{SerializeInLoc, Extra, is_babel, position, serialize_location, semicolon_end};
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 
+/// `'use strict';` and the like, heading the body of a program or function.
+struct Directive<'ast> {
+    value: Loc<DirectiveLiteral<'ast>>,
+}
+
+struct DirectiveLiteral<'ast>(&'ast str);
+
+impl<'ast> DirectiveLiteral<'ast> {
+    /// Directives are kept as written, escapes included.
+    fn value(&self) -> &'ast str {
+        &self.0[1..self.0.len() - 1]
+    }
+}
+
+impl<'ast> SerializeInLoc for Directive<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "Directive", 1, |state| {
+            state.serialize_field("value", &self.value)
+        })
+    }
+
+    #[inline]
+    fn babel_span(&self, start: u32, end: u32) -> (u32, u32) {
+        (start, Terminator::Semicolon.end(end))
+    }
+}
+
+/// How a statement or class member ends, for Babel which includes the
+/// `;` ending it.
+#[derive(Clone, Copy)]
+pub(crate) enum Terminator {
+    /// A `}` or the end of another node.
+    None,
+
+    /// A `;`, if there is one.
+    Semicolon,
+
+    /// A `;` on the same line, as `return`, `break` and `continue` without
+    /// an argument end at a line break.
+    SameLine,
+}
+
+impl Terminator {
+    pub(crate) fn end(self, end: u32) -> u32 {
+        match self {
+            Terminator::None      => end,
+            Terminator::Semicolon => semicolon_end(end, false),
+            Terminator::SameLine  => semicolon_end(end, true),
+        }
+    }
+}
+
+fn terminator(statement: &Statement<'_>) -> Terminator {
+    use self::Statement::*;
+
+    match *statement {
+        Expression(_) | Declaration(_) | Throw(_) | Do(_) => Terminator::Semicolon,
+        TypeAlias(_) | FunctionSignature(_) | OpaqueType(_) | TypeImport(_) => Terminator::Semicolon,
+        Return(ReturnStatement { value: None }) |
+        Break(BreakStatement { label: None }) |
+        Continue(ContinueStatement { label: None }) => Terminator::SameLine,
+        Return(_) | Break(_) | Continue(_) => Terminator::Semicolon,
+        If(IfStatement { consequent, alternate, .. }) => terminator(&alternate.unwrap_or(consequent)),
+        While(WhileStatement { body, .. }) |
+        For(ForStatement { body, .. }) |
+        ForIn(ForInStatement { body, .. }) |
+        ForOf(ForOfStatement { body, .. }) |
+        Labeled(LabeledStatement { body, .. }) |
+        Declare(DeclareStatement { body }) |
+        Export(ExportDeclaration { declaration: body, .. }) => terminator(&body),
+        Empty | Try(_) | Block(_) | Function(_) | Class(_) | Switch(_) => Terminator::None,
+        Interface(_) | Enum(_) | Namespace(_) => Terminator::None,
+    }
+}
+
+impl<'ast> SerializeInLoc for DirectiveLiteral<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "DirectiveLiteral", 1, |state| {
+            state.serialize_field("value", self.value())
+        })
+    }
+
+    fn extra(&self) -> Extra<'_> {
+        Extra::literal(self.0, Some(RawValue::String(Cow::Borrowed(self.value()))))
+    }
+}
+
+fn directive<'ast>(statement: &Loc<Statement<'ast>>) -> Option<(ExpressionNode<'ast>, &'ast str)> {
+    match statement.item {
+        Statement::Expression(expression) => match expression.item {
+            Expression::Literal(Literal::String(raw)) => Some((expression, raw)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Serialize the `body` of a program or block. In Babel flavor, directives
+/// heading the body of a program or function are kept apart from it, as
+/// `directives`.
+pub(crate) fn serialize_body<T>(state: &mut T, body: StatementList<'_>, directives: bool) -> Result<(), T::Error>
+where
+    T: SerializeStruct,
+{
+    if !is_babel() {
+        return state.serialize_field("body", &body);
+    }
+
+    let directives = match directives {
+        true  => body.iter().map_while(|statement| {
+            let (expression, raw) = directive(statement)?;
+
+            Some(Loc::new(statement.start, statement.end, Directive {
+                value: Loc::new(expression.start, expression.end, DirectiveLiteral(raw)),
+            }))
+        }).collect(),
+        false => Vec::new(),
+    };
+
+    state.serialize_field("body", &body.iter().skip(directives.len()).collect::<Vec<_>>())?;
+    state.serialize_field("directives", &directives)
+}
+
+/// Body of a function, which may start with directives.
+pub(crate) struct FunctionBody<'ast>(pub BlockNode<'ast, Statement<'ast>>);
+
+impl<'ast> Serialize for FunctionBody<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if !is_babel() {
+            return self.0.serialize(serializer);
+        }
+
+        let name = "BlockStatement";
+        let mut state = serializer.serialize_struct(name, 5)?;
+        state.serialize_field("type", name)?;
+        serialize_body(&mut state, self.0.body, true)?;
+        serialize_location(&mut state, position(self.0.start), position(self.0.end))?;
+        state.end()
+    }
+}
+
 // TODO: DRY with BlockStatement
 impl<'ast> Serialize for Loc<Block<'ast, SwitchCase<'ast>>> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -98,8 +253,19 @@ impl<'ast> SerializeInLoc for BlockStatement<'ast> {
     where
         S: Serializer,
     {
-        self.in_loc(serializer, "BlockStatement", 1, |state| {
-            state.serialize_field("body", &self.body)
+        self.in_loc(serializer, "BlockStatement", 2, |state| {
+            serialize_body(state, self.body, false)
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for Label<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "Identifier", 1, |state| {
+            state.serialize_field("name", self.as_str())
         })
     }
 }
@@ -138,6 +304,14 @@ impl<'ast> SerializeInLoc for SwitchCase<'ast> {
             state.serialize_field("consequent", &self.consequent)
         })
     }
+
+    #[inline]
+    fn babel_span(&self, start: u32, end: u32) -> (u32, u32) {
+        match self.consequent.iter().last() {
+            Some(statement) if statement.end == end => (start, terminator(statement).end(end)),
+            _                                       => (start, end),
+        }
+    }
 }
 impl<'ast> SerializeInLoc for ForInit<'ast> {
     fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
@@ -203,6 +377,68 @@ impl<'ast> SerializeInLoc for IfStatement<'ast> {
     }
 }
 
+/// Name of a class or function exported by default, which is a
+/// declaration even when anonymous.
+#[derive(Clone, Copy)]
+struct DefaultName<'ast>(OptionalName<'ast>);
+
+impl<'ast> Name<'ast> for DefaultName<'ast> {
+    fn empty() -> Self {
+        DefaultName(OptionalName::empty())
+    }
+}
+
+impl<'ast> NameType<'ast> for DefaultName<'ast> {
+    const IN_CLASS: &'static str = "ClassDeclaration";
+    const IN_FUNCTION: &'static str = "FunctionDeclaration";
+}
+
+impl<'ast> Serialize for DefaultName<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+/// Write the `declaration` of an `ExportDefaultDeclaration`.
+fn serialize_default_declaration<'ast, S>(state: &mut S, declaration: &StatementNode<'ast>) -> Result<(), S::Error>
+where
+    S: SerializeStruct,
+{
+    let expression = match declaration.item {
+        Statement::Expression(expression) => expression,
+        _ => return state.serialize_field("declaration", declaration),
+    };
+
+    // Anonymous classes and functions are still declarations
+    match expression.item {
+        Expression::Class(ref class) if class.name.0.is_none() => {
+            state.serialize_field("declaration", &Loc::new(expression.start, expression.end, Class {
+                name: DefaultName(class.name),
+                decorators: class.decorators,
+                type_parameters: class.type_parameters,
+                extends: class.extends,
+                super_type_arguments: class.super_type_arguments,
+                implements: class.implements,
+                body: class.body,
+            }))
+        },
+        Expression::Function(ref function) if function.name.0.is_none() => {
+            state.serialize_field("declaration", &Loc::new(expression.start, expression.end, Function {
+                name: DefaultName(function.name),
+                generator: function.generator,
+                type_parameters: function.type_parameters,
+                params: function.params,
+                return_type: function.return_type,
+                body: function.body,
+            }))
+        },
+        _ => state.serialize_field("declaration", &expression),
+    }
+}
+
 impl<'ast> SerializeInLoc for Statement<'ast> {
     fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
     where
@@ -260,8 +496,33 @@ impl<'ast> SerializeInLoc for Statement<'ast> {
             Function(statement) => statement.serialize(serializer),
             Class(statement) => statement.serialize(serializer),
             Switch(statement) => statement.serialize(serializer),
+            Interface(statement) => statement.serialize(serializer),
+            TypeAlias(statement) => statement.serialize(serializer),
+            FunctionSignature(statement) => statement.serialize(serializer),
+            Declare(statement) => statement.serialize(serializer),
+            OpaqueType(statement) => statement.serialize(serializer),
+            TypeImport(statement) => statement.serialize(serializer),
+            Enum(statement) => statement.serialize(serializer),
+            Namespace(statement) => statement.serialize(serializer),
+            Export(ExportDeclaration { is_default: true, ref declaration }) => {
+                self.in_loc(serializer, "ExportDefaultDeclaration", 1, |state| {
+                    serialize_default_declaration(state, declaration)
+                })
+            },
+            Export(ExportDeclaration { ref declaration, .. }) => {
+                self.in_loc(serializer, "ExportNamedDeclaration", 3, |state| {
+                    state.serialize_field("declaration", declaration)?;
+                    state.serialize_field("specifiers", &[(); 0])?;
+                    state.serialize_field("source", &())
+                })
+            }
         }
     }
+
+    #[inline]
+    fn babel_span(&self, start: u32, end: u32) -> (u32, u32) {
+        (start, terminator(self).end(end))
+    }
 }
 
 #[cfg(test)]
@@ -512,73 +773,162 @@ mod test {
 
     #[test]
     fn test_statement_break_statement() {
-        expect_parse!("break;", {
+        expect_parse!("while (x) break;", {
             "type": "Program",
             "body": [
                 {
-                    "type": "BreakStatement",
-                    "label": null,
+                    "type": "WhileStatement",
+                    "test": {
+                        "type": "Identifier",
+                        "name": "x",
+                        "start": 7,
+                        "end": 8
+                    },
+                    "body": {
+                        "type": "BreakStatement",
+                        "label": null,
+                        "start": 10,
+                        "end": 15,
+                    },
                     "start": 0,
-                    "end": 5,
+                    "end": 15,
                 }
               ],
               "start": 0,
-              "end": 5,
+              "end": 15,
         });
 
-        expect_parse!("break foo;", {
+        expect_parse!("foo: break foo;", {
             "type": "Program",
             "body": [
                 {
-                    "type": "BreakStatement",
+                    "type": "LabeledStatement",
                     "label": {
                         "type": "Identifier",
                         "name": "foo",
-                        "start": 6,
-                        "end": 9
+                        "start": 0,
+                        "end": 3
+                    },
+                    "body": {
+                        "type": "BreakStatement",
+                        "label": {
+                            "type": "Identifier",
+                            "name": "foo",
+                            "start": 11,
+                            "end": 14
+                        },
+                        "start": 5,
+                        "end": 14,
                     },
                     "start": 0,
-                    "end": 9,
+                    "end": 14,
                 }
               ],
               "start": 0,
-              "end": 9,
+              "end": 14,
         });
     }
 
     #[test]
-    fn test_statement_continue_statement() {
-        expect_parse!("continue;", {
+    fn test_statement_labeled_statement() {
+        expect_parse!("foo: bar;", {
             "type": "Program",
             "body": [
                 {
-                    "type": "ContinueStatement",
-                    "label": null,
+                    "type": "LabeledStatement",
+                    "label": {
+                        "type": "Identifier",
+                        "name": "foo",
+                        "start": 0,
+                        "end": 3
+                    },
+                    "body": {
+                        "type": "ExpressionStatement",
+                        "expression": {
+                            "type": "Identifier",
+                            "name": "bar",
+                            "start": 5,
+                            "end": 8
+                        },
+                        "start": 5,
+                        "end": 8
+                    },
                     "start": 0,
                     "end": 8,
                 }
               ],
               "start": 0,
-              "end": 8
+              "end": 8,
+        });
+    }
+
+    #[test]
+    fn test_statement_continue_statement() {
+        expect_parse!("while (x) continue;", {
+            "type": "Program",
+            "body": [
+                {
+                    "type": "WhileStatement",
+                    "test": {
+                        "type": "Identifier",
+                        "name": "x",
+                        "start": 7,
+                        "end": 8
+                    },
+                    "body": {
+                        "type": "ContinueStatement",
+                        "label": null,
+                        "start": 10,
+                        "end": 18,
+                    },
+                    "start": 0,
+                    "end": 18,
+                }
+              ],
+              "start": 0,
+              "end": 18
         });
 
-        expect_parse!("continue foo;", {
+        expect_parse!("foo: while (true) continue foo;", {
             "type": "Program",
             "body": [
                 {
-                    "type": "ContinueStatement",
+                    "type": "LabeledStatement",
                     "label": {
                         "type": "Identifier",
                         "name": "foo",
-                        "start": 9,
-                        "end": 12
+                        "start": 0,
+                        "end": 3
+                    },
+                    "body": {
+                        "type": "WhileStatement",
+                        "test": {
+                            "type": "Literal",
+                            "value": true,
+                            "raw": "true",
+                            "start": 12,
+                            "end": 16
+                        },
+                        "body": {
+                            "type": "ContinueStatement",
+                            "label": {
+                                "type": "Identifier",
+                                "name": "foo",
+                                "start": 27,
+                                "end": 30
+                            },
+                            "start": 18,
+                            "end": 30,
+                        },
+                        "start": 5,
+                        "end": 30,
                     },
                     "start": 0,
-                    "end": 12,
+                    "end": 30,
                 }
               ],
               "start": 0,
-              "end": 12,
+              "end": 30,
         });
     }
 
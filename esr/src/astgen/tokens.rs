@@ -0,0 +1,307 @@
+//! Tokens and comments of a source, as found in the `tokens` and `comments`
+//! of a program serialized with `EstreeOptions`. They are shaped after the
+//! ones of espree, which eslint consumes.
+
+use serde::ser::{Serialize, Serializer, SerializeStruct};
+use toolshed::Arena;
+use crate::astgen::serialize_location;
+use crate::lexer::{Lexer, Token};
+use crate::lexer::Token::*;
+
+#[derive(Debug, PartialEq)]
+pub struct EstreeToken<'a> {
+    kind: &'static str,
+    value: &'a str,
+    start: u32,
+    end: u32,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Comment<'a> {
+    kind: &'static str,
+    value: &'a str,
+    start: u32,
+    end: u32,
+}
+
+/// Pattern and flags of a regular expression token.
+#[derive(Serialize)]
+struct Regex<'a> {
+    pattern: &'a str,
+    flags: &'a str,
+}
+
+impl<'a> Serialize for EstreeToken<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct(self.kind, 5)?;
+        state.serialize_field("type", self.kind)?;
+        state.serialize_field("value", self.value)?;
+
+        if self.kind == "RegularExpression" {
+            let slash = self.value.rfind('/').unwrap_or(0);
+
+            state.serialize_field("regex", &Regex {
+                pattern: &self.value[1..slash],
+                flags: &self.value[slash + 1..],
+            })?;
+        }
+
+        serialize_location(&mut state, Some(self.start), Some(self.end))?;
+        state.end()
+    }
+}
+
+impl<'a> Serialize for Comment<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct(self.kind, 4)?;
+        state.serialize_field("type", self.kind)?;
+        state.serialize_field("value", self.value)?;
+        serialize_location(&mut state, Some(self.start), Some(self.end))?;
+        state.end()
+    }
+}
+
+fn token_type(token: Token) -> &'static str {
+    match token {
+        OperatorNew        |
+        OperatorTypeof     |
+        OperatorVoid       |
+        OperatorDelete     |
+        OperatorInstanceof |
+        OperatorIn         |
+        DeclarationVar     |
+        DeclarationLet     |
+        DeclarationConst   |
+        Break              |
+        Do                 |
+        Case               |
+        Else               |
+        Catch              |
+        Export             |
+        Class              |
+        Extends            |
+        Return             |
+        While              |
+        Finally            |
+        Super              |
+        With               |
+        Continue           |
+        For                |
+        Switch             |
+        Yield              |
+        Debugger           |
+        Function           |
+        This               |
+        Default            |
+        If                 |
+        Throw              |
+        Import             |
+        Try                |
+        Static             => "Keyword",
+
+        LiteralTrue        |
+        LiteralFalse       => "Boolean",
+        LiteralNull        => "Null",
+        LiteralString      => "String",
+        LiteralNumber      |
+        LiteralBinary      => "Numeric",
+        LiteralRegEx       => "RegularExpression",
+        TemplateOpen       |
+        TemplateClosed     => "Template",
+
+        // `undefined` is only a literal to esr
+        LiteralUndefined   |
+        ReservedEnum       |
+        ReservedImplements |
+        ReservedPackage    |
+        ReservedProtected  |
+        ReservedInterface  |
+        ReservedPrivate    |
+        ReservedPublic     |
+        Identifier         => "Identifier",
+
+        _                  => "Punctuator",
+    }
+}
+
+/// Whether a `/` following the token starts a regular expression, rather
+/// than being a division.
+fn precedes_regex(previous: Option<Token>) -> bool {
+    !matches!(previous, Some(
+        Identifier        |
+        Accessor          |
+        This              |
+        Super             |
+        LiteralTrue       |
+        LiteralFalse      |
+        LiteralNull       |
+        LiteralUndefined  |
+        LiteralString     |
+        LiteralNumber     |
+        LiteralBinary     |
+        LiteralRegEx      |
+        TemplateClosed    |
+        ParenClose        |
+        BracketClose      |
+        BraceClose        |
+        OperatorIncrement |
+        OperatorDecrement
+    ))
+}
+
+/// Read the comments out of a stretch of source between two tokens, which
+/// holds nothing else but whitespace.
+fn read_comments<'a>(source: &'a str, start: usize, end: usize, comments: &mut Vec<Comment<'a>>) {
+    let mut index = start;
+
+    while let Some(offset) = source[index..end].find('/') {
+        let from = index + offset;
+        let rest = &source[from..end];
+
+        let (kind, value, length) = if let Some(line) = rest.strip_prefix("//") {
+            let length = line.find(['\n', '\r', '\u{2028}', '\u{2029}']).unwrap_or(line.len());
+
+            ("Line", &line[..length], length + 2)
+        } else if let Some(block) = rest.strip_prefix("/*") {
+            match block.find("*/") {
+                Some(end) => ("Block", &block[..end], end + 4),
+                None      => ("Block", block, rest.len()),
+            }
+        } else {
+            index = from + 1;
+            continue;
+        };
+
+        comments.push(Comment {
+            kind,
+            value,
+            start: from as u32,
+            end: (from + length) as u32,
+        });
+
+        index = from + length;
+    }
+}
+
+/// Read the tokens and comments of a source that parses. The contents of
+/// JSX elements and of type annotations are read as JavaScript tokens.
+pub fn tokenize(source: &str) -> (Vec<EstreeToken<'_>>, Vec<Comment<'_>>) {
+    let arena = Arena::new();
+    let mut lexer = Lexer::new(&arena, source);
+    let mut tokens = Vec::new();
+    let mut comments = Vec::new();
+
+    // Whether each of the open braces is the `${` of a template
+    let mut braces = Vec::new();
+    let mut previous = None;
+    let mut last = 0;
+
+    loop {
+        let start = lexer.start();
+
+        match lexer.token {
+            OperatorDivision | OperatorDivideAssign if precedes_regex(previous) => {
+                lexer.read_regular_expression();
+            },
+            BraceOpen => braces.push(false),
+            BraceClose if braces.pop() == Some(true) => lexer.read_template_kind(),
+            _ => {},
+        }
+
+        match lexer.token {
+            EndOfProgram           |
+            UnexpectedToken        |
+            UnexpectedEndOfProgram => break,
+            TemplateOpen           => braces.push(true),
+            _                      => {},
+        }
+
+        let end = lexer.end();
+
+        read_comments(source, last, start as usize, &mut comments);
+
+        let value = &source[start as usize..end as usize];
+
+        if lexer.token == Accessor {
+            let property = end - lexer.accessor_as_str().len() as u32;
+
+            tokens.push(EstreeToken { kind: "Punctuator", value: ".", start, end: start + 1 });
+            tokens.push(EstreeToken { kind: "Identifier", value: &source[property as usize..end as usize], start: property, end });
+        } else {
+            tokens.push(EstreeToken { kind: token_type(lexer.token), value, start, end });
+        }
+
+        previous = Some(lexer.token);
+        last = end as usize;
+        lexer.consume();
+    }
+
+    read_comments(source, last, source.len(), &mut comments);
+
+    (tokens, comments)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn token(kind: &'static str, value: &'static str, start: u32, end: u32) -> EstreeToken<'static> {
+        EstreeToken { kind, value, start, end }
+    }
+
+    #[test]
+    fn tokens() {
+        let (tokens, _) = tokenize("let a = b.c / 2;");
+
+        assert_eq!(tokens, [
+            token("Keyword", "let", 0, 3),
+            token("Identifier", "a", 4, 5),
+            token("Punctuator", "=", 6, 7),
+            token("Identifier", "b", 8, 9),
+            token("Punctuator", ".", 9, 10),
+            token("Identifier", "c", 10, 11),
+            token("Punctuator", "/", 12, 13),
+            token("Numeric", "2", 14, 15),
+            token("Punctuator", ";", 15, 16),
+        ]);
+    }
+
+    #[test]
+    fn regular_expressions_and_templates() {
+        let (tokens, _) = tokenize("x = /a}/g; `a${ { b } }c${d}`;");
+
+        assert_eq!(tokens, [
+            token("Identifier", "x", 0, 1),
+            token("Punctuator", "=", 2, 3),
+            token("RegularExpression", "/a}/g", 4, 9),
+            token("Punctuator", ";", 9, 10),
+            token("Template", "`a${", 11, 15),
+            token("Punctuator", "{", 16, 17),
+            token("Identifier", "b", 18, 19),
+            token("Punctuator", "}", 20, 21),
+            token("Template", "}c${", 22, 26),
+            token("Identifier", "d", 26, 27),
+            token("Template", "}`", 27, 29),
+            token("Punctuator", ";", 29, 30),
+        ]);
+    }
+
+    #[test]
+    fn comments() {
+        let (tokens, comments) = tokenize("// foo\na /* b */ / c; /**/\n//");
+
+        assert_eq!(tokens.len(), 4);
+        assert_eq!(comments, [
+            Comment { kind: "Line", value: " foo", start: 0, end: 6 },
+            Comment { kind: "Block", value: " b ", start: 9, end: 16 },
+            Comment { kind: "Block", value: "", start: 22, end: 26 },
+            Comment { kind: "Line", value: "", start: 27, end: 29 },
+        ]);
+    }
+}
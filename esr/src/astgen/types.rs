@@ -0,0 +1,739 @@
+use serde::ser::{Serialize, Serializer, SerializeStruct};
+use crate::ast::{Loc, Node, TypeNode, TypeList, PropertyKey, Literal, IdentifierNode, StatementList};
+use crate::ast::expression::{AsExpression, SatisfiesExpression, TypeCastExpression, NonNullExpression};
+use crate::ast::expression::{TypeAssertion, InstantiationExpression};
+use crate::ast::types::*;
+use crate::ast::Block;
+use crate::astgen::{SerializeInLoc, position, serialize_location};
+
+/// `TSTypeAnnotation` wrapper of a type, as found in annotations of
+/// bindings and return values.
+pub struct TypeAnnotation<'ast>(pub TypeNode<'ast>);
+
+impl<'ast> Serialize for TypeAnnotation<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let name = "TSTypeAnnotation";
+        let mut state = serializer.serialize_struct(name, 4)?;
+        state.serialize_field("type", &name)?;
+        state.serialize_field("typeAnnotation", &self.0)?;
+        serialize_location(&mut state, position(self.0.start), position(self.0.end))?;
+        state.end()
+    }
+}
+
+struct TypeParameters<'ast>(TypeParameterList<'ast>);
+
+impl<'ast> Serialize for TypeParameters<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let name = "TSTypeParameterDeclaration";
+        let mut state = serializer.serialize_struct(name, 2)?;
+        state.serialize_field("type", &name)?;
+        state.serialize_field("params", &self.0)?;
+        state.end()
+    }
+}
+
+struct TypeArguments<'ast>(TypeList<'ast>);
+
+impl<'ast> Serialize for TypeArguments<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let name = "TSTypeParameterInstantiation";
+        let mut state = serializer.serialize_struct(name, 2)?;
+        state.serialize_field("type", &name)?;
+        state.serialize_field("params", &self.0)?;
+        state.end()
+    }
+}
+
+/// Entry of the `extends` list of interfaces and the `implements` list
+/// of classes.
+struct Heritage<'ast>(&'static str, TypeNode<'ast>);
+
+impl<'ast> Serialize for Heritage<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct(self.0, 4)?;
+        state.serialize_field("type", &self.0)?;
+
+        match self.1.item {
+            Type::Reference { name, arguments } => {
+                state.serialize_field("expression", &name)?;
+                serialize_type_arguments(&mut state, arguments)?;
+            },
+            _ => state.serialize_field("expression", &self.1)?,
+        }
+
+        serialize_location(&mut state, position(self.1.start), position(self.1.end))?;
+        state.end()
+    }
+}
+
+/// Serialize the `extends` list of an interface, or the `implements`
+/// list of a class.
+pub fn serialize_heritage<T>(state: &mut T, key: &'static str, name: &'static str, list: TypeList<'_>) -> Result<(), T::Error>
+where
+    T: SerializeStruct,
+{
+    let heritage: Vec<_> = list.iter().map(|node| Heritage(name, *node)).collect();
+
+    state.serialize_field(key, &heritage)
+}
+
+/// Serialize the `typeParameters` field, skipping it if there are none
+/// so that plain JavaScript output isn't affected.
+pub fn serialize_type_parameters<T>(state: &mut T, list: TypeParameterList<'_>) -> Result<(), T::Error>
+where
+    T: SerializeStruct,
+{
+    match list.is_empty() {
+        true  => Ok(()),
+        false => state.serialize_field("typeParameters", &TypeParameters(list)),
+    }
+}
+
+/// Serialize the `typeArguments` field, skipping it if there are none.
+pub fn serialize_type_arguments<T>(state: &mut T, list: TypeList<'_>) -> Result<(), T::Error>
+where
+    T: SerializeStruct,
+{
+    match list.is_empty() {
+        true  => Ok(()),
+        false => state.serialize_field("typeArguments", &TypeArguments(list)),
+    }
+}
+
+/// Serialize the `returnType` field, skipping it if there is none.
+pub fn serialize_return_type<T>(state: &mut T, return_type: Option<TypeNode<'_>>) -> Result<(), T::Error>
+where
+    T: SerializeStruct,
+{
+    match return_type {
+        Some(node) => state.serialize_field("returnType", &TypeAnnotation(node)),
+        None       => Ok(()),
+    }
+}
+
+/// Serialize the fields of a signature, `params` always included.
+fn serialize_signature<T>(state: &mut T, signature: &FunctionType<'_>) -> Result<(), T::Error>
+where
+    T: SerializeStruct,
+{
+    serialize_type_parameters(state, signature.type_parameters)?;
+    state.serialize_field("params", &signature.params)?;
+    serialize_return_type(state, signature.return_type)
+}
+
+/// Append the fields of TypeScript modifiers to a class member or
+/// a parameter property.
+pub fn serialize_modifiers<T>(state: &mut T, modifiers: &Modifiers) -> Result<(), T::Error>
+where
+    T: SerializeStruct,
+{
+    if let Some(accessibility) = modifiers.accessibility {
+        state.serialize_field("accessibility", &accessibility)?;
+    }
+    if modifiers.readonly {
+        state.serialize_field("readonly", &true)?;
+    }
+    if modifiers.declare {
+        state.serialize_field("declare", &true)?;
+    }
+    if modifiers.is_override {
+        state.serialize_field("override", &true)?;
+    }
+
+    Ok(())
+}
+
+#[inline]
+pub(crate) fn is_computed(key: Node<'_, PropertyKey<'_>>) -> bool {
+    matches!(key.item, PropertyKey::Computed(_))
+}
+
+impl Serialize for Accessibility {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use self::Accessibility::*;
+
+        match *self {
+            Public    => serializer.serialize_str("public"),
+            Protected => serializer.serialize_str("protected"),
+            Private   => serializer.serialize_str("private"),
+        }
+    }
+}
+
+impl Serialize for TypeOperator {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use self::TypeOperator::*;
+
+        match *self {
+            KeyOf    => serializer.serialize_str("keyof"),
+            Unique   => serializer.serialize_str("unique"),
+            Readonly => serializer.serialize_str("readonly"),
+        }
+    }
+}
+
+impl<'ast> SerializeInLoc for TypeName<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            TypeName::Identifier(name) => SerializeInLoc::serialize(&name, serializer),
+            TypeName::Qualified { left, right } => self.in_loc(serializer, "TSQualifiedName", 2, |state| {
+                state.serialize_field("left", &left)?;
+                state.serialize_field("right", &right)
+            }),
+        }
+    }
+}
+
+impl<'ast> SerializeInLoc for TypeParameter<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "TSTypeParameter", 3, |state| {
+            state.serialize_field("name", &self.name)?;
+            state.serialize_field("constraint", &self.constraint)?;
+            state.serialize_field("default", &self.default)
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for TypeMember<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        use self::TypeMember::*;
+
+        match *self {
+            Property { readonly, key, optional, annotation } => {
+                self.in_loc(serializer, "TSPropertySignature", 5, |state| {
+                    state.serialize_field("computed", &is_computed(key))?;
+                    state.serialize_field("key", &*key)?;
+                    state.serialize_field("optional", &optional)?;
+                    state.serialize_field("readonly", &readonly)?;
+                    state.serialize_field("typeAnnotation", &annotation.map(TypeAnnotation))
+                })
+            },
+            Method { key, optional, signature } => {
+                self.in_loc(serializer, "TSMethodSignature", 6, |state| {
+                    state.serialize_field("computed", &is_computed(key))?;
+                    state.serialize_field("key", &*key)?;
+                    state.serialize_field("optional", &optional)?;
+                    serialize_signature(state, &signature)
+                })
+            },
+            Index { readonly, parameter, key, annotation } => {
+                let parameter = Loc::new(parameter.start, key.end, Parameter(parameter.item, key));
+
+                self.in_loc(serializer, "TSIndexSignature", 3, |state| {
+                    state.serialize_field("parameters", &[parameter])?;
+                    state.serialize_field("readonly", &readonly)?;
+                    state.serialize_field("typeAnnotation", &TypeAnnotation(annotation))
+                })
+            },
+            Call { signature } => {
+                self.in_loc(serializer, "TSCallSignatureDeclaration", 3, |state| {
+                    serialize_signature(state, &signature)
+                })
+            },
+            Construct { signature } => {
+                self.in_loc(serializer, "TSConstructSignatureDeclaration", 3, |state| {
+                    serialize_signature(state, &signature)
+                })
+            },
+            Spread { argument } => self.in_loc(serializer, "ObjectTypeSpreadProperty", 1, |state| {
+                state.serialize_field("argument", &argument)
+            }),
+        }
+    }
+}
+
+/// Annotated identifier of an index signature, `[key: string]`.
+struct Parameter<'ast>(&'ast str, TypeNode<'ast>);
+
+impl<'ast> SerializeInLoc for Parameter<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "Identifier", 2, |state| {
+            state.serialize_field("name", self.0)?;
+            state.serialize_field("typeAnnotation", &TypeAnnotation(self.1))
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for Block<'ast, TypeMember<'ast>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "TSInterfaceBody", 1, |state| {
+            state.serialize_field("body", &self.body)
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for Type<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        use self::Type::*;
+
+        match *self {
+            Any     => self.in_loc(serializer, "TSAnyKeyword", 0, |_| Ok(())),
+            Unknown => self.in_loc(serializer, "TSUnknownKeyword", 0, |_| Ok(())),
+            Never   => self.in_loc(serializer, "TSNeverKeyword", 0, |_| Ok(())),
+            Void    => self.in_loc(serializer, "TSVoidKeyword", 0, |_| Ok(())),
+            Object  => self.in_loc(serializer, "TSObjectKeyword", 0, |_| Ok(())),
+            This    => self.in_loc(serializer, "TSThisType", 0, |_| Ok(())),
+            Primitive(primitive) => {
+                let name = match primitive {
+                    self::Primitive::Number    => "TSNumberKeyword",
+                    self::Primitive::String    => "TSStringKeyword",
+                    self::Primitive::Boolean   => "TSBooleanKeyword",
+                    self::Primitive::BigInt    => "TSBigIntKeyword",
+                    self::Primitive::Symbol    => "TSSymbolKeyword",
+                    self::Primitive::Null      => "TSNullKeyword",
+                    self::Primitive::Undefined => "TSUndefinedKeyword",
+                };
+
+                self.in_loc(serializer, name, 0, |_| Ok(()))
+            },
+            Literal(literal) => self.in_loc(serializer, "TSLiteralType", 1, |state| {
+                state.serialize_field("literal", &Loc::new(0, 0, literal))
+            }),
+            Reference { name, arguments } => self.in_loc(serializer, "TSTypeReference", 2, |state| {
+                state.serialize_field("typeName", &name)?;
+                serialize_type_arguments(state, arguments)
+            }),
+            Query { name } => self.in_loc(serializer, "TSTypeQuery", 1, |state| {
+                state.serialize_field("exprName", &name)
+            }),
+            Array { element } => self.in_loc(serializer, "TSArrayType", 1, |state| {
+                state.serialize_field("elementType", &element)
+            }),
+            Tuple { elements } => self.in_loc(serializer, "TSTupleType", 1, |state| {
+                state.serialize_field("elementTypes", &elements)
+            }),
+            Optional { argument } => self.in_loc(serializer, "TSOptionalType", 1, |state| {
+                state.serialize_field("typeAnnotation", &argument)
+            }),
+            Rest { argument } => self.in_loc(serializer, "TSRestType", 1, |state| {
+                state.serialize_field("typeAnnotation", &argument)
+            }),
+            Union { variants } => self.in_loc(serializer, "TSUnionType", 1, |state| {
+                state.serialize_field("types", &variants)
+            }),
+            Intersection { variants } => self.in_loc(serializer, "TSIntersectionType", 1, |state| {
+                state.serialize_field("types", &variants)
+            }),
+            Function(ref signature) => self.in_loc(serializer, "TSFunctionType", 3, |state| {
+                serialize_signature(state, signature)
+            }),
+            Constructor(ref signature) => self.in_loc(serializer, "TSConstructorType", 3, |state| {
+                serialize_signature(state, signature)
+            }),
+            ObjectLiteral { members, exact } => self.in_loc(serializer, "TSTypeLiteral", 1 + exact as usize, |state| {
+                state.serialize_field("members", &members)?;
+
+                if exact {
+                    state.serialize_field("exact", &true)?;
+                }
+
+                Ok(())
+            }),
+            Mapped { readonly, parameter, constraint, optional, annotation } => {
+                let parameter = Loc::new(parameter.start, constraint.end, TypeParameter {
+                    name: parameter,
+                    constraint: Some(constraint),
+                    default: None,
+                });
+
+                self.in_loc(serializer, "TSMappedType", 4, |state| {
+                    state.serialize_field("readonly", &readonly)?;
+                    state.serialize_field("typeParameter", &parameter)?;
+                    state.serialize_field("optional", &optional)?;
+                    state.serialize_field("typeAnnotation", &annotation)
+                })
+            },
+            Operator { operator, argument } => self.in_loc(serializer, "TSTypeOperator", 2, |state| {
+                state.serialize_field("operator", &operator)?;
+                state.serialize_field("typeAnnotation", &argument)
+            }),
+            IndexedAccess { object, index } => self.in_loc(serializer, "TSIndexedAccessType", 2, |state| {
+                state.serialize_field("objectType", &object)?;
+                state.serialize_field("indexType", &index)
+            }),
+            Conditional { check, extends, consequent, alternate } => {
+                self.in_loc(serializer, "TSConditionalType", 4, |state| {
+                    state.serialize_field("checkType", &check)?;
+                    state.serialize_field("extendsType", &extends)?;
+                    state.serialize_field("trueType", &consequent)?;
+                    state.serialize_field("falseType", &alternate)
+                })
+            },
+            Infer { parameter } => {
+                let parameter = Loc::new(parameter.start, parameter.end, TypeParameter {
+                    name: parameter,
+                    constraint: None,
+                    default: None,
+                });
+
+                self.in_loc(serializer, "TSInferType", 1, |state| {
+                    state.serialize_field("typeParameter", &parameter)
+                })
+            },
+            Predicate { parameter, annotation } => self.in_loc(serializer, "TSTypePredicate", 2, |state| {
+                state.serialize_field("parameterName", &parameter)?;
+                state.serialize_field("typeAnnotation", &TypeAnnotation(annotation))
+            }),
+            Nullable { argument } => self.in_loc(serializer, "NullableTypeAnnotation", 1, |state| {
+                state.serialize_field("typeAnnotation", &argument)
+            }),
+        }
+    }
+}
+
+impl<'ast> SerializeInLoc for AsExpression<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "TSAsExpression", 2, |state| {
+            state.serialize_field("expression", &self.expression)?;
+            state.serialize_field("typeAnnotation", &self.annotation)
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for SatisfiesExpression<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "TSSatisfiesExpression", 2, |state| {
+            state.serialize_field("expression", &self.expression)?;
+            state.serialize_field("typeAnnotation", &self.annotation)
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for TypeCastExpression<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "TypeCastExpression", 2, |state| {
+            state.serialize_field("expression", &self.expression)?;
+            state.serialize_field("typeAnnotation", &TypeAnnotation(self.annotation))
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for NonNullExpression<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "TSNonNullExpression", 1, |state| {
+            state.serialize_field("expression", &self.expression)
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for TypeAssertion<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "TSTypeAssertion", 2, |state| {
+            state.serialize_field("typeAnnotation", &self.annotation)?;
+            state.serialize_field("expression", &self.expression)
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for InstantiationExpression<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "TSInstantiationExpression", 2, |state| {
+            state.serialize_field("expression", &self.expression)?;
+            state.serialize_field("typeArguments", &TypeArguments(self.arguments))
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for InterfaceDeclaration<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "TSInterfaceDeclaration", 4, |state| {
+            state.serialize_field("id", &self.name)?;
+            serialize_type_parameters(state, self.type_parameters)?;
+            serialize_heritage(state, "extends", "TSInterfaceHeritage", self.extends)?;
+            state.serialize_field("body", &self.body)
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for TypeAliasDeclaration<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "TSTypeAliasDeclaration", 3, |state| {
+            state.serialize_field("id", &self.name)?;
+            serialize_type_parameters(state, self.type_parameters)?;
+            state.serialize_field("typeAnnotation", &self.value)
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for FunctionSignature<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "TSDeclareFunction", 5, |state| {
+            state.serialize_field("id", &self.name)?;
+            state.serialize_field("generator", &false)?;
+            serialize_signature(state, &self.signature)
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for DeclareStatement<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = self.body.item.serialize(serializer)?;
+
+        state.serialize_field("declare", &true)?;
+
+        Ok(state)
+    }
+}
+
+impl<'ast> SerializeInLoc for OpaqueTypeDeclaration<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "OpaqueType", 4, |state| {
+            state.serialize_field("id", &self.name)?;
+            serialize_type_parameters(state, self.type_parameters)?;
+            state.serialize_field("supertype", &self.supertype)?;
+            state.serialize_field("impltype", &self.value)
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for TypeImportSpecifier<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        use self::TypeImportSpecifier::*;
+
+        match *self {
+            Default(local) => self.in_loc(serializer, "ImportDefaultSpecifier", 1, |state| {
+                state.serialize_field("local", &local)
+            }),
+            Namespace(local) => self.in_loc(serializer, "ImportNamespaceSpecifier", 1, |state| {
+                state.serialize_field("local", &local)
+            }),
+            Named { imported, local } => self.in_loc(serializer, "ImportSpecifier", 2, |state| {
+                state.serialize_field("imported", &imported)?;
+                state.serialize_field("local", &local)
+            }),
+        }
+    }
+}
+
+impl<'ast> SerializeInLoc for TypeImportDeclaration<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        let kind = match self.kind {
+            TypeImportKind::Type   => "type",
+            TypeImportKind::Typeof => "typeof",
+        };
+        let source = Loc::new(self.source.start, self.source.end, Literal::String(self.source.item));
+
+        self.in_loc(serializer, "ImportDeclaration", 3, |state| {
+            state.serialize_field("importKind", kind)?;
+            state.serialize_field("specifiers", &self.specifiers)?;
+            state.serialize_field("source", &source)
+        })
+    }
+}
+
+/// Names of enum members and ambient modules can be strings.
+fn serialize_name<S>(state: &mut S, name: IdentifierNode) -> Result<(), S::Error>
+where
+    S: SerializeStruct,
+{
+    match name.item.starts_with(['"', '\'']) {
+        true  => state.serialize_field("id", &Loc::new(name.start, name.end, Literal::String(name.item))),
+        false => state.serialize_field("id", &name),
+    }
+}
+
+impl<'ast> SerializeInLoc for EnumMember<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "TSEnumMember", 2, |state| {
+            serialize_name(state, self.name)?;
+            state.serialize_field("initializer", &self.value)
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for EnumDeclaration<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "TSEnumDeclaration", 3, |state| {
+            state.serialize_field("id", &self.name)?;
+            state.serialize_field("const", &self.is_const)?;
+            state.serialize_field("members", &self.members)
+        })
+    }
+}
+
+/// `TSModuleBlock` wrapper of the namespace body.
+struct ModuleBlock<'ast>(StatementList<'ast>);
+
+impl<'ast> SerializeInLoc for ModuleBlock<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "TSModuleBlock", 1, |state| {
+            state.serialize_field("body", &self.0)
+        })
+    }
+}
+
+impl<'ast> SerializeInLoc for NamespaceDeclaration<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        let body = Loc::new(self.body.start, self.body.end, ModuleBlock(self.body.body));
+
+        self.in_loc(serializer, "TSModuleDeclaration", 2, |state| {
+            serialize_name(state, self.name)?;
+            state.serialize_field("body", &body)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::to_value;
+    use crate::parser::{parse_with_options, ParseOptions};
+
+    #[test]
+    fn test_type_alias() {
+        let options = ParseOptions { typescript: true, ..ParseOptions::default() };
+        let module = parse_with_options("type Foo<T> = T[] | null;", options).unwrap();
+
+        assert_eq!(to_value(&module).unwrap(), json!({
+            "type": "Program",
+            "body": [
+                {
+                    "type": "TSTypeAliasDeclaration",
+                    "id": {
+                        "type": "Identifier",
+                        "name": "Foo",
+                        "start": 5,
+                        "end": 8,
+                    },
+                    "typeParameters": {
+                        "type": "TSTypeParameterDeclaration",
+                        "params": [
+                            {
+                                "type": "TSTypeParameter",
+                                "name": {
+                                    "type": "Identifier",
+                                    "name": "T",
+                                    "start": 9,
+                                    "end": 10,
+                                },
+                                "constraint": null,
+                                "default": null,
+                                "start": 9,
+                                "end": 10,
+                            }
+                        ],
+                    },
+                    "typeAnnotation": {
+                        "type": "TSUnionType",
+                        "types": [
+                            {
+                                "type": "TSArrayType",
+                                "elementType": {
+                                    "type": "TSTypeReference",
+                                    "typeName": {
+                                        "type": "Identifier",
+                                        "name": "T",
+                                        "start": 14,
+                                        "end": 15,
+                                    },
+                                    "start": 14,
+                                    "end": 15,
+                                },
+                                "start": 14,
+                                "end": 17,
+                            },
+                            {
+                                "type": "TSNullKeyword",
+                                "start": 20,
+                                "end": 24,
+                            }
+                        ],
+                        "start": 14,
+                        "end": 24,
+                    },
+                    "start": 0,
+                    "end": 24,
+                }
+            ],
+            "start": 0,
+            "end": 24,
+        }));
+    }
+}
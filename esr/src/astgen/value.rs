@@ -1,6 +1,10 @@
 use crate::ast::expression::{PropertyKey, TaggedTemplateExpression, TemplateLiteral};
-use crate::ast::{Expression, Literal, Loc, NodeList, Pattern, Property};
-use crate::astgen::SerializeInLoc;
+use std::borrow::Cow;
+use crate::ast::{Expression, ExpressionNode, Literal, Loc, NodeList, Pattern, Property};
+use crate::ast::{number_value, string_value, template_value};
+use crate::astgen::types::{TypeAnnotation, serialize_modifiers, is_computed};
+use crate::astgen::function::serialize_method;
+use crate::astgen::{SerializeInLoc, Extra, is_babel, serialize_raw};
 use serde::ser::{Serialize, SerializeSeq, SerializeStruct, Serializer};
 
 #[derive(Debug, Serialize, PartialEq)]
@@ -25,11 +29,6 @@ pub fn parse_regex(value: &str) -> RegExLiteral<'_> {
     }
 }
 
-#[inline]
-pub fn is_float(value: &str) -> bool {
-    value.bytes().any(|c| c == b'.')
-}
-
 #[derive(Debug)]
 pub struct TemplateElement<'ast> {
     pub tail: bool,
@@ -39,7 +38,80 @@ pub struct TemplateElement<'ast> {
 #[derive(Debug, Serialize)]
 pub struct TemplateElementValue<'ast> {
     pub raw: &'ast str,
-    pub cooked: &'ast str,
+    pub cooked: Option<Cow<'ast, str>>,
+}
+
+/// Numbers are serialized as integers whenever that is lossless, same
+/// as `JSON.stringify` would.
+pub struct NumberValue(pub f64);
+
+impl Serialize for NumberValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        const MAX_SAFE_INTEGER: f64 = 9007199254740991.0;
+
+        match self.0 {
+            value if value.fract() == 0.0 && value.abs() <= MAX_SAFE_INTEGER => serializer.serialize_i64(value as i64),
+            value => serializer.serialize_f64(value),
+        }
+    }
+}
+
+/// `extra.rawValue` of a literal in Babel flavor.
+pub enum RawValue<'a> {
+    String(Cow<'a, str>),
+    Number(f64),
+}
+
+impl<'a> Serialize for RawValue<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            RawValue::String(ref value) => serializer.serialize_str(value),
+            RawValue::Number(value)     => NumberValue(value).serialize(serializer),
+        }
+    }
+}
+
+/// Properties of an object pattern, of which a spread is a `RestElement`
+/// in Babel flavor.
+struct PatternProperties<'ast>(NodeList<'ast, Property<'ast>>);
+
+struct RestProperty<'ast>(ExpressionNode<'ast>);
+
+impl<'ast> Serialize for PatternProperties<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(None)?;
+
+        for property in self.0.iter() {
+            match property.item {
+                Property::Spread { argument } => {
+                    seq.serialize_element(&Loc::new(property.start, property.end, RestProperty(argument)))?
+                },
+                _ => seq.serialize_element(property)?,
+            }
+        }
+
+        seq.end()
+    }
+}
+
+impl<'ast> SerializeInLoc for RestProperty<'ast> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::SerializeStruct, S::Error>
+    where
+        S: Serializer,
+    {
+        self.in_loc(serializer, "RestElement", 1, |state| {
+            state.serialize_field("argument", &self.0)
+        })
+    }
 }
 
 #[derive(Debug)]
@@ -54,11 +126,16 @@ impl<'ast> Serialize for TemplateQuasis<'ast> {
         let mut quasis = (self.0).iter().peekable();
 
         while let Some(q) = quasis.next() {
+            let tail = quasis.peek().is_none();
+
+            // Quasis other than the tail end with `${` rather than a backtick
+            let end = if tail { q.end - 1 } else { q.end - 2 };
+
             seq.serialize_element(&Loc::new(
                 q.start + 1,
-                q.end - 1,
+                end,
                 TemplateElement {
-                    tail: quasis.peek().is_none(),
+                    tail,
                     value: q.item,
                 },
             ))?;
@@ -109,9 +186,39 @@ impl<'ast> SerializeInLoc for Property<'ast> {
         S: Serializer,
     {
         use self::Property::*;
+
+        if is_babel() {
+            return match *self {
+                Shorthand(value) => self.in_loc(serializer, "ObjectProperty", 5, |state| {
+                    state.serialize_field("method", &false)?;
+                    state.serialize_field("key", &value)?;
+                    state.serialize_field("computed", &false)?;
+                    state.serialize_field("shorthand", &true)?;
+                    state.serialize_field("value", &value)
+                }),
+                Literal { key, value } => self.in_loc(serializer, "ObjectProperty", 5, |state| {
+                    state.serialize_field("method", &false)?;
+                    state.serialize_field("key", &*key)?;
+                    state.serialize_field("computed", &is_computed(key))?;
+                    state.serialize_field("shorthand", &false)?;
+                    state.serialize_field("value", &value)
+                }),
+                Method { key, value } => self.in_loc(serializer, "ObjectMethod", 9, |state| {
+                    state.serialize_field("method", &true)?;
+                    state.serialize_field("key", &*key)?;
+                    state.serialize_field("computed", &is_computed(key))?;
+                    state.serialize_field("kind", "method")?;
+                    serialize_method(state, &value)
+                }),
+                Spread { argument } => self.in_loc(serializer, "SpreadElement", 1, |state| {
+                    state.serialize_field("argument", &argument)
+                }),
+            };
+        }
+
         match *self {
             Shorthand(value) => {
-                Expression::Identifier(value).serialize(serializer)
+                Expression::Identifier(value.item).serialize(serializer)
             }
             Literal { key, value } => {
                 let computed = if let PropertyKey::Computed(_) = key.item {
@@ -133,7 +240,7 @@ impl<'ast> SerializeInLoc for Property<'ast> {
                 state.serialize_field("key", &*key)?;
                 state.serialize_field("method", &true)?;
                 state.serialize_field("shorthand", &false)?;
-                state.serialize_field("computed", &false)?;
+                state.serialize_field("computed", &is_computed(key))?;
                 state.serialize_field("value", &value)?;
                 state.serialize_field("kind", &"init")
             }),
@@ -153,6 +260,11 @@ impl<'ast> Serialize for Loc<PropertyKey<'ast>> {
 
         match self.item {
             Computed(expr) => serializer.serialize_some(&*expr),
+            Literal(value) if value.starts_with(['"', '\'']) => serializer.serialize_some(&Loc::new(
+                self.start,
+                self.end,
+                Expression::Literal(crate::ast::Literal::String(value)),
+            )),
             Literal(value) => serializer.serialize_some(&Loc::new(
                 self.start,
                 self.end,
@@ -174,75 +286,73 @@ impl<'ast> SerializeInLoc for Literal<'ast> {
     {
         use self::Literal::*;
 
-        // FIXME: StringLiteral vs. Literal
-        let literal_type = match *self {
-            String(_) => "Literal",
-            _ => "Literal",
-        };
+        if is_babel() {
+            return match *self {
+                Undefined => self.in_loc(serializer, "Identifier", 1, |state| {
+                    state.serialize_field("name", "undefined")
+                }),
+                Null => self.in_loc(serializer, "NullLiteral", 0, |_| Ok(())),
+                True | False => self.in_loc(serializer, "BooleanLiteral", 1, |state| {
+                    state.serialize_field("value", &(*self == True))
+                }),
+                Number(raw) | Binary(raw) => self.in_loc(serializer, "NumericLiteral", 1, |state| {
+                    state.serialize_field("value", &NumberValue(number_value(raw)))
+                }),
+                String(raw) => self.in_loc(serializer, "StringLiteral", 1, |state| {
+                    state.serialize_field("value", &string_value(raw))
+                }),
+                RegEx(value) => self.in_loc(serializer, "RegExpLiteral", 2, |state| {
+                    let regex = parse_regex(value);
 
-        self.in_loc(serializer, literal_type, 1, |state| {
+                    state.serialize_field("pattern", regex.pattern)?;
+                    state.serialize_field("flags", regex.flags)
+                }),
+            };
+        }
+
+        self.in_loc(serializer, "Literal", 1, |state| {
             match *self {
                 Undefined => {
-                    state.serialize_field("raw", &"undefined")
+                    serialize_raw(state, "undefined")
                 },
                 Null => {
                     state.serialize_field("value", &())?;
-                    state.serialize_field("raw", &"null")
+                    serialize_raw(state, "null")
                 },
                 True => {
                     state.serialize_field("value", &true)?;
-                    state.serialize_field("raw", &"true")
+                    serialize_raw(state, "true")
                 },
                 False => {
                     state.serialize_field("value", &false)?;
-                    state.serialize_field("raw", &"false")
+                    serialize_raw(state, "false")
                 },
-                Number(number) => {
-                    if number.len() > 2 {
-                        let prefix = &number[0..2];
-                        let is_hexdecimal = prefix == "0x" || prefix == "0X";
-                        let is_octal = prefix == "0o" || prefix == "0O";
-                        if is_hexdecimal || is_octal {
-                            let value = unsafe { number.get_unchecked(2..number.len()) };
-                            let radix = if is_hexdecimal { 16 } else { 8 };
-                            state.serialize_field(
-                                "value",
-                                &i32::from_str_radix(value, radix).expect("Invalid number"),
-                            )?;
-                            return state.serialize_field("raw", &number)
-                        }
-                    }
-
-                    if is_float(number) {
-                        let value: f64 = number.parse().expect("Invalid number");
-                        state.serialize_field("value", &value)?;
-                        return state.serialize_field("raw", &number)
-                    }
-
-                    let value: i64 = number.parse().expect("Invalid number");
-                    state.serialize_field("value", &value)?;
-                    state.serialize_field("raw", &number)
-                }
-                Binary(number) => {
-                    let value = unsafe { number.get_unchecked(2..number.len()) };
-                    state.serialize_field(
-                        "value",
-                        &i32::from_str_radix(value, 2).expect("Invalid number"),
-                    )?;
-                    state.serialize_field("raw", &number)
-                }
-                String(value) => {
-                    let parsed_value = unsafe { value.get_unchecked(1..value.len() - 1) };
-                    state.serialize_field("value", &parsed_value)?;
-                    state.serialize_field("raw", &value)
+                Number(raw) | Binary(raw) => {
+                    state.serialize_field("value", &NumberValue(number_value(raw)))?;
+                    serialize_raw(state, raw)
+                },
+                String(raw) => {
+                    state.serialize_field("value", &string_value(raw))?;
+                    serialize_raw(state, raw)
                 },
                 RegEx(value) => {
                     state.serialize_field("regex", &parse_regex(value))?;
-                    state.serialize_field("raw", &value)
+                    serialize_raw(state, value)
                 },
             }
         })
     }
+
+    fn extra(&self) -> Extra<'_> {
+        use self::Literal::*;
+
+        match *self {
+            Number(raw) | Binary(raw) => Extra::literal(raw, Some(RawValue::Number(number_value(raw)))),
+            String(raw)               => Extra::literal(raw, Some(RawValue::String(string_value(raw)))),
+            RegEx(raw)                => Extra::literal(raw, None),
+            _                         => Extra::default(),
+        }
+    }
 }
 
 impl<'ast> SerializeInLoc for Pattern<'ast> {
@@ -254,7 +364,10 @@ impl<'ast> SerializeInLoc for Pattern<'ast> {
 
         match *self {
             Void => unreachable!(),
-            Identifier(a) => Expression::Identifier(a).serialize(serializer),
+            Identifier(a) => Expression::Identifier(a.item).serialize(serializer),
+            ObjectPattern { properties } if is_babel() => self.in_loc(serializer, "ObjectPattern", 1, |state| {
+                state.serialize_field("properties", &PatternProperties(properties))
+            }),
             ObjectPattern { properties } => self.in_loc(serializer, "ObjectPattern", 1, |state| {
                 state.serialize_field("keys", &properties)
             }),
@@ -270,6 +383,24 @@ impl<'ast> SerializeInLoc for Pattern<'ast> {
             RestElement { argument } => self.in_loc(serializer, "RestElement", 1, |state| {
                 state.serialize_field("argument", &argument)
             }),
+            Annotated { pattern, optional, annotation } => {
+                let mut state = pattern.item.serialize(serializer)?;
+
+                if optional {
+                    state.serialize_field("optional", &true)?;
+                }
+                if let Some(annotation) = annotation {
+                    state.serialize_field("typeAnnotation", &TypeAnnotation(annotation))?;
+                }
+
+                Ok(state)
+            },
+            ParameterProperty { ref modifiers, parameter } => {
+                self.in_loc(serializer, "TSParameterProperty", 2, |state| {
+                    serialize_modifiers(state, modifiers)?;
+                    state.serialize_field("parameter", &parameter)
+                })
+            },
         }
     }
 }
@@ -283,7 +414,7 @@ impl<'ast> SerializeInLoc for TemplateElement<'ast> {
             state.serialize_field("tail", &self.tail)?;
             let value = TemplateElementValue {
                 raw: self.value,
-                cooked: self.value,
+                cooked: template_value(self.value),
             };
             state.serialize_field("value", &value)
         })
@@ -313,14 +444,6 @@ mod test {
         );
     }
 
-    #[test]
-    fn test_is_float() {
-        assert_eq!(is_float("3142"), false);
-        assert_eq!(is_float("3.142"), true);
-        assert_eq!(is_float("1."), true);
-        assert_eq!(is_float(".1"), true);
-    }
-
     #[test]
     fn test_value_undefined() {
         expect_parse!("undefined", {
@@ -535,6 +658,57 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_value_number_forms() {
+        expect_parse!("[1e3, 0b101, 017, 1.5e-1]", {
+            "type": "Program",
+            "body": [
+                {
+                    "type": "ExpressionStatement",
+                    "expression": {
+                        "type": "ArrayExpression",
+                        "elements": [
+                            {
+                                "type": "Literal",
+                                "value": 1000,
+                                "raw": "1e3",
+                                "start": 1,
+                                "end": 4
+                            },
+                            {
+                                "type": "Literal",
+                                "value": 5,
+                                "raw": "0b101",
+                                "start": 6,
+                                "end": 11
+                            },
+                            {
+                                "type": "Literal",
+                                "value": 15,
+                                "raw": "017",
+                                "start": 13,
+                                "end": 16
+                            },
+                            {
+                                "type": "Literal",
+                                "value": 0.15,
+                                "raw": "1.5e-1",
+                                "start": 18,
+                                "end": 24
+                            }
+                        ],
+                        "start": 0,
+                        "end": 25
+                    },
+                    "start": 0,
+                    "end": 25,
+                }
+            ],
+            "start": 0,
+            "end": 25,
+        });
+    }
+
     #[test]
     fn test_value_string() {
         expect_parse!("'foo'", {
@@ -556,6 +730,26 @@ mod test {
             "start": 0,
             "end": 5,
         });
+
+        expect_parse!(r"'a\n\x62\u{63}'", {
+            "type": "Program",
+            "body": [
+                {
+                    "type": "ExpressionStatement",
+                    "expression": {
+                        "type": "Literal",
+                        "value": "a\nbc",
+                        "raw": r"'a\n\x62\u{63}'",
+                        "start": 0,
+                        "end": 15
+                    },
+                    "start": 0,
+                    "end": 15,
+                }
+            ],
+            "start": 0,
+            "end": 15,
+        });
     }
 
     #[test]
@@ -706,7 +900,7 @@ mod test {
                                     "cooked": "foo",
                                 },
                                 "start": 1,
-                                "end": 5
+                                "end": 4
                             },
                             {
                                 "type": "TemplateElement",
@@ -738,6 +932,96 @@ mod test {
             "start": 0,
             "end": 14,
         });
+
+        expect_parse!(r"foo`\unicode\n${bar}\x`", {
+            "type": "Program",
+            "body": [
+                {
+                    "type": "ExpressionStatement",
+                    "expression": {
+                        "type": "TaggedTemplateExpression",
+                        "tag": {
+                            "type": "Identifier",
+                            "name": "foo",
+                            "start": 0,
+                            "end": 3
+                        },
+                        "quasi": {
+                            "type": "TemplateLiteral",
+                            "quasis": [
+                                {
+                                    "type": "TemplateElement",
+                                    "tail": false,
+                                    "value": {
+                                        "raw": r"\unicode\n",
+                                        "cooked": null,
+                                    },
+                                    "start": 4,
+                                    "end": 14
+                                },
+                                {
+                                    "type": "TemplateElement",
+                                    "tail": true,
+                                    "value": {
+                                        "raw": r"\x",
+                                        "cooked": null,
+                                    },
+                                    "start": 20,
+                                    "end": 22
+                                }
+                            ],
+                            "expressions": [
+                                {
+                                    "type": "Identifier",
+                                    "name": "bar",
+                                    "start": 16,
+                                    "end": 19
+                                }
+                            ],
+                            "start": 3,
+                            "end": 23
+                        },
+                        "start": 0,
+                        "end": 23
+                    },
+                    "start": 0,
+                    "end": 23,
+                }
+            ],
+            "start": 0,
+            "end": 23,
+        });
+
+        expect_parse!(r"`a\tb`", {
+            "type": "Program",
+            "body": [
+                {
+                    "type": "ExpressionStatement",
+                    "expression": {
+                        "type": "TemplateLiteral",
+                        "quasis": [
+                            {
+                                "type": "TemplateElement",
+                                "tail": true,
+                                "value": {
+                                    "raw": r"a\tb",
+                                    "cooked": "a\tb",
+                                },
+                                "start": 1,
+                                "end": 5
+                            }
+                        ],
+                        "expressions": [],
+                        "start": 0,
+                        "end": 6
+                    },
+                    "start": 0,
+                    "end": 6,
+                }
+            ],
+            "start": 0,
+            "end": 6,
+        });
     }
 
     #[test]
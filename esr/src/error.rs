@@ -1,9 +1,44 @@
 use std::fmt::{self, Debug, Display};
 use crate::lexer::Token;
+use crate::regex;
+
+/// The reason an `Error` was produced.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ErrorKind {
+    /// The token at the error location was not expected there.
+    UnexpectedToken,
+
+    /// The source is nested deeper than the parser allows, see
+    /// `ParseOptions::max_depth`. Parsing stops at the error location.
+    NestingTooDeep,
+
+    /// The pattern or the flags of a regular expression literal are
+    /// invalid, the error location is within the literal.
+    InvalidRegExp(regex::ErrorKind),
+
+    /// The label of a `break` or `continue` does not belong to any
+    /// enclosing labeled statement within the same function.
+    UndefinedLabel,
+
+    /// A labeled statement reuses the label of an enclosing one.
+    DuplicateLabel,
+
+    /// The label of a `continue` belongs to a statement other than a loop,
+    /// or a `continue` without a label is outside of any loop.
+    IllegalContinue,
+
+    /// A `break` without a label is outside of any loop or `switch`.
+    IllegalBreak,
+
+    /// The syntax is recognized, but esr deliberately doesn't support it,
+    /// such as the `%checks` predicates Flow has deprecated.
+    UnsupportedSyntax,
+}
 
 /// Error type used by the tokenizer and the parser internally.
 #[derive(PartialEq, Clone)]
 pub struct Error {
+    pub kind: ErrorKind,
     pub token: Token,
     pub raw: Box<str>,
     pub start: usize,
@@ -13,7 +48,35 @@ pub struct Error {
 impl Debug for Error {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Unexpected {:?}({}) at {}:{}", &self.token, &*self.raw, self.start, self.end)
+        match self.kind {
+            ErrorKind::UnexpectedToken => {
+                write!(f, "Unexpected {:?}({}) at {}:{}", &self.token, &*self.raw, self.start, self.end)
+            },
+            ErrorKind::NestingTooDeep => {
+                write!(f, "Nesting too deep at {}:{}", self.start, self.end)
+            },
+            ErrorKind::InvalidRegExp(kind) => {
+                write!(f, "Invalid regular expression, {:?}({}) at {}:{}", kind, &*self.raw, self.start, self.end)
+            },
+            ErrorKind::UndefinedLabel => {
+                write!(f, "Undefined label {} at {}:{}", &*self.raw, self.start, self.end)
+            },
+            ErrorKind::DuplicateLabel => {
+                write!(f, "Duplicate label {} at {}:{}", &*self.raw, self.start, self.end)
+            },
+            ErrorKind::IllegalContinue if self.token == Token::Continue => {
+                write!(f, "Illegal continue outside of a loop at {}:{}", self.start, self.end)
+            },
+            ErrorKind::IllegalContinue => {
+                write!(f, "Label {} of continue is not a loop at {}:{}", &*self.raw, self.start, self.end)
+            },
+            ErrorKind::IllegalBreak => {
+                write!(f, "Illegal break outside of a loop or switch at {}:{}", self.start, self.end)
+            },
+            ErrorKind::UnsupportedSyntax => {
+                write!(f, "Unsupported syntax {} at {}:{}", &*self.raw, self.start, self.end)
+            },
+        }
     }
 }
 
@@ -99,6 +162,132 @@ impl Display for ParseError {
     }
 }
 
+/// The reason an `EstreeError` was produced.
+#[derive(Debug, PartialEq, Clone)]
+pub enum EstreeErrorKind {
+    /// The input is not valid JSON.
+    InvalidJson(String),
+
+    /// The `type` of a node is not one of ESTree, Babel, JSX, TypeScript
+    /// or Flow.
+    UnknownNode(String),
+
+    /// The node has no counterpart in the esr AST, such as an `import` of
+    /// values or an `export` list, or it isn't allowed where it was found.
+    UnsupportedNode(String),
+
+    /// The field holds a value the esr AST has no counterpart for, such as
+    /// `async: true` on a function.
+    UnsupportedValue,
+
+    /// A field the node requires is missing, or holds a value of the wrong
+    /// type.
+    InvalidField,
+}
+
+/// Error type returned by `Module::from_estree`, along with the JSON path
+/// of the offending value, such as `$.body[0].expression.callee`.
+#[derive(PartialEq, Clone)]
+pub struct EstreeError {
+    pub kind: EstreeErrorKind,
+    pub path: String,
+}
+
+impl Debug for EstreeError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Display for EstreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            EstreeErrorKind::InvalidJson(ref message) => write!(f, "Invalid JSON, {}", message),
+            EstreeErrorKind::UnknownNode(ref kind) => write!(f, "Unknown node type {} at {}", kind, self.path),
+            EstreeErrorKind::UnsupportedNode(ref kind) => write!(f, "Unsupported node type {} at {}", kind, self.path),
+            EstreeErrorKind::UnsupportedValue => write!(f, "Unsupported value at {}", self.path),
+            EstreeErrorKind::InvalidField => write!(f, "Missing or invalid field at {}", self.path),
+        }
+    }
+}
+
+/// The reason a `TemplateError` was produced.
+#[derive(Debug, PartialEq, Clone)]
+pub enum TemplateErrorKind {
+    /// A string, template string, regular expression or comment isn't
+    /// closed.
+    Unterminated,
+
+    /// The template doesn't parse.
+    Invalid(Vec<Error>),
+
+    /// The template has to be a single statement.
+    NotAStatement,
+
+    /// The template has to be a single expression.
+    NotAnExpression,
+
+    /// A placeholder has no substitution.
+    MissingSubstitution(String),
+
+    /// A substitution has no placeholder.
+    UnusedSubstitution(String),
+
+    /// A placeholder has more than one substitution.
+    DuplicateSubstitution(String),
+
+    /// A placeholder is substituted with a node that can't stand where it
+    /// is, such as an expression in place of the property of a member.
+    MismatchedSubstitution {
+        placeholder: String,
+        expected: &'static str,
+        found: &'static str,
+    },
+}
+
+/// Error type returned by templates, see `esr::template`, along with the
+/// source of the template.
+#[derive(PartialEq, Clone)]
+pub struct TemplateError {
+    pub kind: TemplateErrorKind,
+    pub source: String,
+}
+
+impl Debug for TemplateError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            TemplateErrorKind::Unterminated => {
+                write!(f, "Unterminated string, regular expression or comment in template `{}`", self.source)
+            },
+            TemplateErrorKind::Invalid(ref errors) => write!(f, "Invalid template `{}`: {:?}", self.source, errors),
+            TemplateErrorKind::NotAStatement => write!(f, "Template `{}` has to be a single statement", self.source),
+            TemplateErrorKind::NotAnExpression => write!(f, "Template `{}` has to be a single expression", self.source),
+            TemplateErrorKind::MissingSubstitution(ref name) => {
+                write!(f, "Missing substitution for placeholder `%{}` in template `{}`", name, self.source)
+            },
+            TemplateErrorKind::UnusedSubstitution(ref name) => {
+                write!(f, "Unused substitution `{}`, template `{}` has no placeholder `%{}`", name, self.source, name)
+            },
+            TemplateErrorKind::DuplicateSubstitution(ref name) => {
+                write!(f, "Duplicate substitution `{}` in template `{}`", name, self.source)
+            },
+            TemplateErrorKind::MismatchedSubstitution { ref placeholder, expected, found } => write!(
+                f,
+                "Placeholder `%{}` stands for {}, but is substituted with {} in template `{}`",
+                placeholder, expected, found, self.source
+            ),
+        }
+    }
+}
+
 fn log10(mut num: usize) -> usize {
     let mut log = 0;
 
@@ -0,0 +1,204 @@
+use std::borrow::Cow;
+use std::char;
+
+use crate::error::{Error, ErrorKind};
+use crate::lexer::{Lexer, util};
+use crate::lexer::entities::ENTITIES;
+use crate::lexer::token::Token::*;
+
+/// Lexer states used while parsing JSX. Same as with template strings,
+/// they are driven by the parser, which knows when the source switches
+/// between JavaScript and JSX.
+impl<'arena> Lexer<'arena> {
+    /// Extend the current word token (identifier or keyword) to a JSX
+    /// identifier, which can also contain dashes, such as `aria-label`.
+    /// The token is turned into an `Identifier`.
+    #[inline]
+    pub fn read_jsx_identifier(&mut self) {
+        loop {
+            match self.read_byte() {
+                b'-'                                 => self.bump(),
+                byte if util::legal_in_label(byte)   => self.bump(),
+                _                                    => break,
+            }
+        }
+
+        self.token = Identifier;
+    }
+
+    /// Advance the lexer past the `=` of a JSX attribute. Strings in JSX
+    /// attributes can't contain escape sequences, and are read until the
+    /// matching quote. Any other value is read as a regular token.
+    #[inline]
+    pub fn consume_jsx_attribute_value(&mut self) {
+        while let b' ' | b'\t' | b'\n' | b'\r' = self.read_byte() {
+            self.bump();
+        }
+
+        let quote = match self.read_byte() {
+            quote @ b'"' | quote @ b'\'' => quote,
+            _                            => return self.consume(),
+        };
+
+        self.token_start = self.index;
+        self.bump();
+
+        loop {
+            match self.read_byte() {
+                byte if byte == quote => {
+                    self.bump();
+
+                    return self.token = LiteralString;
+                },
+                0 => return self.token = UnexpectedEndOfProgram,
+                _ => self.bump(),
+            }
+        }
+    }
+
+    /// Read the next child of a JSX element, following the `>` of the
+    /// opening tag, or the `}` of an expression container. Text up to the
+    /// next tag or expression container is read as a `LiteralString`
+    /// token spanning the raw text, otherwise the token is either the `<`
+    /// of a tag or the `{` of an expression container.
+    ///
+    /// **Note:** Parser needs to expect the `>` or `}` token before calling
+    /// this method, without consuming it.
+    pub fn read_jsx_child(&mut self) {
+        let start = self.index;
+
+        self.token_start = start;
+
+        loop {
+            match self.read_byte() {
+                b'<' | b'{' if self.index > start => {
+                    return self.token = LiteralString;
+                },
+                b'<' => {
+                    self.bump();
+
+                    return self.token = OperatorLesser;
+                },
+                b'{' => {
+                    self.bump();
+
+                    return self.token = BraceOpen;
+                },
+                b'>' | b'}' => {
+                    self.token_start = self.index;
+                    self.bump();
+
+                    return self.token = UnexpectedToken;
+                },
+                0 => return self.token = UnexpectedEndOfProgram,
+                _ => self.bump(),
+            }
+        }
+    }
+
+    /// Produce an error for the name of a closing tag at `start..end`
+    /// that doesn't match the name of the opening tag.
+    pub fn mismatched_closing_tag(&self, start: u32, end: u32) -> Error {
+        let (start, end) = (start as usize, end as usize);
+
+        Error {
+            kind: ErrorKind::UnexpectedToken,
+            token: Identifier,
+            start,
+            end,
+            raw: self.slice_source(start, end).to_owned().into_boxed_str(),
+        }
+    }
+}
+
+/// Decode the character references (`&amp;`, `&#123;`, `&#x7B;`) in raw
+/// JSX text or the contents of a JSX attribute string. References that
+/// are malformed or unknown are left untouched.
+pub fn decode_jsx_entities(raw: &str) -> Cow<'_, str> {
+    if !raw.contains('&') {
+        return Cow::Borrowed(raw);
+    }
+
+    let mut decoded = String::with_capacity(raw.len());
+    let mut rest = raw;
+
+    while let Some(index) = rest.find('&') {
+        decoded.push_str(&rest[..index]);
+        rest = &rest[index..];
+
+        let entity = rest[1..].find(';')
+            .filter(|&end| end <= 10)
+            .and_then(|end| decode_entity(&rest[1..end + 1]).map(|ch| (ch, end + 2)));
+
+        match entity {
+            Some((ch, len)) => {
+                decoded.push(ch);
+                rest = &rest[len..];
+            },
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+
+    Cow::Owned(decoded)
+}
+
+#[inline]
+fn decode_entity(entity: &str) -> Option<char> {
+    let (digits, radix) = if let Some(digits) = entity.strip_prefix("#x") {
+        (digits, 16)
+    } else if let Some(digits) = entity.strip_prefix('#') {
+        (digits, 10)
+    } else {
+        return ENTITIES.binary_search_by_key(&entity, |&(name, _)| name)
+                       .ok()
+                       .map(|index| ENTITIES[index].1);
+    };
+
+    if digits.is_empty() || !digits.chars().all(|ch| ch.is_digit(radix)) {
+        return None;
+    }
+
+    u32::from_str_radix(digits, radix).ok().and_then(char::from_u32)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(decode_jsx_entities("foo bar"), "foo bar");
+        assert_eq!(decode_jsx_entities("&lt;a&gt; &amp;&amp; &apos;b&apos;"), "<a> && 'b'");
+        assert_eq!(decode_jsx_entities("&nbsp;&copy;&hellip;"), "\u{a0}\u{a9}\u{2026}");
+        assert_eq!(decode_jsx_entities("&#65;&#x42;&#X43;"), "AB&#X43;");
+    }
+
+    #[test]
+    fn leaves_malformed_references() {
+        assert_eq!(decode_jsx_entities("a & b"), "a & b");
+        assert_eq!(decode_jsx_entities("&amp"), "&amp");
+        assert_eq!(decode_jsx_entities("&doge;"), "&doge;");
+        assert_eq!(decode_jsx_entities("&#xZZ;&#;&#1114112;"), "&#xZZ;&#;&#1114112;");
+        assert_eq!(decode_jsx_entities("&thisisfartoolong;"), "&thisisfartoolong;");
+    }
+
+    #[test]
+    fn borrows_when_nothing_to_decode() {
+        assert!(match decode_jsx_entities("no references here") {
+            Cow::Borrowed(_) => true,
+            Cow::Owned(_) => false,
+        });
+    }
+
+    #[test]
+    fn entities_are_sorted() {
+        use crate::lexer::entities::ENTITIES;
+
+        assert!(ENTITIES.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+}
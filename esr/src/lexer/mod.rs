@@ -1,14 +1,17 @@
 mod token;
 mod labels;
 mod util;
+mod jsx;
+mod entities;
 
 pub use crate::lexer::token::*;
+pub use crate::lexer::jsx::decode_jsx_entities;
 
 use crate::lexer::labels::*;
 use crate::lexer::token::Token::*;
 
 use std::str;
-use crate::error::Error;
+use crate::error::{Error, ErrorKind};
 use toolshed::Arena;
 
 macro_rules! expect_byte {
@@ -63,7 +66,7 @@ static BYTE_HANDLERS: [ByteHandler; 256] = [
     ___, ___, ___, ___, ___, ___, ___, ___, ___, ___, ___, ___, ___, ___, ___, ___, // 1
     ___, EXL, QOT, ERR, IDT, PRC, AMP, QOT, PNO, PNC, ATR, PLS, COM, MIN, PRD, SLH, // 2
     ZER, DIG, DIG, DIG, DIG, DIG, DIG, DIG, DIG, DIG, COL, SEM, LSS, EQL, MOR, QST, // 3
-    ERR, IDT, IDT, IDT, IDT, IDT, IDT, IDT, IDT, IDT, IDT, IDT, IDT, IDT, IDT, IDT, // 4
+    DCR, IDT, IDT, IDT, IDT, IDT, IDT, IDT, IDT, IDT, IDT, IDT, IDT, IDT, IDT, IDT, // 4
     IDT, IDT, IDT, IDT, IDT, IDT, IDT, IDT, IDT, IDT, IDT, BTO, IDT, BTC, CRT, IDT, // 5
     TPL, IDT, L_B, L_C, L_D, L_E, L_F, IDT, IDT, L_I, IDT, IDT, L_L, IDT, L_N, IDT, // 6
     L_P, IDT, L_R, L_S, L_T, L_U, L_V, L_W, IDT, L_Y, IDT, BEO, PIP, BEC, TLD, ERR, // 7
@@ -280,6 +283,13 @@ const TLD: ByteHandler = Some(|lex| {
     lex.token = OperatorBitwiseNot;
 });
 
+// @
+const DCR: ByteHandler = Some(|lex| {
+    lex.bump();
+
+    lex.token = Decorator;
+});
+
 // ^
 const CRT: ByteHandler = Some(|lex| {
     lex.token = match lex.next_byte() {
@@ -598,6 +608,7 @@ const TPL: ByteHandler = Some(|lex| {
     lex.read_template_kind();
 });
 
+#[derive(Clone)]
 pub struct Lexer<'arena> {
     /// Current `Token` from the source.
     pub token: Token,
@@ -775,11 +786,43 @@ impl<'arena> Lexer<'arena> {
                         _ => self.bump()
                     }
                 },
+                0 => {
+                    self.token = UnexpectedEndOfProgram;
+                    return;
+                },
                 _ => self.bump()
             }
         }
     }
 
+    /// Move the lexer to the byte `index` of the source and read the
+    /// token found there. The `index` must not be past the end of the
+    /// source, and must not fall inside of a token, string or comment.
+    #[inline]
+    pub fn seek(&mut self, index: usize) {
+        self.index = index;
+        self.consume();
+    }
+
+    /// Treat the current token as a single `>`, so that the end of a JSX
+    /// tag in `<div>>`, or of nested type arguments in `Foo<Bar<T>>`,
+    /// isn't read as an operator.
+    #[inline]
+    pub fn read_greater(&mut self) {
+        match self.token {
+            OperatorGreater        |
+            OperatorGreaterEquals  |
+            OperatorBitShiftRight  |
+            OperatorUBitShiftRight |
+            OperatorBSRAssign      |
+            OperatorUBSRAssign     => {
+                self.index = self.token_start + 1;
+                self.token = OperatorGreater;
+            },
+            _ => {}
+        }
+    }
+
     /// Get a definition of which ASI rules can be applied.
     #[inline]
     pub fn asi(&self) -> Asi {
@@ -796,6 +839,7 @@ impl<'arena> Lexer<'arena> {
         }
 
         Error {
+            kind: ErrorKind::UnexpectedToken,
             token,
             start,
             end,
@@ -803,6 +847,30 @@ impl<'arena> Lexer<'arena> {
         }
     }
 
+    /// Produce an error for a construct that is nested too deeply at the
+    /// current token, and skip the rest of the source, so that the parser
+    /// can unwind without descending any further.
+    pub fn nesting_too_deep(&mut self) -> Error {
+        let start = self.token_start;
+        let end = self.index;
+        let token = self.token;
+        let raw = self.slice_source(start, end).to_owned().into_boxed_str();
+
+        while self.read_byte() != 0 {
+            self.bump();
+        }
+
+        self.consume();
+
+        Error {
+            kind: ErrorKind::NestingTooDeep,
+            token,
+            start,
+            end,
+            raw,
+        }
+    }
+
     /// Read a byte from the source. Note that this does not increment
     /// the index. In few cases (all of them related to number parsing)
     /// we want to peek at the byte before doing anything. This will,
@@ -878,6 +946,10 @@ impl<'arena> Lexer<'arena> {
         unwind_loop!({
             ch = self.read_byte();
 
+            if ch == 0 {
+                return self.token = UnexpectedEndOfProgram;
+            }
+
             if ch > 0x20 {
                 self.accessor_start = self.index;
 
@@ -1014,13 +1086,18 @@ impl<'arena> Lexer<'arena> {
                     self.token = UnexpectedToken;
                     return "";
                 },
+                0     => {
+                    self.token = UnexpectedEndOfProgram;
+                    return "";
+                },
                 _     => self.bump()
             }
         }
 
+        // Flags are validated by the parser, along with the pattern
         loop {
             match self.read_byte() {
-                b'g' | b'i' | b'm' | b'u' | b'y' => {
+                b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_' | b'$' => {
                     self.bump();
                 },
                 _                                => {
@@ -1156,6 +1233,17 @@ mod test {
     #[test]
     fn unexpected_end() {
         assert_lex("'foo", [(UnexpectedEndOfProgram, "'foo")]);
+        assert_lex("`foo", [(UnexpectedEndOfProgram, "`foo")]);
+    }
+
+    #[test]
+    fn unterminated_regular_expression() {
+        let arena = Arena::new();
+        let mut lex = Lexer::new(&arena, "/foo");
+
+        assert_eq!(lex.token, OperatorDivision);
+        assert_eq!(lex.read_regular_expression(), "");
+        assert_eq!(lex.token, UnexpectedEndOfProgram);
     }
 
     #[test]
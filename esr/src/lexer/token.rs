@@ -10,6 +10,7 @@
 // SUPER WITH  CONT  FOR   SWTCH YIELD DBGGR FUNCT THIS  DEFLT IF    THROW
 // IMPRT TRY   STATI TRUE  FALSE NULL  UNDEF STR   NUM   BIN   REGEX ENUM
 // IMPL  PCKG  PROT  IFACE PRIV  PUBLI IDENT ACCSS TPL_O TPL_C ERR_T ERR_E
+// @
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Token {
@@ -121,6 +122,7 @@ pub enum Token {
     TemplateClosed,
     UnexpectedToken,
     UnexpectedEndOfProgram,
+    Decorator,                //     @ …
 }
 
 impl Token {
@@ -18,10 +18,18 @@ extern crate pretty_assertions;
 pub mod ast;
 pub mod error;
 pub mod lexer;
+pub mod regex;
+pub mod template;
+pub mod owned;
 
 mod module;
 mod parser;
 mod astgen;
 
-pub use crate::parser::parse;
+pub use crate::parser::{parse, parse_with_options, ParseOptions, DEFAULT_MAX_DEPTH};
+pub use crate::parser::{reparse, TextEdit};
+pub use crate::parser::{parse_repl_input, ReplInput};
+pub use crate::parser::{parse_expression, parse_statement, parse_function_body, parse_pattern};
+pub use crate::parser::{parse_json, JsonOptions};
 pub use crate::module::Module;
+pub use crate::astgen::{Flavor, Flavored, Estree, EstreeOptions, SourceType};
@@ -1,21 +1,28 @@
 use toolshed::list::UnsafeList;
 use toolshed::Arena;
 use crate::ast::StatementList;
+use crate::parser::ParseOptions;
+use crate::astgen::{Flavor, Flavored, Estree, EstreeOptions};
+use crate::astgen::de;
+use crate::error::EstreeError;
+use std::cell::Cell;
 use std::marker::PhantomData;
 
 /// A JavaScript module parsed to an AST.
 pub struct Module<'ast> {
-    body: UnsafeList,
-    arena: Arena,
+    pub(crate) body: Cell<UnsafeList>,
+    pub(crate) arena: Arena,
+    pub(crate) options: ParseOptions,
     _phantom: PhantomData<&'ast StatementList<'ast>>
 }
 
 impl<'ast> Module<'ast> {
     #[inline]
-    pub(crate) fn new(body: UnsafeList, arena: Arena) -> Self {
+    pub(crate) fn new(body: UnsafeList, arena: Arena, options: ParseOptions) -> Self {
         Module {
-            body,
+            body: Cell::new(body),
             arena,
+            options,
             _phantom: PhantomData,
         }
     }
@@ -23,7 +30,70 @@ impl<'ast> Module<'ast> {
     /// Get the body of the module as a list of statements.
     #[inline]
     pub fn body(&self) -> StatementList<'ast> {
-        unsafe { self.body.into_list() }
+        unsafe { self.body.get().into_list() }
+    }
+
+    /// Replace the body of the module, for transformations that add top
+    /// level statements. The new body has to be allocated on the module's
+    /// `Arena`.
+    #[inline]
+    pub fn set_body(&self, body: StatementList<'ast>) {
+        self.body.set(body.into_unsafe());
+    }
+
+    /// Read a module from ESTree JSON, as produced by serializing a `Module`
+    /// or by other parsers such as acorn or `@babel/parser`. Nodes without
+    /// positions are read as synthetic.
+    ///
+    /// ESTree has no node for parentheses, they are only read back from
+    /// the `extra.parenthesized` of Babel, as a single level.
+    pub fn from_estree(json: &str) -> Result<Self, EstreeError> {
+        let value = serde_json::from_str(json).map_err(|error| de::invalid_json(&error))?;
+
+        Module::from_estree_value(&value)
+    }
+
+    /// Same as `from_estree`, for JSON that has already been parsed.
+    pub fn from_estree_value(value: &serde_json::Value) -> Result<Self, EstreeError> {
+        de::module(value).map(|module| module.to_module())
+    }
+
+    /// Get the options the module was parsed with. For modules read from
+    /// ESTree, these are the options needed to parse the nodes that were
+    /// read, such as `jsx`.
+    #[inline]
+    pub fn options(&self) -> &ParseOptions {
+        &self.options
+    }
+
+    /// Whether the module was parsed as Flow, either through `ParseOptions`
+    /// or an `@flow` pragma.
+    #[inline]
+    pub fn is_flow(&self) -> bool {
+        self.options.flow
+    }
+
+    /// Serialize the module as the given `Flavor` of AST, rather than the
+    /// ESTree produced by serializing the `Module` itself.
+    #[inline]
+    pub fn flavored(&self, flavor: Flavor) -> Flavored<'_, 'ast> {
+        Flavored {
+            module: self,
+            flavor,
+        }
+    }
+
+    /// Serialize the module as ESTree with the given `EstreeOptions`. The
+    /// `source` the module was parsed from is needed for `loc`, `tokens`
+    /// and `comments`, and to count positions in UTF-16 code units as
+    /// JavaScript does. Other ways of serializing count them in bytes.
+    #[inline]
+    pub fn estree<'a>(&'a self, source: &'a str, options: EstreeOptions) -> Estree<'a, 'ast> {
+        Estree {
+            module: self,
+            source,
+            options,
+        }
     }
 
     /// Get a reference to the `Arena` on which the AST is allocated.
@@ -0,0 +1,415 @@
+use toolshed::Arena;
+
+use crate::ast::expression as ast;
+use crate::owned::{Node, NodeList, Identifier, IdentifierNode, BlockNode, ExpressionNode, ExpressionList};
+use crate::owned::{TypeNode, TypeList, Literal, Pattern, Statement, Function, Class, EmptyName, OptionalName};
+use crate::owned::{OperatorKind, ToOwnedAst, ToArena};
+use crate::owned::jsx::{JSXElement, JSXFragment};
+use crate::owned::types::TypeParameterList;
+
+pub use crate::ast::expression::{ThisExpression, SuperExpression};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum PropertyKey {
+    Computed(ExpressionNode),
+    Literal(String),
+    Binary(String),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Property {
+    Shorthand(IdentifierNode),
+    Literal {
+        key: Node<PropertyKey>,
+        value: ExpressionNode,
+    },
+    Method {
+        key: Node<PropertyKey>,
+        value: Node<Function<EmptyName>>,
+    },
+    Spread {
+        argument: ExpressionNode,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SequenceExpression {
+    pub body: ExpressionList,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParenthesizedExpression {
+    pub expression: ExpressionNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ArrayExpression {
+    pub body: ExpressionList,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct MemberExpression {
+    pub object: ExpressionNode,
+    pub property: IdentifierNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct MetaPropertyExpression {
+    pub meta: IdentifierNode,
+    pub property: IdentifierNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ComputedMemberExpression {
+    pub object: ExpressionNode,
+    pub property: ExpressionNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CallExpression {
+    pub callee: ExpressionNode,
+    pub arguments: ExpressionList,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BinaryExpression {
+    pub operator: OperatorKind,
+    pub left: ExpressionNode,
+    pub right: ExpressionNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PrefixExpression {
+    pub operator: OperatorKind,
+    pub operand: ExpressionNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PostfixExpression {
+    pub operator: OperatorKind,
+    pub operand: ExpressionNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ConditionalExpression {
+    pub test: ExpressionNode,
+    pub consequent: ExpressionNode,
+    pub alternate: ExpressionNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TemplateLiteral {
+    pub expressions: ExpressionList,
+    pub quasis: NodeList<String>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TaggedTemplateExpression {
+    pub tag: ExpressionNode,
+    pub quasi: Node<TemplateLiteral>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SpreadExpression {
+    pub argument: ExpressionNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct AwaitExpression {
+    pub argument: ExpressionNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ArrowBody {
+    Expression(ExpressionNode),
+    Block(BlockNode<Statement>),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ArrowExpression {
+    pub type_parameters: TypeParameterList,
+    pub params: NodeList<Pattern>,
+    pub return_type: Option<TypeNode>,
+    pub body: ArrowBody,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ObjectExpression {
+    pub body: NodeList<Property>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct AsExpression {
+    pub expression: ExpressionNode,
+    pub annotation: TypeNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SatisfiesExpression {
+    pub expression: ExpressionNode,
+    pub annotation: TypeNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TypeCastExpression {
+    pub expression: ExpressionNode,
+    pub annotation: TypeNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct NonNullExpression {
+    pub expression: ExpressionNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TypeAssertion {
+    pub annotation: TypeNode,
+    pub expression: ExpressionNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct InstantiationExpression {
+    pub expression: ExpressionNode,
+    pub arguments: TypeList,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Expression {
+    Void,
+    This(ThisExpression),
+    Super(SuperExpression),
+    Identifier(Identifier),
+    Literal(Literal),
+    Sequence(SequenceExpression),
+    Parenthesized(ParenthesizedExpression),
+    Array(ArrayExpression),
+    Member(MemberExpression),
+    ComputedMember(ComputedMemberExpression),
+    MetaProperty(MetaPropertyExpression),
+    Call(CallExpression),
+    Binary(BinaryExpression),
+    Prefix(PrefixExpression),
+    Postfix(PostfixExpression),
+    Conditional(ConditionalExpression),
+    Template(TemplateLiteral),
+    TaggedTemplate(TaggedTemplateExpression),
+    Spread(SpreadExpression),
+    Await(AwaitExpression),
+    Arrow(ArrowExpression),
+    Object(ObjectExpression),
+    Function(FunctionExpression),
+    Class(ClassExpression),
+    JSXElement(JSXElement),
+    JSXFragment(JSXFragment),
+    As(AsExpression),
+    Satisfies(SatisfiesExpression),
+    TypeCast(TypeCastExpression),
+    NonNull(NonNullExpression),
+    TypeAssertion(TypeAssertion),
+    Instantiation(InstantiationExpression),
+}
+
+pub type FunctionExpression = Function<OptionalName>;
+pub type ClassExpression = Class<OptionalName>;
+
+owned_struct! {
+    SequenceExpression { body }
+    ParenthesizedExpression { expression }
+    ArrayExpression { body }
+    MemberExpression { object, property }
+    MetaPropertyExpression { meta, property }
+    ComputedMemberExpression { object, property }
+    CallExpression { callee, arguments }
+    BinaryExpression { operator, left, right }
+    PrefixExpression { operator, operand }
+    PostfixExpression { operator, operand }
+    ConditionalExpression { test, consequent, alternate }
+    TemplateLiteral { expressions, quasis }
+    TaggedTemplateExpression { tag, quasi }
+    SpreadExpression { argument }
+    AwaitExpression { argument }
+    ArrowExpression { type_parameters, params, return_type, body }
+    ObjectExpression { body }
+    AsExpression { expression, annotation }
+    SatisfiesExpression { expression, annotation }
+    TypeCastExpression { expression, annotation }
+    NonNullExpression { expression }
+    TypeAssertion { annotation, expression }
+    InstantiationExpression { expression, arguments }
+}
+
+impl<'ast> ToOwnedAst for ast::PropertyKey<'ast> {
+    type Owned = PropertyKey;
+
+    #[inline]
+    fn to_owned_ast(&self) -> PropertyKey {
+        match *self {
+            ast::PropertyKey::Computed(value) => PropertyKey::Computed(value.to_owned_ast()),
+            ast::PropertyKey::Literal(value)  => PropertyKey::Literal(value.to_owned_ast()),
+            ast::PropertyKey::Binary(value)   => PropertyKey::Binary(value.to_owned_ast()),
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for PropertyKey {
+    type Output = ast::PropertyKey<'ast>;
+
+    #[inline]
+    fn to_arena(&self, arena: &'ast Arena) -> ast::PropertyKey<'ast> {
+        match *self {
+            PropertyKey::Computed(ref value) => ast::PropertyKey::Computed(value.to_arena(arena)),
+            PropertyKey::Literal(ref value)  => ast::PropertyKey::Literal(value.to_arena(arena)),
+            PropertyKey::Binary(ref value)   => ast::PropertyKey::Binary(value.to_arena(arena)),
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::Property<'ast> {
+    type Owned = Property;
+
+    #[inline]
+    fn to_owned_ast(&self) -> Property {
+        match *self {
+            ast::Property::Shorthand(value) => Property::Shorthand(value.to_owned_ast()),
+            ast::Property::Literal { key, value } => Property::Literal {
+                key: key.to_owned_ast(),
+                value: value.to_owned_ast(),
+            },
+            ast::Property::Method { key, value } => Property::Method {
+                key: key.to_owned_ast(),
+                value: value.to_owned_ast(),
+            },
+            ast::Property::Spread { argument } => Property::Spread {
+                argument: argument.to_owned_ast(),
+            },
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for Property {
+    type Output = ast::Property<'ast>;
+
+    #[inline]
+    fn to_arena(&self, arena: &'ast Arena) -> ast::Property<'ast> {
+        match *self {
+            Property::Shorthand(ref value) => ast::Property::Shorthand(value.to_arena(arena)),
+            Property::Literal { ref key, ref value } => ast::Property::Literal {
+                key: key.to_arena(arena),
+                value: value.to_arena(arena),
+            },
+            Property::Method { ref key, ref value } => ast::Property::Method {
+                key: key.to_arena(arena),
+                value: value.to_arena(arena),
+            },
+            Property::Spread { ref argument } => ast::Property::Spread {
+                argument: argument.to_arena(arena),
+            },
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::ArrowBody<'ast> {
+    type Owned = ArrowBody;
+
+    #[inline]
+    fn to_owned_ast(&self) -> ArrowBody {
+        match *self {
+            ast::ArrowBody::Expression(value) => ArrowBody::Expression(value.to_owned_ast()),
+            ast::ArrowBody::Block(value)      => ArrowBody::Block(value.to_owned_ast()),
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for ArrowBody {
+    type Output = ast::ArrowBody<'ast>;
+
+    #[inline]
+    fn to_arena(&self, arena: &'ast Arena) -> ast::ArrowBody<'ast> {
+        match *self {
+            ArrowBody::Expression(ref value) => ast::ArrowBody::Expression(value.to_arena(arena)),
+            ArrowBody::Block(ref value)      => ast::ArrowBody::Block(value.to_arena(arena)),
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::Expression<'ast> {
+    type Owned = Expression;
+
+    fn to_owned_ast(&self) -> Expression {
+        match *self {
+            ast::Expression::Void                  => Expression::Void,
+            ast::Expression::This(value)           => Expression::This(value),
+            ast::Expression::Super(value)          => Expression::Super(value),
+            ast::Expression::Identifier(value)     => Expression::Identifier(value.to_owned_ast()),
+            ast::Expression::Literal(value)        => Expression::Literal(value.to_owned_ast()),
+            ast::Expression::Sequence(value)       => Expression::Sequence(value.to_owned_ast()),
+            ast::Expression::Parenthesized(value)  => Expression::Parenthesized(value.to_owned_ast()),
+            ast::Expression::Array(value)          => Expression::Array(value.to_owned_ast()),
+            ast::Expression::Member(value)         => Expression::Member(value.to_owned_ast()),
+            ast::Expression::ComputedMember(value) => Expression::ComputedMember(value.to_owned_ast()),
+            ast::Expression::MetaProperty(value)   => Expression::MetaProperty(value.to_owned_ast()),
+            ast::Expression::Call(value)           => Expression::Call(value.to_owned_ast()),
+            ast::Expression::Binary(value)         => Expression::Binary(value.to_owned_ast()),
+            ast::Expression::Prefix(value)         => Expression::Prefix(value.to_owned_ast()),
+            ast::Expression::Postfix(value)        => Expression::Postfix(value.to_owned_ast()),
+            ast::Expression::Conditional(value)    => Expression::Conditional(value.to_owned_ast()),
+            ast::Expression::Template(value)       => Expression::Template(value.to_owned_ast()),
+            ast::Expression::TaggedTemplate(value) => Expression::TaggedTemplate(value.to_owned_ast()),
+            ast::Expression::Spread(value)         => Expression::Spread(value.to_owned_ast()),
+            ast::Expression::Await(value)          => Expression::Await(value.to_owned_ast()),
+            ast::Expression::Arrow(value)          => Expression::Arrow(value.to_owned_ast()),
+            ast::Expression::Object(value)         => Expression::Object(value.to_owned_ast()),
+            ast::Expression::Function(value)       => Expression::Function(value.to_owned_ast()),
+            ast::Expression::Class(value)          => Expression::Class(value.to_owned_ast()),
+            ast::Expression::JSXElement(value)     => Expression::JSXElement(value.to_owned_ast()),
+            ast::Expression::JSXFragment(value)    => Expression::JSXFragment(value.to_owned_ast()),
+            ast::Expression::As(value)             => Expression::As(value.to_owned_ast()),
+            ast::Expression::Satisfies(value)      => Expression::Satisfies(value.to_owned_ast()),
+            ast::Expression::TypeCast(value)       => Expression::TypeCast(value.to_owned_ast()),
+            ast::Expression::NonNull(value)        => Expression::NonNull(value.to_owned_ast()),
+            ast::Expression::TypeAssertion(value)  => Expression::TypeAssertion(value.to_owned_ast()),
+            ast::Expression::Instantiation(value)  => Expression::Instantiation(value.to_owned_ast()),
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for Expression {
+    type Output = ast::Expression<'ast>;
+
+    fn to_arena(&self, arena: &'ast Arena) -> ast::Expression<'ast> {
+        match *self {
+            Expression::Void                      => ast::Expression::Void,
+            Expression::This(value)               => ast::Expression::This(value),
+            Expression::Super(value)              => ast::Expression::Super(value),
+            Expression::Identifier(ref value)     => ast::Expression::Identifier(value.to_arena(arena)),
+            Expression::Literal(ref value)        => ast::Expression::Literal(value.to_arena(arena)),
+            Expression::Sequence(ref value)       => ast::Expression::Sequence(value.to_arena(arena)),
+            Expression::Parenthesized(ref value)  => ast::Expression::Parenthesized(value.to_arena(arena)),
+            Expression::Array(ref value)          => ast::Expression::Array(value.to_arena(arena)),
+            Expression::Member(ref value)         => ast::Expression::Member(value.to_arena(arena)),
+            Expression::ComputedMember(ref value) => ast::Expression::ComputedMember(value.to_arena(arena)),
+            Expression::MetaProperty(ref value)   => ast::Expression::MetaProperty(value.to_arena(arena)),
+            Expression::Call(ref value)           => ast::Expression::Call(value.to_arena(arena)),
+            Expression::Binary(ref value)         => ast::Expression::Binary(value.to_arena(arena)),
+            Expression::Prefix(ref value)         => ast::Expression::Prefix(value.to_arena(arena)),
+            Expression::Postfix(ref value)        => ast::Expression::Postfix(value.to_arena(arena)),
+            Expression::Conditional(ref value)    => ast::Expression::Conditional(value.to_arena(arena)),
+            Expression::Template(ref value)       => ast::Expression::Template(value.to_arena(arena)),
+            Expression::TaggedTemplate(ref value) => ast::Expression::TaggedTemplate(value.to_arena(arena)),
+            Expression::Spread(ref value)         => ast::Expression::Spread(value.to_arena(arena)),
+            Expression::Await(ref value)          => ast::Expression::Await(value.to_arena(arena)),
+            Expression::Arrow(ref value)          => ast::Expression::Arrow(value.to_arena(arena)),
+            Expression::Object(ref value)         => ast::Expression::Object(value.to_arena(arena)),
+            Expression::Function(ref value)       => ast::Expression::Function(value.to_arena(arena)),
+            Expression::Class(ref value)          => ast::Expression::Class(value.to_arena(arena)),
+            Expression::JSXElement(ref value)     => ast::Expression::JSXElement(value.to_arena(arena)),
+            Expression::JSXFragment(ref value)    => ast::Expression::JSXFragment(value.to_arena(arena)),
+            Expression::As(ref value)             => ast::Expression::As(value.to_arena(arena)),
+            Expression::Satisfies(ref value)      => ast::Expression::Satisfies(value.to_arena(arena)),
+            Expression::TypeCast(ref value)       => ast::Expression::TypeCast(value.to_arena(arena)),
+            Expression::NonNull(ref value)        => ast::Expression::NonNull(value.to_arena(arena)),
+            Expression::TypeAssertion(ref value)  => ast::Expression::TypeAssertion(value.to_arena(arena)),
+            Expression::Instantiation(ref value)  => ast::Expression::Instantiation(value.to_arena(arena)),
+        }
+    }
+}
@@ -0,0 +1,305 @@
+use toolshed::Arena;
+
+use crate::ast;
+use crate::owned::{Node, NodeList, IdentifierNode, ExpressionNode, BlockNode, Statement, PatternList};
+use crate::owned::{PropertyKey, TypeNode, TypeList, ToOwnedAst, ToArena};
+use crate::owned::types::{TypeParameterList, FunctionType};
+
+pub use crate::ast::{EmptyName, MethodKind, Modifiers};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct MandatoryName(pub IdentifierNode);
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct OptionalName(pub Option<IdentifierNode>);
+
+pub type Method = Function<EmptyName>;
+
+pub type DecoratorList = NodeList<Decorator>;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Function<N> {
+    pub name: N,
+    pub generator: bool,
+    pub type_parameters: TypeParameterList,
+    pub params: PatternList,
+    pub return_type: Option<TypeNode>,
+    pub body: BlockNode<Statement>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Class<N> {
+    pub decorators: DecoratorList,
+    pub name: N,
+    pub type_parameters: TypeParameterList,
+    pub extends: Option<ExpressionNode>,
+    pub super_type_arguments: TypeList,
+    pub implements: TypeList,
+    pub body: BlockNode<ClassMember>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Decorator {
+    pub expression: ExpressionNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ClassMember {
+    Error,
+    Method {
+        is_static: bool,
+        key: Node<PropertyKey>,
+        kind: MethodKind,
+        value: Node<Function<EmptyName>>,
+    },
+    Literal {
+        is_static: bool,
+        key: Node<PropertyKey>,
+        value: ExpressionNode,
+    },
+    Property {
+        is_static: bool,
+        key: Node<PropertyKey>,
+        optional: bool,
+        annotation: Option<TypeNode>,
+        value: Option<ExpressionNode>,
+    },
+    Accessor {
+        is_static: bool,
+        key: Node<PropertyKey>,
+        annotation: Option<TypeNode>,
+        value: Option<ExpressionNode>,
+    },
+    Signature {
+        is_static: bool,
+        key: Node<PropertyKey>,
+        kind: MethodKind,
+        signature: Node<FunctionType>,
+    },
+    Modified {
+        modifiers: Modifiers,
+        member: Node<ClassMember>,
+    },
+    Decorated {
+        decorators: DecoratorList,
+        member: Node<ClassMember>,
+    },
+}
+
+owned_struct! {
+    Decorator { expression }
+}
+
+impl<'ast> ToOwnedAst for ast::ClassMember<'ast> {
+    type Owned = ClassMember;
+
+    #[inline]
+    fn to_owned_ast(&self) -> ClassMember {
+        match *self {
+            ast::ClassMember::Error => ClassMember::Error,
+            ast::ClassMember::Method { is_static, key, kind, value } => ClassMember::Method {
+                is_static,
+                key: key.to_owned_ast(),
+                kind,
+                value: value.to_owned_ast(),
+            },
+            ast::ClassMember::Literal { is_static, key, value } => ClassMember::Literal {
+                is_static,
+                key: key.to_owned_ast(),
+                value: value.to_owned_ast(),
+            },
+            ast::ClassMember::Property { is_static, key, optional, annotation, value } => ClassMember::Property {
+                is_static,
+                key: key.to_owned_ast(),
+                optional,
+                annotation: annotation.to_owned_ast(),
+                value: value.to_owned_ast(),
+            },
+            ast::ClassMember::Accessor { is_static, key, annotation, value } => ClassMember::Accessor {
+                is_static,
+                key: key.to_owned_ast(),
+                annotation: annotation.to_owned_ast(),
+                value: value.to_owned_ast(),
+            },
+            ast::ClassMember::Signature { is_static, key, kind, signature } => ClassMember::Signature {
+                is_static,
+                key: key.to_owned_ast(),
+                kind,
+                signature: signature.to_owned_ast(),
+            },
+            ast::ClassMember::Modified { modifiers, member } => ClassMember::Modified {
+                modifiers,
+                member: member.to_owned_ast(),
+            },
+            ast::ClassMember::Decorated { decorators, member } => ClassMember::Decorated {
+                decorators: decorators.to_owned_ast(),
+                member: member.to_owned_ast(),
+            },
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for ClassMember {
+    type Output = ast::ClassMember<'ast>;
+
+    #[inline]
+    fn to_arena(&self, arena: &'ast Arena) -> ast::ClassMember<'ast> {
+        match *self {
+            ClassMember::Error => ast::ClassMember::Error,
+            ClassMember::Method { is_static, ref key, kind, ref value } => ast::ClassMember::Method {
+                is_static,
+                key: key.to_arena(arena),
+                kind,
+                value: value.to_arena(arena),
+            },
+            ClassMember::Literal { is_static, ref key, ref value } => ast::ClassMember::Literal {
+                is_static,
+                key: key.to_arena(arena),
+                value: value.to_arena(arena),
+            },
+            ClassMember::Property { is_static, ref key, optional, ref annotation, ref value } => ast::ClassMember::Property {
+                is_static,
+                key: key.to_arena(arena),
+                optional,
+                annotation: annotation.to_arena(arena),
+                value: value.to_arena(arena),
+            },
+            ClassMember::Accessor { is_static, ref key, ref annotation, ref value } => ast::ClassMember::Accessor {
+                is_static,
+                key: key.to_arena(arena),
+                annotation: annotation.to_arena(arena),
+                value: value.to_arena(arena),
+            },
+            ClassMember::Signature { is_static, ref key, kind, ref signature } => ast::ClassMember::Signature {
+                is_static,
+                key: key.to_arena(arena),
+                kind,
+                signature: signature.to_arena(arena),
+            },
+            ClassMember::Modified { modifiers, ref member } => ast::ClassMember::Modified {
+                modifiers,
+                member: member.to_arena(arena),
+            },
+            ClassMember::Decorated { ref decorators, ref member } => ast::ClassMember::Decorated {
+                decorators: decorators.to_arena(arena),
+                member: member.to_arena(arena),
+            },
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::MandatoryName<'ast> {
+    type Owned = MandatoryName;
+
+    #[inline]
+    fn to_owned_ast(&self) -> MandatoryName {
+        MandatoryName(self.0.to_owned_ast())
+    }
+}
+
+impl<'ast> ToArena<'ast> for MandatoryName {
+    type Output = ast::MandatoryName<'ast>;
+
+    #[inline]
+    fn to_arena(&self, arena: &'ast Arena) -> ast::MandatoryName<'ast> {
+        ast::MandatoryName(self.0.to_arena(arena))
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::OptionalName<'ast> {
+    type Owned = OptionalName;
+
+    #[inline]
+    fn to_owned_ast(&self) -> OptionalName {
+        OptionalName(self.0.to_owned_ast())
+    }
+}
+
+impl<'ast> ToArena<'ast> for OptionalName {
+    type Output = ast::OptionalName<'ast>;
+
+    #[inline]
+    fn to_arena(&self, arena: &'ast Arena) -> ast::OptionalName<'ast> {
+        ast::OptionalName(self.0.to_arena(arena))
+    }
+}
+
+impl<'ast, N> ToOwnedAst for ast::Function<'ast, N>
+where
+    N: ast::Name<'ast> + ToOwnedAst,
+{
+    type Owned = Function<N::Owned>;
+
+    #[inline]
+    fn to_owned_ast(&self) -> Self::Owned {
+        Function {
+            name: self.name.to_owned_ast(),
+            generator: self.generator,
+            type_parameters: self.type_parameters.to_owned_ast(),
+            params: self.params.to_owned_ast(),
+            return_type: self.return_type.to_owned_ast(),
+            body: self.body.to_owned_ast(),
+        }
+    }
+}
+
+impl<'ast, N> ToArena<'ast> for Function<N>
+where
+    N: ToArena<'ast>,
+    N::Output: ast::Name<'ast>,
+{
+    type Output = ast::Function<'ast, N::Output>;
+
+    #[inline]
+    fn to_arena(&self, arena: &'ast Arena) -> Self::Output {
+        ast::Function {
+            name: self.name.to_arena(arena),
+            generator: self.generator,
+            type_parameters: self.type_parameters.to_arena(arena),
+            params: self.params.to_arena(arena),
+            return_type: self.return_type.to_arena(arena),
+            body: self.body.to_arena(arena),
+        }
+    }
+}
+
+impl<'ast, N> ToOwnedAst for ast::Class<'ast, N>
+where
+    N: ast::Name<'ast> + ToOwnedAst,
+{
+    type Owned = Class<N::Owned>;
+
+    #[inline]
+    fn to_owned_ast(&self) -> Self::Owned {
+        Class {
+            decorators: self.decorators.to_owned_ast(),
+            name: self.name.to_owned_ast(),
+            type_parameters: self.type_parameters.to_owned_ast(),
+            extends: self.extends.to_owned_ast(),
+            super_type_arguments: self.super_type_arguments.to_owned_ast(),
+            implements: self.implements.to_owned_ast(),
+            body: self.body.to_owned_ast(),
+        }
+    }
+}
+
+impl<'ast, N> ToArena<'ast> for Class<N>
+where
+    N: ToArena<'ast>,
+    N::Output: ast::Name<'ast>,
+{
+    type Output = ast::Class<'ast, N::Output>;
+
+    #[inline]
+    fn to_arena(&self, arena: &'ast Arena) -> Self::Output {
+        ast::Class {
+            decorators: self.decorators.to_arena(arena),
+            name: self.name.to_arena(arena),
+            type_parameters: self.type_parameters.to_arena(arena),
+            extends: self.extends.to_arena(arena),
+            super_type_arguments: self.super_type_arguments.to_arena(arena),
+            implements: self.implements.to_arena(arena),
+            body: self.body.to_arena(arena),
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use toolshed::Arena;
+
+use crate::ast::jsx as ast;
+use crate::owned::{Node, NodeList, ExpressionNode, IdentifierNode, ToOwnedAst, ToArena};
+
+pub use crate::ast::jsx::{JSXOpeningFragment, JSXClosingFragment};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum JSXElementName {
+    Identifier(String),
+    Namespaced {
+        namespace: IdentifierNode,
+        name: IdentifierNode,
+    },
+    Member(JSXMemberExpression),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct JSXMemberExpression {
+    pub object: Node<JSXElementName>,
+    pub property: IdentifierNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum JSXAttributeValue {
+    String(String),
+    Expression(JSXExpressionContainer),
+    Element(JSXElement),
+    Fragment(JSXFragment),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct JSXAttribute {
+    pub name: Node<JSXElementName>,
+    pub value: Option<Node<JSXAttributeValue>>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct JSXSpreadAttribute {
+    pub argument: ExpressionNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum JSXAttributeItem {
+    Attribute(JSXAttribute),
+    Spread(JSXSpreadAttribute),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct JSXExpressionContainer {
+    pub expression: Option<ExpressionNode>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum JSXChild {
+    Text(String),
+    Expression(JSXExpressionContainer),
+    Element(JSXElement),
+    Fragment(JSXFragment),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct JSXOpeningElement {
+    pub name: Node<JSXElementName>,
+    pub attributes: NodeList<JSXAttributeItem>,
+    pub self_closing: bool,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct JSXClosingElement {
+    pub name: Node<JSXElementName>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct JSXElement {
+    pub opening: Node<JSXOpeningElement>,
+    pub children: NodeList<JSXChild>,
+    pub closing: Option<Node<JSXClosingElement>>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct JSXFragment {
+    pub opening: Node<JSXOpeningFragment>,
+    pub children: NodeList<JSXChild>,
+    pub closing: Node<JSXClosingFragment>,
+}
+
+owned_struct! {
+    JSXMemberExpression { object, property }
+    JSXAttribute { name, value }
+    JSXSpreadAttribute { argument }
+    JSXExpressionContainer { expression }
+    JSXOpeningElement { name, attributes, self_closing }
+    JSXClosingElement { name }
+    JSXElement { opening, children, closing }
+    JSXFragment { opening, children, closing }
+}
+
+impl<'ast> ToOwnedAst for ast::JSXElementName<'ast> {
+    type Owned = JSXElementName;
+
+    #[inline]
+    fn to_owned_ast(&self) -> JSXElementName {
+        match *self {
+            ast::JSXElementName::Identifier(value) => JSXElementName::Identifier(value.to_owned_ast()),
+            ast::JSXElementName::Namespaced { namespace, name } => JSXElementName::Namespaced {
+                namespace: namespace.to_owned_ast(),
+                name: name.to_owned_ast(),
+            },
+            ast::JSXElementName::Member(value)     => JSXElementName::Member(value.to_owned_ast()),
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for JSXElementName {
+    type Output = ast::JSXElementName<'ast>;
+
+    #[inline]
+    fn to_arena(&self, arena: &'ast Arena) -> ast::JSXElementName<'ast> {
+        match *self {
+            JSXElementName::Identifier(ref value) => ast::JSXElementName::Identifier(value.to_arena(arena)),
+            JSXElementName::Namespaced { ref namespace, ref name } => ast::JSXElementName::Namespaced {
+                namespace: namespace.to_arena(arena),
+                name: name.to_arena(arena),
+            },
+            JSXElementName::Member(ref value)     => ast::JSXElementName::Member(value.to_arena(arena)),
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::JSXAttributeValue<'ast> {
+    type Owned = JSXAttributeValue;
+
+    #[inline]
+    fn to_owned_ast(&self) -> JSXAttributeValue {
+        match *self {
+            ast::JSXAttributeValue::String(value)     => JSXAttributeValue::String(value.to_owned_ast()),
+            ast::JSXAttributeValue::Expression(value) => JSXAttributeValue::Expression(value.to_owned_ast()),
+            ast::JSXAttributeValue::Element(value)    => JSXAttributeValue::Element(value.to_owned_ast()),
+            ast::JSXAttributeValue::Fragment(value)   => JSXAttributeValue::Fragment(value.to_owned_ast()),
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for JSXAttributeValue {
+    type Output = ast::JSXAttributeValue<'ast>;
+
+    #[inline]
+    fn to_arena(&self, arena: &'ast Arena) -> ast::JSXAttributeValue<'ast> {
+        match *self {
+            JSXAttributeValue::String(ref value)     => ast::JSXAttributeValue::String(value.to_arena(arena)),
+            JSXAttributeValue::Expression(ref value) => ast::JSXAttributeValue::Expression(value.to_arena(arena)),
+            JSXAttributeValue::Element(ref value)    => ast::JSXAttributeValue::Element(value.to_arena(arena)),
+            JSXAttributeValue::Fragment(ref value)   => ast::JSXAttributeValue::Fragment(value.to_arena(arena)),
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::JSXAttributeItem<'ast> {
+    type Owned = JSXAttributeItem;
+
+    #[inline]
+    fn to_owned_ast(&self) -> JSXAttributeItem {
+        match *self {
+            ast::JSXAttributeItem::Attribute(value) => JSXAttributeItem::Attribute(value.to_owned_ast()),
+            ast::JSXAttributeItem::Spread(value)    => JSXAttributeItem::Spread(value.to_owned_ast()),
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for JSXAttributeItem {
+    type Output = ast::JSXAttributeItem<'ast>;
+
+    #[inline]
+    fn to_arena(&self, arena: &'ast Arena) -> ast::JSXAttributeItem<'ast> {
+        match *self {
+            JSXAttributeItem::Attribute(ref value) => ast::JSXAttributeItem::Attribute(value.to_arena(arena)),
+            JSXAttributeItem::Spread(ref value)    => ast::JSXAttributeItem::Spread(value.to_arena(arena)),
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::JSXChild<'ast> {
+    type Owned = JSXChild;
+
+    #[inline]
+    fn to_owned_ast(&self) -> JSXChild {
+        match *self {
+            ast::JSXChild::Text(value)       => JSXChild::Text(value.to_owned_ast()),
+            ast::JSXChild::Expression(value) => JSXChild::Expression(value.to_owned_ast()),
+            ast::JSXChild::Element(value)    => JSXChild::Element(value.to_owned_ast()),
+            ast::JSXChild::Fragment(value)   => JSXChild::Fragment(value.to_owned_ast()),
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for JSXChild {
+    type Output = ast::JSXChild<'ast>;
+
+    #[inline]
+    fn to_arena(&self, arena: &'ast Arena) -> ast::JSXChild<'ast> {
+        match *self {
+            JSXChild::Text(ref value)       => ast::JSXChild::Text(value.to_arena(arena)),
+            JSXChild::Expression(ref value) => ast::JSXChild::Expression(value.to_arena(arena)),
+            JSXChild::Element(ref value)    => ast::JSXChild::Element(value.to_arena(arena)),
+            JSXChild::Fragment(ref value)   => ast::JSXChild::Fragment(value.to_arena(arena)),
+        }
+    }
+}
@@ -0,0 +1,563 @@
+//! Owned representation of the AST.
+//!
+//! The regular AST lives on an `Arena` and is tied to it, which keeps it
+//! on the thread that parsed it. The types in this module mirror those in
+//! `esr::ast`, but own their children through `Box`, `Vec` and `String`,
+//! making them `Send + Sync` and `'static`. Use them to move an AST across
+//! threads, to cache it, or to keep it around longer than its `Module`.
+//!
+//! Conversions go both ways and keep source positions: `ToOwnedAst` turns
+//! an arena node into an owned one, and `ToArena` allocates an owned node
+//! back on an `Arena`.
+
+/// Implement `ToOwnedAst` and `ToArena` for structs, converting every field.
+macro_rules! owned_struct {
+    ($( $name:ident { $( $field:ident ),* } )*) => {
+        $(
+            impl<'ast> ToOwnedAst for ast::$name<'ast> {
+                type Owned = $name;
+
+                #[inline]
+                fn to_owned_ast(&self) -> $name {
+                    $name {
+                        $( $field: self.$field.to_owned_ast(), )*
+                    }
+                }
+            }
+
+            impl<'ast> ToArena<'ast> for $name {
+                type Output = ast::$name<'ast>;
+
+                #[inline]
+                fn to_arena(&self, arena: &'ast Arena) -> ast::$name<'ast> {
+                    ast::$name {
+                        $( $field: self.$field.to_arena(arena), )*
+                    }
+                }
+            }
+        )*
+    };
+}
+
+/// Types that don't borrow from the `Arena` are shared between both ASTs.
+macro_rules! owned_copy {
+    ($( $name:ty ),*) => {
+        $(
+            impl ToOwnedAst for $name {
+                type Owned = $name;
+
+                #[inline]
+                fn to_owned_ast(&self) -> $name {
+                    *self
+                }
+            }
+
+            impl<'ast> ToArena<'ast> for $name {
+                type Output = $name;
+
+                #[inline]
+                fn to_arena(&self, _: &'ast Arena) -> $name {
+                    *self
+                }
+            }
+        )*
+    };
+}
+
+pub mod expression;
+pub mod statement;
+pub mod jsx;
+pub mod types;
+mod function;
+
+use toolshed::Arena;
+use toolshed::list::List;
+use std::ops::Deref;
+
+use crate::ast::{self as ast, Loc};
+use crate::parser::ParseOptions;
+
+pub use crate::ast::{OperatorKind, DeclarationKind};
+pub use crate::owned::types::{Type, Primitive, TypeName, TypeParameter, TypeParameterList};
+pub use crate::owned::types::{FunctionType, TypeMember, Modifiers, Accessibility};
+pub use crate::owned::expression::{Expression, Property, PropertyKey};
+pub use crate::owned::statement::{Statement, Declarator, BlockStatement, Label};
+pub use crate::owned::function::{Function, Class, ClassMember, Method, MethodKind};
+pub use crate::owned::function::{Decorator, DecoratorList};
+pub use crate::owned::function::{EmptyName, OptionalName, MandatoryName};
+
+/// Convert a node allocated on an `Arena` to its owned counterpart.
+pub trait ToOwnedAst {
+    type Owned;
+
+    fn to_owned_ast(&self) -> Self::Owned;
+}
+
+/// Allocate an owned node on an `Arena`, converting it back to the
+/// regular AST.
+pub trait ToArena<'ast> {
+    type Output;
+
+    fn to_arena(&self, arena: &'ast Arena) -> Self::Output;
+}
+
+/// Owned counterpart of `ast::Node`, boxing the item with its position.
+/// Like `Loc`, comparing nodes ignores their positions.
+#[derive(Debug, Clone)]
+pub struct Node<T> {
+    pub start: u32,
+    pub end: u32,
+    pub item: Box<T>,
+}
+
+impl<T> Node<T> {
+    #[inline]
+    pub fn new(start: u32, end: u32, item: T) -> Self {
+        Node {
+            start,
+            end,
+            item: Box::new(item),
+        }
+    }
+
+    /// Create a synthetic `Node`, not spanning any part of the source.
+    #[inline]
+    pub fn synthetic(item: T) -> Self {
+        Node::new(ast::SYNTHETIC, ast::SYNTHETIC, item)
+    }
+
+    /// Whether this is a synthetic `Node`, see `Loc::synthetic`.
+    #[inline]
+    pub fn is_synthetic(&self) -> bool {
+        self.start == ast::SYNTHETIC && self.end == ast::SYNTHETIC
+    }
+}
+
+impl<T> Deref for Node<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.item
+    }
+}
+
+impl<T: PartialEq> PartialEq for Node<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.item.eq(&other.item)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Block<T> {
+    pub body: NodeList<T>
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Pattern {
+    /// Only used inside ArrayPattern
+    Void,
+    Identifier(IdentifierNode),
+    ObjectPattern {
+        properties: NodeList<Property>,
+    },
+    ArrayPattern {
+        elements: NodeList<Pattern>,
+    },
+    RestElement {
+        argument: IdentifierNode,
+    },
+    AssignmentPattern {
+        left: Node<Pattern>,
+        right: ExpressionNode,
+    },
+    /// Pattern with a type annotation, `foo?: number`
+    Annotated {
+        pattern: Node<Pattern>,
+        optional: bool,
+        annotation: Option<TypeNode>,
+    },
+    /// Constructor parameter declaring a class property, `private foo`
+    ParameterProperty {
+        modifiers: Modifiers,
+        parameter: Node<Pattern>,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Literal {
+    Undefined,
+    Null,
+    True,
+    False,
+    Number(String),
+    Binary(String),
+    String(String),
+    RegEx(String),
+}
+
+// Handful of useful aliases
+pub type Identifier = String;
+pub type NodeList<T> = Vec<Node<T>>;
+pub type BlockNode<T> = Node<Block<T>>;
+pub type PatternList = NodeList<Pattern>;
+pub type PropertyNode = Node<Property>;
+pub type ExpressionNode = Node<Expression>;
+pub type ExpressionList = NodeList<Expression>;
+pub type StatementNode = Node<Statement>;
+pub type StatementList = NodeList<Statement>;
+pub type IdentifierNode = Node<Identifier>;
+pub type IdentifierList = NodeList<Identifier>;
+pub type LabelNode = Node<Label>;
+pub type TypeNode = Node<Type>;
+pub type TypeList = NodeList<Type>;
+
+/// Owned counterpart of `Module`, along with the options it was parsed with.
+#[derive(Debug, Clone)]
+pub struct Module {
+    pub body: StatementList,
+    pub options: ParseOptions,
+}
+
+impl Module {
+    /// Copy the AST of a parsed `Module`.
+    pub fn from_module(module: &crate::Module<'_>) -> Self {
+        Module {
+            body: module.body().to_owned_ast(),
+            options: module.options,
+        }
+    }
+
+    /// Allocate the AST on a new `Arena`, as if it had just been parsed.
+    pub fn to_module<'ast>(&self) -> crate::Module<'ast> {
+        let arena = Arena::new();
+        let body = self.body.to_arena(&arena).into_unsafe();
+
+        crate::Module::new(body, arena, self.options)
+    }
+}
+
+impl<'ast, T> ToOwnedAst for ast::Node<'ast, T>
+where
+    T: 'ast + ToOwnedAst,
+{
+    type Owned = Node<T::Owned>;
+
+    #[inline]
+    fn to_owned_ast(&self) -> Node<T::Owned> {
+        Node::new(self.start, self.end, self.item.to_owned_ast())
+    }
+}
+
+impl<'ast, T> ToArena<'ast> for Node<T>
+where
+    T: ToArena<'ast>,
+    T::Output: 'ast + Copy,
+{
+    type Output = ast::Node<'ast, T::Output>;
+
+    #[inline]
+    fn to_arena(&self, arena: &'ast Arena) -> Self::Output {
+        let item = self.item.to_arena(arena);
+
+        ast::Node::new(arena.alloc(Loc::new(self.start, self.end, item)))
+    }
+}
+
+impl<'ast, T> ToOwnedAst for List<'ast, T>
+where
+    T: 'ast + ToOwnedAst,
+{
+    type Owned = Vec<T::Owned>;
+
+    #[inline]
+    fn to_owned_ast(&self) -> Vec<T::Owned> {
+        self.iter().map(ToOwnedAst::to_owned_ast).collect()
+    }
+}
+
+impl<'ast, T> ToArena<'ast> for Vec<T>
+where
+    T: ToArena<'ast>,
+    T::Output: 'ast + Copy,
+{
+    type Output = List<'ast, T::Output>;
+
+    #[inline]
+    fn to_arena(&self, arena: &'ast Arena) -> Self::Output {
+        List::from_iter(arena, self.iter().map(|item| item.to_arena(arena)))
+    }
+}
+
+impl<T: ToOwnedAst> ToOwnedAst for Option<T> {
+    type Owned = Option<T::Owned>;
+
+    #[inline]
+    fn to_owned_ast(&self) -> Self::Owned {
+        self.as_ref().map(ToOwnedAst::to_owned_ast)
+    }
+}
+
+impl<'ast, T: ToArena<'ast>> ToArena<'ast> for Option<T> {
+    type Output = Option<T::Output>;
+
+    #[inline]
+    fn to_arena(&self, arena: &'ast Arena) -> Self::Output {
+        self.as_ref().map(|item| item.to_arena(arena))
+    }
+}
+
+impl ToOwnedAst for &str {
+    type Owned = String;
+
+    #[inline]
+    fn to_owned_ast(&self) -> String {
+        String::from(*self)
+    }
+}
+
+impl<'ast> ToArena<'ast> for String {
+    type Output = &'ast str;
+
+    #[inline]
+    fn to_arena(&self, arena: &'ast Arena) -> &'ast str {
+        arena.alloc_str(self)
+    }
+}
+
+impl<'ast, T> ToOwnedAst for ast::Block<'ast, T>
+where
+    T: 'ast + ToOwnedAst,
+{
+    type Owned = Block<T::Owned>;
+
+    #[inline]
+    fn to_owned_ast(&self) -> Block<T::Owned> {
+        Block {
+            body: self.body.to_owned_ast(),
+        }
+    }
+}
+
+impl<'ast, T> ToArena<'ast> for Block<T>
+where
+    T: ToArena<'ast>,
+    T::Output: 'ast + Copy,
+{
+    type Output = ast::Block<'ast, T::Output>;
+
+    #[inline]
+    fn to_arena(&self, arena: &'ast Arena) -> Self::Output {
+        ast::Block {
+            body: self.body.to_arena(arena),
+        }
+    }
+}
+
+owned_copy! {
+    bool,
+    OperatorKind,
+    DeclarationKind,
+    MethodKind,
+    EmptyName,
+    Primitive,
+    Modifiers,
+    Accessibility,
+    types::TypeOperator,
+    types::TypeImportKind,
+    expression::ThisExpression,
+    expression::SuperExpression,
+    jsx::JSXOpeningFragment,
+    jsx::JSXClosingFragment
+}
+
+impl<'ast> ToOwnedAst for ast::Pattern<'ast> {
+    type Owned = Pattern;
+
+    #[inline]
+    fn to_owned_ast(&self) -> Pattern {
+        match *self {
+            ast::Pattern::Void              => Pattern::Void,
+            ast::Pattern::Identifier(value) => Pattern::Identifier(value.to_owned_ast()),
+            ast::Pattern::ObjectPattern { properties } => Pattern::ObjectPattern {
+                properties: properties.to_owned_ast(),
+            },
+            ast::Pattern::ArrayPattern { elements } => Pattern::ArrayPattern {
+                elements: elements.to_owned_ast(),
+            },
+            ast::Pattern::RestElement { argument } => Pattern::RestElement {
+                argument: argument.to_owned_ast(),
+            },
+            ast::Pattern::AssignmentPattern { left, right } => Pattern::AssignmentPattern {
+                left: left.to_owned_ast(),
+                right: right.to_owned_ast(),
+            },
+            ast::Pattern::Annotated { pattern, optional, annotation } => Pattern::Annotated {
+                pattern: pattern.to_owned_ast(),
+                optional,
+                annotation: annotation.to_owned_ast(),
+            },
+            ast::Pattern::ParameterProperty { modifiers, parameter } => Pattern::ParameterProperty {
+                modifiers,
+                parameter: parameter.to_owned_ast(),
+            },
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for Pattern {
+    type Output = ast::Pattern<'ast>;
+
+    #[inline]
+    fn to_arena(&self, arena: &'ast Arena) -> ast::Pattern<'ast> {
+        match *self {
+            Pattern::Void                  => ast::Pattern::Void,
+            Pattern::Identifier(ref value) => ast::Pattern::Identifier(value.to_arena(arena)),
+            Pattern::ObjectPattern { ref properties } => ast::Pattern::ObjectPattern {
+                properties: properties.to_arena(arena),
+            },
+            Pattern::ArrayPattern { ref elements } => ast::Pattern::ArrayPattern {
+                elements: elements.to_arena(arena),
+            },
+            Pattern::RestElement { ref argument } => ast::Pattern::RestElement {
+                argument: argument.to_arena(arena),
+            },
+            Pattern::AssignmentPattern { ref left, ref right } => ast::Pattern::AssignmentPattern {
+                left: left.to_arena(arena),
+                right: right.to_arena(arena),
+            },
+            Pattern::Annotated { ref pattern, optional, ref annotation } => ast::Pattern::Annotated {
+                pattern: pattern.to_arena(arena),
+                optional,
+                annotation: annotation.to_arena(arena),
+            },
+            Pattern::ParameterProperty { modifiers, ref parameter } => ast::Pattern::ParameterProperty {
+                modifiers,
+                parameter: parameter.to_arena(arena),
+            },
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::Literal<'ast> {
+    type Owned = Literal;
+
+    #[inline]
+    fn to_owned_ast(&self) -> Literal {
+        match *self {
+            ast::Literal::Undefined     => Literal::Undefined,
+            ast::Literal::Null          => Literal::Null,
+            ast::Literal::True          => Literal::True,
+            ast::Literal::False         => Literal::False,
+            ast::Literal::Number(value) => Literal::Number(value.to_owned_ast()),
+            ast::Literal::Binary(value) => Literal::Binary(value.to_owned_ast()),
+            ast::Literal::String(value) => Literal::String(value.to_owned_ast()),
+            ast::Literal::RegEx(value)  => Literal::RegEx(value.to_owned_ast()),
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for Literal {
+    type Output = ast::Literal<'ast>;
+
+    #[inline]
+    fn to_arena(&self, arena: &'ast Arena) -> ast::Literal<'ast> {
+        match *self {
+            Literal::Undefined         => ast::Literal::Undefined,
+            Literal::Null              => ast::Literal::Null,
+            Literal::True              => ast::Literal::True,
+            Literal::False             => ast::Literal::False,
+            Literal::Number(ref value) => ast::Literal::Number(value.to_arena(arena)),
+            Literal::Binary(ref value) => ast::Literal::Binary(value.to_arena(arena)),
+            Literal::String(ref value) => ast::Literal::String(value.to_arena(arena)),
+            Literal::RegEx(ref value)  => ast::Literal::RegEx(value.to_arena(arena)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::thread;
+    use crate::{parse, parse_with_options};
+
+    fn assert_send_sync<T: Send + Sync + 'static>() {}
+
+    fn round_trip(source: &str, options: ParseOptions) {
+        let module = parse_with_options(source, options).unwrap();
+        let owned = Module::from_module(&module);
+        let back = owned.to_module();
+
+        // Debug output includes positions, which `PartialEq` ignores
+        assert_eq!(format!("{:?}", back.body()), format!("{:?}", module.body()));
+        assert_eq!(Module::from_module(&back).body, owned.body);
+    }
+
+    #[test]
+    fn owned_ast_is_send_and_sync() {
+        assert_send_sync::<Module>();
+        assert_send_sync::<StatementNode>();
+        assert_send_sync::<ExpressionNode>();
+    }
+
+    #[test]
+    fn round_trips_javascript() {
+        round_trip(r#"
+            label: for (let i = 0; i < 10; i++) { if (i) continue label; else break; }
+            function gen(a, { b, c: [d, ...e] } = {}, ...f) { return a ** 2; }
+            class Foo extends Bar { static x = 1; get y() { return `a${this.x}b`; } }
+            const re = /ab+c/gi, n = 0x1F, s = 'str\n';
+            try { throw new Error() } catch ({ message }) {} finally { x = null; }
+            while (x) { y(); z.w[0]++; }
+            let fn = (a, b) => ({ ...a, [b]: a ? b : void 0 });
+        "#, ParseOptions::default());
+    }
+
+    #[test]
+    fn round_trips_typescript() {
+        round_trip(r#"
+            interface Foo<T extends object = {}> { bar?: T; baz(x: number): string; }
+            type Union = "a" | "b" | keyof Foo<string>[];
+            enum Color { Red, Green = 2 }
+            class Point { private x: number; constructor(x: number) { this.x = x; } }
+            function foo<T>(x: T): x is T { return x as any; }
+        "#, ParseOptions { typescript: true, ..ParseOptions::default() });
+    }
+
+    #[test]
+    fn round_trips_jsx_and_flow() {
+        round_trip(
+            r#"const el = <div a="b" {...c}><Foo.Bar />{d}<>text</></div>;"#,
+            ParseOptions { jsx: true, ..ParseOptions::default() },
+        );
+        round_trip(
+            "// @flow\nopaque type Id = string; import type { A } from 'a'; function f(x: ?number): A {}",
+            ParseOptions::default(),
+        );
+    }
+
+    #[test]
+    fn moves_between_threads() {
+        let owned = thread::spawn(|| {
+            let module = parse("function foo(bar) { return bar * 2 }").unwrap();
+
+            Module::from_module(&module)
+        }).join().unwrap();
+
+        let module = owned.to_module();
+        let expected = parse("function foo(bar) { return bar * 2 }").unwrap();
+
+        assert_eq!(module.body(), expected.body());
+        assert_eq!(format!("{:?}", module.body()), format!("{:?}", expected.body()));
+    }
+
+    #[test]
+    fn keeps_options() {
+        let module = parse("// @flow\nlet a: number = 1;").unwrap();
+        let owned = Module::from_module(&module);
+
+        assert!(owned.options.flow);
+        assert!(owned.to_module().is_flow());
+    }
+}
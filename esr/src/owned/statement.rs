@@ -0,0 +1,300 @@
+use toolshed::Arena;
+
+use crate::ast::statement as ast;
+use crate::owned::{Node, NodeList, DeclarationKind, Function, Class, MandatoryName, LabelNode};
+use crate::owned::{ExpressionNode, StatementNode, StatementList, Block, BlockNode, Pattern, ToOwnedAst, ToArena};
+use crate::owned::types::{InterfaceDeclaration, TypeAliasDeclaration, FunctionSignature, DeclareStatement};
+use crate::owned::types::{OpaqueTypeDeclaration, TypeImportDeclaration};
+use crate::owned::types::{EnumDeclaration, NamespaceDeclaration};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Label(pub String);
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Declarator {
+    pub id: Node<Pattern>,
+    pub init: Option<ExpressionNode>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct DeclarationStatement {
+    pub kind: DeclarationKind,
+    pub declarators: NodeList<Declarator>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ExportDeclaration {
+    pub is_default: bool,
+    pub declaration: StatementNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ReturnStatement {
+    pub value: Option<ExpressionNode>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BreakStatement {
+    pub label: Option<LabelNode>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ContinueStatement {
+    pub label: Option<LabelNode>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ThrowStatement {
+    pub value: ExpressionNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct IfStatement {
+    pub test: ExpressionNode,
+    pub consequent: StatementNode,
+    pub alternate: Option<StatementNode>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct WhileStatement {
+    pub test: ExpressionNode,
+    pub body: StatementNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct DoStatement {
+    pub body: StatementNode,
+    pub test: ExpressionNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ForInit {
+    Declaration(DeclarationStatement),
+    Expression(ExpressionNode),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ForStatement {
+    pub init: Option<Node<ForInit>>,
+    pub test: Option<ExpressionNode>,
+    pub update: Option<ExpressionNode>,
+    pub body: StatementNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ForInStatement {
+    pub left: Node<ForInit>,
+    pub right: ExpressionNode,
+    pub body: StatementNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ForOfStatement {
+    pub left: Node<ForInit>,
+    pub right: ExpressionNode,
+    pub body: StatementNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CatchClause {
+    pub param: Node<Pattern>,
+    pub body: BlockNode<Statement>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TryStatement {
+    pub block: BlockNode<Statement>,
+    pub handler: Option<Node<CatchClause>>,
+    pub finalizer: Option<BlockNode<Statement>>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct LabeledStatement {
+    pub label: LabelNode,
+    pub body: StatementNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SwitchStatement {
+    pub discriminant: ExpressionNode,
+    pub cases: BlockNode<SwitchCase>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SwitchCase {
+    pub test: Option<ExpressionNode>,
+    pub consequent: StatementList,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Statement {
+    Empty,
+    Expression(ExpressionNode),
+    Declaration(DeclarationStatement),
+    Return(ReturnStatement),
+    Break(BreakStatement),
+    Continue(ContinueStatement),
+    Throw(ThrowStatement),
+    If(IfStatement),
+    While(WhileStatement),
+    Do(DoStatement),
+    For(ForStatement),
+    ForIn(ForInStatement),
+    ForOf(ForOfStatement),
+    Try(TryStatement),
+    Block(BlockStatement),
+    Labeled(LabeledStatement),
+    Function(FunctionStatement),
+    Class(ClassStatement),
+    Switch(SwitchStatement),
+    Interface(InterfaceDeclaration),
+    TypeAlias(TypeAliasDeclaration),
+    FunctionSignature(FunctionSignature),
+    Declare(DeclareStatement),
+    OpaqueType(OpaqueTypeDeclaration),
+    TypeImport(TypeImportDeclaration),
+    Enum(EnumDeclaration),
+    Namespace(NamespaceDeclaration),
+    Export(ExportDeclaration),
+}
+
+pub type BlockStatement = Block<Statement>;
+pub type FunctionStatement = Function<MandatoryName>;
+pub type ClassStatement = Class<MandatoryName>;
+
+owned_struct! {
+    Declarator { id, init }
+    DeclarationStatement { kind, declarators }
+    ExportDeclaration { is_default, declaration }
+    ReturnStatement { value }
+    BreakStatement { label }
+    ContinueStatement { label }
+    ThrowStatement { value }
+    IfStatement { test, consequent, alternate }
+    WhileStatement { test, body }
+    DoStatement { body, test }
+    ForStatement { init, test, update, body }
+    ForInStatement { left, right, body }
+    ForOfStatement { left, right, body }
+    CatchClause { param, body }
+    TryStatement { block, handler, finalizer }
+    LabeledStatement { label, body }
+    SwitchStatement { discriminant, cases }
+    SwitchCase { test, consequent }
+}
+
+impl<'ast> ToOwnedAst for ast::ForInit<'ast> {
+    type Owned = ForInit;
+
+    #[inline]
+    fn to_owned_ast(&self) -> ForInit {
+        match *self {
+            ast::ForInit::Declaration(value) => ForInit::Declaration(value.to_owned_ast()),
+            ast::ForInit::Expression(value)  => ForInit::Expression(value.to_owned_ast()),
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for ForInit {
+    type Output = ast::ForInit<'ast>;
+
+    #[inline]
+    fn to_arena(&self, arena: &'ast Arena) -> ast::ForInit<'ast> {
+        match *self {
+            ForInit::Declaration(ref value) => ast::ForInit::Declaration(value.to_arena(arena)),
+            ForInit::Expression(ref value)  => ast::ForInit::Expression(value.to_arena(arena)),
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::Statement<'ast> {
+    type Owned = Statement;
+
+    fn to_owned_ast(&self) -> Statement {
+        match *self {
+            ast::Statement::Empty                    => Statement::Empty,
+            ast::Statement::Expression(value)        => Statement::Expression(value.to_owned_ast()),
+            ast::Statement::Declaration(value)       => Statement::Declaration(value.to_owned_ast()),
+            ast::Statement::Return(value)            => Statement::Return(value.to_owned_ast()),
+            ast::Statement::Break(value)             => Statement::Break(value.to_owned_ast()),
+            ast::Statement::Continue(value)          => Statement::Continue(value.to_owned_ast()),
+            ast::Statement::Throw(value)             => Statement::Throw(value.to_owned_ast()),
+            ast::Statement::If(value)                => Statement::If(value.to_owned_ast()),
+            ast::Statement::While(value)             => Statement::While(value.to_owned_ast()),
+            ast::Statement::Do(value)                => Statement::Do(value.to_owned_ast()),
+            ast::Statement::For(value)               => Statement::For(value.to_owned_ast()),
+            ast::Statement::ForIn(value)             => Statement::ForIn(value.to_owned_ast()),
+            ast::Statement::ForOf(value)             => Statement::ForOf(value.to_owned_ast()),
+            ast::Statement::Try(value)               => Statement::Try(value.to_owned_ast()),
+            ast::Statement::Block(value)             => Statement::Block(value.to_owned_ast()),
+            ast::Statement::Labeled(value)           => Statement::Labeled(value.to_owned_ast()),
+            ast::Statement::Function(value)          => Statement::Function(value.to_owned_ast()),
+            ast::Statement::Class(value)             => Statement::Class(value.to_owned_ast()),
+            ast::Statement::Switch(value)            => Statement::Switch(value.to_owned_ast()),
+            ast::Statement::Interface(value)         => Statement::Interface(value.to_owned_ast()),
+            ast::Statement::TypeAlias(value)         => Statement::TypeAlias(value.to_owned_ast()),
+            ast::Statement::FunctionSignature(value) => Statement::FunctionSignature(value.to_owned_ast()),
+            ast::Statement::Declare(value)           => Statement::Declare(value.to_owned_ast()),
+            ast::Statement::OpaqueType(value)        => Statement::OpaqueType(value.to_owned_ast()),
+            ast::Statement::TypeImport(value)        => Statement::TypeImport(value.to_owned_ast()),
+            ast::Statement::Enum(value)              => Statement::Enum(value.to_owned_ast()),
+            ast::Statement::Namespace(value)         => Statement::Namespace(value.to_owned_ast()),
+            ast::Statement::Export(value)            => Statement::Export(value.to_owned_ast()),
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for Statement {
+    type Output = ast::Statement<'ast>;
+
+    fn to_arena(&self, arena: &'ast Arena) -> ast::Statement<'ast> {
+        match *self {
+            Statement::Empty                        => ast::Statement::Empty,
+            Statement::Expression(ref value)        => ast::Statement::Expression(value.to_arena(arena)),
+            Statement::Declaration(ref value)       => ast::Statement::Declaration(value.to_arena(arena)),
+            Statement::Return(ref value)            => ast::Statement::Return(value.to_arena(arena)),
+            Statement::Break(ref value)             => ast::Statement::Break(value.to_arena(arena)),
+            Statement::Continue(ref value)          => ast::Statement::Continue(value.to_arena(arena)),
+            Statement::Throw(ref value)             => ast::Statement::Throw(value.to_arena(arena)),
+            Statement::If(ref value)                => ast::Statement::If(value.to_arena(arena)),
+            Statement::While(ref value)             => ast::Statement::While(value.to_arena(arena)),
+            Statement::Do(ref value)                => ast::Statement::Do(value.to_arena(arena)),
+            Statement::For(ref value)               => ast::Statement::For(value.to_arena(arena)),
+            Statement::ForIn(ref value)             => ast::Statement::ForIn(value.to_arena(arena)),
+            Statement::ForOf(ref value)             => ast::Statement::ForOf(value.to_arena(arena)),
+            Statement::Try(ref value)               => ast::Statement::Try(value.to_arena(arena)),
+            Statement::Block(ref value)             => ast::Statement::Block(value.to_arena(arena)),
+            Statement::Labeled(ref value)           => ast::Statement::Labeled(value.to_arena(arena)),
+            Statement::Function(ref value)          => ast::Statement::Function(value.to_arena(arena)),
+            Statement::Class(ref value)             => ast::Statement::Class(value.to_arena(arena)),
+            Statement::Switch(ref value)            => ast::Statement::Switch(value.to_arena(arena)),
+            Statement::Interface(ref value)         => ast::Statement::Interface(value.to_arena(arena)),
+            Statement::TypeAlias(ref value)         => ast::Statement::TypeAlias(value.to_arena(arena)),
+            Statement::FunctionSignature(ref value) => ast::Statement::FunctionSignature(value.to_arena(arena)),
+            Statement::Declare(ref value)           => ast::Statement::Declare(value.to_arena(arena)),
+            Statement::OpaqueType(ref value)        => ast::Statement::OpaqueType(value.to_arena(arena)),
+            Statement::TypeImport(ref value)        => ast::Statement::TypeImport(value.to_arena(arena)),
+            Statement::Enum(ref value)              => ast::Statement::Enum(value.to_arena(arena)),
+            Statement::Namespace(ref value)         => ast::Statement::Namespace(value.to_arena(arena)),
+            Statement::Export(ref value)            => ast::Statement::Export(value.to_arena(arena)),
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::Label<'ast> {
+    type Owned = Label;
+
+    #[inline]
+    fn to_owned_ast(&self) -> Label {
+        Label(self.0.to_owned())
+    }
+}
+
+impl<'ast> ToArena<'ast> for Label {
+    type Output = ast::Label<'ast>;
+
+    #[inline]
+    fn to_arena(&self, arena: &'ast Arena) -> ast::Label<'ast> {
+        ast::Label(arena.alloc_str(&self.0))
+    }
+}
@@ -0,0 +1,512 @@
+use toolshed::Arena;
+
+use crate::ast::types as ast;
+use crate::owned::{Node, NodeList, Literal, IdentifierNode, PatternList, PropertyKey};
+use crate::owned::{BlockNode, StatementNode, ExpressionNode, Statement, TypeNode, TypeList, ToOwnedAst, ToArena};
+
+pub use crate::ast::types::{Primitive, TypeOperator, Accessibility, Modifiers, TypeImportKind};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum TypeName {
+    Identifier(String),
+    Qualified {
+        left: Node<TypeName>,
+        right: IdentifierNode,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TypeParameter {
+    pub name: IdentifierNode,
+    pub constraint: Option<TypeNode>,
+    pub default: Option<TypeNode>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct FunctionType {
+    pub type_parameters: TypeParameterList,
+    pub params: PatternList,
+    pub return_type: Option<TypeNode>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum TypeMember {
+    Property {
+        readonly: bool,
+        key: Node<PropertyKey>,
+        optional: bool,
+        annotation: Option<TypeNode>,
+    },
+    Method {
+        key: Node<PropertyKey>,
+        optional: bool,
+        signature: Node<FunctionType>,
+    },
+    Index {
+        readonly: bool,
+        parameter: IdentifierNode,
+        key: TypeNode,
+        annotation: TypeNode,
+    },
+    Call {
+        signature: Node<FunctionType>,
+    },
+    Construct {
+        signature: Node<FunctionType>,
+    },
+    Spread {
+        argument: TypeNode,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Type {
+    Any,
+    Unknown,
+    Never,
+    Void,
+    Object,
+    This,
+    Primitive(Primitive),
+    Literal(Literal),
+    Reference {
+        name: Node<TypeName>,
+        arguments: TypeList,
+    },
+    Query {
+        name: Node<TypeName>,
+    },
+    Array {
+        element: TypeNode,
+    },
+    Tuple {
+        elements: TypeList,
+    },
+    Optional {
+        argument: TypeNode,
+    },
+    Rest {
+        argument: TypeNode,
+    },
+    Union {
+        variants: TypeList,
+    },
+    Intersection {
+        variants: TypeList,
+    },
+    Function(FunctionType),
+    Constructor(FunctionType),
+    ObjectLiteral {
+        members: NodeList<TypeMember>,
+        exact: bool,
+    },
+    Mapped {
+        readonly: bool,
+        parameter: IdentifierNode,
+        constraint: TypeNode,
+        optional: bool,
+        annotation: Option<TypeNode>,
+    },
+    Operator {
+        operator: TypeOperator,
+        argument: TypeNode,
+    },
+    IndexedAccess {
+        object: TypeNode,
+        index: TypeNode,
+    },
+    Conditional {
+        check: TypeNode,
+        extends: TypeNode,
+        consequent: TypeNode,
+        alternate: TypeNode,
+    },
+    Infer {
+        parameter: IdentifierNode,
+    },
+    Predicate {
+        parameter: IdentifierNode,
+        annotation: TypeNode,
+    },
+    Nullable {
+        argument: TypeNode,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct InterfaceDeclaration {
+    pub name: IdentifierNode,
+    pub type_parameters: TypeParameterList,
+    pub extends: TypeList,
+    pub body: BlockNode<TypeMember>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TypeAliasDeclaration {
+    pub name: IdentifierNode,
+    pub type_parameters: TypeParameterList,
+    pub value: TypeNode,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct FunctionSignature {
+    pub name: IdentifierNode,
+    pub signature: FunctionType,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct OpaqueTypeDeclaration {
+    pub name: IdentifierNode,
+    pub type_parameters: TypeParameterList,
+    pub supertype: Option<TypeNode>,
+    pub value: Option<TypeNode>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum TypeImportSpecifier {
+    Default(IdentifierNode),
+    Namespace(IdentifierNode),
+    Named {
+        imported: IdentifierNode,
+        local: IdentifierNode,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TypeImportDeclaration {
+    pub kind: TypeImportKind,
+    pub specifiers: NodeList<TypeImportSpecifier>,
+    pub source: Node<String>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct EnumMember {
+    pub name: IdentifierNode,
+    pub value: Option<ExpressionNode>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct EnumDeclaration {
+    pub is_const: bool,
+    pub name: IdentifierNode,
+    pub members: NodeList<EnumMember>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct NamespaceDeclaration {
+    pub name: IdentifierNode,
+    pub body: BlockNode<Statement>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct DeclareStatement {
+    pub body: StatementNode,
+}
+
+pub type TypeParameterList = NodeList<TypeParameter>;
+
+owned_struct! {
+    TypeParameter { name, constraint, default }
+    FunctionType { type_parameters, params, return_type }
+    InterfaceDeclaration { name, type_parameters, extends, body }
+    TypeAliasDeclaration { name, type_parameters, value }
+    FunctionSignature { name, signature }
+    OpaqueTypeDeclaration { name, type_parameters, supertype, value }
+    TypeImportDeclaration { kind, specifiers, source }
+    EnumMember { name, value }
+    EnumDeclaration { is_const, name, members }
+    NamespaceDeclaration { name, body }
+    DeclareStatement { body }
+}
+
+impl<'ast> ToOwnedAst for ast::TypeName<'ast> {
+    type Owned = TypeName;
+
+    #[inline]
+    fn to_owned_ast(&self) -> TypeName {
+        match *self {
+            ast::TypeName::Identifier(value) => TypeName::Identifier(value.to_owned_ast()),
+            ast::TypeName::Qualified { left, right } => TypeName::Qualified {
+                left: left.to_owned_ast(),
+                right: right.to_owned_ast(),
+            },
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for TypeName {
+    type Output = ast::TypeName<'ast>;
+
+    #[inline]
+    fn to_arena(&self, arena: &'ast Arena) -> ast::TypeName<'ast> {
+        match *self {
+            TypeName::Identifier(ref value) => ast::TypeName::Identifier(value.to_arena(arena)),
+            TypeName::Qualified { ref left, ref right } => ast::TypeName::Qualified {
+                left: left.to_arena(arena),
+                right: right.to_arena(arena),
+            },
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::TypeMember<'ast> {
+    type Owned = TypeMember;
+
+    #[inline]
+    fn to_owned_ast(&self) -> TypeMember {
+        match *self {
+            ast::TypeMember::Property { readonly, key, optional, annotation } => TypeMember::Property {
+                readonly,
+                key: key.to_owned_ast(),
+                optional,
+                annotation: annotation.to_owned_ast(),
+            },
+            ast::TypeMember::Method { key, optional, signature } => TypeMember::Method {
+                key: key.to_owned_ast(),
+                optional,
+                signature: signature.to_owned_ast(),
+            },
+            ast::TypeMember::Index { readonly, parameter, key, annotation } => TypeMember::Index {
+                readonly,
+                parameter: parameter.to_owned_ast(),
+                key: key.to_owned_ast(),
+                annotation: annotation.to_owned_ast(),
+            },
+            ast::TypeMember::Call { signature } => TypeMember::Call {
+                signature: signature.to_owned_ast(),
+            },
+            ast::TypeMember::Construct { signature } => TypeMember::Construct {
+                signature: signature.to_owned_ast(),
+            },
+            ast::TypeMember::Spread { argument } => TypeMember::Spread {
+                argument: argument.to_owned_ast(),
+            },
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for TypeMember {
+    type Output = ast::TypeMember<'ast>;
+
+    #[inline]
+    fn to_arena(&self, arena: &'ast Arena) -> ast::TypeMember<'ast> {
+        match *self {
+            TypeMember::Property { readonly, ref key, optional, ref annotation } => ast::TypeMember::Property {
+                readonly,
+                key: key.to_arena(arena),
+                optional,
+                annotation: annotation.to_arena(arena),
+            },
+            TypeMember::Method { ref key, optional, ref signature } => ast::TypeMember::Method {
+                key: key.to_arena(arena),
+                optional,
+                signature: signature.to_arena(arena),
+            },
+            TypeMember::Index { readonly, ref parameter, ref key, ref annotation } => ast::TypeMember::Index {
+                readonly,
+                parameter: parameter.to_arena(arena),
+                key: key.to_arena(arena),
+                annotation: annotation.to_arena(arena),
+            },
+            TypeMember::Call { ref signature } => ast::TypeMember::Call {
+                signature: signature.to_arena(arena),
+            },
+            TypeMember::Construct { ref signature } => ast::TypeMember::Construct {
+                signature: signature.to_arena(arena),
+            },
+            TypeMember::Spread { ref argument } => ast::TypeMember::Spread {
+                argument: argument.to_arena(arena),
+            },
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::Type<'ast> {
+    type Owned = Type;
+
+    fn to_owned_ast(&self) -> Type {
+        match *self {
+            ast::Type::Any                => Type::Any,
+            ast::Type::Unknown            => Type::Unknown,
+            ast::Type::Never              => Type::Never,
+            ast::Type::Void               => Type::Void,
+            ast::Type::Object             => Type::Object,
+            ast::Type::This               => Type::This,
+            ast::Type::Primitive(value)   => Type::Primitive(value),
+            ast::Type::Literal(value)     => Type::Literal(value.to_owned_ast()),
+            ast::Type::Reference { name, arguments } => Type::Reference {
+                name: name.to_owned_ast(),
+                arguments: arguments.to_owned_ast(),
+            },
+            ast::Type::Query { name } => Type::Query {
+                name: name.to_owned_ast(),
+            },
+            ast::Type::Array { element } => Type::Array {
+                element: element.to_owned_ast(),
+            },
+            ast::Type::Tuple { elements } => Type::Tuple {
+                elements: elements.to_owned_ast(),
+            },
+            ast::Type::Optional { argument } => Type::Optional {
+                argument: argument.to_owned_ast(),
+            },
+            ast::Type::Rest { argument } => Type::Rest {
+                argument: argument.to_owned_ast(),
+            },
+            ast::Type::Union { variants } => Type::Union {
+                variants: variants.to_owned_ast(),
+            },
+            ast::Type::Intersection { variants } => Type::Intersection {
+                variants: variants.to_owned_ast(),
+            },
+            ast::Type::Function(value)    => Type::Function(value.to_owned_ast()),
+            ast::Type::Constructor(value) => Type::Constructor(value.to_owned_ast()),
+            ast::Type::ObjectLiteral { members, exact } => Type::ObjectLiteral {
+                members: members.to_owned_ast(),
+                exact,
+            },
+            ast::Type::Mapped { readonly, parameter, constraint, optional, annotation } => Type::Mapped {
+                readonly,
+                parameter: parameter.to_owned_ast(),
+                constraint: constraint.to_owned_ast(),
+                optional,
+                annotation: annotation.to_owned_ast(),
+            },
+            ast::Type::Operator { operator, argument } => Type::Operator {
+                operator,
+                argument: argument.to_owned_ast(),
+            },
+            ast::Type::IndexedAccess { object, index } => Type::IndexedAccess {
+                object: object.to_owned_ast(),
+                index: index.to_owned_ast(),
+            },
+            ast::Type::Conditional { check, extends, consequent, alternate } => Type::Conditional {
+                check: check.to_owned_ast(),
+                extends: extends.to_owned_ast(),
+                consequent: consequent.to_owned_ast(),
+                alternate: alternate.to_owned_ast(),
+            },
+            ast::Type::Infer { parameter } => Type::Infer {
+                parameter: parameter.to_owned_ast(),
+            },
+            ast::Type::Predicate { parameter, annotation } => Type::Predicate {
+                parameter: parameter.to_owned_ast(),
+                annotation: annotation.to_owned_ast(),
+            },
+            ast::Type::Nullable { argument } => Type::Nullable {
+                argument: argument.to_owned_ast(),
+            },
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for Type {
+    type Output = ast::Type<'ast>;
+
+    fn to_arena(&self, arena: &'ast Arena) -> ast::Type<'ast> {
+        match *self {
+            Type::Any                    => ast::Type::Any,
+            Type::Unknown                => ast::Type::Unknown,
+            Type::Never                  => ast::Type::Never,
+            Type::Void                   => ast::Type::Void,
+            Type::Object                 => ast::Type::Object,
+            Type::This                   => ast::Type::This,
+            Type::Primitive(value)       => ast::Type::Primitive(value),
+            Type::Literal(ref value)     => ast::Type::Literal(value.to_arena(arena)),
+            Type::Reference { ref name, ref arguments } => ast::Type::Reference {
+                name: name.to_arena(arena),
+                arguments: arguments.to_arena(arena),
+            },
+            Type::Query { ref name } => ast::Type::Query {
+                name: name.to_arena(arena),
+            },
+            Type::Array { ref element } => ast::Type::Array {
+                element: element.to_arena(arena),
+            },
+            Type::Tuple { ref elements } => ast::Type::Tuple {
+                elements: elements.to_arena(arena),
+            },
+            Type::Optional { ref argument } => ast::Type::Optional {
+                argument: argument.to_arena(arena),
+            },
+            Type::Rest { ref argument } => ast::Type::Rest {
+                argument: argument.to_arena(arena),
+            },
+            Type::Union { ref variants } => ast::Type::Union {
+                variants: variants.to_arena(arena),
+            },
+            Type::Intersection { ref variants } => ast::Type::Intersection {
+                variants: variants.to_arena(arena),
+            },
+            Type::Function(ref value)    => ast::Type::Function(value.to_arena(arena)),
+            Type::Constructor(ref value) => ast::Type::Constructor(value.to_arena(arena)),
+            Type::ObjectLiteral { ref members, exact } => ast::Type::ObjectLiteral {
+                members: members.to_arena(arena),
+                exact,
+            },
+            Type::Mapped { readonly, ref parameter, ref constraint, optional, ref annotation } => ast::Type::Mapped {
+                readonly,
+                parameter: parameter.to_arena(arena),
+                constraint: constraint.to_arena(arena),
+                optional,
+                annotation: annotation.to_arena(arena),
+            },
+            Type::Operator { operator, ref argument } => ast::Type::Operator {
+                operator,
+                argument: argument.to_arena(arena),
+            },
+            Type::IndexedAccess { ref object, ref index } => ast::Type::IndexedAccess {
+                object: object.to_arena(arena),
+                index: index.to_arena(arena),
+            },
+            Type::Conditional { ref check, ref extends, ref consequent, ref alternate } => ast::Type::Conditional {
+                check: check.to_arena(arena),
+                extends: extends.to_arena(arena),
+                consequent: consequent.to_arena(arena),
+                alternate: alternate.to_arena(arena),
+            },
+            Type::Infer { ref parameter } => ast::Type::Infer {
+                parameter: parameter.to_arena(arena),
+            },
+            Type::Predicate { ref parameter, ref annotation } => ast::Type::Predicate {
+                parameter: parameter.to_arena(arena),
+                annotation: annotation.to_arena(arena),
+            },
+            Type::Nullable { ref argument } => ast::Type::Nullable {
+                argument: argument.to_arena(arena),
+            },
+        }
+    }
+}
+
+impl<'ast> ToOwnedAst for ast::TypeImportSpecifier<'ast> {
+    type Owned = TypeImportSpecifier;
+
+    #[inline]
+    fn to_owned_ast(&self) -> TypeImportSpecifier {
+        match *self {
+            ast::TypeImportSpecifier::Default(value)   => TypeImportSpecifier::Default(value.to_owned_ast()),
+            ast::TypeImportSpecifier::Namespace(value) => TypeImportSpecifier::Namespace(value.to_owned_ast()),
+            ast::TypeImportSpecifier::Named { imported, local } => TypeImportSpecifier::Named {
+                imported: imported.to_owned_ast(),
+                local: local.to_owned_ast(),
+            },
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for TypeImportSpecifier {
+    type Output = ast::TypeImportSpecifier<'ast>;
+
+    #[inline]
+    fn to_arena(&self, arena: &'ast Arena) -> ast::TypeImportSpecifier<'ast> {
+        match *self {
+            TypeImportSpecifier::Default(ref value)   => ast::TypeImportSpecifier::Default(value.to_arena(arena)),
+            TypeImportSpecifier::Namespace(ref value) => ast::TypeImportSpecifier::Namespace(value.to_arena(arena)),
+            TypeImportSpecifier::Named { ref imported, ref local } => ast::TypeImportSpecifier::Named {
+                imported: imported.to_arena(arena),
+                local: local.to_arena(arena),
+            },
+        }
+    }
+}
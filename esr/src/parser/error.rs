@@ -1,7 +1,7 @@
 use crate::error::Error;
 
-use crate::ast::{Node, Loc, NodeList, Pattern};
-use crate::ast::{Name, ClassMember, Property, PropertyKey, MandatoryName, Block};
+use crate::ast::{Node, Loc, NodeList, Pattern, Expression, Statement, SYNTHETIC};
+use crate::ast::{Name, ClassMember, PropertyKey, MandatoryName, Block};
 use crate::parser::Parser;
 
 pub trait Handle<'ast> {
@@ -24,21 +24,11 @@ impl<'ast> ToError for MandatoryName<'ast> {
     }
 }
 
-impl<'ast> ToError for Node<'ast, Property<'ast>> {
-    fn to_error() -> Self {
-        Node::new(&Loc {
-            start: 0,
-            end: 0,
-            item: Property::Shorthand("")
-        })
-    }
-}
-
 impl<'ast> ToError for Node<'ast, ClassMember<'ast>> {
     fn to_error() -> Self {
         Node::new(&Loc {
-            start: 0,
-            end: 0,
+            start: SYNTHETIC,
+            end: SYNTHETIC,
             item: ClassMember::Error,
         })
     }
@@ -47,8 +37,8 @@ impl<'ast> ToError for Node<'ast, ClassMember<'ast>> {
 impl<'ast, T: 'ast + ToError> ToError for Loc<T> {
     fn to_error() -> Self {
         Loc {
-            start: 0,
-            end: 0,
+            start: SYNTHETIC,
+            end: SYNTHETIC,
             item: T::to_error()
         }
     }
@@ -63,8 +53,8 @@ impl<'ast, T: 'ast + Copy> ToError for NodeList<'ast, T> {
 impl<'ast> ToError for Node<'ast, &'ast str> {
     fn to_error() -> Self {
         Node::new(&Loc {
-            start: 0,
-            end: 0,
+            start: SYNTHETIC,
+            end: SYNTHETIC,
             item: ""
         })
     }
@@ -82,19 +72,41 @@ impl<'ast> ToError for Node<'ast, Pattern<'ast>> {
     #[inline]
     fn to_error() -> Self {
         Node::new(&Loc {
-            start: 0,
-            end: 0,
+            start: SYNTHETIC,
+            end: SYNTHETIC,
             item: Pattern::Void
         })
     }
 }
 
+impl<'ast> ToError for Node<'ast, Expression<'ast>> {
+    #[inline]
+    fn to_error() -> Self {
+        Node::new(&Loc {
+            start: SYNTHETIC,
+            end: SYNTHETIC,
+            item: Expression::Void
+        })
+    }
+}
+
+impl<'ast> ToError for Node<'ast, Statement<'ast>> {
+    #[inline]
+    fn to_error() -> Self {
+        Node::new(&Loc {
+            start: SYNTHETIC,
+            end: SYNTHETIC,
+            item: Statement::Empty
+        })
+    }
+}
+
 impl<'ast> ToError for Node<'ast, PropertyKey<'ast>> {
     #[inline]
     fn to_error() -> Self {
         Node::new(&Loc {
-            start: 0,
-            end: 0,
+            start: SYNTHETIC,
+            end: SYNTHETIC,
             item: PropertyKey::Literal("")
         })
     }
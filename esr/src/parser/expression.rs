@@ -1,14 +1,17 @@
 use toolshed::list::ListBuilder;
 use crate::parser::{Parser, Parse, BindingPower, ANY, B0, B15};
 use crate::lexer::Token::*;
-use crate::ast::{Node, NodeList, Expression, ExpressionNode, IdentifierNode, ExpressionList};
+use crate::ast::{Node, Loc, NodeList, Expression, ExpressionNode, IdentifierNode, ExpressionList};
 use crate::ast::{Property, PropertyKey, OperatorKind, Literal, Function, Class, StatementNode};
+use crate::ast::{FunctionType, DecoratorList};
 use crate::ast::expression::*;
+use crate::error::{Error, ErrorKind};
+use crate::regex;
 
 
 type ExpressionHandler = for<'ast> fn(&mut Parser<'ast>) -> ExpressionNode<'ast>;
 
-pub type Context = &'static [ExpressionHandler; 108];
+pub type Context = &'static [ExpressionHandler; 109];
 
 static DEF_CONTEXT: Context = &[
     ____, ____, ____, ____, PRN,  ____, ARR,  ____, OBJ,  ____, ____, NEW,
@@ -17,7 +20,7 @@ static DEF_CONTEXT: Context = &[
     OP,   OP,   OP,   OP,   OP,   OP,   OP,   ____, REG,  ____, ____, OP,
 //  ++    --    !     ~     TYPOF VOID  DELET *     /     %     **    +
 
-    OP,   ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
+    OP,   ____, ____, ____, JSX,  ____, ____, ____, ____, ____, ____, ____,
 //  -     <<    >>    >>>   <     <=    >     >=    INSOF IN    ===   !==
 
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
@@ -29,7 +32,7 @@ static DEF_CONTEXT: Context = &[
     ____, ____, ____, ____, ____, ____, ____, CLAS, ____, ____, ____, ____,
 //  CONST BREAK DO    CASE  ELSE  CATCH EXPRT CLASS EXTND RET   WHILE FINLY
 
-    ____, ____, ____, ____, ____, ____, ____, FUNC, THIS, ____, ____, ____,
+    SUPR, ____, ____, ____, ____, ____, ____, FUNC, THIS, ____, ____, ____,
 //  SUPER WITH  CONT  FOR   SWTCH YIELD DBGGR FUNCT THIS  DEFLT IF    THROW
 
     ____, ____, ____, TRUE, FALS, NULL, UNDE, STR,  NUM,  BIN,  ____, ____,
@@ -37,32 +40,37 @@ static DEF_CONTEXT: Context = &[
 
     ____, ____, ____, ____, ____, ____, IDEN, ____, TPLE, TPLS, ____, ____,
 //  IMPL  PCKG  PROT  IFACE PRIV  PUBLI IDENT ACCSS TPL_O TPL_C ERR_T ERR_E
+
+    DECO,
+//  @
 ];
 
 // Adds handlers for VoidExpression and SpreadExpression
 pub static ARRAY_CONTEXT: Context = &[
     ____, ____, ____, VOID, PRN,  ____, ARR,  VOID, OBJ,  ____, ____, NEW,
     OP,   OP,   OP,   OP,   OP,   OP,   OP,   ____, REG,  ____, ____, OP,
-    OP,   ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
+    OP,   ____, ____, ____, JSX,  ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, SPRD, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, CLAS, ____, ____, ____, ____,
-    ____, ____, ____, ____, ____, ____, ____, FUNC, THIS, ____, ____, ____,
+    SUPR, ____, ____, ____, ____, ____, ____, FUNC, THIS, ____, ____, ____,
     ____, ____, ____, TRUE, FALS, NULL, UNDE, STR,  NUM,  BIN,  ____, ____,
     ____, ____, ____, ____, ____, ____, IDEN, ____, TPLE, TPLS, ____, ____,
+    DECO,
 ];
 
 // Adds handler for SpreadExpression
 pub static CALL_CONTEXT: Context = &[
     ____, ____, ____, ____, PRN,  ____, ARR,  ____, OBJ,  ____, ____, NEW,
     OP,   OP,   OP,   OP,   OP,   OP,   OP,   ____, REG,  ____, ____, OP,
-    OP,   ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
+    OP,   ____, ____, ____, JSX,  ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, SPRD, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, CLAS, ____, ____, ____, ____,
-    ____, ____, ____, ____, ____, ____, ____, FUNC, THIS, ____, ____, ____,
+    SUPR, ____, ____, ____, ____, ____, ____, FUNC, THIS, ____, ____, ____,
     ____, ____, ____, TRUE, FALS, NULL, UNDE, STR,  NUM,  BIN,  ____, ____,
     ____, ____, ____, ____, ____, ____, IDEN, ____, TPLE, TPLS, ____, ____,
+    DECO,
 ];
 
 macro_rules! create_handlers {
@@ -108,10 +116,22 @@ create_handlers! {
 
     const CLAS = |par| par.class_expression();
 
+    const DECO = |par| {
+        let start = par.lexer.start();
+        let decorators = par.decorators();
+
+        par.decorated_class_expression(start, decorators)
+    };
+
     const FUNC = |par| par.function_expression();
 
     const IDEN = |par| {
         let ident = par.lexer.token_as_str();
+
+        if par.await_allowed && ident == "await" {
+            return par.await_expression();
+        }
+
         let expr = par.alloc_in_loc(ident);
 
         par.lexer.consume();
@@ -132,12 +152,23 @@ create_handlers! {
         expr
     };
 
+    pub const SUPR = |par| {
+        let expr = par.alloc_in_loc(SuperExpression);
+        par.lexer.consume();
+
+        // `super` only stands for a callee, or the object of a member
+        match par.lexer.token {
+            ParenOpen | Accessor | BracketOpen => expr,
+            _                                  => par.error(),
+        }
+    };
+
     pub const OP = |par| {
         let start = par.lexer.start();
         let op = OperatorKind::from_token(par.lexer.token).expect("Must be a prefix operator");
         par.lexer.consume();
         let expression = par.prefix_expression(op);
-        let end = par.lexer.end();
+        let end = expression.operand.end;
         par.alloc_at_loc(start, end, expression)
     };
 
@@ -154,7 +185,7 @@ create_handlers! {
             par.alloc_at_loc(start, end, expression)
         } else {
             let expression = par.prefix_expression(OperatorKind::New);
-            let end = par.lexer.end();
+            let end = expression.operand.end;
             par.alloc_at_loc(start, end, expression)
         }
     };
@@ -232,6 +263,12 @@ create_handlers! {
     };
 
     pub const TPLE = |par| par.template_expression();
+
+    pub const JSX = |par| match (par.types, par.jsx) {
+        (true, false) => par.type_assertion_expression(),
+        (true, true) if par.generic_arrow_ahead() => par.type_assertion_expression(),
+        _ => par.jsx_expression(),
+    };
 }
 
 impl<'ast> Parser<'ast> {
@@ -250,9 +287,11 @@ impl<'ast> Parser<'ast> {
     where
         B: BindingPower
     {
-        let left = self.bound_expression();
+        self.nested(|par| {
+            let left = par.bound_expression();
 
-        self.nested_expression::<B>(left)
+            par.nested_expression::<B>(left)
+        })
     }
 
     #[inline]
@@ -260,26 +299,100 @@ impl<'ast> Parser<'ast> {
     where
         B: BindingPower
     {
-        let left = self.context_bound_expression(context);
+        self.nested(|par| {
+            let left = par.context_bound_expression(context);
 
-        self.nested_expression::<B>(left)
+            par.nested_expression::<B>(left)
+        })
     }
 
     #[inline]
     pub fn arrow_function_expression(&mut self, params: ExpressionList<'ast>) -> ArrowExpression<'ast> {
         let params = self.params_from_expressions(params);
 
-        let body = match self.lexer.token {
-            BraceOpen => ArrowBody::Block(self.unchecked_block()),
-            _         => ArrowBody::Expression(self.expression::<B0>()),
-        };
+        self.arrow_function_body(FunctionType {
+            type_parameters: NodeList::empty(),
+            params,
+            return_type: None,
+        })
+    }
+
+    /// Parse the body of an arrow function following the `=>`.
+    #[inline]
+    pub fn arrow_function_body(&mut self, signature: FunctionType<'ast>) -> ArrowExpression<'ast> {
+        let body = self.function_body(|par| match par.lexer.token {
+            BraceOpen => ArrowBody::Block(par.unchecked_block()),
+            _         => ArrowBody::Expression(par.expression::<B0>()),
+        });
 
         ArrowExpression {
-            params,
+            type_parameters: signature.type_parameters,
+            params: signature.params,
+            return_type: signature.return_type,
             body,
         }
     }
 
+    /// Parse an arrow function with type annotations, if there is one
+    /// at the current token.
+    #[inline]
+    fn typed_arrow_function_expression(&mut self) -> Option<ExpressionNode<'ast>> {
+        let signature = self.speculate(Parser::arrow_signature)?;
+        let expression = self.arrow_function_body(signature.item);
+
+        let end = match expression.body {
+            ArrowBody::Block(block)           => block.end,
+            ArrowBody::Expression(expression) => expression.end,
+        };
+
+        Some(self.alloc_at_loc(signature.start, end, expression))
+    }
+
+    /// With JSX on, `<T>` opens an element. Only `<T,>` and `<T extends U>`
+    /// start a generic arrow function, as in TSX.
+    #[inline]
+    fn generic_arrow_ahead(&mut self) -> bool {
+        self.lookahead(|par| {
+            par.lexer.consume();
+
+            if par.lexer.token != Identifier {
+                return false;
+            }
+
+            par.lexer.consume();
+
+            matches!(par.lexer.token, Comma | Extends)
+        })
+    }
+
+    /// Parse a `<Type>expression` type assertion, or a generic arrow
+    /// function, in place of an expression.
+    #[inline]
+    pub fn type_assertion_expression(&mut self) -> ExpressionNode<'ast> {
+        if let Some(arrow) = self.typed_arrow_function_expression() {
+            return arrow;
+        }
+
+        // Flow has no angle bracket assertions, only generic arrows
+        if !self.typescript {
+            return self.error();
+        }
+
+        let start = self.lexer.start_then_consume();
+        let annotation = self.type_node();
+
+        self.lexer.read_greater();
+
+        expect!(self, OperatorGreater);
+
+        let expression = self.expression::<B15>();
+
+        self.alloc_at_loc(start, expression.end, TypeAssertion {
+            annotation,
+            expression,
+        })
+    }
+
     #[inline]
     pub fn call_arguments(&mut self) -> ExpressionList<'ast> {
         if self.lexer.token == ParenClose {
@@ -315,25 +428,66 @@ impl<'ast> Parser<'ast> {
 
     #[inline]
     pub fn paren_expression(&mut self) -> ExpressionNode<'ast> {
+        if self.types {
+            if let Some(arrow) = self.typed_arrow_function_expression() {
+                return arrow;
+            }
+        }
+
         let start = self.lexer.start_then_consume();
         match self.lexer.token {
             ParenClose => {
                 self.lexer.consume();
                 expect!(self, OperatorFatArrow);
                 let expression = self.arrow_function_expression(NodeList::empty());
-                let end = self.lexer.end();
+                let end = match expression.body {
+                    ArrowBody::Block(block)           => block.end,
+                    ArrowBody::Expression(expression) => expression.end,
+                };
                 self.alloc_at_loc(start, end, expression)
             },
             _ => {
                 let expression = self.expression::<ANY>();
 
+                if self.flow && self.lexer.token == Colon {
+                    self.lexer.consume();
+
+                    let annotation = self.type_node();
+                    let end = self.lexer.end();
+
+                    expect!(self, ParenClose);
+
+                    return self.alloc_at_loc(start, end, TypeCastExpression {
+                        expression,
+                        annotation,
+                    });
+                }
+
+                if !self.parenthesized {
+                    expect!(self, ParenClose);
+
+                    return expression;
+                }
+
+                let end = self.lexer.end();
+
                 expect!(self, ParenClose);
 
-                expression
+                self.alloc_at_loc(start, end, ParenthesizedExpression {
+                    expression
+                })
             }
         }
     }
 
+    #[inline]
+    pub fn await_expression(&mut self) -> ExpressionNode<'ast> {
+        let start = self.lexer.start_then_consume();
+        let argument = self.expression::<B15>();
+
+        self.alloc_at_loc(start, argument.end, AwaitExpression { argument })
+    }
+
     #[inline]
     pub fn prefix_expression(&mut self, operator: OperatorKind) -> PrefixExpression<'ast> {
         let operand = self.expression::<B15>();
@@ -412,7 +566,11 @@ impl<'ast> Parser<'ast> {
                 match self.lexer.token {
                     Colon | ParenOpen => self.alloc_at_loc(start, end, PropertyKey::Literal(label)),
 
-                    _ => return self.alloc_at_loc(start, end, Property::Shorthand(label)),
+                    _ => {
+                        let label = self.alloc_at_loc(start, end, label);
+
+                        return self.alloc_at_loc(start, end, Property::Shorthand(label));
+                    },
                 }
             },
             OperatorSpread => {
@@ -447,7 +605,7 @@ impl<'ast> Parser<'ast> {
 
                 self.alloc_at_loc(start, end, PropertyKey::Computed(expression))
             },
-            _ => return self.error(),
+            _ => return self.property_error(),
         };
 
         match self.lexer.token {
@@ -469,10 +627,18 @@ impl<'ast> Parser<'ast> {
                     value,
                 })
             },
-            _ => self.error()
+            _ => self.property_error()
         }
     }
 
+    /// Report an error, producing a synthetic property in place of the
+    /// invalid one.
+    fn property_error(&mut self) -> Node<'ast, Property<'ast>> {
+        let label = self.error();
+
+        self.alloc(Loc::synthetic(Property::Shorthand(label)))
+    }
+
     #[inline]
     pub fn array_expression(&mut self) -> ExpressionNode<'ast> {
         let start = self.lexer.start_then_consume();
@@ -523,11 +689,29 @@ impl<'ast> Parser<'ast> {
         let value = self.lexer.read_regular_expression();
         let end = self.lexer.end();
 
+        if self.lexer.token == LiteralRegEx {
+            self.validate_regular_expression(start as usize, value);
+        }
+
         expect!(self, LiteralRegEx);
 
         self.alloc_at_loc(start, end, Literal::RegEx(value))
     }
 
+    fn validate_regular_expression(&mut self, start: usize, value: &'ast str) {
+        if let Err(errors) = regex::parse(value, self.arena) {
+            for err in errors {
+                self.errors.push(Error {
+                    kind: ErrorKind::InvalidRegExp(err.kind),
+                    token: LiteralRegEx,
+                    raw: value[err.start..err.end].to_owned().into_boxed_str(),
+                    start: start + err.start,
+                    end: start + err.end,
+                });
+            }
+        }
+    }
+
     #[inline]
     pub fn template_string<T>(&mut self) -> Node<'ast, T>
     where
@@ -627,10 +811,21 @@ impl<'ast> Parser<'ast> {
 
     #[inline]
     pub fn class_expression(&mut self) -> ExpressionNode<'ast> {
-        let start = self.lexer.start_then_consume();
+        let start = self.lexer.start();
+
+        self.decorated_class_expression(start, NodeList::empty())
+    }
+
+    #[inline]
+    pub fn decorated_class_expression(&mut self, start: u32, decorators: DecoratorList<'ast>) -> ExpressionNode<'ast> {
+        expect!(self, Class);
+
         let class = Class::parse(self);
 
-        self.alloc_at_loc(start, class.body.end, class)
+        self.alloc_at_loc(start, class.body.end, Class {
+            decorators,
+            ..class
+        })
     }
 }
 
@@ -640,7 +835,7 @@ mod test {
     use crate::ast::{OperatorKind, Literal, Statement, Function, Pattern, Class};
     use crate::ast::expression::*;
     use crate::ast::statement::*;
-    use crate::parser::parse;
+    use crate::parser::{parse, parse_with_options, ParseOptions};
     use crate::parser::mock::Mock;
 
     #[test]
@@ -764,6 +959,43 @@ mod test {
         assert_expr!(src, expected);
     }
 
+    #[test]
+    fn parenthesized_expression() {
+        let options = ParseOptions { parenthesized: true, ..ParseOptions::default() };
+        let module = parse_with_options("(a + (b));", options).unwrap();
+        let mock = Mock::new();
+
+        let expected = ParenthesizedExpression {
+            expression: mock.ptr(BinaryExpression {
+                operator: OperatorKind::Addition,
+                left: mock.ptr("a"),
+                right: mock.ptr(ParenthesizedExpression {
+                    expression: mock.ptr("b"),
+                }),
+            }),
+        };
+
+        match module.body().only_element().map(|statement| statement.item) {
+            Some(Statement::Expression(expression)) => {
+                assert_eq!(expression.item, Expression::from(expected));
+                assert_eq!((expression.start, expression.end), (0, 9));
+            },
+            _ => panic!("Statement isn't an expression!"),
+        }
+    }
+
+    #[test]
+    fn parenthesized_expression_targets() {
+        let options = ParseOptions { parenthesized: true, ..ParseOptions::default() };
+
+        assert!(parse_with_options("(a, b) => a;", options).is_ok());
+        assert!(parse_with_options("(a) => a;", options).is_ok());
+        assert!(parse_with_options("(a) = 1; (a.b)++; ((a[b])) += 1;", options).is_ok());
+        assert!(parse_with_options("([a]) = 1;", options).is_err());
+        assert!(parse_with_options("(a + b) = 1;", options).is_err());
+        assert!(parse_with_options("((a)) => a;", options).is_err());
+    }
+
     #[test]
     fn conditional_expression() {
         let src = "true ? foo : bar";
@@ -939,6 +1171,22 @@ mod test {
         assert!(parse("new.callee").is_err());
     }
 
+    #[test]
+    fn super_expression() {
+        let mock = Mock::new();
+
+        assert_expr!("super(a)", CallExpression {
+            callee: mock.ptr(SuperExpression),
+            arguments: mock.list(["a"]),
+        });
+        assert_expr!("super.a", MemberExpression {
+            object: mock.ptr(SuperExpression),
+            property: mock.ptr("a"),
+        });
+        assert!(parse("super;").is_err());
+        assert!(parse("a = super + 1;").is_err());
+    }
+
     #[test]
     fn regular_expression() {
         let src = r#"/^[A-Z]+\/[\d]+/g"#;
@@ -948,6 +1196,25 @@ mod test {
         assert_expr!(src, expected);
     }
 
+    #[test]
+    fn regular_expression_flags() {
+        assert_expr!("/a/dgimsy", Literal::RegEx("/a/dgimsy"));
+        assert_expr!("/[\\p{L}--[a-z]]/v", Literal::RegEx("/[\\p{L}--[a-z]]/v"));
+    }
+
+    #[test]
+    fn invalid_regular_expression() {
+        let errors = match parse("foo(/a{2,1}|b/gx);") {
+            Ok(_)       => Vec::new(),
+            Err(errors) => errors.into_iter().map(|err| (err.kind, err.start, err.end)).collect(),
+        };
+
+        assert_eq!(errors, [
+            (ErrorKind::InvalidRegExp(regex::ErrorKind::InvalidQuantifier), 6, 11),
+            (ErrorKind::InvalidRegExp(regex::ErrorKind::InvalidFlag), 15, 16),
+        ]);
+    }
+
     #[test]
     fn array_expression() {
         let src = "[0, 1, 2]";
@@ -1031,6 +1298,8 @@ mod test {
         let mock = Mock::new();
 
         let expected = Function {
+            type_parameters: NodeList::empty(),
+            return_type: None,
             name: None.into(),
             generator: false,
             params: NodeList::empty(),
@@ -1046,6 +1315,8 @@ mod test {
         let mock = Mock::new();
 
         let expected = Function {
+            type_parameters: NodeList::empty(),
+            return_type: None,
             name: mock.name("foo"),
             generator: false,
             params: NodeList::empty(),
@@ -1061,6 +1332,8 @@ mod test {
         let mock = Mock::new();
 
         let expected = ArrowExpression {
+            type_parameters: NodeList::empty(),
+            return_type: None,
             params: NodeList::empty(),
             body: ArrowBody::Expression(mock.ptr("bar")),
         };
@@ -1073,8 +1346,10 @@ mod test {
         let mock = Mock::new();
 
         let expected = ArrowExpression {
+            type_parameters: NodeList::empty(),
+            return_type: None,
             params: mock.list([
-                Pattern::Identifier("n")
+                Pattern::Identifier(mock.ptr("n"))
             ]),
 
             body: ArrowBody::Expression(mock.ptr(BinaryExpression {
@@ -1093,10 +1368,12 @@ mod test {
         let mock = Mock::new();
 
         let expected = ArrowExpression {
+            type_parameters: NodeList::empty(),
+            return_type: None,
             params: mock.list([
-                Pattern::Identifier("a"),
-                Pattern::Identifier("b"),
-                Pattern::Identifier("c")
+                Pattern::Identifier(mock.ptr("a")),
+                Pattern::Identifier(mock.ptr("b")),
+                Pattern::Identifier(mock.ptr("c"))
             ]),
             body: ArrowBody::Expression(mock.ptr("bar"))
         };
@@ -1114,11 +1391,13 @@ mod test {
         let mock = Mock::new();
 
         let expected = ArrowExpression {
+            type_parameters: NodeList::empty(),
+            return_type: None,
             params: mock.list([
-                Pattern::Identifier("a"),
-                Pattern::Identifier("b"),
+                Pattern::Identifier(mock.ptr("a")),
+                Pattern::Identifier(mock.ptr("b")),
                 Pattern::AssignmentPattern {
-                    left: mock.ptr(Pattern::Identifier("c")),
+                    left: mock.ptr(Pattern::Identifier(mock.ptr("c"))),
                     right: mock.number("2")
                 }
             ]),
@@ -1133,6 +1412,10 @@ mod test {
         let mock = Mock::new();
 
         let expected = Class {
+            decorators: NodeList::empty(),
+            type_parameters: NodeList::empty(),
+            super_type_arguments: NodeList::empty(),
+            implements: NodeList::empty(),
             name: None.into(),
             extends: None,
             body: mock.empty_block()
@@ -1147,6 +1430,10 @@ mod test {
         let mock = Mock::new();
 
         let expected = Class {
+            decorators: NodeList::empty(),
+            type_parameters: NodeList::empty(),
+            super_type_arguments: NodeList::empty(),
+            implements: NodeList::empty(),
             name: mock.name("Foo"),
             extends: None,
             body: mock.empty_block()
@@ -1161,6 +1448,10 @@ mod test {
         let mock = Mock::new();
 
         let expected = Class {
+            decorators: NodeList::empty(),
+            type_parameters: NodeList::empty(),
+            super_type_arguments: NodeList::empty(),
+            implements: NodeList::empty(),
             name: mock.name("Foo"),
             extends: Some(mock.ptr("Bar")),
             body: mock.empty_block()
@@ -1191,6 +1482,32 @@ mod test {
         assert_expr!(src, expected);
     }
 
+    #[test]
+    fn exponent_is_right_associative() {
+        let src = "a * b ** c ** d * e";
+        let mock = Mock::new();
+
+        let expected = BinaryExpression {
+            operator: OperatorKind::Multiplication,
+            left: mock.ptr(BinaryExpression {
+                operator: OperatorKind::Multiplication,
+                left: mock.ptr("a"),
+                right: mock.ptr(BinaryExpression {
+                    operator: OperatorKind::Exponent,
+                    left: mock.ptr("b"),
+                    right: mock.ptr(BinaryExpression {
+                        operator: OperatorKind::Exponent,
+                        left: mock.ptr("c"),
+                        right: mock.ptr("d"),
+                    }),
+                }),
+            }),
+            right: mock.ptr("e"),
+        };
+
+        assert_expr!(src, expected);
+    }
+
     #[test]
     fn arrow_function_in_sequence() {
         let src = "(() => {}, foo)";
@@ -1199,6 +1516,8 @@ mod test {
         let expected = SequenceExpression {
             body: mock.list([
                 Expression::Arrow(ArrowExpression {
+                    type_parameters: NodeList::empty(),
+                    return_type: None,
                     params: NodeList::empty(),
                     body: ArrowBody::Block(mock.ptr(BlockStatement {
                         body: NodeList::empty()
@@ -1255,6 +1574,7 @@ mod test {
     fn assignment_to_lvalue() {
         assert!(parse("(x++)++").is_err());
         assert!(parse("x+++++y").is_err());
+        assert!(parse("foo[bar] = 1; foo[0]++").is_ok());
     }
 
     #[test]
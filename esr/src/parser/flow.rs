@@ -0,0 +1,570 @@
+use toolshed::list::ListBuilder;
+use crate::parser::{Parser, Parse, ANY};
+use crate::error::ErrorKind;
+use crate::lexer::Token::*;
+use crate::ast::{Node, NodeList, StatementNode, TypeNode, TypeMember, MandatoryName};
+use crate::ast::types::*;
+
+/// Check if any of the comments leading the source contains the `@flow`
+/// pragma. Comments after the first token are not considered.
+pub fn has_flow_pragma(source: &str) -> bool {
+    let mut rest = source.trim_start();
+
+    loop {
+        let comment = if rest.starts_with("//") {
+            let end = rest.find('\n').unwrap_or(rest.len());
+            let (comment, tail) = rest.split_at(end);
+
+            rest = tail;
+            comment
+        } else if rest.starts_with("/*") {
+            let end = rest[2..].find("*/").map(|end| end + 4).unwrap_or(rest.len());
+            let (comment, tail) = rest.split_at(end);
+
+            rest = tail;
+            comment
+        } else {
+            return false;
+        };
+
+        let pragma = comment.match_indices("@flow").any(|(index, pragma)| {
+            !comment[index + pragma.len()..].starts_with(|ch: char| ch.is_alphanumeric() || ch == '_')
+        });
+
+        if pragma {
+            return true;
+        }
+
+        rest = rest.trim_start();
+    }
+}
+
+impl<'ast> Parser<'ast> {
+    /// Flow maybe type, `?T`. Binds looser than array types, so that
+    /// `?T[]` is a maybe array.
+    #[inline]
+    pub fn nullable_type(&mut self) -> TypeNode<'ast> {
+        let start = self.lexer.start_then_consume();
+        let argument = self.nested(Parser::type_operator);
+
+        self.alloc_at_loc(start, argument.end, Type::Nullable { argument })
+    }
+
+    /// Flow exact object type, `{| a: number |}`.
+    #[inline]
+    pub fn exact_object_type(&mut self) -> TypeNode<'ast> {
+        self.nested(|par| {
+            let start = par.lexer.start_then_consume();
+
+            // `{||}` is lexed as a logical or
+            if par.lexer.token == OperatorLogicalOr {
+                par.lexer.consume();
+
+                let end = par.lexer.end();
+
+                expect!(par, BraceClose);
+
+                return par.alloc_at_loc(start, end, Type::ObjectLiteral {
+                    members: NodeList::empty(),
+                    exact: true,
+                });
+            }
+
+            par.lexer.consume();
+
+            let members = match par.lexer.token {
+                OperatorBitwiseOr => NodeList::empty(),
+                _ => {
+                    let builder = ListBuilder::new(par.arena, TypeMember::parse(par));
+
+                    while par.lexer.token != OperatorBitwiseOr && par.lexer.token != EndOfProgram {
+                        builder.push(par.arena, TypeMember::parse(par));
+                    }
+
+                    builder.as_list()
+                }
+            };
+
+            expect!(par, OperatorBitwiseOr);
+
+            let end = par.lexer.end();
+
+            expect!(par, BraceClose);
+
+            par.alloc_at_loc(start, end, Type::ObjectLiteral {
+                members,
+                exact: true,
+            })
+        })
+    }
+
+    /// Parse the rest of `opaque type Foo: Super = Bar;` after the
+    /// `opaque` keyword.
+    #[inline]
+    pub fn opaque_type_statement(&mut self, start: u32) -> StatementNode<'ast> {
+        if self.lexer.token_as_str() != "type" {
+            return self.error();
+        }
+
+        self.lexer.consume();
+
+        let MandatoryName(name) = MandatoryName::parse(self);
+        let type_parameters = self.type_parameters();
+        let mut end = name.end;
+
+        let supertype = match self.lexer.token {
+            Colon => {
+                self.lexer.consume();
+
+                let supertype = self.type_node();
+
+                end = supertype.end;
+                Some(supertype)
+            },
+            _ => None,
+        };
+
+        let value = match self.lexer.token {
+            OperatorAssign => {
+                self.lexer.consume();
+
+                let value = self.type_node();
+
+                end = value.end;
+                Some(value)
+            },
+            _ => None,
+        };
+
+        self.expect_semicolon();
+
+        self.alloc_at_loc(start, end, OpaqueTypeDeclaration {
+            name,
+            type_parameters,
+            supertype,
+            value,
+        })
+    }
+
+    /// Flow `import type` and `import typeof` declarations. Regular
+    /// imports are not supported.
+    #[inline]
+    pub fn type_import_statement(&mut self) -> StatementNode<'ast> {
+        let start = self.lexer.start_then_consume();
+
+        let kind = match self.lexer.token {
+            OperatorTypeof => TypeImportKind::Typeof,
+            Identifier if self.lexer.token_as_str() == "type" => TypeImportKind::Type,
+            _ => return self.error(),
+        };
+
+        self.lexer.consume();
+
+        let specifiers = self.type_import_specifiers();
+
+        if self.lexer.token != Identifier || self.lexer.token_as_str() != "from" {
+            return self.error();
+        }
+
+        self.lexer.consume();
+
+        let source = match self.lexer.token {
+            LiteralString => {
+                let source = self.lexer.token_as_str();
+                let source = self.alloc_in_loc(source);
+
+                self.lexer.consume();
+                source
+            },
+            _ => return self.error(),
+        };
+
+        self.expect_semicolon();
+
+        self.alloc_at_loc(start, source.end, TypeImportDeclaration {
+            kind,
+            specifiers,
+            source,
+        })
+    }
+
+    /// Specifiers of a type import: a default import, optionally followed
+    /// by either a namespace import or a list of named imports.
+    #[inline]
+    fn type_import_specifiers(&mut self) -> NodeList<'ast, TypeImportSpecifier<'ast>> {
+        let mut specifiers = Vec::new();
+
+        if self.lexer.token == Identifier {
+            let local = self.identifier();
+
+            specifiers.push(self.alloc_at_loc(local.start, local.end, TypeImportSpecifier::Default(local)));
+
+            if self.lexer.token != Comma {
+                return NodeList::from_iter(self.arena, specifiers);
+            }
+
+            self.lexer.consume();
+        }
+
+        match self.lexer.token {
+            OperatorMultiplication => {
+                let start = self.lexer.start_then_consume();
+
+                if self.lexer.token != Identifier || self.lexer.token_as_str() != "as" {
+                    return self.error();
+                }
+
+                self.lexer.consume();
+
+                let local = self.identifier();
+
+                specifiers.push(self.alloc_at_loc(start, local.end, TypeImportSpecifier::Namespace(local)));
+            },
+            BraceOpen => {
+                self.lexer.consume();
+
+                while self.lexer.token != BraceClose {
+                    specifiers.push(self.named_type_import_specifier());
+
+                    match self.lexer.token {
+                        Comma      => self.lexer.consume(),
+                        BraceClose => break,
+                        _          => return self.error(),
+                    }
+                }
+
+                self.lexer.consume();
+            },
+            _ => return self.error(),
+        }
+
+        NodeList::from_iter(self.arena, specifiers)
+    }
+
+    #[inline]
+    fn named_type_import_specifier(&mut self) -> Node<'ast, TypeImportSpecifier<'ast>> {
+        let imported = self.identifier();
+
+        let local = match self.lexer.token {
+            Identifier if self.lexer.token_as_str() == "as" => {
+                self.lexer.consume();
+                self.identifier()
+            },
+            _ => imported,
+        };
+
+        self.alloc_at_loc(imported.start, local.end, TypeImportSpecifier::Named {
+            imported,
+            local,
+        })
+    }
+
+    /// Reject a `%checks` predicate following the return type of a
+    /// function, `function f(x): boolean %checks { ... }`. Flow has
+    /// deprecated them in favour of type guards, `x is T`, so they are
+    /// reported as `ErrorKind::UnsupportedSyntax` rather than parsed.
+    pub fn checks_predicate(&mut self) {
+        let start = self.lexer.start_then_consume();
+
+        if self.lexer.token != Identifier || self.lexer.token_as_str() != "checks" {
+            return self.error();
+        }
+
+        let mut end = self.lexer.end_then_consume();
+
+        // The predicate of a declared function, `%checks(typeof x === "string")`
+        if self.lexer.token == ParenOpen {
+            self.lexer.consume();
+            self.expression::<ANY>();
+            end = self.lexer.end();
+
+            expect!(self, ParenClose);
+        }
+
+        self.keyword_error(ErrorKind::UnsupportedSyntax, OperatorRemainder, "%checks", start, end);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::{parse, parse_with_options, ParseOptions};
+    use crate::parser::mock::Mock;
+    use crate::ast::{Statement, Expression, PropertyKey, Primitive};
+    use crate::ast::expression::{TypeCastExpression, ConditionalExpression};
+    use crate::error::Error;
+    use crate::module::Module;
+
+    fn parse_flow<'ast>(source: &str) -> Result<Module<'ast>, Vec<Error>> {
+        parse_with_options(source, ParseOptions { flow: true, ..ParseOptions::default() })
+    }
+
+    fn reference<'ast>(mock: &'ast Mock, name: &'ast str) -> Type<'ast> {
+        Type::Reference {
+            name: mock.ptr(TypeName::Identifier(name)),
+            arguments: NodeList::empty(),
+        }
+    }
+
+    #[test]
+    fn flow_pragma() {
+        assert!(has_flow_pragma("// @flow\nfoo;"));
+        assert!(has_flow_pragma("/**\n * Copyright\n * @flow strict\n */\nfoo;"));
+        assert!(has_flow_pragma("// Copyright\n\n/* @flow */"));
+        assert!(!has_flow_pragma("// @noflow\nfoo;"));
+        assert!(!has_flow_pragma("// @flowtype\nfoo;"));
+        assert!(!has_flow_pragma("foo; // @flow"));
+    }
+
+    #[test]
+    fn pragma_enables_flow() {
+        let source = "type Foo = ?string;";
+
+        assert!(parse(source).is_err());
+        assert!(parse(&format!("// @flow\n{}", source)).unwrap().is_flow());
+        assert!(parse_flow(source).unwrap().is_flow());
+    }
+
+    #[test]
+    fn nullable_type() {
+        let mock = Mock::new();
+        let module = parse_flow("type Foo = ?T[];").unwrap();
+
+        let expected = Type::Nullable {
+            argument: mock.ptr(Type::Array {
+                element: mock.ptr(reference(&mock, "T")),
+            }),
+        };
+
+        match module.body().only_element().map(|s| s.item) {
+            Some(Statement::TypeAlias(TypeAliasDeclaration { value, .. })) => assert_eq!(value.item, expected),
+            _ => panic!("Statement isn't a type alias!")
+        }
+    }
+
+    #[test]
+    fn exact_object_type() {
+        let mock = Mock::new();
+        let module = parse_flow("type Foo = {| a: number, b?: string |}; type Bar = {||};").unwrap();
+
+        let expected = mock.list([
+            Statement::from(TypeAliasDeclaration {
+                name: mock.ptr("Foo"),
+                type_parameters: NodeList::empty(),
+                value: mock.ptr(Type::ObjectLiteral {
+                    members: mock.list([
+                        TypeMember::Property {
+                            readonly: false,
+                            key: mock.ptr(PropertyKey::Literal("a")),
+                            optional: false,
+                            annotation: Some(mock.ptr(Type::Primitive(Primitive::Number))),
+                        },
+                        TypeMember::Property {
+                            readonly: false,
+                            key: mock.ptr(PropertyKey::Literal("b")),
+                            optional: true,
+                            annotation: Some(mock.ptr(Type::Primitive(Primitive::String))),
+                        },
+                    ]),
+                    exact: true,
+                }),
+            }),
+            Statement::from(TypeAliasDeclaration {
+                name: mock.ptr("Bar"),
+                type_parameters: NodeList::empty(),
+                value: mock.ptr(Type::ObjectLiteral {
+                    members: NodeList::empty(),
+                    exact: true,
+                }),
+            }),
+        ]);
+
+        assert_eq!(module.body(), expected);
+    }
+
+    #[test]
+    fn opaque_type() {
+        let mock = Mock::new();
+        let module = parse_flow("opaque type Id: string = string; declare opaque type Token;").unwrap();
+
+        let expected = mock.list([
+            Statement::from(OpaqueTypeDeclaration {
+                name: mock.ptr("Id"),
+                type_parameters: NodeList::empty(),
+                supertype: Some(mock.ptr(Type::Primitive(Primitive::String))),
+                value: Some(mock.ptr(Type::Primitive(Primitive::String))),
+            }),
+            Statement::from(DeclareStatement {
+                body: mock.ptr(OpaqueTypeDeclaration {
+                    name: mock.ptr("Token"),
+                    type_parameters: NodeList::empty(),
+                    supertype: None,
+                    value: None,
+                }),
+            }),
+        ]);
+
+        assert_eq!(module.body(), expected);
+    }
+
+    #[test]
+    fn object_type_spread() {
+        let mock = Mock::new();
+        let module = parse_flow("type Foo = { ...Bar, a: number };").unwrap();
+
+        let expected = Type::ObjectLiteral {
+            members: mock.list([
+                TypeMember::Spread {
+                    argument: mock.ptr(reference(&mock, "Bar")),
+                },
+                TypeMember::Property {
+                    readonly: false,
+                    key: mock.ptr(PropertyKey::Literal("a")),
+                    optional: false,
+                    annotation: Some(mock.ptr(Type::Primitive(Primitive::Number))),
+                },
+            ]),
+            exact: false,
+        };
+
+        match module.body().only_element().map(|s| s.item) {
+            Some(Statement::TypeAlias(TypeAliasDeclaration { value, .. })) => assert_eq!(value.item, expected),
+            _ => panic!("Statement isn't a type alias!")
+        }
+
+        assert!(parse_flow("type Foo = {| ...Bar |};").is_ok());
+        assert!(parse_with_options("type Foo = { ...Bar };", ParseOptions {
+            typescript: true,
+            ..ParseOptions::default()
+        }).is_err());
+    }
+
+    #[test]
+    fn declare_export() {
+        let module = parse_flow("declare export function foo(): void; declare export class Bar {}").unwrap();
+
+        for statement in module.body().iter() {
+            match statement.item {
+                Statement::Declare(DeclareStatement { body }) => {
+                    assert!(matches!(body.item, Statement::Export(_)));
+                    assert_eq!(body.start, statement.start + 8);
+                },
+                _ => panic!("Statement isn't a declaration!")
+            }
+        }
+
+        // Neither `export default` nor export lists are supported
+        assert!(parse_flow("declare export default class Foo {}").is_err());
+        assert!(parse_flow("declare export { foo };").is_err());
+        assert!(parse("declare export function foo(): void;").is_err());
+    }
+
+    #[test]
+    fn checks_predicates_are_unsupported() {
+        let kinds = |source| match parse_flow(source) {
+            Ok(_) => panic!("{} parsed", source),
+            Err(errors) => errors.iter().map(|error| (error.kind, error.start, error.end)).collect::<Vec<_>>(),
+        };
+
+        assert_eq!(kinds("function f(x): boolean %checks { return !!x; }")[0], (ErrorKind::UnsupportedSyntax, 23, 30));
+        assert_eq!(kinds("const f = (x): %checks => !!x;")[0], (ErrorKind::UnsupportedSyntax, 15, 22));
+        assert_eq!(kinds("declare function f(x: mixed): boolean %checks(!!x);")[0], (ErrorKind::UnsupportedSyntax, 38, 50));
+
+        // Type guards are the replacement
+        assert!(parse_flow("function f(x: mixed): x is string { return typeof x === 'string'; }").is_ok());
+        assert!(parse_flow("a % checks;").is_ok());
+    }
+
+    #[test]
+    fn type_imports() {
+        let mock = Mock::new();
+        let module = parse_flow(r#"
+            import type Foo, { Bar, Baz as Qux } from "foo";
+            import typeof * as React from "react";
+        "#).unwrap();
+
+        let expected = mock.list([
+            Statement::from(TypeImportDeclaration {
+                kind: TypeImportKind::Type,
+                specifiers: mock.list([
+                    TypeImportSpecifier::Default(mock.ptr("Foo")),
+                    TypeImportSpecifier::Named {
+                        imported: mock.ptr("Bar"),
+                        local: mock.ptr("Bar"),
+                    },
+                    TypeImportSpecifier::Named {
+                        imported: mock.ptr("Baz"),
+                        local: mock.ptr("Qux"),
+                    },
+                ]),
+                source: mock.ptr(r#""foo""#),
+            }),
+            Statement::from(TypeImportDeclaration {
+                kind: TypeImportKind::Typeof,
+                specifiers: mock.list([
+                    TypeImportSpecifier::Namespace(mock.ptr("React")),
+                ]),
+                source: mock.ptr(r#""react""#),
+            }),
+        ]);
+
+        assert_eq!(module.body(), expected);
+    }
+
+    #[test]
+    fn type_casts() {
+        let module = parse_flow("let x = (y: any); b ? (c: d) : e;").unwrap();
+        let body: Vec<_> = module.body().iter().map(|statement| statement.item).collect();
+
+        match body[0] {
+            Statement::Declaration(declaration) => {
+                let init = declaration.declarators.only_element().unwrap().init.unwrap();
+
+                match init.item {
+                    Expression::TypeCast(TypeCastExpression { expression, annotation }) => {
+                        assert_eq!(expression.item, Expression::Identifier("y"));
+                        assert_eq!(annotation.item, Type::Any);
+                        assert_eq!((init.start, init.end), (8, 16));
+                    },
+                    _ => panic!("Initializer isn't a type cast!")
+                }
+            },
+            _ => panic!("Statement isn't a declaration!")
+        }
+
+        match body[1] {
+            Statement::Expression(expression) => match expression.item {
+                Expression::Conditional(ConditionalExpression { consequent, .. }) => {
+                    assert!(matches!(consequent.item, Expression::TypeCast(_)));
+                },
+                _ => panic!("Expression isn't a conditional!")
+            },
+            _ => panic!("Statement isn't an expression!")
+        }
+
+        assert!(parse_flow("f((a: T), ((b, c): U));").is_ok());
+        assert!(parse_flow("(a: T): U => a;").is_ok());
+        assert!(parse("(y: any);").is_err());
+        assert!(parse_flow("(y: any, z);").is_err());
+    }
+
+    #[test]
+    fn typescript_only_syntax() {
+        assert!(parse_flow("foo as Bar;").is_err());
+        assert!(parse_flow("<T>foo;").is_err());
+        assert!(parse_flow("import foo from 'foo';").is_err());
+        assert!(parse_with_options("opaque type Foo = string;", ParseOptions {
+            typescript: true,
+            ..ParseOptions::default()
+        }).is_err());
+    }
+
+    #[test]
+    fn annotations_and_generics() {
+        assert!(parse_flow("function foo<T>(x: ?T, y?: number = 1): Array<T> { return []; }").is_ok());
+        assert!(parse_flow("const foo = <T>(x: T): T => x; foo<string>('a');").is_ok());
+        assert!(parse_flow("class Foo<T> extends Bar<T> implements Baz { prop: T; static x: number = 1; }").is_ok());
+        assert!(parse_flow("declare var foo: number; declare function bar(x: string): void; declare class Baz {}").is_ok());
+        assert!(parse_flow("declare type Foo = string; interface Bar { x: number }").is_ok());
+    }
+}
@@ -1,8 +1,11 @@
 use toolshed::list::ListBuilder;
-use crate::parser::{Parser, Parse, ANY, B0};
+use crate::parser::{Parser, Parse, ANY, B0, B15};
 use crate::lexer::Token::*;
-use crate::ast::{Node, NodeList, EmptyName, OptionalName, MandatoryName, Name};
-use crate::ast::{MethodKind, Pattern, Function, Class, ClassMember, PropertyKey};
+use crate::lexer::Asi;
+use crate::ast::{Node, NodeList, Loc, EmptyName, OptionalName, MandatoryName, Name};
+use crate::ast::{MethodKind, Pattern, Function, Class, ClassMember, PropertyKey, Modifiers};
+use crate::ast::{Decorator, DecoratorList, ExpressionNode};
+use crate::ast::expression::{MemberExpression, CallExpression};
 
 impl<'ast> Parse<'ast> for EmptyName {
     type Output = Self;
@@ -74,12 +77,15 @@ impl<'ast, N> Parse<'ast> for Function<'ast, N> where
         };
 
         let name = N::parse(par);
+        let signature = par.signature().item;
 
         Function {
             name,
             generator,
-            params: par.params(),
-            body: par.block(),
+            type_parameters: signature.type_parameters,
+            params: signature.params,
+            return_type: signature.return_type,
+            body: par.function_body(Parser::block),
         }
     }
 }
@@ -105,6 +111,24 @@ impl<'ast> Parse<'ast> for ClassMember<'ast> {
     fn parse(par: &mut Parser<'ast>) -> Self::Output {
         let start = par.lexer.start();
 
+        if par.lexer.token == Decorator {
+            let decorators = par.decorators();
+            let member = ClassMember::parse(par);
+
+            return par.alloc_at_loc(start, member.end, ClassMember::Decorated {
+                decorators,
+                member,
+            });
+        }
+
+        let mut modifiers = Modifiers::default();
+
+        if par.typescript {
+            par.modifiers(&mut modifiers);
+        }
+
+        let member_start = par.lexer.start();
+
         let is_static = match par.lexer.token {
             Static => {
                 par.lexer.consume();
@@ -113,16 +137,36 @@ impl<'ast> Parse<'ast> for ClassMember<'ast> {
             _ => false
         };
 
+        if par.typescript {
+            par.modifiers(&mut modifiers);
+        }
+
+        // `accessor` followed by a key on the same line, rather than
+        // a member named `accessor`
+        let is_accessor = par.lexer.token.is_word() && par.lexer.token_as_str() == "accessor" && par.lookahead(|par| {
+            par.lexer.consume();
+
+            par.asi() != Asi::ImplicitSemicolon && match par.lexer.token {
+                LiteralNumber | LiteralBinary | BracketOpen => true,
+                token                                       => token.is_word(),
+            }
+        });
+
+        if is_accessor {
+            par.lexer.consume();
+        }
+
         let mut kind = MethodKind::Method;
 
-        let token_start = par.lexer.start();
-        let token_end;
+        let mut token_start = par.lexer.start();
+        let mut token_end;
 
         let key = match par.lexer.token {
             _ if par.lexer.token.is_word() => {
                 let mut label = par.lexer.token_as_str();
                 token_end = par.lexer.end_then_consume();
 
+                // The key of an accessor is the name after `get` or `set`
                 if par.lexer.token.is_word() {
                     kind = match label {
                         "get" => MethodKind::Get,
@@ -130,7 +174,8 @@ impl<'ast> Parse<'ast> for ClassMember<'ast> {
                         _     => return par.error()
                     };
                     label = par.lexer.token_as_str();
-                    par.lexer.consume();
+                    token_start = par.lexer.start();
+                    token_end = par.lexer.end_then_consume();
                 } else if !is_static && label == "constructor" {
                     kind = MethodKind::Constructor;
                 }
@@ -162,8 +207,52 @@ impl<'ast> Parse<'ast> for ClassMember<'ast> {
 
         let key = par.alloc_at_loc(token_start, token_end, key);
         let end;
+        let is_signature = match par.lexer.token {
+            ParenOpen | OperatorLesser => par.types && par.lookahead(Parser::is_signature),
+            _                          => false,
+        };
+
         let member = match par.lexer.token {
-            ParenOpen => {
+            _ if is_accessor => {
+                if kind != MethodKind::Method {
+                    return par.error();
+                }
+
+                let annotation = par.type_annotation();
+                let value = match par.lexer.token {
+                    OperatorAssign => {
+                        par.lexer.consume();
+
+                        Some(par.expression::<B0>())
+                    },
+                    _ => None,
+                };
+
+                end = value.map(|value| value.end)
+                    .or_else(|| annotation.map(|annotation| annotation.end))
+                    .unwrap_or(key.end);
+
+                ClassMember::Accessor {
+                    is_static,
+                    key,
+                    annotation,
+                    value,
+                }
+            },
+            ParenOpen | OperatorLesser if is_signature => {
+                let signature = par.signature();
+                let signature = par.alloc(signature);
+
+                end = signature.end;
+
+                ClassMember::Signature {
+                    is_static,
+                    key,
+                    kind,
+                    signature,
+                }
+            },
+            ParenOpen | OperatorLesser => {
                 let value = Node::parse(par);
 
                 end = value.end;
@@ -188,6 +277,13 @@ impl<'ast> Parse<'ast> for ClassMember<'ast> {
                     value: expression,
                 }
             },
+            _ if par.types => {
+                let property = par.class_property(is_static, key);
+
+                end = property.end;
+
+                property.item
+            },
             _ => return par.error(),
         };
 
@@ -195,7 +291,16 @@ impl<'ast> Parse<'ast> for ClassMember<'ast> {
             par.lexer.consume();
         }
 
-        par.alloc_at_loc(start, end, member)
+        if modifiers.is_empty() {
+            return par.alloc_at_loc(start, end, member);
+        }
+
+        let member = par.alloc_at_loc(member_start, end, member);
+
+        par.alloc_at_loc(start, end, ClassMember::Modified {
+            modifiers,
+            member,
+        })
     }
 }
 
@@ -207,8 +312,16 @@ impl<'ast, N> Parse<'ast> for Class<'ast, N> where
     #[inline]
     fn parse(par: &mut Parser<'ast>) -> Self::Output {
         let name = N::parse(par);
+        let type_parameters = par.type_parameters();
 
+        // Type arguments of the super class can't be told apart from
+        // a comparison, so they are parsed separately.
         let super_class = match par.lexer.token {
+            Extends if par.types => {
+                par.lexer.consume();
+
+                Some(par.expression::<B15>())
+            },
             Extends => {
                 par.lexer.consume();
 
@@ -217,15 +330,107 @@ impl<'ast, N> Parse<'ast> for Class<'ast, N> where
             _ => None
         };
 
+        let super_type_arguments = match par.lexer.token {
+            OperatorLesser if super_class.is_some() && par.types => {
+                let arguments = par.type_arguments();
+
+                expect!(par, OperatorGreater);
+
+                arguments
+            },
+            _ => NodeList::empty(),
+        };
+
+        let implements = match par.lexer.token {
+            ReservedImplements if par.types => {
+                par.lexer.consume();
+
+                par.type_references()
+            },
+            _ => NodeList::empty(),
+        };
+
         Class {
+            decorators: NodeList::empty(),
             name,
+            type_parameters,
             extends: super_class,
+            super_type_arguments,
+            implements,
             body: par.block(),
         }
     }
 }
 
 impl<'ast> Parser<'ast> {
+    /// Decorators preceding a class or a class member, `@foo @bar.baz() class {}`
+    pub fn decorators(&mut self) -> DecoratorList<'ast> {
+        let mut decorators = Vec::new();
+
+        while self.lexer.token == Decorator {
+            let start = self.lexer.start_then_consume();
+            let expression = self.decorator_expression();
+
+            decorators.push(self.alloc_at_loc(start, expression.end, Decorator {
+                expression,
+            }));
+        }
+
+        NodeList::from_iter(self.arena, decorators)
+    }
+
+    /// Either a parenthesized expression, `@(foo)`, or a chain of member
+    /// accesses that can end with a call, `@foo.bar()`.
+    fn decorator_expression(&mut self) -> ExpressionNode<'ast> {
+        if self.lexer.token == ParenOpen {
+            self.lexer.consume();
+
+            let expression = self.expression::<ANY>();
+
+            expect!(self, ParenClose);
+
+            return expression;
+        }
+
+        if self.lexer.token != Identifier {
+            return self.error();
+        }
+
+        let name = self.lexer.token_as_str();
+        let mut expression = self.alloc_in_loc(name);
+
+        self.lexer.consume();
+
+        while self.lexer.token == Accessor {
+            let member = self.lexer.accessor_as_str();
+            let end = self.lexer.end();
+            let property = self.alloc_at_loc(end - member.len() as u32, end, member);
+
+            self.lexer.consume();
+
+            expression = self.alloc_at_loc(expression.start, property.end, MemberExpression {
+                object: expression,
+                property,
+            });
+        }
+
+        if self.lexer.token == ParenOpen {
+            self.lexer.consume();
+
+            let arguments = self.call_arguments();
+            let end = self.lexer.end();
+
+            expect!(self, ParenClose);
+
+            expression = self.alloc_at_loc(expression.start, end, CallExpression {
+                callee: expression,
+                arguments,
+            });
+        }
+
+        expression
+    }
+
     #[inline]
     fn pattern_void(&mut self) -> Node<'ast, Pattern<'ast>> {
         let loc = self.lexer.start();
@@ -234,22 +439,24 @@ impl<'ast> Parser<'ast> {
 
     #[inline]
     fn pattern_identifier(&mut self) -> Node<'ast, Pattern<'ast>> {
-        let ident = Pattern::Identifier(self.lexer.token_as_str());
-        let ident = self.alloc_in_loc(ident);
+        let (start, end) = self.lexer.loc();
+        let ident = self.alloc_in_loc(self.lexer.token_as_str());
 
         self.lexer.consume();
 
-        ident
+        self.alloc_at_loc(start, end, Pattern::Identifier(ident))
     }
 
     #[inline]
     fn pattern_array(&mut self) -> Node<'ast, Pattern<'ast>> {
-        let start = self.lexer.start_then_consume();
-        let elements = self.array_elements(Parser::pattern_array_element);
-        let end = self.lexer.end_then_consume();
-
-        self.alloc_at_loc(start, end, Pattern::ArrayPattern {
-            elements
+        self.nested(|par| {
+            let start = par.lexer.start_then_consume();
+            let elements = par.array_elements(Parser::pattern_array_element);
+            let end = par.lexer.end_then_consume();
+
+            par.alloc_at_loc(start, end, Pattern::ArrayPattern {
+                elements
+            })
         })
     }
 
@@ -295,7 +502,7 @@ impl<'ast> Parser<'ast> {
     }
 
     #[inline]
-    fn pattern_param(&mut self) -> Node<'ast, Pattern<'ast>> {
+    pub fn pattern_param(&mut self) -> Node<'ast, Pattern<'ast>> {
         let left = match self.lexer.token {
             Identifier           => self.pattern_identifier(),
             BracketOpen          => self.pattern_array(),
@@ -303,9 +510,36 @@ impl<'ast> Parser<'ast> {
             _                    => self.error()
         };
 
+        let left = self.pattern_annotation(left);
+
         self.pattern_assign(left)
     }
 
+    /// Parameter of a function, which in TypeScript can also declare
+    /// a class property when preceded by modifiers.
+    #[inline]
+    fn param(&mut self) -> Node<'ast, Pattern<'ast>> {
+        if !self.typescript {
+            return self.pattern_param();
+        }
+
+        let start = self.lexer.start();
+        let mut modifiers = Modifiers::default();
+
+        self.modifiers(&mut modifiers);
+
+        let parameter = self.pattern_param();
+
+        if modifiers.is_empty() {
+            return parameter;
+        }
+
+        self.alloc_at_loc(start, parameter.end, Pattern::ParameterProperty {
+            modifiers,
+            parameter,
+        })
+    }
+
     #[inline]
     fn rest_element(&mut self) -> Node<'ast, Pattern<'ast>> {
         let start = self.lexer.start_then_consume();
@@ -321,25 +555,29 @@ impl<'ast> Parser<'ast> {
             _ => self.error()
         };
 
-        expect!(self, ParenClose);
-
-        self.alloc_at_loc(start, argument.end, Pattern::RestElement {
+        let rest = self.alloc_at_loc(start, argument.end, Pattern::RestElement {
             argument
-        })
+        });
+
+        let rest = self.pattern_annotation(rest);
+
+        if self.lexer.token != ParenClose {
+            self.error::<()>();
+        }
+
+        rest
     }
 
+    /// Parse the parameters of a function, returning them along with
+    /// the end of the closing parenthesis.
     #[inline]
-    fn params(&mut self) -> NodeList<'ast, Pattern<'ast>> {
+    pub fn params(&mut self) -> (NodeList<'ast, Pattern<'ast>>, u32) {
         expect!(self, ParenOpen);
 
         let item = match self.lexer.token {
-            ParenClose     => {
-                self.lexer.consume();
-
-                return NodeList::empty();
-            },
-            OperatorSpread => return NodeList::from(self.arena, self.rest_element()),
-            _              => self.pattern_param()
+            ParenClose     => return (NodeList::empty(), self.lexer.end_then_consume()),
+            OperatorSpread => self.rest_element(),
+            _              => self.param()
         };
 
         let builder = ListBuilder::new(self.arena, item);
@@ -349,11 +587,7 @@ impl<'ast> Parser<'ast> {
                 Comma => {
                     self.lexer.consume();
                 },
-                ParenClose => {
-                    self.lexer.consume();
-
-                    break;
-                },
+                ParenClose => break,
                 _ => {
                     self.error::<()>();
 
@@ -362,23 +596,74 @@ impl<'ast> Parser<'ast> {
             }
 
             match self.lexer.token {
-                ParenClose => {
-                    self.lexer.consume();
-
-                    break;
-                },
+                ParenClose => break,
                 OperatorSpread => {
                     builder.push(self.arena, self.rest_element());
-
-                    break;
                 },
                 _ => {
-                    builder.push(self.arena, self.pattern_param());
+                    builder.push(self.arena, self.param());
                 }
             }
         }
 
-        builder.as_list()
+        let end = self.lexer.end();
+
+        expect!(self, ParenClose);
+
+        (builder.as_list(), end)
+    }
+
+    /// Check if the current method has no body, and is only a signature.
+    #[inline]
+    fn is_signature(&mut self) -> bool {
+        self.signature();
+
+        self.lexer.token != BraceOpen
+    }
+
+    /// Parse the rest of a TypeScript class property following its `key`.
+    #[inline]
+    fn class_property(&mut self, is_static: bool, key: Node<'ast, PropertyKey<'ast>>) -> Loc<ClassMember<'ast>> {
+        let mut end = key.end;
+
+        let optional = match self.lexer.token {
+            OperatorConditional => {
+                end = self.lexer.end_then_consume();
+                true
+            },
+            // Definite assignment assertion, `foo!: Bar`
+            OperatorLogicalNot => {
+                end = self.lexer.end_then_consume();
+                false
+            },
+            _ => false,
+        };
+
+        let annotation = self.type_annotation();
+
+        if let Some(annotation) = annotation {
+            end = annotation.end;
+        }
+
+        let value = match self.lexer.token {
+            OperatorAssign => {
+                self.lexer.consume();
+
+                let value = self.expression::<B0>();
+                end = value.end;
+
+                Some(value)
+            },
+            _ => None,
+        };
+
+        Loc::new(key.start, end, ClassMember::Property {
+            is_static,
+            key,
+            optional,
+            annotation,
+            value,
+        })
     }
 }
 
@@ -388,7 +673,8 @@ mod test {
     use crate::parser::parse;
     use crate::parser::mock::Mock;
     use crate::ast::{NodeList, Literal, Expression, Function, Class};
-    use crate::ast::{ClassMember, Pattern};
+    use crate::ast::{ClassMember, Pattern, Statement};
+    use crate::ast::expression::ComputedMemberExpression;
     use crate::ast::statement::*;
 
     #[test]
@@ -398,6 +684,8 @@ mod test {
 
         let expected = mock.list([
             Function {
+                type_parameters: NodeList::empty(),
+                return_type: None,
                 name: mock.name("foo"),
                 generator: false,
                 params: NodeList::empty(),
@@ -416,6 +704,8 @@ mod test {
 
             let expected = mock.list([
                 Function {
+                    type_parameters: NodeList::empty(),
+                    return_type: None,
                     name: mock.name("foo"),
                     generator: true,
                     params: NodeList::empty(),
@@ -432,6 +722,8 @@ mod test {
 
             let expected = mock.list([
                 Function {
+                    type_parameters: NodeList::empty(),
+                    return_type: None,
                     name: mock.name("foo"),
                     generator: true,
                     params: NodeList::empty(),
@@ -448,6 +740,8 @@ mod test {
 
             let expected = mock.list([
                 Function {
+                    type_parameters: NodeList::empty(),
+                    return_type: None,
                     name: mock.name("foo"),
                     generator: true,
                     params: NodeList::empty(),
@@ -466,11 +760,13 @@ mod test {
 
         let expected = mock.list([
             Function {
+                type_parameters: NodeList::empty(),
+                return_type: None,
                 name: mock.name("foo"),
                 generator: false,
                 params: mock.list([
-                    Pattern::Identifier("bar"),
-                    Pattern::Identifier("baz"),
+                    Pattern::Identifier(mock.ptr("bar")),
+                    Pattern::Identifier(mock.ptr("baz")),
                 ]),
                 body: mock.empty_block(),
             }
@@ -486,6 +782,8 @@ mod test {
 
         let expected = mock.list([
             Function {
+                type_parameters: NodeList::empty(),
+                return_type: None,
                 name: mock.name("foo"),
                 generator: false,
                 params: NodeList::empty(),
@@ -506,19 +804,21 @@ mod test {
 
         let expected = mock.list([
             Function {
+                type_parameters: NodeList::empty(),
+                return_type: None,
                 name: mock.name("foo"),
                 generator: false,
                 params: mock.list([
                     Pattern::AssignmentPattern {
-                        left: mock.ptr(Pattern::Identifier("a")),
+                        left: mock.ptr(Pattern::Identifier(mock.ptr("a"))),
                         right: mock.number("0")
                     },
                     Pattern::AssignmentPattern {
-                        left: mock.ptr(Pattern::Identifier("b")),
+                        left: mock.ptr(Pattern::Identifier(mock.ptr("b"))),
                         right: mock.number("1")
                     },
                     Pattern::AssignmentPattern {
-                        left: mock.ptr(Pattern::Identifier("c")),
+                        left: mock.ptr(Pattern::Identifier(mock.ptr("c"))),
                         right: mock.number("2")
                     }
                 ]),
@@ -539,16 +839,18 @@ mod test {
 
         let expected = mock.list([
             Function {
+                type_parameters: NodeList::empty(),
+                return_type: None,
                 name: mock.name("foo"),
                 generator: false,
                 params: mock.list([
-                    Pattern::Identifier("a"),
-                    Pattern::Identifier("b"),
+                    Pattern::Identifier(mock.ptr("a")),
+                    Pattern::Identifier(mock.ptr("b")),
                     Pattern::AssignmentPattern {
-                        left: mock.ptr(Pattern::Identifier("c")),
+                        left: mock.ptr(Pattern::Identifier(mock.ptr("c"))),
                         right: mock.number("2")
                     },
-                    Pattern::Identifier("d")
+                    Pattern::Identifier(mock.ptr("d"))
                 ]),
                 body: mock.block([
                     ReturnStatement {
@@ -567,6 +869,8 @@ mod test {
 
         let expected = mock.list([
             Function {
+                type_parameters: NodeList::empty(),
+                return_type: None,
                 name: mock.name("foo"),
                 generator: false,
                 params: mock.list([
@@ -587,12 +891,14 @@ mod test {
 
         let expected = mock.list([
             Function {
+                type_parameters: NodeList::empty(),
+                return_type: None,
                 name: mock.name("foo"),
                 generator: false,
                 params: mock.list([
-                    Pattern::Identifier("a"),
+                    Pattern::Identifier(mock.ptr("a")),
                     Pattern::AssignmentPattern {
-                        left: mock.ptr(Pattern::Identifier("b")),
+                        left: mock.ptr(Pattern::Identifier(mock.ptr("b"))),
                         right: mock.number("10")
                     },
                     Pattern::RestElement {
@@ -617,6 +923,10 @@ mod test {
 
         let expected = mock.list([
             Class {
+                decorators: NodeList::empty(),
+                type_parameters: NodeList::empty(),
+                super_type_arguments: NodeList::empty(),
+                implements: NodeList::empty(),
                 name: mock.name("Foo"),
                 extends: None,
                 body: mock.empty_block(),
@@ -633,6 +943,10 @@ mod test {
 
         let expected = mock.list([
             Class {
+                decorators: NodeList::empty(),
+                type_parameters: NodeList::empty(),
+                super_type_arguments: NodeList::empty(),
+                implements: NodeList::empty(),
                 name: mock.name("Foo"),
                 extends: Some(mock.ptr("Bar")),
                 body: mock.empty_block(),
@@ -657,6 +971,10 @@ mod test {
 
         let expected = mock.list([
             Class {
+                decorators: NodeList::empty(),
+                type_parameters: NodeList::empty(),
+                super_type_arguments: NodeList::empty(),
+                implements: NodeList::empty(),
                 name: mock.name("Foo"),
                 extends: None,
                 body: mock.block([
@@ -665,11 +983,13 @@ mod test {
                         key: mock.ptr(PropertyKey::Literal("constructor")),
                         kind: MethodKind::Constructor,
                         value: mock.ptr(Function {
+                            type_parameters: NodeList::empty(),
+                            return_type: None,
                             name: EmptyName,
                             generator: false,
                             params: mock.list([
-                                Pattern::Identifier("bar"),
-                                Pattern::Identifier("baz")
+                                Pattern::Identifier(mock.ptr("bar")),
+                                Pattern::Identifier(mock.ptr("baz"))
                             ]),
                             body: mock.block([
                                 mock.ptr("debug")
@@ -706,6 +1026,10 @@ mod test {
 
         let expected = mock.list([
             Class {
+                decorators: NodeList::empty(),
+                type_parameters: NodeList::empty(),
+                super_type_arguments: NodeList::empty(),
+                implements: NodeList::empty(),
                 name: mock.name("Foo"),
                 extends: None,
                 body: mock.block([
@@ -714,11 +1038,13 @@ mod test {
                         key: mock.ptr(PropertyKey::Literal("doge")),
                         kind: MethodKind::Method,
                         value: mock.ptr(Function {
+                            type_parameters: NodeList::empty(),
+                            return_type: None,
                             name: EmptyName,
                             generator: false,
                             params: mock.list([
-                                Pattern::Identifier("bar"),
-                                Pattern::Identifier("baz")
+                                Pattern::Identifier(mock.ptr("bar")),
+                                Pattern::Identifier(mock.ptr("baz"))
                             ]),
                             body: mock.block([
                                 mock.ptr("debug")
@@ -730,10 +1056,12 @@ mod test {
                         key: mock.ptr(PropertyKey::Literal("toThe")),
                         kind: MethodKind::Method,
                         value: mock.ptr(Function {
+                            type_parameters: NodeList::empty(),
+                            return_type: None,
                             name: EmptyName,
                             generator: false,
                             params: mock.list([
-                                Pattern::Identifier("moon")
+                                Pattern::Identifier(mock.ptr("moon"))
                             ]),
                             body: mock.block([
                                 mock.ptr("debug")
@@ -745,6 +1073,8 @@ mod test {
                         key: mock.ptr(PropertyKey::Literal("function")),
                         kind: MethodKind::Method,
                         value: mock.ptr(Function {
+                            type_parameters: NodeList::empty(),
+                            return_type: None,
                             name: EmptyName,
                             generator: false,
                             params: NodeList::empty(),
@@ -756,6 +1086,8 @@ mod test {
                         key: mock.ptr(PropertyKey::Literal("function")),
                         kind: MethodKind::Method,
                         value: mock.ptr(Function {
+                            type_parameters: NodeList::empty(),
+                            return_type: None,
                             name: EmptyName,
                             generator: false,
                             params: NodeList::empty(),
@@ -767,6 +1099,8 @@ mod test {
                         key: mock.ptr(PropertyKey::Literal("constructor")),
                         kind: MethodKind::Method,
                         value: mock.ptr(Function {
+                            type_parameters: NodeList::empty(),
+                            return_type: None,
                             name: EmptyName,
                             generator: false,
                             params: NodeList::empty(),
@@ -796,6 +1130,10 @@ mod test {
 
         let expected = mock.list([
             Class {
+                decorators: NodeList::empty(),
+                type_parameters: NodeList::empty(),
+                super_type_arguments: NodeList::empty(),
+                implements: NodeList::empty(),
                 name: mock.name("Foo"),
                 extends: None,
                 body: mock.block([
@@ -834,6 +1172,10 @@ mod test {
 
         let expected = mock.list([
             Class {
+                decorators: NodeList::empty(),
+                type_parameters: NodeList::empty(),
+                super_type_arguments: NodeList::empty(),
+                implements: NodeList::empty(),
                 name: mock.name("Foo"),
                 extends: Some(mock.ptr(Expression::Literal(Literal::Null))),
                 body: mock.empty_block()
@@ -857,6 +1199,10 @@ mod test {
 
         let expected = mock.list([
             Class {
+                decorators: NodeList::empty(),
+                type_parameters: NodeList::empty(),
+                super_type_arguments: NodeList::empty(),
+                implements: NodeList::empty(),
                 name: mock.name("Foo"),
                 extends: None,
                 body: mock.block([
@@ -865,10 +1211,12 @@ mod test {
                         key: mock.ptr(PropertyKey::Literal("length")),
                         kind: MethodKind::Get,
                         value: mock.ptr(Function {
+                            type_parameters: NodeList::empty(),
+                            return_type: None,
                             name: EmptyName,
                             generator: false,
                             params: mock.list([
-                                Pattern::Identifier("foo")
+                                Pattern::Identifier(mock.ptr("foo"))
                             ]),
                             body: mock.empty_block()
                         })
@@ -878,10 +1226,12 @@ mod test {
                         key: mock.ptr(PropertyKey::Literal("length")),
                         kind: MethodKind::Set,
                         value: mock.ptr(Function {
+                            type_parameters: NodeList::empty(),
+                            return_type: None,
                             name: EmptyName,
                             generator: false,
                             params: mock.list([
-                                Pattern::Identifier("bar")
+                                Pattern::Identifier(mock.ptr("bar"))
                             ]),
                             body: mock.empty_block()
                         })
@@ -892,4 +1242,159 @@ mod test {
 
         assert_eq!(parse(src).unwrap().body(), expected);
     }
+
+    #[test]
+    fn class_decorators() {
+        let src = "@foo @bar.baz(1) @(qux[0]) class Foo { @foo static a() {} @bar b = 1; }";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            Class {
+                decorators: mock.list([
+                    Decorator {
+                        expression: mock.ptr("foo"),
+                    },
+                    Decorator {
+                        expression: mock.ptr(CallExpression {
+                            callee: mock.ptr(MemberExpression {
+                                object: mock.ptr("bar"),
+                                property: mock.ptr("baz"),
+                            }),
+                            arguments: mock.list([Literal::Number("1")]),
+                        }),
+                    },
+                    Decorator {
+                        expression: mock.ptr(ComputedMemberExpression {
+                            object: mock.ptr("qux"),
+                            property: mock.number("0"),
+                        }),
+                    },
+                ]),
+                type_parameters: NodeList::empty(),
+                super_type_arguments: NodeList::empty(),
+                implements: NodeList::empty(),
+                name: mock.name("Foo"),
+                extends: None,
+                body: mock.block([
+                    ClassMember::Decorated {
+                        decorators: mock.list([
+                            Decorator {
+                                expression: mock.ptr("foo"),
+                            },
+                        ]),
+                        member: mock.ptr(ClassMember::Method {
+                            is_static: true,
+                            key: mock.ptr(PropertyKey::Literal("a")),
+                            kind: MethodKind::Method,
+                            value: mock.ptr(Function {
+                                type_parameters: NodeList::empty(),
+                                return_type: None,
+                                name: EmptyName,
+                                generator: false,
+                                params: NodeList::empty(),
+                                body: mock.empty_block()
+                            })
+                        }),
+                    },
+                    ClassMember::Decorated {
+                        decorators: mock.list([
+                            Decorator {
+                                expression: mock.ptr("bar"),
+                            },
+                        ]),
+                        member: mock.ptr(ClassMember::Literal {
+                            is_static: false,
+                            key: mock.ptr(PropertyKey::Literal("b")),
+                            value: mock.number("1")
+                        }),
+                    },
+                ])
+            }
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn decorated_class_expression() {
+        let module = parse("const Foo = @foo class {};").unwrap();
+
+        let declarator = match module.body().only_element().unwrap().item {
+            Statement::Declaration(declaration) => *declaration.declarators.only_element().unwrap(),
+            _ => panic!("Statement isn't a declaration!")
+        };
+
+        match declarator.init.unwrap().item {
+            Expression::Class(class) => assert_eq!(class.decorators.iter().count(), 1),
+            expression => panic!("Unexpected expression {:?}", expression),
+        }
+    }
+
+    #[test]
+    fn decorators_before_export() {
+        use crate::parser::{parse_with_options, ParseOptions};
+
+        let options = ParseOptions { typescript: true, ..ParseOptions::default() };
+
+        assert!(parse_with_options("namespace A { @foo export class B {} export @bar class C {} }", options).is_ok());
+        assert!(parse_with_options("namespace A { @foo export const b = 1; }", options).is_err());
+
+        let module = parse("@foo export class A {} export @bar class B {} export function c() {}").unwrap();
+
+        for statement in module.body().iter() {
+            match statement.item {
+                Statement::Export(ExportDeclaration { declaration, .. }) => assert!(declaration.is_declaration()),
+                _ => panic!("Statement isn't an export!")
+            }
+        }
+
+        assert_eq!(module.body().iter().next().map(|statement| (statement.start, statement.end)), Some((0, 22)));
+        assert!(parse("export foo();").is_err());
+        assert!(parse("export { foo };").is_err());
+        assert!(parse("@foo export const a = 1;").is_err());
+    }
+
+    #[test]
+    fn accessors() {
+        let mock = Mock::new();
+        let module = parse("class A { accessor b; static accessor [c] = 1; @d accessor e; accessor = 2; }").unwrap();
+
+        let members: Vec<_> = match module.body().only_element().unwrap().item {
+            Statement::Class(class) => class.body.body.iter().map(|member| member.item).collect(),
+            _ => panic!("Statement isn't a class!")
+        };
+
+        assert_eq!(members[0], ClassMember::Accessor {
+            is_static: false,
+            key: mock.ptr(PropertyKey::Literal("b")),
+            annotation: None,
+            value: None,
+        });
+        assert_eq!(members[1], ClassMember::Accessor {
+            is_static: true,
+            key: mock.ptr(PropertyKey::Computed(mock.ptr("c"))),
+            annotation: None,
+            value: Some(mock.number("1")),
+        });
+        match members[2] {
+            ClassMember::Decorated { member, .. } => assert!(matches!(member.item, ClassMember::Accessor { .. })),
+            _ => panic!("Member isn't decorated!")
+        }
+        assert_eq!(members[3], ClassMember::Literal {
+            is_static: false,
+            key: mock.ptr(PropertyKey::Literal("accessor")),
+            value: mock.number("2"),
+        });
+
+        assert!(parse("class A { accessor get b() {} }").is_err());
+        assert!(parse("class A { accessor b() {} }").is_err());
+    }
+
+    #[test]
+    fn invalid_decorators() {
+        assert!(parse("@foo function bar() {}").is_err());
+        assert!(parse("@foo[0] class Bar {}").is_err());
+        assert!(parse("@foo").is_err());
+        assert!(parse("class Foo { @bar }").is_err());
+    }
 }
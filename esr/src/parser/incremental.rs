@@ -0,0 +1,1070 @@
+use toolshed::Arena;
+use crate::error::Error;
+use crate::module::Module;
+use crate::parser::Parser;
+use crate::lexer::Token::*;
+use crate::ast::{Node, NodeList, Loc, Block, Pattern, Literal, OperatorKind, DeclarationKind};
+use crate::ast::{Statement, StatementNode, StatementList, Expression, Property, PropertyKey};
+use crate::ast::{Function, Class, ClassMember, MethodKind, Name, EmptyName, OptionalName, MandatoryName};
+use crate::ast::{Decorator, SYNTHETIC};
+use crate::ast::expression::*;
+use crate::ast::statement::*;
+use crate::ast::jsx::*;
+use crate::ast::types::*;
+
+/// A single change to the source: replaces the bytes in `start..end`
+/// with `text`. Offsets are in bytes and, when applying a list of edits,
+/// refer to the source as modified by the preceding edits.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TextEdit {
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+}
+
+impl TextEdit {
+    pub fn new<S: Into<String>>(start: usize, end: usize, text: S) -> Self {
+        TextEdit {
+            start,
+            end,
+            text: text.into(),
+        }
+    }
+
+    /// Apply the edit to the `source`.
+    ///
+    /// Panics if the range is out of bounds or doesn't lie on `char` boundaries.
+    #[inline]
+    pub fn apply(&self, source: &mut String) {
+        source.replace_range(self.start..self.end, &self.text);
+    }
+}
+
+/// Region of the source that differs between the previous and the new source.
+struct Change {
+    /// Start of the region, same in both sources
+    start: u32,
+
+    /// End of the region in the previous source
+    old_end: u32,
+
+    /// End of the region in the new source
+    new_end: u32,
+
+    /// Offset between the previous and the new source after the region
+    delta: i64,
+}
+
+impl Change {
+    fn new(old: &str, new: &str) -> Self {
+        let old = old.as_bytes();
+        let new = new.as_bytes();
+
+        let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+        let suffix = old[prefix..].iter().rev()
+                        .zip(new[prefix..].iter().rev())
+                        .take_while(|(a, b)| a == b)
+                        .count();
+
+        Change {
+            start: prefix as u32,
+            old_end: (old.len() - suffix) as u32,
+            new_end: (new.len() - suffix) as u32,
+            delta: new.len() as i64 - old.len() as i64,
+        }
+    }
+
+    #[inline]
+    fn shift(&self, offset: u32) -> u32 {
+        match offset {
+            SYNTHETIC => SYNTHETIC,
+            _         => (offset as i64 + self.delta) as u32,
+        }
+    }
+}
+
+/// Statements that are guaranteed to start at their first token, parsing
+/// can be resumed from them. Expression statements can start inside of
+/// parentheses that are not part of the AST.
+#[inline]
+fn is_resumable(statement: &StatementNode<'_>) -> bool {
+    !matches!(statement.item, Statement::Expression(_))
+}
+
+impl<'ast> Parser<'ast> {
+    /// Parse a list of statements affected by the `change` again, reusing
+    /// all `old` statements that can't be affected by it. The list begins
+    /// at the `start` offset, and is either closed by a brace at `close`
+    /// (in the previous source), or runs until the end of the program.
+    ///
+    /// Returns `None` if the list can't be reparsed in isolation, which
+    /// happens when the edit moves the closing brace.
+    fn reparse_statements(
+        &mut self,
+        change: &Change,
+        old: StatementList<'ast>,
+        start: u32,
+        close: Option<u32>,
+    ) -> Option<StatementList<'ast>> {
+        let old: Vec<StatementNode<'ast>> = old.iter().cloned().collect();
+
+        // An edit within the body of a function declaration only
+        // requires that body to be parsed again.
+        for (index, statement) in old.iter().enumerate() {
+            if statement.start >= change.start {
+                break;
+            }
+
+            let function = match statement.item {
+                Statement::Function(ref function) => function,
+                _                                 => continue,
+            };
+
+            if function.body.start >= change.start || change.old_end >= function.body.end {
+                continue;
+            }
+
+            if let Some(function) = self.reparse_function_body(change, function) {
+                let statement = self.alloc_at_loc(statement.start, change.shift(statement.end), function);
+
+                let body = old[..index].iter().cloned()
+                    .chain(Some(statement))
+                    .chain(old[index + 1..].iter().map(|statement| statement.relocate(self.arena, change)));
+
+                return Some(NodeList::from_iter(self.arena, body));
+            }
+
+            break;
+        }
+
+        // Resume parsing from the last statement before the edit whose
+        // first token remains intact, or from the start of the list.
+        let resume = old.iter().enumerate().rev()
+            .filter(|(_, statement)| statement.start < change.start && is_resumable(statement))
+            .find(|(_, statement)| {
+                self.lexer.seek(statement.start as usize);
+                self.lexer.end() < change.start
+            })
+            .map(|(index, _)| index);
+
+        let mut body = match resume {
+            Some(index) => old[..index].to_vec(),
+            None => {
+                self.lexer.seek(start as usize);
+
+                Vec::new()
+            }
+        };
+
+        loop {
+            // Once past the edit, the remaining statements can be reused
+            // as soon as parsing lines up with one of them.
+            let token_start = self.lexer.start();
+
+            if token_start >= change.new_end {
+                let old_start = (token_start as i64 - change.delta) as u32;
+
+                if let Ok(index) = old.binary_search_by_key(&old_start, |statement| statement.start) {
+                    if is_resumable(&old[index]) {
+                        body.extend(old[index..].iter().map(|statement| statement.relocate(self.arena, change)));
+
+                        break;
+                    }
+                }
+            }
+
+            match self.lexer.token {
+                EndOfProgram                  => break,
+                BraceClose if close.is_some() => break,
+                _                             => body.push(self.statement()),
+            }
+        }
+
+        if let Some(close) = close {
+            if self.lexer.token != BraceClose || self.lexer.start() != change.shift(close) {
+                return None;
+            }
+        }
+
+        Some(NodeList::from_iter(self.arena, body))
+    }
+
+    fn reparse_function_body(
+        &mut self,
+        change: &Change,
+        function: &Function<'ast, MandatoryName<'ast>>,
+    ) -> Option<Function<'ast, MandatoryName<'ast>>> {
+        let block = function.body;
+
+        let body = self.function_body(|par| {
+            par.depth += 1;
+
+            let body = par.reparse_statements(change, block.body, block.start + 1, Some(block.end - 1));
+
+            par.depth -= 1;
+
+            body
+        });
+
+        Some(Function {
+            body: self.alloc_at_loc(block.start, change.shift(block.end), Block { body: body? }),
+            ..*function
+        })
+    }
+}
+
+/// Parse the source of a `module` again after applying `edits` to it,
+/// reusing the parts of the AST the edits can't affect. The result is
+/// identical to parsing the edited source from scratch.
+///
+/// On error the `module` is left untouched. New nodes are allocated in
+/// the arena of the `module`, and the memory of replaced nodes is only
+/// reclaimed once the `module` is dropped.
+///
+/// Panics if any of the `edits` is out of bounds for the source.
+pub fn reparse(module: &mut Module<'_>, source: &str, edits: &[TextEdit]) -> Result<(), Vec<Error>> {
+    let mut edited = source.to_owned();
+
+    for edit in edits {
+        edit.apply(&mut edited);
+    }
+
+    let change = Change::new(source, &edited);
+
+    if change.start == change.old_end && change.start == change.new_end {
+        return Ok(());
+    }
+
+    let body = {
+        let mut parser = Parser::with_options(&edited, &module.arena, module.options);
+        let old = unsafe { module.body.get().into_list() };
+
+        match parser.reparse_statements(&change, old, 0, None) {
+            Some(body) if parser.errors.is_empty() => Ok(body.into_unsafe()),
+            _ => {
+                let mut parser = Parser::with_options(&edited, &module.arena, module.options);
+
+                parser.parse();
+
+                match parser.errors.len() {
+                    0 => Ok(parser.body.into_unsafe()),
+                    _ => Err(parser.errors)
+                }
+            }
+        }
+    };
+
+    module.body.set(body?);
+
+    Ok(())
+}
+
+/// Copy of an AST node with all of its `Loc`s moved past a `Change`.
+trait Relocate<'ast> {
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self;
+}
+
+impl<'ast, T> Relocate<'ast> for Node<'ast, T> where
+    T: Relocate<'ast> + Copy,
+{
+    #[inline]
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+        Node::new(arena.alloc(Loc::new(
+            change.shift(self.start),
+            change.shift(self.end),
+            self.item.relocate(arena, change),
+        )))
+    }
+}
+
+impl<'ast, T> Relocate<'ast> for NodeList<'ast, T> where
+    T: Relocate<'ast> + Copy,
+{
+    #[inline]
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+        NodeList::from_iter(arena, self.iter().map(|node| node.relocate(arena, change)))
+    }
+}
+
+impl<'ast, T: Relocate<'ast>> Relocate<'ast> for Option<T> {
+    #[inline]
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+        self.as_ref().map(|item| item.relocate(arena, change))
+    }
+}
+
+macro_rules! relocate_copy {
+    ($( $type:ty ),*) => ($(
+        impl<'ast> Relocate<'ast> for $type {
+            #[inline]
+            fn relocate(&self, _: &'ast Arena, _: &Change) -> Self {
+                *self
+            }
+        }
+    )*)
+}
+
+macro_rules! relocate_struct {
+    ($( $type:ident { $( $field:ident ),* } )*) => ($(
+        impl<'ast> Relocate<'ast> for $type<'ast> {
+            #[inline]
+            fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+                $type {
+                    $( $field: self.$field.relocate(arena, change), )*
+                }
+            }
+        }
+    )*)
+}
+
+relocate_copy! {
+    bool, &'ast str, Literal<'ast>, OperatorKind, DeclarationKind, MethodKind,
+    EmptyName, Label<'ast>, ThisExpression, SuperExpression, JSXOpeningFragment, JSXClosingFragment,
+    Primitive, TypeOperator, Modifiers, TypeImportKind
+}
+
+relocate_struct! {
+    Decorator { expression }
+    SequenceExpression { body }
+    ParenthesizedExpression { expression }
+    ArrayExpression { body }
+    MemberExpression { object, property }
+    MetaPropertyExpression { meta, property }
+    ComputedMemberExpression { object, property }
+    CallExpression { callee, arguments }
+    BinaryExpression { operator, left, right }
+    PrefixExpression { operator, operand }
+    PostfixExpression { operator, operand }
+    ConditionalExpression { test, consequent, alternate }
+    TemplateLiteral { expressions, quasis }
+    TaggedTemplateExpression { tag, quasi }
+    SpreadExpression { argument }
+    AwaitExpression { argument }
+    ArrowExpression { type_parameters, params, return_type, body }
+    AsExpression { expression, annotation }
+    SatisfiesExpression { expression, annotation }
+    TypeCastExpression { expression, annotation }
+    NonNullExpression { expression }
+    TypeAssertion { annotation, expression }
+    InstantiationExpression { expression, arguments }
+    ObjectExpression { body }
+    Declarator { id, init }
+    DeclarationStatement { kind, declarators }
+    ReturnStatement { value }
+    BreakStatement { label }
+    ContinueStatement { label }
+    ThrowStatement { value }
+    IfStatement { test, consequent, alternate }
+    WhileStatement { test, body }
+    DoStatement { body, test }
+    ForStatement { init, test, update, body }
+    ForInStatement { left, right, body }
+    ForOfStatement { left, right, body }
+    CatchClause { param, body }
+    TryStatement { block, handler, finalizer }
+    LabeledStatement { label, body }
+    SwitchStatement { discriminant, cases }
+    SwitchCase { test, consequent }
+    JSXMemberExpression { object, property }
+    JSXAttribute { name, value }
+    JSXSpreadAttribute { argument }
+    JSXExpressionContainer { expression }
+    JSXOpeningElement { name, attributes, self_closing }
+    JSXClosingElement { name }
+    JSXElement { opening, children, closing }
+    JSXFragment { opening, children, closing }
+    TypeParameter { name, constraint, default }
+    FunctionType { type_parameters, params, return_type }
+    InterfaceDeclaration { name, type_parameters, extends, body }
+    TypeAliasDeclaration { name, type_parameters, value }
+    FunctionSignature { name, signature }
+    DeclareStatement { body }
+    OpaqueTypeDeclaration { name, type_parameters, supertype, value }
+    TypeImportDeclaration { kind, specifiers, source }
+    EnumMember { name, value }
+    EnumDeclaration { is_const, name, members }
+    NamespaceDeclaration { name, body }
+    ExportDeclaration { is_default, declaration }
+}
+
+impl<'ast> Relocate<'ast> for MandatoryName<'ast> {
+    #[inline]
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+        MandatoryName(self.0.relocate(arena, change))
+    }
+}
+
+impl<'ast> Relocate<'ast> for OptionalName<'ast> {
+    #[inline]
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+        OptionalName(self.0.relocate(arena, change))
+    }
+}
+
+impl<'ast, T> Relocate<'ast> for Block<'ast, T> where
+    T: Relocate<'ast> + Copy,
+{
+    #[inline]
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+        Block {
+            body: self.body.relocate(arena, change),
+        }
+    }
+}
+
+impl<'ast, N> Relocate<'ast> for Function<'ast, N> where
+    N: Name<'ast> + Relocate<'ast>,
+{
+    #[inline]
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+        Function {
+            name: self.name.relocate(arena, change),
+            generator: self.generator,
+            type_parameters: self.type_parameters.relocate(arena, change),
+            params: self.params.relocate(arena, change),
+            return_type: self.return_type.relocate(arena, change),
+            body: self.body.relocate(arena, change),
+        }
+    }
+}
+
+impl<'ast, N> Relocate<'ast> for Class<'ast, N> where
+    N: Name<'ast> + Relocate<'ast>,
+{
+    #[inline]
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+        Class {
+            decorators: self.decorators.relocate(arena, change),
+            name: self.name.relocate(arena, change),
+            type_parameters: self.type_parameters.relocate(arena, change),
+            extends: self.extends.relocate(arena, change),
+            super_type_arguments: self.super_type_arguments.relocate(arena, change),
+            implements: self.implements.relocate(arena, change),
+            body: self.body.relocate(arena, change),
+        }
+    }
+}
+
+impl<'ast> Relocate<'ast> for ClassMember<'ast> {
+    #[inline]
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+        match *self {
+            ClassMember::Error => ClassMember::Error,
+            ClassMember::Method { is_static, key, kind, value } => ClassMember::Method {
+                is_static,
+                key: key.relocate(arena, change),
+                kind,
+                value: value.relocate(arena, change),
+            },
+            ClassMember::Literal { is_static, key, value } => ClassMember::Literal {
+                is_static,
+                key: key.relocate(arena, change),
+                value: value.relocate(arena, change),
+            },
+            ClassMember::Property { is_static, key, optional, annotation, value } => ClassMember::Property {
+                is_static,
+                key: key.relocate(arena, change),
+                optional,
+                annotation: annotation.relocate(arena, change),
+                value: value.relocate(arena, change),
+            },
+            ClassMember::Accessor { is_static, key, annotation, value } => ClassMember::Accessor {
+                is_static,
+                key: key.relocate(arena, change),
+                annotation: annotation.relocate(arena, change),
+                value: value.relocate(arena, change),
+            },
+            ClassMember::Signature { is_static, key, kind, signature } => ClassMember::Signature {
+                is_static,
+                key: key.relocate(arena, change),
+                kind,
+                signature: signature.relocate(arena, change),
+            },
+            ClassMember::Modified { modifiers, member } => ClassMember::Modified {
+                modifiers,
+                member: member.relocate(arena, change),
+            },
+            ClassMember::Decorated { decorators, member } => ClassMember::Decorated {
+                decorators: decorators.relocate(arena, change),
+                member: member.relocate(arena, change),
+            },
+        }
+    }
+}
+
+impl<'ast> Relocate<'ast> for PropertyKey<'ast> {
+    #[inline]
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+        match *self {
+            PropertyKey::Computed(expression) => PropertyKey::Computed(expression.relocate(arena, change)),
+            key                               => key,
+        }
+    }
+}
+
+impl<'ast> Relocate<'ast> for Property<'ast> {
+    #[inline]
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+        match *self {
+            Property::Shorthand(name) => Property::Shorthand(name.relocate(arena, change)),
+            Property::Literal { key, value } => Property::Literal {
+                key: key.relocate(arena, change),
+                value: value.relocate(arena, change),
+            },
+            Property::Method { key, value } => Property::Method {
+                key: key.relocate(arena, change),
+                value: value.relocate(arena, change),
+            },
+            Property::Spread { argument } => Property::Spread {
+                argument: argument.relocate(arena, change),
+            },
+        }
+    }
+}
+
+impl<'ast> Relocate<'ast> for Pattern<'ast> {
+    #[inline]
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+        match *self {
+            Pattern::Void => Pattern::Void,
+            Pattern::Identifier(name) => Pattern::Identifier(name.relocate(arena, change)),
+            Pattern::ObjectPattern { properties } => Pattern::ObjectPattern {
+                properties: properties.relocate(arena, change),
+            },
+            Pattern::ArrayPattern { elements } => Pattern::ArrayPattern {
+                elements: elements.relocate(arena, change),
+            },
+            Pattern::RestElement { argument } => Pattern::RestElement {
+                argument: argument.relocate(arena, change),
+            },
+            Pattern::AssignmentPattern { left, right } => Pattern::AssignmentPattern {
+                left: left.relocate(arena, change),
+                right: right.relocate(arena, change),
+            },
+            Pattern::Annotated { pattern, optional, annotation } => Pattern::Annotated {
+                pattern: pattern.relocate(arena, change),
+                optional,
+                annotation: annotation.relocate(arena, change),
+            },
+            Pattern::ParameterProperty { modifiers, parameter } => Pattern::ParameterProperty {
+                modifiers,
+                parameter: parameter.relocate(arena, change),
+            },
+        }
+    }
+}
+
+impl<'ast> Relocate<'ast> for ArrowBody<'ast> {
+    #[inline]
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+        match *self {
+            ArrowBody::Expression(expression) => ArrowBody::Expression(expression.relocate(arena, change)),
+            ArrowBody::Block(block)           => ArrowBody::Block(block.relocate(arena, change)),
+        }
+    }
+}
+
+impl<'ast> Relocate<'ast> for ForInit<'ast> {
+    #[inline]
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+        match *self {
+            ForInit::Declaration(declaration) => ForInit::Declaration(declaration.relocate(arena, change)),
+            ForInit::Expression(expression)   => ForInit::Expression(expression.relocate(arena, change)),
+        }
+    }
+}
+
+impl<'ast> Relocate<'ast> for JSXElementName<'ast> {
+    #[inline]
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+        match *self {
+            JSXElementName::Identifier(name) => JSXElementName::Identifier(name),
+            JSXElementName::Namespaced { namespace, name } => JSXElementName::Namespaced {
+                namespace: namespace.relocate(arena, change),
+                name: name.relocate(arena, change),
+            },
+            JSXElementName::Member(ref member) => JSXElementName::Member(member.relocate(arena, change)),
+        }
+    }
+}
+
+impl<'ast> Relocate<'ast> for JSXAttributeValue<'ast> {
+    #[inline]
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+        match *self {
+            JSXAttributeValue::String(value)        => JSXAttributeValue::String(value),
+            JSXAttributeValue::Expression(ref expr) => JSXAttributeValue::Expression(expr.relocate(arena, change)),
+            JSXAttributeValue::Element(ref element) => JSXAttributeValue::Element(element.relocate(arena, change)),
+            JSXAttributeValue::Fragment(ref frag)   => JSXAttributeValue::Fragment(frag.relocate(arena, change)),
+        }
+    }
+}
+
+impl<'ast> Relocate<'ast> for JSXAttributeItem<'ast> {
+    #[inline]
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+        match *self {
+            JSXAttributeItem::Attribute(ref attribute) => JSXAttributeItem::Attribute(attribute.relocate(arena, change)),
+            JSXAttributeItem::Spread(ref attribute)    => JSXAttributeItem::Spread(attribute.relocate(arena, change)),
+        }
+    }
+}
+
+impl<'ast> Relocate<'ast> for JSXChild<'ast> {
+    #[inline]
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+        match *self {
+            JSXChild::Text(text)            => JSXChild::Text(text),
+            JSXChild::Expression(ref expr)  => JSXChild::Expression(expr.relocate(arena, change)),
+            JSXChild::Element(ref element)  => JSXChild::Element(element.relocate(arena, change)),
+            JSXChild::Fragment(ref frag)    => JSXChild::Fragment(frag.relocate(arena, change)),
+        }
+    }
+}
+
+impl<'ast> Relocate<'ast> for Expression<'ast> {
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+        use self::Expression::*;
+
+        match *self {
+            Void                       => Void,
+            This(this)                 => This(this),
+            Super(this)                => Super(this),
+            Identifier(name)           => Identifier(name),
+            Literal(literal)           => Literal(literal),
+            Sequence(ref expr)         => Sequence(expr.relocate(arena, change)),
+            Parenthesized(ref expr)    => Parenthesized(expr.relocate(arena, change)),
+            Array(ref expr)            => Array(expr.relocate(arena, change)),
+            Member(ref expr)           => Member(expr.relocate(arena, change)),
+            ComputedMember(ref expr)   => ComputedMember(expr.relocate(arena, change)),
+            MetaProperty(ref expr)     => MetaProperty(expr.relocate(arena, change)),
+            Call(ref expr)             => Call(expr.relocate(arena, change)),
+            Binary(ref expr)           => Binary(expr.relocate(arena, change)),
+            Prefix(ref expr)           => Prefix(expr.relocate(arena, change)),
+            Postfix(ref expr)          => Postfix(expr.relocate(arena, change)),
+            Conditional(ref expr)      => Conditional(expr.relocate(arena, change)),
+            Template(ref expr)         => Template(expr.relocate(arena, change)),
+            TaggedTemplate(ref expr)   => TaggedTemplate(expr.relocate(arena, change)),
+            Spread(ref expr)           => Spread(expr.relocate(arena, change)),
+            Await(ref expr)            => Await(expr.relocate(arena, change)),
+            Arrow(ref expr)            => Arrow(expr.relocate(arena, change)),
+            Object(ref expr)           => Object(expr.relocate(arena, change)),
+            Function(ref expr)         => Function(expr.relocate(arena, change)),
+            Class(ref expr)            => Class(expr.relocate(arena, change)),
+            JSXElement(ref expr)       => JSXElement(expr.relocate(arena, change)),
+            JSXFragment(ref expr)      => JSXFragment(expr.relocate(arena, change)),
+            As(ref expr)               => As(expr.relocate(arena, change)),
+            Satisfies(ref expr)        => Satisfies(expr.relocate(arena, change)),
+            TypeCast(ref expr)         => TypeCast(expr.relocate(arena, change)),
+            NonNull(ref expr)          => NonNull(expr.relocate(arena, change)),
+            TypeAssertion(ref expr)    => TypeAssertion(expr.relocate(arena, change)),
+            Instantiation(ref expr)    => Instantiation(expr.relocate(arena, change)),
+        }
+    }
+}
+
+impl<'ast> Relocate<'ast> for Statement<'ast> {
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+        use self::Statement::*;
+
+        match *self {
+            Empty                   => Empty,
+            Expression(ref stmt)    => Expression(stmt.relocate(arena, change)),
+            Declaration(ref stmt)   => Declaration(stmt.relocate(arena, change)),
+            Return(ref stmt)        => Return(stmt.relocate(arena, change)),
+            Break(ref stmt)         => Break(stmt.relocate(arena, change)),
+            Continue(ref stmt)      => Continue(stmt.relocate(arena, change)),
+            Throw(ref stmt)         => Throw(stmt.relocate(arena, change)),
+            If(ref stmt)            => If(stmt.relocate(arena, change)),
+            While(ref stmt)         => While(stmt.relocate(arena, change)),
+            Do(ref stmt)            => Do(stmt.relocate(arena, change)),
+            For(ref stmt)           => For(stmt.relocate(arena, change)),
+            ForIn(ref stmt)         => ForIn(stmt.relocate(arena, change)),
+            ForOf(ref stmt)         => ForOf(stmt.relocate(arena, change)),
+            Try(ref stmt)           => Try(stmt.relocate(arena, change)),
+            Block(ref stmt)         => Block(stmt.relocate(arena, change)),
+            Labeled(ref stmt)       => Labeled(stmt.relocate(arena, change)),
+            Function(ref stmt)      => Function(stmt.relocate(arena, change)),
+            Class(ref stmt)         => Class(stmt.relocate(arena, change)),
+            Switch(ref stmt)        => Switch(stmt.relocate(arena, change)),
+            Interface(ref stmt)     => Interface(stmt.relocate(arena, change)),
+            TypeAlias(ref stmt)     => TypeAlias(stmt.relocate(arena, change)),
+            FunctionSignature(ref stmt) => FunctionSignature(stmt.relocate(arena, change)),
+            Declare(ref stmt)       => Declare(stmt.relocate(arena, change)),
+            OpaqueType(ref stmt)    => OpaqueType(stmt.relocate(arena, change)),
+            TypeImport(ref stmt)    => TypeImport(stmt.relocate(arena, change)),
+            Enum(ref stmt)          => Enum(stmt.relocate(arena, change)),
+            Namespace(ref stmt)     => Namespace(stmt.relocate(arena, change)),
+            Export(ref stmt)        => Export(stmt.relocate(arena, change)),
+        }
+    }
+}
+
+impl<'ast> Relocate<'ast> for TypeImportSpecifier<'ast> {
+    #[inline]
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+        match *self {
+            TypeImportSpecifier::Default(local)   => TypeImportSpecifier::Default(local.relocate(arena, change)),
+            TypeImportSpecifier::Namespace(local) => TypeImportSpecifier::Namespace(local.relocate(arena, change)),
+            TypeImportSpecifier::Named { imported, local } => TypeImportSpecifier::Named {
+                imported: imported.relocate(arena, change),
+                local: local.relocate(arena, change),
+            },
+        }
+    }
+}
+
+impl<'ast> Relocate<'ast> for TypeName<'ast> {
+    #[inline]
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+        match *self {
+            TypeName::Identifier(name) => TypeName::Identifier(name),
+            TypeName::Qualified { left, right } => TypeName::Qualified {
+                left: left.relocate(arena, change),
+                right: right.relocate(arena, change),
+            },
+        }
+    }
+}
+
+impl<'ast> Relocate<'ast> for TypeMember<'ast> {
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+        match *self {
+            TypeMember::Property { readonly, key, optional, annotation } => TypeMember::Property {
+                readonly,
+                key: key.relocate(arena, change),
+                optional,
+                annotation: annotation.relocate(arena, change),
+            },
+            TypeMember::Method { key, optional, signature } => TypeMember::Method {
+                key: key.relocate(arena, change),
+                optional,
+                signature: signature.relocate(arena, change),
+            },
+            TypeMember::Index { readonly, parameter, key, annotation } => TypeMember::Index {
+                readonly,
+                parameter: parameter.relocate(arena, change),
+                key: key.relocate(arena, change),
+                annotation: annotation.relocate(arena, change),
+            },
+            TypeMember::Call { signature } => TypeMember::Call {
+                signature: signature.relocate(arena, change),
+            },
+            TypeMember::Construct { signature } => TypeMember::Construct {
+                signature: signature.relocate(arena, change),
+            },
+            TypeMember::Spread { argument } => TypeMember::Spread {
+                argument: argument.relocate(arena, change),
+            },
+        }
+    }
+}
+
+impl<'ast> Relocate<'ast> for Type<'ast> {
+    fn relocate(&self, arena: &'ast Arena, change: &Change) -> Self {
+        use self::Type::*;
+
+        match *self {
+            Any                        => Any,
+            Unknown                    => Unknown,
+            Never                      => Never,
+            Void                       => Void,
+            Object                     => Object,
+            This                       => This,
+            Primitive(primitive)       => Primitive(primitive),
+            Literal(literal)           => Literal(literal),
+            Reference { name, arguments } => Reference {
+                name: name.relocate(arena, change),
+                arguments: arguments.relocate(arena, change),
+            },
+            Query { name } => Query {
+                name: name.relocate(arena, change),
+            },
+            Array { element } => Array {
+                element: element.relocate(arena, change),
+            },
+            Tuple { elements } => Tuple {
+                elements: elements.relocate(arena, change),
+            },
+            Optional { argument } => Optional {
+                argument: argument.relocate(arena, change),
+            },
+            Rest { argument } => Rest {
+                argument: argument.relocate(arena, change),
+            },
+            Union { variants } => Union {
+                variants: variants.relocate(arena, change),
+            },
+            Intersection { variants } => Intersection {
+                variants: variants.relocate(arena, change),
+            },
+            Function(ref signature)    => Function(signature.relocate(arena, change)),
+            Constructor(ref signature) => Constructor(signature.relocate(arena, change)),
+            ObjectLiteral { members, exact } => ObjectLiteral {
+                members: members.relocate(arena, change),
+                exact,
+            },
+            Mapped { readonly, parameter, constraint, optional, annotation } => Mapped {
+                readonly,
+                parameter: parameter.relocate(arena, change),
+                constraint: constraint.relocate(arena, change),
+                optional,
+                annotation: annotation.relocate(arena, change),
+            },
+            Operator { operator, argument } => Operator {
+                operator,
+                argument: argument.relocate(arena, change),
+            },
+            IndexedAccess { object, index } => IndexedAccess {
+                object: object.relocate(arena, change),
+                index: index.relocate(arena, change),
+            },
+            Conditional { check, extends, consequent, alternate } => Conditional {
+                check: check.relocate(arena, change),
+                extends: extends.relocate(arena, change),
+                consequent: consequent.relocate(arena, change),
+                alternate: alternate.relocate(arena, change),
+            },
+            Infer { parameter } => Infer {
+                parameter: parameter.relocate(arena, change),
+            },
+            Nullable { argument } => Nullable {
+                argument: argument.relocate(arena, change),
+            },
+            Predicate { parameter, annotation } => Predicate {
+                parameter: parameter.relocate(arena, change),
+                annotation: annotation.relocate(arena, change),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::{parse, parse_with_options, ParseOptions};
+
+    fn assert_reparse(source: &str, edits: &[TextEdit]) -> Module<'static> {
+        assert_reparse_with_options(source, edits, ParseOptions::default())
+    }
+
+    fn assert_reparse_with_options(source: &str, edits: &[TextEdit], options: ParseOptions) -> Module<'static> {
+        let mut module = parse_with_options(source, options).unwrap();
+        let mut edited = source.to_owned();
+
+        for edit in edits {
+            edit.apply(&mut edited);
+        }
+
+        reparse(&mut module, source, edits).unwrap();
+
+        // `Loc`s are ignored by `PartialEq`, but not by `Debug`
+        assert_eq!(
+            format!("{:#?}", module.body()),
+            format!("{:#?}", parse_with_options(&edited, options).unwrap().body())
+        );
+
+        module
+    }
+
+    fn ptr<T>(node: Option<&Node<'_, T>>) -> *const Loc<T> {
+        &**node.unwrap()
+    }
+
+    #[test]
+    fn reparse_reuses_statements_around_edit() {
+        let source = "var a = 1;\nvar b = 2;\nvar c = 3;";
+        let module = parse(source).unwrap();
+        let first = ptr(module.body().iter().next());
+
+        let mut module = module;
+
+        reparse(&mut module, source, &[TextEdit::new(19, 20, "20")]).unwrap();
+
+        assert_eq!(ptr(module.body().iter().next()), first);
+
+        assert_reparse(source, &[TextEdit::new(19, 20, "20")]);
+        assert_reparse(source, &[TextEdit::new(0, 0, "foo();\n")]);
+        assert_reparse(source, &[TextEdit::new(32, 32, "\nvar d = 4;")]);
+    }
+
+    #[test]
+    fn reparse_function_body() {
+        let source = "function foo() {\n  var a = 1;\n  return a;\n}\nfoo();";
+        let module = parse(source).unwrap();
+
+        let inner = match module.body().iter().next().unwrap().item {
+            Statement::Function(ref function) => ptr(function.body.body.iter().next()),
+            _ => unreachable!(),
+        };
+
+        let mut module = module;
+
+        reparse(&mut module, source, &[TextEdit::new(39, 40, "a + 1")]).unwrap();
+
+        match module.body().iter().next().unwrap().item {
+            Statement::Function(ref function) => {
+                assert_eq!(ptr(function.body.body.iter().next()), inner);
+            },
+            _ => panic!("Expected a function declaration"),
+        }
+
+        assert_reparse(source, &[TextEdit::new(39, 40, "a + 1")]);
+        assert_reparse(source, &[TextEdit::new(17, 17, "  function bar() {}\n")]);
+        assert_reparse(source, &[TextEdit::new(41, 41, "\n}\nfunction bar() {")]);
+    }
+
+    #[test]
+    fn reparse_function_body_with_options() {
+        let options = ParseOptions { top_level_await: true, ..ParseOptions::default() };
+        let source = "await a;\nfunction foo() {\n  await(1);\n}\nfoo();";
+
+        // `await` is an identifier within the function
+        assert_reparse_with_options(source, &[TextEdit::new(34, 35, "2")], options);
+        assert_reparse_with_options(source, &[TextEdit::new(26, 26, "  await;\n")], options);
+        assert!(reparse(&mut parse_with_options(source, options).unwrap(), source, &[TextEdit::new(33, 36, " b")]).is_err());
+    }
+
+    #[test]
+    fn reparse_multiple_edits() {
+        let source = "a = 1;\nb = 2;\nc = 3;";
+
+        assert_reparse(source, &[
+            TextEdit::new(0, 1, "foo"),
+            TextEdit::new(16, 17, "bar"),
+        ]);
+    }
+
+    #[test]
+    fn reparse_error() {
+        let source = "var a = 1;\nvar b = 2;";
+        let mut module = parse(source).unwrap();
+        let expected = format!("{:#?}", module.body());
+
+        assert!(reparse(&mut module, source, &[TextEdit::new(8, 9, "(")]).is_err());
+        assert_eq!(format!("{:#?}", module.body()), expected);
+    }
+
+    const SOURCE: &str = r#"
+var a = 1, b = [1, 2, 3], c = { foo: 'bar', baz() { return 1; } };
+
+function add(a, b = 2, ...rest) {
+    if (a > b) {
+        return a + b * 2;
+    } else if (a) {
+        return -a;
+    }
+
+    for (let i = 0; i < 10; i++) {
+        b += i;
+    }
+
+    function nested([x, y], { z }) {
+        return x ? y : z;
+    }
+
+    return nested;
+}
+
+(a + b) * c;
+
+const arrow = (x, y) => x * y;
+let template = `foo ${ a + b } bar ${ c }`;
+
+class Foo extends Bar {
+    constructor() {
+        this.foo = 1;
+    }
+
+    static method(a) {
+        return this.a[a];
+    }
+}
+
+label: while (true) {
+    switch (a) {
+        case 1:
+            break label;
+        default:
+            continue;
+    }
+}
+
+try {
+    throw new Error("oops");
+} catch (err) {
+    do a--; while (a > 0)
+} finally {
+    c = /regex/g.test(a)
+}
+
+for (var key in c) foo(key);
+for (let item of b) { bar(item) }
+"#;
+
+    const SNIPPETS: &[&str] = &[
+        "", "", "a", "1", ";", "\n", " ", "(", ")", "{", "}", "[", "]", ",", "+", "=",
+        "foo", "var x = 1;", "function g() { return 2; }", "if (a) b;", "'str'",
+        "/* comment */", "// comment\n", "`t${x}`", "return", ".", "=>", "\n(a)", "}\n{",
+    ];
+
+    struct Random(u64);
+
+    impl Random {
+        fn next(&mut self, max: usize) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+
+            (self.0 % max as u64) as usize
+        }
+    }
+
+    #[test]
+    fn reparse_random_edits() {
+        let mut random = Random(0x2545_F491_4F6C_DD1D);
+        let mut source = SOURCE.to_owned();
+        let mut module = parse(&source).unwrap();
+
+        let mut reparsed = 0;
+
+        for _ in 0..2000 {
+            let mut edited = source.clone();
+
+            let edits: Vec<TextEdit> = (0..random.next(2) + 1).map(|_| {
+                let start = random.next(edited.len() + 1);
+                let end = (start + random.next(4)).min(edited.len());
+                let edit = TextEdit::new(start, end, SNIPPETS[random.next(SNIPPETS.len())]);
+
+                edit.apply(&mut edited);
+                edit
+            }).collect();
+
+            match parse(&edited) {
+                Ok(expected) => {
+                    reparse(&mut module, &source, &edits).unwrap();
+
+                    assert_eq!(
+                        format!("{:?}", module.body()),
+                        format!("{:?}", expected.body()),
+                        "Reparsing {:?} after {:?}", source, edits
+                    );
+
+                    source = edited;
+                    reparsed += 1;
+                },
+                Err(_) => {
+                    assert!(reparse(&mut module, &source, &edits).is_err());
+                }
+            }
+
+            // Keep the source from drifting too far away from valid code
+            if source.len() > SOURCE.len() * 2 || random.next(200) == 0 {
+                source = SOURCE.to_owned();
+                module = parse(&source).unwrap();
+            }
+        }
+
+        assert!(reparsed > 200, "Only {} edits produced valid source", reparsed);
+    }
+}
@@ -0,0 +1,602 @@
+use toolshed::Arena;
+
+use crate::parser::Parser;
+use crate::parser::error::ToError;
+use crate::error::{Error, ErrorKind};
+use crate::lexer::Token::{self, *};
+use crate::ast::{Node, NodeList, ExpressionNode, Literal, OperatorKind, Property, PropertyKey};
+use crate::ast::expression::{ObjectExpression, ArrayExpression, PrefixExpression};
+
+/// Options for `parse_json`. With all of them disabled, which is the
+/// default, only strict JSON is accepted.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonOptions {
+    /// Allow `//` and `/* */` comments, as in `tsconfig.json`.
+    pub comments: bool,
+
+    /// Allow a comma after the last element of arrays and objects.
+    pub trailing_commas: bool,
+
+    /// Accept JSON5, which on top of comments and trailing commas allows
+    /// identifiers and single quoted strings as keys, single quoted strings,
+    /// hexadecimal numbers, leading and trailing decimal points, explicit
+    /// `+` signs, `Infinity` and `NaN`.
+    pub json5: bool,
+}
+
+struct JsonParser<'src, 'ast> {
+    par: Parser<'ast>,
+
+    source: &'src str,
+
+    options: JsonOptions,
+}
+
+impl<'src, 'ast> JsonParser<'src, 'ast> {
+    fn new(source: &'src str, arena: &'ast Arena, options: JsonOptions) -> Self {
+        let mut json = JsonParser {
+            par: Parser::new(source, arena),
+            source,
+            options,
+        };
+
+        json.check_trivia(0);
+
+        json
+    }
+
+    #[inline]
+    fn comments(&self) -> bool {
+        self.options.comments || self.options.json5
+    }
+
+    #[inline]
+    fn trailing_commas(&self) -> bool {
+        self.options.trailing_commas || self.options.json5
+    }
+
+    /// Advance to the next token, checking what the lexer skipped on the way.
+    #[inline]
+    fn next(&mut self) {
+        let end = self.par.lexer.end();
+
+        self.par.lexer.consume();
+        self.check_trivia(end);
+    }
+
+    #[inline]
+    fn expect(&mut self, token: Token) {
+        match self.par.lexer.token {
+            t if t == token => self.next(),
+            _               => self.par.error::<()>(),
+        }
+    }
+
+    /// Report an error spanning the given part of the source.
+    fn error_at(&mut self, token: Token, start: usize, end: usize) {
+        self.par.errors.push(Error {
+            kind: ErrorKind::UnexpectedToken,
+            token,
+            raw: self.source[start..end].into(),
+            start,
+            end,
+        });
+    }
+
+    /// Only whitespace may separate tokens in strict JSON, JSON5 has no
+    /// restrictions beyond those of the lexer.
+    fn check_trivia(&mut self, end: u32) {
+        // Once a token has been skipped as erroneous, the trivia leading
+        // to the current token include it, so there's no point checking.
+        if self.options.json5 || !self.par.errors.is_empty() {
+            return;
+        }
+
+        let start = self.par.lexer.start() as usize;
+        let trivia = &self.source.as_bytes()[end as usize..start];
+        let mut index = 0;
+
+        while index < trivia.len() {
+            let comment = match (trivia[index], trivia.get(index + 1)) {
+                (b' ', _) | (b'\t', _) | (b'\n', _) | (b'\r', _) => {
+                    index += 1;
+                    continue;
+                },
+                (b'/', Some(b'/')) => trivia[index..].iter().position(|&byte| byte == b'\n'),
+                (b'/', Some(b'*')) => trivia[index..].windows(2).position(|pair| pair == b"*/").map(|end| end + 2),
+                _ => {
+                    let at = end as usize + index;
+                    let len = self.source[at..].chars().next().map_or(1, char::len_utf8);
+
+                    return self.error_at(UnexpectedToken, at, at + len);
+                }
+            };
+
+            let length = comment.unwrap_or(trivia.len() - index);
+
+            if !self.comments() {
+                let at = end as usize + index;
+
+                return self.error_at(UnexpectedToken, at, at + length);
+            }
+
+            index += length;
+        }
+    }
+
+    fn value(&mut self) -> ExpressionNode<'ast> {
+        match self.par.lexer.token {
+            BraceOpen   => self.object(),
+            BracketOpen => self.array(),
+            LiteralString if self.is_valid_string() => self.literal(Literal::String(self.par.lexer.token_as_str())),
+            LiteralNumber |
+            LiteralBinary if self.is_valid_number() => self.literal(Literal::Number(self.par.lexer.token_as_str())),
+            LiteralTrue  => self.literal(Literal::True),
+            LiteralFalse => self.literal(Literal::False),
+            LiteralNull  => self.literal(Literal::Null),
+            OperatorSubtraction => self.signed(OperatorKind::Subtraction),
+            OperatorAddition if self.options.json5 => self.signed(OperatorKind::Addition),
+            Identifier if self.is_json5_number_word() => {
+                let ident = self.par.lexer.token_as_str();
+                let expression = self.par.alloc_in_loc(ident);
+
+                self.next();
+
+                expression
+            },
+            _ => self.par.error(),
+        }
+    }
+
+    #[inline]
+    fn literal(&mut self, literal: Literal<'ast>) -> ExpressionNode<'ast> {
+        let expression = self.par.alloc_in_loc(literal);
+
+        self.next();
+
+        expression
+    }
+
+    /// `-1`, or in JSON5 `+1`, `-Infinity` and the like. The sign is
+    /// part of the number, no whitespace may follow it.
+    fn signed(&mut self, operator: OperatorKind) -> ExpressionNode<'ast> {
+        let (start, end) = self.par.lexer.loc();
+
+        self.next();
+
+        if self.par.lexer.start() != end {
+            return self.par.error();
+        }
+
+        let operand = match self.par.lexer.token {
+            LiteralNumber |
+            LiteralBinary if self.is_valid_number() => self.literal(Literal::Number(self.par.lexer.token_as_str())),
+            Identifier if self.is_json5_number_word() => {
+                let ident = self.par.lexer.token_as_str();
+                let expression = self.par.alloc_in_loc(ident);
+
+                self.next();
+
+                expression
+            },
+            _ => return self.par.error(),
+        };
+
+        self.par.alloc_at_loc(start, operand.end, PrefixExpression {
+            operator,
+            operand,
+        })
+    }
+
+    #[inline]
+    fn is_json5_number_word(&self) -> bool {
+        self.options.json5 && matches!(self.par.lexer.token_as_str(), "Infinity" | "NaN")
+    }
+
+    fn is_valid_number(&self) -> bool {
+        let raw = self.par.lexer.token_as_str();
+
+        // Binary literals are the only ones the lexer tells apart
+        if self.par.lexer.token == LiteralBinary {
+            return false;
+        }
+
+        if self.options.json5 {
+            // JSON5 takes hexadecimal numbers, but neither octal ones
+            // nor any other number with a leading zero
+            let bytes = raw.as_bytes();
+            let leading_zero = bytes.len() > 1 && bytes[0] == b'0' && match bytes[1] {
+                b'x' | b'X' | b'e' | b'E' => false,
+                byte                      => byte.is_ascii_alphanumeric(),
+            };
+
+            return !leading_zero && !raw.contains('_');
+        }
+
+        is_json_number(raw)
+    }
+
+    fn is_valid_string(&self) -> bool {
+        let raw = self.par.lexer.token_as_str();
+        let json5 = self.options.json5;
+
+        if !json5 && !raw.starts_with('"') {
+            return false;
+        }
+
+        let mut bytes = raw[1..raw.len() - 1].bytes();
+
+        while let Some(byte) = bytes.next() {
+            match byte {
+                b'\\' => match bytes.next() {
+                    Some(b'"') | Some(b'\\') | Some(b'/') | Some(b'b') |
+                    Some(b'f') | Some(b'n') | Some(b'r') | Some(b't') => {},
+                    Some(b'u') => {
+                        let hex = bytes.by_ref().take(4).filter(u8::is_ascii_hexdigit).count();
+
+                        if hex != 4 {
+                            return false;
+                        }
+                    },
+                    Some(b'1'..=b'9') => return false,
+                    Some(_) if json5 => {},
+                    _ => return false,
+                },
+                b'\n' | b'\r' => return false,
+                0..=0x1F if !json5 => return false,
+                _ => {},
+            }
+        }
+
+        true
+    }
+
+    fn object(&mut self) -> ExpressionNode<'ast> {
+        if !self.par.descend() {
+            return ExpressionNode::to_error();
+        }
+
+        let start = self.par.lexer.start();
+        let mut properties = Vec::new();
+
+        self.next();
+
+        while self.par.lexer.token != BraceClose {
+            properties.push(self.property());
+
+            if self.par.lexer.token != Comma {
+                break;
+            }
+
+            let (comma_start, comma_end) = self.par.lexer.loc();
+
+            self.next();
+
+            if self.par.lexer.token == BraceClose && !self.trailing_commas() {
+                self.error_at(Comma, comma_start as usize, comma_end as usize);
+            }
+        }
+
+        let end = self.par.lexer.end();
+
+        self.expect(BraceClose);
+        self.par.depth -= 1;
+
+        self.par.alloc_at_loc(start, end, ObjectExpression {
+            body: NodeList::from_iter(self.par.arena, properties),
+        })
+    }
+
+    fn property(&mut self) -> Node<'ast, Property<'ast>> {
+        let start = self.par.lexer.start();
+
+        let key = match self.par.lexer.token {
+            LiteralString if self.is_valid_string() => {
+                let key = self.par.alloc_in_loc(PropertyKey::Literal(self.par.lexer.token_as_str()));
+
+                self.next();
+
+                key
+            },
+            token if token.is_word() && self.options.json5 => {
+                let key = self.par.alloc_in_loc(PropertyKey::Literal(self.par.lexer.token_as_str()));
+
+                self.next();
+
+                key
+            },
+            _ => self.par.error(),
+        };
+
+        self.expect(Colon);
+
+        let value = self.value();
+
+        self.par.alloc_at_loc(start, value.end, Property::Literal {
+            key,
+            value,
+        })
+    }
+
+    fn array(&mut self) -> ExpressionNode<'ast> {
+        if !self.par.descend() {
+            return ExpressionNode::to_error();
+        }
+
+        let start = self.par.lexer.start();
+        let mut elements = Vec::new();
+
+        self.next();
+
+        while self.par.lexer.token != BracketClose {
+            elements.push(self.value());
+
+            if self.par.lexer.token != Comma {
+                break;
+            }
+
+            let (comma_start, comma_end) = self.par.lexer.loc();
+
+            self.next();
+
+            if self.par.lexer.token == BracketClose && !self.trailing_commas() {
+                self.error_at(Comma, comma_start as usize, comma_end as usize);
+            }
+        }
+
+        let end = self.par.lexer.end();
+
+        self.expect(BracketClose);
+        self.par.depth -= 1;
+
+        self.par.alloc_at_loc(start, end, ArrayExpression {
+            body: NodeList::from_iter(self.par.arena, elements),
+        })
+    }
+}
+
+/// `-?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?`, less the sign which
+/// the lexer produces as a separate token.
+fn is_json_number(raw: &str) -> bool {
+    let bytes = raw.as_bytes();
+    let digits = |from: usize| bytes[from..].iter().take_while(|byte| byte.is_ascii_digit()).count();
+
+    let mut index = match bytes.first() {
+        Some(b'0')         => 1,
+        Some(b'1'..=b'9')  => 1 + digits(1),
+        _                  => return false,
+    };
+
+    if bytes.get(index) == Some(&b'.') {
+        let fraction = digits(index + 1);
+
+        if fraction == 0 {
+            return false;
+        }
+
+        index += 1 + fraction;
+    }
+
+    if let Some(b'e') | Some(b'E') = bytes.get(index) {
+        index += 1;
+
+        if let Some(b'+') | Some(b'-') = bytes.get(index) {
+            index += 1;
+        }
+
+        let exponent = digits(index);
+
+        if exponent == 0 {
+            return false;
+        }
+
+        index += exponent;
+    }
+
+    index == bytes.len()
+}
+
+/// Parse a JSON document, allocating it in the provided `Arena`. The
+/// document is produced as an expression, built from `ObjectExpression`s,
+/// `ArrayExpression`s and `Literal`s. Negative numbers are `PrefixExpression`s,
+/// `Infinity` and `NaN` of JSON5 are identifiers.
+pub fn parse_json<'ast>(source: &str, arena: &'ast Arena, options: JsonOptions) -> Result<ExpressionNode<'ast>, Vec<Error>> {
+    let mut json = JsonParser::new(source, arena, options);
+    let value = json.value();
+
+    json.par.finish(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::mock::Mock;
+    use crate::ast::Expression;
+
+    fn errors(source: &str, options: JsonOptions) -> Vec<(Token, usize, usize)> {
+        let arena = Arena::new();
+
+        match parse_json(source, &arena, options) {
+            Ok(_) => Vec::new(),
+            Err(errors) => errors.iter().map(|err| (err.token, err.start, err.end)).collect(),
+        }
+    }
+
+    fn strict(source: &str) -> bool {
+        errors(source, JsonOptions::default()).is_empty()
+    }
+
+    fn json5(source: &str) -> bool {
+        errors(source, JsonOptions { json5: true, ..JsonOptions::default() }).is_empty()
+    }
+
+    #[test]
+    fn document() {
+        let arena = Arena::new();
+        let mock = Mock::new();
+
+        let expected = mock.ptr(ObjectExpression {
+            body: mock.list([
+                Property::Literal {
+                    key: mock.ptr(PropertyKey::Literal("\"name\"")),
+                    value: mock.ptr(Literal::String("\"esr\"")),
+                },
+                Property::Literal {
+                    key: mock.ptr(PropertyKey::Literal("\"tags\"")),
+                    value: mock.ptr(ArrayExpression {
+                        body: mock.list([
+                            Expression::Literal(Literal::Number("1.5e3")),
+                            Expression::Prefix(PrefixExpression {
+                                operator: OperatorKind::Subtraction,
+                                operand: mock.ptr(Literal::Number("0")),
+                            }),
+                            Expression::Literal(Literal::True),
+                            Expression::Literal(Literal::Null),
+                        ]),
+                    }),
+                },
+            ]),
+        });
+
+        let source = r#"{ "name": "esr", "tags": [1.5e3, -0, true, null] }"#;
+
+        assert_eq!(parse_json(source, &arena, JsonOptions::default()), Ok(expected));
+    }
+
+    #[test]
+    fn spans() {
+        let arena = Arena::new();
+        let source = "{\n  \"a\": [1, 2]\n}";
+        let value = parse_json(source, &arena, JsonOptions::default()).unwrap();
+
+        assert_eq!((value.start, value.end), (0, 17));
+
+        match value.item {
+            Expression::Object(ObjectExpression { body }) => {
+                let property = body.only_element().unwrap();
+
+                assert_eq!((property.start, property.end), (4, 15));
+            },
+            _ => panic!("Document isn't an object!"),
+        }
+    }
+
+    #[test]
+    fn serializes_to_estree() {
+        let arena = Arena::new();
+        let value = parse_json("{\"a\": -1}", &arena, JsonOptions::default()).unwrap();
+
+        assert_eq!(serde_json::to_value(&value).unwrap(), json!({
+            "type": "ObjectExpression",
+            "properties": [
+                {
+                    "type": "Property",
+                    "key": {
+                        "type": "Literal",
+                        "value": "a",
+                        "raw": "\"a\"",
+                        "start": 1,
+                        "end": 4,
+                    },
+                    "method": false,
+                    "shorthand": false,
+                    "computed": false,
+                    "value": {
+                        "type": "UnaryExpression",
+                        "operator": "-",
+                        "prefix": true,
+                        "argument": {
+                            "type": "Literal",
+                            "value": 1,
+                            "raw": "1",
+                            "start": 7,
+                            "end": 8,
+                        },
+                        "start": 6,
+                        "end": 8,
+                    },
+                    "kind": "init",
+                    "start": 1,
+                    "end": 8,
+                }
+            ],
+            "start": 0,
+            "end": 9,
+        }));
+    }
+
+    #[test]
+    fn strict_json() {
+        assert!(strict("[1, -2.5, 3e-7, \"a\\n\\u00e9\", false]"));
+        assert!(strict("  \t\r\n\"top level string\"\n"));
+
+        assert!(!strict(""));
+        assert!(!strict("[1] [2]"));
+        assert!(!strict("{ a: 1 }"));
+        assert!(!strict("'foo'"));
+        assert!(!strict("[01]"));
+        assert!(!strict("[1.]"));
+        assert!(!strict("[.5]"));
+        assert!(!strict("[0x10]"));
+        assert!(!strict("[0o7]"));
+        assert!(!strict("[0b1]"));
+        assert!(!strict("[+1]"));
+        assert!(!strict("[- 1]"));
+        assert!(!strict("[NaN]"));
+        assert!(!strict("[\"\\x41\"]"));
+        assert!(!strict("[\"a\tb\"]"));
+        assert!(!strict("[undefined]"));
+        assert!(!strict("[1, 2,]"));
+        assert!(!strict("{ \"a\": 1, }"));
+        assert!(!strict("{ \"a\" 1 }"));
+    }
+
+    #[test]
+    fn comments() {
+        let options = JsonOptions { comments: true, ..JsonOptions::default() };
+
+        assert_eq!(errors("// foo\n[1, /* bar */ 2]", options), []);
+        assert_eq!(errors("// foo\n[1, /* bar */ 2]", JsonOptions::default()), [(UnexpectedToken, 0, 6)]);
+        assert_eq!(errors("[1, /* bar */ 2]", JsonOptions::default()), [(UnexpectedToken, 4, 13)]);
+    }
+
+    #[test]
+    fn trailing_commas() {
+        let options = JsonOptions { trailing_commas: true, ..JsonOptions::default() };
+
+        assert_eq!(errors("[1, 2,]", options), []);
+        assert_eq!(errors("{ \"a\": 1, }", options), []);
+        assert_eq!(errors("[1, 2,]", JsonOptions::default()), [(Comma, 5, 6)]);
+        assert_eq!(errors("[1,,]", options), [(Comma, 3, 4)]);
+    }
+
+    #[test]
+    fn json5_extensions() {
+        assert!(json5("{ foo: 'bar', 'baz': \"qux\", null: 1, }"));
+        assert!(json5("[0x1F, .5, 5., +1, -Infinity, NaN, 1e3, /* comment */ 'a\\x41']"));
+
+        assert!(!json5("[01]"));
+        assert!(!json5("[0b1]"));
+        assert!(!json5("[0B1]"));
+        assert!(!json5("[0o7]"));
+        assert!(!json5("[-0O7]"));
+        assert!(json5("[0e1, 0.5, 0XaB, -0x1]"));
+        assert!(!json5("[1_000]"));
+        assert!(!json5("[undefined]"));
+        assert!(!json5("{ [foo]: 1 }"));
+        assert!(!json5("['a\nb']"));
+    }
+
+    #[test]
+    fn nesting_too_deep() {
+        let arena = Arena::new();
+        let source = format!("{}{}", "[".repeat(10_000), "]".repeat(10_000));
+
+        let kinds: Vec<_> = match parse_json(&source, &arena, JsonOptions::default()) {
+            Ok(_) => Vec::new(),
+            Err(errors) => errors.into_iter().map(|err| err.kind).collect(),
+        };
+
+        assert_eq!(kinds, [ErrorKind::NestingTooDeep]);
+    }
+}
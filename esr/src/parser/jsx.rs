@@ -0,0 +1,645 @@
+use toolshed::list::ListBuilder;
+use crate::parser::{Parser, ANY};
+use crate::parser::error::ToError;
+use crate::lexer::Token::*;
+use crate::ast::{Node, NodeList, Loc, Expression, ExpressionNode, SYNTHETIC};
+use crate::ast::jsx::*;
+
+impl<'ast> ToError for JSXChild<'ast> {
+    #[inline]
+    fn to_error() -> Self {
+        JSXChild::Text("")
+    }
+}
+
+impl<'ast> ToError for Node<'ast, JSXElementName<'ast>> {
+    #[inline]
+    fn to_error() -> Self {
+        Node::new(&Loc {
+            start: SYNTHETIC,
+            end: SYNTHETIC,
+            item: JSXElementName::Identifier("")
+        })
+    }
+}
+
+impl<'ast> ToError for Node<'ast, JSXAttributeValue<'ast>> {
+    #[inline]
+    fn to_error() -> Self {
+        Node::new(&Loc {
+            start: SYNTHETIC,
+            end: SYNTHETIC,
+            item: JSXAttributeValue::String("")
+        })
+    }
+}
+
+impl<'ast> Parser<'ast> {
+    /// Parse a JSX element or fragment in place of an expression,
+    /// the `<` is the current token.
+    #[inline]
+    pub fn jsx_expression(&mut self) -> ExpressionNode<'ast> {
+        let start = self.lexer.start();
+
+        if !self.jsx {
+            self.error::<()>();
+
+            return self.alloc_at_loc(start, start, Expression::Void);
+        }
+
+        self.lexer.consume();
+
+        let element = self.jsx_element(start, false);
+
+        let expression = match element.item {
+            JSXChild::Element(element)   => Expression::JSXElement(element),
+            JSXChild::Fragment(fragment) => Expression::JSXFragment(fragment),
+            _                            => Expression::Void,
+        };
+
+        self.alloc_at_loc(element.start, element.end, expression)
+    }
+
+    /// Parse the rest of a JSX element or fragment starting with the `<`
+    /// at `start`. Elements that are children of another element are
+    /// followed by more JSX, otherwise the source continues with regular
+    /// JavaScript after the final `>`.
+    fn jsx_element(&mut self, start: u32, child: bool) -> Loc<JSXChild<'ast>> {
+        self.nested(|par| {
+            par.lexer.read_greater();
+
+            if par.lexer.token == OperatorGreater {
+                return par.jsx_fragment(start, child);
+            }
+
+            let name = par.jsx_element_name();
+            let attributes = par.jsx_attributes();
+
+            if par.lexer.token == OperatorDivision {
+                par.lexer.consume();
+
+                let end = par.jsx_tag_end(child);
+                let opening = par.alloc_at_loc(start, end, JSXOpeningElement {
+                    name,
+                    attributes,
+                    self_closing: true,
+                });
+
+                return Loc::new(start, end, JSXChild::Element(JSXElement {
+                    opening,
+                    children: NodeList::empty(),
+                    closing: None,
+                }));
+            }
+
+            let opening_end = par.jsx_tag_end(true);
+            let opening = par.alloc_at_loc(start, opening_end, JSXOpeningElement {
+                name,
+                attributes,
+                self_closing: false,
+            });
+
+            let (children, closing_start) = par.jsx_children();
+
+            let closing_name = par.jsx_element_name();
+
+            // A closing name that failed to parse has been reported already,
+            // and its synthetic location is nowhere in the source.
+            if closing_name.start != SYNTHETIC && closing_name != name {
+                let err = par.lexer.mismatched_closing_tag(closing_name.start, closing_name.end);
+
+                if !par.bailed_out() {
+                    par.errors.push(err);
+                }
+            }
+
+            let end = par.jsx_tag_end(child);
+            let closing = par.alloc_at_loc(closing_start, end, JSXClosingElement {
+                name: closing_name,
+            });
+
+            Loc::new(start, end, JSXChild::Element(JSXElement {
+                opening,
+                children,
+                closing: Some(closing),
+            }))
+        })
+    }
+
+    /// Parse a fragment, the `>` of `<>` is the current token.
+    fn jsx_fragment(&mut self, start: u32, child: bool) -> Loc<JSXChild<'ast>> {
+        let opening_end = self.jsx_tag_end(true);
+        let opening = self.alloc_at_loc(start, opening_end, JSXOpeningFragment);
+
+        let (children, closing_start) = self.jsx_children();
+
+        let end = self.jsx_tag_end(child);
+        let closing = self.alloc_at_loc(closing_start, end, JSXClosingFragment);
+
+        Loc::new(start, end, JSXChild::Fragment(JSXFragment {
+            opening,
+            children,
+            closing,
+        }))
+    }
+
+    /// Expect the `>` ending a tag and return its end. The lexer then
+    /// either reads the children of the element, or continues as usual.
+    #[inline]
+    fn jsx_tag_end(&mut self, children: bool) -> u32 {
+        self.lexer.read_greater();
+
+        let end = self.lexer.end();
+
+        match self.lexer.token {
+            OperatorGreater if children => self.lexer.read_jsx_child(),
+            OperatorGreater             => self.lexer.consume(),
+            _                           => self.error(),
+        }
+
+        end
+    }
+
+    /// Parse children up to, and including, the `</` of the closing tag.
+    /// Returns the children along with the start of the closing tag.
+    fn jsx_children(&mut self) -> (NodeList<'ast, JSXChild<'ast>>, u32) {
+        let builder = match self.jsx_child() {
+            Ok(child)  => ListBuilder::new(self.arena, child),
+            Err(start) => return (NodeList::empty(), start),
+        };
+
+        loop {
+            match self.jsx_child() {
+                Ok(child)  => builder.push(self.arena, child),
+                Err(start) => return (builder.as_list(), start),
+            }
+        }
+    }
+
+    /// Parse a single child, or the `</` of the closing tag, in which
+    /// case its start is returned as the error. Any other token is
+    /// unexpected and also ends the children.
+    fn jsx_child(&mut self) -> Result<Node<'ast, JSXChild<'ast>>, u32> {
+        let start = self.lexer.start();
+
+        let child = match self.lexer.token {
+            LiteralString => {
+                let text = self.lexer.token_as_str();
+                let end = self.lexer.end();
+
+                self.lexer.read_jsx_child();
+
+                Loc::new(start, end, JSXChild::Text(text))
+            },
+            BraceOpen => {
+                self.lexer.consume();
+
+                let expression = match self.lexer.token {
+                    BraceClose => None,
+                    _          => Some(self.expression::<ANY>()),
+                };
+
+                let end = self.lexer.end();
+
+                match self.lexer.token {
+                    BraceClose => self.lexer.read_jsx_child(),
+                    _          => {
+                        self.error::<()>();
+
+                        return Err(start);
+                    },
+                }
+
+                Loc::new(start, end, JSXChild::Expression(JSXExpressionContainer {
+                    expression,
+                }))
+            },
+            OperatorLesser => {
+                self.lexer.consume();
+
+                if self.lexer.token == OperatorDivision {
+                    self.lexer.consume();
+
+                    return Err(start);
+                }
+
+                self.jsx_element(start, true)
+            },
+            _ => {
+                self.error::<()>();
+
+                return Err(start);
+            },
+        };
+
+        Ok(self.alloc(child))
+    }
+
+    /// Parse an identifier, or a namespaced name such as `xlink:href`.
+    fn jsx_name(&mut self) -> Node<'ast, JSXElementName<'ast>> {
+        if !self.lexer.token.is_word() {
+            return self.error();
+        }
+
+        let start = self.lexer.start();
+
+        self.lexer.read_jsx_identifier();
+
+        let ident = self.alloc_in_loc(self.lexer.token_as_str());
+
+        self.lexer.consume();
+
+        if self.lexer.token != Colon {
+            return self.alloc_at_loc(start, ident.end, JSXElementName::Identifier(ident.item));
+        }
+
+        self.lexer.consume();
+
+        if !self.lexer.token.is_word() {
+            return self.error();
+        }
+
+        self.lexer.read_jsx_identifier();
+
+        let name = self.alloc_in_loc(self.lexer.token_as_str());
+
+        self.lexer.consume();
+
+        self.alloc_at_loc(start, name.end, JSXElementName::Namespaced {
+            namespace: ident,
+            name,
+        })
+    }
+
+    /// Same as above, but also allows member expressions, such as `Foo.Bar`.
+    fn jsx_element_name(&mut self) -> Node<'ast, JSXElementName<'ast>> {
+        let mut name = self.jsx_name();
+
+        if let JSXElementName::Namespaced { .. } = name.item {
+            return name;
+        }
+
+        while self.lexer.token == Accessor {
+            let end = self.lexer.end();
+            let property = self.lexer.accessor_as_str();
+            let property = self.alloc_at_loc(end - property.len() as u32, end, property);
+
+            self.lexer.consume();
+
+            name = self.alloc_at_loc(name.start, end, JSXElementName::Member(JSXMemberExpression {
+                object: name,
+                property,
+            }));
+        }
+
+        name
+    }
+
+    fn jsx_attributes(&mut self) -> NodeList<'ast, JSXAttributeItem<'ast>> {
+        let builder = match self.jsx_attribute() {
+            Some(attribute) => ListBuilder::new(self.arena, attribute),
+            None            => return NodeList::empty(),
+        };
+
+        while let Some(attribute) = self.jsx_attribute() {
+            builder.push(self.arena, attribute);
+        }
+
+        builder.as_list()
+    }
+
+    fn jsx_attribute(&mut self) -> Option<Node<'ast, JSXAttributeItem<'ast>>> {
+        let attribute = match self.lexer.token {
+            BraceOpen => {
+                let start = self.lexer.start_then_consume();
+
+                expect!(self, OperatorSpread);
+
+                let argument = self.expression::<ANY>();
+                let end = self.lexer.end();
+
+                expect!(self, BraceClose);
+
+                self.alloc_at_loc(start, end, JSXSpreadAttribute { argument })
+            },
+            token if token.is_word() => {
+                let name = self.jsx_name();
+
+                let value = match self.lexer.token {
+                    OperatorAssign => {
+                        self.lexer.consume_jsx_attribute_value();
+
+                        Some(self.jsx_attribute_value())
+                    },
+                    _ => None,
+                };
+
+                let end = value.map(|value| value.end).unwrap_or(name.end);
+
+                self.alloc_at_loc(name.start, end, JSXAttribute { name, value })
+            },
+            _ => return None,
+        };
+
+        Some(attribute)
+    }
+
+    fn jsx_attribute_value(&mut self) -> Node<'ast, JSXAttributeValue<'ast>> {
+        let start = self.lexer.start();
+
+        match self.lexer.token {
+            LiteralString => {
+                let value = self.alloc_in_loc(JSXAttributeValue::String(self.lexer.token_as_str()));
+
+                self.lexer.consume();
+
+                value
+            },
+            BraceOpen => {
+                self.lexer.consume();
+
+                // Attributes can't be assigned an empty expression
+                let expression = match self.lexer.token {
+                    BraceClose => self.error(),
+                    _          => self.expression::<ANY>(),
+                };
+
+                let end = self.lexer.end();
+
+                expect!(self, BraceClose);
+
+                self.alloc_at_loc(start, end, JSXAttributeValue::Expression(JSXExpressionContainer {
+                    expression: Some(expression),
+                }))
+            },
+            OperatorLesser => {
+                self.lexer.consume();
+
+                let element = self.jsx_element(start, false);
+
+                let value = match element.item {
+                    JSXChild::Fragment(fragment) => JSXAttributeValue::Fragment(fragment),
+                    JSXChild::Element(element)   => JSXAttributeValue::Element(element),
+                    _                            => return self.error(),
+                };
+
+                self.alloc_at_loc(element.start, element.end, value)
+            },
+            _ => self.error(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ast::{Statement, Literal, OperatorKind};
+    use crate::ast::expression::BinaryExpression;
+    use crate::error::ErrorKind;
+    use crate::module::Module;
+    use crate::parser::{parse, parse_with_options, ParseOptions};
+    use crate::parser::mock::Mock;
+
+    fn parse_jsx<'ast>(source: &str) -> Result<Module<'ast>, Vec<crate::error::Error>> {
+        parse_with_options(source, ParseOptions { jsx: true, ..ParseOptions::default() })
+    }
+
+    macro_rules! assert_jsx {
+        ($src:expr, $expr:expr) => ({
+            let module = parse_jsx($src).unwrap();
+            let mut body = module.body().iter();
+
+            match body.next().map(|s| s.item).unwrap() {
+                Statement::Expression(ref expression) => assert_eq!(expression.item, Expression::from($expr)),
+                _ => panic!("Statement isn't an expression!")
+            }
+
+            assert_eq!(body.next(), None);
+        })
+    }
+
+    fn first_error(source: &str) -> Option<(usize, usize)> {
+        match parse_jsx(source) {
+            Ok(_) => None,
+            Err(errors) => errors.first().map(|err| (err.start, err.end)),
+        }
+    }
+
+    #[test]
+    fn jsx_self_closing_element() {
+        let mock = Mock::new();
+
+        let expected = JSXElement {
+            opening: mock.ptr(JSXOpeningElement {
+                name: mock.ptr(JSXElementName::Identifier("div")),
+                attributes: NodeList::empty(),
+                self_closing: true,
+            }),
+            children: NodeList::empty(),
+            closing: None,
+        };
+
+        assert_jsx!("<div/>", expected);
+        assert_jsx!("< div />;", expected);
+    }
+
+    #[test]
+    fn jsx_element_names() {
+        let mock = Mock::new();
+
+        let element = |name| JSXElement {
+            opening: mock.ptr(JSXOpeningElement {
+                name: mock.ptr(name),
+                attributes: NodeList::empty(),
+                self_closing: true,
+            }),
+            children: NodeList::empty(),
+            closing: None,
+        };
+
+        assert_jsx!("<my-element/>", element(JSXElementName::Identifier("my-element")));
+        assert_jsx!("<class/>", element(JSXElementName::Identifier("class")));
+        assert_jsx!("<svg:rect/>", element(JSXElementName::Namespaced {
+            namespace: mock.ptr("svg"),
+            name: mock.ptr("rect"),
+        }));
+        assert_jsx!("<Foo.Bar.Baz/>", element(JSXElementName::Member(JSXMemberExpression {
+            object: mock.ptr(JSXElementName::Member(JSXMemberExpression {
+                object: mock.ptr(JSXElementName::Identifier("Foo")),
+                property: mock.ptr("Bar"),
+            })),
+            property: mock.ptr("Baz"),
+        })));
+    }
+
+    #[test]
+    fn jsx_attributes() {
+        let mock = Mock::new();
+
+        let expected = JSXElement {
+            opening: mock.ptr(JSXOpeningElement {
+                name: mock.ptr(JSXElementName::Identifier("a")),
+                attributes: mock.list([
+                    JSXAttributeItem::from(JSXAttribute {
+                        name: mock.ptr(JSXElementName::Identifier("href")),
+                        value: Some(mock.ptr(JSXAttributeValue::String(r#""\""#))),
+                    }),
+                    JSXAttributeItem::from(JSXAttribute {
+                        name: mock.ptr(JSXElementName::Identifier("data-id")),
+                        value: Some(mock.ptr(JSXAttributeValue::Expression(JSXExpressionContainer {
+                            expression: Some(mock.number("1")),
+                        }))),
+                    }),
+                    JSXAttributeItem::from(JSXAttribute {
+                        name: mock.ptr(JSXElementName::Identifier("disabled")),
+                        value: None,
+                    }),
+                    JSXAttributeItem::from(JSXSpreadAttribute {
+                        argument: mock.ptr(Expression::Identifier("props")),
+                    }),
+                ]),
+                self_closing: true,
+            }),
+            children: NodeList::empty(),
+            closing: None,
+        };
+
+        assert_jsx!(r#"<a href="\" data-id={1} disabled {...props}/>"#, expected);
+    }
+
+    #[test]
+    fn jsx_children() {
+        let mock = Mock::new();
+
+        let expected = JSXElement {
+            opening: mock.ptr(JSXOpeningElement {
+                name: mock.ptr(JSXElementName::Identifier("a")),
+                attributes: NodeList::empty(),
+                self_closing: false,
+            }),
+            children: mock.list([
+                JSXChild::Text(" foo "),
+                JSXChild::Expression(JSXExpressionContainer {
+                    expression: Some(mock.ptr(Expression::Identifier("bar"))),
+                }),
+                JSXChild::Expression(JSXExpressionContainer {
+                    expression: None,
+                }),
+                JSXChild::Fragment(JSXFragment {
+                    opening: mock.ptr(JSXOpeningFragment),
+                    children: mock.list([JSXChild::Text("&gt;")]),
+                    closing: mock.ptr(JSXClosingFragment),
+                }),
+            ]),
+            closing: Some(mock.ptr(JSXClosingElement {
+                name: mock.ptr(JSXElementName::Identifier("a")),
+            })),
+        };
+
+        assert_jsx!("<a> foo {bar}{/* baz */}<>&gt;</></a>", expected);
+    }
+
+    #[test]
+    fn jsx_followed_by_operators() {
+        let mock = Mock::new();
+
+        let expected = BinaryExpression {
+            operator: OperatorKind::Greater,
+            left: mock.ptr(JSXFragment {
+                opening: mock.ptr(JSXOpeningFragment),
+                children: NodeList::empty(),
+                closing: mock.ptr(JSXClosingFragment),
+            }),
+            right: mock.number("1"),
+        };
+
+        assert_jsx!("<></>>1", expected);
+    }
+
+    #[test]
+    fn jsx_element_locations() {
+        let module = parse_jsx("x = <a b='c'>d</a>;").unwrap();
+
+        let element = match module.body().only_element().unwrap().item {
+            Statement::Expression(expression) => match expression.item {
+                Expression::Binary(BinaryExpression { right, .. }) => right,
+                _ => panic!("Expected an assignment"),
+            },
+            _ => panic!("Statement isn't an expression!"),
+        };
+
+        assert_eq!((element.start, element.end), (4, 18));
+
+        match element.item {
+            Expression::JSXElement(JSXElement { opening, children, closing }) => {
+                assert_eq!((opening.start, opening.end), (4, 13));
+                assert_eq!(children.iter().map(|c| (c.start, c.end)).collect::<Vec<_>>(), [(13, 14)]);
+                assert_eq!(closing.map(|c| (c.start, c.end)), Some((14, 18)));
+            },
+            _ => panic!("Expected an element"),
+        }
+    }
+
+    #[test]
+    fn jsx_literal_values() {
+        let mock = Mock::new();
+
+        let expected = JSXElement {
+            opening: mock.ptr(JSXOpeningElement {
+                name: mock.ptr(JSXElementName::Identifier("a")),
+                attributes: mock.list([
+                    JSXAttributeItem::from(JSXAttribute {
+                        name: mock.ptr(JSXElementName::Identifier("b")),
+                        value: Some(mock.ptr(JSXAttributeValue::Expression(JSXExpressionContainer {
+                            expression: Some(mock.ptr(Literal::String("'c'"))),
+                        }))),
+                    }),
+                ]),
+                self_closing: true,
+            }),
+            children: NodeList::empty(),
+            closing: None,
+        };
+
+        assert_jsx!("<a b={'c'}/>", expected);
+    }
+
+    #[test]
+    fn jsx_requires_option() {
+        assert!(parse("<div/>").is_err());
+        assert!(parse("a < b").is_ok());
+        assert!(parse_jsx("a < b").is_ok());
+    }
+
+    #[test]
+    fn jsx_errors() {
+        assert_eq!(first_error("<a></b>"), Some((5, 6)));
+        assert_eq!(first_error("<a.b></a.c>"), Some((7, 10)));
+        assert_eq!(first_error("<a>></a>"), Some((3, 4)));
+        assert_eq!(first_error("<a>}</a>"), Some((3, 4)));
+        assert_eq!(first_error("<a b={}/>"), Some((6, 7)));
+        assert!(parse_jsx("<a>foo").is_err());
+        assert!(parse_jsx("<a b='c/>").is_err());
+        assert!(parse_jsx("<a>{foo</a>").is_err());
+
+        // Errors always point into the source
+        for source in ["x = <div>></div>;", "<a></>;", "<a></1>;", "<T,>(x) => x;"].iter() {
+            match parse_jsx(source) {
+                Err(errors) => assert!(errors.iter().all(|err| err.end <= source.len()), "{}", source),
+                Ok(_)       => panic!("{} should not parse", source),
+            }
+        }
+    }
+
+    #[test]
+    fn jsx_nesting_too_deep() {
+        let options = ParseOptions { max_depth: 6, jsx: true, ..ParseOptions::default() };
+        let errors = |source| match parse_with_options(source, options) {
+            Ok(_) => Vec::new(),
+            Err(errors) => errors.into_iter().map(|err| err.kind).collect(),
+        };
+
+        assert_eq!(errors("<a><a><a><a></a></a></a></a>"), []);
+        assert_eq!(errors("<a><a><a><a><a><a></a></a></a></a></a></a>"), [ErrorKind::NestingTooDeep]);
+    }
+}
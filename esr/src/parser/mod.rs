@@ -5,20 +5,28 @@ mod expression;
 mod statement;
 mod function;
 mod nested;
+mod incremental;
+mod jsx;
+mod types;
+mod flow;
+mod json;
 
 use toolshed::list::ListBuilder;
 use toolshed::Arena;
-use crate::error::Error;
+use crate::error::{Error, ErrorKind};
 use crate::module::Module;
 
 use self::error::ToError;
 use self::nested::*;
 
-use crate::ast::{Loc, Node, Statement, NodeList, Block, BlockNode};
-use crate::ast::{Expression, ExpressionNode, ExpressionList, IdentifierNode};
+pub use self::incremental::{reparse, TextEdit};
+pub use self::json::{parse_json, JsonOptions};
+
+use crate::ast::{Loc, Node, Statement, StatementNode, NodeList, Block, BlockNode};
+use crate::ast::{Expression, ExpressionNode, ExpressionList, IdentifierNode, LabelNode};
 use crate::ast::{OperatorKind, Pattern};
 use crate::ast::expression::BinaryExpression;
-use crate::lexer::{Lexer, Asi};
+use crate::lexer::{Lexer, Asi, Token};
 use crate::lexer::Token::*;
 
 pub trait Parse<'ast> {
@@ -27,6 +35,67 @@ pub trait Parse<'ast> {
     fn parse(_: &mut Parser<'ast>) -> Self::Output;
 }
 
+/// Default value of `ParseOptions::max_depth`.
+pub const DEFAULT_MAX_DEPTH: usize = 1024;
+
+/// Options controlling how the source is parsed.
+#[derive(Debug, Clone, Copy)]
+pub struct ParseOptions {
+    /// Maximum depth to which statements, expressions and patterns can be
+    /// nested. Sources exceeding it are rejected with an error of kind
+    /// `ErrorKind::NestingTooDeep`, instead of overflowing the stack while
+    /// parsing, visiting, or generating code for the resulting tree.
+    /// Left associative chains of operators, member accesses and calls,
+    /// `a + b.c(d)[e]`, don't count towards it, however long they are.
+    pub max_depth: usize,
+
+    /// Parse JSX elements and fragments, such as `<div>{foo}</div>`,
+    /// in places where an expression is expected.
+    pub jsx: bool,
+
+    /// Parse TypeScript syntax, such as type annotations, interfaces,
+    /// or `as` expressions.
+    pub typescript: bool,
+
+    /// Parse Flow syntax, such as type annotations, `opaque type`,
+    /// `import type` or `declare export`. Also enabled by an `@flow`
+    /// pragma in the comments leading the source. The deprecated `%checks`
+    /// predicates are rejected with `ErrorKind::UnsupportedSyntax`, and
+    /// `declare export default` is unsupported like `export default`.
+    pub flow: bool,
+
+    /// Keep parentheses around expressions as `ParenthesizedExpression`
+    /// nodes, instead of discarding them.
+    pub parenthesized: bool,
+
+    /// Parse `await` outside of functions as an `AwaitExpression`,
+    /// as REPLs and module code allow.
+    pub top_level_await: bool,
+}
+
+impl ParseOptions {
+    /// Options in effect for the given source, taking pragmas into account.
+    fn resolve(self, source: &str) -> Self {
+        ParseOptions {
+            flow: self.flow || flow::has_flow_pragma(source),
+            ..self
+        }
+    }
+}
+
+impl ::std::default::Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            max_depth: DEFAULT_MAX_DEPTH,
+            jsx: false,
+            typescript: false,
+            flow: false,
+            parenthesized: false,
+            top_level_await: false,
+        }
+    }
+}
+
 pub struct Parser<'ast> {
     arena: &'ast Arena,
 
@@ -38,26 +107,236 @@ pub struct Parser<'ast> {
 
     /// AST under construction
     body: NodeList<'ast, Statement<'ast>>,
+
+    /// Current nesting depth of the AST under construction
+    depth: usize,
+
+    /// Maximum nesting depth before bailing out
+    max_depth: usize,
+
+    /// Whether JSX is enabled
+    jsx: bool,
+
+    /// Whether TypeScript is enabled
+    typescript: bool,
+
+    /// Whether Flow is enabled
+    flow: bool,
+
+    /// Whether type annotations are enabled, either by TypeScript or Flow
+    types: bool,
+
+    /// Whether parentheses are kept as `ParenthesizedExpression`s
+    parenthesized: bool,
+
+    /// Whether `await` is an operator at the current position, that is
+    /// with `top_level_await` enabled and outside of any function
+    await_allowed: bool,
+
+    /// Labeled statements enclosing the current one, within the current function
+    labels: Vec<LabelScope<'ast>>,
+}
+
+/// Label of a labeled statement that is being parsed, or the scope of
+/// a loop or `switch` targeted by unlabeled `break` and `continue`.
+struct LabelScope<'ast> {
+    /// `None` for a loop or `switch` scope
+    label: Option<&'ast str>,
+
+    /// Start of the labeled statement
+    start: u32,
+
+    /// Start of the body, `a: b: for ...` chains both labels to the loop
+    body: u32,
+
+    /// Whether the labeled statement is a loop, `continue` can only target those
+    is_loop: bool,
 }
 
 impl<'ast> Parser<'ast> {
     pub fn new(source: &str, arena: &'ast Arena) -> Self {
+        Parser::with_options(source, arena, ParseOptions::default())
+    }
+
+    pub fn with_options(source: &str, arena: &'ast Arena, options: ParseOptions) -> Self {
+        let options = options.resolve(source);
+
         Parser {
             arena,
             lexer: Lexer::new(arena, source),
             errors: Vec::new(),
             body: NodeList::empty(),
+            depth: 0,
+            max_depth: options.max_depth,
+            jsx: options.jsx,
+            typescript: options.typescript,
+            flow: options.flow,
+            types: options.typescript || options.flow,
+            parenthesized: options.parenthesized,
+            await_allowed: options.top_level_await,
+            labels: Vec::new(),
         }
     }
 
     fn error<T: ToError>(&mut self) -> T {
         let err = self.lexer.invalid_token();
 
-        self.errors.push(err);
+        // Once nesting got too deep the rest of the source is skipped,
+        // any errors from unwinding the parser are just noise.
+        if !self.bailed_out() {
+            self.errors.push(err);
+        }
 
         T::to_error()
     }
 
+    /// Report an error for a label, unlike `error` this doesn't consume any tokens.
+    fn label_error(&mut self, kind: ErrorKind, label: LabelNode<'ast>) {
+        if !self.bailed_out() {
+            self.errors.push(Error {
+                kind,
+                token: Identifier,
+                raw: label.item.as_str().into(),
+                start: label.start as usize,
+                end: label.end as usize,
+            });
+        }
+    }
+
+    /// Report an error for a `break` or `continue` keyword without a target.
+    fn keyword_error(&mut self, kind: ErrorKind, token: Token, raw: &str, start: u32, end: u32) {
+        if !self.bailed_out() {
+            self.errors.push(Error {
+                kind,
+                token,
+                raw: raw.into(),
+                start: start as usize,
+                end: end as usize,
+            });
+        }
+    }
+
+    /// Parse the body of a function. Labels of the enclosing function
+    /// cannot be targeted from within it, and `await` is an identifier.
+    #[inline]
+    fn function_body<T, F>(&mut self, parse: F) -> T
+    where
+        F: FnOnce(&mut Self) -> T,
+    {
+        let labels = std::mem::take(&mut self.labels);
+        let await_allowed = std::mem::replace(&mut self.await_allowed, false);
+        let body = parse(self);
+
+        self.labels = labels;
+        self.await_allowed = await_allowed;
+
+        body
+    }
+
+    #[inline]
+    fn bailed_out(&self) -> bool {
+        self.errors.last().map(|err| err.kind) == Some(ErrorKind::NestingTooDeep)
+    }
+
+    /// Increase the nesting depth by one level. If that would exceed the
+    /// maximum depth, an error is reported, the rest of the source is
+    /// skipped and `false` is returned instead.
+    #[inline]
+    fn descend(&mut self) -> bool {
+        if self.depth < self.max_depth {
+            self.depth += 1;
+
+            return true;
+        }
+
+        if !self.bailed_out() {
+            let err = self.lexer.nesting_too_deep();
+
+            self.errors.push(err);
+        }
+
+        false
+    }
+
+    /// Parse a construct that is nested one level deeper than the
+    /// current one.
+    #[inline]
+    fn nested<T, F>(&mut self, parse: F) -> T where
+        T: ToError,
+        F: FnOnce(&mut Self) -> T,
+    {
+        if !self.descend() {
+            return T::to_error();
+        }
+
+        let item = parse(self);
+
+        self.depth -= 1;
+
+        item
+    }
+
+    /// Attempt to parse an ambiguous construct. If any errors occur on
+    /// the way, the parser is rewound to where it was before the attempt
+    /// and `None` is returned. Unsupported syntax doesn't count, as the
+    /// construct was recognized.
+    #[inline]
+    fn speculate<T, F>(&mut self, parse: F) -> Option<T> where
+        F: FnOnce(&mut Self) -> T,
+    {
+        let lexer = self.lexer.clone();
+        let errors = self.errors.len();
+        let depth = self.depth;
+
+        let item = parse(self);
+
+        if self.only_unsupported_since(errors) {
+            return Some(item);
+        }
+
+        self.lexer = lexer;
+        self.errors.truncate(errors);
+        self.depth = depth;
+
+        None
+    }
+
+    /// Check whether the source ahead matches what `check` is looking
+    /// for, without advancing the parser. Unsupported syntax doesn't
+    /// prevent a match, so that it's reported once the parser commits.
+    #[inline]
+    fn lookahead<F>(&mut self, check: F) -> bool where
+        F: FnOnce(&mut Self) -> bool,
+    {
+        let lexer = self.lexer.clone();
+        let errors = self.errors.len();
+        let depth = self.depth;
+
+        let matches = check(self) && self.only_unsupported_since(errors);
+
+        self.lexer = lexer;
+        self.errors.truncate(errors);
+        self.depth = depth;
+
+        matches
+    }
+
+    /// Whether the errors reported after the first `errors` are all about
+    /// syntax that is recognized but unsupported.
+    #[inline]
+    fn only_unsupported_since(&self, errors: usize) -> bool {
+        self.errors[errors..].iter().all(|error| error.kind == ErrorKind::UnsupportedSyntax)
+    }
+
+    /// Get the token following the current one.
+    #[inline]
+    fn peek(&self) -> Token {
+        let mut lexer = self.lexer.clone();
+
+        lexer.consume();
+        lexer.token
+    }
+
     #[inline]
     fn asi(&mut self) -> Asi {
         self.lexer.asi()
@@ -115,6 +394,20 @@ impl<'ast> Parser<'ast> {
         self.body = builder.as_list()
     }
 
+    /// Make sure the whole source has been consumed, and hand over the
+    /// parsed `item` unless any errors have been collected on the way.
+    #[inline]
+    fn finish<T>(mut self, item: T) -> Result<T, Vec<Error>> {
+        if self.lexer.token != EndOfProgram {
+            self.error::<()>();
+        }
+
+        match self.errors.len() {
+            0 => Ok(item),
+            _ => Err(self.errors)
+        }
+    }
+
     #[inline]
     fn block<I>(&mut self) -> BlockNode<'ast, I> where
         I: Parse<'ast, Output = Node<'ast, I>> + Copy
@@ -127,7 +420,9 @@ impl<'ast> Parser<'ast> {
         }
 
         let block = self.raw_block();
-        let end   = self.lexer.end_then_consume();
+        let end   = self.lexer.end();
+
+        expect!(self, BraceClose);
 
         self.alloc_at_loc(start, end, block)
     }
@@ -139,7 +434,9 @@ impl<'ast> Parser<'ast> {
     {
         let start = self.lexer.start_then_consume();
         let block = self.raw_block();
-        let end   = self.lexer.end_then_consume();
+        let end   = self.lexer.end();
+
+        expect!(self, BraceClose);
 
         self.alloc_at_loc(start, end, block)
     }
@@ -189,6 +486,8 @@ impl<'ast> Parser<'ast> {
                 }
             },
             Expression::Identifier(ident) => {
+                let ident = self.alloc_at_loc(expression.start, expression.end, ident);
+
                 Pattern::Identifier(ident)
             },
             _ => self.error()
@@ -220,10 +519,16 @@ impl<'ast> Parser<'ast> {
 
 /// Parse the JavaScript source `&str` and produce an Abstract Syntax Tree `Module`.
 pub fn parse<'src, 'ast>(source: &'src str) -> Result<Module<'ast>, Vec<Error>> {
+    parse_with_options(source, ParseOptions::default())
+}
+
+/// Same as `parse`, but with custom `ParseOptions`.
+pub fn parse_with_options<'ast>(source: &str, options: ParseOptions) -> Result<Module<'ast>, Vec<Error>> {
     let arena = Arena::new();
+    let options = options.resolve(source);
 
     let (body, errors) = {
-        let mut parser = Parser::new(source, &arena);
+        let mut parser = Parser::with_options(source, &arena, options);
 
         parser.parse();
 
@@ -231,13 +536,97 @@ pub fn parse<'src, 'ast>(source: &'src str) -> Result<Module<'ast>, Vec<Error>>
     };
 
     match errors.len() {
-        0 => Ok(Module::new(body, arena)),
+        0 => Ok(Module::new(body, arena, options)),
         _ => Err(errors)
     }
 }
 
+/// Outcome of parsing an entry of a REPL with `parse_repl_input`.
+pub enum ReplInput<'ast> {
+    /// The input is a complete program.
+    Complete(Module<'ast>),
+
+    /// The input ended prematurely, within an unclosed block, template,
+    /// comment or string, or right after an operator. More lines of input
+    /// might complete it.
+    Incomplete,
+
+    /// The input has errors that no further input can fix.
+    Invalid(Vec<Error>),
+}
+
+/// Parse an entry of a REPL, telling apart inputs that need more lines
+/// from invalid ones. `await` is allowed outside of functions.
+///
+/// Each entry is parsed on its own, bindings declared with `let` or
+/// `const` in previous entries don't conflict with those in this one.
+pub fn parse_repl_input<'ast>(source: &str, options: ParseOptions) -> ReplInput<'ast> {
+    let options = ParseOptions {
+        top_level_await: true,
+        ..options
+    };
+
+    let errors = match parse_with_options(source, options) {
+        Ok(module) => return ReplInput::Complete(module),
+        Err(errors) => errors,
+    };
+
+    // The parser reports errors in source order, if the first one is at
+    // the end of the input everything before it was valid so far.
+    match errors.first() {
+        Some(&Error { kind: ErrorKind::UnexpectedToken, token: EndOfProgram, .. })           |
+        Some(&Error { kind: ErrorKind::UnexpectedToken, token: UnexpectedEndOfProgram, .. }) => {
+            ReplInput::Incomplete
+        },
+        _ => ReplInput::Invalid(errors),
+    }
+}
+
+/// Parse a single expression, such as `a + b`, allocating it in the provided `Arena`.
+///
+/// Any input remaining after the expression is reported as an error.
+pub fn parse_expression<'ast>(source: &str, arena: &'ast Arena) -> Result<ExpressionNode<'ast>, Vec<Error>> {
+    let mut parser = Parser::new(source, arena);
+    let expression = parser.expression::<ANY>();
+
+    parser.finish(expression)
+}
+
+/// Parse a single statement, allocating it in the provided `Arena`.
+///
+/// Any input remaining after the statement is reported as an error.
+pub fn parse_statement<'ast>(source: &str, arena: &'ast Arena) -> Result<StatementNode<'ast>, Vec<Error>> {
+    let mut parser = Parser::new(source, arena);
+    let statement = parser.statement();
+
+    parser.finish(statement)
+}
+
+/// Parse the body of a function (the statements between the braces,
+/// without the braces themselves), allocating it in the provided `Arena`.
+pub fn parse_function_body<'ast>(source: &str, arena: &'ast Arena) -> Result<NodeList<'ast, Statement<'ast>>, Vec<Error>> {
+    let mut parser = Parser::new(source, arena);
+
+    parser.parse();
+
+    let body = parser.body;
+
+    parser.finish(body)
+}
+
+/// Parse a binding pattern, such as `{ a, b: [c, d] }` or `foo = 10`,
+/// allocating it in the provided `Arena`.
+///
+/// Any input remaining after the pattern is reported as an error.
+pub fn parse_pattern<'ast>(source: &str, arena: &'ast Arena) -> Result<Node<'ast, Pattern<'ast>>, Vec<Error>> {
+    let mut parser = Parser::new(source, arena);
+    let pattern = parser.pattern_param();
+
+    parser.finish(pattern)
+}
+
 #[cfg(test)]
-mod mock {
+pub(crate) mod mock {
     use super::*;
     use crate::ast::{Literal, ExpressionNode, Block, BlockNode, Name};
 
@@ -297,6 +686,7 @@ mod mock {
 mod test {
     use super::*;
     use crate::parser::mock::Mock;
+    use crate::ast::expression::{AwaitExpression, CallExpression};
 
     #[test]
     fn empty_parse() {
@@ -315,4 +705,256 @@ mod test {
 
         assert_eq!(parse(";;;").unwrap().body(), expected);
     }
+
+    fn nesting_errors(source: &str, max_depth: usize) -> Vec<ErrorKind> {
+        match parse_with_options(source, ParseOptions { max_depth, ..ParseOptions::default() }) {
+            Ok(_) => Vec::new(),
+            Err(errors) => errors.into_iter().map(|err| err.kind).collect(),
+        }
+    }
+
+    #[test]
+    fn nesting_within_limit() {
+        assert_eq!(nesting_errors("((((a))));", 6), vec![]);
+        assert_eq!(nesting_errors("[[[[]]]];", 6), vec![]);
+        assert_eq!(nesting_errors("a + b + c + d;", 6), vec![]);
+        assert_eq!(nesting_errors("a + b + c + d + e + f + g;", 6), vec![]);
+        assert_eq!(nesting_errors("a.b(c)[d].e(f)[g].h;", 6), vec![]);
+        assert_eq!(nesting_errors("{{{{}}}}", 6), vec![]);
+        assert_eq!(nesting_errors("var [[[[a]]]] = b;", 6), vec![]);
+    }
+
+    #[test]
+    fn nesting_too_deep() {
+        let too_deep = vec![ErrorKind::NestingTooDeep];
+
+        assert_eq!(nesting_errors("((((((a))))));", 6), too_deep);
+        assert_eq!(nesting_errors("[[[[[[[]]]]]]];", 6), too_deep);
+        assert_eq!(nesting_errors("a = b = c = d = e = f = g;", 6), too_deep);
+        assert_eq!(nesting_errors("a ** b ** c ** d ** e ** f ** g;", 6), too_deep);
+        assert_eq!(nesting_errors("a + (b + (c + (d + (e + f))));", 6), too_deep);
+        assert_eq!(nesting_errors("{{{{{{{}}}}}}}", 6), too_deep);
+        assert_eq!(nesting_errors("var [[[[[[a]]]]]] = b;", 6), too_deep);
+    }
+
+    #[test]
+    fn nesting_too_deep_by_default() {
+        let too_deep = vec![ErrorKind::NestingTooDeep];
+        let depth = 300_000;
+
+        let parens = format!("{}a{};", "(".repeat(depth), ")".repeat(depth));
+        let arrays = format!("{}{};", "[".repeat(depth), "]".repeat(depth));
+        let assignments = format!("{}a;", "a=".repeat(depth));
+
+        assert_eq!(nesting_errors(&parens, DEFAULT_MAX_DEPTH), too_deep);
+        assert_eq!(nesting_errors(&arrays, DEFAULT_MAX_DEPTH), too_deep);
+        assert_eq!(nesting_errors(&assignments, DEFAULT_MAX_DEPTH), too_deep);
+    }
+
+    #[test]
+    fn left_associative_chains_are_not_limited() {
+        let links = 300_000;
+
+        let operators = format!("a{};", "+a".repeat(links));
+        let members = format!("a{};", ".a".repeat(links));
+        let mixed = format!("a{};", ".b(c)[d]+e".repeat(links));
+
+        assert_eq!(nesting_errors(&operators, DEFAULT_MAX_DEPTH), vec![]);
+        assert_eq!(nesting_errors(&members, DEFAULT_MAX_DEPTH), vec![]);
+        assert_eq!(nesting_errors(&mixed, DEFAULT_MAX_DEPTH), vec![]);
+
+        // The deepest nesting accepted by default, with a long chain at the
+        // bottom. The code generator and visitor tests use it as well.
+        let chain = format!("a{}", ".b(c)[d]+e".repeat(10_000));
+        let deepest = format!("{}{}{};", "[".repeat(1019), chain, "]".repeat(1019));
+        let deeper = format!("{}{}{};", "[".repeat(1020), chain, "]".repeat(1020));
+
+        assert_eq!(nesting_errors(&deepest, DEFAULT_MAX_DEPTH), vec![]);
+        assert_eq!(nesting_errors(&deeper, DEFAULT_MAX_DEPTH), vec![ErrorKind::NestingTooDeep]);
+    }
+
+    #[test]
+    fn expression_entry_point() {
+        let arena = Arena::new();
+        let mock = Mock::new();
+
+        let expected = mock.ptr(BinaryExpression {
+            operator: OperatorKind::Addition,
+            left: mock.ptr("a"),
+            right: mock.ptr("b"),
+        });
+
+        assert_eq!(parse_expression("a + b", &arena).unwrap(), expected);
+    }
+
+    #[test]
+    fn expression_entry_point_rejects_trailing_input() {
+        let arena = Arena::new();
+
+        assert!(parse_expression("a + b c", &arena).is_err());
+        assert!(parse_expression("a + b;", &arena).is_err());
+    }
+
+    #[test]
+    fn statement_entry_point() {
+        let arena = Arena::new();
+        let mock = Mock::new();
+
+        let expected = mock.ptr(Statement::Return(crate::ast::statement::ReturnStatement {
+            value: Some(mock.ptr("a"))
+        }));
+
+        assert_eq!(parse_statement("return a;", &arena).unwrap(), expected);
+        assert!(parse_statement("return a; b", &arena).is_err());
+    }
+
+    #[test]
+    fn function_body_entry_point() {
+        let arena = Arena::new();
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            Statement::Empty,
+            Statement::Empty,
+        ]);
+
+        assert_eq!(parse_function_body(";;", &arena).unwrap(), expected);
+        assert_eq!(parse_function_body("", &arena).unwrap(), NodeList::empty());
+        assert!(parse_function_body("}", &arena).is_err());
+    }
+
+    #[test]
+    fn pattern_entry_point() {
+        let arena = Arena::new();
+        let mock = Mock::new();
+
+        let expected = mock.ptr(Pattern::AssignmentPattern {
+            left: mock.ptr(Pattern::Identifier(mock.ptr("a"))),
+            right: mock.number("10"),
+        });
+
+        assert_eq!(parse_pattern("a = 10", &arena).unwrap(), expected);
+        assert!(parse_pattern("a b", &arena).is_err());
+        assert!(parse_pattern("10", &arena).is_err());
+    }
+
+    fn repl(source: &str) -> &'static str {
+        match parse_repl_input(source, ParseOptions::default()) {
+            ReplInput::Complete(_) => "complete",
+            ReplInput::Incomplete => "incomplete",
+            ReplInput::Invalid(_) => "invalid",
+        }
+    }
+
+    #[test]
+    fn repl_complete_input() {
+        assert_eq!(repl(""), "complete");
+        assert_eq!(repl("foo"), "complete");
+        assert_eq!(repl("function foo() {}"), "complete");
+        assert_eq!(repl("await foo();"), "complete");
+    }
+
+    #[test]
+    fn repl_incomplete_input() {
+        assert_eq!(repl("function foo() {"), "incomplete");
+        assert_eq!(repl("if (foo) {\n    bar();\n"), "incomplete");
+        assert_eq!(repl("foo(1,"), "incomplete");
+        assert_eq!(repl("[1, 2"), "incomplete");
+        assert_eq!(repl("foo = { bar: 1,"), "incomplete");
+        assert_eq!(repl("class Foo {"), "incomplete");
+        assert_eq!(repl("switch (foo) { case 1:"), "incomplete");
+        assert_eq!(repl("() => {"), "incomplete");
+        assert_eq!(repl("a +"), "incomplete");
+        assert_eq!(repl("`foo ${bar}"), "incomplete");
+        assert_eq!(repl("/* foo"), "incomplete");
+        assert_eq!(repl("await"), "incomplete");
+    }
+
+    #[test]
+    fn repl_invalid_input() {
+        assert_eq!(repl("foo bar"), "invalid");
+        assert_eq!(repl("function foo() { ) "), "invalid");
+        assert_eq!(repl("}"), "invalid");
+        assert_eq!(repl("break foo;"), "invalid");
+    }
+
+    #[test]
+    fn repl_redeclared_bindings() {
+        assert_eq!(repl("let foo = 1;"), "complete");
+        assert_eq!(repl("let foo = 2;"), "complete");
+        assert_eq!(repl("const foo = await bar;"), "complete");
+    }
+
+    #[test]
+    fn top_level_await() {
+        let mock = Mock::new();
+        let options = ParseOptions { top_level_await: true, ..ParseOptions::default() };
+
+        let expected = mock.list([
+            mock.ptr(AwaitExpression {
+                argument: mock.ptr(CallExpression {
+                    callee: mock.ptr("foo"),
+                    arguments: NodeList::empty(),
+                }),
+            })
+        ]);
+
+        assert_eq!(parse_with_options("await foo();", options).unwrap().body(), expected);
+
+        // Outside of the top level and by default `await` remains an identifier
+        assert!(parse("await foo();").is_err());
+        assert!(parse("await;").is_ok());
+        assert!(parse_with_options("function f() { await; }", options).is_ok());
+        assert!(parse_with_options("() => await;", options).is_ok());
+        assert!(parse_with_options("function f() { await foo(); }", options).is_err());
+    }
+
+    #[test]
+    fn identifier_spans() {
+        use crate::ast::{Property, IdentifierNode};
+        use crate::ast::expression::ArrowExpression;
+        use crate::ast::statement::{DeclarationStatement, LabeledStatement};
+
+        let source = "let a = 1, { b } = c; (d) => d; foo: bar;";
+        let module = parse(source).unwrap();
+        let body: Vec<_> = module.body().iter().map(|statement| statement.item).collect();
+        let slice = |ident: IdentifierNode| &source[ident.start as usize..ident.end as usize];
+
+        let declarators: Vec<_> = match body[0] {
+            Statement::Declaration(DeclarationStatement { declarators, .. }) => {
+                declarators.iter().map(|declarator| declarator.id.item).collect()
+            },
+            _ => panic!("Statement isn't a declaration!"),
+        };
+
+        match declarators[..] {
+            [Pattern::Identifier(a), Pattern::ObjectPattern { properties }] => {
+                assert_eq!(slice(a), "a");
+
+                match properties.only_element().unwrap().item {
+                    Property::Shorthand(b) => assert_eq!(slice(b), "b"),
+                    _ => panic!("Property isn't shorthand!"),
+                }
+            },
+            _ => panic!("Unexpected declarators!"),
+        }
+
+        match body[1] {
+            Statement::Expression(expression) => match expression.item {
+                Expression::Arrow(ArrowExpression { params, .. }) => match params.only_element().unwrap().item {
+                    Pattern::Identifier(d) => assert_eq!(slice(d), "d"),
+                    _ => panic!("Parameter isn't an identifier!"),
+                },
+                _ => panic!("Expression isn't an arrow function!"),
+            },
+            _ => panic!("Statement isn't an expression!"),
+        }
+
+        match body[2] {
+            Statement::Labeled(LabeledStatement { label, .. }) => {
+                assert_eq!(&source[label.start as usize..label.end as usize], "foo");
+            },
+            _ => panic!("Statement isn't labeled!"),
+        }
+    }
 }
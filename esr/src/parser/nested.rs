@@ -7,7 +7,7 @@ use crate::ast::expression::*;
 use crate::ast::OperatorKind::*;
 use crate::lexer::Asi;
 
-const TOTAL_TOKENS: usize = 108;
+const TOTAL_TOKENS: usize = 109;
 
 type NestedHandler = Option<for<'ast> fn(&mut Parser<'ast>, ExpressionNode<'ast>) -> ExpressionNode<'ast>>;
 
@@ -66,6 +66,9 @@ bp!(ANY, [
 
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
 //  IMPL  PCKG  PROT  IFACE PRIV  PUBLI IDENT ACCSS TPL_O TPL_C ERR_T ERR_E
+
+    ____,
+//  @
 ]);
 
 bp!(B0, [
@@ -78,6 +81,7 @@ bp!(B0, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    ____,
 ]);
 
 bp!(B1, [
@@ -90,6 +94,7 @@ bp!(B1, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    ____,
 ]);
 
 bp!(B5, [
@@ -102,6 +107,7 @@ bp!(B5, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    ____,
 ]);
 
 bp!(B6, [
@@ -114,6 +120,7 @@ bp!(B6, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    ____,
 ]);
 
 bp!(B7, [
@@ -126,6 +133,7 @@ bp!(B7, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    ____,
 ]);
 
 bp!(B8, [
@@ -138,6 +146,7 @@ bp!(B8, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    ____,
 ]);
 
 bp!(B9, [
@@ -150,6 +159,7 @@ bp!(B9, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    ____,
 ]);
 
 bp!(B10, [
@@ -162,6 +172,7 @@ bp!(B10, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    ____,
 ]);
 
 bp!(B11, [
@@ -174,6 +185,7 @@ bp!(B11, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    ____,
 ]);
 
 bp!(B12, [
@@ -186,6 +198,7 @@ bp!(B12, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    ____,
 ]);
 
 bp!(B13, [
@@ -198,6 +211,7 @@ bp!(B13, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    ____,
 ]);
 
 bp!(B14, [
@@ -210,6 +224,7 @@ bp!(B14, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    ____,
 ]);
 
 bp!(B15, [
@@ -222,6 +237,7 @@ bp!(B15, [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
     ____, ____, ____, ____, ____, ____, ____, ACCS, TPLE, TPLS, ____, ____,
+    ____,
 ]);
 
 const ____: NestedHandler = None;
@@ -230,13 +246,15 @@ const SEQ: NestedHandler = Some(|par, left| {
     par.lexer.consume();
 
     let builder = ListBuilder::new(par.arena, left);
-    builder.push(par.arena, par.expression::<B0>());
+    let mut last = par.expression::<B0>();
+    builder.push(par.arena, last);
 
     while let Comma = par.lexer.token {
         par.lexer.consume();
-        builder.push(par.arena, par.expression::<B0>());
+        last = par.expression::<B0>();
+        builder.push(par.arena, last);
     }
-    let end = par.lexer.end();
+    let end = last.end;
     par.alloc_at_loc(left.start, end, SequenceExpression {
         body: builder.as_list()
     })
@@ -261,21 +279,29 @@ const ARRW: NestedHandler = Some(|par, left| {
     par.lexer.consume();
 
     let params = match left.item {
+        Expression::Parenthesized(ParenthesizedExpression { expression }) => match expression.item {
+            Expression::Sequence(SequenceExpression { body }) => body,
+            _ => NodeList::from(par.arena, expression)
+        },
         Expression::Sequence(SequenceExpression { body }) => body,
         _ => NodeList::from(par.arena, left)
     };
 
     let expression = par.arrow_function_expression(params);
     let start = left.start;
-    let end = par.lexer.end();
+    let end = match expression.body {
+        ArrowBody::Block(block)           => block.end,
+        ArrowBody::Expression(expression) => expression.end,
+    };
     par.alloc_at_loc(start, end, expression)
 });
 
 const ACCS: NestedHandler = Some(|par, left| {
     let member = par.lexer.accessor_as_str();
+    let end = par.lexer.end();
     par.lexer.consume();
 
-    let right = par.alloc_in_loc(member);
+    let right = par.alloc_at_loc(end - member.len() as u32, end, member);
 
     par.alloc_at_loc(left.start, right.end, MemberExpression {
         object: left,
@@ -297,9 +323,9 @@ const CALL: NestedHandler = Some(|par, left| {
 const CMEM: NestedHandler = Some(|par, left| {
     par.lexer.consume();
     let property = par.expression::<ANY>();
+    let end = par.lexer.end();
 
     expect!(par, BracketClose);
-    let end = par.lexer.end();
 
     par.alloc_at_loc(left.start, end, ComputedMemberExpression {
         object: left,
@@ -426,19 +452,113 @@ binary!(SUB  , B13 => Subtraction);
 binary!(MUL  , B14 => Multiplication);
 binary!(DIV  , B14 => Division);
 binary!(REM  , B14 => Remainder);
-binary!(EXPN , B15 => Exponent);
+binary!(EXPN , B14 => Exponent); // Right associative
 
 
+const TSAS: NestedHandler = Some(|par, left| {
+    par.lexer.consume();
+
+    let annotation = par.type_node();
+
+    par.alloc_at_loc(left.start, annotation.end, AsExpression {
+        expression: left,
+        annotation,
+    })
+});
+
+const SATS: NestedHandler = Some(|par, left| {
+    par.lexer.consume();
+
+    let annotation = par.type_node();
+
+    par.alloc_at_loc(left.start, annotation.end, SatisfiesExpression {
+        expression: left,
+        annotation,
+    })
+});
+
+const NNUL: NestedHandler = Some(|par, left| {
+    let end = par.lexer.end_then_consume();
+
+    par.alloc_at_loc(left.start, end, NonNullExpression {
+        expression: left,
+    })
+});
+
+const TARG: NestedHandler = Some(|par, left| {
+    let arguments = par.type_arguments();
+    let end = par.lexer.end_then_consume();
+
+    par.alloc_at_loc(left.start, end, InstantiationExpression {
+        expression: left,
+        arguments,
+    })
+});
+
 impl<'ast> Parser<'ast> {
+    #[inline]
+    fn nested_handler<B>(&mut self) -> NestedHandler
+    where
+        B: BindingPower
+    {
+        let handler = B::handler(self.asi(), self.lexer.token);
+
+        if !self.types || self.asi() != Asi::NoSemicolon {
+            return handler;
+        }
+
+        // Both TypeScript and Flow allow explicit type arguments on calls.
+        if self.lexer.token == OperatorLesser {
+            return match self.lookahead(Parser::is_type_arguments) {
+                true  => TARG,
+                false => handler,
+            };
+        }
+
+        if !self.typescript {
+            return handler;
+        }
+
+        // TypeScript operators, `as` and `satisfies` bind as tightly
+        // as relational operators do.
+        match self.lexer.token {
+            OperatorLogicalNot => NNUL,
+            Identifier if B::LUT[OperatorLesser as usize].is_some() => match self.lexer.token_as_str() {
+                "as"        => TSAS,
+                "satisfies" => SATS,
+                _           => handler,
+            },
+            _ => handler,
+        }
+    }
+
     #[inline]
     pub fn nested_expression<B>(&mut self, mut left: ExpressionNode<'ast>) -> ExpressionNode<'ast>
     where
         B: BindingPower
     {
-        while let Some(handler) = B::handler(self.asi(), self.lexer.token) {
+        let depth = self.depth;
+
+        // Every handler wraps `left` in a new node. The extra level counts
+        // while the handler runs, so right associative operators that
+        // recurse for their right operand are limited. Left associative
+        // chains of operators, calls or member accesses are parsed here in
+        // a loop, and the code generator and visitor walk them in a loop
+        // too, so they don't count towards the limit once built.
+        while let Some(handler) = self.nested_handler::<B>() {
+            if !self.descend() {
+                break;
+            }
+
             left = handler(self, left);
+
+            if left.chained().is_some() {
+                self.depth -= 1;
+            }
         }
 
+        self.depth = depth;
+
         left
     }
 }
@@ -1,27 +1,31 @@
 use toolshed::list::{ListBuilder, GrowableList};
-use crate::parser::{Parser, Parse, ANY, B0};
+use crate::parser::{Parser, Parse, LabelScope, ANY, B0};
+use crate::error::ErrorKind;
 use crate::lexer::Token::*;
 use crate::lexer::Asi;
-use crate::ast::{Node, NodeList, Declarator, DeclarationKind};
+use crate::ast::{Node, NodeList, Declarator, DeclarationKind, Block, BlockNode};
 use crate::ast::{Statement, StatementNode, Expression, ExpressionNode, Class, Function, Pattern};
 use crate::ast::expression::BinaryExpression;
 use crate::ast::statement::{ThrowStatement, ContinueStatement, BreakStatement, ReturnStatement};
 use crate::ast::statement::{TryStatement, CatchClause, IfStatement, WhileStatement, DoStatement};
 use crate::ast::statement::{DeclarationStatement, ForStatement, ForInStatement, ForOfStatement};
-use crate::ast::statement::{SwitchStatement, SwitchCase, LabeledStatement, ForInit};
+use crate::ast::statement::{SwitchStatement, SwitchCase, LabeledStatement, ForInit, ExportDeclaration};
+use crate::ast::types::{InterfaceDeclaration, TypeAliasDeclaration, FunctionSignature, DeclareStatement};
+use crate::ast::types::{EnumDeclaration, EnumMember, NamespaceDeclaration};
+use crate::ast::{TypeMember, MandatoryName, DecoratorList, Label, LabelNode};
 use crate::ast::OperatorKind::*;
 
 
 type StatementHandler = for<'ast> fn(&mut Parser<'ast>) -> StatementNode<'ast>;
 
-static STMT_HANDLERS: [StatementHandler; 108] = [
+static STMT_HANDLERS: [StatementHandler; 109] = [
     ____, EMPT, ____, ____, PRN,  ____, ARR,  ____, BLCK, ____, ____, NEW,
 //  EOF   ;     :     ,     (     )     [     ]     {     }     =>    NEW
 
     OP,   OP,   OP,   OP,   OP,   OP,   OP,   ____, REG,  ____, ____, OP,
 //  ++    --    !     ~     TYPOF VOID  DELET *     /     %     **    +
 
-    OP,   ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
+    OP,   ____, ____, ____, JSX,  ____, ____, ____, ____, ____, ____, ____,
 //  -     <<    >>    >>>   <     <=    >     >=    INSOF IN    ===   !==
 
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, ____,
@@ -30,17 +34,20 @@ static STMT_HANDLERS: [StatementHandler; 108] = [
     ____, ____, ____, ____, ____, ____, ____, ____, ____, ____, VAR,  LET,
 //  *=    /=    %=    <<=   >>=   >>>=  &=    ^=    |=    ...   VAR   LET
 
-    CONS, BRK,  DO,   ____, ____, ____, ____, CLAS, ____, RET,  WHL,  ____,
+    CONS, BRK,  DO,   ____, ____, ____, EXPT, CLAS, ____, RET,  WHL,  ____,
 //  CONST BREAK DO    CASE  ELSE  CATCH EXPRT CLASS EXTND RET   WHILE FINLY
 
-    ____, ____, CONT, FOR,  SWCH, ____, ____, FUNC, THIS, ____, IF,   THRW,
+    SUPR, ____, CONT, FOR,  SWCH, ____, ____, FUNC, THIS, ____, IF,   THRW,
 //  SUPER WITH  CONT  FOR   SWTCH YIELD DBGGR FUNCT THIS  DEFLT IF    THROW
 
-    ____, TRY,  ____, TRUE, FALS, NULL, UNDE, STR,  NUM,  BIN,  ____, ____,
+    IMPT, TRY,  ____, TRUE, FALS, NULL, UNDE, STR,  NUM,  BIN,  ____, ENUM,
 //  IMPRT TRY   STATI TRUE  FALSE NULL  UNDEF STR   NUM   BIN   REGEX ENUM
 
-    ____, ____, ____, ____, ____, ____, LABL, ____, TPLE, TPLS, ____, ____,
+    ____, ____, ____, IFCE, ____, ____, LABL, ____, TPLE, TPLS, ____, ____,
 //  IMPL  PCKG  PROT  IFACE PRIV  PUBLI IDENT ACCSS TPL_O TPL_C ERR_T ERR_E
+
+    DECO,
+//  @
 ];
 
 
@@ -57,7 +64,7 @@ macro_rules! create_handlers {
 
 /// Shared expression handlers that produce StatementNode<'ast>
 use crate::parser::expression::handlers::{
-    PRN, ARR, OP, NEW, REG, THIS, TRUE, FALS, NULL, UNDE, STR, NUM, BIN, TPLS, TPLE
+    PRN, ARR, OP, NEW, REG, THIS, SUPR, TRUE, FALS, NULL, UNDE, STR, NUM, BIN, TPLS, TPLE, JSX
 };
 
 create_handlers! {
@@ -75,13 +82,33 @@ create_handlers! {
     const BLCK = |par| par.block_statement();
     const VAR  = |par| par.variable_declaration_statement(DeclarationKind::Var);
     const LET  = |par| par.variable_declaration_statement(DeclarationKind::Let);
-    const CONS = |par| par.variable_declaration_statement(DeclarationKind::Const);
+    const CONS = |par| match par.typescript && par.peek() == ReservedEnum {
+        true  => {
+            let start = par.lexer.start_then_consume();
+            par.enum_statement(start, true)
+        },
+        false => par.variable_declaration_statement(DeclarationKind::Const),
+    };
     const RET  = |par| par.return_statement();
     const BRK  = |par| par.break_statement();
     const THRW = |par| par.throw_statement();
     const CONT = |par| par.continue_statement();
     const FUNC = |par| par.function_statement();
     const CLAS = |par| par.class_statement();
+    const EXPT = |par| {
+        let start = par.lexer.start();
+
+        par.export_statement(start, NodeList::empty())
+    };
+    const DECO = |par| {
+        let start = par.lexer.start();
+        let decorators = par.decorators();
+
+        match par.lexer.token {
+            Export => par.export_statement(start, decorators),
+            _      => par.decorated_class_statement(start, decorators),
+        }
+    };
     const IF   = |par| par.if_statement();
     const WHL  = |par| par.while_statement();
     const DO   = |par| par.do_statement();
@@ -89,6 +116,21 @@ create_handlers! {
     const TRY  = |par| par.try_statement();
     const SWCH = |par| par.switch_statement();
     const LABL = |par| par.labeled_or_expression_statement();
+    const IFCE = |par| match par.types {
+        true  => par.interface_statement(),
+        false => ____(par),
+    };
+    const ENUM = |par| match par.typescript {
+        true  => {
+            let start = par.lexer.start();
+            par.enum_statement(start, false)
+        },
+        false => ____(par),
+    };
+    const IMPT = |par| match par.flow {
+        true  => par.type_import_statement(),
+        false => ____(par),
+    };
 }
 
 impl<'ast> Parse<'ast> for Statement<'ast> {
@@ -131,7 +173,7 @@ impl<'ast> Parse<'ast> for SwitchCase<'ast> {
 
         loop {
             match par.lexer.token {
-                Case | Default | BraceClose => break,
+                Case | Default | BraceClose | EndOfProgram => break,
                 _ => {
                     let statement = par.statement();
                     end = statement.end;
@@ -150,13 +192,15 @@ impl<'ast> Parse<'ast> for SwitchCase<'ast> {
 impl<'ast> Parser<'ast> {
     #[inline]
     pub fn statement(&mut self) -> StatementNode<'ast> {
-        unsafe { (*(&STMT_HANDLERS as *const StatementHandler).offset(self.lexer.token as isize))(self) }
+        self.nested(|par| unsafe {
+            (*(&STMT_HANDLERS as *const StatementHandler).offset(par.lexer.token as isize))(par)
+        })
     }
 
     /// Expect a semicolon to terminate a statement. Will assume a semicolon
     /// following the ASI rules.
     #[inline]
-    fn expect_semicolon(&mut self) {
+    pub fn expect_semicolon(&mut self) {
         match self.asi() {
             Asi::ExplicitSemicolon => self.lexer.consume(),
             Asi::ImplicitSemicolon => {},
@@ -168,7 +212,9 @@ impl<'ast> Parser<'ast> {
     pub fn block_statement(&mut self) -> StatementNode<'ast> {
         let start = self.lexer.start_then_consume();
         let block = self.raw_block();
-        let end   = self.lexer.end_then_consume();
+        let end   = self.lexer.end();
+
+        expect!(self, BraceClose);
 
         self.alloc_at_loc(start, end, block)
     }
@@ -189,6 +235,12 @@ impl<'ast> Parser<'ast> {
     #[inline]
     pub fn labeled_or_expression_statement(&mut self) -> StatementNode<'ast> {
         let label = self.lexer.token_as_str();
+
+        if self.await_allowed && label == "await" {
+            let expression = self.await_expression();
+
+            return self.expression_statement(expression);
+        }
         let (start, end) = self.lexer.loc();
 
         self.lexer.consume();
@@ -196,14 +248,73 @@ impl<'ast> Parser<'ast> {
         if self.lexer.token == Colon {
             self.lexer.consume();
 
+            let label = self.alloc_at_loc(start, end, Label(label));
+
+            self.enter_label(label);
+
             let body = self.statement();
 
+            self.labels.pop();
+
             return self.alloc_at_loc(start, body.end, LabeledStatement {
                 label,
                 body,
             });
         }
 
+        if self.types && self.asi() == Asi::NoSemicolon {
+            match (label, self.lexer.token) {
+                ("type", Identifier) => return self.type_alias_statement(start),
+                ("opaque", Identifier) if self.flow => return self.opaque_type_statement(start),
+                ("namespace", Identifier)   |
+                ("module", Identifier)      |
+                ("module", LiteralString)   if self.typescript => return self.namespace_statement(start),
+                ("global", BraceOpen)       if self.typescript => {
+                    let name = self.alloc_at_loc(start, end, label);
+                    let body = self.namespace_body();
+
+                    return self.alloc_at_loc(start, body.end, NamespaceDeclaration {
+                        name,
+                        body,
+                    });
+                },
+                ("declare", Identifier) => {
+                    let body = self.statement();
+
+                    return self.alloc_at_loc(start, body.end, DeclareStatement {
+                        body,
+                    });
+                },
+                ("declare", DeclarationVar)    |
+                ("declare", DeclarationLet)    |
+                ("declare", DeclarationConst)  |
+                ("declare", Function)          |
+                ("declare", Class)             |
+                ("declare", ReservedEnum)      |
+                ("declare", ReservedInterface) => {
+                    let body = self.statement();
+
+                    return self.alloc_at_loc(start, body.end, DeclareStatement {
+                        body,
+                    });
+                },
+                // Flow library definitions, `declare export function foo(): void;`
+                ("declare", Export) if self.flow => {
+                    // Declared default exports aren't supported
+                    if self.peek() == Default {
+                        return self.error();
+                    }
+
+                    let body = self.statement();
+
+                    return self.alloc_at_loc(start, body.end, DeclareStatement {
+                        body,
+                    });
+                },
+                _ => {},
+            }
+        }
+
         let expression = self.alloc_at_loc(start, end, label);
         let expression = self.nested_expression::<ANY>(expression);
 
@@ -215,17 +326,326 @@ impl<'ast> Parser<'ast> {
     #[inline]
     pub fn function_statement(&mut self) -> StatementNode<'ast> {
         let start = self.lexer.start_then_consume();
+
+        if self.types && self.lookahead(Parser::is_function_signature) {
+            let MandatoryName(name) = MandatoryName::parse(self);
+            let signature = self.signature();
+
+            self.expect_semicolon();
+
+            return self.alloc_at_loc(start, signature.end, FunctionSignature {
+                name,
+                signature: signature.item,
+            });
+        }
+
         let function = Function::parse(self);
 
         self.alloc_at_loc(start, function.body.end, function)
     }
 
+    /// Check if the function following the `function` keyword is
+    /// an overload signature without a body.
+    fn is_function_signature(&mut self) -> bool {
+        MandatoryName::parse(self);
+        self.signature();
+
+        self.lexer.token != BraceOpen
+    }
+
     #[inline]
-    fn class_statement(&mut self) -> StatementNode<'ast> {
+    fn interface_statement(&mut self) -> StatementNode<'ast> {
         let start = self.lexer.start_then_consume();
+        let MandatoryName(name) = MandatoryName::parse(self);
+        let type_parameters = self.type_parameters();
+
+        let extends = match self.lexer.token {
+            Extends => {
+                self.lexer.consume();
+                self.type_references()
+            },
+            _ => NodeList::empty(),
+        };
+
+        let body = self.block::<TypeMember>();
+
+        self.alloc_at_loc(start, body.end, InterfaceDeclaration {
+            name,
+            type_parameters,
+            extends,
+            body,
+        })
+    }
+
+    /// Parse an enum, starting at the `enum` keyword. The `const` modifier,
+    /// if any, was consumed by the caller.
+    #[inline]
+    pub fn enum_statement(&mut self, start: u32, is_const: bool) -> StatementNode<'ast> {
+        expect!(self, ReservedEnum);
+
+        let MandatoryName(name) = MandatoryName::parse(self);
+        let mut members = Vec::new();
+
+        expect!(self, BraceOpen);
+
+        while self.lexer.token != BraceClose {
+            if self.lexer.token != LiteralString && !self.lexer.token.is_word() {
+                return self.error();
+            }
+
+            members.push(self.enum_member());
+
+            match self.lexer.token {
+                Comma      => self.lexer.consume(),
+                BraceClose => break,
+                _          => return self.error(),
+            }
+        }
+
+        let end = self.lexer.end_then_consume();
+
+        self.alloc_at_loc(start, end, EnumDeclaration {
+            is_const,
+            name,
+            members: NodeList::from_iter(self.arena, members),
+        })
+    }
+
+    #[inline]
+    fn enum_member(&mut self) -> Node<'ast, EnumMember<'ast>> {
+        let name = self.lexer.token_as_str();
+        let name = self.alloc_in_loc(name);
+
+        self.lexer.consume();
+
+        let value = match self.lexer.token {
+            OperatorAssign => {
+                self.lexer.consume();
+
+                Some(self.expression::<B0>())
+            },
+            _ => None,
+        };
+
+        let end = value.map(|value| value.end).unwrap_or(name.end);
+
+        self.alloc_at_loc(name.start, end, EnumMember {
+            name,
+            value,
+        })
+    }
+
+    /// Parse the rest of a namespace after the `namespace` or `module`
+    /// keyword. Ambient modules, `declare module 'foo' {}`, are named
+    /// by a string literal instead.
+    #[inline]
+    fn namespace_statement(&mut self, start: u32) -> StatementNode<'ast> {
+        let name = self.lexer.token_as_str();
+        let mut name = self.alloc_in_loc(name);
+        let mut parents = Vec::new();
+
+        self.lexer.consume();
+
+        while self.lexer.token == Accessor {
+            let accessor = self.lexer.accessor_as_str();
+            let end = self.lexer.end();
+
+            parents.push(name);
+            name = self.alloc_at_loc(end - accessor.len() as u32, end, accessor);
+
+            self.lexer.consume();
+        }
+
+        let mut body = self.namespace_body();
+
+        loop {
+            let namespace_start = match parents.is_empty() {
+                true  => start,
+                false => name.start,
+            };
+
+            let namespace = self.alloc_at_loc(namespace_start, body.end, NamespaceDeclaration {
+                name,
+                body,
+            });
+
+            name = match parents.pop() {
+                Some(parent) => parent,
+                None         => return namespace,
+            };
+
+            let export = self.alloc_at_loc(namespace.start, namespace.end, ExportDeclaration {
+                is_default: false,
+                declaration: namespace,
+            });
+
+            body = self.alloc_at_loc(namespace.start, namespace.end, Block {
+                body: NodeList::from(self.arena, export),
+            });
+        }
+    }
+
+    #[inline]
+    fn namespace_body(&mut self) -> BlockNode<'ast, Statement<'ast>> {
+        let start = self.lexer.start();
+
+        expect!(self, BraceOpen);
+
+        let mut body = Vec::new();
+
+        while self.lexer.token != BraceClose && self.lexer.token != EndOfProgram {
+            body.push(self.namespace_member());
+        }
+
+        let end = self.lexer.end();
+
+        expect!(self, BraceClose);
+
+        self.alloc_at_loc(start, end, Block {
+            body: NodeList::from_iter(self.arena, body),
+        })
+    }
+
+    #[inline]
+    fn namespace_member(&mut self) -> StatementNode<'ast> {
+        let start = self.lexer.start();
+        let decorators = self.decorators();
+
+        if self.lexer.token != Export {
+            return match decorators.is_empty() {
+                true  => self.statement(),
+                false => self.decorated_class_statement(start, decorators),
+            };
+        }
+
+        // Namespaces have no default export
+        if self.peek() == Default {
+            return self.error();
+        }
+
+        self.export_statement(start, decorators)
+    }
+
+    /// Export of a declaration, `export const foo = 1;`. Decorators can
+    /// precede `export`, `@foo export class Bar {}`.
+    pub fn export_statement(&mut self, start: u32, decorators: DecoratorList<'ast>) -> StatementNode<'ast> {
+        expect!(self, Export);
+
+        if self.lexer.token == Default {
+            return self.export_default_statement(start, decorators);
+        }
+
+        let declaration = match decorators.is_empty() {
+            true  => self.statement(),
+            false => {
+                let class_start = self.lexer.start();
+
+                self.decorated_class_statement(class_start, decorators)
+            },
+        };
+
+        if !declaration.is_declaration() {
+            return self.error();
+        }
+
+        self.alloc_at_loc(start, declaration.end, ExportDeclaration {
+            is_default: false,
+            declaration,
+        })
+    }
+
+    /// Default export following `export`, `export default foo;`. Named
+    /// classes and functions are declarations, anything else is exported
+    /// as an expression. Decorators can follow `default` as well,
+    /// `export default @foo class {}`.
+    fn export_default_statement(&mut self, start: u32, decorators: DecoratorList<'ast>) -> StatementNode<'ast> {
+        self.lexer.consume();
+
+        let class_start = self.lexer.start();
+        let decorators = match decorators.is_empty() {
+            true  => self.decorators(),
+            false => decorators,
+        };
+
+        let named = match self.lexer.token {
+            Class | Function => self.lookahead(Parser::is_named_declaration),
+            _                => false,
+        };
+
+        let declaration = match self.lexer.token {
+            Class if named => self.decorated_class_statement(class_start, decorators),
+            Class => {
+                let class = self.decorated_class_expression(class_start, decorators);
+
+                self.alloc_at_loc(class.start, class.end, class)
+            },
+            _ if !decorators.is_empty() => return self.error(),
+            Function if named => self.function_statement(),
+            Function => {
+                let function = self.function_expression();
+
+                self.alloc_at_loc(function.start, function.end, function)
+            },
+            _ => {
+                let expression = self.expression::<B0>();
+
+                self.wrap_expression(expression)
+            },
+        };
+
+        self.alloc_at_loc(start, declaration.end, ExportDeclaration {
+            is_default: true,
+            declaration,
+        })
+    }
+
+    /// Check if the class or function keyword is followed by a name,
+    /// making it a declaration.
+    fn is_named_declaration(&mut self) -> bool {
+        self.lexer.consume();
+
+        if self.lexer.token == OperatorMultiplication {
+            self.lexer.consume();
+        }
+
+        self.lexer.token == Identifier
+    }
+
+    #[inline]
+    fn type_alias_statement(&mut self, start: u32) -> StatementNode<'ast> {
+        let MandatoryName(name) = MandatoryName::parse(self);
+        let type_parameters = self.type_parameters();
+
+        expect!(self, OperatorAssign);
+
+        let value = self.type_node();
+
+        self.expect_semicolon();
+
+        self.alloc_at_loc(start, value.end, TypeAliasDeclaration {
+            name,
+            type_parameters,
+            value,
+        })
+    }
+
+    #[inline]
+    fn class_statement(&mut self) -> StatementNode<'ast> {
+        let start = self.lexer.start();
+
+        self.decorated_class_statement(start, NodeList::empty())
+    }
+
+    #[inline]
+    pub fn decorated_class_statement(&mut self, start: u32, decorators: DecoratorList<'ast>) -> StatementNode<'ast> {
+        expect!(self, Class);
+
         let class = Class::parse(self);
 
-        self.alloc_at_loc(start, class.body.end, class)
+        self.alloc_at_loc(start, class.body.end, Class {
+            decorators,
+            ..class
+        })
     }
 
     #[inline]
@@ -245,7 +665,16 @@ impl<'ast> Parser<'ast> {
 
     #[inline]
     pub fn variable_declarator(&mut self) -> Node<'ast, Declarator<'ast>> {
-        let id = Pattern::parse(self);
+        let mut id = Pattern::parse(self);
+
+        if self.types {
+            // Definite assignment assertion, `let foo!: number;`
+            if self.lexer.token == OperatorLogicalNot {
+                self.lexer.consume();
+            }
+
+            id = self.pattern_annotation(id);
+        }
 
         let (init, end) = match self.lexer.token {
             OperatorAssign => {
@@ -320,15 +749,20 @@ impl<'ast> Parser<'ast> {
             },
             Asi::ImplicitSemicolon => None,
             Asi::NoSemicolon => {
-                let label = self.identifier();
+                let label = self.label();
                 end = label.end;
 
+                self.resolve_label(label);
                 self.expect_semicolon();
 
                 Some(label)
             }
         };
 
+        if label.is_none() {
+            self.resolve_break(start, end);
+        }
+
         self.alloc_at_loc(start, end, BreakStatement { label })
     }
 
@@ -344,18 +778,124 @@ impl<'ast> Parser<'ast> {
             },
             Asi::ImplicitSemicolon => None,
             Asi::NoSemicolon => {
-                let label = self.identifier();
+                let label = self.label();
                 end = label.end;
 
+                self.resolve_continue_label(label);
                 self.expect_semicolon();
 
                 Some(label)
             }
         };
 
+        if label.is_none() {
+            self.resolve_continue(start, end);
+        }
+
         self.alloc_at_loc(start, end, ContinueStatement { label })
     }
 
+    #[inline]
+    fn label(&mut self) -> LabelNode<'ast> {
+        let label = self.identifier();
+
+        self.alloc_at_loc(label.start, label.end, Label(label.item))
+    }
+
+    /// Push the label of a labeled statement, the body of which starts
+    /// at the current token.
+    fn enter_label(&mut self, label: LabelNode<'ast>) {
+        if self.labels.iter().any(|scope| scope.label == Some(label.item.as_str())) {
+            self.label_error(ErrorKind::DuplicateLabel, label);
+        }
+
+        let is_loop = matches!(self.lexer.token, For | While | Do);
+
+        // Labels directly preceding this one label the same statement
+        if is_loop {
+            let mut start = label.start;
+
+            for scope in self.labels.iter_mut().rev() {
+                if scope.label.is_none() || scope.body != start {
+                    break;
+                }
+
+                scope.is_loop = true;
+                start = scope.start;
+            }
+        }
+
+        self.labels.push(LabelScope {
+            label: Some(label.item.as_str()),
+            start: label.start,
+            body: self.lexer.start(),
+            is_loop,
+        });
+    }
+
+    fn resolve_label(&mut self, label: LabelNode<'ast>) -> Option<&LabelScope<'ast>> {
+        let position = self.labels.iter().rposition(|scope| scope.label == Some(label.item.as_str()));
+
+        match position {
+            Some(index) => Some(&self.labels[index]),
+            None => {
+                self.label_error(ErrorKind::UndefinedLabel, label);
+                None
+            }
+        }
+    }
+
+    fn resolve_continue_label(&mut self, label: LabelNode<'ast>) {
+        if let Some(scope) = self.resolve_label(label) {
+            if !scope.is_loop {
+                self.label_error(ErrorKind::IllegalContinue, label);
+            }
+        }
+    }
+
+    /// Check that an unlabeled `break` spanning `start..end` is within
+    /// a loop or a `switch`.
+    fn resolve_break(&mut self, start: u32, end: u32) {
+        if self.labels.iter().all(|scope| scope.label.is_some()) {
+            self.keyword_error(ErrorKind::IllegalBreak, Break, "break", start, end);
+        }
+    }
+
+    /// Check that an unlabeled `continue` spanning `start..end` is within a loop.
+    fn resolve_continue(&mut self, start: u32, end: u32) {
+        if !self.labels.iter().any(|scope| scope.label.is_none() && scope.is_loop) {
+            self.keyword_error(ErrorKind::IllegalContinue, Continue, "continue", start, end);
+        }
+    }
+
+    /// Parse the body of a loop, unlabeled `break` and `continue`
+    /// statements within it target the loop.
+    fn loop_body(&mut self) -> StatementNode<'ast> {
+        self.breakable(true, Self::statement)
+    }
+
+    /// Parse the body of a loop or the cases of a `switch`, with a scope
+    /// for unlabeled `break` and `continue` statements pushed.
+    fn breakable<T, F>(&mut self, is_loop: bool, parse: F) -> T
+    where
+        F: FnOnce(&mut Self) -> T,
+    {
+        let start = self.lexer.start();
+
+        self.labels.push(LabelScope {
+            label: None,
+            start,
+            body: start,
+            is_loop,
+        });
+
+        let body = parse(self);
+
+        self.labels.pop();
+
+        body
+    }
+
     #[inline]
     pub fn throw_statement(&mut self) -> StatementNode<'ast> {
         let start = self.lexer.start_then_consume();
@@ -446,7 +986,7 @@ impl<'ast> Parser<'ast> {
         let test = self.expression::<ANY>();
         expect!(self, ParenClose);
 
-        let body = self.statement();
+        let body = self.loop_body();
 
         self.alloc_at_loc(start, body.end, WhileStatement {
             test,
@@ -457,13 +997,18 @@ impl<'ast> Parser<'ast> {
     #[inline]
     pub fn do_statement(&mut self) -> StatementNode<'ast> {
         let start = self.lexer.start_then_consume();
-        let body = self.statement();
+        let body = self.loop_body();
         expect!(self, While);
         expect!(self, ParenOpen);
         let test = self.expression::<ANY>();
         let end = self.lexer.end();
         expect!(self, ParenClose);
 
+        // The semicolon is optional after `do..while`, even on the same line
+        if self.asi() == Asi::ExplicitSemicolon {
+            self.lexer.consume();
+        }
+
         self.alloc_at_loc(start, end, DoStatement {
             body,
             test,
@@ -474,8 +1019,8 @@ impl<'ast> Parser<'ast> {
     fn for_init(&mut self, kind: DeclarationKind) -> Node<'ast, ForInit<'ast>> {
         let start = self.lexer.start_then_consume();
         let declarators = self.variable_declarators();
-        let end = self.lexer.end();
-        
+        let end = declarators.iter().last().map_or(start, |declarator| declarator.end);
+
         self.alloc_at_loc(start, end, DeclarationStatement {
             kind,
             declarators
@@ -553,7 +1098,7 @@ impl<'ast> Parser<'ast> {
             }
         };
 
-        let body = self.statement();
+        let body = self.loop_body();
 
         self.alloc_at_loc(start, body.end, ForStatement {
             init,
@@ -566,7 +1111,7 @@ impl<'ast> Parser<'ast> {
     fn for_in_statement_from_parts(&mut self, start: u32, left: Node<'ast, ForInit<'ast>>, right: ExpressionNode<'ast>) -> StatementNode<'ast> {
         expect!(self, ParenClose);
 
-        let body = self.statement();
+        let body = self.loop_body();
 
         self.alloc_at_loc(start, body.end, ForInStatement {
             left,
@@ -580,7 +1125,7 @@ impl<'ast> Parser<'ast> {
 
         expect!(self, ParenClose);
 
-        let body = self.statement();
+        let body = self.loop_body();
 
         self.alloc_at_loc(start, body.end, ForInStatement {
             left,
@@ -594,7 +1139,7 @@ impl<'ast> Parser<'ast> {
 
         expect!(self, ParenClose);
 
-        let body = self.statement();
+        let body = self.loop_body();
 
         self.alloc_at_loc(start, body.end, ForOfStatement {
             left,
@@ -611,7 +1156,7 @@ impl<'ast> Parser<'ast> {
 
         expect!(self, ParenClose);
 
-        let cases = self.block();
+        let cases = self.breakable(false, Self::block);
 
         self.alloc_at_loc(start, cases.end, SwitchStatement {
             discriminant,
@@ -625,7 +1170,7 @@ mod test {
     use super::*;
     use crate::parser::parse;
     use crate::parser::mock::Mock;
-    use crate::ast::{NodeList, Literal, Function, Class, OperatorKind, BlockStatement};
+    use crate::ast::{NodeList, Literal, Function, Class, OperatorKind, BlockStatement, Decorator, OptionalName};
     use crate::ast::expression::*;
 
     #[test]
@@ -644,6 +1189,12 @@ mod test {
         assert_eq!(parse(src).unwrap().body(), expected);
     }
 
+    #[test]
+    fn unclosed_block_statement() {
+        assert!(parse("{ foo; ").is_err());
+        assert!(parse("if (foo) { bar; ").is_err());
+    }
+
     #[test]
     fn labeled_block_statement() {
         let src = "foobar: { true }";
@@ -651,7 +1202,7 @@ mod test {
 
         let expected = mock.list([
             LabeledStatement {
-                label: "foobar",
+                label: mock.ptr("foobar"),
                 body: mock.ptr(BlockStatement {
                     body: mock.list([
                         mock.ptr(Literal::True)
@@ -748,14 +1299,35 @@ mod test {
         assert_eq!(parse(src).unwrap().body(), expected);
     }
 
+    #[test]
+    fn do_statement_with_semicolon() {
+        let src = "do foo; while (true); bar";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            Statement::from(DoStatement {
+                body: mock.ptr(mock.ptr("foo")),
+                test: mock.ptr(Literal::True)
+            }),
+            Statement::from(mock.ptr("bar")),
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+    }
+
     #[test]
     fn break_statement() {
-        let src = "break;";
+        let src = "for (;;) break;";
         let mock = Mock::new();
 
         let expected = mock.list([
-            BreakStatement {
-                label: None,
+            ForStatement {
+                init: None,
+                test: None,
+                update: None,
+                body: mock.ptr(BreakStatement {
+                    label: None,
+                }),
             }
         ]);
 
@@ -764,18 +1336,83 @@ mod test {
 
     #[test]
     fn break_statement_label() {
-        let src = "break foo;";
+        let src = "foo: break foo;";
         let mock = Mock::new();
 
         let expected = mock.list([
-            BreakStatement {
-                label: Some(mock.ptr("foo")),
+            LabeledStatement {
+                label: mock.ptr("foo"),
+                body: mock.ptr(BreakStatement {
+                    label: Some(mock.ptr("foo")),
+                })
             }
         ]);
 
         assert_eq!(parse(src).unwrap().body(), expected);
     }
 
+    fn label_errors(src: &str) -> Vec<(ErrorKind, usize, usize)> {
+        match parse(src) {
+            Ok(_) => Vec::new(),
+            Err(errors) => errors.iter().map(|err| (err.kind, err.start, err.end)).collect(),
+        }
+    }
+
+    #[test]
+    fn labels_resolve_to_enclosing_statements() {
+        assert_eq!(label_errors("foo: { break foo; }"), []);
+        assert_eq!(label_errors("foo: for (;;) { bar: { continue foo; } }"), []);
+        assert_eq!(label_errors("foo: bar: while (true) continue foo;"), []);
+        assert_eq!(label_errors("foo: do { continue foo; } while (x)"), []);
+        assert_eq!(label_errors("foo: {} foo: {}"), []);
+        assert_eq!(label_errors("foo: { function f() { foo: for (;;) break foo; } }"), []);
+    }
+
+    #[test]
+    fn undefined_label() {
+        assert_eq!(label_errors("break foo;"), [(ErrorKind::UndefinedLabel, 6, 9)]);
+        assert_eq!(label_errors("foo: {} break foo;"), [(ErrorKind::UndefinedLabel, 14, 17)]);
+        assert_eq!(label_errors("foo: while (x) { () => { continue foo; }; }"), [(ErrorKind::UndefinedLabel, 34, 37)]);
+        assert_eq!(label_errors("foo: { function f() { break foo; } }"), [(ErrorKind::UndefinedLabel, 28, 31)]);
+    }
+
+    #[test]
+    fn duplicate_label() {
+        assert_eq!(label_errors("foo: { foo: x; }"), [(ErrorKind::DuplicateLabel, 7, 10)]);
+        assert_eq!(label_errors("foo: foo: x;"), [(ErrorKind::DuplicateLabel, 5, 8)]);
+    }
+
+    #[test]
+    fn continue_to_non_loop_label() {
+        assert_eq!(label_errors("foo: { continue foo; }"), [(ErrorKind::IllegalContinue, 16, 19)]);
+        assert_eq!(label_errors("foo: { while (x) continue foo; }"), [(ErrorKind::IllegalContinue, 26, 29)]);
+    }
+
+    #[test]
+    fn unlabeled_break_and_continue() {
+        assert_eq!(label_errors("while (x) { if (y) break; else continue; }"), []);
+        assert_eq!(label_errors("do { break; } while (x)"), []);
+        assert_eq!(label_errors("for (x in y) continue;"), []);
+        assert_eq!(label_errors("for (x of y) { break; }"), []);
+        assert_eq!(label_errors("switch (x) { case 1: break; }"), []);
+        assert_eq!(label_errors("for (;;) switch (x) { case 1: continue; }"), []);
+        assert_eq!(label_errors("foo: for (;;) { bar: { break; } }"), []);
+    }
+
+    #[test]
+    fn illegal_break() {
+        assert_eq!(label_errors("break;"), [(ErrorKind::IllegalBreak, 0, 5)]);
+        assert_eq!(label_errors("foo: { break; }"), [(ErrorKind::IllegalBreak, 7, 12)]);
+        assert_eq!(label_errors("while (x) { () => { break; }; }"), [(ErrorKind::IllegalBreak, 20, 25)]);
+    }
+
+    #[test]
+    fn illegal_continue() {
+        assert_eq!(label_errors("continue;"), [(ErrorKind::IllegalContinue, 0, 8)]);
+        assert_eq!(label_errors("switch (x) { case 1: continue; }"), [(ErrorKind::IllegalContinue, 21, 29)]);
+        assert_eq!(label_errors("for (;;) { function f() { continue; } }"), [(ErrorKind::IllegalContinue, 26, 34)]);
+    }
+
     #[test]
     fn throw_statement() {
         let src = "throw '3'";
@@ -799,7 +1436,7 @@ mod test {
             TryStatement {
                 block: mock.empty_block(),
                 handler: Some(mock.ptr(CatchClause {
-                    param: mock.ptr(Pattern::Identifier("err")),
+                    param: mock.ptr(Pattern::Identifier(mock.ptr("err"))),
                     body: mock.empty_block()
                 })),
                 finalizer: None
@@ -840,7 +1477,7 @@ mod test {
                     mock.ptr("foo")
                 ]),
                 handler: Some(mock.ptr(CatchClause {
-                    param: mock.ptr(Pattern::Identifier("err")),
+                    param: mock.ptr(Pattern::Identifier(mock.ptr("err"))),
                     body: mock.block([
                         mock.ptr("bar")
                     ])
@@ -869,15 +1506,15 @@ mod test {
                 kind: DeclarationKind::Var,
                 declarators: mock.list([
                     Declarator {
-                        id: mock.ptr(Pattern::Identifier("x")),
+                        id: mock.ptr(Pattern::Identifier(mock.ptr("x"))),
                         init: None,
                     },
                     Declarator {
-                        id: mock.ptr(Pattern::Identifier("y")),
+                        id: mock.ptr(Pattern::Identifier(mock.ptr("y"))),
                         init: None,
                     },
                     Declarator {
-                        id: mock.ptr(Pattern::Identifier("z")),
+                        id: mock.ptr(Pattern::Identifier(mock.ptr("z"))),
                         init: Some(mock.number("42"))
                     }
                 ])
@@ -897,7 +1534,7 @@ mod test {
                 kind: DeclarationKind::Const,
                 declarators: mock.list([
                     Declarator {
-                        id: mock.ptr(Pattern::Identifier("a")),
+                        id: mock.ptr(Pattern::Identifier(mock.ptr("a"))),
                         init: Some(mock.ptr(ObjectExpression {
                             body: mock.list([
                                 Property::Spread {
@@ -925,8 +1562,8 @@ mod test {
                     Declarator {
                         id: mock.ptr(Pattern::ArrayPattern {
                             elements: mock.list([
-                                Pattern::Identifier("x"),
-                                Pattern::Identifier("y")
+                                Pattern::Identifier(mock.ptr("x")),
+                                Pattern::Identifier(mock.ptr("y"))
                             ])
                         }),
                         init: Some(mock.ptr(ArrayExpression {
@@ -956,7 +1593,7 @@ mod test {
                         id: mock.ptr(Pattern::ArrayPattern {
                             elements: mock.list([
                                 Pattern::Void,
-                                Pattern::Identifier("foo")
+                                Pattern::Identifier(mock.ptr("foo"))
                             ])
                         }),
                         init: Some(mock.ptr("bar")),
@@ -980,14 +1617,14 @@ mod test {
                     Declarator {
                         id: mock.ptr(Pattern::ObjectPattern {
                             properties: mock.list([
-                                Property::Shorthand("x"),
-                                Property::Shorthand("y"),
+                                Property::Shorthand(mock.ptr("x")),
+                                Property::Shorthand(mock.ptr("y")),
                             ])
                         }),
                         init: Some(mock.ptr(ObjectExpression {
                             body: mock.list([
-                                Property::Shorthand("a"),
-                                Property::Shorthand("b"),
+                                Property::Shorthand(mock.ptr("a")),
+                                Property::Shorthand(mock.ptr("b")),
                             ])
                         })),
                     },
@@ -1009,7 +1646,7 @@ mod test {
                     kind: DeclarationKind::Let,
                     declarators: mock.list([
                         Declarator {
-                            id: mock.ptr(Pattern::Identifier("i")),
+                            id: mock.ptr(Pattern::Identifier(mock.ptr("i"))),
                             init: Some(mock.number("0")),
                         }
                     ]),
@@ -1063,11 +1700,11 @@ mod test {
                     kind: DeclarationKind::Let,
                     declarators: mock.list([
                         Declarator {
-                            id: mock.ptr(Pattern::Identifier("i")),
+                            id: mock.ptr(Pattern::Identifier(mock.ptr("i"))),
                             init: Some(mock.number("0")),
                         },
                         Declarator {
-                            id: mock.ptr(Pattern::Identifier("j")),
+                            id: mock.ptr(Pattern::Identifier(mock.ptr("j"))),
                             init: Some(mock.number("10")),
                         }
                     ]),
@@ -1114,11 +1751,11 @@ mod test {
                     kind: DeclarationKind::Let,
                     declarators: mock.list([
                         Declarator {
-                            id: mock.ptr(Pattern::Identifier("i")),
+                            id: mock.ptr(Pattern::Identifier(mock.ptr("i"))),
                             init: Some(mock.number("0")),
                         },
                         Declarator {
-                            id: mock.ptr(Pattern::Identifier("j")),
+                            id: mock.ptr(Pattern::Identifier(mock.ptr("j"))),
                             init: Some(mock.number("10")),
                         }
                     ]),
@@ -1156,6 +1793,8 @@ mod test {
 
         let expected = mock.list([
             Function {
+                type_parameters: NodeList::empty(),
+                return_type: None,
                 name: mock.name("foo"),
                 generator: false,
                 params: NodeList::empty(),
@@ -1178,6 +1817,10 @@ mod test {
 
         let expected = mock.list([
             Class {
+                decorators: NodeList::empty(),
+                type_parameters: NodeList::empty(),
+                super_type_arguments: NodeList::empty(),
+                implements: NodeList::empty(),
                 name: mock.name("Foo"),
                 extends: None,
                 body: mock.empty_block(),
@@ -1193,6 +1836,55 @@ mod test {
         parse("class {}").unwrap();
     }
 
+    #[test]
+    fn export_default_statement() {
+        let src = "export default @foo class {} export default function bar() {} export default a + b;";
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            ExportDeclaration {
+                is_default: true,
+                declaration: mock.ptr(mock.ptr(Class {
+                    decorators: mock.list([
+                        Decorator {
+                            expression: mock.ptr("foo"),
+                        }
+                    ]),
+                    type_parameters: NodeList::empty(),
+                    super_type_arguments: NodeList::empty(),
+                    implements: NodeList::empty(),
+                    name: OptionalName(None),
+                    extends: None,
+                    body: mock.empty_block(),
+                })),
+            },
+            ExportDeclaration {
+                is_default: true,
+                declaration: mock.ptr(Function {
+                    type_parameters: NodeList::empty(),
+                    return_type: None,
+                    name: mock.name("bar"),
+                    generator: false,
+                    params: NodeList::empty(),
+                    body: mock.empty_block(),
+                }),
+            },
+            ExportDeclaration {
+                is_default: true,
+                declaration: mock.ptr(mock.ptr(BinaryExpression {
+                    operator: OperatorKind::Addition,
+                    left: mock.ptr("a"),
+                    right: mock.ptr("b"),
+                })),
+            },
+        ]);
+
+        assert_eq!(parse(src).unwrap().body(), expected);
+        assert!(parse("@foo export default class Bar {} export default function* () {}").is_ok());
+        assert!(parse("export default a, b;").is_err());
+        assert!(parse("export default @foo function bar() {}").is_err());
+    }
+
     #[test]
     fn switch_statement() {
         let src = r#"
@@ -0,0 +1,1471 @@
+use toolshed::list::ListBuilder;
+use crate::parser::{Parser, Parse, ANY};
+use crate::parser::error::ToError;
+use crate::lexer::{Asi, Token};
+use crate::lexer::Token::*;
+use crate::ast::{Node, NodeList, Loc, Literal, Pattern, PropertyKey, TypeNode, TypeList, SYNTHETIC};
+use crate::ast::types::*;
+
+impl<'ast> ToError for Node<'ast, Type<'ast>> {
+    #[inline]
+    fn to_error() -> Self {
+        Node::new(&Loc {
+            start: SYNTHETIC,
+            end: SYNTHETIC,
+            item: Type::Any
+        })
+    }
+}
+
+impl<'ast> ToError for Node<'ast, TypeName<'ast>> {
+    #[inline]
+    fn to_error() -> Self {
+        Node::new(&Loc {
+            start: SYNTHETIC,
+            end: SYNTHETIC,
+            item: TypeName::Identifier("")
+        })
+    }
+}
+
+impl<'ast> Parse<'ast> for TypeMember<'ast> {
+    type Output = Node<'ast, Self>;
+
+    #[inline]
+    fn parse(par: &mut Parser<'ast>) -> Self::Output {
+        let member = match par.lexer.token {
+            ParenOpen | OperatorLesser => {
+                let signature = par.signature();
+                let signature = par.alloc(signature);
+
+                par.alloc_at_loc(signature.start, signature.end, TypeMember::Call { signature })
+            },
+            OperatorNew if par.peek() == ParenOpen || par.peek() == OperatorLesser => {
+                let start = par.lexer.start_then_consume();
+                let signature = par.signature();
+                let signature = par.alloc(signature);
+
+                par.alloc_at_loc(start, signature.end, TypeMember::Construct { signature })
+            },
+            OperatorSpread if par.flow => {
+                let start = par.lexer.start_then_consume();
+                let argument = par.type_node();
+
+                par.alloc_at_loc(start, argument.end, TypeMember::Spread { argument })
+            },
+            _ => par.property_type_member(),
+        };
+
+        match par.lexer.token {
+            Semicolon | Comma => par.lexer.consume(),
+            _                 => {},
+        }
+
+        member
+    }
+}
+
+impl<'ast> Parser<'ast> {
+    /// Parse the `: Type` annotation of a binding, a property or the
+    /// return value of a function, if there is one.
+    #[inline]
+    pub fn type_annotation(&mut self) -> Option<TypeNode<'ast>> {
+        match self.lexer.token {
+            Colon if self.types => {
+                self.lexer.consume();
+
+                Some(self.type_node())
+            },
+            _ => None,
+        }
+    }
+
+    /// Parse the optional `?` marker and type annotation following
+    /// a binding `pattern`.
+    #[inline]
+    pub fn pattern_annotation(&mut self, pattern: Node<'ast, Pattern<'ast>>) -> Node<'ast, Pattern<'ast>> {
+        if !self.types {
+            return pattern;
+        }
+
+        let mut end = pattern.end;
+
+        let optional = match self.lexer.token {
+            OperatorConditional => {
+                end = self.lexer.end_then_consume();
+                true
+            },
+            _ => false,
+        };
+
+        let annotation = self.type_annotation();
+
+        if let Some(annotation) = annotation {
+            end = annotation.end;
+        } else if !optional {
+            return pattern;
+        }
+
+        self.alloc_at_loc(pattern.start, end, Pattern::Annotated {
+            pattern,
+            optional,
+            annotation,
+        })
+    }
+
+    /// Parse the `<T, U extends V = W>` type parameters of a declaration,
+    /// if there are any.
+    #[inline]
+    pub fn type_parameters(&mut self) -> TypeParameterList<'ast> {
+        if !self.types || self.lexer.token != OperatorLesser {
+            return NodeList::empty();
+        }
+
+        self.lexer.consume();
+
+        let builder = ListBuilder::new(self.arena, self.type_parameter());
+
+        loop {
+            self.lexer.read_greater();
+
+            match self.lexer.token {
+                Comma => self.lexer.consume(),
+                OperatorGreater => break,
+                _ => {
+                    self.error::<()>();
+                    break;
+                }
+            }
+
+            self.lexer.read_greater();
+
+            match self.lexer.token {
+                OperatorGreater => break,
+                _               => builder.push(self.arena, self.type_parameter()),
+            }
+        }
+
+        expect!(self, OperatorGreater);
+
+        builder.as_list()
+    }
+
+    #[inline]
+    fn type_parameter(&mut self) -> Node<'ast, TypeParameter<'ast>> {
+        let name = self.identifier();
+        let mut end = name.end;
+
+        let constraint = match self.lexer.token {
+            Extends => {
+                self.lexer.consume();
+
+                let constraint = self.type_node();
+                end = constraint.end;
+
+                Some(constraint)
+            },
+            _ => None,
+        };
+
+        let default = match self.lexer.token {
+            OperatorAssign => {
+                self.lexer.consume();
+
+                let default = self.type_node();
+                end = default.end;
+
+                Some(default)
+            },
+            _ => None,
+        };
+
+        self.alloc_at_loc(name.start, end, TypeParameter {
+            name,
+            constraint,
+            default,
+        })
+    }
+
+    /// Parse the `<A, B>` type arguments of a reference, call or class
+    /// heritage. The `<` is the current token, the closing `>` is left
+    /// for the caller to consume.
+    #[inline]
+    pub fn type_arguments(&mut self) -> TypeList<'ast> {
+        self.lexer.consume();
+
+        let builder = ListBuilder::new(self.arena, self.type_node());
+
+        loop {
+            self.lexer.read_greater();
+
+            match self.lexer.token {
+                Comma => {
+                    self.lexer.consume();
+                    builder.push(self.arena, self.type_node());
+                },
+                OperatorGreater => break,
+                _ => {
+                    self.error::<()>();
+                    break;
+                }
+            }
+        }
+
+        builder.as_list()
+    }
+
+    /// Check whether a `<` in an expression opens type arguments, that
+    /// is followed by something that can't continue a comparison.
+    #[inline]
+    pub fn is_type_arguments(&mut self) -> bool {
+        self.type_arguments();
+
+        if self.lexer.token != OperatorGreater {
+            return false;
+        }
+
+        self.lexer.consume();
+
+        match self.lexer.token {
+            ParenOpen      |
+            TemplateOpen   |
+            TemplateClosed |
+            Semicolon      |
+            Colon          |
+            Comma          |
+            ParenClose     |
+            BracketClose   |
+            BraceClose     |
+            EndOfProgram   => true,
+            _              => self.asi() == Asi::ImplicitSemicolon,
+        }
+    }
+
+    /// Parse the type parameters, parameters and the return type
+    /// of a function.
+    #[inline]
+    pub fn signature(&mut self) -> Loc<FunctionType<'ast>> {
+        let start = self.lexer.start();
+        let type_parameters = self.type_parameters();
+        let (params, end) = self.params();
+
+        if self.flow && self.lexer.token == Colon && self.peek() == OperatorRemainder {
+            self.lexer.consume();
+            self.checks_predicate();
+        }
+
+        let return_type = self.type_annotation();
+        let end = return_type.map(|node| node.end).unwrap_or(end);
+
+        if self.flow && self.lexer.token == OperatorRemainder {
+            self.checks_predicate();
+        }
+
+        Loc::new(start, end, FunctionType {
+            type_parameters,
+            params,
+            return_type,
+        })
+    }
+
+    /// Parse the head of an arrow function, up to and including the `=>`.
+    #[inline]
+    pub fn arrow_signature(&mut self) -> Loc<FunctionType<'ast>> {
+        let signature = self.signature();
+
+        expect!(self, OperatorFatArrow);
+
+        signature
+    }
+
+    /// Same as `signature`, but with a mandatory `=> Type` return type,
+    /// as used by function and constructor types.
+    #[inline]
+    fn function_type(&mut self) -> Loc<FunctionType<'ast>> {
+        let start = self.lexer.start();
+        let type_parameters = self.type_parameters();
+        let (params, _) = self.params();
+
+        expect!(self, OperatorFatArrow);
+
+        let return_type = self.type_node();
+
+        Loc::new(start, return_type.end, FunctionType {
+            type_parameters,
+            params,
+            return_type: Some(return_type),
+        })
+    }
+
+    /// Parse a list of comma separated type references, as used in the
+    /// `implements` clause of classes, or `extends` of interfaces.
+    #[inline]
+    pub fn type_references(&mut self) -> TypeList<'ast> {
+        let builder = ListBuilder::new(self.arena, self.type_reference());
+
+        while self.lexer.token == Comma {
+            self.lexer.consume();
+            builder.push(self.arena, self.type_reference());
+        }
+
+        builder.as_list()
+    }
+
+    /// Parse any type.
+    #[inline]
+    pub fn type_node(&mut self) -> TypeNode<'ast> {
+        self.nested(|par| {
+            let start = par.lexer.start();
+
+            match par.lexer.token {
+                OperatorLesser => {
+                    let Loc { start, end, item } = par.function_type();
+
+                    par.alloc_at_loc(start, end, Type::Function(item))
+                },
+                OperatorNew => {
+                    par.lexer.consume();
+
+                    let Loc { end, item, .. } = par.function_type();
+
+                    par.alloc_at_loc(start, end, Type::Constructor(item))
+                },
+                ParenOpen => match par.speculate(Parser::function_type) {
+                    Some(Loc { start, end, item }) => par.alloc_at_loc(start, end, Type::Function(item)),
+                    None                           => par.conditional_type(),
+                },
+                _ => par.conditional_type(),
+            }
+        })
+    }
+
+    #[inline]
+    fn conditional_type(&mut self) -> TypeNode<'ast> {
+        let check = self.union_type();
+
+        if self.lexer.token != Extends {
+            return check;
+        }
+
+        self.lexer.consume();
+
+        let extends = self.union_type();
+
+        expect!(self, OperatorConditional);
+
+        let consequent = self.type_node();
+
+        expect!(self, Colon);
+
+        let alternate = self.type_node();
+
+        self.alloc_at_loc(check.start, alternate.end, Type::Conditional {
+            check,
+            extends,
+            consequent,
+            alternate,
+        })
+    }
+
+    #[inline]
+    fn union_type(&mut self) -> TypeNode<'ast> {
+        self.type_list(OperatorBitwiseOr, Parser::intersection_type, |variants| Type::Union { variants })
+    }
+
+    #[inline]
+    fn intersection_type(&mut self) -> TypeNode<'ast> {
+        self.type_list(OperatorBitwiseAnd, Parser::type_operator, |variants| Type::Intersection { variants })
+    }
+
+    /// Check if the current token is the `separator`. In Flow, a `|`
+    /// followed by `}` closes an exact object type instead.
+    #[inline]
+    fn is_type_separator(&self, separator: Token) -> bool {
+        self.lexer.token == separator && !(self.flow && separator == OperatorBitwiseOr && self.peek() == BraceClose)
+    }
+
+    /// Parse types separated by the `separator` token, which can also
+    /// lead the first type, such as in `| A | B`.
+    #[inline]
+    fn type_list<F, W>(&mut self, separator: Token, get: F, wrap: W) -> TypeNode<'ast> where
+        F: Fn(&mut Parser<'ast>) -> TypeNode<'ast>,
+        W: FnOnce(TypeList<'ast>) -> Type<'ast>,
+    {
+        let start = self.lexer.start();
+
+        if self.lexer.token == separator {
+            self.lexer.consume();
+        }
+
+        let first = get(self);
+
+        if !self.is_type_separator(separator) {
+            return first;
+        }
+
+        let builder = ListBuilder::new(self.arena, first);
+        let mut end = first.end;
+
+        while self.is_type_separator(separator) {
+            self.lexer.consume();
+
+            let variant = get(self);
+            end = variant.end;
+
+            builder.push(self.arena, variant);
+        }
+
+        self.alloc_at_loc(start, end, wrap(builder.as_list()))
+    }
+
+    #[inline]
+    pub fn type_operator(&mut self) -> TypeNode<'ast> {
+        if self.flow && self.lexer.token == OperatorConditional {
+            return self.nullable_type();
+        }
+
+        if self.lexer.token != Identifier {
+            return self.postfix_type();
+        }
+
+        let operator = match self.lexer.token_as_str() {
+            "keyof"    => TypeOperator::KeyOf,
+            "unique"   => TypeOperator::Unique,
+            "readonly" => TypeOperator::Readonly,
+            "infer"    => {
+                let start = self.lexer.start_then_consume();
+                let parameter = self.identifier();
+
+                return self.alloc_at_loc(start, parameter.end, Type::Infer { parameter });
+            },
+            _ => return self.postfix_type(),
+        };
+
+        let start = self.lexer.start_then_consume();
+        let argument = self.nested(Parser::type_operator);
+
+        self.alloc_at_loc(start, argument.end, Type::Operator {
+            operator,
+            argument,
+        })
+    }
+
+    #[inline]
+    fn postfix_type(&mut self) -> TypeNode<'ast> {
+        let depth = self.depth;
+        let mut left = self.primary_type();
+
+        while self.lexer.token == BracketOpen && self.asi() == Asi::NoSemicolon {
+            if !self.descend() {
+                break;
+            }
+
+            self.lexer.consume();
+
+            left = match self.lexer.token {
+                BracketClose => {
+                    let end = self.lexer.end_then_consume();
+
+                    self.alloc_at_loc(left.start, end, Type::Array { element: left })
+                },
+                _ => {
+                    let index = self.type_node();
+                    let end = self.lexer.end();
+
+                    expect!(self, BracketClose);
+
+                    self.alloc_at_loc(left.start, end, Type::IndexedAccess {
+                        object: left,
+                        index,
+                    })
+                }
+            };
+        }
+
+        self.depth = depth;
+
+        left
+    }
+
+    #[inline]
+    fn primary_type(&mut self) -> TypeNode<'ast> {
+        let (start, end) = self.lexer.loc();
+
+        let item = match self.lexer.token {
+            Identifier => match self.lexer.token_as_str() {
+                "any"     => Type::Any,
+                "unknown" => Type::Unknown,
+                "never"   => Type::Never,
+                "object"  => Type::Object,
+                "number"  => Type::Primitive(Primitive::Number),
+                "string"  => Type::Primitive(Primitive::String),
+                "boolean" => Type::Primitive(Primitive::Boolean),
+                "bigint"  => Type::Primitive(Primitive::BigInt),
+                "symbol"  => Type::Primitive(Primitive::Symbol),
+                _         => return self.type_reference(),
+            },
+            OperatorVoid     => Type::Void,
+            This             => Type::This,
+            LiteralNull      => Type::Primitive(Primitive::Null),
+            LiteralUndefined => Type::Primitive(Primitive::Undefined),
+            LiteralTrue      => Type::Literal(Literal::True),
+            LiteralFalse     => Type::Literal(Literal::False),
+            LiteralString    => Type::Literal(Literal::String(self.lexer.token_as_str())),
+            LiteralNumber    => Type::Literal(Literal::Number(self.lexer.token_as_str())),
+            LiteralBinary    => Type::Literal(Literal::Binary(self.lexer.token_as_str())),
+            OperatorSubtraction => {
+                self.lexer.consume();
+
+                let (_, end) = self.lexer.loc();
+                let number = match self.lexer.token {
+                    LiteralNumber => self.lexer.token_as_str(),
+                    _             => return self.error(),
+                };
+                let number = self.arena.alloc_str(&format!("-{}", number));
+
+                self.lexer.consume();
+
+                return self.alloc_at_loc(start, end, Type::Literal(Literal::Number(number)));
+            },
+            // `as const` assertions refer to the `const` keyword
+            DeclarationConst => {
+                let name = self.alloc_in_loc(TypeName::Identifier("const"));
+
+                self.lexer.consume();
+
+                return self.alloc_at_loc(start, end, Type::Reference {
+                    name,
+                    arguments: NodeList::empty(),
+                });
+            },
+            OperatorTypeof => {
+                self.lexer.consume();
+
+                let name = self.type_name();
+
+                return self.alloc_at_loc(start, name.end, Type::Query { name });
+            },
+            ParenOpen => {
+                self.lexer.consume();
+
+                let item = self.type_node();
+
+                expect!(self, ParenClose);
+
+                return item;
+            },
+            BraceOpen   => return self.object_type(),
+            BracketOpen => return self.tuple_type(),
+            _           => return self.error(),
+        };
+
+        self.lexer.consume();
+
+        self.alloc_at_loc(start, end, item)
+    }
+
+    #[inline]
+    fn type_name(&mut self) -> Node<'ast, TypeName<'ast>> {
+        let name = match self.lexer.token {
+            Identifier => self.lexer.token_as_str(),
+            _          => return self.error(),
+        };
+
+        let mut left = self.alloc_in_loc(TypeName::Identifier(name));
+
+        self.lexer.consume();
+
+        while self.lexer.token == Accessor {
+            let name = self.lexer.accessor_as_str();
+            let end = self.lexer.end();
+            let right = self.alloc_at_loc(end - name.len() as u32, end, name);
+
+            self.lexer.consume();
+
+            left = self.alloc_at_loc(left.start, end, TypeName::Qualified {
+                left,
+                right,
+            });
+        }
+
+        left
+    }
+
+    #[inline]
+    fn type_reference(&mut self) -> TypeNode<'ast> {
+        let name = self.type_name();
+
+        if let TypeName::Identifier(parameter) = name.item {
+            if self.lexer.token == Identifier && self.lexer.token_as_str() == "is" && self.asi() == Asi::NoSemicolon {
+                self.lexer.consume();
+
+                let parameter = self.alloc_at_loc(name.start, name.end, parameter);
+                let annotation = self.type_node();
+
+                return self.alloc_at_loc(name.start, annotation.end, Type::Predicate {
+                    parameter,
+                    annotation,
+                });
+            }
+        }
+
+        let (arguments, end) = if self.lexer.token == OperatorLesser && self.asi() == Asi::NoSemicolon {
+            let arguments = self.type_arguments();
+
+            (arguments, self.lexer.end_then_consume())
+        } else {
+            (NodeList::empty(), name.end)
+        };
+
+        self.alloc_at_loc(name.start, end, Type::Reference {
+            name,
+            arguments,
+        })
+    }
+
+    #[inline]
+    fn tuple_type(&mut self) -> TypeNode<'ast> {
+        self.nested(|par| {
+            let start = par.lexer.start_then_consume();
+
+            let elements = match par.lexer.token {
+                BracketClose => NodeList::empty(),
+                _ => {
+                    let builder = ListBuilder::new(par.arena, par.tuple_element());
+
+                    while par.lexer.token == Comma {
+                        par.lexer.consume();
+
+                        if par.lexer.token == BracketClose {
+                            break;
+                        }
+
+                        builder.push(par.arena, par.tuple_element());
+                    }
+
+                    builder.as_list()
+                }
+            };
+
+            let end = par.lexer.end();
+
+            expect!(par, BracketClose);
+
+            par.alloc_at_loc(start, end, Type::Tuple { elements })
+        })
+    }
+
+    #[inline]
+    fn tuple_element(&mut self) -> TypeNode<'ast> {
+        if self.lexer.token == OperatorSpread {
+            let start = self.lexer.start_then_consume();
+            let argument = self.type_node();
+
+            return self.alloc_at_loc(start, argument.end, Type::Rest { argument });
+        }
+
+        let argument = self.type_node();
+
+        match self.lexer.token {
+            OperatorConditional => {
+                let end = self.lexer.end_then_consume();
+
+                self.alloc_at_loc(argument.start, end, Type::Optional { argument })
+            },
+            _ => argument,
+        }
+    }
+
+    #[inline]
+    fn object_type(&mut self) -> TypeNode<'ast> {
+        if self.lookahead(Parser::is_mapped_type) {
+            return self.mapped_type();
+        }
+
+        if self.flow {
+            if let OperatorBitwiseOr | OperatorLogicalOr = self.peek() {
+                return self.exact_object_type();
+            }
+        }
+
+        self.nested(|par| {
+            let start = par.lexer.start_then_consume();
+            let members = par.raw_block::<TypeMember>().body;
+            let end = par.lexer.end();
+
+            expect!(par, BraceClose);
+
+            par.alloc_at_loc(start, end, Type::ObjectLiteral {
+                members,
+                exact: false,
+            })
+        })
+    }
+
+    /// Check if the `{` is followed by `[K in`, optionally preceded by
+    /// a `readonly` modifier.
+    #[inline]
+    fn is_mapped_type(&mut self) -> bool {
+        self.lexer.consume();
+
+        if let OperatorAddition | OperatorSubtraction = self.lexer.token {
+            self.lexer.consume();
+        }
+
+        if self.lexer.token == Identifier && self.lexer.token_as_str() == "readonly" {
+            self.lexer.consume();
+        }
+
+        if self.lexer.token != BracketOpen {
+            return false;
+        }
+
+        self.lexer.consume();
+
+        if self.lexer.token != Identifier {
+            return false;
+        }
+
+        self.lexer.consume();
+
+        self.lexer.token == OperatorIn
+    }
+
+    #[inline]
+    fn mapped_type(&mut self) -> TypeNode<'ast> {
+        self.nested(|par| {
+            let start = par.lexer.start_then_consume();
+
+            let mut readonly = true;
+
+            match par.lexer.token {
+                OperatorAddition    => par.lexer.consume(),
+                OperatorSubtraction => {
+                    par.lexer.consume();
+                    readonly = false;
+                },
+                _ => {},
+            }
+
+            readonly = readonly && match par.lexer.token {
+                Identifier if par.lexer.token_as_str() == "readonly" => {
+                    par.lexer.consume();
+                    true
+                },
+                _ => false,
+            };
+
+            expect!(par, BracketOpen);
+
+            let parameter = par.identifier();
+
+            expect!(par, OperatorIn);
+
+            let constraint = par.type_node();
+
+            expect!(par, BracketClose);
+
+            let mut optional = true;
+
+            match par.lexer.token {
+                OperatorAddition    => par.lexer.consume(),
+                OperatorSubtraction => {
+                    par.lexer.consume();
+                    optional = false;
+                },
+                _ => {},
+            }
+
+            optional = optional && match par.lexer.token {
+                OperatorConditional => {
+                    par.lexer.consume();
+                    true
+                },
+                _ => false,
+            };
+
+            let annotation = par.type_annotation();
+
+            if let Semicolon | Comma = par.lexer.token {
+                par.lexer.consume();
+            }
+
+            let end = par.lexer.end();
+
+            expect!(par, BraceClose);
+
+            par.alloc_at_loc(start, end, Type::Mapped {
+                readonly,
+                parameter,
+                constraint,
+                optional,
+                annotation,
+            })
+        })
+    }
+
+    #[inline]
+    fn property_type_member(&mut self) -> Node<'ast, TypeMember<'ast>> {
+        let start = self.lexer.start();
+        let readonly = self.modifier("readonly");
+
+        if self.lexer.token == BracketOpen && self.lookahead(Parser::is_index_signature) {
+            self.lexer.consume();
+
+            let parameter = self.identifier();
+
+            expect!(self, Colon);
+
+            let key = self.type_node();
+
+            expect!(self, BracketClose);
+            expect!(self, Colon);
+
+            let annotation = self.type_node();
+
+            return self.alloc_at_loc(start, annotation.end, TypeMember::Index {
+                readonly,
+                parameter,
+                key,
+                annotation,
+            });
+        }
+
+        let key = self.type_member_key();
+        let mut end = key.end;
+
+        let optional = match self.lexer.token {
+            OperatorConditional => {
+                end = self.lexer.end_then_consume();
+                true
+            },
+            _ => false,
+        };
+
+        match self.lexer.token {
+            ParenOpen | OperatorLesser => {
+                let signature = self.signature();
+                let signature = self.alloc(signature);
+
+                self.alloc_at_loc(start, signature.end, TypeMember::Method {
+                    key,
+                    optional,
+                    signature,
+                })
+            },
+            _ => {
+                let annotation = self.type_annotation();
+
+                if let Some(annotation) = annotation {
+                    end = annotation.end;
+                }
+
+                self.alloc_at_loc(start, end, TypeMember::Property {
+                    readonly,
+                    key,
+                    optional,
+                    annotation,
+                })
+            }
+        }
+    }
+
+    #[inline]
+    fn is_index_signature(&mut self) -> bool {
+        self.lexer.consume();
+
+        if self.lexer.token != Identifier {
+            return false;
+        }
+
+        self.lexer.consume();
+
+        self.lexer.token == Colon
+    }
+
+    #[inline]
+    fn type_member_key(&mut self) -> Node<'ast, PropertyKey<'ast>> {
+        let key = match self.lexer.token {
+            _ if self.lexer.token.is_word() => PropertyKey::Literal(self.lexer.token_as_str()),
+            LiteralString | LiteralNumber   => PropertyKey::Literal(self.lexer.token_as_str()),
+            LiteralBinary                   => PropertyKey::Binary(self.lexer.token_as_str()),
+            BracketOpen => {
+                let start = self.lexer.start_then_consume();
+                let expression = self.expression::<ANY>();
+                let end = self.lexer.end();
+
+                expect!(self, BracketClose);
+
+                return self.alloc_at_loc(start, end, PropertyKey::Computed(expression));
+            },
+            _ => return self.error(),
+        };
+
+        let key = self.alloc_in_loc(key);
+
+        self.lexer.consume();
+
+        key
+    }
+
+    /// Consume a contextual keyword used as a modifier, such as `readonly`,
+    /// unless it's the name of a property or parameter itself.
+    #[inline]
+    fn modifier(&mut self, keyword: &str) -> bool {
+        if self.lexer.token != Identifier || self.lexer.token_as_str() != keyword {
+            return false;
+        }
+
+        match self.peek() {
+            Colon               |
+            Comma               |
+            Semicolon           |
+            ParenOpen           |
+            ParenClose          |
+            BraceClose          |
+            OperatorLesser      |
+            OperatorAssign      |
+            OperatorConditional |
+            OperatorLogicalNot  |
+            EndOfProgram        => false,
+            _                   => {
+                self.lexer.consume();
+                true
+            }
+        }
+    }
+
+    /// Parse modifiers of a class member or constructor parameter into
+    /// `modifiers`.
+    #[inline]
+    pub fn modifiers(&mut self, modifiers: &mut Modifiers) {
+        loop {
+            let accessibility = match self.lexer.token {
+                ReservedPublic    => Accessibility::Public,
+                ReservedProtected => Accessibility::Protected,
+                ReservedPrivate   => Accessibility::Private,
+                _ => {
+                    if self.modifier("readonly") {
+                        modifiers.readonly = true;
+                    } else if self.modifier("declare") {
+                        modifiers.declare = true;
+                    } else if self.modifier("override") {
+                        modifiers.is_override = true;
+                    } else {
+                        return;
+                    }
+
+                    continue;
+                }
+            };
+
+            self.lexer.consume();
+
+            modifiers.accessibility = Some(accessibility);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::{parse_with_options, ParseOptions};
+    use crate::parser::mock::Mock;
+    use crate::ast::{Statement, Expression, Function, Literal, Block, Declarator, DeclarationKind};
+    use crate::ast::expression::*;
+    use crate::ast::statement::*;
+    use crate::error::Error;
+    use crate::module::Module;
+
+    fn parse_ts<'ast>(source: &str) -> Result<Module<'ast>, Vec<Error>> {
+        parse_with_options(source, ParseOptions { typescript: true, ..ParseOptions::default() })
+    }
+
+    macro_rules! assert_type {
+        ($src:expr, $expected:expr) => ({
+            let module = parse_ts(&format!("type T = {};", $src)).unwrap();
+
+            match module.body().only_element().map(|s| s.item) {
+                Some(Statement::TypeAlias(TypeAliasDeclaration { value, .. })) => assert_eq!(value.item, $expected),
+                _ => panic!("Statement isn't a type alias!")
+            }
+        })
+    }
+
+    fn reference<'ast>(mock: &'ast Mock, name: &'ast str) -> Type<'ast> {
+        Type::Reference {
+            name: mock.ptr(TypeName::Identifier(name)),
+            arguments: NodeList::empty(),
+        }
+    }
+
+    #[test]
+    fn keyword_types() {
+        assert_type!("any", Type::Any);
+        assert_type!("unknown", Type::Unknown);
+        assert_type!("never", Type::Never);
+        assert_type!("void", Type::Void);
+        assert_type!("object", Type::Object);
+        assert_type!("number", Type::Primitive(Primitive::Number));
+        assert_type!("string", Type::Primitive(Primitive::String));
+        assert_type!("null", Type::Primitive(Primitive::Null));
+        assert_type!("undefined", Type::Primitive(Primitive::Undefined));
+        assert_type!("'foo'", Type::Literal(Literal::String("'foo'")));
+        assert_type!("-1", Type::Literal(Literal::Number("-1")));
+    }
+
+    #[test]
+    fn union_and_array_types() {
+        let mock = Mock::new();
+
+        assert_type!("| Foo | Bar[]", Type::Union {
+            variants: mock.list([
+                reference(&mock, "Foo"),
+                Type::Array { element: mock.ptr(reference(&mock, "Bar")) },
+            ])
+        });
+
+        assert_type!("(Foo & Bar)[]", Type::Array {
+            element: mock.ptr(Type::Intersection {
+                variants: mock.list([
+                    reference(&mock, "Foo"),
+                    reference(&mock, "Bar"),
+                ])
+            })
+        });
+    }
+
+    #[test]
+    fn generic_references() {
+        let mock = Mock::new();
+
+        assert_type!("Map<string, Array<Foo.Bar>>", Type::Reference {
+            name: mock.ptr(TypeName::Identifier("Map")),
+            arguments: mock.list([
+                Type::Primitive(Primitive::String),
+                Type::Reference {
+                    name: mock.ptr(TypeName::Identifier("Array")),
+                    arguments: mock.list([
+                        Type::Reference {
+                            name: mock.ptr(TypeName::Qualified {
+                                left: mock.ptr(TypeName::Identifier("Foo")),
+                                right: mock.ptr("Bar"),
+                            }),
+                            arguments: NodeList::empty(),
+                        }
+                    ]),
+                }
+            ]),
+        });
+    }
+
+    #[test]
+    fn function_types() {
+        let mock = Mock::new();
+
+        assert_type!("(a: number, ...rest) => void", Type::Function(FunctionType {
+            type_parameters: NodeList::empty(),
+            params: mock.list([
+                Pattern::Annotated {
+                    pattern: mock.ptr(Pattern::Identifier(mock.ptr("a"))),
+                    optional: false,
+                    annotation: Some(mock.ptr(Type::Primitive(Primitive::Number))),
+                },
+                Pattern::RestElement { argument: mock.ptr("rest") },
+            ]),
+            return_type: Some(mock.ptr(Type::Void)),
+        }));
+
+        assert_type!("new <T>() => T", Type::Constructor(FunctionType {
+            type_parameters: mock.list([
+                TypeParameter { name: mock.ptr("T"), constraint: None, default: None }
+            ]),
+            params: NodeList::empty(),
+            return_type: Some(mock.ptr(reference(&mock, "T"))),
+        }));
+    }
+
+    #[test]
+    fn object_and_mapped_types() {
+        let mock = Mock::new();
+
+        assert_type!("{ readonly a?: string; b(): void, [key: string]: any }", Type::ObjectLiteral {
+            members: mock.list([
+                TypeMember::Property {
+                    readonly: true,
+                    key: mock.ptr(PropertyKey::Literal("a")),
+                    optional: true,
+                    annotation: Some(mock.ptr(Type::Primitive(Primitive::String))),
+                },
+                TypeMember::Method {
+                    key: mock.ptr(PropertyKey::Literal("b")),
+                    optional: false,
+                    signature: mock.ptr(FunctionType {
+                        type_parameters: NodeList::empty(),
+                        params: NodeList::empty(),
+                        return_type: Some(mock.ptr(Type::Void)),
+                    }),
+                },
+                TypeMember::Index {
+                    readonly: false,
+                    parameter: mock.ptr("key"),
+                    key: mock.ptr(Type::Primitive(Primitive::String)),
+                    annotation: mock.ptr(Type::Any),
+                },
+            ]),
+            exact: false,
+        });
+
+        assert_type!("{ readonly [K in keyof T]?: T[K] }", Type::Mapped {
+            readonly: true,
+            parameter: mock.ptr("K"),
+            constraint: mock.ptr(Type::Operator {
+                operator: TypeOperator::KeyOf,
+                argument: mock.ptr(reference(&mock, "T")),
+            }),
+            optional: true,
+            annotation: Some(mock.ptr(Type::IndexedAccess {
+                object: mock.ptr(reference(&mock, "T")),
+                index: mock.ptr(reference(&mock, "K")),
+            })),
+        });
+    }
+
+    #[test]
+    fn conditional_and_tuple_types() {
+        let mock = Mock::new();
+
+        assert_type!("T extends [infer U, ...any[]] ? U : never", Type::Conditional {
+            check: mock.ptr(reference(&mock, "T")),
+            extends: mock.ptr(Type::Tuple {
+                elements: mock.list([
+                    Type::Infer { parameter: mock.ptr("U") },
+                    Type::Rest { argument: mock.ptr(Type::Array { element: mock.ptr(Type::Any) }) },
+                ])
+            }),
+            consequent: mock.ptr(reference(&mock, "U")),
+            alternate: mock.ptr(Type::Never),
+        });
+    }
+
+    #[test]
+    fn annotated_declarations() {
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            DeclarationStatement {
+                kind: crate::ast::DeclarationKind::Let,
+                declarators: mock.list([
+                    Declarator {
+                        id: mock.ptr(Pattern::Annotated {
+                            pattern: mock.ptr(Pattern::Identifier(mock.ptr("a"))),
+                            optional: false,
+                            annotation: Some(mock.ptr(Type::Primitive(Primitive::Number))),
+                        }),
+                        init: Some(mock.number("1")),
+                    }
+                ]),
+            }
+        ]);
+
+        assert_eq!(parse_ts("let a: number = 1;").unwrap().body(), expected);
+    }
+
+    #[test]
+    fn typed_functions() {
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            Function {
+                name: mock.name("foo"),
+                generator: false,
+                type_parameters: mock.list([
+                    TypeParameter {
+                        name: mock.ptr("T"),
+                        constraint: Some(mock.ptr(Type::Object)),
+                        default: None,
+                    }
+                ]),
+                params: mock.list([
+                    Pattern::Annotated {
+                        pattern: mock.ptr(Pattern::Identifier(mock.ptr("a"))),
+                        optional: true,
+                        annotation: Some(mock.ptr(reference(&mock, "T"))),
+                    }
+                ]),
+                return_type: Some(mock.ptr(Type::Primitive(Primitive::Boolean))),
+                body: mock.empty_block(),
+            }
+        ]);
+
+        assert_eq!(parse_ts("function foo<T extends object>(a?: T): boolean {}").unwrap().body(), expected);
+    }
+
+    #[test]
+    fn typed_arrow_functions() {
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            mock.ptr(ArrowExpression {
+                type_parameters: mock.list([
+                    TypeParameter { name: mock.ptr("T"), constraint: None, default: None }
+                ]),
+                params: mock.list([
+                    Pattern::Annotated {
+                        pattern: mock.ptr(Pattern::Identifier(mock.ptr("a"))),
+                        optional: false,
+                        annotation: Some(mock.ptr(reference(&mock, "T"))),
+                    }
+                ]),
+                return_type: Some(mock.ptr(reference(&mock, "T"))),
+                body: ArrowBody::Expression(mock.ptr("a")),
+            })
+        ]);
+
+        assert_eq!(parse_ts("<T>(a: T): T => a;").unwrap().body(), expected);
+    }
+
+    #[test]
+    fn tsx_generic_arrow_functions() {
+        let options = ParseOptions { typescript: true, jsx: true, ..ParseOptions::default() };
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            mock.ptr(ArrowExpression {
+                type_parameters: mock.list([
+                    TypeParameter { name: mock.ptr("T"), constraint: None, default: None }
+                ]),
+                params: mock.list([
+                    Pattern::Annotated {
+                        pattern: mock.ptr(Pattern::Identifier(mock.ptr("x"))),
+                        optional: false,
+                        annotation: Some(mock.ptr(reference(&mock, "T"))),
+                    }
+                ]),
+                return_type: None,
+                body: ArrowBody::Expression(mock.ptr("x")),
+            })
+        ]);
+
+        assert_eq!(parse_with_options("<T,>(x: T) => x;", options).unwrap().body(), expected);
+        assert!(parse_with_options("<T extends U>(x: T) => x;", options).is_ok());
+
+        // `<T>` is still an element
+        let module = parse_with_options("<T>(x: T) =&gt; x</T>;", options).unwrap();
+
+        match module.body().only_element().map(|s| s.item) {
+            Some(Statement::Expression(expression)) => assert!(matches!(expression.item, Expression::JSXElement(_))),
+            _ => panic!("Statement isn't an expression!")
+        }
+    }
+
+    #[test]
+    fn type_expressions() {
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            mock.ptr(Expression::As(AsExpression {
+                expression: mock.ptr(Expression::NonNull(NonNullExpression {
+                    expression: mock.ptr("foo"),
+                })),
+                annotation: mock.ptr(Type::Any),
+            })),
+            mock.ptr(Expression::Satisfies(SatisfiesExpression {
+                expression: mock.ptr(Expression::TypeAssertion(TypeAssertion {
+                    annotation: mock.ptr(reference(&mock, "Bar")),
+                    expression: mock.ptr("foo"),
+                })),
+                annotation: mock.ptr(reference(&mock, "Baz")),
+            })),
+            mock.ptr(CallExpression {
+                callee: mock.ptr(InstantiationExpression {
+                    expression: mock.ptr("foo"),
+                    arguments: mock.list([Type::Primitive(Primitive::Number)]),
+                }),
+                arguments: mock.list([Literal::Number("1")]),
+            }),
+        ]);
+
+        assert_eq!(parse_ts("foo! as any; <Bar>foo satisfies Baz; foo<number>(1);").unwrap().body(), expected);
+    }
+
+    #[test]
+    fn comparisons_are_not_type_arguments() {
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            mock.ptr(BinaryExpression {
+                operator: crate::ast::OperatorKind::Greater,
+                left: mock.ptr(BinaryExpression {
+                    operator: crate::ast::OperatorKind::Lesser,
+                    left: mock.ptr("a"),
+                    right: mock.ptr("b"),
+                }),
+                right: mock.ptr("c"),
+            })
+        ]);
+
+        assert_eq!(parse_ts("a < b > c;").unwrap().body(), expected);
+    }
+
+    #[test]
+    fn type_declarations() {
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            Statement::from(InterfaceDeclaration {
+                name: mock.ptr("Foo"),
+                type_parameters: NodeList::empty(),
+                extends: mock.list([reference(&mock, "Bar")]),
+                body: mock.block([
+                    TypeMember::Property {
+                        readonly: false,
+                        key: mock.ptr(PropertyKey::Literal("a")),
+                        optional: false,
+                        annotation: Some(mock.ptr(Type::Primitive(Primitive::Number))),
+                    }
+                ]),
+            }),
+            Statement::from(TypeAliasDeclaration {
+                name: mock.ptr("Baz"),
+                type_parameters: NodeList::empty(),
+                value: mock.ptr(reference(&mock, "Foo")),
+            }),
+            Statement::from(DeclareStatement {
+                body: mock.ptr(DeclarationStatement {
+                    kind: crate::ast::DeclarationKind::Const,
+                    declarators: mock.list([
+                        Declarator {
+                            id: mock.ptr(Pattern::Annotated {
+                                pattern: mock.ptr(Pattern::Identifier(mock.ptr("qux"))),
+                                optional: false,
+                                annotation: Some(mock.ptr(Type::Primitive(Primitive::String))),
+                            }),
+                            init: None,
+                        }
+                    ]),
+                }),
+            }),
+            Statement::from(FunctionSignature {
+                name: mock.ptr("quux"),
+                signature: FunctionType {
+                    type_parameters: NodeList::empty(),
+                    params: NodeList::empty(),
+                    return_type: Some(mock.ptr(Type::Void)),
+                },
+            }),
+        ]);
+
+        let src = "interface Foo extends Bar { a: number } type Baz = Foo; declare const qux: string; function quux(): void;";
+
+        assert_eq!(parse_ts(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn enums() {
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            Statement::from(EnumDeclaration {
+                is_const: false,
+                name: mock.ptr("Foo"),
+                members: mock.list([
+                    EnumMember {
+                        name: mock.ptr("A"),
+                        value: None,
+                    },
+                    EnumMember {
+                        name: mock.ptr("'b-c'"),
+                        value: Some(mock.number("2")),
+                    },
+                ]),
+            }),
+            Statement::from(EnumDeclaration {
+                is_const: true,
+                name: mock.ptr("Bar"),
+                members: NodeList::empty(),
+            }),
+            Statement::from(DeclareStatement {
+                body: mock.ptr(EnumDeclaration {
+                    is_const: false,
+                    name: mock.ptr("Baz"),
+                    members: mock.list([
+                        EnumMember {
+                            name: mock.ptr("default"),
+                            value: None,
+                        },
+                    ]),
+                }),
+            }),
+        ]);
+
+        let src = "enum Foo { A, 'b-c' = 2, } const enum Bar {} declare enum Baz { default }";
+
+        assert_eq!(parse_ts(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn namespaces() {
+        let mock = Mock::new();
+
+        let expected = mock.list([
+            Statement::from(NamespaceDeclaration {
+                name: mock.ptr("Foo"),
+                body: mock.ptr(Block {
+                    body: mock.list([
+                        Statement::from(ExportDeclaration {
+                            is_default: false,
+                            declaration: mock.ptr(NamespaceDeclaration {
+                                name: mock.ptr("Bar"),
+                                body: mock.ptr(Block {
+                                    body: mock.list([
+                                        Statement::from(ExportDeclaration {
+                                            is_default: false,
+                                            declaration: mock.ptr(Statement::from(DeclarationStatement {
+                                                kind: DeclarationKind::Const,
+                                                declarators: mock.list([
+                                                    Declarator {
+                                                        id: mock.ptr(Pattern::Identifier(mock.ptr("a"))),
+                                                        init: Some(mock.number("1")),
+                                                    }
+                                                ]),
+                                            })),
+                                        }),
+                                        Statement::from(mock.ptr(Expression::Identifier("a"))),
+                                    ]),
+                                }),
+                            }),
+                        }),
+                    ]),
+                }),
+            }),
+            Statement::from(NamespaceDeclaration {
+                name: mock.ptr("Baz"),
+                body: mock.ptr(Block {
+                    body: NodeList::empty(),
+                }),
+            }),
+            Statement::from(DeclareStatement {
+                body: mock.ptr(NamespaceDeclaration {
+                    name: mock.ptr("'qux'"),
+                    body: mock.ptr(Block {
+                        body: NodeList::empty(),
+                    }),
+                }),
+            }),
+        ]);
+
+        let src = "namespace Foo.Bar { export const a = 1; a; } module Baz {} declare module 'qux' {}";
+
+        assert_eq!(parse_ts(src).unwrap().body(), expected);
+    }
+
+    #[test]
+    fn types_are_not_parsed_by_default() {
+        assert!(parse_with_options("let a: number;", ParseOptions::default()).is_err());
+        assert!(parse_with_options("interface Foo {}", ParseOptions::default()).is_err());
+        assert!(parse_with_options("foo!;", ParseOptions::default()).is_err());
+        assert!(parse_with_options("enum Foo {}", ParseOptions::default()).is_err());
+        assert!(parse_with_options("namespace Foo {}", ParseOptions::default()).is_err());
+    }
+}
@@ -0,0 +1,722 @@
+//! Parser and validator for regular expression literals.
+//!
+//! The main parser keeps regular expressions as a raw `Literal::RegEx`,
+//! validating each of them with this module, so that invalid patterns
+//! and flags are reported as errors. Linters and transformers can use
+//! `regex::parse` on the raw literal to inspect its pattern.
+//!
+//! All positions, of both nodes and errors, are byte offsets into the
+//! literal, with the leading `/` at offset 0.
+
+use toolshed::Arena;
+use crate::ast::{Node, NodeList};
+
+mod parser;
+
+use self::parser::Parser;
+
+pub type DisjunctionNode<'ast> = Node<'ast, Disjunction<'ast>>;
+pub type AlternativeNode<'ast> = Node<'ast, Alternative<'ast>>;
+pub type TermNode<'ast> = Node<'ast, Term<'ast>>;
+pub type ClassItemNode<'ast> = Node<'ast, ClassItem<'ast>>;
+
+/// A parsed regular expression literal, such as `/a|b/g`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct RegExp<'ast> {
+    pub pattern: DisjunctionNode<'ast>,
+    pub flags: Flags,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct Flags {
+    /// `d`
+    pub has_indices: bool,
+    /// `g`
+    pub global: bool,
+    /// `i`
+    pub ignore_case: bool,
+    /// `m`
+    pub multiline: bool,
+    /// `s`
+    pub dot_all: bool,
+    /// `u`
+    pub unicode: bool,
+    /// `y`
+    pub sticky: bool,
+    /// `v`
+    pub unicode_sets: bool,
+}
+
+impl Flags {
+    /// Whether the pattern is parsed with the strict Unicode grammar,
+    /// instead of the lenient one from Annex B.
+    #[inline]
+    pub fn unicode_mode(&self) -> bool {
+        self.unicode || self.unicode_sets
+    }
+}
+
+/// `a|b|c`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Disjunction<'ast> {
+    pub alternatives: NodeList<'ast, Alternative<'ast>>,
+}
+
+/// Sequence of terms, `abc`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Alternative<'ast> {
+    pub terms: NodeList<'ast, Term<'ast>>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Term<'ast> {
+    /// Code point matched literally, either as is or escaped. Escapes may
+    /// produce lone surrogates, hence this is not a `char`.
+    Character(u32),
+
+    /// `.`
+    Dot,
+
+    Assertion(Assertion),
+
+    /// `\d`, `\p{Letter}`
+    Escape(ClassEscape<'ast>),
+
+    /// `[a-z]`
+    Class(CharacterClass<'ast>),
+
+    /// `(a)`, `(?<name>a)`, `(?:a)`
+    Group(Group<'ast>),
+
+    /// `(?=a)`, `(?<!a)`
+    Lookaround(Lookaround<'ast>),
+
+    /// `\1`, `\k<name>`
+    Backreference(Backreference<'ast>),
+
+    /// `a*`, `a{1,2}?`
+    Quantified(Quantified<'ast>),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Assertion {
+    /// `^`
+    Start,
+    /// `$`
+    End,
+    /// `\b`
+    WordBoundary,
+    /// `\B`
+    NotWordBoundary,
+}
+
+/// Escapes matching a whole class of characters.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ClassEscape<'ast> {
+    /// `\d`
+    Digit,
+    /// `\D`
+    NotDigit,
+    /// `\s`
+    Space,
+    /// `\S`
+    NotSpace,
+    /// `\w`
+    Word,
+    /// `\W`
+    NotWord,
+    /// `\p{Name}`, `\P{Name=Value}`
+    Property {
+        negated: bool,
+        name: &'ast str,
+        value: Option<&'ast str>,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct CharacterClass<'ast> {
+    pub negated: bool,
+    pub kind: ClassKind,
+    pub items: NodeList<'ast, ClassItem<'ast>>,
+}
+
+/// How the items of a class are combined. Only classes in `v` mode can
+/// be intersections or subtractions.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ClassKind {
+    /// `[ab]`
+    Union,
+    /// `[a&&b]`
+    Intersection,
+    /// `[a--b]`
+    Subtraction,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ClassItem<'ast> {
+    Character(u32),
+
+    /// `a-z`
+    Range(u32, u32),
+
+    Escape(ClassEscape<'ast>),
+
+    /// Nested class, `[[a-z]--[aeiou]]`, only in `v` mode.
+    Class(CharacterClass<'ast>),
+
+    /// `\q{abc|d}`, only in `v` mode.
+    Strings(NodeList<'ast, ClassString<'ast>>),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ClassString<'ast> {
+    pub characters: NodeList<'ast, u32>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Group<'ast> {
+    pub kind: GroupKind<'ast>,
+    pub body: DisjunctionNode<'ast>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum GroupKind<'ast> {
+    /// Capturing group, with its 1-based index as used by backreferences.
+    Capturing {
+        index: u32,
+        name: Option<&'ast str>,
+    },
+    NonCapturing,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Lookaround<'ast> {
+    pub kind: LookaroundKind,
+    pub body: DisjunctionNode<'ast>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum LookaroundKind {
+    /// `(?=a)`
+    Lookahead,
+    /// `(?!a)`
+    NegativeLookahead,
+    /// `(?<=a)`
+    Lookbehind,
+    /// `(?<!a)`
+    NegativeLookbehind,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Backreference<'ast> {
+    Index(u32),
+    Named(&'ast str),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Quantified<'ast> {
+    pub term: TermNode<'ast>,
+    pub quantifier: Quantifier,
+}
+
+/// `*` is `{ min: 0, max: None }`, `a{2}?` is `{ min: 2, max: Some(2) }`
+/// without being greedy.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Quantifier {
+    pub min: u32,
+    pub max: Option<u32>,
+    pub greedy: bool,
+}
+
+/// The reason an `Error` was produced.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ErrorKind {
+    /// The literal is not delimited by slashes.
+    UnterminatedLiteral,
+
+    /// Flag other than one of `dgimsuyv`.
+    InvalidFlag,
+
+    DuplicateFlag,
+
+    /// Both the `u` and the `v` flag are set.
+    ConflictingFlags,
+
+    /// `(` without a matching `)`.
+    UnterminatedGroup,
+
+    /// `)` without a matching `(`.
+    UnmatchedParenthesis,
+
+    /// `(?` not followed by `:`, `=`, `!`, `<=`, `<!` or a group name.
+    InvalidGroup,
+
+    InvalidGroupName,
+
+    /// Group name used twice within the same alternative.
+    DuplicateGroupName,
+
+    /// `\k<name>` without a group of that name.
+    UnknownGroupName,
+
+    /// `\3` with fewer than 3 groups, in Unicode mode.
+    InvalidBackreference,
+
+    /// `[` without a matching `]`.
+    UnterminatedClass,
+
+    /// Quantifier with nothing, or an assertion, to repeat.
+    NothingToRepeat,
+
+    /// `{` or `}` that is not part of a quantifier, or `]` outside of a
+    /// class, in Unicode mode.
+    LoneBracket,
+
+    /// Quantifier with bounds out of order, `a{2,1}`.
+    InvalidQuantifier,
+
+    InvalidEscape,
+
+    /// Unknown Unicode property, or a property of strings where those
+    /// are not allowed.
+    InvalidProperty,
+
+    /// Class range with bounds out of order, `[z-a]`.
+    RangeOutOfOrder,
+
+    /// Class range with a class escape as a bound, `[\d-z]`, in Unicode mode.
+    InvalidClassRange,
+
+    /// Misplaced or mixed set operators, `[a&&b--c]`, in `v` mode.
+    InvalidClassOperation,
+
+    /// Unescaped syntax character or double punctuator in a class, in `v` mode.
+    InvalidClassCharacter,
+
+    /// Negated class that may match strings, in `v` mode.
+    NegatedClassWithStrings,
+}
+
+/// Error in a regular expression literal, with positions relative to
+/// the literal.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Error {
+    pub kind: ErrorKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Parse a regular expression literal, including the slashes and flags,
+/// such as `/[a-z]+/gi`, allocating it in the provided `Arena`.
+pub fn parse<'ast>(literal: &'ast str, arena: &'ast Arena) -> Result<RegExp<'ast>, Vec<Error>> {
+    let end = match literal.rfind('/') {
+        Some(end) if end > 0 && literal.starts_with('/') => end,
+        _ => return Err(vec![Error {
+            kind: ErrorKind::UnterminatedLiteral,
+            start: 0,
+            end: literal.len(),
+        }]),
+    };
+
+    let mut errors = Vec::new();
+    let flags = parse_flags(literal, end + 1, &mut errors);
+    let pattern = Parser::new(literal, end, flags, arena, &mut errors).parse();
+
+    match errors.len() {
+        0 => Ok(RegExp { pattern, flags }),
+        _ => {
+            errors.sort_by_key(|err| err.start);
+
+            Err(errors)
+        },
+    }
+}
+
+fn parse_flags(literal: &str, start: usize, errors: &mut Vec<Error>) -> Flags {
+    let mut flags = Flags::default();
+
+    for (offset, character) in literal[start..].char_indices() {
+        let flag = match character {
+            'd' => &mut flags.has_indices,
+            'g' => &mut flags.global,
+            'i' => &mut flags.ignore_case,
+            'm' => &mut flags.multiline,
+            's' => &mut flags.dot_all,
+            'u' => &mut flags.unicode,
+            'y' => &mut flags.sticky,
+            'v' => &mut flags.unicode_sets,
+            _   => {
+                errors.push(Error {
+                    kind: ErrorKind::InvalidFlag,
+                    start: start + offset,
+                    end: start + offset + character.len_utf8(),
+                });
+                continue;
+            },
+        };
+
+        if *flag {
+            errors.push(Error {
+                kind: ErrorKind::DuplicateFlag,
+                start: start + offset,
+                end: start + offset + 1,
+            });
+        }
+
+        *flag = true;
+    }
+
+    if flags.unicode && flags.unicode_sets {
+        errors.push(Error {
+            kind: ErrorKind::ConflictingFlags,
+            start,
+            end: literal.len(),
+        });
+    }
+
+    flags
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::mock::Mock;
+
+    fn errors(literal: &str) -> Vec<(ErrorKind, usize, usize)> {
+        let arena = Arena::new();
+        let literal = arena.alloc_str(literal);
+
+        match parse(literal, &arena) {
+            Ok(_)       => Vec::new(),
+            Err(errors) => errors.into_iter().map(|err| (err.kind, err.start, err.end)).collect(),
+        }
+    }
+
+    fn assert_valid(literal: &str) {
+        assert_eq!(errors(literal), [], "{}", literal);
+    }
+
+    fn alternatives<'a>(mock: &'a Mock, alternatives: &[&[Term<'a>]]) -> DisjunctionNode<'a> {
+        let alternatives = alternatives.iter().map(|terms| Alternative {
+            terms: mock.list(terms),
+        }).collect::<Vec<_>>();
+
+        mock.ptr(Disjunction {
+            alternatives: mock.list(alternatives),
+        })
+    }
+
+    fn character<'a>(c: char) -> Term<'a> {
+        Term::Character(c as u32)
+    }
+
+    fn quantified<'a>(mock: &'a Mock, term: Term<'a>, min: u32, max: Option<u32>, greedy: bool) -> Term<'a> {
+        Term::Quantified(Quantified {
+            term: mock.ptr(term),
+            quantifier: Quantifier { min, max, greedy },
+        })
+    }
+
+    fn group<'a>(mock: &'a Mock, kind: GroupKind<'a>, terms: &[Term<'a>]) -> Term<'a> {
+        Term::Group(Group {
+            kind,
+            body: alternatives(mock, &[terms]),
+        })
+    }
+
+    fn lookaround<'a>(mock: &'a Mock, kind: LookaroundKind, terms: &[Term<'a>]) -> Term<'a> {
+        Term::Lookaround(Lookaround {
+            kind,
+            body: alternatives(mock, &[terms]),
+        })
+    }
+
+    fn class<'a>(mock: &'a Mock, kind: ClassKind, items: &[ClassItem<'a>]) -> CharacterClass<'a> {
+        CharacterClass {
+            negated: false,
+            kind,
+            items: mock.list(items),
+        }
+    }
+
+    fn string<'a>(mock: &'a Mock, characters: &str) -> ClassString<'a> {
+        ClassString {
+            characters: mock.list(characters.chars().map(|c| c as u32).collect::<Vec<_>>()),
+        }
+    }
+
+    #[test]
+    fn flags() {
+        let arena = Arena::new();
+        let regexp = parse("/a/dgimsy", &arena).unwrap();
+
+        assert_eq!(regexp.flags, Flags {
+            has_indices: true,
+            global: true,
+            ignore_case: true,
+            multiline: true,
+            dot_all: true,
+            unicode: false,
+            sticky: true,
+            unicode_sets: false,
+        });
+        assert!(parse("/a/v", &arena).unwrap().flags.unicode_mode());
+
+        assert_eq!(errors("/a/gx"), [(ErrorKind::InvalidFlag, 4, 5)]);
+        assert_eq!(errors("/a/gig"), [(ErrorKind::DuplicateFlag, 5, 6)]);
+        assert_eq!(errors("/a/uv"), [(ErrorKind::ConflictingFlags, 3, 5)]);
+        assert_eq!(errors("a/g"), [(ErrorKind::UnterminatedLiteral, 0, 3)]);
+    }
+
+    #[test]
+    fn alternatives_and_quantifiers() {
+        let arena = Arena::new();
+        let mock = Mock::new();
+        let regexp = parse("/^ab*|c{2,}?|.{3}$/", &arena).unwrap();
+
+
+        let expected = alternatives(&mock, &[
+            &[
+                Term::Assertion(Assertion::Start),
+                character('a'),
+                quantified(&mock, character('b'), 0, None, true),
+            ],
+            &[
+                quantified(&mock, character('c'), 2, None, false),
+            ],
+            &[
+                quantified(&mock, Term::Dot, 3, Some(3), true),
+                Term::Assertion(Assertion::End),
+            ],
+        ]);
+
+        assert_eq!(regexp.pattern, expected);
+    }
+
+    #[test]
+    fn groups_and_backreferences() {
+        let arena = Arena::new();
+        let mock = Mock::new();
+        let regexp = parse(r"/(?<year>\d)(?:-)(a)(?<=x)(?!y)\k<year>\2/", &arena).unwrap();
+
+
+        let expected = alternatives(&mock, &[&[
+            group(&mock, GroupKind::Capturing { index: 1, name: Some("year") }, &[Term::Escape(ClassEscape::Digit)]),
+            group(&mock, GroupKind::NonCapturing, &[character('-')]),
+            group(&mock, GroupKind::Capturing { index: 2, name: None }, &[character('a')]),
+            lookaround(&mock, LookaroundKind::Lookbehind, &[character('x')]),
+            lookaround(&mock, LookaroundKind::NegativeLookahead, &[character('y')]),
+            Term::Backreference(Backreference::Named("year")),
+            Term::Backreference(Backreference::Index(2)),
+        ]]);
+
+        assert_eq!(regexp.pattern, expected);
+    }
+
+    #[test]
+    fn character_classes() {
+        let arena = Arena::new();
+        let mock = Mock::new();
+        let regexp = parse(r"/[^a-z\d\-_][\p{Script=Greek}]/u", &arena).unwrap();
+
+        let expected = alternatives(&mock, &[&[
+            Term::Class(CharacterClass {
+                negated: true,
+                kind: ClassKind::Union,
+                items: mock.list([
+                    ClassItem::Range('a' as u32, 'z' as u32),
+                    ClassItem::Escape(ClassEscape::Digit),
+                    ClassItem::Character('-' as u32),
+                    ClassItem::Character('_' as u32),
+                ]),
+            }),
+            Term::Class(CharacterClass {
+                negated: false,
+                kind: ClassKind::Union,
+                items: mock.list([
+                    ClassItem::Escape(ClassEscape::Property {
+                        negated: false,
+                        name: "Script",
+                        value: Some("Greek"),
+                    }),
+                ]),
+            }),
+        ]]);
+
+        assert_eq!(regexp.pattern, expected);
+    }
+
+    #[test]
+    fn astral_class_characters() {
+        let arena = Arena::new();
+        let mock = Mock::new();
+
+        // Surrogates are separate characters outside of Unicode mode
+        let regexp = parse("/[a-💩]/", &arena).unwrap();
+        let expected = alternatives(&mock, &[&[
+            Term::Class(class(&mock, ClassKind::Union, &[
+                ClassItem::Range('a' as u32, 0xD83D),
+                ClassItem::Character(0xDCA9),
+            ])),
+        ]]);
+
+        assert_eq!(regexp.pattern, expected);
+
+        let regexp = parse("/[💩-💫]/u", &arena).unwrap();
+        let expected = alternatives(&mock, &[&[
+            Term::Class(class(&mock, ClassKind::Union, &[ClassItem::Range(0x1F4A9, 0x1F4AB)])),
+        ]]);
+
+        assert_eq!(regexp.pattern, expected);
+    }
+
+    #[test]
+    fn class_set_operations() {
+        let arena = Arena::new();
+        let mock = Mock::new();
+        let regexp = parse(r"/[[a-z]--[aeiou]][\p{L}&&\q{a|bc}]/v", &arena).unwrap();
+
+
+        let expected = alternatives(&mock, &[&[
+            Term::Class(CharacterClass {
+                negated: false,
+                kind: ClassKind::Subtraction,
+                items: mock.list([
+                    ClassItem::Class(class(&mock, ClassKind::Union, &[ClassItem::Range('a' as u32, 'z' as u32)])),
+                    ClassItem::Class(class(&mock, ClassKind::Union, &"aeiou".chars().map(|c| ClassItem::Character(c as u32)).collect::<Vec<_>>())),
+                ]),
+            }),
+            Term::Class(CharacterClass {
+                negated: false,
+                kind: ClassKind::Intersection,
+                items: mock.list([
+                    ClassItem::Escape(ClassEscape::Property {
+                        negated: false,
+                        name: "L",
+                        value: None,
+                    }),
+                    ClassItem::Strings(mock.list([string(&mock, "a"), string(&mock, "bc")])),
+                ]),
+            }),
+        ]]);
+
+        assert_eq!(regexp.pattern, expected);
+    }
+
+    #[test]
+    fn escapes() {
+        let arena = Arena::new();
+        let mock = Mock::new();
+        let regexp = parse(r"/\n\x41\u{1F600}😀\cJ\0\//u", &arena).unwrap();
+
+        let expected = alternatives(&mock, &[&[
+            character('\n'),
+            character('A'),
+            character('😀'),
+            character('😀'),
+            character('\n'),
+            character('\0'),
+            character('/'),
+        ]]);
+
+        assert_eq!(regexp.pattern, expected);
+    }
+
+    #[test]
+    fn annex_b() {
+        let arena = Arena::new();
+        let mock = Mock::new();
+        let regexp = parse(r"/a{]\c\8\12\k/", &arena).unwrap();
+
+        let expected = alternatives(&mock, &[&[
+            character('a'),
+            character('{'),
+            character(']'),
+            character('\\'),
+            character('c'),
+            character('8'),
+            character('\n'),
+            character('k'),
+        ]]);
+
+        assert_eq!(regexp.pattern, expected);
+
+        assert_valid(r"/(?=a)*[\d-z]\p{Foo}\u{12}/");
+        assert_valid(r"/[\c_]\q/");
+    }
+
+    #[test]
+    fn valid_patterns() {
+        assert_valid(r"/^(?:[a-z0-9!#$%&'*+\/=?^_`{|}~-]+)@[a-z]+\.[a-z]{2,}$/i");
+        assert_valid(r"/(?<a>x)|(?<a>y)/");
+        assert_valid(r"/(?<$A>.)\k<$A>/");
+        assert_valid(r"/\1(a)/u");
+        assert_valid(r"/\p{Lu}\P{gc=Nd}\p{Emoji_Presentation}/u");
+        assert_valid(r"/[\p{RGI_Emoji}--\q{x}][^\p{L}&&[a-z]]/v");
+        assert_valid(r"/[\w\-\]]/u");
+        assert_valid(r"/[&\-!]/v");
+        assert_valid(r"/\//");
+        assert_valid(r"/[/]/");
+        assert_valid("/é+/");
+    }
+
+    #[test]
+    fn invalid_groups() {
+        assert_eq!(errors("/(a/"), [(ErrorKind::UnterminatedGroup, 1, 3)]);
+        assert_eq!(errors("/a)/"), [(ErrorKind::UnmatchedParenthesis, 2, 3)]);
+        assert_eq!(errors("/(?a)/"), [(ErrorKind::InvalidGroup, 1, 3)]);
+        assert_eq!(errors("/(?<1>a)/"), [(ErrorKind::InvalidGroupName, 4, 5)]);
+        assert_eq!(errors("/(?<a>x)(?<a>y)/"), [(ErrorKind::DuplicateGroupName, 11, 12)]);
+        assert_eq!(errors("/(?<a>x)|((?<a>y)(?<a>z))/"), [(ErrorKind::DuplicateGroupName, 20, 21)]);
+        assert_eq!(errors(r"/(?<a>x)\k<b>/"), [(ErrorKind::UnknownGroupName, 8, 13)]);
+        assert_eq!(errors(r"/(?<a>x)\k/"), [(ErrorKind::InvalidEscape, 8, 10)]);
+        assert_eq!(errors(r"/(a)\2/u"), [(ErrorKind::InvalidBackreference, 4, 6)]);
+    }
+
+    #[test]
+    fn invalid_quantifiers() {
+        assert_eq!(errors("/*a/"), [(ErrorKind::NothingToRepeat, 1, 2)]);
+        assert_eq!(errors("/a**/"), [(ErrorKind::NothingToRepeat, 3, 4)]);
+        assert_eq!(errors("/a|{1}/"), [(ErrorKind::NothingToRepeat, 3, 6)]);
+        assert_eq!(errors("/^*/"), [(ErrorKind::NothingToRepeat, 2, 3)]);
+        assert_eq!(errors(r"/\b+/"), [(ErrorKind::NothingToRepeat, 3, 4)]);
+        assert_eq!(errors("/(?<=a)?/"), [(ErrorKind::NothingToRepeat, 7, 8)]);
+        assert_eq!(errors("/(?=a)?/u"), [(ErrorKind::NothingToRepeat, 6, 7)]);
+        assert_eq!(errors("/a{2,1}/"), [(ErrorKind::InvalidQuantifier, 2, 7)]);
+        assert_eq!(errors("/a{/u"), [(ErrorKind::LoneBracket, 2, 3)]);
+        assert_eq!(errors("/]/u"), [(ErrorKind::LoneBracket, 1, 2)]);
+    }
+
+    #[test]
+    fn invalid_classes() {
+        assert_eq!(errors("/[a/"), [(ErrorKind::UnterminatedClass, 1, 3)]);
+        assert_eq!(errors("/[z-a]/"), [(ErrorKind::RangeOutOfOrder, 2, 5)]);
+        assert_eq!(errors(r"/[\d-z]/u"), [(ErrorKind::InvalidClassRange, 2, 6)]);
+        assert_eq!(errors("/[💩-💫]/"), [(ErrorKind::RangeOutOfOrder, 2, 11)]);
+        assert_eq!(errors("/[💩-💫]/u"), []);
+        assert_eq!(errors("/[a-💩]/"), []);
+        assert_eq!(errors("/[a&&b--c]/v"), [(ErrorKind::InvalidClassOperation, 6, 8)]);
+        assert_eq!(errors("/[a-z&&b]/v"), [(ErrorKind::InvalidClassOperation, 5, 7)]);
+        assert_eq!(errors("/[ab&&c]/v"), [(ErrorKind::InvalidClassOperation, 4, 6)]);
+        assert_eq!(errors("/[a&&]/v"), [(ErrorKind::InvalidClassOperation, 3, 5)]);
+        assert_eq!(errors("/[(]/v"), [(ErrorKind::InvalidClassCharacter, 2, 3)]);
+        assert_eq!(errors("/[a!!]/v"), [(ErrorKind::InvalidClassCharacter, 3, 5)]);
+        assert_eq!(errors(r"/[^\q{ab}]/v"), [(ErrorKind::NegatedClassWithStrings, 1, 10)]);
+    }
+
+    #[test]
+    fn invalid_escapes() {
+        assert_eq!(errors(r"/\a/u"), [(ErrorKind::InvalidEscape, 1, 3)]);
+        assert_eq!(errors(r"/\c1/u"), [(ErrorKind::InvalidEscape, 1, 3)]);
+        assert_eq!(errors(r"/\x4/u"), [(ErrorKind::InvalidEscape, 1, 3)]);
+        assert_eq!(errors(r"/\u{110000}/u"), [(ErrorKind::InvalidEscape, 1, 11)]);
+        assert_eq!(errors(r"/\00/u"), [(ErrorKind::InvalidEscape, 1, 3)]);
+        assert_eq!(errors(r"/[\-]/"), []);
+        assert_eq!(errors(r"/\-/u"), [(ErrorKind::InvalidEscape, 1, 3)]);
+        assert_eq!(errors(r"/\p{Foo}/u"), [(ErrorKind::InvalidProperty, 1, 8)]);
+        assert_eq!(errors(r"/\p{gc=Greek}/u"), [(ErrorKind::InvalidProperty, 1, 13)]);
+        assert_eq!(errors(r"/\p{RGI_Emoji}/u"), [(ErrorKind::InvalidProperty, 1, 14)]);
+        assert_eq!(errors(r"/\P{RGI_Emoji}/v"), [(ErrorKind::InvalidProperty, 1, 14)]);
+        assert_eq!(errors(r"/\p/u"), [(ErrorKind::InvalidProperty, 1, 3)]);
+    }
+}
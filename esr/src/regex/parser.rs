@@ -0,0 +1,1122 @@
+use toolshed::Arena;
+use crate::ast::{Loc, Node, NodeList};
+use crate::regex::*;
+
+/// Values of the `General_Category` property, which can also be used
+/// without the property name, `\p{Lu}`.
+static GENERAL_CATEGORIES: &[&str] = &[
+    "C", "Cased_Letter", "Cc", "Cf", "Close_Punctuation", "Cn", "Co", "Combining_Mark",
+    "Connector_Punctuation", "Control", "Cs", "Currency_Symbol", "Dash_Punctuation",
+    "Decimal_Number", "Enclosing_Mark", "Final_Punctuation", "Format", "Initial_Punctuation",
+    "L", "LC", "Letter", "Letter_Number", "Line_Separator", "Ll", "Lm", "Lo", "Lowercase_Letter",
+    "Lt", "Lu", "M", "Mark", "Math_Symbol", "Mc", "Me", "Mn", "Modifier_Letter", "Modifier_Symbol",
+    "N", "Nd", "Nl", "No", "Nonspacing_Mark", "Number", "Open_Punctuation", "Other",
+    "Other_Letter", "Other_Number", "Other_Punctuation", "Other_Symbol", "P",
+    "Paragraph_Separator", "Pc", "Pd", "Pe", "Pf", "Pi", "Po", "Private_Use", "Ps", "Punctuation",
+    "S", "Sc", "Separator", "Sk", "Sm", "So", "Space_Separator", "Spacing_Mark", "Surrogate",
+    "Symbol", "Titlecase_Letter", "Unassigned", "Uppercase_Letter", "Z", "Zl", "Zp", "Zs",
+    "cntrl", "digit", "punct",
+];
+
+/// Binary properties, `\p{Alphabetic}`.
+static BINARY_PROPERTIES: &[&str] = &[
+    "ASCII", "ASCII_Hex_Digit", "AHex", "Alphabetic", "Alpha", "Any", "Assigned", "Bidi_Control",
+    "Bidi_C", "Bidi_Mirrored", "Bidi_M", "Case_Ignorable", "CI", "Cased", "Changes_When_Casefolded",
+    "CWCF", "Changes_When_Casemapped", "CWCM", "Changes_When_Lowercased", "CWL",
+    "Changes_When_NFKC_Casefolded", "CWKCF", "Changes_When_Titlecased", "CWT",
+    "Changes_When_Uppercased", "CWU", "Dash", "Default_Ignorable_Code_Point", "DI", "Deprecated",
+    "Dep", "Diacritic", "Dia", "Emoji", "Emoji_Component", "EComp", "Emoji_Modifier", "EMod",
+    "Emoji_Modifier_Base", "EBase", "Emoji_Presentation", "EPres", "Extended_Pictographic",
+    "ExtPict", "Extender", "Ext", "Grapheme_Base", "Gr_Base", "Grapheme_Extend", "Gr_Ext",
+    "Hex_Digit", "Hex", "IDS_Binary_Operator", "IDSB", "IDS_Trinary_Operator", "IDST",
+    "ID_Continue", "IDC", "ID_Start", "IDS", "Ideographic", "Ideo", "Join_Control", "Join_C",
+    "Logical_Order_Exception", "LOE", "Lowercase", "Lower", "Math", "Noncharacter_Code_Point",
+    "NChar", "Pattern_Syntax", "Pat_Syn", "Pattern_White_Space", "Pat_WS", "Quotation_Mark",
+    "QMark", "Radical", "Regional_Indicator", "RI", "Sentence_Terminal", "STerm", "Soft_Dotted",
+    "SD", "Terminal_Punctuation", "Term", "Unified_Ideograph", "UIdeo", "Uppercase", "Upper",
+    "Variation_Selector", "VS", "White_Space", "space", "XID_Continue", "XIDC", "XID_Start", "XIDS",
+];
+
+/// Properties of strings, only available in `v` mode.
+static STRING_PROPERTIES: &[&str] = &[
+    "Basic_Emoji", "Emoji_Keycap_Sequence", "RGI_Emoji", "RGI_Emoji_Flag_Sequence",
+    "RGI_Emoji_Modifier_Sequence", "RGI_Emoji_Tag_Sequence", "RGI_Emoji_ZWJ_Sequence",
+];
+
+/// Result of parsing an escape, which is either a single character or
+/// a class of them.
+enum Escape<'ast> {
+    Character(u32),
+    Class(ClassEscape<'ast>),
+}
+
+struct GroupName<'ast> {
+    name: &'ast str,
+    /// Disjunctions, and the index of the alternative in each of them,
+    /// enclosing the group.
+    path: Vec<(u32, u32)>,
+}
+
+pub struct Parser<'ast, 'err> {
+    arena: &'ast Arena,
+    source: &'ast str,
+    index: usize,
+    /// Position of the slash closing the pattern.
+    end: usize,
+    /// Parsing in either `u` or `v` mode.
+    unicode: bool,
+    /// Parsing in `v` mode.
+    sets: bool,
+    /// Whether the pattern has any named groups, which turns `\k` into
+    /// a backreference even outside of Unicode mode.
+    named_groups: bool,
+    /// Number of capturing groups in the whole pattern.
+    group_count: u32,
+    /// Number of capturing groups parsed so far.
+    captures: u32,
+    names: Vec<GroupName<'ast>>,
+    references: Vec<(&'ast str, usize, usize)>,
+    path: Vec<(u32, u32)>,
+    disjunctions: u32,
+    errors: &'err mut Vec<Error>,
+}
+
+impl<'ast, 'err> Parser<'ast, 'err> {
+    pub fn new(source: &'ast str, end: usize, flags: Flags, arena: &'ast Arena, errors: &'err mut Vec<Error>) -> Self {
+        Parser {
+            arena,
+            source,
+            index: 1,
+            end,
+            unicode: flags.unicode_mode(),
+            sets: flags.unicode_sets,
+            named_groups: false,
+            group_count: 0,
+            captures: 0,
+            names: Vec::new(),
+            references: Vec::new(),
+            path: Vec::new(),
+            disjunctions: 0,
+            errors,
+        }
+    }
+
+    pub fn parse(mut self) -> DisjunctionNode<'ast> {
+        self.scan_groups();
+
+        let pattern = self.disjunction();
+
+        // Only a `)` can stop the top level disjunction early
+        while self.index < self.end {
+            let start = self.index;
+
+            self.index += 1;
+            self.error(ErrorKind::UnmatchedParenthesis, start);
+            self.disjunction();
+        }
+
+        for &(name, start, end) in self.references.iter() {
+            if !self.names.iter().any(|group| group.name == name) {
+                self.errors.push(Error {
+                    kind: ErrorKind::UnknownGroupName,
+                    start,
+                    end,
+                });
+            }
+        }
+
+        pattern
+    }
+
+    #[inline]
+    fn error(&mut self, kind: ErrorKind, start: usize) {
+        self.errors.push(Error {
+            kind,
+            start,
+            end: self.index,
+        });
+    }
+
+    #[inline]
+    fn alloc<T: Copy>(&self, start: usize, item: T) -> Node<'ast, T> {
+        Node::new(self.arena.alloc(Loc::new(start as u32, self.index as u32, item)))
+    }
+
+    #[inline]
+    fn list<T: Copy>(&self, items: Vec<Node<'ast, T>>) -> NodeList<'ast, T> {
+        NodeList::from_iter(self.arena, items)
+    }
+
+    /// Byte at an offset from the current position, or 0 past the end
+    /// of the pattern.
+    #[inline]
+    fn byte(&self, offset: usize) -> u8 {
+        match self.index + offset < self.end {
+            true  => self.source.as_bytes()[self.index + offset],
+            false => 0,
+        }
+    }
+
+    #[inline]
+    fn eat(&mut self, byte: u8) -> bool {
+        if self.byte(0) == byte {
+            self.index += 1;
+            return true;
+        }
+
+        false
+    }
+
+    #[inline]
+    fn read_char(&mut self) -> char {
+        let character = self.source[self.index..].chars().next().unwrap_or('\0');
+
+        self.index += character.len_utf8();
+
+        character
+    }
+
+    /// Count capturing groups ahead of parsing, so that backreferences
+    /// to groups further in the pattern can be told apart from octal
+    /// escapes.
+    fn scan_groups(&mut self) {
+        let bytes = self.source.as_bytes();
+        let end = self.end;
+        let byte = |index: usize| if index < end { bytes[index] } else { 0 };
+        let mut index = 1;
+        let mut classes = 0;
+
+        while index < self.end {
+            match bytes[index] {
+                b'\\' => index += 1,
+                b'[' if self.sets => classes += 1,
+                b'['  => classes = 1,
+                b']' if classes > 0 => classes -= 1,
+                b'(' if classes == 0 => {
+                    if byte(index + 1) != b'?' {
+                        self.group_count += 1;
+                    } else if byte(index + 2) == b'<' && byte(index + 3) != b'=' && byte(index + 3) != b'!' {
+                        self.group_count += 1;
+                        self.named_groups = true;
+                    }
+                },
+                _ => {},
+            }
+
+            index += 1;
+        }
+    }
+
+    fn disjunction(&mut self) -> DisjunctionNode<'ast> {
+        let start = self.index;
+        let id = self.disjunctions;
+        let mut alternatives = Vec::new();
+
+        self.disjunctions += 1;
+
+        loop {
+            self.path.push((id, alternatives.len() as u32));
+            alternatives.push(self.alternative());
+            self.path.pop();
+
+            if !self.eat(b'|') {
+                break;
+            }
+        }
+
+        let alternatives = self.list(alternatives);
+
+        self.alloc(start, Disjunction { alternatives })
+    }
+
+    fn alternative(&mut self) -> AlternativeNode<'ast> {
+        let start = self.index;
+        let mut terms = Vec::new();
+
+        while let Some(term) = self.term() {
+            terms.push(term);
+        }
+
+        let terms = self.list(terms);
+
+        self.alloc(start, Alternative { terms })
+    }
+
+    fn term(&mut self) -> Option<TermNode<'ast>> {
+        let start = self.index;
+
+        if start >= self.end {
+            return None;
+        }
+
+        let (term, quantifiable) = match self.byte(0) {
+            b'|' | b')' => return None,
+            b'^' => {
+                self.index += 1;
+                (Term::Assertion(Assertion::Start), false)
+            },
+            b'$' => {
+                self.index += 1;
+                (Term::Assertion(Assertion::End), false)
+            },
+            b'.' => {
+                self.index += 1;
+                (Term::Dot, true)
+            },
+            b'(' => self.group(),
+            b'[' => (Term::Class(self.class().0), true),
+            b'\\' => self.atom_escape(),
+            byte @ b'*' | byte @ b'+' | byte @ b'?' => {
+                self.index += 1;
+                self.error(ErrorKind::NothingToRepeat, start);
+                (Term::Character(byte as u32), true)
+            },
+            b'{' if self.braced_quantifier().is_some() => {
+                self.error(ErrorKind::NothingToRepeat, start);
+                (Term::Character('{' as u32), true)
+            },
+            byte @ b'{' | byte @ b'}' | byte @ b']' => {
+                self.index += 1;
+
+                if self.unicode {
+                    self.error(ErrorKind::LoneBracket, start);
+                }
+
+                (Term::Character(byte as u32), true)
+            },
+            _ => (Term::Character(self.read_char() as u32), true),
+        };
+
+        let term = self.alloc(start, term);
+
+        Some(self.quantified(term, quantifiable))
+    }
+
+    fn quantified(&mut self, term: TermNode<'ast>, quantifiable: bool) -> TermNode<'ast> {
+        let start = self.index;
+
+        let (min, max) = match self.byte(0) {
+            b'*' => {
+                self.index += 1;
+                (0, None)
+            },
+            b'+' => {
+                self.index += 1;
+                (1, None)
+            },
+            b'?' => {
+                self.index += 1;
+                (0, Some(1))
+            },
+            b'{' => match self.braced_quantifier() {
+                Some(bounds) => bounds,
+                None         => return term,
+            },
+            _ => return term,
+        };
+
+        let greedy = !self.eat(b'?');
+
+        if !quantifiable {
+            self.error(ErrorKind::NothingToRepeat, start);
+        }
+
+        if let Some(max) = max {
+            if min > max {
+                self.error(ErrorKind::InvalidQuantifier, start);
+            }
+        }
+
+        let quantified = Quantified {
+            term,
+            quantifier: Quantifier { min, max, greedy },
+        };
+
+        self.alloc(term.start as usize, Term::Quantified(quantified))
+    }
+
+    /// Read `{n}`, `{n,}` or `{n,m}`. Nothing is consumed if the input
+    /// is not one of those.
+    fn braced_quantifier(&mut self) -> Option<(u32, Option<u32>)> {
+        let start = self.index;
+
+        self.index += 1;
+
+        let min = self.decimal();
+        let max = match self.eat(b',') {
+            true  => self.decimal(),
+            false => min,
+        };
+
+        match (min, self.eat(b'}')) {
+            (Some(min), true) => Some((min, max)),
+            _ => {
+                self.index = start;
+                None
+            },
+        }
+    }
+
+    /// Read a decimal number, saturating on overflow.
+    fn decimal(&mut self) -> Option<u32> {
+        let mut value: Option<u32> = None;
+
+        while let byte @ b'0'..=b'9' = self.byte(0) {
+            self.index += 1;
+
+            let digit = (byte - b'0') as u32;
+
+            value = Some(value.unwrap_or(0).saturating_mul(10).saturating_add(digit));
+        }
+
+        value
+    }
+
+    /// Read hexadecimal digits, `len` of them, or as many as there are if
+    /// `len` is 0. Nothing is consumed if there are not enough digits.
+    fn hexadecimal(&mut self, len: usize) -> Option<u32> {
+        let start = self.index;
+        let mut value: u32 = 0;
+        let mut count = 0;
+
+        while len == 0 || count < len {
+            let digit = match self.byte(0) {
+                byte @ b'0'..=b'9' => byte - b'0',
+                byte @ b'a'..=b'f' => byte - b'a' + 10,
+                byte @ b'A'..=b'F' => byte - b'A' + 10,
+                _ => break,
+            };
+
+            self.index += 1;
+            count += 1;
+            value = value.saturating_mul(16).saturating_add(digit as u32);
+        }
+
+        if count == 0 || (len != 0 && count < len) {
+            self.index = start;
+            return None;
+        }
+
+        Some(value)
+    }
+
+    fn group(&mut self) -> (Term<'ast>, bool) {
+        let start = self.index;
+        let mut kind = GroupKind::NonCapturing;
+        let mut lookaround = None;
+
+        self.index += 1;
+
+        if self.eat(b'?') {
+            match (self.byte(0), self.byte(1)) {
+                (b':', _) => self.index += 1,
+                (b'=', _) => {
+                    self.index += 1;
+                    lookaround = Some(LookaroundKind::Lookahead);
+                },
+                (b'!', _) => {
+                    self.index += 1;
+                    lookaround = Some(LookaroundKind::NegativeLookahead);
+                },
+                (b'<', b'=') => {
+                    self.index += 2;
+                    lookaround = Some(LookaroundKind::Lookbehind);
+                },
+                (b'<', b'!') => {
+                    self.index += 2;
+                    lookaround = Some(LookaroundKind::NegativeLookbehind);
+                },
+                (b'<', _) => {
+                    self.index += 1;
+                    self.captures += 1;
+
+                    let name = self.group_name();
+
+                    self.declare(name);
+
+                    kind = GroupKind::Capturing {
+                        index: self.captures,
+                        name: Some(name),
+                    };
+                },
+                _ => self.error(ErrorKind::InvalidGroup, start),
+            }
+        } else {
+            self.captures += 1;
+
+            kind = GroupKind::Capturing {
+                index: self.captures,
+                name: None,
+            };
+        }
+
+        let body = self.disjunction();
+
+        if !self.eat(b')') {
+            self.error(ErrorKind::UnterminatedGroup, start);
+        }
+
+        match lookaround {
+            Some(kind) => {
+                // Annex B allows quantified lookaheads outside of Unicode mode
+                let quantifiable = !self.unicode && matches!(kind, LookaroundKind::Lookahead | LookaroundKind::NegativeLookahead);
+
+                (Term::Lookaround(Lookaround { kind, body }), quantifiable)
+            },
+            None => (Term::Group(Group { kind, body }), true),
+        }
+    }
+
+    /// Read a group name and the closing `>`, the opening `<` has
+    /// already been consumed.
+    fn group_name(&mut self) -> &'ast str {
+        let start = self.index;
+        let mut decoded: Option<String> = None;
+
+        loop {
+            let position = self.index;
+
+            let character = match self.byte(0) {
+                b'>' | 0 => break,
+                b'\\' => {
+                    self.index += 1;
+
+                    let escaped = match self.byte(0) {
+                        b'u' => {
+                            self.index += 1;
+                            self.unicode_escape(true)
+                        },
+                        _ => None,
+                    };
+
+                    match escaped.and_then(std::char::from_u32) {
+                        Some(character) => {
+                            decoded.get_or_insert_with(|| self.source[start..position].to_owned());
+                            character
+                        },
+                        None => {
+                            self.error(ErrorKind::InvalidGroupName, position);
+                            continue;
+                        },
+                    }
+                },
+                _ => self.read_char(),
+            };
+
+            let valid = match position == start {
+                true  => character.is_alphabetic() || character == '$' || character == '_',
+                false => character.is_alphanumeric() || matches!(character, '$' | '_' | '\u{200C}' | '\u{200D}'),
+            };
+
+            if !valid {
+                self.error(ErrorKind::InvalidGroupName, position);
+            }
+
+            if let Some(decoded) = decoded.as_mut() {
+                decoded.push(character);
+            }
+        }
+
+        let end = self.index;
+
+        if start == end || !self.eat(b'>') {
+            self.error(ErrorKind::InvalidGroupName, start);
+        }
+
+        match decoded {
+            Some(decoded) => self.arena.alloc_string(decoded),
+            None          => &self.source[start..end],
+        }
+    }
+
+    /// Record a group name, which can be reused only in different
+    /// alternatives, `(?<a>x)|(?<a>y)`.
+    fn declare(&mut self, name: &'ast str) {
+        let path = self.path.clone();
+
+        let duplicate = self.names.iter().filter(|group| group.name == name).any(|group| {
+            for (a, b) in group.path.iter().zip(path.iter()) {
+                if a.0 != b.0 {
+                    return true;
+                }
+
+                if a.1 != b.1 {
+                    return false;
+                }
+            }
+
+            true
+        });
+
+        if duplicate {
+            let start = self.index - name.len() - 1;
+
+            self.errors.push(Error {
+                kind: ErrorKind::DuplicateGroupName,
+                start,
+                end: self.index - 1,
+            });
+        }
+
+        self.names.push(GroupName { name, path });
+    }
+
+    fn atom_escape(&mut self) -> (Term<'ast>, bool) {
+        let start = self.index;
+
+        match self.byte(1) {
+            b'b' => {
+                self.index += 2;
+                return (Term::Assertion(Assertion::WordBoundary), false);
+            },
+            b'B' => {
+                self.index += 2;
+                return (Term::Assertion(Assertion::NotWordBoundary), false);
+            },
+            b'1'..=b'9' => {
+                self.index += 1;
+
+                let index = self.decimal().unwrap_or(0);
+
+                if index <= self.group_count {
+                    return (Term::Backreference(Backreference::Index(index)), true);
+                }
+
+                if self.unicode {
+                    self.error(ErrorKind::InvalidBackreference, start);
+                    return (Term::Backreference(Backreference::Index(index)), true);
+                }
+
+                // Legacy octal escape, parsed along with other escapes
+                self.index = start;
+            },
+            b'k' if self.unicode || self.named_groups => {
+                self.index += 2;
+
+                if !self.eat(b'<') {
+                    self.error(ErrorKind::InvalidEscape, start);
+                    return (Term::Character('k' as u32), true);
+                }
+
+                let name = self.group_name();
+
+                self.references.push((name, start, self.index));
+
+                return (Term::Backreference(Backreference::Named(name)), true);
+            },
+            _ => {},
+        }
+
+        match self.escape(false) {
+            Escape::Character(value) => (Term::Character(value), true),
+            Escape::Class(escape)    => (Term::Escape(escape), true),
+        }
+    }
+
+    /// Read an escape shared by atoms and classes, starting at the `\`.
+    fn escape(&mut self, in_class: bool) -> Escape<'ast> {
+        let start = self.index;
+
+        self.index += 1;
+
+        if self.index >= self.end {
+            self.error(ErrorKind::InvalidEscape, start);
+            return Escape::Character('\\' as u32);
+        }
+
+        let character = self.read_char();
+
+        let value = match character {
+            'd' => return Escape::Class(ClassEscape::Digit),
+            'D' => return Escape::Class(ClassEscape::NotDigit),
+            's' => return Escape::Class(ClassEscape::Space),
+            'S' => return Escape::Class(ClassEscape::NotSpace),
+            'w' => return Escape::Class(ClassEscape::Word),
+            'W' => return Escape::Class(ClassEscape::NotWord),
+            'p' | 'P' if self.unicode => return Escape::Class(self.property(start, character == 'P')),
+            'f' => 0x0C,
+            'n' => 0x0A,
+            'r' => 0x0D,
+            't' => 0x09,
+            'v' => 0x0B,
+            'b' if in_class => 0x08,
+            'c' => match self.byte(0) {
+                byte @ b'a'..=b'z' | byte @ b'A'..=b'Z' => {
+                    self.index += 1;
+                    byte as u32 % 32
+                },
+                byte @ b'0'..=b'9' | byte @ b'_' if in_class && !self.unicode => {
+                    self.index += 1;
+                    byte as u32 % 32
+                },
+                _ if self.unicode => {
+                    self.error(ErrorKind::InvalidEscape, start);
+                    'c' as u32
+                },
+                _ => {
+                    // `\c` is read as a backslash followed by `c`
+                    self.index = start + 1;
+                    '\\' as u32
+                },
+            },
+            '0' if !self.byte(0).is_ascii_digit() => 0,
+            '0'..='9' if self.unicode => {
+                self.error(ErrorKind::InvalidEscape, start);
+                character as u32
+            },
+            '8' | '9' => character as u32,
+            '0'..='7' => self.legacy_octal(character as u32 - '0' as u32),
+            'x' => match self.hexadecimal(2) {
+                Some(value) => value,
+                None        => self.identity(start, character, in_class),
+            },
+            'u' => match self.unicode_escape(self.unicode) {
+                Some(value) => value,
+                None        => self.identity(start, character, in_class),
+            },
+            _ => self.identity(start, character, in_class),
+        };
+
+        Escape::Character(value)
+    }
+
+    /// Escaped character standing for itself. In Unicode mode, only
+    /// syntax characters can be escaped this way.
+    fn identity(&mut self, start: usize, character: char, in_class: bool) -> u32 {
+        if self.unicode {
+            let valid = match character {
+                '^' | '$' | '\\' | '.' | '*' | '+' | '?' | '(' | ')' | '[' | ']' | '{' | '}' | '|' | '/' => true,
+                '-' => in_class,
+                '&' | '!' | '#' | '%' | ',' | ':' | ';' | '<' | '=' | '>' | '@' | '`' | '~' => in_class && self.sets,
+                _ => false,
+            };
+
+            if !valid {
+                self.error(ErrorKind::InvalidEscape, start);
+            }
+        }
+
+        character as u32
+    }
+
+    /// Read the rest of a legacy octal escape, `\12`, up to a value of `\377`.
+    fn legacy_octal(&mut self, mut value: u32) -> u32 {
+        let max_digits = if value <= 3 { 2 } else { 1 };
+
+        for _ in 0..max_digits {
+            match self.byte(0) {
+                byte @ b'0'..=b'7' => {
+                    self.index += 1;
+                    value = value * 8 + (byte - b'0') as u32;
+                },
+                _ => break,
+            }
+        }
+
+        value
+    }
+
+    /// Read the rest of `\uXXXX`, or `\u{X}` if braces are allowed. In
+    /// Unicode mode a pair of escaped surrogates is read as one character.
+    /// Nothing is consumed if the escape is malformed.
+    fn unicode_escape(&mut self, braces: bool) -> Option<u32> {
+        let start = self.index;
+
+        if braces && self.eat(b'{') {
+            let value = self.hexadecimal(0);
+
+            if !self.eat(b'}') {
+                self.index = start;
+                return None;
+            }
+
+            if value? > 0x10FFFF {
+                self.error(ErrorKind::InvalidEscape, start - 2);
+            }
+
+            return value;
+        }
+
+        let value = self.hexadecimal(4)?;
+
+        if (0xD800..0xDC00).contains(&value) && braces && self.byte(0) == b'\\' && self.byte(1) == b'u' {
+            let lead = self.index;
+
+            self.index += 2;
+
+            match self.hexadecimal(4) {
+                Some(trail) if (0xDC00..0xE000).contains(&trail) => {
+                    return Some(0x10000 + ((value - 0xD800) << 10) + (trail - 0xDC00));
+                },
+                _ => self.index = lead,
+            }
+        }
+
+        Some(value)
+    }
+
+    /// Read the rest of `\p{Name}` or `\p{Name=Value}`.
+    fn property(&mut self, start: usize, negated: bool) -> ClassEscape<'ast> {
+        let word = |parser: &mut Self| {
+            let start = parser.index;
+
+            while let b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_' = parser.byte(0) {
+                parser.index += 1;
+            }
+
+            &parser.source[start..parser.index]
+        };
+
+        let mut name = "";
+        let mut value = None;
+        let mut valid = self.eat(b'{');
+
+        if valid {
+            name = word(self);
+
+            if self.eat(b'=') {
+                value = Some(word(self));
+            }
+
+            valid = self.eat(b'}') && match value {
+                Some(value) => match name {
+                    "General_Category" | "gc" => GENERAL_CATEGORIES.contains(&value),
+                    // Script values are only checked for their syntax
+                    "Script" | "sc" | "Script_Extensions" | "scx" => !value.is_empty(),
+                    _ => false,
+                },
+                None => {
+                    GENERAL_CATEGORIES.contains(&name)
+                        || BINARY_PROPERTIES.contains(&name)
+                        || (self.sets && !negated && STRING_PROPERTIES.contains(&name))
+                },
+            };
+        }
+
+        if !valid {
+            self.error(ErrorKind::InvalidProperty, start);
+        }
+
+        ClassEscape::Property { negated, name, value }
+    }
+
+    /// Read a class, `[a-z]`. The second value is whether the class may
+    /// match strings, rather than single characters.
+    fn class(&mut self) -> (CharacterClass<'ast>, bool) {
+        let start = self.index;
+
+        self.index += 1;
+
+        let negated = self.eat(b'^');
+
+        if self.sets {
+            return self.class_set(start, negated);
+        }
+
+        let mut items = Vec::new();
+
+        loop {
+            if self.index >= self.end {
+                self.error(ErrorKind::UnterminatedClass, start);
+                break;
+            }
+
+            if self.eat(b']') {
+                break;
+            }
+
+            let mut from = self.class_atom();
+
+            if let Some((high, low)) = self.surrogates(&from) {
+                items.push(high);
+                from = low;
+            }
+
+            if self.byte(0) != b'-' || self.byte(1) == b']' || self.byte(1) == 0 {
+                items.push(from);
+                continue;
+            }
+
+            let dash = self.index;
+
+            self.index += 1;
+
+            let mut to = self.class_atom();
+            let mut trailing = None;
+
+            if let Some((high, low)) = self.surrogates(&to) {
+                to = high;
+                trailing = Some(low);
+            }
+
+            match (from.item, to.item) {
+                (ClassItem::Character(a), ClassItem::Character(b)) => {
+                    if a > b {
+                        self.error(ErrorKind::RangeOutOfOrder, from.start as usize);
+                    }
+
+                    items.push(self.alloc(from.start as usize, ClassItem::Range(a, b)));
+                },
+                _ => {
+                    if self.unicode {
+                        self.error(ErrorKind::InvalidClassRange, from.start as usize);
+                    }
+
+                    items.push(from);
+                    items.push(Node::new(self.arena.alloc(Loc::new(dash as u32, dash as u32 + 1, ClassItem::Character('-' as u32)))));
+                    items.push(to);
+                },
+            }
+
+            if let Some(low) = trailing {
+                items.push(low);
+            }
+        }
+
+        let class = CharacterClass {
+            negated,
+            kind: ClassKind::Union,
+            items: self.list(items),
+        };
+
+        (class, false)
+    }
+
+    /// Split an astral character of a class into its surrogates, which are
+    /// separate characters outside of Unicode mode. Only the low surrogate
+    /// is a bound of a range, making `[💩-💫]` out of order.
+    fn surrogates(&self, atom: &ClassItemNode<'ast>) -> Option<(ClassItemNode<'ast>, ClassItemNode<'ast>)> {
+        match atom.item {
+            ClassItem::Character(value) if !self.unicode && value > 0xFFFF => {
+                let value = value - 0x10000;
+                let surrogate = |value| Node::new(self.arena.alloc(Loc::new(atom.start, atom.end, ClassItem::Character(value))));
+
+                Some((surrogate(0xD800 + (value >> 10)), surrogate(0xDC00 + (value & 0x3FF))))
+            },
+            _ => None,
+        }
+    }
+
+    fn class_atom(&mut self) -> ClassItemNode<'ast> {
+        let start = self.index;
+
+        let item = match self.byte(0) {
+            b'\\' => match self.escape(true) {
+                Escape::Character(value) => ClassItem::Character(value),
+                Escape::Class(escape)    => ClassItem::Escape(escape),
+            },
+            _ => ClassItem::Character(self.read_char() as u32),
+        };
+
+        self.alloc(start, item)
+    }
+
+    /// Read the rest of a class in `v` mode, which can contain nested
+    /// classes, strings and set operations.
+    fn class_set(&mut self, start: usize, negated: bool) -> (CharacterClass<'ast>, bool) {
+        let mut items = Vec::new();
+        let mut strings = Vec::new();
+        let mut kind = ClassKind::Union;
+        let mut pending = false;
+
+        loop {
+            let operator_start = self.index;
+
+            if self.index >= self.end {
+                self.error(ErrorKind::UnterminatedClass, start);
+                break;
+            }
+
+            if self.byte(0) == b']' {
+                if pending {
+                    self.error(ErrorKind::InvalidClassOperation, operator_start - 2);
+                }
+
+                self.index += 1;
+                break;
+            }
+
+            let operator = match (self.byte(0), self.byte(1)) {
+                (b'&', b'&') => Some(ClassKind::Intersection),
+                (b'-', b'-') => Some(ClassKind::Subtraction),
+                _            => None,
+            };
+
+            if let Some(operator) = operator {
+                self.index += 2;
+
+                let misplaced = pending
+                    || self.byte(0) == b'&'
+                    || match (items.first(), kind) {
+                        (None, _) => true,
+                        (Some(_), ClassKind::Union) => items.len() > 1,
+                        _ => kind != operator,
+                    }
+                    || items.iter().any(|item: &ClassItemNode| matches!(item.item, ClassItem::Range(..)));
+
+                if misplaced {
+                    self.error(ErrorKind::InvalidClassOperation, operator_start);
+                }
+
+                kind = operator;
+                pending = true;
+                continue;
+            }
+
+            if kind != ClassKind::Union && !pending {
+                self.error(ErrorKind::InvalidClassOperation, operator_start);
+            }
+
+            pending = false;
+
+            let (operand, may_contain_strings) = self.class_set_operand(kind == ClassKind::Union);
+
+            items.push(operand);
+            strings.push(may_contain_strings);
+        }
+
+        let may_contain_strings = match kind {
+            ClassKind::Union        => strings.iter().any(|&strings| strings),
+            ClassKind::Intersection => !strings.is_empty() && strings.iter().all(|&strings| strings),
+            ClassKind::Subtraction  => strings.first() == Some(&true),
+        };
+
+        if negated && may_contain_strings {
+            self.error(ErrorKind::NegatedClassWithStrings, start);
+        }
+
+        let class = CharacterClass {
+            negated,
+            kind,
+            items: self.list(items),
+        };
+
+        (class, may_contain_strings)
+    }
+
+    fn class_set_operand(&mut self, ranges: bool) -> (ClassItemNode<'ast>, bool) {
+        let start = self.index;
+
+        let (item, may_contain_strings) = match (self.byte(0), self.byte(1)) {
+            (b'[', _) => {
+                let (class, strings) = self.class();
+
+                (ClassItem::Class(class), strings)
+            },
+            (b'\\', b'q') if self.byte(2) == b'{' => {
+                self.index += 3;
+                self.class_strings()
+            },
+            (b'\\', _) => match self.escape(true) {
+                Escape::Character(value) => (ClassItem::Character(value), false),
+                Escape::Class(escape) => {
+                    let strings = match escape {
+                        ClassEscape::Property { name, value: None, .. } => STRING_PROPERTIES.contains(&name),
+                        _ => false,
+                    };
+
+                    (ClassItem::Escape(escape), strings)
+                },
+            },
+            _ => (ClassItem::Character(self.class_set_character()), false),
+        };
+
+        if let ClassItem::Character(from) = item {
+            if ranges && self.byte(0) == b'-' && self.byte(1) != b'-' {
+                self.index += 1;
+
+                let to = match self.byte(0) {
+                    b'\\' => match self.escape(true) {
+                        Escape::Character(value) => Some(value),
+                        Escape::Class(_)         => None,
+                    },
+                    b'[' | b']' | 0 => None,
+                    _ => Some(self.class_set_character()),
+                };
+
+                match to {
+                    Some(to) => {
+                        if from > to {
+                            self.error(ErrorKind::RangeOutOfOrder, start);
+                        }
+
+                        return (self.alloc(start, ClassItem::Range(from, to)), false);
+                    },
+                    None => self.error(ErrorKind::InvalidClassRange, start),
+                }
+            }
+        }
+
+        (self.alloc(start, item), may_contain_strings)
+    }
+
+    /// Read an unescaped character in a class in `v` mode.
+    fn class_set_character(&mut self) -> u32 {
+        let start = self.index;
+        let character = self.read_char();
+
+        let valid = match character {
+            '(' | ')' | '[' | ']' | '{' | '}' | '/' | '-' | '\\' | '|' => false,
+            // Double punctuators are reserved
+            '&' | '!' | '#' | '$' | '%' | '*' | '+' | ',' | '.' | ':' | ';' | '<' | '=' | '>' | '?' | '@' | '^' | '`' | '~'
+                if self.byte(0) == character as u8 =>
+            {
+                self.index += 1;
+                false
+            },
+            _ => true,
+        };
+
+        if !valid {
+            self.error(ErrorKind::InvalidClassCharacter, start);
+        }
+
+        character as u32
+    }
+
+    /// Read the rest of `\q{abc|d}`.
+    fn class_strings(&mut self) -> (ClassItem<'ast>, bool) {
+        let start = self.index - 3;
+        let mut strings = Vec::new();
+        let mut may_contain_strings = false;
+
+        loop {
+            let string_start = self.index;
+            let mut characters = Vec::new();
+
+            loop {
+                let character_start = self.index;
+
+                let value = match self.byte(0) {
+                    b'|' | b'}' | 0 => break,
+                    b'\\' => match self.escape(true) {
+                        Escape::Character(value) => value,
+                        Escape::Class(_) => {
+                            self.error(ErrorKind::InvalidEscape, character_start);
+                            continue;
+                        },
+                    },
+                    _ => self.class_set_character(),
+                };
+
+                characters.push(self.alloc(character_start, value));
+            }
+
+            may_contain_strings |= characters.len() != 1;
+
+            let characters = self.list(characters);
+
+            strings.push(self.alloc(string_start, ClassString { characters }));
+
+            if !self.eat(b'|') {
+                break;
+            }
+        }
+
+        if !self.eat(b'}') {
+            self.error(ErrorKind::UnterminatedClass, start);
+        }
+
+        (ClassItem::Strings(self.list(strings)), may_contain_strings)
+    }
+}
@@ -0,0 +1,682 @@
+//! Quasi-quoting, building AST from snippets of JavaScript with
+//! `template_expr!`, `template_stmt!` and `template_block!`:
+//!
+//! ```ignore
+//! let pow = template_expr!(arena, "Math.pow(%left, %right)", left = node.left, right = node.right)?;
+//! ```
+//!
+//! Placeholders are identifiers prefixed with `%` where an operand or a name
+//! is expected, following an operand `%` is the modulo operator, `a % b`.
+//! Strings, template strings, regular expressions and comments are left as
+//! they are. A placeholder can stand for:
+//!
+//! * an expression, substituted with an `ExpressionNode` or a name,
+//! * a statement, `%body;`, substituted with a `StatementNode`, an expression
+//!   or, within a list of statements, a `StatementList`,
+//! * a name anywhere else an identifier is expected, such as the property
+//!   of a member expression or a variable in a pattern, substituted with
+//!   a `&str` or an `IdentifierNode`.
+//!
+//! Every template is parsed once per thread and cached. Each instantiation
+//! clones it into the given arena, with all nodes synthetic. Substituted
+//! nodes are inserted as they are, placeholders used more than once get
+//! a clone of the node for every other use, see `DeepClone`.
+//!
+//! Invalid templates, placeholders without a substitution, substitutions
+//! without a placeholder and substitutions that can't stand in place of
+//! their placeholders are returned as a `TemplateError`.
+
+use toolshed::Arena;
+
+use crate::ast::{Node, Loc, Expression, ExpressionNode, Statement, StatementNode, StatementList};
+use crate::ast::IdentifierNode;
+use crate::ast::{CloneWith, Cloner, DeepClone};
+use crate::error::{TemplateError, TemplateErrorKind};
+use crate::module::Module;
+use crate::parser::parse;
+
+/// Identifiers of placeholders are parsed with this prefix in place of `%`.
+const PLACEHOLDER: &str = "__template_";
+
+/// Name of the placeholder an identifier stands for, if it does.
+#[inline]
+pub(crate) fn placeholder_name(identifier: &str) -> Option<&str> {
+    identifier.strip_prefix(PLACEHOLDER)
+}
+
+/// Node to replace a placeholder with.
+#[derive(Debug, Clone, Copy)]
+pub enum Substitution<'ast> {
+    Name(&'ast str),
+    Expression(ExpressionNode<'ast>),
+    Statement(StatementNode<'ast>),
+    Statements(StatementList<'ast>),
+}
+
+impl<'ast> Substitution<'ast> {
+    pub(crate) fn name(self, placeholder: &str) -> Result<&'ast str, TemplateErrorKind> {
+        match self {
+            Substitution::Name(name) => Ok(name),
+            _ => Err(self.mismatch(placeholder, "a name")),
+        }
+    }
+
+    pub(crate) fn expression(self, placeholder: &str, arena: &'ast Arena) -> Result<ExpressionNode<'ast>, TemplateErrorKind> {
+        match self {
+            Substitution::Name(name)             => Ok(synthetic(arena, Expression::Identifier(name))),
+            Substitution::Expression(expression) => Ok(expression),
+            _ => Err(self.mismatch(placeholder, "an expression")),
+        }
+    }
+
+    pub(crate) fn statement(self, placeholder: &str, arena: &'ast Arena) -> Result<StatementNode<'ast>, TemplateErrorKind> {
+        match self {
+            Substitution::Statement(statement) => Ok(statement),
+            Substitution::Statements(_) => Err(self.mismatch(placeholder, "a single statement")),
+            _ => Ok(synthetic(arena, Statement::Expression(self.expression(placeholder, arena)?))),
+        }
+    }
+
+    fn mismatch(self, placeholder: &str, expected: &'static str) -> TemplateErrorKind {
+        TemplateErrorKind::MismatchedSubstitution {
+            placeholder: placeholder.to_owned(),
+            expected,
+            found: self.kind(),
+        }
+    }
+
+    pub(crate) fn clone_in(self, arena: &'ast Arena) -> Self {
+        match self {
+            Substitution::Name(name)             => Substitution::Name(name),
+            Substitution::Expression(expression) => Substitution::Expression(expression.clone_in(arena)),
+            Substitution::Statement(statement)   => Substitution::Statement(statement.clone_in(arena)),
+            Substitution::Statements(statements) => Substitution::Statements(statements.clone_in(arena)),
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match *self {
+            Substitution::Name(_)       => "a name",
+            Substitution::Expression(_) => "an expression",
+            Substitution::Statement(_)  => "a statement",
+            Substitution::Statements(_) => "statements",
+        }
+    }
+}
+
+#[inline]
+fn synthetic<'ast, T: Copy>(arena: &'ast Arena, item: T) -> Node<'ast, T> {
+    Node::new(arena.alloc(Loc::synthetic(item)))
+}
+
+impl<'ast> From<&'ast str> for Substitution<'ast> {
+    #[inline]
+    fn from(name: &'ast str) -> Self {
+        Substitution::Name(name)
+    }
+}
+
+impl<'ast> From<IdentifierNode<'ast>> for Substitution<'ast> {
+    #[inline]
+    fn from(name: IdentifierNode<'ast>) -> Self {
+        Substitution::Name(name.item)
+    }
+}
+
+impl<'ast> From<ExpressionNode<'ast>> for Substitution<'ast> {
+    #[inline]
+    fn from(expression: ExpressionNode<'ast>) -> Self {
+        Substitution::Expression(expression)
+    }
+}
+
+impl<'ast> From<StatementNode<'ast>> for Substitution<'ast> {
+    #[inline]
+    fn from(statement: StatementNode<'ast>) -> Self {
+        Substitution::Statement(statement)
+    }
+}
+
+impl<'ast> From<StatementList<'ast>> for Substitution<'ast> {
+    #[inline]
+    fn from(statements: StatementList<'ast>) -> Self {
+        Substitution::Statements(statements)
+    }
+}
+
+/// Parsed template, see the module documentation. Usually created and
+/// cached by the template macros.
+pub struct Template {
+    source: String,
+    module: Module<'static>,
+}
+
+impl Template {
+    /// Template of any number of statements.
+    pub fn block(source: &str) -> Result<Self, TemplateError> {
+        let error = |kind| TemplateError {
+            kind,
+            source: source.to_owned(),
+        };
+
+        let module = replace_placeholders(source)
+            .ok_or_else(|| error(TemplateErrorKind::Unterminated))
+            .and_then(|replaced| parse(&replaced).map_err(|errors| error(TemplateErrorKind::Invalid(errors))))?;
+
+        Ok(Template {
+            source: source.to_owned(),
+            module,
+        })
+    }
+
+    /// Template of a single statement.
+    pub fn statement(source: &str) -> Result<Self, TemplateError> {
+        let template = Template::block(source)?;
+
+        template.only_statement()?;
+
+        Ok(template)
+    }
+
+    /// Template of a single expression. It is parsed in parentheses,
+    /// so object literals don't need any.
+    pub fn expression(source: &str) -> Result<Self, TemplateError> {
+        let mut template = Template::block(&format!("({})", source)).map_err(|mut error| {
+            error.source = source.to_owned();
+            error
+        })?;
+
+        template.source = source.to_owned();
+        template.only_expression()?;
+
+        Ok(template)
+    }
+
+    pub fn instantiate_block<'ast>(&self, arena: &'ast Arena, substitutions: &[(&str, Substitution<'ast>)]) -> Result<StatementList<'ast>, TemplateError> {
+        self.instantiate(&self.module.body(), arena, substitutions)
+    }
+
+    pub fn instantiate_statement<'ast>(&self, arena: &'ast Arena, substitutions: &[(&str, Substitution<'ast>)]) -> Result<StatementNode<'ast>, TemplateError> {
+        self.instantiate(&self.only_statement()?, arena, substitutions)
+    }
+
+    pub fn instantiate_expression<'ast>(&self, arena: &'ast Arena, substitutions: &[(&str, Substitution<'ast>)]) -> Result<ExpressionNode<'ast>, TemplateError> {
+        self.instantiate(&self.only_expression()?, arena, substitutions)
+    }
+
+    fn instantiate<'ast, T>(&self, node: &T, arena: &'ast Arena, substitutions: &[(&str, Substitution<'ast>)]) -> Result<T::Cloned, TemplateError> where
+        T: CloneWith<'ast>,
+    {
+        for (index, &(name, _)) in substitutions.iter().enumerate() {
+            if substitutions[..index].iter().any(|&(other, _)| other == name) {
+                return Err(self.error(TemplateErrorKind::DuplicateSubstitution(name.to_owned())));
+            }
+        }
+
+        let mut cloner = Cloner::template(arena, substitutions);
+        let cloned = node.clone_with(&mut cloner);
+
+        if let Some(kind) = cloner.error() {
+            return Err(self.error(kind));
+        }
+
+        if let Some(name) = cloner.unused() {
+            return Err(self.error(TemplateErrorKind::UnusedSubstitution(name.to_owned())));
+        }
+
+        Ok(cloned)
+    }
+
+    fn error(&self, kind: TemplateErrorKind) -> TemplateError {
+        TemplateError {
+            kind,
+            source: self.source.clone(),
+        }
+    }
+
+    // Nodes only live as long as the module, they are never handed out
+    // without being cloned first.
+    fn only_statement(&self) -> Result<StatementNode<'static>, TemplateError> {
+        match self.module.body().only_element() {
+            Some(statement) => Ok(*statement),
+            None => Err(self.error(TemplateErrorKind::NotAStatement)),
+        }
+    }
+
+    fn only_expression(&self) -> Result<ExpressionNode<'static>, TemplateError> {
+        match self.only_statement()?.item {
+            Statement::Expression(expression) => Ok(expression),
+            _ => Err(self.error(TemplateErrorKind::NotAnExpression)),
+        }
+    }
+}
+
+/// Words after which an operand or a name follows, so that `/` starts a
+/// regular expression rather than a division and `%` a placeholder rather
+/// than a modulo.
+const OPERAND_KEYWORDS: &[&str] = &[
+    "return", "typeof", "instanceof", "in", "of", "new", "delete", "void",
+    "throw", "case", "do", "else", "yield", "await", "var", "let", "const",
+    "function", "class", "extends",
+];
+
+/// Words heading a statement whose parenthesized head is followed by a
+/// statement rather than by an operator, `if (a) %body;`.
+const CONTROL_KEYWORDS: &[&str] = &["if", "while", "for", "with"];
+
+#[inline]
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_' || ch == '$'
+}
+
+/// Replace `%foo` with an identifier the parser accepts, unless it follows
+/// an operand. Strings, template strings, regular expressions and comments
+/// are copied as they are, `None` if any of them isn't closed.
+fn replace_placeholders(source: &str) -> Option<String> {
+    let mut replaced = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    // Depth of braces at which substitutions of template strings end
+    let mut substitutions = Vec::new();
+    let mut braces = 0;
+    // Whether the last token ends an operand, so that `/` is a division
+    // and `%` a modulo
+    let mut operand = false;
+    // Whether each open parenthesis heads a statement, and whether the
+    // last token is a keyword such a parenthesis follows
+    let mut parens = Vec::new();
+    let mut control = false;
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '%' if !operand && matches!(chars.peek(), Some(&next) if next.is_alphabetic() || next == '_') => {
+                replaced.push_str(PLACEHOLDER);
+                continue;
+            },
+            _ => replaced.push(ch),
+        }
+
+        let after_control = match ch.is_whitespace() {
+            true  => control,
+            false => std::mem::replace(&mut control, false),
+        };
+
+        match ch {
+            '\'' | '"' => {
+                loop {
+                    let next = chars.next()?;
+
+                    replaced.push(next);
+
+                    match next {
+                        '\\'                   => replaced.push(chars.next()?),
+                        '\n'                    => return None,
+                        next if next == ch      => break,
+                        _                       => {},
+                    }
+                }
+
+                operand = true;
+            },
+            '`' => {
+                let substitution = read_template_string(&mut chars, &mut replaced)?;
+
+                if substitution {
+                    braces += 1;
+                    substitutions.push(braces);
+                }
+
+                operand = !substitution;
+            },
+            '/' if chars.peek() == Some(&'/') => {
+                for next in chars.by_ref() {
+                    replaced.push(next);
+
+                    if next == '\n' {
+                        break;
+                    }
+                }
+            },
+            '/' if chars.peek() == Some(&'*') => {
+                replaced.push(chars.next()?);
+
+                loop {
+                    let next = chars.next()?;
+
+                    replaced.push(next);
+
+                    if next == '*' && chars.peek() == Some(&'/') {
+                        replaced.push(chars.next()?);
+                        break;
+                    }
+                }
+            },
+            '/' if !operand => {
+                let mut class = false;
+
+                loop {
+                    let next = chars.next()?;
+
+                    replaced.push(next);
+
+                    match next {
+                        '\\'          => replaced.push(chars.next()?),
+                        '\n'          => return None,
+                        '['           => class = true,
+                        ']'           => class = false,
+                        '/' if !class => break,
+                        _             => {},
+                    }
+                }
+
+                operand = true;
+            },
+            '{' => {
+                braces += 1;
+                operand = false;
+            },
+            '}' if substitutions.last() == Some(&braces) => {
+                substitutions.pop();
+
+                let substitution = read_template_string(&mut chars, &mut replaced)?;
+
+                if !substitution {
+                    braces -= 1;
+                } else {
+                    substitutions.push(braces);
+                }
+
+                operand = !substitution;
+            },
+            '}' => {
+                braces -= 1;
+                operand = false;
+            },
+            '(' => {
+                parens.push(after_control);
+                operand = false;
+            },
+            ')' => operand = !parens.pop().unwrap_or(false),
+            ']' => operand = true,
+            ch if is_word_char(ch) => {
+                let start = replaced.len() - ch.len_utf8();
+
+                while let Some(&next) = chars.peek() {
+                    if !is_word_char(next) {
+                        break;
+                    }
+
+                    replaced.push(next);
+                    chars.next();
+                }
+
+                let word = &replaced[start..];
+
+                operand = !OPERAND_KEYWORDS.contains(&word);
+                control = CONTROL_KEYWORDS.contains(&word);
+            },
+            ch if ch.is_whitespace() => {},
+            _ => operand = false,
+        }
+    }
+
+    match substitutions.is_empty() {
+        true  => Some(replaced),
+        false => None,
+    }
+}
+
+/// Copy a template string up to the closing backtick, `false`, or up to
+/// the opening brace of a substitution, `true`.
+fn read_template_string<I>(chars: &mut std::iter::Peekable<I>, replaced: &mut String) -> Option<bool> where
+    I: Iterator<Item = char>,
+{
+    loop {
+        let next = chars.next()?;
+
+        replaced.push(next);
+
+        match next {
+            '\\'                                => replaced.push(chars.next()?),
+            '`'                                 => return Some(false),
+            '$' if chars.peek() == Some(&'{')   => {
+                replaced.push(chars.next()?);
+
+                return Some(true);
+            },
+            _                                   => {},
+        }
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __template {
+    ($kind:ident, $instantiate:ident, $arena:expr, $source:expr $(, $name:ident = $value:expr)*) => ({
+        thread_local! {
+            static TEMPLATE: ::std::result::Result<$crate::template::Template, $crate::error::TemplateError> =
+                $crate::template::Template::$kind($source);
+        }
+
+        TEMPLATE.with(|template| match *template {
+            Ok(ref template) => template.$instantiate($arena, &[
+                $( (stringify!($name), $crate::template::Substitution::from($value)), )*
+            ]),
+            Err(ref error) => Err(error.clone()),
+        })
+    })
+}
+
+/// Build an `ExpressionNode` from a template, see `esr::template`.
+///
+/// ```ignore
+/// template_expr!(arena, "Math.pow(%left, %right)", left = node.left, right = node.right)?
+/// ```
+#[macro_export]
+macro_rules! template_expr {
+    ($arena:expr, $source:expr $(, $name:ident = $value:expr)* $(,)*) => {
+        $crate::__template!(expression, instantiate_expression, $arena, $source $(, $name = $value)*)
+    }
+}
+
+/// Build a `StatementNode` from a template, see `esr::template`.
+#[macro_export]
+macro_rules! template_stmt {
+    ($arena:expr, $source:expr $(, $name:ident = $value:expr)* $(,)*) => {
+        $crate::__template!(statement, instantiate_statement, $arena, $source $(, $name = $value)*)
+    }
+}
+
+/// Build a `StatementList` from a template, see `esr::template`.
+#[macro_export]
+macro_rules! template_block {
+    ($arena:expr, $source:expr $(, $name:ident = $value:expr)* $(,)*) => {
+        $crate::__template!(block, instantiate_block, $arena, $source $(, $name = $value)*)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ast::Pattern;
+    use crate::{parse_expression, parse_statement, parse_function_body};
+
+    #[test]
+    fn substitutes_expressions() {
+        let arena = Arena::new();
+        let left = parse_expression("a.b", &arena).unwrap();
+        let right = parse_expression("2", &arena).unwrap();
+
+        let expression = template_expr!(&arena, "Math.pow(%left, %right)", left = left, right = right).unwrap();
+
+        assert_eq!(expression, parse_expression("Math.pow(a.b, 2)", &arena).unwrap());
+        assert!(expression.is_synthetic());
+    }
+
+    #[test]
+    fn substitutes_names() {
+        let arena = Arena::new();
+
+        assert_eq!(
+            template_expr!(&arena, "{ [%key]: %value.%property }", key = "foo", value = "bar", property = "baz").unwrap(),
+            parse_expression("{ [foo]: bar.baz }", &arena).unwrap(),
+        );
+
+        assert_eq!(
+            template_stmt!(&arena, "const %name = 1;", name = "foo").unwrap(),
+            parse_statement("const foo = 1;", &arena).unwrap(),
+        );
+    }
+
+    #[test]
+    fn substitutes_statements() {
+        let arena = Arena::new();
+        let body = parse_function_body("a(); b();", &arena).unwrap();
+        let test = parse_expression("ready", &arena).unwrap();
+
+        assert_eq!(
+            template_stmt!(&arena, "if (%test) { %body; done(); }", test = test, body = body).unwrap(),
+            parse_statement("if (ready) { a(); b(); done(); }", &arena).unwrap(),
+        );
+
+        let statement = parse_statement("return;", &arena).unwrap();
+
+        assert_eq!(
+            template_block!(&arena, "before(); %statement; after();", statement = statement).unwrap(),
+            parse_function_body("before(); return; after();", &arena).unwrap(),
+        );
+    }
+
+    #[test]
+    fn templates_are_cached() {
+        for source in &["1", "foo"] {
+            let arena = Arena::new();
+            let value = parse_expression(source, &arena).unwrap();
+
+            assert_eq!(
+                template_expr!(&arena, "[%value]", value = value).unwrap(),
+                parse_expression(&format!("[{}]", source), &arena).unwrap(),
+            );
+        }
+    }
+
+    #[test]
+    fn repeated_placeholders_get_clones() {
+        let arena = Arena::new();
+        let value = parse_expression("a.b", &arena).unwrap();
+
+        match template_expr!(&arena, "%value = %value", value = value).unwrap().item {
+            Expression::Binary(ref binary) => {
+                assert_eq!(binary.left, binary.right);
+                assert!(!std::ptr::eq(&*binary.left, &*binary.right));
+            },
+            _ => panic!("Expected an assignment"),
+        }
+    }
+
+    #[test]
+    fn modulo_follows_operands() {
+        let arena = Arena::new();
+
+        assert_eq!(
+            template_expr!(&arena, "%a % 2", a = "b").unwrap(),
+            parse_expression("b % 2", &arena).unwrap(),
+        );
+        assert_eq!(
+            template_expr!(&arena, "[a % b, a%b, %a%b, (a) %b, c[0]%b, 1 %b]", a = "d").unwrap(),
+            parse_expression("[a % b, a%b, d%b, (a) %b, c[0]%b, 1 %b]", &arena).unwrap(),
+        );
+        assert_eq!(
+            template_stmt!(&arena, "if (a) %b; else while (c) %b;", b = "d").unwrap(),
+            parse_statement("if (a) d; else while (c) d;", &arena).unwrap(),
+        );
+    }
+
+    #[test]
+    fn pattern_placeholders_are_names() {
+        let arena = Arena::new();
+
+        match template_stmt!(&arena, "let [%a] = b;", a = "c").unwrap().item {
+            Statement::Declaration(ref declaration) => {
+                let declarator = declaration.declarators.only_element().unwrap();
+
+                match declarator.id.item {
+                    Pattern::ArrayPattern { elements } => {
+                        assert_eq!(elements.only_element().unwrap().item, Pattern::Identifier(Node::new(&Loc::new(0, 0, "c"))));
+                    },
+                    _ => panic!("Expected an array pattern"),
+                }
+            },
+            _ => panic!("Expected a declaration"),
+        }
+    }
+
+    #[test]
+    fn placeholders_only_in_code() {
+        let arena = Arena::new();
+
+        assert_eq!(
+            template_expr!(&arena, "[%a, '50%off', \"%a\", `%a ${ %a } %a`, /%a[/]/g, 1 /%a/ 2]", a = "b").unwrap(),
+            parse_expression("[b, '50%off', \"%a\", `%a ${ b } %a`, /%a[/]/g, 1 /b/ 2]", &arena).unwrap(),
+        );
+
+        assert_eq!(
+            template_block!(&arena, "// %a\n/* %a */ %a; return /%a/;", a = "b").unwrap(),
+            parse_function_body("b; return /%a/;", &arena).unwrap(),
+        );
+    }
+
+    fn error<T>(result: Result<T, TemplateError>) -> TemplateErrorKind {
+        match result {
+            Ok(_) => panic!("Expected an error"),
+            Err(error) => error.kind,
+        }
+    }
+
+    #[test]
+    fn missing_substitution() {
+        let arena = Arena::new();
+        let left = parse_expression("a", &arena).unwrap();
+
+        assert_eq!(
+            error(template_expr!(&arena, "%left + %right", left = left)),
+            TemplateErrorKind::MissingSubstitution("right".into())
+        );
+    }
+
+    #[test]
+    fn unused_substitution() {
+        let arena = Arena::new();
+        let left = parse_expression("a", &arena).unwrap();
+
+        assert_eq!(
+            error(template_expr!(&arena, "-%left", left = left, right = left)),
+            TemplateErrorKind::UnusedSubstitution("right".into())
+        );
+    }
+
+    #[test]
+    fn mismatched_substitution() {
+        let arena = Arena::new();
+        let name = parse_expression("a", &arena).unwrap();
+        let result = template_expr!(&arena, "foo.%name", name = name);
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Placeholder `%name` stands for a name, but is substituted with an expression in template `foo.%name`"
+        );
+    }
+
+    #[test]
+    fn invalid_templates() {
+        let arena = Arena::new();
+
+        assert_eq!(error(template_stmt!(&arena, "a; b;")), TemplateErrorKind::NotAStatement);
+        assert_eq!(error(template_expr!(&arena, "'50%off")), TemplateErrorKind::Unterminated);
+        assert_eq!(error(template_expr!(&arena, "`${ %a `", a = "b")), TemplateErrorKind::Unterminated);
+
+        match error(template_expr!(&arena, "%a +", a = "b")) {
+            TemplateErrorKind::Invalid(_) => {},
+            kind => panic!("Expected a parse error, got {:?}", kind),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! Compares the Babel flavor of the AST against the fixtures in
+//! `tests/fixtures/babel`. They follow the output of `@babel/parser`,
+//! positions included, but couldn't be recorded with it where they were
+//! made and haven't been checked against it yet: run the `record.js` script
+//! there to replace them with its actual output.
+//!
+//! `loc` is left out, as Babel adds the `index` and `identifierName` esr
+//! doesn't have.
+
+use std::fs;
+use std::path::Path;
+use esr::{parse_with_options, EstreeOptions, Flavor, Module, ParseOptions, SourceType};
+use serde_json::Value;
+
+fn without_loc(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            object.remove("loc");
+            object.values_mut().for_each(without_loc);
+        },
+        Value::Array(array) => array.iter_mut().for_each(without_loc),
+        _ => {},
+    }
+}
+
+fn babel_ast(source: &str, jsx: bool) -> Value {
+    let options = ParseOptions { jsx, parenthesized: true, ..ParseOptions::default() };
+    let module = parse_with_options(source, options).unwrap();
+    let options = EstreeOptions {
+        flavor: Flavor::Babel,
+        source_type: Some(SourceType::Script),
+        ..EstreeOptions::default()
+    };
+
+    let mut ast = serde_json::to_value(module.estree(source, options)).unwrap();
+
+    assert!(Module::from_estree_value(&ast).is_ok(), "Babel AST doesn't read back");
+
+    without_loc(&mut ast);
+
+    ast
+}
+
+#[test]
+fn babel_fixtures() {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/babel");
+    let mut fixtures = 0;
+
+    for entry in fs::read_dir(&directory).unwrap() {
+        let path = entry.unwrap().path();
+        let jsx = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("js") if !path.ends_with("record.js") => false,
+            Some("jsx") => true,
+            _ => continue,
+        };
+
+        let source = fs::read_to_string(&path).unwrap();
+        let expected: Value = serde_json::from_str(&fs::read_to_string(path.with_extension("json")).unwrap()).unwrap();
+        let actual = babel_ast(&source, jsx);
+
+        assert!(
+            actual == expected,
+            "{} differs from @babel/parser:\n{}",
+            path.display(),
+            serde_json::to_string_pretty(&actual).unwrap(),
+        );
+
+        fixtures += 1;
+    }
+
+    assert!(fixtures > 0);
+}
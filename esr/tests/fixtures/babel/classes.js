@@ -0,0 +1,6 @@
+class A extends B {
+  constructor(a) { this.a = a; }
+  static get b() { return 1; }
+  c = 2;
+  [d]() {}
+}
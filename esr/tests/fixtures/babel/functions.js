@@ -0,0 +1,7 @@
+function f(a, b = 1, ...c) {
+  'use strict';
+  return (a + b);
+}
+var g = function* () {};
+var h = (x) => x * 2;
+var k = () => { 'use asm'; };
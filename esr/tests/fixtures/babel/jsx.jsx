@@ -0,0 +1 @@
+<a b="c &amp; d" e={f}>text {g}</a>;
@@ -0,0 +1,9 @@
+'use strict';
+"a\n";
+1.5;
+0x10;
+true, false, null;
+/ab+c/gi;
+x = undefined;
+`a${b}c`;
+('d');
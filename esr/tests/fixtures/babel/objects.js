@@ -0,0 +1,2 @@
+var o = { a: 1, 'b': 2, [c]: 3, d, e() { return this; } };
+var { f, g: h } = o;
@@ -0,0 +1,22 @@
+// Records the program `@babel/parser` produces for each fixture next to it,
+// as expected by `tests/babel.rs`: `npm install @babel/parser && node record.js`
+const fs = require("fs");
+const path = require("path");
+const { parse } = require("@babel/parser");
+
+for (const name of fs.readdirSync(__dirname)) {
+    const extension = path.extname(name);
+
+    if (name === "record.js" || (extension !== ".js" && extension !== ".jsx")) {
+        continue;
+    }
+
+    const source = fs.readFileSync(path.join(__dirname, name), "utf8");
+    const { program } = parse(source, {
+        sourceType: "script",
+        plugins: extension === ".jsx" ? ["jsx"] : [],
+    });
+    const json = JSON.stringify(program, (key, value) => key === "loc" ? undefined : value, 2);
+
+    fs.writeFileSync(path.join(__dirname, path.basename(name, extension) + ".json"), json + "\n");
+}
@@ -0,0 +1,9 @@
+if (a) b(); else { c(); }
+for (var i = 0; i < 10; i++) continue;
+for (var j in k) break;
+for (let l of m) {}
+while (n) n--;
+do o(); while (p);
+switch (q) { case 1: r(); break; default: }
+try { throw new Error('s'); } catch (t) {} finally {}
+u: for (;;) break u;
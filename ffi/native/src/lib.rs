@@ -7,7 +7,7 @@ extern crate serde_json;
 
 use neon::prelude::*;
 
-use esr::Module;
+use esr::{Module, EstreeOptions, Flavor, SourceType};
 use esr::error::{Error, ParseError};
 
 #[inline]
@@ -26,14 +26,55 @@ fn format_errors(errors: Vec<Error>, source: Handle<JsString>) -> Vec<String> {
 }
 
 #[inline]
-fn generate_ast(module: &Module, minify: bool) -> Result<String, serde_json::Error> {
+fn generate_ast(module: &Module, source: &str, minify: bool, options: EstreeOptions) -> Result<String, serde_json::Error> {
     if minify {
-        serde_json::to_string(&module)
+        serde_json::to_string(&module.estree(source, options))
     } else {
-        serde_json::to_string_pretty(&module)
+        serde_json::to_string_pretty(&module.estree(source, options))
     }
 }
 
+fn flag(cx: &mut FunctionContext, object: Handle<JsObject>, key: &str, default: bool) -> NeonResult<bool> {
+    let value = object.get(cx, key)?;
+
+    Ok(match value.downcast::<JsBoolean>() {
+        Ok(value) => value.value(),
+        Err(_)    => default,
+    })
+}
+
+fn string(cx: &mut FunctionContext, object: Handle<JsObject>, key: &str) -> NeonResult<Option<String>> {
+    let value = object.get(cx, key)?;
+
+    Ok(value.downcast::<JsString>().ok().map(|value| value.value()))
+}
+
+/// Read `EstreeOptions` from an object such as `{ loc: true, sourceType: 'module' }`,
+/// missing fields keep their defaults.
+fn estree_options(cx: &mut FunctionContext, object: Handle<JsObject>) -> NeonResult<EstreeOptions> {
+    let defaults = EstreeOptions::default();
+
+    let flavor = match string(cx, object, "flavor")?.as_ref().map(String::as_str) {
+        Some("babel") => Flavor::Babel,
+        _             => Flavor::ESTree,
+    };
+    let source_type = match string(cx, object, "sourceType")?.as_ref().map(String::as_str) {
+        Some("script") => Some(SourceType::Script),
+        Some("module") => Some(SourceType::Module),
+        _              => None,
+    };
+
+    Ok(EstreeOptions {
+        flavor,
+        loc: flag(cx, object, "loc", defaults.loc)?,
+        range: flag(cx, object, "range", defaults.range)?,
+        raw: flag(cx, object, "raw", defaults.raw)?,
+        tokens: flag(cx, object, "tokens", defaults.tokens)?,
+        comments: flag(cx, object, "comments", defaults.comments)?,
+        source_type,
+    })
+}
+
 fn ast(mut cx: FunctionContext) -> JsResult<JsString> {
     if cx.len() == 0 {
         return cx.throw_type_error("First argument must be a string")
@@ -42,7 +83,18 @@ fn ast(mut cx: FunctionContext) -> JsResult<JsString> {
     let source = cx.argument::<JsString>(0)?;
     let minify = cx.argument::<JsBoolean>(1)?;
 
-    let module = match esr::parse(&source.value()) {
+    let options = match cx.argument_opt(2) {
+        Some(options) => {
+            let options = options.downcast_or_throw::<JsObject, _>(&mut cx)?;
+
+            estree_options(&mut cx, options)?
+        },
+        None => EstreeOptions::default(),
+    };
+
+    let value = source.value();
+
+    let module = match esr::parse(&value) {
         Err(errors) => {
             let str = format_errors(errors, source).join("\n");
             return cx.throw_type_error(&str)
@@ -50,7 +102,7 @@ fn ast(mut cx: FunctionContext) -> JsResult<JsString> {
         Ok(module) => module,
     };
 
-    let result = generate_ast(&module, minify.value()).unwrap();
+    let result = generate_ast(&module, &value, minify.value(), options).unwrap();
 
     Ok(cx.string(&result))
 }
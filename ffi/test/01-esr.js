@@ -80,5 +80,19 @@ describe('Esr ffi', () => {
       delete acornAST['sourceType'];
       assert.deepEqual(tree, acornAST);
     });
+
+    it('generates locations, ranges and source type like acorn', () => {
+      const source = `const foo = 2;\nlet bar=4;\nfoo**bar === 16`;
+      const options = { loc: true, range: true, sourceType: 'script' };
+      const tree = JSON.parse(Esr.ast(source, true, options));
+      const acornAST = acorn.parse(source, { locations: true, ranges: true });
+      assert.deepEqual(tree, JSON.parse(JSON.stringify(acornAST)));
+    });
+
+    it('generates tokens and comments', () => {
+      const tree = JSON.parse(Esr.ast('/* a */ b;', true, { tokens: true, comments: true }));
+      assert.deepEqual(tree.tokens.map(token => token.value), ['b', ';']);
+      assert.deepEqual(tree.comments.map(comment => comment.value), [' a ']);
+    });
   });
 });